@@ -17,6 +17,9 @@ pub struct McpApps {
     pub grok: bool,
     #[serde(default)]
     pub qwen: bool,
+    /// Claude Desktop 应用（claude_desktop_config.json），仅作为 MCP 同步目标
+    #[serde(default, rename = "claudeDesktop")]
+    pub claude_desktop: bool,
 }
 
 impl McpApps {
@@ -65,7 +68,114 @@ impl McpApps {
 
     /// 检查是否所有应用都未启用
     pub fn is_empty(&self) -> bool {
-        !self.claude && !self.codex && !self.gemini && !self.grok && !self.qwen
+        !self.claude
+            && !self.codex
+            && !self.gemini
+            && !self.grok
+            && !self.qwen
+            && !self.claude_desktop
+    }
+}
+
+/// MCP 同步目标：CLI 应用或 Claude Desktop（后者不属于 AppType）
+#[derive(Debug, Clone, PartialEq)]
+pub enum McpTarget {
+    App(AppType),
+    ClaudeDesktop,
+}
+
+impl McpTarget {
+    pub fn as_str(&self) -> &str {
+        match self {
+            McpTarget::App(app) => app.as_str(),
+            McpTarget::ClaudeDesktop => "claudeDesktop",
+        }
+    }
+}
+
+impl From<AppType> for McpTarget {
+    fn from(app: AppType) -> Self {
+        McpTarget::App(app)
+    }
+}
+
+impl From<&AppType> for McpTarget {
+    fn from(app: &AppType) -> Self {
+        McpTarget::App(app.clone())
+    }
+}
+
+impl FromStr for McpTarget {
+    type Err = AppError;
+
+    /// 接受应用名与 `claudeDesktop`（大小写、`-` / `_` 不敏感）
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace(['-', '_'], "").as_str() {
+            "claudedesktop" => Ok(McpTarget::ClaudeDesktop),
+            _ => AppType::from_str(s).map(McpTarget::App),
+        }
+    }
+}
+
+/// MCP 服务器分应用覆盖配置（同步时合并到基础 server 定义之上）
+///
+/// 例如同一服务器在 Claude 与 Codex 下需要不同的 env 取值：
+/// `{"claude": {"env": {"SCOPE": "a"}}, "codex": {"env": {"SCOPE": "b"}}}`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct McpAppOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claude: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codex: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gemini: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grok: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qwen: Option<serde_json::Value>,
+    #[serde(
+        default,
+        rename = "claudeDesktop",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub claude_desktop: Option<serde_json::Value>,
+}
+
+impl McpAppOverrides {
+    /// 获取指定目标的覆盖配置
+    pub fn get(&self, target: impl Into<McpTarget>) -> Option<&serde_json::Value> {
+        match target.into() {
+            McpTarget::App(AppType::Claude) => self.claude.as_ref(),
+            McpTarget::App(AppType::Codex) => self.codex.as_ref(),
+            McpTarget::App(AppType::Gemini) => self.gemini.as_ref(),
+            McpTarget::App(AppType::Grok) => self.grok.as_ref(),
+            McpTarget::App(AppType::Qwen) => self.qwen.as_ref(),
+            McpTarget::ClaudeDesktop => self.claude_desktop.as_ref(),
+        }
+    }
+
+    /// 全部已设置的覆盖配置（用于统一清理密钥等）
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut serde_json::Value> {
+        [
+            &mut self.claude,
+            &mut self.codex,
+            &mut self.gemini,
+            &mut self.grok,
+            &mut self.qwen,
+            &mut self.claude_desktop,
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// 检查是否没有任何覆盖配置
+    pub fn is_empty(&self) -> bool {
+        self.claude.is_none()
+            && self.codex.is_none()
+            && self.gemini.is_none()
+            && self.grok.is_none()
+            && self.qwen.is_none()
+            && self.claude_desktop.is_none()
     }
 }
 
@@ -76,6 +186,13 @@ pub struct McpServer {
     pub name: String,
     pub server: serde_json::Value,
     pub apps: McpApps,
+    /// 分应用覆盖配置
+    #[serde(
+        rename = "appOverrides",
+        default,
+        skip_serializing_if = "McpAppOverrides::is_empty"
+    )]
+    pub app_overrides: McpAppOverrides,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -86,6 +203,43 @@ pub struct McpServer {
     pub tags: Vec<String>,
 }
 
+impl McpServer {
+    /// 计算指定目标实际写入的服务器定义：基础定义 + 该目标的覆盖配置
+    ///
+    /// 合并规则：顶层字段逐个覆盖；若两侧均为对象（如 env、headers）则按键合并；
+    /// 覆盖值为 null 时删除该字段。
+    pub fn spec_for(&self, target: impl Into<McpTarget>) -> serde_json::Value {
+        let mut spec = self.server.clone();
+        let (Some(base), Some(overrides)) = (
+            spec.as_object_mut(),
+            self.app_overrides.get(target).and_then(|v| v.as_object()),
+        ) else {
+            return spec;
+        };
+
+        for (key, value) in overrides {
+            if value.is_null() {
+                base.remove(key);
+                continue;
+            }
+            if let (Some(serde_json::Value::Object(inner)), serde_json::Value::Object(patch)) =
+                (base.get_mut(key), value)
+            {
+                for (k, v) in patch {
+                    if v.is_null() {
+                        inner.remove(k);
+                    } else {
+                        inner.insert(k.clone(), v.clone());
+                    }
+                }
+                continue;
+            }
+            base.insert(key.clone(), value.clone());
+        }
+        spec
+    }
+}
+
 /// MCP 配置：单客户端维度（v3.6.x 及以前，保留用于向后兼容）
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct McpConfig {
@@ -175,6 +329,17 @@ pub enum AppType {
 }
 
 impl AppType {
+    /// 所有支持的应用
+    pub fn all() -> [AppType; 5] {
+        [
+            AppType::Claude,
+            AppType::Codex,
+            AppType::Gemini,
+            AppType::Grok,
+            AppType::Qwen,
+        ]
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             AppType::Claude => "claude",
@@ -197,10 +362,9 @@ impl FromStr for AppType {
             "gemini" => Ok(AppType::Gemini),
             "grok" => Ok(AppType::Grok),
             "qwen" => Ok(AppType::Qwen),
-            other => Err(AppError::localized(
+            other => Err(AppError::localized_with(
                 "unsupported_app",
-                format!("不支持的应用标识: '{other}'。可选值: claude, codex, gemini, grok, qwen。"),
-                format!("Unsupported app id: '{other}'. Allowed: claude, codex, gemini, grok, qwen."),
+                &[("app", other.to_string())],
             )),
         }
     }
@@ -328,11 +492,7 @@ impl MultiAppConfig {
             has_providers && has_current && !has_apps
         });
         if is_v1 {
-            return Err(AppError::localized(
-                "config.unsupported_v1",
-                "检测到旧版 v1 配置格式。当前版本已不再支持运行时自动迁移。\n\n解决方案：\n1. 安装 v3.2.x 版本进行一次性自动迁移\n2. 或手动编辑 ~/.cc-switch/config.json，将顶层结构调整为：\n   {\"version\": 2, \"claude\": {...}, \"codex\": {...}, \"mcp\": {...}}\n\n",
-                "Detected legacy v1 config. Runtime auto-migration is no longer supported.\n\nSolutions:\n1. Install v3.2.x for one-time auto-migration\n2. Or manually edit ~/.cc-switch/config.json to adjust the top-level structure:\n   {\"version\": 2, \"claude\": {...}, \"codex\": {...}, \"mcp\": {...}}\n\n",
-            ));
+            return Err(AppError::localized("config.unsupported_v1"));
         }
 
         let has_skills_in_config = value
@@ -567,6 +727,9 @@ impl MultiAppConfig {
             content,
             description: Some("Automatically imported on first launch".to_string()),
             enabled: true, // 自动启用
+            tags: Vec::new(),
+            target_apps: Vec::new(),
+            apps: Default::default(),
             created_at: Some(timestamp),
             updated_at: Some(timestamp),
         };
@@ -680,6 +843,7 @@ impl MultiAppConfig {
                             name,
                             server,
                             apps,
+                            app_overrides: McpAppOverrides::default(),
                             description,
                             homepage,
                             docs,
@@ -929,4 +1093,77 @@ mod tests {
                 .enabled
         );
     }
+
+    #[test]
+    fn mcp_spec_for_merges_app_overrides() {
+        let server = McpServer {
+            id: "demo".to_string(),
+            name: "demo".to_string(),
+            server: serde_json::json!({
+                "command": "npx",
+                "env": { "SCOPE": "base", "TOKEN": "t" }
+            }),
+            apps: McpApps {
+                claude: true,
+                codex: true,
+                ..McpApps::default()
+            },
+            app_overrides: McpAppOverrides {
+                codex: Some(serde_json::json!({
+                    "env": { "SCOPE": "codex", "TOKEN": null },
+                    "cwd": "/tmp"
+                })),
+                ..McpAppOverrides::default()
+            },
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: Vec::new(),
+        };
+
+        assert_eq!(server.spec_for(&AppType::Claude), server.server);
+
+        let codex = server.spec_for(&AppType::Codex);
+        assert_eq!(codex["env"], serde_json::json!({ "SCOPE": "codex" }));
+        assert_eq!(codex["cwd"], "/tmp");
+        assert_eq!(codex["command"], "npx");
+    }
+
+    #[test]
+    fn mcp_target_parses_claude_desktop_and_apps() {
+        assert_eq!(
+            McpTarget::from_str("claudeDesktop").unwrap(),
+            McpTarget::ClaudeDesktop
+        );
+        assert_eq!(
+            McpTarget::from_str("claude_desktop").unwrap(),
+            McpTarget::ClaudeDesktop
+        );
+        assert_eq!(
+            McpTarget::from_str("codex").unwrap(),
+            McpTarget::App(AppType::Codex)
+        );
+        assert!(McpTarget::from_str("cursor").is_err());
+
+        let overrides: McpAppOverrides = serde_json::from_value(serde_json::json!({
+            "claudeDesktop": { "env": { "SCOPE": "desktop" } }
+        }))
+        .unwrap();
+        let server = McpServer {
+            id: "demo".to_string(),
+            name: "demo".to_string(),
+            server: serde_json::json!({ "command": "npx", "env": { "SCOPE": "base" } }),
+            apps: McpApps::default(),
+            app_overrides: overrides,
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: Vec::new(),
+        };
+        assert_eq!(
+            server.spec_for(McpTarget::ClaudeDesktop)["env"]["SCOPE"],
+            "desktop"
+        );
+        assert_eq!(server.spec_for(&AppType::Claude)["env"]["SCOPE"], "base");
+    }
 }
@@ -0,0 +1,154 @@
+//! 系统身份验证（Touch ID / Windows Hello）
+//!
+//! 开启 `biometricKeyReveal` 后，后端在把 API Key 原文交给前端或写入剪贴板前
+//! 先调用系统身份验证：
+//! - macOS: LocalAuthentication（Touch ID，不可用时回退到登录密码）
+//! - Windows: Windows Hello（`UserConsentVerifier`）
+//!
+//! 其他平台不支持，设置开启时直接放行。验证会弹出系统对话框并阻塞当前线程，
+//! 需在阻塞线程中调用。
+
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// 系统身份验证能力
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BiometricStatus {
+    /// 当前平台是否支持（macOS / Windows）
+    pub supported: bool,
+    /// 本机是否已配置可用的验证方式
+    pub available: bool,
+    pub enabled: bool,
+}
+
+pub fn status() -> BiometricStatus {
+    BiometricStatus {
+        supported: platform::SUPPORTED,
+        available: platform::available(),
+        enabled: crate::settings::get_settings().biometric_key_reveal,
+    }
+}
+
+/// 弹出系统身份验证，`reason` 显示在对话框中
+pub fn verify(reason: &str) -> Result<(), AppError> {
+    if !platform::available() {
+        return Err(AppError::localized("auth.unavailable"));
+    }
+    match platform::verify(reason) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(AppError::localized("auth.cancelled")),
+        Err(e) => {
+            log::warn!("系统身份验证失败: {e}");
+            Err(AppError::localized_with("auth.failed", &[("error", e)]))
+        }
+    }
+}
+
+/// 返回 API Key 原文前调用：开启验证且平台支持时要求通过系统身份验证
+pub fn ensure_key_reveal_allowed(provider_name: &str) -> Result<(), AppError> {
+    if !platform::SUPPORTED || !crate::settings::get_settings().biometric_key_reveal {
+        return Ok(());
+    }
+    verify(&crate::i18n::tr(
+        "auth.reveal_reason",
+        &[("provider", provider_name.to_string())],
+    ))
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::sync::mpsc;
+
+    use block2::RcBlock;
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyClass, AnyObject, Bool};
+    use objc2::{msg_send, msg_send_id};
+    use objc2_foundation::NSString;
+
+    #[link(name = "LocalAuthentication", kind = "framework")]
+    extern "C" {}
+
+    pub const SUPPORTED: bool = true;
+
+    /// `LAPolicyDeviceOwnerAuthentication`：生物识别，不可用时允许输入登录密码
+    const POLICY_DEVICE_OWNER_AUTHENTICATION: isize = 2;
+
+    fn context() -> Option<Retained<AnyObject>> {
+        let class = AnyClass::get("LAContext")?;
+        Some(unsafe { msg_send_id![class, new] })
+    }
+
+    pub fn available() -> bool {
+        let Some(context) = context() else {
+            return false;
+        };
+        let can: Bool = unsafe {
+            msg_send![
+                &context,
+                canEvaluatePolicy: POLICY_DEVICE_OWNER_AUTHENTICATION,
+                error: std::ptr::null_mut::<*mut AnyObject>()
+            ]
+        };
+        can.as_bool()
+    }
+
+    pub fn verify(reason: &str) -> Result<bool, String> {
+        let context = context().ok_or("LocalAuthentication 不可用")?;
+        let (tx, rx) = mpsc::channel();
+        let reply = RcBlock::new(move |success: Bool, _error: *mut AnyObject| {
+            let _ = tx.send(success.as_bool());
+        });
+        let reason = NSString::from_str(reason);
+        unsafe {
+            let _: () = msg_send![
+                &context,
+                evaluatePolicy: POLICY_DEVICE_OWNER_AUTHENTICATION,
+                localizedReason: &*reason,
+                reply: &*reply
+            ];
+        }
+        rx.recv().map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::core::HSTRING;
+    use windows::Security::Credentials::UI::{
+        UserConsentVerificationResult, UserConsentVerifier, UserConsentVerifierAvailability,
+    };
+
+    pub const SUPPORTED: bool = true;
+
+    pub fn available() -> bool {
+        UserConsentVerifier::CheckAvailabilityAsync()
+            .and_then(|operation| operation.get())
+            .is_ok_and(|availability| availability == UserConsentVerifierAvailability::Available)
+    }
+
+    pub fn verify(reason: &str) -> Result<bool, String> {
+        let result = UserConsentVerifier::RequestVerificationAsync(&HSTRING::from(reason))
+            .and_then(|operation| operation.get())
+            .map_err(|e| e.to_string())?;
+        match result {
+            UserConsentVerificationResult::Verified => Ok(true),
+            UserConsentVerificationResult::Canceled => Ok(false),
+            other => Err(format!("{other:?}")),
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    pub const SUPPORTED: bool = false;
+
+    pub fn available() -> bool {
+        false
+    }
+
+    pub fn verify(_reason: &str) -> Result<bool, String> {
+        Ok(true)
+    }
+}
@@ -1,6 +1,5 @@
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -197,43 +196,6 @@ pub fn delete_mcp_server(id: &str) -> Result<bool, AppError> {
     Ok(true)
 }
 
-pub fn validate_command_in_path(cmd: &str) -> Result<bool, AppError> {
-    if cmd.trim().is_empty() {
-        return Ok(false);
-    }
-    // 如果包含路径分隔符，直接判断是否存在可执行文件
-    if cmd.contains('/') || cmd.contains('\\') {
-        return Ok(Path::new(cmd).exists());
-    }
-
-    let path_var = env::var_os("PATH").unwrap_or_default();
-    let paths = env::split_paths(&path_var);
-
-    #[cfg(windows)]
-    let exts: Vec<String> = env::var("PATHEXT")
-        .unwrap_or(".COM;.EXE;.BAT;.CMD".into())
-        .split(';')
-        .map(|s| s.trim().to_uppercase())
-        .collect();
-
-    for p in paths {
-        let candidate = p.join(cmd);
-        if candidate.is_file() {
-            return Ok(true);
-        }
-        #[cfg(windows)]
-        {
-            for ext in &exts {
-                let cand = p.join(format!("{}{}", cmd, ext));
-                if cand.is_file() {
-                    return Ok(true);
-                }
-            }
-        }
-    }
-    Ok(false)
-}
-
 /// 读取 ~/.claude.json 中的 mcpServers 映射
 pub fn read_mcp_servers_map() -> Result<std::collections::HashMap<String, Value>, AppError> {
     let path = user_config_path();
@@ -1,10 +1,20 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
 
 use crate::error::AppError;
 
 const CLAUDE_DIR: &str = ".claude";
 const CLAUDE_CONFIG_FILE: &str = "config.json";
+const PLUGINS_DIR: &str = "plugins";
+const KNOWN_MARKETPLACES_FILE: &str = "known_marketplaces.json";
+const INSTALLED_PLUGINS_FILE: &str = "installed_plugins.json";
+/// settings.json 中记录插件启用状态的字段（键为 `插件@市场`）
+const ENABLED_PLUGINS_KEY: &str = "enabledPlugins";
+/// 保留的插件配置备份数量
+const MAX_PLUGIN_BACKUPS: usize = 10;
 
 fn claude_dir() -> Result<PathBuf, AppError> {
     // 优先使用设置中的覆盖目录
@@ -130,3 +140,330 @@ pub fn is_claude_config_applied() -> Result<bool, AppError> {
         None => Ok(false),
     }
 }
+
+// ===== 插件与插件市场 =====
+
+/// 已安装的插件
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledPlugin {
+    /// `插件@市场`
+    pub id: String,
+    pub name: String,
+    pub marketplace: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installed_at: Option<String>,
+    pub enabled: bool,
+}
+
+/// 已添加的插件市场
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginMarketplace {
+    pub name: String,
+    /// 来源描述（GitHub 仓库、Git 地址或本地目录）
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install_location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
+    /// 从该市场安装的插件数
+    pub installed_count: usize,
+    /// 已启用的插件数
+    pub enabled_count: usize,
+}
+
+/// `~/.claude/plugins` 概览
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInventory {
+    pub plugins_dir: String,
+    pub marketplaces: Vec<PluginMarketplace>,
+    pub plugins: Vec<InstalledPlugin>,
+}
+
+/// 修改插件启用状态的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginToggleResult {
+    /// 状态实际发生变化的插件 ID
+    pub changed: Vec<String>,
+    /// 修改前创建的备份目录（无变化时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<String>,
+}
+
+pub fn plugins_dir() -> Result<PathBuf, AppError> {
+    Ok(claude_dir()?.join(PLUGINS_DIR))
+}
+
+/// 读取 JSON 文件；文件不存在时返回空对象
+fn read_json_or_empty(path: &Path) -> Result<Value, AppError> {
+    if !path.exists() {
+        return Ok(Value::Object(Map::new()));
+    }
+    let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+    serde_json::from_str(&content).map_err(|e| AppError::json(path, e))
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// 列出已添加的插件市场与已安装的插件
+pub fn list_plugins() -> Result<PluginInventory, AppError> {
+    let dir = plugins_dir()?;
+    let marketplaces = read_json_or_empty(&dir.join(KNOWN_MARKETPLACES_FILE))?;
+    let installed = read_json_or_empty(&dir.join(INSTALLED_PLUGINS_FILE))?;
+    let settings = read_json_or_empty(&crate::config::get_claude_settings_path())?;
+
+    let plugins = parse_installed_plugins(&installed, settings.get(ENABLED_PLUGINS_KEY));
+    Ok(PluginInventory {
+        plugins_dir: dir.to_string_lossy().to_string(),
+        marketplaces: parse_marketplaces(&marketplaces, &plugins),
+        plugins,
+    })
+}
+
+/// 解析 installed_plugins.json：`{ "plugins": { "插件@市场": {..} | [{..}] } }`
+fn parse_installed_plugins(installed: &Value, enabled: Option<&Value>) -> Vec<InstalledPlugin> {
+    let Some(entries) = installed.get("plugins").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<InstalledPlugin> = entries
+        .iter()
+        .map(|(id, entry)| {
+            // 新版按安装范围记录为数组，取第一项
+            let entry = entry
+                .as_array()
+                .and_then(|list| list.first())
+                .unwrap_or(entry);
+            let (name, marketplace) = id.split_once('@').unwrap_or((id.as_str(), ""));
+            InstalledPlugin {
+                id: id.clone(),
+                name: name.to_string(),
+                marketplace: marketplace.to_string(),
+                version: str_field(entry, "version"),
+                install_path: str_field(entry, "installPath"),
+                installed_at: str_field(entry, "installedAt"),
+                enabled: enabled
+                    .and_then(|map| map.get(id))
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+            }
+        })
+        .collect();
+    plugins.sort_by(|a, b| a.id.cmp(&b.id));
+    plugins
+}
+
+/// 解析 known_marketplaces.json：`{ "市场名": { "source": {..}, "installLocation": .. } }`
+fn parse_marketplaces(known: &Value, plugins: &[InstalledPlugin]) -> Vec<PluginMarketplace> {
+    let Some(entries) = known.as_object() else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .map(|(name, entry)| {
+            let source = entry.get("source").unwrap_or(&Value::Null);
+            let source = ["repo", "url", "path"]
+                .iter()
+                .find_map(|key| str_field(source, key))
+                .unwrap_or_default();
+            let from_here = plugins.iter().filter(|p| &p.marketplace == name);
+            PluginMarketplace {
+                name: name.clone(),
+                source,
+                install_location: str_field(entry, "installLocation"),
+                last_updated: str_field(entry, "lastUpdated"),
+                installed_count: from_here.clone().count(),
+                enabled_count: from_here.filter(|p| p.enabled).count(),
+            }
+        })
+        .collect()
+}
+
+/// 启用 / 停用单个插件
+pub fn set_plugin_enabled(plugin_id: &str, enabled: bool) -> Result<PluginToggleResult, AppError> {
+    let inventory = list_plugins()?;
+    if !inventory.plugins.iter().any(|p| p.id == plugin_id) {
+        return Err(AppError::localized_with(
+            "claude_plugin.not_installed",
+            &[("id", plugin_id.to_string())],
+        ));
+    }
+    write_enabled(&[plugin_id.to_string()], enabled)
+}
+
+/// 启用 / 停用某个市场安装的全部插件
+pub fn set_marketplace_enabled(
+    marketplace: &str,
+    enabled: bool,
+) -> Result<PluginToggleResult, AppError> {
+    let inventory = list_plugins()?;
+    if !inventory.marketplaces.iter().any(|m| m.name == marketplace) {
+        return Err(AppError::localized_with(
+            "claude_plugin.marketplace_not_found",
+            &[("name", marketplace.to_string())],
+        ));
+    }
+    let ids: Vec<String> = inventory
+        .plugins
+        .into_iter()
+        .filter(|p| p.marketplace == marketplace)
+        .map(|p| p.id)
+        .collect();
+    write_enabled(&ids, enabled)
+}
+
+/// 修改 settings.json 的 enabledPlugins（有变化时先备份插件配置）
+fn write_enabled(ids: &[String], enabled: bool) -> Result<PluginToggleResult, AppError> {
+    let path = crate::config::get_claude_settings_path();
+    let mut settings = read_json_or_empty(&path)?;
+    let changed = apply_enabled(&mut settings, ids, enabled);
+    if changed.is_empty() {
+        return Ok(PluginToggleResult {
+            changed,
+            backup: None,
+        });
+    }
+
+    let backup = backup_plugin_config()?;
+    crate::config::write_json_file(&path, &settings)?;
+    log::info!(
+        "已{}Claude 插件: {}",
+        if enabled { "启用" } else { "停用" },
+        changed.join(", ")
+    );
+    Ok(PluginToggleResult {
+        changed,
+        backup: Some(backup.to_string_lossy().to_string()),
+    })
+}
+
+/// 设置 enabledPlugins 中的条目，返回状态有变化的 ID
+fn apply_enabled(settings: &mut Value, ids: &[String], enabled: bool) -> Vec<String> {
+    if !settings.is_object() {
+        *settings = Value::Object(Map::new());
+    }
+    let Some(root) = settings.as_object_mut() else {
+        return Vec::new();
+    };
+    let map = root
+        .entry(ENABLED_PLUGINS_KEY)
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !map.is_object() {
+        *map = Value::Object(Map::new());
+    }
+    let Some(map) = map.as_object_mut() else {
+        return Vec::new();
+    };
+
+    let mut changed = Vec::new();
+    for id in ids {
+        if map.get(id).and_then(Value::as_bool) != Some(enabled) {
+            map.insert(id.clone(), Value::Bool(enabled));
+            changed.push(id.clone());
+        }
+    }
+    changed
+}
+
+/// 备份 settings.json 与插件清单到 `<应用配置目录>/backups/claude-plugins/<时间戳>/`
+pub fn backup_plugin_config() -> Result<PathBuf, AppError> {
+    let root = crate::config::get_app_config_dir()
+        .join("backups")
+        .join("claude-plugins");
+    let backup_dir = root.join(chrono::Local::now().format("%Y%m%d_%H%M%S").to_string());
+    fs::create_dir_all(&backup_dir).map_err(|e| AppError::io(&backup_dir, e))?;
+
+    let plugins = plugins_dir()?;
+    let sources = [
+        ("settings.json", crate::config::get_claude_settings_path()),
+        (
+            KNOWN_MARKETPLACES_FILE,
+            plugins.join(KNOWN_MARKETPLACES_FILE),
+        ),
+        (INSTALLED_PLUGINS_FILE, plugins.join(INSTALLED_PLUGINS_FILE)),
+    ];
+    for (name, source) in sources {
+        if source.exists() {
+            let target = backup_dir.join(name);
+            fs::copy(&source, &target).map_err(|e| AppError::io(&target, e))?;
+        }
+    }
+
+    cleanup_plugin_backups(&root);
+    Ok(backup_dir)
+}
+
+/// 只保留最近的若干份备份（目录名即时间戳，按名称排序）
+fn cleanup_plugin_backups(root: &Path) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    if dirs.len() <= MAX_PLUGIN_BACKUPS {
+        return;
+    }
+    dirs.sort();
+    for dir in dirs.iter().take(dirs.len() - MAX_PLUGIN_BACKUPS) {
+        if let Err(e) = fs::remove_dir_all(dir) {
+            log::warn!("删除旧的插件配置备份失败 {}: {e}", dir.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_installed_plugins_and_marketplaces() {
+        let installed = json!({
+            "version": 2,
+            "plugins": {
+                "review@official": [{ "version": "1.2.0", "installPath": "/p/review" }],
+                "lint@team": { "version": "0.1.0" }
+            }
+        });
+        let enabled = json!({ "review@official": true, "lint@team": false });
+        let plugins = parse_installed_plugins(&installed, Some(&enabled));
+        assert_eq!(plugins.len(), 2);
+        assert_eq!(plugins[0].id, "lint@team");
+        assert!(!plugins[0].enabled);
+        assert_eq!(plugins[1].name, "review");
+        assert_eq!(plugins[1].version.as_deref(), Some("1.2.0"));
+        assert!(plugins[1].enabled);
+
+        let known = json!({
+            "official": { "source": { "source": "github", "repo": "anthropics/claude-code" } },
+            "team": { "source": { "source": "git", "url": "https://git.example.com/p.git" } }
+        });
+        let marketplaces = parse_marketplaces(&known, &plugins);
+        assert_eq!(marketplaces[0].source, "anthropics/claude-code");
+        assert_eq!(marketplaces[0].enabled_count, 1);
+        assert_eq!(marketplaces[1].installed_count, 1);
+        assert_eq!(marketplaces[1].enabled_count, 0);
+    }
+
+    #[test]
+    fn apply_enabled_reports_only_changes() {
+        let mut settings = json!({ "env": {}, "enabledPlugins": { "a@m": true } });
+        let ids = vec!["a@m".to_string(), "b@m".to_string()];
+        assert_eq!(apply_enabled(&mut settings, &ids, true), vec!["b@m"]);
+        assert_eq!(settings["enabledPlugins"]["b@m"], json!(true));
+        assert_eq!(apply_enabled(&mut settings, &ids, false).len(), 2);
+        assert!(apply_enabled(&mut settings, &ids, false).is_empty());
+        assert!(settings.get("env").is_some());
+    }
+}
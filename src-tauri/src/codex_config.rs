@@ -5,6 +5,7 @@ use crate::config::{
     atomic_write, delete_file, sanitize_provider_name, write_json_file, write_text_file,
 };
 use crate::error::AppError;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fs;
 use std::path::Path;
@@ -136,3 +137,162 @@ pub fn read_and_validate_codex_config_text() -> Result<String, AppError> {
     validate_config_toml(&s)?;
     Ok(s)
 }
+
+/// Codex 命名 profile（config.toml 中的 `[profiles.<name>]`）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexProfile {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// 引用的 `[model_providers.<id>]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_policy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sandbox_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_reasoning_effort: Option<String>,
+}
+
+/// config.toml 中的 profile 列表及当前选择（顶层 `profile` 键）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexProfiles {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+    pub profiles: Vec<CodexProfile>,
+}
+
+/// 解析 config.toml 文本中的 `[profiles.*]` 表（空文本返回空列表）
+pub fn parse_codex_profiles(text: &str) -> Result<CodexProfiles, AppError> {
+    if text.trim().is_empty() {
+        return Ok(CodexProfiles::default());
+    }
+    let table: toml::Table =
+        toml::from_str(text).map_err(|e| AppError::toml(Path::new("config.toml"), e))?;
+
+    let active = table
+        .get("profile")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let profiles = table
+        .get("profiles")
+        .and_then(|v| v.as_table())
+        .map(|profiles| {
+            profiles
+                .iter()
+                .filter_map(|(name, value)| {
+                    let profile = value.as_table()?;
+                    let field = |key: &str| {
+                        profile
+                            .get(key)
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string)
+                    };
+                    Some(CodexProfile {
+                        name: name.clone(),
+                        model: field("model"),
+                        model_provider: field("model_provider"),
+                        approval_policy: field("approval_policy"),
+                        sandbox_mode: field("sandbox_mode"),
+                        model_reasoning_effort: field("model_reasoning_effort"),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(CodexProfiles { active, profiles })
+}
+
+/// 只修改顶层 `profile` 键（None 表示移除），其余内容与注释保持原样
+pub fn set_active_codex_profile(text: &str, profile: Option<&str>) -> Result<String, AppError> {
+    let mut doc = if text.trim().is_empty() {
+        toml_edit::DocumentMut::new()
+    } else {
+        text.parse::<toml_edit::DocumentMut>()
+            .map_err(|e| AppError::Config(format!("解析 config.toml 失败: {e}")))?
+    };
+    match profile {
+        Some(name) => doc["profile"] = toml_edit::value(name),
+        None => {
+            doc.as_table_mut().remove("profile");
+        }
+    }
+    Ok(doc.to_string())
+}
+
+/// 读取 `~/.codex/config.toml` 中的 profile
+pub fn read_codex_profiles() -> Result<CodexProfiles, AppError> {
+    parse_codex_profiles(&read_codex_config_text()?)
+}
+
+/// 切换 `~/.codex/config.toml` 的当前 profile，只改写 `profile` 一行
+///
+/// 指定的 profile 必须已在 `[profiles.*]` 中定义；None 表示回到不使用 profile。
+pub fn switch_codex_profile(profile: Option<&str>) -> Result<CodexProfiles, AppError> {
+    let text = read_and_validate_codex_config_text()?;
+    let mut profiles = parse_codex_profiles(&text)?;
+    if let Some(name) = profile {
+        if !profiles.profiles.iter().any(|p| p.name == name) {
+            return Err(AppError::localized_with(
+                "codex.profile.not_found",
+                &[("name", name.to_string())],
+            ));
+        }
+    }
+
+    let updated = set_active_codex_profile(&text, profile)?;
+    if updated != text {
+        write_text_file(&get_codex_config_path(), &updated)?;
+    }
+    profiles.active = profile.map(str::to_string);
+    Ok(profiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"# hand-crafted
+model = "gpt-5"
+profile = "work"
+
+[profiles.work]
+model = "o3"
+model_provider = "azure"
+
+[profiles.fast]
+model = "gpt-5-mini"
+approval_policy = "never"
+"#;
+
+    #[test]
+    fn parses_profiles_and_active_selection() {
+        let parsed = parse_codex_profiles(CONFIG).expect("parse");
+        assert_eq!(parsed.active.as_deref(), Some("work"));
+        let names: Vec<&str> = parsed.profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names.len(), 2);
+        let work = parsed.profiles.iter().find(|p| p.name == "work").unwrap();
+        assert_eq!(work.model.as_deref(), Some("o3"));
+        assert_eq!(work.model_provider.as_deref(), Some("azure"));
+        assert!(parse_codex_profiles("").unwrap().profiles.is_empty());
+    }
+
+    #[test]
+    fn edits_only_the_profile_key() {
+        let switched = set_active_codex_profile(CONFIG, Some("fast")).expect("set");
+        assert!(switched.contains(r#"profile = "fast""#));
+        assert!(switched.starts_with("# hand-crafted\nmodel = \"gpt-5\"\n"));
+        let parsed = parse_codex_profiles(&switched).unwrap();
+        assert_eq!(parsed.active.as_deref(), Some("fast"));
+        assert_eq!(parsed.profiles.len(), 2);
+
+        let cleared = set_active_codex_profile(CONFIG, None).expect("clear");
+        assert!(!cleared.contains("profile = "));
+        assert!(cleared.contains("[profiles.work]"));
+        assert_eq!(parse_codex_profiles(&cleared).unwrap().active, None);
+    }
+}
@@ -0,0 +1,51 @@
+use tauri::State;
+
+use crate::error::ErrorPayload;
+use crate::services::{AppLockService, AppLockStatus};
+use crate::store::AppState;
+
+/// 获取应用锁状态
+#[tauri::command]
+pub async fn get_app_lock_status(
+    state: State<'_, AppState>,
+) -> Result<AppLockStatus, ErrorPayload> {
+    state
+        .run(|state| AppLockService::status(&state.db))
+        .await
+        .map_err(Into::into)
+}
+
+/// 设置、修改或清除（`passcode` 为空）应用锁口令；已设置口令时需提供当前口令
+#[tauri::command]
+pub async fn set_app_lock_passcode(
+    state: State<'_, AppState>,
+    current: Option<String>,
+    passcode: Option<String>,
+) -> Result<bool, ErrorPayload> {
+    state
+        .run(move |state| {
+            AppLockService::set_passcode(&state.db, current.as_deref(), passcode.as_deref())
+        })
+        .await
+        .map_err(ErrorPayload::from)?;
+    Ok(true)
+}
+
+/// 校验口令，在设置的时长内允许受保护的操作
+#[tauri::command]
+pub async fn unlock_session(
+    state: State<'_, AppState>,
+    passcode: String,
+) -> Result<AppLockStatus, ErrorPayload> {
+    state
+        .run(move |state| AppLockService::unlock(&state.db, &passcode))
+        .await
+        .map_err(Into::into)
+}
+
+/// 立即恢复锁定
+#[tauri::command]
+pub fn lock_session() -> bool {
+    AppLockService::lock();
+    true
+}
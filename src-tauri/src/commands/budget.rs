@@ -0,0 +1,146 @@
+#![allow(non_snake_case)]
+
+use std::str::FromStr;
+
+use tauri::{AppHandle, Emitter, State};
+
+use crate::app_config::AppType;
+use crate::error::ErrorPayload;
+use crate::notifications::NotificationCategory;
+use crate::services::budget::{BudgetStatus, UsageAmount, UsageRecord, UsageRecordResult};
+use crate::services::cost::{CostSummary, DEFAULT_SUMMARY_MONTHS};
+use crate::services::{BudgetService, CostService};
+use crate::settings::ModelPrice;
+use crate::store::AppState;
+use crate::webhooks::WebhookEvent;
+
+/// 获取单个供应商的本月预算状态
+#[tauri::command]
+pub async fn get_provider_budget_status(
+    state: State<'_, AppState>,
+    app: String,
+    providerId: String,
+) -> Result<Option<BudgetStatus>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| BudgetService::get_status(state, app_type, &providerId))
+        .await
+        .map_err(Into::into)
+}
+
+/// 获取指定应用下所有设置了预算的供应商状态
+#[tauri::command]
+pub async fn get_budget_statuses(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<Vec<BudgetStatus>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| BudgetService::list_statuses(state, app_type))
+        .await
+        .map_err(Into::into)
+}
+
+/// 记录一次用量脚本结果；跨越 80%/100% 阈值时向前端发射 `budget-warning` 事件
+#[tauri::command]
+pub async fn record_provider_usage(
+    handle: AppHandle,
+    state: State<'_, AppState>,
+    app: String,
+    providerId: String,
+    tokens: Option<i64>,
+    cost: Option<f64>,
+    inputTokens: Option<i64>,
+    outputTokens: Option<i64>,
+    model: Option<String>,
+    raw: Option<String>,
+) -> Result<UsageRecordResult, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let usage = UsageAmount {
+        tokens: tokens.unwrap_or(0),
+        input_tokens: inputTokens.unwrap_or(0),
+        output_tokens: outputTokens.unwrap_or(0),
+        cost: cost.unwrap_or(0.0),
+        model,
+    };
+    let result = state
+        .run(move |state| BudgetService::record_usage(state, app_type, &providerId, usage, raw))
+        .await?;
+
+    if result.crossed_threshold {
+        if let Some(status) = &result.status {
+            if let Err(e) = handle.emit("budget-warning", status) {
+                log::error!("发射 budget-warning 事件失败: {e}");
+            }
+            crate::notifications::notify(
+                NotificationCategory::UsageAlert,
+                &[
+                    ("provider", status.provider_name.clone()),
+                    ("percent", format!("{:.0}", status.ratio * 100.0)),
+                ],
+            );
+            crate::webhooks::emit(WebhookEvent::UsageThreshold {
+                app: status.app_type.clone(),
+                provider_id: status.provider_id.clone(),
+                provider_name: status.provider_name.clone(),
+                level: format!("{:?}", status.level).to_lowercase(),
+                ratio: status.ratio,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+/// 获取供应商用量历史记录
+#[tauri::command]
+pub async fn get_provider_usage_history(
+    state: State<'_, AppState>,
+    app: String,
+    providerId: String,
+    limit: Option<usize>,
+) -> Result<Vec<UsageRecord>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let limit = limit.unwrap_or(100);
+    state
+        .run(move |state| {
+            state
+                .db
+                .get_usage_history(app_type.as_str(), &providerId, limit)
+        })
+        .await
+        .map_err(Into::into)
+}
+
+/// 清空供应商用量历史记录
+#[tauri::command]
+pub async fn clear_provider_usage_history(
+    state: State<'_, AppState>,
+    app: String,
+    providerId: String,
+) -> Result<bool, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| state.db.clear_usage_history(app_type.as_str(), &providerId))
+        .await?;
+    Ok(true)
+}
+
+/// 获取最近几个月（默认 6 个月）按供应商汇总的费用
+#[tauri::command]
+pub async fn get_cost_summary(
+    state: State<'_, AppState>,
+    months: Option<u32>,
+) -> Result<CostSummary, ErrorPayload> {
+    let months = months.unwrap_or(DEFAULT_SUMMARY_MONTHS);
+    state
+        .run(move |state| CostService::summary(state, months))
+        .await
+        .map_err(Into::into)
+}
+
+/// 获取生效的模型价格表（内置表合并设置中的覆盖项）
+#[tauri::command]
+pub async fn get_model_pricing() -> Result<Vec<ModelPrice>, ErrorPayload> {
+    Ok(CostService::pricing_table())
+}
@@ -7,18 +7,19 @@ use tauri_plugin_opener::OpenerExt;
 use crate::app_config::AppType;
 use crate::codex_config;
 use crate::config::{self, get_claude_settings_path, ConfigStatus};
+use crate::error::ErrorPayload;
 
 /// 获取 Claude Code 配置状态
 #[tauri::command]
-pub async fn get_claude_config_status() -> Result<ConfigStatus, String> {
+pub async fn get_claude_config_status() -> Result<ConfigStatus, ErrorPayload> {
     Ok(config::get_claude_config_status())
 }
 
 use std::str::FromStr;
 
 #[tauri::command]
-pub async fn get_config_status(app: String) -> Result<ConfigStatus, String> {
-    match AppType::from_str(&app).map_err(|e| e.to_string())? {
+pub async fn get_config_status(app: String) -> Result<ConfigStatus, ErrorPayload> {
+    match AppType::from_str(&app)? {
         AppType::Claude => Ok(config::get_claude_config_status()),
         AppType::Codex => {
             let auth_path = codex_config::get_codex_auth_path();
@@ -62,14 +63,14 @@ pub async fn get_config_status(app: String) -> Result<ConfigStatus, String> {
 
 /// 获取 Claude Code 配置文件路径
 #[tauri::command]
-pub async fn get_claude_code_config_path() -> Result<String, String> {
+pub async fn get_claude_code_config_path() -> Result<String, ErrorPayload> {
     Ok(get_claude_settings_path().to_string_lossy().to_string())
 }
 
 /// 获取当前生效的配置目录
 #[tauri::command]
-pub async fn get_config_dir(app: String) -> Result<String, String> {
-    let dir = match AppType::from_str(&app).map_err(|e| e.to_string())? {
+pub async fn get_config_dir(app: String) -> Result<String, ErrorPayload> {
+    let dir = match AppType::from_str(&app)? {
         AppType::Claude => config::get_claude_config_dir(),
         AppType::Codex => codex_config::get_codex_config_dir(),
         AppType::Gemini => crate::gemini_config::get_gemini_dir(),
@@ -82,8 +83,8 @@ pub async fn get_config_dir(app: String) -> Result<String, String> {
 
 /// 打开配置文件夹
 #[tauri::command]
-pub async fn open_config_folder(handle: AppHandle, app: String) -> Result<bool, String> {
-    let config_dir = match AppType::from_str(&app).map_err(|e| e.to_string())? {
+pub async fn open_config_folder(handle: AppHandle, app: String) -> Result<bool, ErrorPayload> {
+    let config_dir = match AppType::from_str(&app)? {
         AppType::Claude => config::get_claude_config_dir(),
         AppType::Codex => codex_config::get_codex_config_dir(),
         AppType::Gemini => crate::gemini_config::get_gemini_dir(),
@@ -108,7 +109,7 @@ pub async fn open_config_folder(handle: AppHandle, app: String) -> Result<bool,
 pub async fn pick_directory(
     app: AppHandle,
     #[allow(non_snake_case)] defaultPath: Option<String>,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, ErrorPayload> {
     let initial = defaultPath
         .map(|p| p.trim().to_string())
         .filter(|p| !p.is_empty());
@@ -137,14 +138,14 @@ pub async fn pick_directory(
 
 /// 获取应用配置文件路径
 #[tauri::command]
-pub async fn get_app_config_path() -> Result<String, String> {
+pub async fn get_app_config_path() -> Result<String, ErrorPayload> {
     let config_path = config::get_app_config_path();
     Ok(config_path.to_string_lossy().to_string())
 }
 
 /// 打开应用配置文件夹
 #[tauri::command]
-pub async fn open_app_config_folder(handle: AppHandle) -> Result<bool, String> {
+pub async fn open_app_config_folder(handle: AppHandle) -> Result<bool, ErrorPayload> {
     let config_dir = config::get_app_config_dir();
 
     if !config_dir.exists() {
@@ -163,11 +164,11 @@ pub async fn open_app_config_folder(handle: AppHandle) -> Result<bool, String> {
 #[tauri::command]
 pub async fn get_claude_common_config_snippet(
     state: tauri::State<'_, crate::store::AppState>,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, ErrorPayload> {
     state
-        .db
-        .get_config_snippet("claude")
-        .map_err(|e| e.to_string())
+        .run(|state| state.db.get_config_snippet("claude"))
+        .await
+        .map_err(Into::into)
 }
 
 /// 设置 Claude 通用配置片段（已废弃，使用 set_common_config_snippet）
@@ -175,7 +176,7 @@ pub async fn get_claude_common_config_snippet(
 pub async fn set_claude_common_config_snippet(
     snippet: String,
     state: tauri::State<'_, crate::store::AppState>,
-) -> Result<(), String> {
+) -> Result<(), ErrorPayload> {
     // 验证是否为有效的 JSON（如果不为空）
     if !snippet.trim().is_empty() {
         serde_json::from_str::<serde_json::Value>(&snippet)
@@ -189,9 +190,8 @@ pub async fn set_claude_common_config_snippet(
     };
 
     state
-        .db
-        .set_config_snippet("claude", value)
-        .map_err(|e| e.to_string())?;
+        .run(move |state| state.db.set_config_snippet("claude", value))
+        .await?;
     Ok(())
 }
 
@@ -200,11 +200,11 @@ pub async fn set_claude_common_config_snippet(
 pub async fn get_common_config_snippet(
     app_type: String,
     state: tauri::State<'_, crate::store::AppState>,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, ErrorPayload> {
     state
-        .db
-        .get_config_snippet(&app_type)
-        .map_err(|e| e.to_string())
+        .run(move |state| state.db.get_config_snippet(&app_type))
+        .await
+        .map_err(Into::into)
 }
 
 /// 设置通用配置片段（统一接口）
@@ -213,7 +213,7 @@ pub async fn set_common_config_snippet(
     app_type: String,
     snippet: String,
     state: tauri::State<'_, crate::store::AppState>,
-) -> Result<(), String> {
+) -> Result<(), ErrorPayload> {
     // 验证格式（根据应用类型）
     if !snippet.trim().is_empty() {
         match app_type.as_str() {
@@ -237,8 +237,18 @@ pub async fn set_common_config_snippet(
     };
 
     state
-        .db
-        .set_config_snippet(&app_type, value)
-        .map_err(|e| e.to_string())?;
+        .run(move |state| state.db.set_config_snippet(&app_type, value))
+        .await?;
     Ok(())
 }
+
+/// 检测各应用 live 配置文件是否在 cc-switch 写入后被外部修改
+#[tauri::command]
+pub async fn get_live_drift(
+    state: tauri::State<'_, crate::store::AppState>,
+) -> Result<Vec<crate::services::LiveDrift>, ErrorPayload> {
+    state
+        .run(crate::services::LiveStateService::drift)
+        .await
+        .map_err(Into::into)
+}
@@ -1,15 +1,16 @@
 use crate::deeplink::{
-    import_mcp_from_deeplink, import_prompt_from_deeplink, import_provider_from_deeplink,
-    import_skill_from_deeplink, parse_deeplink_url, DeepLinkImportRequest,
+    decision_log, import_deeplink_request, import_provider_from_deeplink, parse_deeplink_url,
+    run_action, DeepLinkDecisionRecord, DeepLinkImportRequest,
 };
+use crate::error::ErrorPayload;
 use crate::store::AppState;
-use tauri::State;
+use tauri::{AppHandle, State};
 
 /// Parse a deep link URL and return the parsed request for frontend confirmation
 #[tauri::command]
-pub fn parse_deeplink(url: String) -> Result<DeepLinkImportRequest, String> {
+pub fn parse_deeplink(url: String) -> Result<DeepLinkImportRequest, ErrorPayload> {
     log::info!("Parsing deep link URL: {url}");
-    parse_deeplink_url(&url).map_err(|e| e.to_string())
+    parse_deeplink_url(&url).map_err(Into::into)
 }
 
 /// Merge configuration from Base64/URL into a deep link request
@@ -17,9 +18,9 @@ pub fn parse_deeplink(url: String) -> Result<DeepLinkImportRequest, String> {
 #[tauri::command]
 pub fn merge_deeplink_config(
     request: DeepLinkImportRequest,
-) -> Result<DeepLinkImportRequest, String> {
+) -> Result<DeepLinkImportRequest, ErrorPayload> {
     log::info!("Merging config for deep link request: {:?}", request.name);
-    crate::deeplink::parse_and_merge_config(&request).map_err(|e| e.to_string())
+    crate::deeplink::parse_and_merge_config(&request).map_err(Into::into)
 }
 
 /// Import a provider from a deep link request (legacy, kept for compatibility)
@@ -27,14 +28,14 @@ pub fn merge_deeplink_config(
 pub fn import_from_deeplink(
     state: State<AppState>,
     request: DeepLinkImportRequest,
-) -> Result<String, String> {
+) -> Result<String, ErrorPayload> {
     log::info!(
         "Importing provider from deep link: {:?} for app {:?}",
         request.name,
         request.app
     );
 
-    let provider_id = import_provider_from_deeplink(&state, request).map_err(|e| e.to_string())?;
+    let provider_id = import_provider_from_deeplink(&state, request)?;
 
     log::info!("Successfully imported provider with ID: {provider_id}");
 
@@ -44,46 +45,30 @@ pub fn import_from_deeplink(
 /// Import resource from a deep link request (unified handler)
 #[tauri::command]
 pub async fn import_from_deeplink_unified(
+    app: AppHandle,
     state: State<'_, AppState>,
     request: DeepLinkImportRequest,
-) -> Result<serde_json::Value, String> {
-    log::info!("Importing {} resource from deep link", request.resource);
-
-    match request.resource.as_str() {
-        "provider" => {
-            let provider_id =
-                import_provider_from_deeplink(&state, request).map_err(|e| e.to_string())?;
-            Ok(serde_json::json!({
-                "type": "provider",
-                "id": provider_id
-            }))
-        }
-        "prompt" => {
-            let prompt_id =
-                import_prompt_from_deeplink(&state, request).map_err(|e| e.to_string())?;
-            Ok(serde_json::json!({
-                "type": "prompt",
-                "id": prompt_id
-            }))
-        }
-        "mcp" => {
-            let result = import_mcp_from_deeplink(&state, request).map_err(|e| e.to_string())?;
-            // Add type field to the result
-            Ok(serde_json::json!({
-                "type": "mcp",
-                "importedCount": result.imported_count,
-                "importedIds": result.imported_ids,
-                "failed": result.failed
-            }))
-        }
-        "skill" => {
-            let skill_key =
-                import_skill_from_deeplink(&state, request).map_err(|e| e.to_string())?;
-            Ok(serde_json::json!({
-                "type": "skill",
-                "key": skill_key
-            }))
-        }
-        _ => Err(format!("Unsupported resource type: {}", request.resource)),
+) -> Result<serde_json::Value, ErrorPayload> {
+    if request.resource == "action" {
+        let app = app.clone();
+        return state
+            .run(move |state| run_action(&app, state, &request))
+            .await
+            .map_err(Into::into);
+    }
+    let is_project = request.resource == "project";
+    let result = state
+        .run(move |state| import_deeplink_request(state, request))
+        .await?;
+    if is_project {
+        // 项目切换会改变多个应用的当前供应商
+        crate::tray::refresh_tray(&app, &state);
     }
+    Ok(result)
+}
+
+/// Recent deep link policy decisions, most recent first
+#[tauri::command]
+pub fn get_deeplink_decision_log() -> Vec<DeepLinkDecisionRecord> {
+    decision_log()
 }
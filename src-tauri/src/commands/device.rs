@@ -0,0 +1,41 @@
+use tauri::State;
+
+use crate::error::ErrorPayload;
+use crate::services::{AppLockService, DeviceInfo, DeviceService};
+use crate::store::AppState;
+
+/// 列出共享同一数据库的设备（含当前设备标记）
+#[tauri::command]
+pub async fn list_devices(state: State<'_, AppState>) -> Result<Vec<DeviceInfo>, ErrorPayload> {
+    state
+        .run(|state| DeviceService::list(&state.db))
+        .await
+        .map_err(Into::into)
+}
+
+/// 重命名设备
+#[tauri::command]
+pub async fn rename_device(
+    state: State<'_, AppState>,
+    id: String,
+    name: String,
+) -> Result<bool, ErrorPayload> {
+    state
+        .run(move |state| DeviceService::rename(&state.db, &id, &name))
+        .await
+        .map_err(ErrorPayload::from)?;
+    Ok(true)
+}
+
+/// 删除不再使用的设备
+#[tauri::command]
+pub async fn remove_device(state: State<'_, AppState>, id: String) -> Result<bool, ErrorPayload> {
+    state
+        .run(move |state| {
+            AppLockService::ensure_unlocked(&state.db)?;
+            DeviceService::remove(&state.db, &id)
+        })
+        .await
+        .map_err(ErrorPayload::from)?;
+    Ok(true)
+}
@@ -0,0 +1,113 @@
+#![allow(non_snake_case)]
+
+use std::path::PathBuf;
+
+use tauri::{AppHandle, State};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::error::ErrorPayload;
+use crate::services::diagnostics::{DiagnosticReport, RuntimeStatus};
+use crate::services::{
+    crash_report, logs, CliVersionInfo, CliVersionService, CrashReportEntry, CrashReportService,
+    DiagnosticsService, LogService,
+};
+use crate::store::AppState;
+
+/// 运行启动自检，返回结构化报告（供 Doctor 面板与问题反馈使用）
+#[tauri::command]
+pub async fn run_diagnostics(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<DiagnosticReport, ErrorPayload> {
+    let runtime = runtime_status(&app);
+    state
+        .run(move |state| Ok(DiagnosticsService::run(state, runtime)))
+        .await
+        .map_err(Into::into)
+}
+
+/// 检测已安装 CLI 的版本并给出兼容性提示
+#[tauri::command]
+pub async fn detect_cli_versions(
+    state: State<'_, AppState>,
+) -> Result<Vec<CliVersionInfo>, ErrorPayload> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || CliVersionService::detect(&db))
+        .await
+        .map_err(|e| format!("检测 CLI 版本失败: {e}"))?
+        .map_err(Into::into)
+}
+
+/// 读取最近的日志（已脱敏）
+#[tauri::command]
+pub async fn get_recent_logs(lines: usize) -> Result<Vec<String>, ErrorPayload> {
+    tauri::async_runtime::spawn_blocking(move || LogService::recent_lines(&logs::log_dir(), lines))
+        .await
+        .map_err(|e| format!("读取日志失败: {e}"))?
+        .map_err(Into::into)
+}
+
+/// 将日志与自检报告打包为 zip，便于附在问题反馈中
+#[tauri::command]
+pub async fn export_logs_zip(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    filePath: String,
+) -> Result<String, ErrorPayload> {
+    let runtime = runtime_status(&app);
+    let target = PathBuf::from(filePath);
+    state
+        .run(move |state| {
+            let report = DiagnosticsService::run(state, runtime);
+            LogService::export_zip(&logs::log_dir(), &report, &target)
+        })
+        .await
+        .map(|path| path.display().to_string())
+        .map_err(Into::into)
+}
+
+/// 列出本地崩溃报告
+#[tauri::command]
+pub async fn list_crash_reports() -> Result<Vec<CrashReportEntry>, ErrorPayload> {
+    tauri::async_runtime::spawn_blocking(|| CrashReportService::list(&crash_report::crash_dir()))
+        .await
+        .map_err(|e| format!("读取崩溃报告失败: {e}"))?
+        .map_err(Into::into)
+}
+
+/// 将崩溃报告与自检报告打包为 zip，便于附在 GitHub issue 中
+#[tauri::command]
+pub async fn export_crash_bundle(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    filePath: String,
+) -> Result<String, ErrorPayload> {
+    let runtime = runtime_status(&app);
+    let target = PathBuf::from(filePath);
+    state
+        .run(move |state| {
+            let report = DiagnosticsService::run(state, runtime);
+            CrashReportService::export_bundle(&crash_report::crash_dir(), &report, &target)
+        })
+        .await
+        .map(|path| path.display().to_string())
+        .map_err(Into::into)
+}
+
+/// 删除全部本地崩溃报告，返回删除的文件数
+#[tauri::command]
+pub async fn clear_crash_reports() -> Result<usize, ErrorPayload> {
+    tauri::async_runtime::spawn_blocking(|| CrashReportService::clear(&crash_report::crash_dir()))
+        .await
+        .map_err(|e| format!("删除崩溃报告失败: {e}"))?
+        .map_err(Into::into)
+}
+
+/// 采集只能从 AppHandle 获取的状态
+pub(crate) fn runtime_status(app: &AppHandle) -> RuntimeStatus {
+    RuntimeStatus {
+        // macOS 不支持查询注册状态，返回错误时记为未知
+        deep_link_registered: app.deep_link().is_registered("ccswitch").ok(),
+        tray_created: app.tray_by_id("main").is_some(),
+    }
+}
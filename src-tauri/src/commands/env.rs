@@ -2,25 +2,33 @@ use crate::services::env_checker::{check_env_conflicts as check_conflicts, EnvCo
 use crate::services::env_manager::{
     delete_env_vars as delete_vars, restore_from_backup, BackupInfo,
 };
+use crate::error::ErrorPayload;
+use crate::services::AppLockService;
+use crate::store::AppState;
 use crate::gemini_config::{read_gemini_env, serialize_env_file, write_gemini_env_atomic};
 use serde::Serialize;
+use tauri::State;
 
 /// Check environment variable conflicts for a specific app
 #[tauri::command]
-pub fn check_env_conflicts(app: String) -> Result<Vec<EnvConflict>, String> {
-    check_conflicts(&app)
+pub fn check_env_conflicts(app: String) -> Result<Vec<EnvConflict>, ErrorPayload> {
+    check_conflicts(&app).map_err(Into::into)
 }
 
 /// Delete environment variables with backup
 #[tauri::command]
-pub fn delete_env_vars(conflicts: Vec<EnvConflict>) -> Result<BackupInfo, String> {
-    delete_vars(conflicts)
+pub fn delete_env_vars(
+    conflicts: Vec<EnvConflict>,
+    state: State<'_, AppState>,
+) -> Result<BackupInfo, ErrorPayload> {
+    AppLockService::ensure_unlocked(&state.db)?;
+    delete_vars(conflicts).map_err(Into::into)
 }
 
 /// Restore environment variables from backup file
 #[tauri::command]
-pub fn restore_env_backup(backup_path: String) -> Result<(), String> {
-    restore_from_backup(backup_path)
+pub fn restore_env_backup(backup_path: String) -> Result<(), ErrorPayload> {
+    restore_from_backup(backup_path).map_err(Into::into)
 }
 
 #[derive(Serialize)]
@@ -30,8 +38,8 @@ pub struct GeminiEnvProxyStatus {
 }
 
 #[tauri::command]
-pub fn get_gemini_proxy_status() -> Result<GeminiEnvProxyStatus, String> {
-    let env_map = read_gemini_env().map_err(|e| e.to_string())?;
+pub fn get_gemini_proxy_status() -> Result<GeminiEnvProxyStatus, ErrorPayload> {
+    let env_map = read_gemini_env()?;
     let enabled = match (
         env_map.get("https_proxy"),
         env_map.get("http_proxy"),
@@ -53,8 +61,8 @@ pub fn set_gemini_proxy_enabled(
     enabled: bool,
     host: Option<String>,
     port: Option<String>,
-) -> Result<GeminiEnvProxyStatus, String> {
-    let mut env_map = read_gemini_env().map_err(|e| e.to_string())?;
+) -> Result<GeminiEnvProxyStatus, ErrorPayload> {
+    let mut env_map = read_gemini_env()?;
 
     if enabled {
         let host = host.unwrap_or_else(|| "127.0.0.1".to_string());
@@ -67,7 +75,7 @@ pub fn set_gemini_proxy_enabled(
         env_map.remove("http_proxy");
     }
 
-    write_gemini_env_atomic(&env_map).map_err(|e| e.to_string())?;
+    write_gemini_env_atomic(&env_map)?;
 
     let content = serialize_env_file(&env_map);
     let final_enabled = match (
@@ -0,0 +1,68 @@
+use tauri::State;
+
+use crate::error::ErrorPayload;
+use crate::services::gemini_oauth::GeminiOAuthAccount;
+use crate::services::GeminiOAuthService;
+use crate::store::AppState;
+
+/// 列出已保存的 Gemini OAuth 账号
+#[tauri::command]
+pub async fn list_gemini_oauth_accounts(
+    state: State<'_, AppState>,
+) -> Result<Vec<GeminiOAuthAccount>, ErrorPayload> {
+    state
+        .run(GeminiOAuthService::list)
+        .await
+        .map_err(Into::into)
+}
+
+/// 将当前 Gemini CLI 登录的 Google 账号保存为快照
+#[tauri::command]
+pub async fn capture_gemini_oauth_account(
+    state: State<'_, AppState>,
+    label: Option<String>,
+) -> Result<GeminiOAuthAccount, ErrorPayload> {
+    state
+        .run(move |state| {
+            GeminiOAuthService::capture_current(state, label.as_deref().unwrap_or_default())
+        })
+        .await
+        .map_err(Into::into)
+}
+
+/// 重命名 Gemini OAuth 账号
+#[tauri::command]
+pub async fn rename_gemini_oauth_account(
+    state: State<'_, AppState>,
+    id: String,
+    label: String,
+) -> Result<bool, ErrorPayload> {
+    state
+        .run(move |state| GeminiOAuthService::rename(state, &id, &label))
+        .await
+        .map_err(Into::into)
+}
+
+/// 删除 Gemini OAuth 账号
+#[tauri::command]
+pub async fn delete_gemini_oauth_account(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, ErrorPayload> {
+    state
+        .run(move |state| GeminiOAuthService::delete(state, &id))
+        .await?;
+    Ok(true)
+}
+
+/// 切换 Gemini CLI 当前使用的 Google 账号
+#[tauri::command]
+pub async fn activate_gemini_oauth_account(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, ErrorPayload> {
+    state
+        .run(move |state| GeminiOAuthService::activate(state, &id))
+        .await?;
+    Ok(true)
+}
@@ -1,35 +1,67 @@
+#![allow(non_snake_case)]
+
 use tauri::State;
 
 use crate::app_config::AppType;
+use crate::error::ErrorPayload;
 use crate::grok_config::{GrokSettings, read_grok_settings, write_grok_settings};
-use crate::services::ProviderService;
+use crate::services::sync_report::failures_to_result;
+use crate::services::{GrokModelsRefresh, GrokModelsService, ProviderService};
 use crate::store::AppState;
 
 /// 读取 Grok 配置文件
 #[tauri::command]
-pub fn read_grok_settings_command() -> Result<GrokSettings, String> {
-    read_grok_settings().map_err(|e| e.to_string())
+pub fn read_grok_settings_command() -> Result<GrokSettings, ErrorPayload> {
+    read_grok_settings().map_err(Into::into)
 }
 
 /// 写入 Grok 配置文件
 #[tauri::command]
-pub fn write_grok_settings_command(settings: GrokSettings) -> Result<(), String> {
-    write_grok_settings(&settings).map_err(|e| e.to_string())
+pub fn write_grok_settings_command(settings: GrokSettings) -> Result<(), ErrorPayload> {
+    write_grok_settings(&settings).map_err(Into::into)
 }
 
 /// 读取当前生效的 Grok 配置内容
 #[tauri::command]
-pub fn read_live_grok_settings() -> Result<serde_json::Value, String> {
+pub fn read_live_grok_settings() -> Result<serde_json::Value, ErrorPayload> {
     let app_type = AppType::Grok;
-    ProviderService::read_live_settings(app_type).map_err(|e| e.to_string())
+    ProviderService::read_live_settings(app_type).map_err(Into::into)
 }
 
 /// 同步当前 Grok 供应商到 live 配置
 #[tauri::command]
 pub async fn sync_current_grok_provider_live(
     state: State<'_, AppState>,
-) -> Result<bool, String> {
-    ProviderService::sync_current_to_live(state.inner())
-        .map(|_| true)
-        .map_err(|e| e.to_string())
+) -> Result<bool, ErrorPayload> {
+    // 只关心 Grok 自身的结果，其他应用的失败不影响返回值
+    state
+        .run(|state| {
+            let reports = ProviderService::sync_current_to_live(state)?;
+            let own: Vec<_> = reports
+                .into_iter()
+                .filter(|report| report.app == AppType::Grok)
+                .collect();
+            failures_to_result(&own).map(|_| true)
+        })
+        .await
+        .map_err(Into::into)
+}
+
+/// 从 xAI 模型接口刷新 Grok 供应商的模型列表（默认当前供应商，缓存 24 小时，`force` 跳过缓存）
+#[tauri::command]
+pub async fn refresh_grok_models(
+    state: State<'_, AppState>,
+    providerId: Option<String>,
+    force: Option<bool>,
+) -> Result<GrokModelsRefresh, ErrorPayload> {
+    let request = state
+        .run(move |state| GrokModelsService::prepare(state, providerId, force.unwrap_or(false)))
+        .await?;
+    let (models, fetched_at, from_cache) = GrokModelsService::fetch(&request).await?;
+    state
+        .run(move |state| {
+            GrokModelsService::apply(state, &request.provider_id, models, fetched_at, from_cache)
+        })
+        .await
+        .map_err(Into::into)
 }
@@ -0,0 +1,44 @@
+use std::str::FromStr;
+
+use tauri::State;
+
+use crate::app_config::AppType;
+use crate::error::ErrorPayload;
+use crate::hooks::{HookContext, HookRun};
+use crate::settings::SwitchHook;
+use crate::store::AppState;
+
+/// 获取最近的切换钩子执行记录（最新的在前）
+#[tauri::command]
+pub async fn get_recent_hook_runs() -> Result<Vec<HookRun>, ErrorPayload> {
+    Ok(crate::hooks::recent())
+}
+
+/// 试运行一个钩子（以当前供应商作为上下文，不触发切换）
+#[tauri::command]
+pub async fn test_switch_hook(
+    state: State<'_, AppState>,
+    hook: SwitchHook,
+    app: String,
+) -> Result<HookRun, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let current = crate::settings::get_effective_current_provider(&state.db, &app_type)?;
+    let provider_name = match &current {
+        Some(id) => state
+            .db
+            .get_all_providers(app_type.as_str())?
+            .get(id)
+            .map(|provider| provider.name.clone())
+            .unwrap_or_default(),
+        None => String::new(),
+    };
+    let context = HookContext {
+        app_type,
+        provider_id: current.clone().unwrap_or_default(),
+        provider_name,
+        previous_provider_id: current,
+    };
+    tauri::async_runtime::spawn_blocking(move || crate::hooks::run_hook(&hook, &context))
+        .await
+        .map_err(|e| ErrorPayload::from(format!("执行钩子失败: {e}")))
+}
@@ -1,52 +1,80 @@
 #![allow(non_snake_case)]
 
 use serde_json::{json, Value};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::State;
 use tauri_plugin_dialog::DialogExt;
 
-use crate::error::AppError;
+use crate::database::{ExportOptions, MergePreview, RestoreResource};
+use crate::error::{AppError, ErrorPayload};
+use crate::services::config::{BackupEntry, BackupKind, BackupPreview};
+use crate::services::config_snapshot::ConfigSnapshotResult;
 use crate::services::provider::ProviderService;
+use crate::services::{AppLockService, ConfigService, ConfigSnapshotService, SyncReport};
 use crate::store::AppState;
 
-/// 导出数据库为 SQL 备份
+/// 是否按 JSON 文档导出/导入（按扩展名判断，其余均视为 SQL）
+fn is_json_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+/// 导出数据库为 SQL 备份（`.json` 路径导出为便于 diff 的 JSON 文档）
+///
+/// 传入 `options` 时仅导出选中的应用与资源，并可脱敏 API 密钥。
 #[tauri::command]
 pub async fn export_config_to_file(
     #[allow(non_snake_case)] filePath: String,
+    options: Option<ExportOptions>,
     state: State<'_, AppState>,
-) -> Result<Value, String> {
+) -> Result<Value, ErrorPayload> {
     let db = state.db.clone();
     tauri::async_runtime::spawn_blocking(move || {
         let target_path = PathBuf::from(&filePath);
-        db.export_sql(&target_path)?;
+        let options = options.unwrap_or_default();
+        // 导出供应商或 MCP 配置且不脱敏时会带出密钥
+        if !options.redact_keys && (options.providers || options.mcp) {
+            AppLockService::ensure_unlocked(&db)?;
+        }
+        let message = if is_json_path(&target_path) {
+            db.export_json_with(&target_path, &options)?;
+            "JSON exported successfully"
+        } else {
+            db.export_sql_with(&target_path, &options)?;
+            "SQL exported successfully"
+        };
         Ok::<_, AppError>(json!({
             "success": true,
-            "message": "SQL exported successfully",
+            "message": message,
             "filePath": filePath
         }))
     })
     .await
     .map_err(|e| format!("导出配置失败: {e}"))?
-    .map_err(|e: AppError| e.to_string())
+    .map_err(ErrorPayload::from)
 }
 
-/// 从 SQL 备份导入数据库
+/// 从 SQL 备份导入数据库（`.json` 路径按 JSON 文档导入）
+///
+/// 整体导入会替换全部配置，需先解锁应用。
 #[tauri::command]
 pub async fn import_config_from_file(
     #[allow(non_snake_case)] filePath: String,
     state: State<'_, AppState>,
-) -> Result<Value, String> {
+) -> Result<Value, ErrorPayload> {
     let db = state.db.clone();
     let db_for_state = db.clone();
     tauri::async_runtime::spawn_blocking(move || {
+        AppLockService::ensure_unlocked(&db)?;
         let path_buf = PathBuf::from(&filePath);
-        let backup_id = db.import_sql(&path_buf)?;
+        let (backup_id, message) = if is_json_path(&path_buf) {
+            (db.import_json(&path_buf)?, "JSON imported successfully")
+        } else {
+            (db.import_sql(&path_buf)?, "SQL imported successfully")
+        };
 
         // 导入后同步当前供应商到各自的 live 配置
-        let app_state = AppState::new(db_for_state);
-        if let Err(err) = ProviderService::sync_current_to_live(&app_state) {
-            log::warn!("导入后同步 live 配置失败: {err}");
-        }
+        let sync_reports = sync_live_quietly(&AppState::new(db_for_state), "导入后");
 
         // 重新加载设置到内存缓存，确保导入的设置生效
         if let Err(err) = crate::settings::reload_settings() {
@@ -55,29 +83,223 @@ pub async fn import_config_from_file(
 
         Ok::<_, AppError>(json!({
             "success": true,
-            "message": "SQL imported successfully",
-            "backupId": backup_id
+            "message": message,
+            "backupId": backup_id,
+            "syncReports": sync_reports
         }))
     })
     .await
     .map_err(|e| format!("导入配置失败: {e}"))?
-    .map_err(|e: AppError| e.to_string())
+    .map_err(ErrorPayload::from)
+}
+
+/// 预览增量导入：对比 SQL 备份与当前数据库，列出将新增或更新的条目
+#[tauri::command]
+pub async fn preview_config_merge(
+    #[allow(non_snake_case)] filePath: String,
+    state: State<'_, AppState>,
+) -> Result<MergePreview, ErrorPayload> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || db.preview_sql_merge(&PathBuf::from(&filePath)))
+        .await
+        .map_err(|e| format!("预览导入失败: {e}"))?
+        .map_err(ErrorPayload::from)
+}
+
+/// 确认增量导入：按预览结果合并（fingerprint 需与预览一致，需先解锁应用）
+#[tauri::command]
+pub async fn apply_config_merge(
+    #[allow(non_snake_case)] filePath: String,
+    fingerprint: String,
+    state: State<'_, AppState>,
+) -> Result<Value, ErrorPayload> {
+    let db = state.db.clone();
+    let db_for_state = db.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        AppLockService::ensure_unlocked(&db)?;
+        let path_buf = PathBuf::from(&filePath);
+        let (preview, backup_id) = db.apply_sql_merge(&path_buf, &fingerprint)?;
+
+        // 合并可能更新了当前供应商，同步到各自的 live 配置
+        let sync_reports = if preview.changes.is_empty() {
+            Vec::new()
+        } else {
+            sync_live_quietly(&AppState::new(db_for_state), "增量导入后")
+        };
+
+        Ok::<_, AppError>(json!({
+            "success": true,
+            "message": "SQL merged successfully",
+            "backupId": backup_id,
+            "added": preview.added,
+            "updated": preview.updated,
+            "syncReports": sync_reports
+        }))
+    })
+    .await
+    .map_err(|e| format!("增量导入失败: {e}"))?
+    .map_err(ErrorPayload::from)
+}
+
+/// 列出数据库备份
+#[tauri::command]
+pub async fn list_db_backups() -> Result<Vec<crate::database::DbBackupInfo>, ErrorPayload> {
+    crate::database::Database::list_db_backups().map_err(Into::into)
+}
+
+/// 从数据库备份恢复（恢复前会自动备份当前数据库）
+///
+/// 传入 `resources` 时只恢复选中的资源类型，其余数据保持不变。
+#[tauri::command]
+pub async fn restore_db_backup(
+    #[allow(non_snake_case)] backupId: String,
+    resources: Option<Vec<RestoreResource>>,
+    state: State<'_, AppState>,
+) -> Result<Value, ErrorPayload> {
+    let db = state.db.clone();
+    let db_for_state = db.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        AppLockService::ensure_unlocked(&db)?;
+        let safety_backup_id = match &resources {
+            Some(resources) => db.restore_from_backup(&backupId, resources)?,
+            None => db.restore_db_backup(&backupId)?,
+        };
+
+        // 恢复了设置时重新加载到内存缓存，确保恢复的设置生效
+        let restores_settings = match &resources {
+            Some(resources) => resources.contains(&RestoreResource::Settings),
+            None => true,
+        };
+        if restores_settings {
+            if let Err(err) = crate::settings::reload_settings() {
+                log::warn!("恢复后重载设置失败: {err}");
+            }
+        }
+
+        // 恢复后同步当前供应商到各自的 live 配置
+        let sync_reports = sync_live_quietly(&AppState::new(db_for_state), "恢复后");
+
+        Ok::<_, AppError>(json!({
+            "success": true,
+            "message": "Database restored successfully",
+            "backupId": safety_backup_id,
+            "syncReports": sync_reports
+        }))
+    })
+    .await
+    .map_err(|e| format!("恢复数据库备份失败: {e}"))?
+    .map_err(ErrorPayload::from)
+}
+
+/// 立即导出脱敏配置快照到设置中的快照目录
+#[tauri::command]
+pub async fn export_config_snapshot(
+    state: State<'_, AppState>,
+) -> Result<ConfigSnapshotResult, ErrorPayload> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || ConfigSnapshotService::export_now(&db))
+        .await
+        .map_err(|e| format!("导出配置快照失败: {e}"))?
+        .map_err(ErrorPayload::from)
+}
+
+/// 列出所有备份（JSON 配置备份与数据库备份）
+#[tauri::command]
+pub async fn list_backups() -> Result<Vec<BackupEntry>, ErrorPayload> {
+    tauri::async_runtime::spawn_blocking(ConfigService::list_backups)
+        .await
+        .map_err(|e| format!("读取备份列表失败: {e}"))?
+        .map_err(ErrorPayload::from)
+}
+
+/// 预览备份内容（不执行恢复）
+#[tauri::command]
+pub async fn preview_backup(
+    kind: BackupKind,
+    #[allow(non_snake_case)] backupId: String,
+) -> Result<BackupPreview, ErrorPayload> {
+    tauri::async_runtime::spawn_blocking(move || ConfigService::preview_backup(kind, &backupId))
+        .await
+        .map_err(|e| format!("读取备份失败: {e}"))?
+        .map_err(ErrorPayload::from)
 }
 
+/// 从任意备份恢复（恢复前会自动备份当前数据库）
 #[tauri::command]
-pub async fn sync_current_providers_live(state: State<'_, AppState>) -> Result<Value, String> {
+pub async fn restore_backup(
+    kind: BackupKind,
+    #[allow(non_snake_case)] backupId: String,
+    state: State<'_, AppState>,
+) -> Result<Value, ErrorPayload> {
     let db = state.db.clone();
     tauri::async_runtime::spawn_blocking(move || {
         let app_state = AppState::new(db);
-        ProviderService::sync_current_to_live(&app_state)?;
+        let safety_backup_id = ConfigService::restore_backup(&app_state, kind, &backupId)?;
         Ok::<_, AppError>(json!({
             "success": true,
-            "message": "Live configuration synchronized"
+            "message": "Backup restored successfully",
+            "backupId": safety_backup_id
+        }))
+    })
+    .await
+    .map_err(|e| format!("恢复备份失败: {e}"))?
+    .map_err(ErrorPayload::from)
+}
+
+/// 检查数据库健康状态（repair 为 true 时修复孤立端点与损坏的 JSON）
+#[tauri::command]
+pub async fn check_database_health(
+    repair: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<crate::database::DbHealthReport, ErrorPayload> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || db.check_health(repair.unwrap_or(false)))
+        .await
+        .map_err(|e| format!("数据库健康检查失败: {e}"))?
+        .map_err(ErrorPayload::from)
+}
+
+/// 手动压缩数据库（VACUUM / ANALYZE），返回回收的空间
+#[tauri::command]
+pub async fn compact_database(
+    state: State<'_, AppState>,
+) -> Result<crate::database::CompactReport, ErrorPayload> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || db.compact())
+        .await
+        .map_err(|e| format!("压缩数据库失败: {e}"))?
+        .map_err(ErrorPayload::from)
+}
+
+/// 获取数据库写入租约状态（其他进程正在写入时为只读）
+#[tauri::command]
+pub async fn get_db_lease_status(
+    state: State<'_, AppState>,
+) -> Result<crate::database::DbLeaseStatus, ErrorPayload> {
+    state
+        .run(|state| state.db.lease_status())
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn sync_current_providers_live(
+    state: State<'_, AppState>,
+) -> Result<Value, ErrorPayload> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let app_state = AppState::new(db);
+        let reports = ProviderService::sync_current_to_live(&app_state)?;
+        // 部分应用失败时 success 为 false，由 reports 给出各应用的结果
+        Ok::<_, AppError>(json!({
+            "success": !reports.iter().any(SyncReport::is_failed),
+            "message": "Live configuration synchronized",
+            "reports": reports
         }))
     })
     .await
     .map_err(|e| format!("同步当前供应商失败: {e}"))?
-    .map_err(|e: AppError| e.to_string())
+    .map_err(ErrorPayload::from)
 }
 
 /// 保存文件对话框
@@ -85,11 +307,12 @@ pub async fn sync_current_providers_live(state: State<'_, AppState>) -> Result<V
 pub async fn save_file_dialog<R: tauri::Runtime>(
     app: tauri::AppHandle<R>,
     #[allow(non_snake_case)] defaultName: String,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, ErrorPayload> {
     let dialog = app.dialog();
     let result = dialog
         .file()
         .add_filter("SQL", &["sql"])
+        .add_filter("JSON", &["json"])
         .set_file_name(&defaultName)
         .blocking_save_file();
 
@@ -100,12 +323,23 @@ pub async fn save_file_dialog<R: tauri::Runtime>(
 #[tauri::command]
 pub async fn open_file_dialog<R: tauri::Runtime>(
     app: tauri::AppHandle<R>,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, ErrorPayload> {
     let dialog = app.dialog();
     let result = dialog
         .file()
-        .add_filter("SQL", &["sql"])
+        .add_filter("SQL", &["sql", "json"])
         .blocking_pick_file();
 
     Ok(result.map(|p| p.to_string()))
 }
+
+/// 同步当前供应商到 live 配置，失败只记录日志（MCP 同步失败时返回空结果）
+fn sync_live_quietly(state: &AppState, context: &str) -> Vec<SyncReport> {
+    match ProviderService::sync_current_to_live(state) {
+        Ok(reports) => reports,
+        Err(err) => {
+            log::warn!("{context}同步 live 配置失败: {err}");
+            Vec::new()
+        }
+    }
+}
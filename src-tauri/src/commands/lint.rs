@@ -0,0 +1,44 @@
+use tauri::{AppHandle, Emitter, State};
+
+use crate::error::{AppError, ErrorPayload};
+use crate::services::{LintConfig, LintReport, LintService, LintWarning};
+use crate::store::AppState;
+
+/// 检查全部供应商与提示词，返回配置警告（供界面标记）
+#[tauri::command]
+pub async fn lint_all(state: State<'_, AppState>) -> Result<Vec<LintWarning>, ErrorPayload> {
+    state.run(LintService::lint_all).await.map_err(Into::into)
+}
+
+/// 获取检查规则配置
+#[tauri::command]
+pub async fn get_lint_config(state: State<'_, AppState>) -> Result<LintConfig, ErrorPayload> {
+    state
+        .run(|state| LintService::get_config(&state.db))
+        .await
+        .map_err(Into::into)
+}
+
+/// 保存检查规则配置
+#[tauri::command]
+pub async fn save_lint_config(
+    state: State<'_, AppState>,
+    config: LintConfig,
+) -> Result<(), ErrorPayload> {
+    state
+        .run(move |state| LintService::save_config(&state.db, &config))
+        .await
+        .map_err(Into::into)
+}
+
+/// 向前端发射保存后的检查结果（检查失败只记录日志，不影响保存）
+pub(crate) fn emit_lint_report(handle: &AppHandle, report: Result<LintReport, AppError>) {
+    match report {
+        Ok(report) => {
+            if let Err(e) = handle.emit("lint-warnings", &report) {
+                log::error!("发射 lint-warnings 事件失败: {e}");
+            }
+        }
+        Err(e) => log::warn!("配置检查失败: {e}"),
+    }
+}
@@ -4,41 +4,55 @@ use indexmap::IndexMap;
 use std::collections::HashMap;
 
 use serde::Serialize;
-use tauri::State;
+use tauri::{AppHandle, State};
 
-use crate::app_config::AppType;
+use crate::app_config::{AppType, McpTarget};
 use crate::claude_mcp;
-use crate::services::McpService;
+use crate::error::ErrorPayload;
+use crate::services::{
+    AppLockService, McpProcessStatus, McpService, McpSupervisorService, SupervisedCommand,
+};
 use crate::store::AppState;
 
 /// 获取 Claude MCP 状态
 #[tauri::command]
-pub async fn get_claude_mcp_status() -> Result<claude_mcp::McpStatus, String> {
-    claude_mcp::get_mcp_status().map_err(|e| e.to_string())
+pub async fn get_claude_mcp_status() -> Result<claude_mcp::McpStatus, ErrorPayload> {
+    claude_mcp::get_mcp_status().map_err(Into::into)
 }
 
 /// 读取 mcp.json 文本内容
 #[tauri::command]
-pub async fn read_claude_mcp_config() -> Result<Option<String>, String> {
-    claude_mcp::read_mcp_json().map_err(|e| e.to_string())
+pub async fn read_claude_mcp_config() -> Result<Option<String>, ErrorPayload> {
+    claude_mcp::read_mcp_json().map_err(Into::into)
 }
 
 /// 新增或更新一个 MCP 服务器条目
 #[tauri::command]
-pub async fn upsert_claude_mcp_server(id: String, spec: serde_json::Value) -> Result<bool, String> {
-    claude_mcp::upsert_mcp_server(&id, spec).map_err(|e| e.to_string())
+pub async fn upsert_claude_mcp_server(
+    id: String,
+    spec: serde_json::Value,
+) -> Result<bool, ErrorPayload> {
+    claude_mcp::upsert_mcp_server(&id, spec).map_err(Into::into)
 }
 
 /// 删除一个 MCP 服务器条目
 #[tauri::command]
-pub async fn delete_claude_mcp_server(id: String) -> Result<bool, String> {
-    claude_mcp::delete_mcp_server(&id).map_err(|e| e.to_string())
+pub async fn delete_claude_mcp_server(id: String) -> Result<bool, ErrorPayload> {
+    claude_mcp::delete_mcp_server(&id).map_err(Into::into)
+}
+
+/// 检查 stdio 命令能否启动（PATH 解析、可执行权限、运行时是否安装，不执行命令）
+#[tauri::command]
+pub async fn validate_mcp_command(
+    cmd: String,
+) -> Result<crate::mcp::CommandPreflight, ErrorPayload> {
+    Ok(crate::mcp::preflight_command(&cmd))
 }
 
-/// 校验命令是否在 PATH 中可用（不执行）
+/// 按内置 JSON Schema 校验 MCP 连接定义，返回逐路径的错误列表（供表单实时校验）
 #[tauri::command]
-pub async fn validate_mcp_command(cmd: String) -> Result<bool, String> {
-    claude_mcp::validate_command_in_path(&cmd).map_err(|e| e.to_string())
+pub fn validate_mcp_spec(spec: serde_json::Value) -> Vec<crate::mcp::SpecValidationError> {
+    crate::mcp::validate_spec_detailed(&spec)
 }
 
 #[derive(Serialize)]
@@ -55,12 +69,14 @@ use std::str::FromStr;
 pub async fn get_mcp_config(
     state: State<'_, AppState>,
     app: String,
-) -> Result<McpConfigResponse, String> {
+) -> Result<McpConfigResponse, ErrorPayload> {
     let config_path = crate::config::get_app_config_path()
         .to_string_lossy()
         .to_string();
-    let app_ty = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    let servers = McpService::get_servers(&state, app_ty).map_err(|e| e.to_string())?;
+    let app_ty = AppType::from_str(&app)?;
+    let servers = state
+        .run(move |state| McpService::get_servers(state, app_ty))
+        .await?;
     Ok(McpConfigResponse {
         config_path,
         servers,
@@ -76,59 +92,64 @@ pub async fn upsert_mcp_server_in_config(
     id: String,
     spec: serde_json::Value,
     sync_other_side: Option<bool>,
-) -> Result<bool, String> {
+) -> Result<bool, ErrorPayload> {
     use crate::app_config::McpServer;
 
-    let app_ty = AppType::from_str(&app).map_err(|e| e.to_string())?;
-
-    // 读取现有的服务器（如果存在）
-    let existing_server = {
-        let servers = state.db.get_all_mcp_servers().map_err(|e| e.to_string())?;
-        servers.get(&id).cloned()
-    };
-
-    // 构建新的统一服务器结构
-    let mut new_server = if let Some(mut existing) = existing_server {
-        // 更新现有服务器
-        existing.server = spec.clone();
-        existing.apps.set_enabled_for(&app_ty, true);
-        existing
-    } else {
-        // 创建新服务器
-        let mut apps = crate::app_config::McpApps::default();
-        apps.set_enabled_for(&app_ty, true);
-
-        // 尝试从 spec 中提取 name，否则使用 id
-        let name = spec
-            .get("name")
-            .and_then(|v| v.as_str())
-            .unwrap_or(&id)
-            .to_string();
-
-        McpServer {
-            id: id.clone(),
-            name,
-            server: spec,
-            apps,
-            description: None,
-            homepage: None,
-            docs: None,
-            tags: Vec::new(),
-        }
-    };
-
-    // 如果 sync_other_side 为 true，也启用其他应用
-    if sync_other_side.unwrap_or(false) {
-        new_server.apps.claude = true;
-        new_server.apps.codex = true;
-        new_server.apps.gemini = true;
-        new_server.apps.grok = true;
-        new_server.apps.qwen = true;
-    }
-
-    McpService::upsert_server(&state, new_server)
+    let app_ty = AppType::from_str(&app)?;
+
+    state
+        .run(move |state| {
+            // 读取现有的服务器（如果存在）
+            let existing_server = {
+                let servers = state.db.get_all_mcp_servers()?;
+                servers.get(&id).cloned()
+            };
+
+            // 构建新的统一服务器结构
+            let mut new_server = if let Some(mut existing) = existing_server {
+                // 更新现有服务器
+                existing.server = spec.clone();
+                existing.apps.set_enabled_for(&app_ty, true);
+                existing
+            } else {
+                // 创建新服务器
+                let mut apps = crate::app_config::McpApps::default();
+                apps.set_enabled_for(&app_ty, true);
+
+                // 尝试从 spec 中提取 name，否则使用 id
+                let name = spec
+                    .get("name")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(&id)
+                    .to_string();
+
+                McpServer {
+                    id: id.clone(),
+                    name,
+                    server: spec,
+                    apps,
+                    app_overrides: Default::default(),
+                    description: None,
+                    homepage: None,
+                    docs: None,
+                    tags: Vec::new(),
+                }
+            };
+
+            // 如果 sync_other_side 为 true，也启用其他应用
+            if sync_other_side.unwrap_or(false) {
+                new_server.apps.claude = true;
+                new_server.apps.codex = true;
+                new_server.apps.gemini = true;
+                new_server.apps.grok = true;
+                new_server.apps.qwen = true;
+            }
+
+            McpService::upsert_server(state, new_server)
+        })
+        .await
         .map(|_| true)
-        .map_err(|e| e.to_string())
+        .map_err(Into::into)
 }
 
 /// 在 config.json 中删除一个 MCP 服务器定义
@@ -137,8 +158,14 @@ pub async fn delete_mcp_server_in_config(
     state: State<'_, AppState>,
     _app: String, // 参数保留用于向后兼容，但在统一结构中不再需要
     id: String,
-) -> Result<bool, String> {
-    McpService::delete_server(&state, &id).map_err(|e| e.to_string())
+) -> Result<bool, ErrorPayload> {
+    state
+        .run(move |state| {
+            AppLockService::ensure_unlocked(&state.db)?;
+            McpService::delete_server(state, &id)
+        })
+        .await
+        .map_err(Into::into)
 }
 
 /// 设置启用状态并同步到客户端配置
@@ -149,9 +176,12 @@ pub async fn set_mcp_enabled(
     app: String,
     id: String,
     enabled: bool,
-) -> Result<bool, String> {
-    let app_ty = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    McpService::set_enabled(&state, app_ty, &id, enabled).map_err(|e| e.to_string())
+) -> Result<bool, ErrorPayload> {
+    let app_ty = AppType::from_str(&app)?;
+    state
+        .run(move |state| McpService::set_enabled(state, app_ty, &id, enabled))
+        .await
+        .map_err(Into::into)
 }
 
 // ============================================================================
@@ -164,8 +194,11 @@ use crate::app_config::McpServer;
 #[tauri::command]
 pub async fn get_mcp_servers(
     state: State<'_, AppState>,
-) -> Result<IndexMap<String, McpServer>, String> {
-    McpService::get_all_servers(&state).map_err(|e| e.to_string())
+) -> Result<IndexMap<String, McpServer>, ErrorPayload> {
+    state
+        .run(McpService::get_all_servers)
+        .await
+        .map_err(Into::into)
 }
 
 /// 添加或更新 MCP 服务器
@@ -173,24 +206,303 @@ pub async fn get_mcp_servers(
 pub async fn upsert_mcp_server(
     state: State<'_, AppState>,
     server: McpServer,
-) -> Result<(), String> {
-    McpService::upsert_server(&state, server).map_err(|e| e.to_string())
+) -> Result<(), ErrorPayload> {
+    state
+        .run(move |state| McpService::upsert_server(state, server))
+        .await
+        .map_err(Into::into)
 }
 
 /// 删除 MCP 服务器
 #[tauri::command]
-pub async fn delete_mcp_server(state: State<'_, AppState>, id: String) -> Result<bool, String> {
-    McpService::delete_server(&state, &id).map_err(|e| e.to_string())
+pub async fn delete_mcp_server(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, ErrorPayload> {
+    state
+        .run(move |state| {
+            AppLockService::ensure_unlocked(&state.db)?;
+            McpService::delete_server(state, &id)
+        })
+        .await
+        .map_err(Into::into)
 }
 
 /// 切换 MCP 服务器在指定应用的启用状态
 #[tauri::command]
 pub async fn toggle_mcp_app(
+    handle: AppHandle,
     state: State<'_, AppState>,
     server_id: String,
     app: String,
     enabled: bool,
-) -> Result<(), String> {
-    let app_ty = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    McpService::toggle_app(&state, &server_id, app_ty, enabled).map_err(|e| e.to_string())
+) -> Result<(), ErrorPayload> {
+    let target = McpTarget::from_str(&app)?;
+    state
+        .run(move |state| match target {
+            McpTarget::ClaudeDesktop => {
+                McpService::toggle_claude_desktop(state, &server_id, enabled)
+            }
+            // 连续切换在去抖窗口内合并为每个应用一次 live 写入；写入失败时回滚并发射 mcp-sync-failed
+            McpTarget::App(app_ty) => {
+                McpService::toggle_app_batched(state, handle, &server_id, app_ty, enabled)
+            }
+        })
+        .await
+        .map_err(Into::into)
+}
+
+/// 将源应用已启用的 MCP 服务器一键启用到目标应用
+#[tauri::command]
+pub async fn copy_mcp_enablement(
+    state: State<'_, AppState>,
+    from_app: String,
+    to_app: String,
+) -> Result<crate::services::mcp::McpCopyResult, ErrorPayload> {
+    let from_ty = AppType::from_str(&from_app)?;
+    let to_ty = AppType::from_str(&to_app)?;
+    state
+        .run(move |state| McpService::copy_enablement(state, from_ty, to_ty))
+        .await
+        .map_err(Into::into)
+}
+
+/// 从 Cursor（~/.cursor/mcp.json）导入 MCP 服务器
+#[tauri::command]
+pub async fn import_from_cursor(state: State<'_, AppState>) -> Result<usize, ErrorPayload> {
+    state
+        .run(McpService::import_from_cursor)
+        .await
+        .map_err(Into::into)
+}
+
+/// 从 VS Code（用户 mcp.json / settings.json）导入 MCP 服务器
+#[tauri::command]
+pub async fn import_from_vscode(state: State<'_, AppState>) -> Result<usize, ErrorPayload> {
+    state
+        .run(McpService::import_from_vscode)
+        .await
+        .map_err(Into::into)
+}
+
+/// 从 Windsurf（~/.codeium/windsurf/mcp_config.json）导入 MCP 服务器
+#[tauri::command]
+pub async fn import_from_windsurf(state: State<'_, AppState>) -> Result<usize, ErrorPayload> {
+    state
+        .run(McpService::import_from_windsurf)
+        .await
+        .map_err(Into::into)
+}
+
+/// 将已启用的 MCP 服务器导出为编辑器格式（cursor / vscode / claudeDesktop）
+///
+/// `write` 为 true 时写入对应编辑器的配置文件，否则仅返回渲染结果
+#[tauri::command]
+pub async fn export_mcp_for(
+    state: State<'_, AppState>,
+    target: String,
+    write: Option<bool>,
+) -> Result<crate::mcp::McpExportResult, ErrorPayload> {
+    let target = crate::mcp::McpExportTarget::from_str(&target)?;
+    let write = write.unwrap_or(false);
+    state
+        .run(move |state| McpService::export_for(state, target, write))
+        .await
+        .map_err(Into::into)
+}
+
+/// 将单个 MCP 服务器导出为可分享的 JSON 文档，返回文档内容
+///
+/// 默认清空密钥；`includeSecrets` 为 true 时保留（需应用已解锁）。传入 `filePath` 时同时写入文件
+#[tauri::command]
+pub async fn export_mcp_server(
+    state: State<'_, AppState>,
+    id: String,
+    filePath: Option<String>,
+    includeSecrets: Option<bool>,
+) -> Result<String, ErrorPayload> {
+    let include_secrets = includeSecrets.unwrap_or(false);
+    let target = filePath.map(std::path::PathBuf::from);
+    state
+        .run(move |state| {
+            if include_secrets {
+                AppLockService::ensure_unlocked(&state.db)?;
+            }
+            McpService::export_server(state, &id, include_secrets, target.as_deref())
+        })
+        .await
+        .map_err(Into::into)
+}
+
+/// 从分享文件导入 MCP 服务器（默认不启用任何应用）
+#[tauri::command]
+pub async fn import_mcp_server_file(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<McpServer, ErrorPayload> {
+    state
+        .run(move |state| McpService::import_server_file(state, std::path::Path::new(&path)))
+        .await
+        .map_err(Into::into)
+}
+
+/// 从 Claude Desktop（claude_desktop_config.json）导入 MCP 服务器
+#[tauri::command]
+pub async fn import_from_claude_desktop(state: State<'_, AppState>) -> Result<usize, ErrorPayload> {
+    state
+        .run(McpService::import_from_claude_desktop)
+        .await
+        .map_err(Into::into)
+}
+
+/// 将 MCP 服务器与各应用 live 配置对账（导入新增、补齐启用、标记外部删除）
+#[tauri::command]
+pub async fn reconcile_mcp_servers(
+    state: State<'_, AppState>,
+) -> Result<crate::services::mcp::McpReconcileReport, ErrorPayload> {
+    state
+        .run(McpService::reconcile_with_live)
+        .await
+        .map_err(Into::into)
+}
+
+/// 获取最近一次 MCP 对账结果（含启动时自动对账）
+#[tauri::command]
+pub fn get_mcp_reconcile_report() -> Option<crate::services::mcp::McpReconcileReport> {
+    McpService::last_reconcile_report()
+}
+
+/// 清理指定应用 live 配置中 cc-switch 曾写入但已不再管理的 MCP 条目
+///
+/// `dry_run` 为 true 时仅返回将被移除的服务器 ID
+#[tauri::command]
+pub async fn prune_orphaned_mcp(
+    state: State<'_, AppState>,
+    app: String,
+    dry_run: Option<bool>,
+) -> Result<crate::services::mcp::McpPruneResult, ErrorPayload> {
+    let app_ty = AppType::from_str(&app)?;
+    let dry_run = dry_run.unwrap_or(true);
+    state
+        .run(move |state| McpService::prune_orphaned(state, app_ty, dry_run))
+        .await
+        .map_err(Into::into)
+}
+
+/// 获取各应用支持的 MCP 传输类型（键为应用名，含 claudeDesktop）
+#[tauri::command]
+pub fn get_mcp_transport_matrix() -> std::collections::BTreeMap<String, Vec<String>> {
+    crate::mcp::transport_matrix()
+}
+
+/// 获取远程 MCP 服务器的 stdio 桥接配置
+#[tauri::command]
+pub async fn get_mcp_bridges(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::mcp::McpBridgeConfig>, ErrorPayload> {
+    state
+        .run(|state| McpService::list_bridges(&state.db))
+        .await
+        .map_err(Into::into)
+}
+
+/// 新增或更新桥接配置（同时重新同步该服务器到已启用的应用）
+#[tauri::command]
+pub async fn save_mcp_bridge(
+    state: State<'_, AppState>,
+    bridge: crate::mcp::McpBridgeConfig,
+) -> Result<(), ErrorPayload> {
+    state
+        .run(move |state| McpService::save_bridge(state, bridge))
+        .await
+        .map_err(Into::into)
+}
+
+/// 删除桥接配置
+#[tauri::command]
+pub async fn remove_mcp_bridge(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, ErrorPayload> {
+    state
+        .run(move |state| McpService::remove_bridge(state, &id))
+        .await
+        .map_err(Into::into)
+}
+
+/// 预览服务器经指定工具（mcpRemote / supergateway）桥接后的 stdio 条目
+#[tauri::command]
+pub async fn preview_mcp_bridge(
+    state: State<'_, AppState>,
+    id: String,
+    tool: crate::mcp::McpBridgeTool,
+) -> Result<serde_json::Value, ErrorPayload> {
+    state
+        .run(move |state| McpService::preview_bridge(state, &id, tool))
+        .await
+        .map_err(Into::into)
+}
+
+/// 获取守护进程状态
+#[tauri::command]
+pub async fn get_mcp_process_status(
+    state: State<'_, AppState>,
+) -> Result<Vec<McpProcessStatus>, ErrorPayload> {
+    state
+        .run(|state| McpSupervisorService::status(&state.db))
+        .await
+        .map_err(Into::into)
+}
+
+/// 新增或更新 MCP 服务器的守护进程命令
+#[tauri::command]
+pub async fn save_mcp_supervised_command(
+    state: State<'_, AppState>,
+    command: SupervisedCommand,
+) -> Result<(), ErrorPayload> {
+    state
+        .run(move |state| McpSupervisorService::save_command(&state.db, command))
+        .await
+        .map_err(Into::into)
+}
+
+/// 删除守护进程命令（同时结束进程）
+#[tauri::command]
+pub async fn remove_mcp_supervised_command(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, ErrorPayload> {
+    let removed = state
+        .run(move |state| McpSupervisorService::remove_command(&state.db, &id))
+        .await?;
+    crate::tray::refresh_tray(&app, &state);
+    Ok(removed)
+}
+
+/// 启动 / 停止 / 重启守护进程
+///
+/// `action`: "start" | "stop" | "restart"
+#[tauri::command]
+pub async fn control_mcp_process(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+    action: String,
+) -> Result<(), ErrorPayload> {
+    state
+        .run(move |state| match action.as_str() {
+            "start" => McpSupervisorService::start(&state.db, &id),
+            "stop" => {
+                McpSupervisorService::stop(&id);
+                Ok(())
+            }
+            "restart" => McpSupervisorService::restart(&state.db, &id),
+            _ => Err(crate::error::AppError::InvalidInput(format!(
+                "Unsupported process action: {action}"
+            ))),
+        })
+        .await?;
+    crate::tray::refresh_tray(&app, &state);
+    Ok(())
 }
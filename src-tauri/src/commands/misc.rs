@@ -1,12 +1,15 @@
 #![allow(non_snake_case)]
 
+use crate::error::ErrorPayload;
 use crate::init_status::InitErrorPayload;
+use crate::services::icon::CustomIcon;
+use crate::services::IconService;
 use tauri::AppHandle;
 use tauri_plugin_opener::OpenerExt;
 
 /// 打开外部链接
 #[tauri::command]
-pub async fn open_external(app: AppHandle, url: String) -> Result<bool, String> {
+pub async fn open_external(app: AppHandle, url: String) -> Result<bool, ErrorPayload> {
     let url = if url.starts_with("http://") || url.starts_with("https://") {
         url
     } else {
@@ -22,7 +25,7 @@ pub async fn open_external(app: AppHandle, url: String) -> Result<bool, String>
 
 /// 判断是否为便携版（绿色版）运行
 #[tauri::command]
-pub async fn is_portable_mode() -> Result<bool, String> {
+pub async fn is_portable_mode() -> Result<bool, ErrorPayload> {
     let exe_path = std::env::current_exe().map_err(|e| format!("获取可执行路径失败: {e}"))?;
     if let Some(dir) = exe_path.parent() {
         Ok(dir.join("portable.ini").is_file())
@@ -34,53 +37,36 @@ pub async fn is_portable_mode() -> Result<bool, String> {
 /// 获取应用启动阶段的初始化错误（若有）。
 /// 用于前端在早期主动拉取，避免事件订阅竞态导致的提示缺失。
 #[tauri::command]
-pub async fn get_init_error() -> Result<Option<InitErrorPayload>, String> {
+pub async fn get_init_error() -> Result<Option<InitErrorPayload>, ErrorPayload> {
     Ok(crate::init_status::get_init_error())
 }
 
 /// 获取 JSON→SQLite 迁移结果（若有）。
 /// 只返回一次 true，之后返回 false，用于前端显示一次性 Toast 通知。
 #[tauri::command]
-pub async fn get_migration_result() -> Result<bool, String> {
+pub async fn get_migration_result() -> Result<bool, ErrorPayload> {
     Ok(crate::init_status::take_migration_success())
 }
 
-/// 保存自定义图标文件
+/// 自定义图标目录
+fn custom_icons_dir(app: &AppHandle) -> Result<std::path::PathBuf, ErrorPayload> {
+    use tauri::Manager;
+
+    let data_dir = app.path().app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {e}"))?;
+    Ok(data_dir.join("icons"))
+}
+
+/// 保存自定义图标文件（SVG 会被清理并生成 PNG 回退，内容相同的图标复用已有文件）
 #[tauri::command]
 pub async fn save_custom_icon(
     app: AppHandle,
     file_name: String,
     file_data: Vec<u8>,
     metadata: serde_json::Value,
-) -> Result<String, String> {
-    use tauri::Manager;
-    use std::fs;
-
-    // 获取用户数据目录
-    let data_dir = app.path().app_data_dir()
-        .map_err(|e| format!("获取应用数据目录失败: {e}"))?;
-
-    // 创建 icons 目录
-    let icons_dir = data_dir.join("icons");
-    if !icons_dir.exists() {
-        fs::create_dir_all(&icons_dir)
-            .map_err(|e| format!("创建图标目录失败: {e}"))?;
-    }
-
-    // 保存图片文件
-    let file_path = icons_dir.join(&file_name);
-    fs::write(&file_path, &file_data)
-        .map_err(|e| format!("保存图标文件失败: {e}"))?;
-
-    // 保存元数据
-    let metadata_path = icons_dir.join(format!("{}.json", file_name));
-    let metadata_str = serde_json::to_string_pretty(&metadata)
-        .map_err(|e| format!("序列化元数据失败: {e}"))?;
-    fs::write(&metadata_path, metadata_str)
-        .map_err(|e| format!("保存元数据失败: {e}"))?;
-
-    // 返回自定义协议路径
-    Ok(format!("custom://{}", file_name))
+) -> Result<String, ErrorPayload> {
+    let icons_dir = custom_icons_dir(&app)?;
+    IconService::save(&icons_dir, &file_name, &file_data, metadata).map_err(Into::into)
 }
 
 /// 读取自定义图标文件
@@ -88,23 +74,16 @@ pub async fn save_custom_icon(
 pub async fn read_custom_icon(
     app: AppHandle,
     file_name: String,
-) -> Result<Vec<u8>, String> {
-    use tauri::Manager;
-    use std::fs;
-
-    let data_dir = app.path().app_data_dir()
-        .map_err(|e| format!("获取应用数据目录失败: {e}"))?;
-
-    let file_path = data_dir.join("icons").join(&file_name);
-    
-    if !file_path.exists() {
-        return Err(format!("图标文件不存在: {file_name}"));
-    }
-
-    let data = fs::read(&file_path)
-        .map_err(|e| format!("读取图标文件失败: {e}"))?;
+) -> Result<Vec<u8>, ErrorPayload> {
+    let icons_dir = custom_icons_dir(&app)?;
+    IconService::read(&icons_dir, &file_name).map_err(Into::into)
+}
 
-    Ok(data)
+/// 列出自定义图标库
+#[tauri::command]
+pub async fn list_custom_icons(app: AppHandle) -> Result<Vec<CustomIcon>, ErrorPayload> {
+    let icons_dir = custom_icons_dir(&app)?;
+    IconService::list(&icons_dir).map_err(Into::into)
 }
 
 /// 删除自定义图标文件
@@ -112,28 +91,8 @@ pub async fn read_custom_icon(
 pub async fn delete_custom_icon(
     app: AppHandle,
     file_name: String,
-) -> Result<bool, String> {
-    use tauri::Manager;
-    use std::fs;
-
-    let data_dir = app.path().app_data_dir()
-        .map_err(|e| format!("获取应用数据目录失败: {e}"))?;
-
-    let icons_dir = data_dir.join("icons");
-    let file_path = icons_dir.join(&file_name);
-    let metadata_path = icons_dir.join(format!("{}.json", file_name));
-
-    // 删除图片文件
-    if file_path.exists() {
-        fs::remove_file(&file_path)
-            .map_err(|e| format!("删除图标文件失败: {e}"))?;
-    }
-
-    // 删除元数据文件
-    if metadata_path.exists() {
-        fs::remove_file(&metadata_path)
-            .map_err(|e| format!("删除元数据文件失败: {e}"))?;
-    }
-
+) -> Result<bool, ErrorPayload> {
+    let icons_dir = custom_icons_dir(&app)?;
+    IconService::delete(&icons_dir, &file_name)?;
     Ok(true)
 }
@@ -1,29 +1,61 @@
 #![allow(non_snake_case)]
 
+mod app_lock;
+mod budget;
 mod config;
 mod deeplink;
+mod device;
+mod diagnostics;
 mod env;
+mod gemini_oauth;
 mod grok;
+mod hooks;
 mod import_export;
+mod lint;
 mod mcp;
 mod misc;
+mod models;
+mod network;
+mod onboarding;
 mod plugin;
+mod project;
 mod prompt;
 mod provider;
+mod provider_notes;
+mod provision;
 mod qwen;
 mod settings;
 pub mod skill;
+mod stats;
+mod terminal;
+mod update;
 
+pub use app_lock::*;
+pub use budget::*;
 pub use config::*;
 pub use deeplink::*;
+pub use device::*;
+pub use diagnostics::*;
 pub use env::*;
+pub use gemini_oauth::*;
 pub use grok::*;
+pub use hooks::*;
 pub use import_export::*;
+pub use lint::*;
 pub use mcp::*;
 pub use misc::*;
+pub use models::*;
+pub use network::*;
+pub use onboarding::*;
 pub use plugin::*;
+pub use project::*;
 pub use prompt::*;
 pub use provider::*;
+pub use provider_notes::*;
+pub use provision::*;
 pub use qwen::*;
 pub use settings::*;
 pub use skill::*;
+pub use stats::*;
+pub use terminal::*;
+pub use update::*;
@@ -0,0 +1,66 @@
+#![allow(non_snake_case)]
+
+use std::str::FromStr;
+
+use tauri::State;
+
+use crate::app_config::AppType;
+use crate::error::ErrorPayload;
+use crate::services::models::ModelWarning;
+use crate::services::{ModelService, ProviderService};
+use crate::store::AppState;
+
+/// 获取应用的已知模型列表
+#[tauri::command]
+pub async fn get_known_models(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<Vec<String>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| Ok(ModelService::known_models(state, &app_type)))
+        .await
+        .map_err(Into::into)
+}
+
+/// 检查供应商配置中的模型字段，返回未知模型警告（编辑供应商时提示）
+#[tauri::command]
+pub async fn validate_provider_models(
+    state: State<'_, AppState>,
+    app: String,
+    settingsConfig: serde_json::Value,
+) -> Result<Vec<ModelWarning>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| Ok(ModelService::validate(state, &app_type, &settingsConfig)))
+        .await
+        .map_err(Into::into)
+}
+
+/// 从供应商的 /models 接口获取其实际提供的模型（结果同时并入已知模型目录）
+#[tauri::command]
+pub async fn list_remote_models(
+    state: State<'_, AppState>,
+    app: String,
+    providerId: String,
+) -> Result<Vec<String>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let (api_key, base_url) = {
+        let app_type = app_type.clone();
+        state
+            .run(move |state| ProviderService::credentials(state, &app_type, &providerId))
+            .await?
+    };
+
+    let models = ModelService::fetch_remote(&app_type, &api_key, &base_url).await?;
+
+    let remembered = models.clone();
+    if let Err(e) = state
+        .run(move |state| ModelService::remember(state, &app_type, &remembered))
+        .await
+    {
+        log::warn!("记录远端模型列表失败: {e}");
+    }
+
+    Ok(models)
+}
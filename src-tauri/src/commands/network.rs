@@ -0,0 +1,14 @@
+use crate::error::ErrorPayload;
+use crate::network::NetworkStatus;
+
+/// 获取网络状态（模式、是否在线、暂存的操作）
+#[tauri::command]
+pub async fn get_network_status() -> Result<NetworkStatus, ErrorPayload> {
+    Ok(crate::network::status())
+}
+
+/// 立即探测连通性；恢复联网时会执行暂存的操作
+#[tauri::command]
+pub async fn check_network() -> Result<NetworkStatus, ErrorPayload> {
+    Ok(crate::network::check_now().await)
+}
@@ -0,0 +1,28 @@
+use tauri::State;
+
+use crate::error::ErrorPayload;
+use crate::services::{GuidedImportResult, ImportSelection, OnboardingService, OnboardingState};
+use crate::store::AppState;
+
+/// 首次运行引导状态：已安装的 CLI、可导入的配置与启动时自动导入的内容
+#[tauri::command]
+pub async fn get_onboarding_state(
+    state: State<'_, AppState>,
+) -> Result<OnboardingState, ErrorPayload> {
+    state
+        .run(OnboardingService::state)
+        .await
+        .map_err(Into::into)
+}
+
+/// 按引导向导中的选择导入现有配置
+#[tauri::command]
+pub async fn run_guided_import(
+    state: State<'_, AppState>,
+    selection: Vec<ImportSelection>,
+) -> Result<GuidedImportResult, ErrorPayload> {
+    state
+        .run(move |state| OnboardingService::run_guided_import(state, &selection))
+        .await
+        .map_err(Into::into)
+}
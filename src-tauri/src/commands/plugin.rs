@@ -1,36 +1,70 @@
 #![allow(non_snake_case)]
 
+use crate::claude_plugin::{PluginInventory, PluginToggleResult};
 use crate::config::ConfigStatus;
+use crate::error::ErrorPayload;
 
 /// Claude 插件：获取 ~/.claude/config.json 状态
 #[tauri::command]
-pub async fn get_claude_plugin_status() -> Result<ConfigStatus, String> {
+pub async fn get_claude_plugin_status() -> Result<ConfigStatus, ErrorPayload> {
     crate::claude_plugin::claude_config_status()
         .map(|(exists, path)| ConfigStatus {
             exists,
             path: path.to_string_lossy().to_string(),
         })
-        .map_err(|e| e.to_string())
+        .map_err(Into::into)
 }
 
 /// Claude 插件：读取配置内容（若不存在返回 Ok(None)）
 #[tauri::command]
-pub async fn read_claude_plugin_config() -> Result<Option<String>, String> {
-    crate::claude_plugin::read_claude_config().map_err(|e| e.to_string())
+pub async fn read_claude_plugin_config() -> Result<Option<String>, ErrorPayload> {
+    crate::claude_plugin::read_claude_config().map_err(Into::into)
 }
 
 /// Claude 插件：写入/清除固定配置
 #[tauri::command]
-pub async fn apply_claude_plugin_config(official: bool) -> Result<bool, String> {
+pub async fn apply_claude_plugin_config(official: bool) -> Result<bool, ErrorPayload> {
     if official {
-        crate::claude_plugin::clear_claude_config().map_err(|e| e.to_string())
+        crate::claude_plugin::clear_claude_config().map_err(Into::into)
     } else {
-        crate::claude_plugin::write_claude_config().map_err(|e| e.to_string())
+        crate::claude_plugin::write_claude_config().map_err(Into::into)
     }
 }
 
 /// Claude 插件：检测是否已写入目标配置
 #[tauri::command]
-pub async fn is_claude_plugin_applied() -> Result<bool, String> {
-    crate::claude_plugin::is_claude_config_applied().map_err(|e| e.to_string())
+pub async fn is_claude_plugin_applied() -> Result<bool, ErrorPayload> {
+    crate::claude_plugin::is_claude_config_applied().map_err(Into::into)
+}
+
+/// Claude 插件：列出 ~/.claude/plugins 中的插件市场与已安装插件
+#[tauri::command]
+pub async fn list_claude_plugins() -> Result<PluginInventory, ErrorPayload> {
+    crate::claude_plugin::list_plugins().map_err(Into::into)
+}
+
+/// Claude 插件：启用 / 停用单个插件（修改前自动备份）
+#[tauri::command]
+pub async fn set_claude_plugin_enabled(
+    pluginId: String,
+    enabled: bool,
+) -> Result<PluginToggleResult, ErrorPayload> {
+    crate::claude_plugin::set_plugin_enabled(&pluginId, enabled).map_err(Into::into)
+}
+
+/// Claude 插件：启用 / 停用某个插件市场安装的全部插件（修改前自动备份）
+#[tauri::command]
+pub async fn set_claude_marketplace_enabled(
+    marketplace: String,
+    enabled: bool,
+) -> Result<PluginToggleResult, ErrorPayload> {
+    crate::claude_plugin::set_marketplace_enabled(&marketplace, enabled).map_err(Into::into)
+}
+
+/// Claude 插件：手动备份插件配置，返回备份目录
+#[tauri::command]
+pub async fn backup_claude_plugin_config() -> Result<String, ErrorPayload> {
+    crate::claude_plugin::backup_plugin_config()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .map_err(Into::into)
 }
@@ -0,0 +1,89 @@
+use indexmap::IndexMap;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::error::ErrorPayload;
+use crate::services::project::Project;
+use crate::services::{AppLockService, ProjectApplyResult, ProjectService};
+use crate::store::AppState;
+
+/// 获取所有项目
+#[tauri::command]
+pub async fn get_projects(
+    state: State<'_, AppState>,
+) -> Result<IndexMap<String, Project>, ErrorPayload> {
+    state.run(ProjectService::list).await.map_err(Into::into)
+}
+
+/// 新增或更新项目
+#[tauri::command]
+pub async fn upsert_project(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    project: Project,
+) -> Result<Project, ErrorPayload> {
+    let saved = state
+        .run(move |state| ProjectService::upsert(state, project))
+        .await?;
+    crate::tray::refresh_tray(&app, &state);
+    Ok(saved)
+}
+
+/// 删除项目
+#[tauri::command]
+pub async fn delete_project(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<(), ErrorPayload> {
+    state
+        .run(move |state| {
+            AppLockService::ensure_unlocked(&state.db)?;
+            ProjectService::delete(state, &path)
+        })
+        .await?;
+    crate::tray::refresh_tray(&app, &state);
+    Ok(())
+}
+
+/// 当前项目（最近应用且各应用仍使用其供应商）
+#[tauri::command]
+pub async fn get_active_project(
+    state: State<'_, AppState>,
+) -> Result<Option<Project>, ErrorPayload> {
+    state.run(ProjectService::active).await.map_err(Into::into)
+}
+
+/// 将目录所在项目的供应商应用到各应用
+#[tauri::command]
+pub async fn apply_project(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<ProjectApplyResult, ErrorPayload> {
+    let result = state
+        .run(move |state| ProjectService::apply(state, &path))
+        .await?;
+    notify_project_applied(&app, &state, &result);
+    Ok(result)
+}
+
+/// 刷新托盘并通知前端项目已应用（命令行 / 深链接共用）
+pub(crate) fn notify_project_applied(
+    app: &AppHandle,
+    state: &AppState,
+    result: &ProjectApplyResult,
+) {
+    crate::tray::refresh_tray(app, state);
+    for app_type in &result.switched {
+        let provider_id = &result.project.providers[app_type];
+        if let Err(e) = app.emit(
+            "provider-switched",
+            serde_json::json!({ "appType": app_type, "providerId": provider_id }),
+        ) {
+            log::error!("发射供应商切换事件失败: {e}");
+        }
+    }
+    if let Err(e) = app.emit("project-applied", result) {
+        log::error!("发射项目应用事件失败: {e}");
+    }
+}
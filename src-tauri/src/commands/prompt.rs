@@ -1,31 +1,46 @@
 use indexmap::IndexMap;
 use std::str::FromStr;
 
-use tauri::State;
+use tauri::{AppHandle, State};
 
 use crate::app_config::AppType;
-use crate::prompt::Prompt;
-use crate::services::PromptService;
+use crate::error::ErrorPayload;
+use crate::prompt::{Prompt, PromptSet};
+use crate::services::{AppLockService, LintService, PromptService};
 use crate::store::AppState;
 
+use super::lint::emit_lint_report;
+
 #[tauri::command]
 pub async fn get_prompts(
     app: String,
     state: State<'_, AppState>,
-) -> Result<IndexMap<String, Prompt>, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    PromptService::get_prompts(&state, app_type).map_err(|e| e.to_string())
+) -> Result<IndexMap<String, Prompt>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| PromptService::get_prompts(state, app_type))
+        .await
+        .map_err(Into::into)
 }
 
 #[tauri::command]
 pub async fn upsert_prompt(
+    handle: AppHandle,
     app: String,
     id: String,
     prompt: Prompt,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    PromptService::upsert_prompt(&state, app_type, &id, prompt).map_err(|e| e.to_string())
+) -> Result<(), ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let prompt_id = prompt.id.clone();
+    let report = state
+        .run(move |state| {
+            PromptService::upsert_prompt(state, app_type.clone(), &id, prompt)?;
+            Ok(LintService::lint_saved_prompt(state, &app_type, &prompt_id))
+        })
+        .await?;
+    emit_lint_report(&handle, report);
+    Ok(())
 }
 
 #[tauri::command]
@@ -33,9 +48,15 @@ pub async fn delete_prompt(
     app: String,
     id: String,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    PromptService::delete_prompt(&state, app_type, &id).map_err(|e| e.to_string())
+) -> Result<(), ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| {
+            AppLockService::ensure_unlocked(&state.db)?;
+            PromptService::delete_prompt(state, app_type, &id)
+        })
+        .await
+        .map_err(Into::into)
 }
 
 #[tauri::command]
@@ -43,22 +64,111 @@ pub async fn enable_prompt(
     app: String,
     id: String,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    PromptService::enable_prompt(&state, app_type, &id).map_err(|e| e.to_string())
+) -> Result<(), ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| PromptService::enable_prompt(state, app_type, &id))
+        .await
+        .map_err(Into::into)
 }
 
 #[tauri::command]
 pub async fn import_prompt_from_file(
     app: String,
     state: State<'_, AppState>,
-) -> Result<String, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    PromptService::import_from_file(&state, app_type).map_err(|e| e.to_string())
+) -> Result<String, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| PromptService::import_from_file(state, app_type))
+        .await
+        .map_err(Into::into)
 }
 
+/// 将提示词连同 frontmatter 元数据导出为 Markdown 文件
 #[tauri::command]
-pub async fn get_current_prompt_file_content(app: String) -> Result<Option<String>, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    PromptService::get_current_file_content(app_type).map_err(|e| e.to_string())
+pub async fn export_prompt_to_file(
+    app: String,
+    id: String,
+    file_path: String,
+    state: State<'_, AppState>,
+) -> Result<String, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let target = std::path::PathBuf::from(&file_path);
+    state
+        .run(move |state| PromptService::export_to_file(state, app_type, &id, &target))
+        .await?;
+    Ok(file_path)
+}
+
+#[tauri::command]
+pub async fn get_current_prompt_file_content(app: String) -> Result<Option<String>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    PromptService::get_current_file_content(app_type).map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_prompt_sets(
+    app: String,
+    state: State<'_, AppState>,
+) -> Result<IndexMap<String, PromptSet>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| PromptService::get_prompt_sets(state, app_type))
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn upsert_prompt_set(
+    app: String,
+    set: PromptSet,
+    state: State<'_, AppState>,
+) -> Result<(), ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| PromptService::upsert_prompt_set(state, app_type, set))
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn delete_prompt_set(
+    app: String,
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<(), ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| {
+            AppLockService::ensure_unlocked(&state.db)?;
+            PromptService::delete_prompt_set(state, app_type, &id)
+        })
+        .await
+        .map_err(Into::into)
+}
+
+/// 启用提示词组；`selected_prompt_id` 用于 select 模式下切换成员
+#[tauri::command]
+pub async fn apply_prompt_set(
+    app: String,
+    id: String,
+    selected_prompt_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| PromptService::apply_prompt_set(state, app_type, &id, selected_prompt_id))
+        .await
+        .map_err(Into::into)
+}
+
+/// 各应用提示词文件当前的来源（含多目标提示词写入的应用）
+#[tauri::command]
+pub async fn get_prompt_live_state(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::services::prompt::PromptLiveState>, ErrorPayload> {
+    state
+        .run(PromptService::live_state)
+        .await
+        .map_err(Into::into)
 }
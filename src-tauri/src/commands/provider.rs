@@ -1,63 +1,147 @@
 use indexmap::IndexMap;
-use tauri::State;
+use tauri::{AppHandle, State};
 
 use crate::app_config::AppType;
-use crate::error::AppError;
-use crate::provider::Provider;
-use crate::services::{EndpointLatency, ProviderService, ProviderSortUpdate, SpeedtestService};
+use crate::codex_config::CodexProfiles;
+use crate::error::{AppError, ErrorPayload};
+use crate::provider::{Provider, ProviderGroup};
+use crate::services::provider::{
+    CodexProfileImportResult, DetectedTool, FormatMigrationReport, OtherTool,
+    OtherToolImportResult, ProviderDeletionImpact, ShellEnvImportResult, ShellEnvProvider,
+    ShellEnvSelection,
+};
+use crate::services::{
+    AppLockService, BackfillResolution, CodexAuthInfo, CopiedCredential, CredentialCopyService,
+    CredentialField, EndpointLatency, KeyCheckResult, KeyCheckService, LintService, ProviderDraft,
+    ProviderService, ProviderSortUpdate, SmokeTestResult, SmokeTestService, SpeedtestService,
+    SwitchOutcome, SwitchPreparation,
+};
 use crate::store::AppState;
 use std::str::FromStr;
 
+use super::lint::emit_lint_report;
+
 /// 获取所有供应商
 #[tauri::command]
-pub fn get_providers(
+pub async fn get_providers(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<IndexMap<String, Provider>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let mut providers = state
+        .run(move |state| ProviderService::list(state, app_type))
+        .await?;
+    providers
+        .values_mut()
+        .for_each(ProviderService::mask_credentials);
+    Ok(providers)
+}
+
+/// 获取供应商的完整配置（含 API Key 原文，仅供编辑表单使用）
+///
+/// 受应用锁与系统身份验证保护。
+#[tauri::command]
+pub async fn reveal_provider_credentials(
     state: State<'_, AppState>,
     app: String,
-) -> Result<IndexMap<String, Provider>, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::list(state.inner(), app_type).map_err(|e| e.to_string())
+    id: String,
+) -> Result<serde_json::Value, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| ProviderService::reveal_credentials(state, &app_type, &id))
+        .await
+        .map_err(Into::into)
 }
 
 /// 获取当前供应商ID
 #[tauri::command]
-pub fn get_current_provider(state: State<'_, AppState>, app: String) -> Result<String, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::current(state.inner(), app_type).map_err(|e| e.to_string())
+pub async fn get_current_provider(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<String, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| ProviderService::current(state, app_type))
+        .await
+        .map_err(Into::into)
 }
 
 /// 添加供应商
 #[tauri::command]
-pub fn add_provider(
+pub async fn add_provider(
+    handle: AppHandle,
     state: State<'_, AppState>,
     app: String,
     provider: Provider,
-) -> Result<bool, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::add(state.inner(), app_type, provider).map_err(|e| e.to_string())
+) -> Result<bool, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let id = provider.id.clone();
+    let (added, report) = state
+        .run(move |state| {
+            let added = ProviderService::add(state, app_type.clone(), provider)?;
+            Ok((
+                added,
+                LintService::lint_saved_provider(state, &app_type, &id),
+            ))
+        })
+        .await?;
+    emit_lint_report(&handle, report);
+    Ok(added)
 }
 
 /// 更新供应商
 #[tauri::command]
-pub fn update_provider(
+pub async fn update_provider(
+    handle: AppHandle,
     state: State<'_, AppState>,
     app: String,
     provider: Provider,
-) -> Result<bool, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::update(state.inner(), app_type, provider).map_err(|e| e.to_string())
+) -> Result<bool, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let id = provider.id.clone();
+    let (updated, report) = state
+        .run(move |state| {
+            let updated = ProviderService::update(state, app_type.clone(), provider)?;
+            Ok((
+                updated,
+                LintService::lint_saved_provider(state, &app_type, &id),
+            ))
+        })
+        .await?;
+    emit_lint_report(&handle, report);
+    Ok(updated)
 }
 
 /// 删除供应商
 #[tauri::command]
-pub fn delete_provider(
+pub async fn delete_provider(
     state: State<'_, AppState>,
     app: String,
     id: String,
-) -> Result<bool, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::delete(state.inner(), app_type, &id)
+) -> Result<bool, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| {
+            AppLockService::ensure_unlocked(&state.db)?;
+            ProviderService::delete(state, app_type, &id)
+        })
+        .await
         .map(|_| true)
-        .map_err(|e| e.to_string())
+        .map_err(Into::into)
+}
+
+/// 分析删除供应商的影响（当前使用、项目、其他设备、历史记录）
+#[tauri::command]
+pub async fn analyze_provider_deletion(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+) -> Result<ProviderDeletionImpact, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| ProviderService::analyze_deletion(state, app_type, &id))
+        .await
+        .map_err(Into::into)
 }
 
 /// 切换供应商
@@ -75,15 +159,52 @@ pub fn switch_provider_test_hook(
 }
 
 #[tauri::command]
-pub fn switch_provider(
+pub async fn switch_provider(
     state: State<'_, AppState>,
     app: String,
     id: String,
-) -> Result<bool, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    switch_provider_internal(&state, app_type, &id)
+) -> Result<bool, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| switch_provider_internal(state, app_type, &id))
+        .await
         .map(|_| true)
-        .map_err(|e| e.to_string())
+        .map_err(Into::into)
+}
+
+/// 切换供应商（检测回填冲突）
+///
+/// 当前供应商的存储配置与 live 配置都被修改过时，不执行切换并返回冲突详情；
+/// 前端让用户选择后携带 resolution（keepMine / takeLive / merge）再次调用。
+/// 目标 CLI 正在运行且设置为阻止切换时返回进程列表，用户确认后携带 force 再次调用。
+#[tauri::command]
+pub async fn switch_provider_with_resolution(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+    resolution: Option<BackfillResolution>,
+    force: Option<bool>,
+) -> Result<SwitchOutcome, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let force = force.unwrap_or(false);
+    let pending = match state
+        .run(move |state| ProviderService::prepare_switch(state, app_type, &id, resolution, force))
+        .await?
+    {
+        SwitchPreparation::Done(outcome) => return Ok(outcome),
+        SwitchPreparation::Ready(pending) => pending,
+    };
+
+    // 切换前钩子可能运行较久，不放在数据库任务中执行
+    let context = pending.hook_context().clone();
+    tauri::async_runtime::spawn_blocking(move || crate::hooks::run_before(&context))
+        .await
+        .map_err(|e| AppError::Message(format!("执行切换前钩子失败: {e}")))??;
+
+    state
+        .run(move |state| ProviderService::finish_switch(state, pending))
+        .await
+        .map_err(Into::into)
 }
 
 fn import_default_config_internal(state: &AppState, app_type: AppType) -> Result<bool, AppError> {
@@ -100,16 +221,32 @@ pub fn import_default_config_test_hook(
 
 /// 导入当前配置为默认供应商
 #[tauri::command]
-pub fn import_default_config(state: State<'_, AppState>, app: String) -> Result<bool, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    import_default_config_internal(&state, app_type).map_err(Into::into)
+pub async fn import_default_config(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<bool, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| import_default_config_internal(state, app_type))
+        .await
+        .map_err(Into::into)
 }
 
 /// 读取当前生效的配置内容
 #[tauri::command]
-pub fn read_live_provider_settings(app: String) -> Result<serde_json::Value, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::read_live_settings(app_type).map_err(|e| e.to_string())
+pub fn read_live_provider_settings(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<serde_json::Value, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    ProviderService::read_current_live_settings(&state, app_type).map_err(Into::into)
+}
+
+/// 解析用户粘贴的配置文件内容（settings.json / config.toml + auth.json / .env），返回预填的供应商草稿
+#[tauri::command]
+pub fn parse_pasted_config(app: String, text: String) -> Result<ProviderDraft, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    ProviderService::parse_pasted_config(app_type, &text).map_err(Into::into)
 }
 
 /// 测试第三方/自定义供应商端点的网络延迟
@@ -117,83 +254,398 @@ pub fn read_live_provider_settings(app: String) -> Result<serde_json::Value, Str
 pub async fn test_api_endpoints(
     urls: Vec<String>,
     #[allow(non_snake_case)] timeoutSecs: Option<u64>,
-) -> Result<Vec<EndpointLatency>, String> {
+) -> Result<Vec<EndpointLatency>, ErrorPayload> {
     SpeedtestService::test_endpoints(urls, timeoutSecs)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(Into::into)
 }
 
 /// 获取自定义端点列表
 #[tauri::command]
-pub fn get_custom_endpoints(
+pub async fn get_custom_endpoints(
     state: State<'_, AppState>,
     app: String,
     #[allow(non_snake_case)] providerId: String,
-) -> Result<Vec<crate::settings::CustomEndpoint>, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::get_custom_endpoints(state.inner(), app_type, &providerId)
-        .map_err(|e| e.to_string())
+) -> Result<Vec<crate::settings::CustomEndpoint>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| ProviderService::get_custom_endpoints(state, app_type, &providerId))
+        .await
+        .map_err(Into::into)
 }
 
 /// 添加自定义端点
 #[tauri::command]
-pub fn add_custom_endpoint(
+pub async fn add_custom_endpoint(
     state: State<'_, AppState>,
     app: String,
     #[allow(non_snake_case)] providerId: String,
     url: String,
-) -> Result<(), String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::add_custom_endpoint(state.inner(), app_type, &providerId, url)
-        .map_err(|e| e.to_string())
+) -> Result<(), ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| ProviderService::add_custom_endpoint(state, app_type, &providerId, url))
+        .await
+        .map_err(Into::into)
 }
 
 /// 删除自定义端点
 #[tauri::command]
-pub fn remove_custom_endpoint(
+pub async fn remove_custom_endpoint(
     state: State<'_, AppState>,
     app: String,
     #[allow(non_snake_case)] providerId: String,
     url: String,
-) -> Result<(), String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::remove_custom_endpoint(state.inner(), app_type, &providerId, url)
-        .map_err(|e| e.to_string())
+) -> Result<(), ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| {
+            ProviderService::remove_custom_endpoint(state, app_type, &providerId, url)
+        })
+        .await
+        .map_err(Into::into)
 }
 
 /// 更新端点最后使用时间
 #[tauri::command]
-pub fn update_endpoint_last_used(
+pub async fn update_endpoint_last_used(
     state: State<'_, AppState>,
     app: String,
     #[allow(non_snake_case)] providerId: String,
     url: String,
-) -> Result<(), String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::update_endpoint_last_used(state.inner(), app_type, &providerId, url)
-        .map_err(|e| e.to_string())
+) -> Result<(), ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| {
+            ProviderService::update_endpoint_last_used(state, app_type, &providerId, url)
+        })
+        .await
+        .map_err(Into::into)
 }
 
 /// 更新供应商置顶状态
 #[tauri::command]
-pub fn update_provider_pin_status(
+pub async fn update_provider_pin_status(
     state: State<'_, AppState>,
     app: String,
     provider_id: String,
     is_pinned: bool,
-) -> Result<bool, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::update_pin_status(state.inner(), app_type, &provider_id, is_pinned)
-        .map_err(|e| e.to_string())
+) -> Result<bool, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| {
+            ProviderService::update_pin_status(state, app_type, &provider_id, is_pinned)
+        })
+        .await
+        .map_err(Into::into)
 }
 
 /// 更新多个供应商的排序
 #[tauri::command]
-pub fn update_providers_sort_order(
+pub async fn update_providers_sort_order(
     state: State<'_, AppState>,
     app: String,
     updates: Vec<ProviderSortUpdate>,
-) -> Result<bool, String> {
-    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::update_sort_order(state.inner(), app_type, updates).map_err(|e| e.to_string())
+) -> Result<bool, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| ProviderService::update_sort_order(state, app_type, updates))
+        .await
+        .map_err(Into::into)
+}
+
+/// 获取按分类分组的供应商（分组按已保存的分类顺序排列）
+#[tauri::command]
+pub async fn get_providers_grouped(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<Vec<ProviderGroup>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let mut groups = state
+        .run(move |state| ProviderService::list_grouped(state, app_type))
+        .await?;
+    groups
+        .iter_mut()
+        .flat_map(|group| group.providers.iter_mut())
+        .for_each(ProviderService::mask_credentials);
+    Ok(groups)
+}
+
+/// 获取供应商分类顺序
+#[tauri::command]
+pub async fn get_category_order(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<Vec<String>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| ProviderService::category_order(state, app_type))
+        .await
+        .map_err(Into::into)
+}
+
+/// 保存供应商分类顺序（拖拽分组后调用）
+#[tauri::command]
+pub async fn set_category_order(
+    state: State<'_, AppState>,
+    app: String,
+    order: Vec<String>,
+) -> Result<Vec<String>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| ProviderService::set_category_order(state, app_type, order))
+        .await
+        .map_err(Into::into)
+}
+
+/// 将供应商移动到其他分类（category 为空表示未分类）
+#[tauri::command]
+pub async fn set_provider_category(
+    state: State<'_, AppState>,
+    app: String,
+    provider_id: String,
+    category: Option<String>,
+    sort_index: Option<usize>,
+) -> Result<bool, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| {
+            ProviderService::set_category(state, app_type, &provider_id, category, sort_index)
+        })
+        .await
+        .map_err(Into::into)
+}
+
+/// 扫描本机已安装的其他切换工具（如 claude-code-router）
+#[tauri::command]
+pub async fn detect_other_tools() -> Result<Vec<DetectedTool>, ErrorPayload> {
+    Ok(ProviderService::detect_other_tools())
+}
+
+/// 从其他切换工具的配置导入供应商（未指定 path 时读取默认位置）
+#[tauri::command]
+pub async fn import_from_other_tool(
+    state: State<'_, AppState>,
+    tool: OtherTool,
+    path: Option<String>,
+) -> Result<OtherToolImportResult, ErrorPayload> {
+    state
+        .run(move |state| ProviderService::import_other_tool(state, tool, path.as_deref()))
+        .await
+        .map_err(Into::into)
+}
+
+/// 扫描 Shell 配置文件（~/.zshrc、~/.bashrc 等）中导出的供应商环境变量
+#[tauri::command]
+pub async fn scan_shell_env_providers(
+    state: State<'_, AppState>,
+) -> Result<Vec<ShellEnvProvider>, ErrorPayload> {
+    state
+        .run(ProviderService::scan_shell_env)
+        .await
+        .map_err(Into::into)
+}
+
+/// 将选中的 Shell 环境变量导入为供应商，可选注释掉原有行（自动备份）
+#[tauri::command]
+pub async fn import_shell_env_providers(
+    state: State<'_, AppState>,
+    selections: Vec<ShellEnvSelection>,
+    comment_out: Option<bool>,
+) -> Result<ShellEnvImportResult, ErrorPayload> {
+    state
+        .run(move |state| {
+            ProviderService::import_shell_env(state, &selections, comment_out.unwrap_or(false))
+        })
+        .await
+        .map_err(Into::into)
+}
+
+/// 将已保存的供应商配置迁移到 CLI 当前的配置格式（未指定 app 时处理所有应用）
+#[tauri::command]
+pub async fn run_format_migrations(
+    state: State<'_, AppState>,
+    app: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<FormatMigrationReport, ErrorPayload> {
+    let app_type = app.as_deref().map(AppType::from_str).transpose()?;
+    state
+        .run(move |state| {
+            ProviderService::run_format_migrations(
+                state,
+                app_type.as_ref(),
+                dry_run.unwrap_or(false),
+            )
+        })
+        .await
+        .map_err(Into::into)
+}
+
+/// 获取 Codex 供应商的认证类型（API Key / ChatGPT 登录）及令牌过期状态
+///
+/// 未指定 providerId 时读取当前 `~/.codex/auth.json`。
+#[tauri::command]
+pub async fn get_codex_auth_info(
+    state: State<'_, AppState>,
+    provider_id: Option<String>,
+) -> Result<CodexAuthInfo, ErrorPayload> {
+    state
+        .run(move |state| ProviderService::codex_auth_info(state, provider_id.as_deref()))
+        .await
+        .map_err(Into::into)
+}
+
+/// 获取所有 Codex 供应商的认证类型（用于列表徽标）
+#[tauri::command]
+pub async fn get_codex_auth_kinds(
+    state: State<'_, AppState>,
+) -> Result<IndexMap<String, CodexAuthInfo>, ErrorPayload> {
+    state
+        .run(ProviderService::codex_auth_kinds)
+        .await
+        .map_err(Into::into)
+}
+
+/// 获取 `~/.codex/config.toml` 中的 profile 列表与当前选择
+#[tauri::command]
+pub async fn get_codex_profiles(state: State<'_, AppState>) -> Result<CodexProfiles, ErrorPayload> {
+    state
+        .run(ProviderService::codex_profiles)
+        .await
+        .map_err(Into::into)
+}
+
+/// 切换 Codex profile（只改写 config.toml 的顶层 `profile` 键，profile 为空时清除选择）
+#[tauri::command]
+pub async fn switch_codex_profile(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<CodexProfiles, ErrorPayload> {
+    state
+        .run(move |state| ProviderService::switch_codex_profile(state, profile.as_deref()))
+        .await
+        .map_err(Into::into)
+}
+
+/// 将 Codex profile 导入为绑定该 profile 的供应商（已绑定的跳过）
+#[tauri::command]
+pub async fn import_codex_profiles(
+    state: State<'_, AppState>,
+) -> Result<CodexProfileImportResult, ErrorPayload> {
+    state
+        .run(ProviderService::import_codex_profiles)
+        .await
+        .map_err(Into::into)
+}
+
+/// 设置供应商标签
+#[tauri::command]
+pub async fn set_provider_tags(
+    state: State<'_, AppState>,
+    app: String,
+    provider_id: String,
+    tags: Vec<String>,
+) -> Result<Vec<String>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| ProviderService::set_tags(state, app_type, &provider_id, tags))
+        .await
+        .map_err(Into::into)
+}
+
+/// 按标签筛选供应商（matchAll 为 true 时需包含全部标签）
+#[tauri::command]
+pub async fn get_providers_by_tags(
+    state: State<'_, AppState>,
+    app: String,
+    tags: Vec<String>,
+    match_all: Option<bool>,
+) -> Result<IndexMap<String, Provider>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let mut providers = state
+        .run(move |state| {
+            ProviderService::list_by_tags(state, app_type, &tags, match_all.unwrap_or(false))
+        })
+        .await?;
+    providers
+        .values_mut()
+        .for_each(ProviderService::mask_credentials);
+    Ok(providers)
+}
+
+/// 获取应用下所有供应商使用过的标签
+#[tauri::command]
+pub async fn get_provider_tags(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<Vec<String>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| ProviderService::all_tags(state, app_type))
+        .await
+        .map_err(Into::into)
+}
+
+/// 验证供应商 API Key（对端点发起一次最小鉴权请求，切换前确认可用）
+#[tauri::command]
+pub async fn verify_provider_key(
+    state: State<'_, AppState>,
+    app: String,
+    provider_id: String,
+) -> Result<KeyCheckResult, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let (api_key, base_url) = {
+        let app_type = app_type.clone();
+        state
+            .run(move |state| ProviderService::credentials(state, &app_type, &provider_id))
+            .await?
+    };
+    KeyCheckService::verify(&app_type, &api_key, &base_url)
+        .await
+        .map_err(Into::into)
+}
+
+/// 复制供应商（缺省为当前供应商）的请求地址或 API Key 到剪贴板
+///
+/// `confirm` 为真时复制 Key 原文前弹出原生确认框；用户取消返回 `null`。
+#[tauri::command]
+pub async fn copy_provider_credential(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    app: String,
+    field: CredentialField,
+    provider_id: Option<String>,
+    confirm: Option<bool>,
+) -> Result<Option<CopiedCredential>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| {
+            CredentialCopyService::copy(
+                &app_handle,
+                state,
+                &app_type,
+                provider_id.as_deref(),
+                field,
+                confirm.unwrap_or(false),
+            )
+        })
+        .await
+        .map_err(Into::into)
+}
+
+/// 冒烟测试：通过供应商配置的端点与模型发送一次极小的真实补全请求
+///
+/// `model` 为空时使用供应商配置中的模型。
+#[tauri::command]
+pub async fn smoke_test_provider(
+    state: State<'_, AppState>,
+    app: String,
+    provider_id: String,
+    model: Option<String>,
+) -> Result<SmokeTestResult, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let plan = state
+        .run(move |state| SmokeTestService::prepare(state, &app_type, &provider_id, model))
+        .await?;
+    SmokeTestService::run(plan).await.map_err(Into::into)
 }
@@ -0,0 +1,104 @@
+#![allow(non_snake_case)]
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use tauri::State;
+
+use crate::app_config::AppType;
+use crate::error::ErrorPayload;
+use crate::services::{AppLockService, ProviderAttachment, ProviderNotesService};
+use crate::store::AppState;
+
+/// 将供应商备注渲染为 HTML（已清理，可直接嵌入页面）
+#[tauri::command]
+pub async fn render_provider_notes(
+    state: State<'_, AppState>,
+    app: String,
+    providerId: String,
+) -> Result<String, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| ProviderNotesService::render_notes(state, app_type, &providerId))
+        .await
+        .map_err(Into::into)
+}
+
+/// 将供应商备注导出为 HTML 文件
+#[tauri::command]
+pub async fn export_provider_notes_html(
+    state: State<'_, AppState>,
+    app: String,
+    providerId: String,
+    filePath: String,
+) -> Result<bool, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let target = PathBuf::from(filePath);
+    state
+        .run(move |state| {
+            ProviderNotesService::export_notes_html(state, app_type, &providerId, &target)
+        })
+        .await?;
+    Ok(true)
+}
+
+/// 从本地文件添加供应商附件
+#[tauri::command]
+pub async fn add_provider_attachment(
+    state: State<'_, AppState>,
+    app: String,
+    providerId: String,
+    filePath: String,
+) -> Result<ProviderAttachment, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    let source = PathBuf::from(filePath);
+    state
+        .run(move |state| {
+            ProviderNotesService::add_attachment(state, app_type, &providerId, &source)
+        })
+        .await
+        .map_err(Into::into)
+}
+
+/// 获取供应商附件列表（不含内容）
+#[tauri::command]
+pub async fn get_provider_attachments(
+    state: State<'_, AppState>,
+    app: String,
+    providerId: String,
+) -> Result<Vec<ProviderAttachment>, ErrorPayload> {
+    let app_type = AppType::from_str(&app)?;
+    state
+        .run(move |state| ProviderNotesService::list_attachments(state, app_type, &providerId))
+        .await
+        .map_err(Into::into)
+}
+
+/// 将附件另存到本地文件
+#[tauri::command]
+pub async fn save_provider_attachment(
+    state: State<'_, AppState>,
+    id: i64,
+    filePath: String,
+) -> Result<bool, ErrorPayload> {
+    let target = PathBuf::from(filePath);
+    state
+        .run(move |state| ProviderNotesService::save_attachment(state, id, &target))
+        .await?;
+    Ok(true)
+}
+
+/// 删除供应商附件
+#[tauri::command]
+pub async fn delete_provider_attachment(
+    state: State<'_, AppState>,
+    id: i64,
+) -> Result<bool, ErrorPayload> {
+    state
+        .run(move |state| {
+            AppLockService::ensure_unlocked(&state.db)?;
+            ProviderNotesService::delete_attachment(state, id)
+        })
+        .await
+        .map_err(Into::into)
+}
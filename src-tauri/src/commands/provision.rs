@@ -0,0 +1,18 @@
+use tauri::State;
+
+use crate::error::ErrorPayload;
+use crate::services::{ProvisionResult, ProvisionService};
+use crate::store::AppState;
+
+/// 从团队模板清单地址（HTTPS）导入默认配置，已存在的条目保持不变
+///
+/// `url` 为空时使用 `CC_SWITCH_PROVISION_URL` 环境变量或设置中的地址。
+#[tauri::command]
+pub async fn provision_from_url(
+    url: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ProvisionResult, ErrorPayload> {
+    ProvisionService::provision(&state, url)
+        .await
+        .map_err(Into::into)
+}
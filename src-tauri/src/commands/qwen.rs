@@ -1,35 +1,46 @@
 use tauri::State;
 
 use crate::app_config::AppType;
+use crate::error::ErrorPayload;
 use crate::qwen_config::{QwenSettings, read_qwen_settings, write_qwen_settings};
+use crate::services::sync_report::failures_to_result;
 use crate::services::ProviderService;
 use crate::store::AppState;
 
 /// 读取 Qwen 配置文件
 #[tauri::command]
-pub fn read_qwen_settings_command() -> Result<QwenSettings, String> {
-    read_qwen_settings().map_err(|e| e.to_string())
+pub fn read_qwen_settings_command() -> Result<QwenSettings, ErrorPayload> {
+    read_qwen_settings().map_err(Into::into)
 }
 
 /// 写入 Qwen 配置文件
 #[tauri::command]
-pub fn write_qwen_settings_command(settings: QwenSettings) -> Result<(), String> {
-    write_qwen_settings(&settings).map_err(|e| e.to_string())
+pub fn write_qwen_settings_command(settings: QwenSettings) -> Result<(), ErrorPayload> {
+    write_qwen_settings(&settings).map_err(Into::into)
 }
 
 /// 读取当前生效的 Qwen 配置内容
 #[tauri::command]
-pub fn read_live_qwen_settings() -> Result<serde_json::Value, String> {
+pub fn read_live_qwen_settings() -> Result<serde_json::Value, ErrorPayload> {
     let app_type = AppType::Qwen;
-    ProviderService::read_live_settings(app_type).map_err(|e| e.to_string())
+    ProviderService::read_live_settings(app_type).map_err(Into::into)
 }
 
 /// 同步当前 Qwen 供应商到 live 配置
 #[tauri::command]
 pub async fn sync_current_qwen_provider_live(
     state: State<'_, AppState>,
-) -> Result<bool, String> {
-    ProviderService::sync_current_to_live(state.inner())
-        .map(|_| true)
-        .map_err(|e| e.to_string())
+) -> Result<bool, ErrorPayload> {
+    // 只关心 Qwen 自身的结果，其他应用的失败不影响返回值
+    state
+        .run(|state| {
+            let reports = ProviderService::sync_current_to_live(state)?;
+            let own: Vec<_> = reports
+                .into_iter()
+                .filter(|report| report.app == AppType::Qwen)
+                .collect();
+            failures_to_result(&own).map(|_| true)
+        })
+        .await
+        .map_err(Into::into)
 }
\ No newline at end of file
@@ -1,23 +1,74 @@
 #![allow(non_snake_case)]
 
-use tauri::AppHandle;
+use tauri::{AppHandle, State};
+
+use crate::error::{AppError, ErrorPayload};
+use crate::store::AppState;
 
 /// 获取设置
 #[tauri::command]
-pub async fn get_settings() -> Result<crate::settings::AppSettings, String> {
+pub async fn get_settings() -> Result<crate::settings::AppSettings, ErrorPayload> {
     Ok(crate::settings::get_settings())
 }
 
 /// 保存设置
 #[tauri::command]
-pub async fn save_settings(settings: crate::settings::AppSettings) -> Result<bool, String> {
-    crate::settings::update_settings(settings).map_err(|e| e.to_string())?;
+pub async fn save_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    settings: crate::settings::AppSettings,
+) -> Result<bool, ErrorPayload> {
+    // 关闭 API Key 身份验证本身需要先通过验证
+    if crate::settings::get_settings().biometric_key_reveal && !settings.biometric_key_reveal {
+        tauri::async_runtime::spawn_blocking(|| {
+            crate::auth::verify(&crate::i18n::tr("auth.disable_reason", &[]))
+        })
+        .await
+        .map_err(|e| AppError::Message(format!("系统身份验证失败: {e}")))??;
+    }
+    state
+        .run(move |_| crate::settings::update_settings(settings))
+        .await?;
+    // 动态托盘图标开关可能已变化
+    crate::tray_icon::refresh(&app, state.inner());
     Ok(true)
 }
 
+/// 获取系统身份验证（Touch ID / Windows Hello）的支持情况与开关
+#[tauri::command]
+pub async fn get_biometric_status() -> Result<crate::auth::BiometricStatus, ErrorPayload> {
+    tauri::async_runtime::spawn_blocking(crate::auth::status)
+        .await
+        .map_err(|e| AppError::Message(format!("读取系统身份验证状态失败: {e}")).into())
+}
+
+/// 生效设置（已应用机器策略）
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveSettings {
+    pub settings: crate::settings::AppSettings,
+    /// 被机器策略锁定的字段（与 Settings 字段名一致）
+    pub locked: Vec<&'static str>,
+    /// 策略来源（文件路径或注册表路径）
+    pub policy_source: Option<&'static str>,
+    pub policy: crate::policy::Policy,
+}
+
+/// 获取生效设置及被策略锁定的字段
+#[tauri::command]
+pub async fn get_effective_settings() -> Result<EffectiveSettings, ErrorPayload> {
+    let policy = crate::policy::current();
+    Ok(EffectiveSettings {
+        settings: crate::settings::get_settings(),
+        locked: policy.locked_fields(),
+        policy_source: crate::policy::source(),
+        policy: policy.clone(),
+    })
+}
+
 /// 重启应用程序（当 app_config_dir 变更后使用）
 #[tauri::command]
-pub async fn restart_app(app: AppHandle) -> Result<bool, String> {
+pub async fn restart_app(app: AppHandle) -> Result<bool, ErrorPayload> {
     // 在后台延迟重启，让函数有时间返回响应
     tauri::async_runtime::spawn(async move {
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
@@ -28,7 +79,7 @@ pub async fn restart_app(app: AppHandle) -> Result<bool, String> {
 
 /// 获取 app_config_dir 覆盖配置 (从 Store)
 #[tauri::command]
-pub async fn get_app_config_dir_override(app: AppHandle) -> Result<Option<String>, String> {
+pub async fn get_app_config_dir_override(app: AppHandle) -> Result<Option<String>, ErrorPayload> {
     Ok(crate::app_store::refresh_app_config_dir_override(&app)
         .map(|p| p.to_string_lossy().to_string()))
 }
@@ -38,14 +89,17 @@ pub async fn get_app_config_dir_override(app: AppHandle) -> Result<Option<String
 pub async fn set_app_config_dir_override(
     app: AppHandle,
     path: Option<String>,
-) -> Result<bool, String> {
+) -> Result<bool, ErrorPayload> {
+    if crate::policy::current().lock_config_dirs {
+        return Err(AppError::localized("policy.config_dir_locked").into());
+    }
     crate::app_store::set_app_config_dir_to_store(&app, path.as_deref())?;
     Ok(true)
 }
 
 /// 设置开机自启
 #[tauri::command]
-pub async fn set_auto_launch(enabled: bool) -> Result<bool, String> {
+pub async fn set_auto_launch(enabled: bool) -> Result<bool, ErrorPayload> {
     if enabled {
         crate::auto_launch::enable_auto_launch().map_err(|e| format!("启用开机自启失败: {e}"))?;
     } else {
@@ -56,6 +110,13 @@ pub async fn set_auto_launch(enabled: bool) -> Result<bool, String> {
 
 /// 获取开机自启状态
 #[tauri::command]
-pub async fn get_auto_launch_status() -> Result<bool, String> {
-    crate::auto_launch::is_auto_launch_enabled().map_err(|e| format!("获取开机自启状态失败: {e}"))
+pub async fn get_auto_launch_status() -> Result<bool, ErrorPayload> {
+    crate::auto_launch::is_auto_launch_enabled()
+        .map_err(|e| ErrorPayload::from(format!("获取开机自启状态失败: {e}")))
+}
+
+/// 向设置中的 Webhook 地址发送测试事件
+#[tauri::command]
+pub async fn test_webhook() -> Result<crate::webhooks::WebhookDelivery, ErrorPayload> {
+    crate::webhooks::send_test().await.map_err(Into::into)
 }
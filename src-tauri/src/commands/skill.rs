@@ -1,8 +1,9 @@
-use crate::error::format_skill_error;
+use crate::error::{format_skill_error, ErrorPayload};
 use crate::services::skill::SkillState;
-use crate::services::{Skill, SkillRepo, SkillService};
+use crate::services::{AppLockService, Skill, SkillPreview, SkillRepo, SkillService};
 use crate::store::AppState;
 use chrono::Utc;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::State;
 
@@ -12,51 +13,97 @@ pub struct SkillServiceState(pub Arc<SkillService>);
 pub async fn get_skills(
     service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
-) -> Result<Vec<Skill>, String> {
-    let repos = app_state.db.get_skill_repos().map_err(|e| e.to_string())?;
+) -> Result<Vec<Skill>, ErrorPayload> {
+    let repos = app_state.run(|state| state.db.get_skill_repos()).await?;
 
-    let skills = service
-        .0
-        .list_skills(repos)
-        .await
-        .map_err(|e| e.to_string())?;
+    let skills = service.0.list_skills(repos).await?;
 
     // 自动同步本地已安装的 skills 到数据库
     // 这样用户在首次运行时，已有的 skills 会被自动记录
-    let existing_states = app_state.db.get_skills().unwrap_or_default();
-
-    for skill in &skills {
-        if skill.installed && !existing_states.contains_key(&skill.directory) {
-            // 本地有该 skill，但数据库中没有记录，自动添加
-            if let Err(e) = app_state.db.update_skill_state(
-                &skill.directory,
-                &SkillState {
-                    installed: true,
-                    installed_at: Utc::now(),
-                },
-            ) {
-                log::warn!("同步本地 skill {} 状态到数据库失败: {}", skill.directory, e);
+    let installed: Vec<String> = skills
+        .iter()
+        .filter(|skill| skill.installed)
+        .map(|skill| skill.directory.clone())
+        .collect();
+    app_state
+        .run(move |state| {
+            let existing_states = state.db.get_skills().unwrap_or_default();
+            for directory in installed {
+                if existing_states.contains_key(&directory) {
+                    continue;
+                }
+                // 本地有该 skill，但数据库中没有记录，自动添加
+                if let Err(e) = state.db.update_skill_state(
+                    &directory,
+                    &SkillState {
+                        installed: true,
+                        installed_at: Utc::now(),
+                    },
+                ) {
+                    log::warn!("同步本地 skill {directory} 状态到数据库失败: {e}");
+                }
             }
-        }
-    }
+            Ok(())
+        })
+        .await?;
 
     Ok(skills)
 }
 
+/// 浏览任意仓库中的候选技能（无需先添加仓库），用于安装前预览
+#[tauri::command]
+pub async fn browse_skill_repo(
+    owner: String,
+    name: String,
+    branch: Option<String>,
+    service: State<'_, SkillServiceState>,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<SkillPreview>, ErrorPayload> {
+    // 若该仓库已配置，沿用其 Enterprise 地址与访问令牌
+    let configured = app_state
+        .run(|state| state.db.get_skill_repos())
+        .await?
+        .into_iter()
+        .find(|r| r.owner.eq_ignore_ascii_case(&owner) && r.name.eq_ignore_ascii_case(&name));
+
+    let branch = branch.unwrap_or_default();
+    let repo = match configured {
+        Some(repo) => SkillRepo {
+            branch: if branch.is_empty() { repo.branch.clone() } else { branch },
+            ..repo
+        },
+        None => SkillRepo::new(owner, name, branch),
+    };
+
+    service.0.browse_repo(&repo).await.map_err(Into::into)
+}
+
+/// 在已配置的仓库中按关键字搜索技能
+#[tauri::command]
+pub async fn search_skills(
+    keyword: String,
+    service: State<'_, SkillServiceState>,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<Skill>, ErrorPayload> {
+    let repos = app_state.run(|state| state.db.get_skill_repos()).await?;
+
+    service
+        .0
+        .search_skills(repos, &keyword)
+        .await
+        .map_err(Into::into)
+}
+
 #[tauri::command]
 pub async fn install_skill(
     directory: String,
     service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
-) -> Result<bool, String> {
+) -> Result<bool, ErrorPayload> {
     // 先在不持有写锁的情况下收集仓库与技能信息
-    let repos = app_state.db.get_skill_repos().map_err(|e| e.to_string())?;
+    let repos = app_state.run(|state| state.db.get_skill_repos()).await?;
 
-    let skills = service
-        .0
-        .list_skills(repos)
-        .await
-        .map_err(|e| e.to_string())?;
+    let skills = service.0.list_skills(repos.clone()).await?;
 
     let skill = skills
         .iter()
@@ -70,45 +117,48 @@ pub async fn install_skill(
         })?;
 
     if !skill.installed {
-        let repo = SkillRepo {
-            owner: skill.repo_owner.clone().ok_or_else(|| {
-                format_skill_error(
-                    "MISSING_REPO_INFO",
-                    &[("directory", &directory), ("field", "owner")],
-                    None,
-                )
-            })?,
-            name: skill.repo_name.clone().ok_or_else(|| {
-                format_skill_error(
-                    "MISSING_REPO_INFO",
-                    &[("directory", &directory), ("field", "name")],
-                    None,
-                )
-            })?,
-            branch: skill
-                .repo_branch
-                .clone()
-                .unwrap_or_else(|| "main".to_string()),
-            enabled: true,
+        let owner = skill.repo_owner.clone().ok_or_else(|| {
+            format_skill_error(
+                "MISSING_REPO_INFO",
+                &[("directory", &directory), ("field", "owner")],
+                None,
+            )
+        })?;
+        let name = skill.repo_name.clone().ok_or_else(|| {
+            format_skill_error(
+                "MISSING_REPO_INFO",
+                &[("directory", &directory), ("field", "name")],
+                None,
+            )
+        })?;
+        let branch = skill
+            .repo_branch
+            .clone()
+            .unwrap_or_else(|| "main".to_string());
+
+        // 沿用仓库配置中的 Enterprise 地址与访问令牌
+        let repo = match repos
+            .into_iter()
+            .find(|r| r.owner == owner && r.name == name)
+        {
+            Some(repo) => SkillRepo { branch, ..repo },
+            None => SkillRepo::new(owner, name, branch),
         };
 
-        service
-            .0
-            .install_skill(directory.clone(), repo)
-            .await
-            .map_err(|e| e.to_string())?;
+        service.0.install_skill(directory.clone(), repo).await?;
     }
 
     app_state
-        .db
-        .update_skill_state(
-            &directory,
-            &SkillState {
-                installed: true,
-                installed_at: Utc::now(),
-            },
-        )
-        .map_err(|e| e.to_string())?;
+        .run(move |state| {
+            state.db.update_skill_state(
+                &directory,
+                &SkillState {
+                    installed: true,
+                    installed_at: Utc::now(),
+                },
+            )
+        })
+        .await?;
 
     Ok(true)
 }
@@ -118,23 +168,17 @@ pub fn uninstall_skill(
     directory: String,
     service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
-) -> Result<bool, String> {
-    service
-        .0
-        .uninstall_skill(directory.clone())
-        .map_err(|e| e.to_string())?;
+) -> Result<bool, ErrorPayload> {
+    service.0.uninstall_skill(directory.clone())?;
 
     // Remove from database by setting installed = false
-    app_state
-        .db
-        .update_skill_state(
-            &directory,
-            &SkillState {
-                installed: false,
-                installed_at: Utc::now(),
-            },
-        )
-        .map_err(|e| e.to_string())?;
+    app_state.db.update_skill_state(
+        &directory,
+        &SkillState {
+            installed: false,
+            installed_at: Utc::now(),
+        },
+    )?;
 
     Ok(true)
 }
@@ -143,8 +187,8 @@ pub fn uninstall_skill(
 pub fn get_skill_repos(
     _service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
-) -> Result<Vec<SkillRepo>, String> {
-    app_state.db.get_skill_repos().map_err(|e| e.to_string())
+) -> Result<Vec<SkillRepo>, ErrorPayload> {
+    app_state.db.get_skill_repos().map_err(Into::into)
 }
 
 #[tauri::command]
@@ -152,11 +196,22 @@ pub fn add_skill_repo(
     repo: SkillRepo,
     _service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
-) -> Result<bool, String> {
+) -> Result<bool, ErrorPayload> {
+    app_state.db.save_skill_repo(&repo)?;
+    Ok(true)
+}
+
+/// 设置或清除私有仓库的访问令牌（令牌为空时清除）
+#[tauri::command]
+pub fn set_skill_repo_token(
+    owner: String,
+    name: String,
+    token: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<bool, ErrorPayload> {
     app_state
         .db
-        .save_skill_repo(&repo)
-        .map_err(|e| e.to_string())?;
+        .set_skill_repo_token(&owner, &name, token.as_deref())?;
     Ok(true)
 }
 
@@ -166,10 +221,81 @@ pub fn remove_skill_repo(
     name: String,
     _service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
-) -> Result<bool, String> {
-    app_state
-        .db
-        .delete_skill_repo(&owner, &name)
-        .map_err(|e| e.to_string())?;
+) -> Result<bool, ErrorPayload> {
+    AppLockService::ensure_unlocked(&app_state.db)?;
+    app_state.db.delete_skill_repo(&owner, &name)?;
+    Ok(true)
+}
+
+/// 创建（或覆盖）自建技能
+#[tauri::command]
+pub fn create_local_skill(
+    name: String,
+    content: String,
+    service: State<'_, SkillServiceState>,
+) -> Result<Skill, ErrorPayload> {
+    service
+        .0
+        .create_local_skill(&name, &content, true)
+        .map_err(Into::into)
+}
+
+/// 列出自建技能
+#[tauri::command]
+pub fn get_local_skills(service: State<'_, SkillServiceState>) -> Result<Vec<Skill>, ErrorPayload> {
+    service.0.list_authored_skills().map_err(Into::into)
+}
+
+/// 将自建技能安装到 ~/.claude/skills
+#[tauri::command]
+pub fn install_local_skill(
+    directory: String,
+    service: State<'_, SkillServiceState>,
+    app_state: State<'_, AppState>,
+) -> Result<bool, ErrorPayload> {
+    service.0.install_authored_skill(&directory)?;
+
+    app_state.db.update_skill_state(
+        &directory,
+        &SkillState {
+            installed: true,
+            installed_at: Utc::now(),
+        },
+    )?;
+
+    Ok(true)
+}
+
+/// 删除自建技能源文件（已安装副本需通过 uninstall_skill 移除）
+#[tauri::command]
+pub fn delete_local_skill(
+    directory: String,
+    service: State<'_, SkillServiceState>,
+    app_state: State<'_, AppState>,
+) -> Result<bool, ErrorPayload> {
+    AppLockService::ensure_unlocked(&app_state.db)?;
+    service.0.delete_authored_skill(&directory)?;
     Ok(true)
 }
+
+/// 将技能导出为 zip 文件
+#[tauri::command]
+pub fn export_skill_as_zip(
+    directory: String,
+    #[allow(non_snake_case)] filePath: String,
+    service: State<'_, SkillServiceState>,
+) -> Result<String, ErrorPayload> {
+    let path = service
+        .0
+        .export_skill_as_zip(&directory, &PathBuf::from(&filePath))?;
+    Ok(path.display().to_string())
+}
+
+/// 生成技能分享链接（ccswitch://）
+#[tauri::command]
+pub fn get_skill_share_link(
+    directory: String,
+    service: State<'_, SkillServiceState>,
+) -> Result<String, ErrorPayload> {
+    service.0.build_share_link(&directory).map_err(Into::into)
+}
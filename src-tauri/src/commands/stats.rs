@@ -0,0 +1,18 @@
+use tauri::State;
+
+use crate::error::ErrorPayload;
+use crate::services::{StatsRange, StatsService, SwitchStats};
+use crate::store::AppState;
+
+/// 获取供应商切换统计（切换次数、最常用供应商、累计使用时长）
+#[tauri::command]
+pub async fn get_switch_stats(
+    state: State<'_, AppState>,
+    range: Option<StatsRange>,
+) -> Result<SwitchStats, ErrorPayload> {
+    let range = range.unwrap_or_default();
+    state
+        .run(move |state| StatsService::get_switch_stats(state, range))
+        .await
+        .map_err(Into::into)
+}
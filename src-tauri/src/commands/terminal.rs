@@ -0,0 +1,31 @@
+#![allow(non_snake_case)]
+
+use std::str::FromStr;
+
+use tauri::{AppHandle, Manager};
+
+use crate::app_config::AppType;
+use crate::error::ErrorPayload;
+use crate::services::{CliLaunch, TerminalService};
+use crate::store::AppState;
+
+/// 在终端中启动应用的 CLI；传入 `providerId` 时先切换到该供应商
+#[tauri::command]
+pub async fn launch_cli(
+    app: AppHandle,
+    appType: String,
+    providerId: Option<String>,
+    cwd: Option<String>,
+) -> Result<CliLaunch, ErrorPayload> {
+    let app_type = AppType::from_str(&appType)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Some(provider_id) = providerId {
+            crate::tray::switch_provider_internal(&app, app_type.clone(), provider_id)?;
+        }
+        let state = app.state::<AppState>();
+        TerminalService::launch(&state, &app_type, cwd.as_deref())
+    })
+    .await
+    .map_err(|e| format!("启动 CLI 失败: {e}"))?
+    .map_err(Into::into)
+}
@@ -0,0 +1,35 @@
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+use crate::error::ErrorPayload;
+use crate::services::update::{UpdateInfo, UpdateService};
+
+/// 检查 GitHub Releases 上的最新版本
+#[tauri::command]
+pub async fn check_update() -> Result<UpdateInfo, ErrorPayload> {
+    UpdateService::check().await.map_err(Into::into)
+}
+
+/// 下载并校验当前平台的安装包，然后交给系统安装程序打开
+///
+/// 返回下载后的安装包路径；前端可随后调用 process 插件退出应用以完成安装。
+#[tauri::command]
+pub async fn perform_update(handle: AppHandle) -> Result<String, ErrorPayload> {
+    let target_dir = std::env::temp_dir().join("cc-switch-update");
+    let path = UpdateService::download(&target_dir).await?;
+    let path_str = path.to_string_lossy().to_string();
+
+    handle
+        .opener()
+        .open_path(path_str.clone(), None::<String>)
+        .map_err(|e| format!("打开安装包失败: {e}"))?;
+
+    Ok(path_str)
+}
+
+/// 跳过指定版本（不再提示），传入空值取消跳过
+#[tauri::command]
+pub async fn skip_update_version(version: Option<String>) -> Result<bool, ErrorPayload> {
+    UpdateService::skip_version(version)?;
+    Ok(true)
+}
@@ -4,6 +4,7 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use crate::error::AppError;
+use crate::settings::SymlinkWriteMode;
 
 /// 获取 Claude Code 配置目录路径
 pub fn get_claude_config_dir() -> PathBuf {
@@ -62,6 +63,33 @@ pub fn get_claude_settings_path() -> PathBuf {
     settings
 }
 
+/// 获取 Claude Code 本地配置文件路径（settings.local.json，优先级高于 settings.json）
+pub fn get_claude_local_settings_path() -> PathBuf {
+    get_claude_config_dir().join("settings.local.json")
+}
+
+/// 获取 Claude Code 企业托管配置文件路径（managed-settings.json，优先级最高，只读）
+pub fn get_claude_managed_settings_path() -> PathBuf {
+    #[cfg(target_os = "macos")]
+    {
+        PathBuf::from("/Library/Application Support/ClaudeCode/managed-settings.json")
+    }
+
+    #[cfg(windows)]
+    {
+        let program_data =
+            std::env::var_os("ProgramData").unwrap_or_else(|| "C:\\ProgramData".into());
+        PathBuf::from(program_data)
+            .join("ClaudeCode")
+            .join("managed-settings.json")
+    }
+
+    #[cfg(not(any(target_os = "macos", windows)))]
+    {
+        PathBuf::from("/etc/claude-code/managed-settings.json")
+    }
+}
+
 /// 获取应用配置目录路径 (~/.cc-switch)
 pub fn get_app_config_dir() -> PathBuf {
     if let Some(custom) = crate::app_store::get_app_config_dir_override() {
@@ -133,7 +161,12 @@ pub fn write_text_file(path: &Path, data: &str) -> Result<(), AppError> {
 }
 
 /// 原子写入：写入临时文件后 rename 替换，避免半写状态
+///
+/// 目标为符号链接时按 `symlinkWriteMode` 设置处理：默认写入链接指向的文件，
+/// 避免 rename 把指向 dotfiles 仓库的链接替换为普通文件。
 pub fn atomic_write(path: &Path, data: &[u8]) -> Result<(), AppError> {
+    let target = resolve_write_target(path);
+    let path = target.as_path();
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
     }
@@ -190,10 +223,134 @@ pub fn atomic_write(path: &Path, data: &[u8]) -> Result<(), AppError> {
     Ok(())
 }
 
+/// 符号链接解析的最大层数
+const MAX_SYMLINK_DEPTH: usize = 40;
+
+/// 解析原子写入的实际目标文件
+fn resolve_write_target(path: &Path) -> PathBuf {
+    let is_symlink = fs::symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_symlink());
+    if !is_symlink {
+        return path.to_path_buf();
+    }
+    match crate::settings::get_settings().symlink_write_mode {
+        SymlinkWriteMode::Follow => match resolve_symlink(path) {
+            Ok(target) => target,
+            Err(e) => {
+                log::warn!(
+                    "解析符号链接失败，将替换为普通文件: {}: {e}",
+                    path.display()
+                );
+                path.to_path_buf()
+            }
+        },
+        SymlinkWriteMode::Warn => {
+            log::warn!("{} 是符号链接，写入后将被替换为普通文件", path.display());
+            path.to_path_buf()
+        }
+    }
+}
+
+/// 逐级解析符号链接；链接可能悬空（指向尚不存在的文件），此时返回其指向的路径
+fn resolve_symlink(path: &Path) -> std::io::Result<PathBuf> {
+    let mut current = path.to_path_buf();
+    for _ in 0..MAX_SYMLINK_DEPTH {
+        match fs::symlink_metadata(&current) {
+            Ok(meta) if meta.file_type().is_symlink() => {
+                let link = fs::read_link(&current)?;
+                current = match current.parent() {
+                    Some(parent) if link.is_relative() => parent.join(link),
+                    _ => link,
+                };
+            }
+            _ => return Ok(current),
+        }
+    }
+    Err(std::io::Error::other("符号链接层级过多"))
+}
+
+/// 检查能否以原子写入方式写入文件
+///
+/// 原子写入需要在所在目录创建临时文件，目录不存在时检查最近的已存在上级目录（写入时会逐级创建）；
+/// 文件已存在时还需要可写（部分写入直接覆盖原文件）。无权限或位于只读挂载时返回注明路径的多语言错误。
+pub fn ensure_writable(path: &Path) -> Result<(), AppError> {
+    let mut dir = path.parent();
+    while let Some(current) = dir {
+        if current.exists() {
+            break;
+        }
+        dir = current.parent();
+    }
+    if let Some(dir) = dir.filter(|dir| !dir.as_os_str().is_empty()) {
+        let probe = dir.join(format!(".cc-switch-write-test.{}", std::process::id()));
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&probe)
+        {
+            Ok(_) => {
+                let _ = fs::remove_file(&probe);
+            }
+            Err(e) if is_permission_error(&e) => {
+                return Err(AppError::localized_with(
+                    "config.dir_not_writable",
+                    &[("path", dir.display().to_string())],
+                ));
+            }
+            // 其他错误（如残留的探测文件）交给实际写入时报告
+            Err(_) => {}
+        }
+    }
+    if path.is_file() {
+        if let Err(e) = fs::OpenOptions::new().write(true).open(path) {
+            if is_permission_error(&e) {
+                return Err(AppError::localized_with(
+                    "config.file_not_writable",
+                    &[("path", path.display().to_string())],
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_permission_error(err: &std::io::Error) -> bool {
+    matches!(
+        err.kind(),
+        std::io::ErrorKind::PermissionDenied | std::io::ErrorKind::ReadOnlyFilesystem
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(unix)]
+    #[test]
+    fn atomic_write_follows_dangling_relative_symlink() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let link = dir.path().join("settings.json");
+        std::os::unix::fs::symlink("dotfiles/claude/settings.json", &link).unwrap();
+
+        let target = resolve_symlink(&link).expect("resolve");
+        assert_eq!(target, dir.path().join("dotfiles/claude/settings.json"));
+
+        atomic_write(&link, b"{}").expect("write");
+        assert!(fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "{}");
+    }
+
+    #[test]
+    fn ensure_writable_checks_nearest_existing_dir_and_leaves_no_probe() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let target = dir.path().join("missing").join("settings.json");
+        ensure_writable(&target).expect("writable temp dir");
+        assert!(!target.parent().unwrap().exists());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
     #[test]
     fn derive_mcp_path_from_override_preserves_folder_name() {
         let override_dir = PathBuf::from("/tmp/profile/.claude");
@@ -2,28 +2,48 @@
 //!
 //! 提供 SQL 导出/导入和二进制快照备份功能。
 
-use super::{lock_conn, Database, DB_BACKUP_RETAIN};
+use super::export::ExportOptions;
+use super::{lock_conn, Database};
 use crate::config::get_app_config_dir;
 use crate::error::AppError;
+use crate::services::config::{BackupContents, BackupProvider};
 use chrono::Utc;
 use rusqlite::backup::Backup;
 use rusqlite::types::ValueRef;
-use rusqlite::Connection;
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tempfile::NamedTempFile;
 
+/// 数据库二进制备份信息
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbBackupInfo {
+    /// 备份 ID（文件名去掉 `.db` 后缀）
+    pub id: String,
+    pub path: String,
+    pub size: u64,
+    /// 创建时间（Unix 秒，取文件修改时间）
+    pub created_at: i64,
+}
+
+/// 数据库备份目录
+fn db_backup_dir() -> PathBuf {
+    get_app_config_dir().join("backups")
+}
+
+/// 导出 SQL 时仅保留表结构、不导出数据的表（敏感凭据、本机 live 文件状态、MCP 写入记录与设备级设置）
+pub(crate) const SCHEMA_ONLY_TABLES: &[&str] =
+    &["secrets", "live_state", "mcp_live_owned", "device_settings"];
+
+/// 从备份整体恢复时保留本机数据的表（设备级设置随设置一同恢复）
+const RESTORE_KEEP_TABLES: &[&str] = &["secrets", "live_state", "mcp_live_owned"];
+
 impl Database {
     /// 导出为 SQLite 兼容的 SQL 文本
     pub fn export_sql(&self, target_path: &Path) -> Result<(), AppError> {
-        let snapshot = self.snapshot_to_memory()?;
-        let dump = Self::dump_sql(&snapshot)?;
-
-        if let Some(parent) = target_path.parent() {
-            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
-        }
-
-        crate::config::atomic_write(target_path, dump.as_bytes())
+        self.export_sql_with(target_path, &ExportOptions::default())
     }
 
     /// 从 SQL 文件导入，返回生成的备份 ID（若无备份则为空字符串）
@@ -35,20 +55,91 @@ impl Database {
             )));
         }
 
-        let sql_raw = fs::read_to_string(source_path).map_err(|e| AppError::io(source_path, e))?;
-        let sql_content = Self::sanitize_import_sql(&sql_raw);
+        // 在临时数据库执行导入，确保失败不会污染主库
+        let (_temp_file, temp_conn) = Self::load_sql_into_temp(source_path)?;
+        self.replace_with_conn(&temp_conn)
+    }
 
+    /// 备份现有数据库后，用已校验的临时库整体替换主库，返回备份 ID（若无备份则为空字符串）
+    ///
+    /// 导出文件不含 `SCHEMA_ONLY_TABLES` 的数据，替换前先把本机的这些行复制到临时库，
+    /// 避免导入清空凭据（应用锁口令、仓库令牌、OAuth 凭据）与 live 文件记录。
+    pub(crate) fn replace_with_conn(&self, source: &Connection) -> Result<String, AppError> {
         // 导入前备份现有数据库
         let backup_path = self.backup_database_file()?;
 
-        // 在临时数据库执行导入，确保失败不会污染主库
+        // 使用 Backup 将临时库原子写回主库
+        {
+            let mut main_conn = lock_conn!(self.conn);
+            for table in SCHEMA_ONLY_TABLES {
+                Self::copy_table_rows(&main_conn, source, table)?;
+            }
+            let backup = Backup::new(source, &mut main_conn)
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            backup
+                .step(-1)
+                .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        let backup_id = backup_path
+            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap_or_default();
+
+        Ok(backup_id)
+    }
+
+    /// 用 `from` 中的行整体替换 `to` 中同名表的数据（两库表结构一致）
+    fn copy_table_rows(from: &Connection, to: &Connection, table: &str) -> Result<(), AppError> {
+        let mut stmt = from
+            .prepare(&format!("SELECT * FROM \"{table}\""))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let columns: Vec<String> = stmt
+            .column_names()
+            .into_iter()
+            .map(|name| format!("\"{name}\""))
+            .collect();
+        let rows = stmt
+            .query_map([], |row| {
+                (0..columns.len())
+                    .map(|i| row.get::<_, rusqlite::types::Value>(i))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        to.execute(&format!("DELETE FROM \"{table}\""), [])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let placeholders = vec!["?"; columns.len()].join(", ");
+        let mut insert = to
+            .prepare(&format!(
+                "INSERT INTO \"{table}\" ({}) VALUES ({placeholders})",
+                columns.join(", ")
+            ))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        for row in rows {
+            insert
+                .execute(rusqlite::params_from_iter(row))
+                .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// 将 SQL 文件载入临时数据库，补齐表结构并做基础校验
+    ///
+    /// 返回的临时文件需与连接一同持有，释放后文件即被删除。
+    pub(crate) fn load_sql_into_temp(
+        source_path: &Path,
+    ) -> Result<(NamedTempFile, Connection), AppError> {
+        let sql_raw = fs::read_to_string(source_path).map_err(|e| AppError::io(source_path, e))?;
+        let sql_content = Self::sanitize_import_sql(&sql_raw);
+
         let temp_file = NamedTempFile::new().map_err(|e| AppError::IoContext {
             context: "创建临时数据库文件失败".to_string(),
             source: e,
         })?;
-        let temp_path = temp_file.path().to_path_buf();
         let temp_conn =
-            Connection::open(&temp_path).map_err(|e| AppError::Database(e.to_string()))?;
+            Connection::open(temp_file.path()).map_err(|e| AppError::Database(e.to_string()))?;
 
         temp_conn
             .execute_batch(&sql_content)
@@ -59,21 +150,7 @@ impl Database {
         Self::apply_schema_migrations_on_conn(&temp_conn)?;
         Self::validate_basic_state(&temp_conn)?;
 
-        // 使用 Backup 将临时库原子写回主库
-        {
-            let mut main_conn = lock_conn!(self.conn);
-            let backup = Backup::new(&temp_conn, &mut main_conn)
-                .map_err(|e| AppError::Database(e.to_string()))?;
-            backup
-                .step(-1)
-                .map_err(|e| AppError::Database(e.to_string()))?;
-        }
-
-        let backup_id = backup_path
-            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
-            .unwrap_or_default();
-
-        Ok(backup_id)
+        Ok((temp_file, temp_conn))
     }
 
     /// 创建内存快照以避免长时间持有数据库锁
@@ -116,20 +193,19 @@ impl Database {
     }
 
     /// 生成一致性快照备份，返回备份文件路径（不存在主库时返回 None）
-    fn backup_database_file(&self) -> Result<Option<PathBuf>, AppError> {
+    ///
+    /// 备份后按设置中的保留策略（数量、天数）清理旧备份。
+    pub(crate) fn backup_database_file(&self) -> Result<Option<PathBuf>, AppError> {
         let db_path = get_app_config_dir().join("cc-switch.db");
         if !db_path.exists() {
             return Ok(None);
         }
 
-        let backup_dir = db_path
-            .parent()
-            .ok_or_else(|| AppError::Config("无效的数据库路径".to_string()))?
-            .join("backups");
-
+        let backup_dir = db_backup_dir();
         fs::create_dir_all(&backup_dir).map_err(|e| AppError::io(&backup_dir, e))?;
 
-        let backup_id = format!("db_backup_{}", Utc::now().format("%Y%m%d_%H%M%S"));
+        // 毫秒精度，避免恢复时的安全备份与刚创建的备份重名
+        let backup_id = format!("db_backup_{}", Utc::now().format("%Y%m%d_%H%M%S_%3f"));
         let backup_path = backup_dir.join(format!("{backup_id}.db"));
 
         {
@@ -147,7 +223,165 @@ impl Database {
         Ok(Some(backup_path))
     }
 
-    /// 清理旧的数据库备份，保留最新的 N 个
+    /// 列出数据库备份（按创建时间倒序）
+    pub fn list_db_backups() -> Result<Vec<DbBackupInfo>, AppError> {
+        let dir = db_backup_dir();
+        let entries = match fs::read_dir(&dir) {
+            Ok(iter) => iter,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut backups: Vec<DbBackupInfo> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension().map(|ext| ext != "db").unwrap_or(true) {
+                    return None;
+                }
+                let metadata = entry.metadata().ok()?;
+                let created_at = metadata
+                    .modified()
+                    .ok()
+                    .map(|t| chrono::DateTime::<Utc>::from(t).timestamp())
+                    .unwrap_or(0);
+                Some(DbBackupInfo {
+                    id: path.file_stem()?.to_string_lossy().to_string(),
+                    path: path.to_string_lossy().to_string(),
+                    size: metadata.len(),
+                    created_at,
+                })
+            })
+            .collect();
+
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        Ok(backups)
+    }
+
+    /// 从数据库备份恢复，返回恢复前生成的安全备份 ID
+    pub fn restore_db_backup(&self, backup_id: &str) -> Result<String, AppError> {
+        let source_path = Self::db_backup_path(backup_id)?;
+        let source_conn = Connection::open_with_flags(&source_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Self::validate_basic_state(&source_conn)?;
+
+        // 恢复前备份当前数据库，便于撤销
+        let safety_backup = self.backup_database_file()?;
+        self.restore_from_conn(&source_conn)?;
+
+        Ok(safety_backup
+            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap_or_default())
+    }
+
+    /// 用备份库整体替换主库，保留本机的 `RESTORE_KEEP_TABLES`
+    ///
+    /// 备份先复制到临时库并补齐表结构，再写入本机的凭据与 live 记录后整体写回，
+    /// 避免恢复旧备份清除应用锁口令或丢失之后添加的令牌。
+    pub(crate) fn restore_from_conn(&self, source: &Connection) -> Result<(), AppError> {
+        let temp_file = NamedTempFile::new().map_err(|e| AppError::IoContext {
+            context: "创建临时数据库文件失败".to_string(),
+            source: e,
+        })?;
+        let mut staged =
+            Connection::open(temp_file.path()).map_err(|e| AppError::Database(e.to_string()))?;
+        {
+            let backup =
+                Backup::new(source, &mut staged).map_err(|e| AppError::Database(e.to_string()))?;
+            backup
+                .step(-1)
+                .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        // 旧版本备份可能缺少新表/新列
+        Self::create_tables_on_conn(&staged)?;
+        Self::apply_schema_migrations_on_conn(&staged)?;
+
+        let mut main_conn = lock_conn!(self.conn);
+        for table in RESTORE_KEEP_TABLES {
+            Self::copy_table_rows(&main_conn, &staged, table)?;
+        }
+        let backup =
+            Backup::new(&staged, &mut main_conn).map_err(|e| AppError::Database(e.to_string()))?;
+        backup
+            .step(-1)
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 读取数据库备份内容摘要（只读打开，不影响主库）
+    pub fn read_db_backup_contents(backup_id: &str) -> Result<BackupContents, AppError> {
+        let path = Self::db_backup_path(backup_id)?;
+        let conn = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT app_type, id, name, is_current FROM providers
+                 ORDER BY app_type ASC, COALESCE(sort_index, 999999), created_at ASC, id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let providers = stmt
+            .query_map([], |row| {
+                Ok(BackupProvider {
+                    app_type: row.get(0)?,
+                    id: row.get(1)?,
+                    name: row.get(2)?,
+                    is_current: row.get(3)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare("SELECT name FROM mcp_servers ORDER BY name ASC")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mcp_servers = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| AppError::Database(e.to_string()))?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let count = |table: &str| -> Result<usize, AppError> {
+            conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .map(|n| n as usize)
+            .map_err(|e| AppError::Database(e.to_string()))
+        };
+
+        Ok(BackupContents {
+            providers,
+            mcp_servers,
+            prompt_count: count("prompts")?,
+            skill_repo_count: count("skill_repos")?,
+        })
+    }
+
+    /// 最近一次数据库备份时间（无备份时返回 None）
+    pub fn latest_db_backup_at() -> Option<i64> {
+        Self::list_db_backups()
+            .ok()?
+            .first()
+            .map(|backup| backup.created_at)
+    }
+
+    /// 解析备份 ID 对应的文件路径（拒绝路径穿越）
+    pub(crate) fn db_backup_path(backup_id: &str) -> Result<PathBuf, AppError> {
+        let valid = !backup_id.is_empty()
+            && backup_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid {
+            return Err(AppError::InvalidInput(format!("无效的备份 ID: {backup_id}")));
+        }
+        let path = db_backup_dir().join(format!("{backup_id}.db"));
+        if !path.exists() {
+            return Err(AppError::InvalidInput(format!("备份不存在: {backup_id}")));
+        }
+        Ok(path)
+    }
+
+    /// 按保留策略清理旧的数据库备份
     fn cleanup_db_backups(dir: &Path) -> Result<(), AppError> {
         let entries = match fs::read_dir(dir) {
             Ok(iter) => iter
@@ -159,28 +393,36 @@ impl Database {
                         .map(|ext| ext == "db")
                         .unwrap_or(false)
                 })
+                .map(|entry| {
+                    let modified = entry
+                        .metadata()
+                        .and_then(|m| m.modified())
+                        .map(|t| chrono::DateTime::<Utc>::from(t).timestamp())
+                        .unwrap_or(0);
+                    (entry.path(), modified)
+                })
                 .collect::<Vec<_>>(),
             Err(_) => return Ok(()),
         };
 
-        if entries.len() <= DB_BACKUP_RETAIN {
-            return Ok(());
-        }
-
-        let remove_count = entries.len().saturating_sub(DB_BACKUP_RETAIN);
-        let mut sorted = entries;
-        sorted.sort_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok());
-
-        for entry in sorted.into_iter().take(remove_count) {
-            if let Err(err) = fs::remove_file(entry.path()) {
-                log::warn!("删除旧数据库备份失败 {}: {}", entry.path().display(), err);
+        let settings = crate::settings::get_settings();
+        let expired = backups_to_remove(
+            entries,
+            settings.db_backup_retain_count,
+            settings.db_backup_retain_days,
+            Utc::now().timestamp(),
+        );
+
+        for path in expired {
+            if let Err(err) = fs::remove_file(&path) {
+                log::warn!("删除旧数据库备份失败 {}: {}", path.display(), err);
             }
         }
         Ok(())
     }
 
     /// 基础状态校验
-    fn validate_basic_state(conn: &Connection) -> Result<(), AppError> {
+    pub(crate) fn validate_basic_state(conn: &Connection) -> Result<(), AppError> {
         let provider_count: i64 = conn
             .query_row("SELECT COUNT(*) FROM providers", [], |row| row.get(0))
             .map_err(|e| AppError::Database(e.to_string()))?;
@@ -197,7 +439,7 @@ impl Database {
     }
 
     /// 导出数据库为 SQL 文本
-    fn dump_sql(conn: &Connection) -> Result<String, AppError> {
+    pub(crate) fn dump_sql(conn: &Connection) -> Result<String, AppError> {
         let mut output = String::new();
         let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
         let user_version: i64 = conn
@@ -245,6 +487,9 @@ impl Database {
 
         // 导出数据
         for table in tables {
+            if SCHEMA_ONLY_TABLES.contains(&table.as_str()) {
+                continue;
+            }
             let columns = Self::get_table_columns(conn, &table)?;
             if columns.is_empty() {
                 continue;
@@ -283,7 +528,10 @@ impl Database {
     }
 
     /// 获取表的列名列表
-    fn get_table_columns(conn: &Connection, table: &str) -> Result<Vec<String>, AppError> {
+    pub(crate) fn get_table_columns(
+        conn: &Connection,
+        table: &str,
+    ) -> Result<Vec<String>, AppError> {
         let mut stmt = conn
             .prepare(&format!("PRAGMA table_info(\"{table}\")"))
             .map_err(|e| AppError::Database(e.to_string()))?;
@@ -322,3 +570,54 @@ impl Database {
         }
     }
 }
+
+/// 计算需要清理的备份
+///
+/// 超出保留数量或超过保留天数的备份会被清理，但始终保留最新的一个。
+fn backups_to_remove(
+    mut backups: Vec<(PathBuf, i64)>,
+    retain_count: usize,
+    retain_days: u32,
+    now: i64,
+) -> Vec<PathBuf> {
+    backups.sort_by(|a, b| b.1.cmp(&a.1));
+    let max_age = i64::from(retain_days) * 24 * 60 * 60;
+
+    backups
+        .into_iter()
+        .enumerate()
+        .filter(|(index, (_, modified))| {
+            *index > 0
+                && (*index >= retain_count.max(1) || (retain_days > 0 && now - modified > max_age))
+        })
+        .map(|(_, (path, _))| path)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backups_to_remove_applies_count_and_age() {
+        let day = 24 * 60 * 60;
+        let now = 100 * day;
+        let backups = vec![
+            (PathBuf::from("a.db"), now),
+            (PathBuf::from("b.db"), now - day),
+            (PathBuf::from("c.db"), now - 10 * day),
+            (PathBuf::from("d.db"), now - 40 * day),
+        ];
+
+        let removed = backups_to_remove(backups.clone(), 10, 30, now);
+        assert_eq!(removed, vec![PathBuf::from("d.db")]);
+
+        let removed = backups_to_remove(backups.clone(), 2, 0, now);
+        assert_eq!(removed, vec![PathBuf::from("c.db"), PathBuf::from("d.db")]);
+
+        // 即使全部过期也保留最新的一个
+        let removed = backups_to_remove(backups, 10, 1, now + 100 * day);
+        assert_eq!(removed.len(), 3);
+        assert!(!removed.contains(&PathBuf::from("a.db")));
+    }
+}
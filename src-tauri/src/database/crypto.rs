@@ -0,0 +1,178 @@
+//! 敏感凭据加密
+//!
+//! 使用 AES-256-GCM 加密 secrets 表中的数据。
+//! 密钥保存在 `~/.cc-switch/secret.key`（仅所有者可读写），与数据库文件分离存放；
+//! 内存数据库（测试）使用临时密钥。
+
+use crate::error::AppError;
+use base64::prelude::*;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// 密文前缀，用于区分旧版明文数据
+const CIPHER_PREFIX: &str = "enc:v1:";
+const KEY_LEN: usize = 32;
+
+/// 本机加密密钥
+pub(crate) struct SecretKey([u8; KEY_LEN]);
+
+impl SecretKey {
+    /// 生成随机密钥
+    pub(crate) fn generate() -> Result<Self, AppError> {
+        let mut bytes = [0u8; KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| AppError::Config("生成加密密钥失败".to_string()))?;
+        Ok(Self(bytes))
+    }
+
+    /// 读取密钥文件，不存在时生成并写入
+    pub(crate) fn load_or_create(path: &Path) -> Result<Self, AppError> {
+        if path.exists() {
+            return Self::load(path);
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+
+        // 创建时即设置权限为 600（仅所有者可读写），密钥不会以默认权限短暂落盘
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = match options.open(path) {
+            Ok(file) => file,
+            // 其他进程已抢先创建，使用其密钥
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => return Self::load(path),
+            Err(e) => return Err(AppError::io(path, e)),
+        };
+
+        let key = Self::generate()?;
+        let written = file
+            .write_all(BASE64_STANDARD.encode(key.0).as_bytes())
+            .and_then(|_| file.sync_all());
+        if let Err(e) = written {
+            // 不留下半写的密钥文件
+            drop(file);
+            let _ = fs::remove_file(path);
+            return Err(AppError::io(path, e));
+        }
+
+        Ok(key)
+    }
+
+    fn load(path: &Path) -> Result<Self, AppError> {
+        let raw = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+        let bytes = BASE64_STANDARD
+            .decode(raw.trim())
+            .map_err(|e| AppError::Config(format!("密钥文件格式错误: {e}")))?;
+        let key: [u8; KEY_LEN] = bytes
+            .try_into()
+            .map_err(|_| AppError::Config("密钥文件长度错误".to_string()))?;
+        Ok(Self(key))
+    }
+
+    fn cipher(&self) -> Result<LessSafeKey, AppError> {
+        let unbound = UnboundKey::new(&AES_256_GCM, &self.0)
+            .map_err(|_| AppError::Config("加密密钥无效".to_string()))?;
+        Ok(LessSafeKey::new(unbound))
+    }
+
+    /// 加密为 `enc:v1:<base64(nonce || ciphertext || tag)>`
+    pub(crate) fn encrypt(&self, plaintext: &str) -> Result<String, AppError> {
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce_bytes)
+            .map_err(|_| AppError::Config("生成随机数失败".to_string()))?;
+
+        let mut in_out = plaintext.as_bytes().to_vec();
+        self.cipher()?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce_bytes),
+                Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| AppError::Config("加密失败".to_string()))?;
+
+        let mut payload = nonce_bytes.to_vec();
+        payload.extend_from_slice(&in_out);
+        Ok(format!("{CIPHER_PREFIX}{}", BASE64_STANDARD.encode(payload)))
+    }
+
+    /// 解密；无前缀的旧数据按明文返回
+    pub(crate) fn decrypt(&self, stored: &str) -> Result<String, AppError> {
+        let Some(encoded) = stored.strip_prefix(CIPHER_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+
+        let payload = BASE64_STANDARD
+            .decode(encoded)
+            .map_err(|e| AppError::Config(format!("密文格式错误: {e}")))?;
+        if payload.len() < NONCE_LEN {
+            return Err(AppError::Config("密文长度错误".to_string()));
+        }
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let nonce: [u8; NONCE_LEN] = nonce
+            .try_into()
+            .map_err(|_| AppError::Config("密文长度错误".to_string()))?;
+        let mut in_out = ciphertext.to_vec();
+        let plain = self
+            .cipher()?
+            .open_in_place(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut in_out)
+            .map_err(|_| AppError::Config("解密失败：密钥不匹配或数据已损坏".to_string()))?;
+
+        String::from_utf8(plain.to_vec())
+            .map_err(|e| AppError::Config(format!("解密结果不是有效的 UTF-8: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_round_trip_and_plaintext_fallback() {
+        let key = SecretKey::generate().expect("key");
+        let cipher = key.encrypt("refresh-token").expect("encrypt");
+        assert!(cipher.starts_with(CIPHER_PREFIX));
+        assert!(!cipher.contains("refresh-token"));
+        assert_eq!(key.decrypt(&cipher).expect("decrypt"), "refresh-token");
+
+        // 旧版明文数据原样返回
+        assert_eq!(key.decrypt("plain").expect("plain"), "plain");
+
+        // 其他密钥无法解密
+        let other = SecretKey::generate().expect("key");
+        assert!(other.decrypt(&cipher).is_err());
+    }
+
+    #[test]
+    fn load_or_create_persists_key() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("secret.key");
+        let first = SecretKey::load_or_create(&path).expect("create");
+        let second = SecretKey::load_or_create(&path).expect("load");
+        let cipher = first.encrypt("value").expect("encrypt");
+        assert_eq!(second.decrypt(&cipher).expect("decrypt"), "value");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn key_file_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("nested").join("secret.key");
+        SecretKey::load_or_create(&path).expect("create");
+        let mode = fs::metadata(&path).expect("metadata").permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,127 @@
+//! 供应商附件数据访问对象
+//!
+//! 附件内容以 BLOB 存放于 `provider_attachments` 表，列表查询只返回元数据。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::provider_notes::ProviderAttachment;
+use rusqlite::{params, OptionalExtension};
+
+impl Database {
+    /// 新增附件，返回附件 ID
+    pub fn insert_provider_attachment(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        file_name: &str,
+        mime_type: &str,
+        data: &[u8],
+    ) -> Result<i64, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT INTO provider_attachments
+                (provider_id, app_type, file_name, mime_type, size, data, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                provider_id,
+                app_type,
+                file_name,
+                mime_type,
+                data.len() as i64,
+                data,
+                chrono::Utc::now().timestamp(),
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// 获取指定供应商的附件列表（不含内容，按添加时间排序）
+    pub fn get_provider_attachments(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<Vec<ProviderAttachment>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, provider_id, app_type, file_name, mime_type, size, created_at
+                 FROM provider_attachments
+                 WHERE app_type = ?1 AND provider_id = ?2
+                 ORDER BY created_at ASC, id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type, provider_id], |row| {
+                Ok(ProviderAttachment {
+                    id: row.get(0)?,
+                    provider_id: row.get(1)?,
+                    app_type: row.get(2)?,
+                    file_name: row.get(3)?,
+                    mime_type: row.get(4)?,
+                    size: row.get(5)?,
+                    created_at: row.get(6)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 指定供应商已有附件的总大小（字节）
+    pub fn provider_attachments_size(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<i64, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            "SELECT COALESCE(SUM(size), 0) FROM provider_attachments
+             WHERE app_type = ?1 AND provider_id = ?2",
+            params![app_type, provider_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 读取附件内容，返回 (元数据, 内容)
+    pub fn get_provider_attachment_data(
+        &self,
+        id: i64,
+    ) -> Result<Option<(ProviderAttachment, Vec<u8>)>, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            "SELECT id, provider_id, app_type, file_name, mime_type, size, created_at, data
+             FROM provider_attachments WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok((
+                    ProviderAttachment {
+                        id: row.get(0)?,
+                        provider_id: row.get(1)?,
+                        app_type: row.get(2)?,
+                        file_name: row.get(3)?,
+                        mime_type: row.get(4)?,
+                        size: row.get(5)?,
+                        created_at: row.get(6)?,
+                    },
+                    row.get(7)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 删除附件，返回是否存在
+    pub fn delete_provider_attachment(&self, id: i64) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute(
+                "DELETE FROM provider_attachments WHERE id = ?1",
+                params![id],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+}
@@ -0,0 +1,119 @@
+//! 设备级设置数据访问对象
+//!
+//! 每个设置字段一行，写入只更新变化的字段，多个进程同时修改不同字段时不会互相覆盖。
+//! `revision` 单调递增，用于检测其他进程的修改。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use rusqlite::{params, TransactionBehavior};
+use serde_json::{Map, Value};
+
+impl Database {
+    /// 当前设备设置版本号（表为空时为 0）
+    pub fn device_settings_revision(&self) -> Result<i64, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            "SELECT COALESCE(MAX(revision), 0) FROM device_settings",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 读取全部设备设置字段及版本号
+    pub fn get_device_settings(&self) -> Result<(Map<String, Value>, i64), AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare("SELECT key, value, revision FROM device_settings ORDER BY key ASC")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut fields = Map::new();
+        let mut revision = 0;
+        for row in rows {
+            let (key, value, row_revision) = row.map_err(|e| AppError::Database(e.to_string()))?;
+            revision = revision.max(row_revision);
+            match serde_json::from_str(&value) {
+                Ok(value) => {
+                    fields.insert(key, value);
+                }
+                Err(e) => log::warn!("设备设置字段 {key} 解析失败，已忽略: {e}"),
+            }
+        }
+        Ok((fields, revision))
+    }
+
+    /// 写入变化的设备设置字段，返回新的版本号
+    ///
+    /// 使用 IMMEDIATE 事务，保证版本号在多进程间唯一递增。
+    pub fn update_device_settings(&self, changes: &Map<String, Value>) -> Result<i64, AppError> {
+        if changes.is_empty() {
+            return self.device_settings_revision();
+        }
+
+        let mut conn = lock_conn!(self.conn);
+        let tx = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let revision: i64 = tx
+            .query_row(
+                "SELECT COALESCE(MAX(revision), 0) + 1 FROM device_settings",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let now = chrono::Utc::now().timestamp();
+        for (key, value) in changes {
+            tx.execute(
+                "INSERT INTO device_settings (key, value, revision, updated_at)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(key) DO UPDATE SET
+                    value = excluded.value,
+                    revision = excluded.revision,
+                    updated_at = excluded.updated_at",
+                params![key, value.to_string(), revision, now],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(revision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn updates_only_touch_changed_fields() {
+        let db = Database::memory().expect("memory db");
+        assert_eq!(db.device_settings_revision().expect("revision"), 0);
+
+        let mut first = Map::new();
+        first.insert("language".into(), json!("zh"));
+        first.insert("showInTray".into(), json!(true));
+        assert_eq!(db.update_device_settings(&first).expect("update"), 1);
+
+        // 另一个进程只修改了一个字段
+        let mut second = Map::new();
+        second.insert("showInTray".into(), json!(false));
+        assert_eq!(db.update_device_settings(&second).expect("update"), 2);
+
+        let (fields, revision) = db.get_device_settings().expect("read");
+        assert_eq!(revision, 2);
+        assert_eq!(fields.get("language"), Some(&json!("zh")));
+        assert_eq!(fields.get("showInTray"), Some(&json!(false)));
+    }
+}
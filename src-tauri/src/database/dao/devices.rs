@@ -0,0 +1,152 @@
+//! 设备数据访问对象
+//!
+//! 数据库通过云盘在多台设备间同步时，各设备的目录覆盖与当前供应商按设备 ID 分行存储，
+//! 每台设备只写自己的行。
+
+use crate::database::{lock_conn, to_json_string, Database};
+use crate::error::AppError;
+use crate::services::device::DeviceRecord;
+use rusqlite::{params, OptionalExtension, Row};
+
+const DEVICE_COLUMNS: &str =
+    "id, name, hostname, config_dirs, current_providers, last_seen_at, updated_at";
+
+fn device_from_row(row: &Row) -> rusqlite::Result<(DeviceRecord, String, String)> {
+    Ok((
+        DeviceRecord {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            hostname: row.get(2)?,
+            last_seen_at: row.get(5)?,
+            updated_at: row.get(6)?,
+            ..Default::default()
+        },
+        row.get(3)?,
+        row.get(4)?,
+    ))
+}
+
+fn parse_device(
+    (mut device, config_dirs, current_providers): (DeviceRecord, String, String),
+) -> DeviceRecord {
+    device.config_dirs = serde_json::from_str(&config_dirs).unwrap_or_default();
+    device.current_providers = serde_json::from_str(&current_providers).unwrap_or_default();
+    device
+}
+
+impl Database {
+    /// 获取所有设备，按最近活跃时间倒序
+    pub fn list_devices(&self) -> Result<Vec<DeviceRecord>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {DEVICE_COLUMNS} FROM devices ORDER BY last_seen_at DESC, id ASC"
+            ))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let iter = stmt
+            .query_map([], device_from_row)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut devices = Vec::new();
+        for row in iter {
+            devices.push(parse_device(
+                row.map_err(|e| AppError::Database(e.to_string()))?,
+            ));
+        }
+        Ok(devices)
+    }
+
+    /// 获取指定设备
+    pub fn get_device(&self, id: &str) -> Result<Option<DeviceRecord>, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            &format!("SELECT {DEVICE_COLUMNS} FROM devices WHERE id = ?1"),
+            params![id],
+            device_from_row,
+        )
+        .optional()
+        .map(|row| row.map(parse_device))
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 新增或整行更新设备
+    pub fn save_device(&self, device: &DeviceRecord) -> Result<(), AppError> {
+        let config_dirs = to_json_string(&device.config_dirs)?;
+        let current_providers = to_json_string(&device.current_providers)?;
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT INTO devices (id, name, hostname, config_dirs, current_providers, last_seen_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                hostname = excluded.hostname,
+                config_dirs = excluded.config_dirs,
+                current_providers = excluded.current_providers,
+                last_seen_at = excluded.last_seen_at,
+                updated_at = excluded.updated_at",
+            params![
+                device.id,
+                device.name,
+                device.hostname,
+                config_dirs,
+                current_providers,
+                device.last_seen_at,
+                device.updated_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 重命名设备
+    pub fn rename_device(&self, id: &str, name: &str) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute(
+                "UPDATE devices SET name = ?2, updated_at = ?3 WHERE id = ?1",
+                params![id, name, chrono::Utc::now().timestamp()],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+
+    /// 删除设备（不再使用的旧设备）
+    pub fn delete_device(&self, id: &str) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute("DELETE FROM devices WHERE id = ?1", params![id])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn devices_round_trip_overrides() {
+        let db = Database::memory().expect("memory db");
+        let mut device = DeviceRecord {
+            id: "laptop".into(),
+            name: "Laptop".into(),
+            hostname: "laptop.local".into(),
+            last_seen_at: 1,
+            updated_at: 1,
+            ..Default::default()
+        };
+        device
+            .config_dirs
+            .insert("claude".into(), "/Users/me/.claude".into());
+        device
+            .current_providers
+            .insert("claude".into(), "work".into());
+        db.save_device(&device).expect("save");
+
+        assert_eq!(db.get_device("laptop").expect("get"), Some(device));
+        assert!(db.rename_device("laptop", "Work Laptop").expect("rename"));
+        assert_eq!(db.list_devices().expect("list")[0].name, "Work Laptop");
+        assert!(db.delete_device("laptop").expect("delete"));
+        assert!(db.get_device("laptop").expect("get").is_none());
+    }
+}
@@ -0,0 +1,105 @@
+//! Gemini OAuth 账号数据访问对象
+//!
+//! 账号元数据存放于 `gemini_oauth_accounts` 表，
+//! `oauth_creds.json` 快照加密存放于 secrets 表（不随 SQL 导出）。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::gemini_oauth::GeminiOAuthAccount;
+use rusqlite::params;
+
+/// secrets 表中凭据快照的键名
+fn creds_secret_key(id: &str) -> String {
+    format!("gemini_oauth_creds:{id}")
+}
+
+impl Database {
+    /// 获取所有 Gemini OAuth 账号（不含凭据）
+    pub fn get_gemini_oauth_accounts(&self) -> Result<Vec<GeminiOAuthAccount>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, label, email, created_at, updated_at
+                 FROM gemini_oauth_accounts ORDER BY created_at ASC, id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let account_iter = stmt
+            .query_map([], |row| {
+                Ok(GeminiOAuthAccount {
+                    id: row.get(0)?,
+                    label: row.get(1)?,
+                    email: row.get(2)?,
+                    created_at: row.get(3)?,
+                    updated_at: row.get(4)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut accounts = Vec::new();
+        for account_res in account_iter {
+            accounts.push(account_res.map_err(|e| AppError::Database(e.to_string()))?);
+        }
+        Ok(accounts)
+    }
+
+    /// 保存账号元数据与凭据快照
+    pub fn save_gemini_oauth_account(
+        &self,
+        account: &GeminiOAuthAccount,
+        creds: &serde_json::Value,
+    ) -> Result<(), AppError> {
+        let creds_json = crate::database::to_json_string(creds)?;
+        {
+            let conn = lock_conn!(self.conn);
+            conn.execute(
+                "INSERT OR REPLACE INTO gemini_oauth_accounts (id, label, email, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    account.id,
+                    account.label,
+                    account.email,
+                    account.created_at,
+                    account.updated_at
+                ],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        self.set_secret(&creds_secret_key(&account.id), &creds_json)
+    }
+
+    /// 读取账号的凭据快照
+    pub fn get_gemini_oauth_creds(&self, id: &str) -> Result<Option<serde_json::Value>, AppError> {
+        match self.get_secret(&creds_secret_key(id))? {
+            Some(raw) => serde_json::from_str(&raw)
+                .map(Some)
+                .map_err(|e| AppError::Database(format!("Gemini OAuth 凭据解析失败: {e}"))),
+            None => Ok(None),
+        }
+    }
+
+    /// 修改账号显示名称
+    pub fn rename_gemini_oauth_account(&self, id: &str, label: &str) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute(
+                "UPDATE gemini_oauth_accounts SET label = ?1 WHERE id = ?2",
+                params![label, id],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+
+    /// 删除账号及其凭据快照
+    pub fn delete_gemini_oauth_account(&self, id: &str) -> Result<(), AppError> {
+        {
+            let conn = lock_conn!(self.conn);
+            conn.execute(
+                "DELETE FROM gemini_oauth_accounts WHERE id = ?1",
+                params![id],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        self.delete_secret(&creds_secret_key(id))
+    }
+}
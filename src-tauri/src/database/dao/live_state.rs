@@ -0,0 +1,78 @@
+//! Live 文件状态数据访问对象
+//!
+//! 记录 cc-switch 最近一次写入各应用 live 配置文件时的哈希，
+//! 用于检测其他工具对配置文件的外部修改。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use rusqlite::params;
+
+/// 单个 live 文件的写入记录
+#[derive(Debug, Clone)]
+pub struct LiveFileState {
+    pub app_type: String,
+    pub path: String,
+    pub hash: String,
+    pub written_at: i64,
+}
+
+impl Database {
+    /// 获取所有 live 文件写入记录
+    pub fn get_live_file_states(&self) -> Result<Vec<LiveFileState>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT app_type, path, hash, written_at FROM live_state
+                 ORDER BY app_type ASC, path ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let state_iter = stmt
+            .query_map([], |row| {
+                Ok(LiveFileState {
+                    app_type: row.get(0)?,
+                    path: row.get(1)?,
+                    hash: row.get(2)?,
+                    written_at: row.get(3)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut states = Vec::new();
+        for state_res in state_iter {
+            states.push(state_res.map_err(|e| AppError::Database(e.to_string()))?);
+        }
+        Ok(states)
+    }
+
+    /// 替换指定应用的 live 文件写入记录
+    ///
+    /// `files` 为 (路径, 哈希) 列表；不存在的文件不记录。
+    pub fn set_live_file_states(
+        &self,
+        app_type: &str,
+        files: &[(String, String)],
+        written_at: i64,
+    ) -> Result<(), AppError> {
+        let mut conn = lock_conn!(self.conn);
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        tx.execute(
+            "DELETE FROM live_state WHERE app_type = ?1",
+            params![app_type],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        for (path, hash) in files {
+            tx.execute(
+                "INSERT INTO live_state (app_type, path, hash, written_at) VALUES (?1, ?2, ?3, ?4)",
+                params![app_type, path, hash, written_at],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
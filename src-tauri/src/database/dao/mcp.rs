@@ -1,6 +1,6 @@
 //! MCP 服务器数据访问对象
 //!
-//! 提供 MCP 服务器的 CRUD 操作。
+//! 提供 MCP 服务器的 CRUD 操作，以及 cc-switch 写入各应用 live 配置的服务器记录。
 
 use crate::app_config::{McpApps, McpServer};
 use crate::database::{lock_conn, Database};
@@ -13,7 +13,7 @@ impl Database {
     pub fn get_all_mcp_servers(&self) -> Result<IndexMap<String, McpServer>, AppError> {
         let conn = lock_conn!(self.conn);
         let mut stmt = conn.prepare(
-            "SELECT id, name, server_config, description, homepage, docs, tags, enabled_claude, enabled_codex, enabled_gemini, enabled_grok, enabled_qwen
+            "SELECT id, name, server_config, description, homepage, docs, tags, enabled_claude, enabled_codex, enabled_gemini, enabled_grok, enabled_qwen, app_overrides, enabled_claude_desktop
              FROM mcp_servers
              ORDER BY name ASC, id ASC"
         ).map_err(|e| AppError::Database(e.to_string()))?;
@@ -32,9 +32,12 @@ impl Database {
                 let enabled_gemini: bool = row.get(9)?;
                 let enabled_grok: bool = row.get(10)?;
                 let enabled_qwen: bool = row.get(11)?;
+                let app_overrides_str: String = row.get(12)?;
+                let enabled_claude_desktop: bool = row.get(13)?;
 
                 let server = serde_json::from_str(&server_config_str).unwrap_or_default();
                 let tags = serde_json::from_str(&tags_str).unwrap_or_default();
+                let app_overrides = serde_json::from_str(&app_overrides_str).unwrap_or_default();
 
                 Ok((
                     id.clone(),
@@ -48,7 +51,9 @@ impl Database {
                             gemini: enabled_gemini,
                             grok: enabled_grok,
                             qwen: enabled_qwen,
+                            claude_desktop: enabled_claude_desktop,
                         },
+                        app_overrides,
                         description,
                         homepage,
                         docs,
@@ -72,8 +77,9 @@ impl Database {
         conn.execute(
             "INSERT OR REPLACE INTO mcp_servers (
                 id, name, server_config, description, homepage, docs, tags,
-                enabled_claude, enabled_codex, enabled_gemini, enabled_grok, enabled_qwen
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                enabled_claude, enabled_codex, enabled_gemini, enabled_grok, enabled_qwen,
+                app_overrides, enabled_claude_desktop
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
             params![
                 server.id,
                 server.name,
@@ -87,6 +93,8 @@ impl Database {
                 server.apps.gemini,
                 server.apps.grok,
                 server.apps.qwen,
+                serde_json::to_string(&server.app_overrides).unwrap(),
+                server.apps.claude_desktop,
             ],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
@@ -100,4 +108,43 @@ impl Database {
             .map_err(|e| AppError::Database(e.to_string()))?;
         Ok(())
     }
+
+    /// 记录 cc-switch 已将服务器写入指定应用的 live 配置
+    pub fn mark_mcp_live_owned(&self, app_type: &str, server_id: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT OR REPLACE INTO mcp_live_owned (app_type, server_id, written_at)
+             VALUES (?1, ?2, ?3)",
+            params![app_type, server_id, chrono::Utc::now().timestamp()],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 获取 cc-switch 曾写入指定应用 live 配置的服务器 ID
+    pub fn get_mcp_live_owned(&self, app_type: &str) -> Result<Vec<String>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT server_id FROM mcp_live_owned WHERE app_type = ?1 ORDER BY server_id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let ids = stmt
+            .query_map(params![app_type], |row| row.get(0))
+            .map_err(|e| AppError::Database(e.to_string()))?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(ids)
+    }
+
+    /// 移除指定应用的服务器写入记录
+    pub fn unmark_mcp_live_owned(&self, app_type: &str, server_id: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "DELETE FROM mcp_live_owned WHERE app_type = ?1 AND server_id = ?2",
+            params![app_type, server_id],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
 }
@@ -2,10 +2,20 @@
 //!
 //! 提供各类数据的 CRUD 操作。
 
+mod attachments;
+mod devices;
+mod device_settings;
+mod gemini_oauth;
+mod live_state;
 mod mcp;
+mod projects;
+mod prompt_sets;
 mod prompts;
 mod providers;
+mod secrets;
 mod settings;
 mod skills;
+mod switch_events;
+mod usage;
 
 // 所有 DAO 方法都通过 Database impl 提供，无需单独导出
@@ -0,0 +1,112 @@
+//! 项目数据访问对象
+//!
+//! 项目目录与各应用期望供应商的映射（`projects` + `project_providers`）。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::project::Project;
+use indexmap::IndexMap;
+use rusqlite::params;
+
+impl Database {
+    /// 获取所有项目（按名称排序，含各应用的供应商映射）
+    pub fn get_projects(&self) -> Result<IndexMap<String, Project>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT path, name, created_at, updated_at FROM projects
+                 ORDER BY name COLLATE NOCASE ASC, path ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let project_iter = stmt
+            .query_map([], |row| {
+                Ok(Project {
+                    path: row.get(0)?,
+                    name: row.get(1)?,
+                    providers: IndexMap::new(),
+                    created_at: row.get(2)?,
+                    updated_at: row.get(3)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut projects = IndexMap::new();
+        for project_res in project_iter {
+            let project = project_res.map_err(|e| AppError::Database(e.to_string()))?;
+            projects.insert(project.path.clone(), project);
+        }
+
+        let mut provider_stmt = conn
+            .prepare(
+                "SELECT project_path, app_type, provider_id FROM project_providers
+                 ORDER BY project_path ASC, app_type ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let provider_iter = provider_stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        for provider_res in provider_iter {
+            let (path, app_type, provider_id) =
+                provider_res.map_err(|e| AppError::Database(e.to_string()))?;
+            if let Some(project) = projects.get_mut(&path) {
+                project.providers.insert(app_type, provider_id);
+            }
+        }
+
+        Ok(projects)
+    }
+
+    /// 保存项目（供应商映射整体替换）
+    pub fn save_project(&self, project: &Project) -> Result<(), AppError> {
+        let mut conn = lock_conn!(self.conn);
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        tx.execute(
+            "INSERT INTO projects (path, name, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(path) DO UPDATE SET
+                name = excluded.name,
+                updated_at = excluded.updated_at",
+            params![
+                project.path,
+                project.name,
+                project.created_at,
+                project.updated_at
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        tx.execute(
+            "DELETE FROM project_providers WHERE project_path = ?1",
+            params![project.path],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        for (app_type, provider_id) in &project.providers {
+            tx.execute(
+                "INSERT INTO project_providers (project_path, app_type, provider_id)
+                 VALUES (?1, ?2, ?3)",
+                params![project.path, app_type, provider_id],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 删除项目（供应商映射随之删除）
+    pub fn delete_project(&self, path: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute("DELETE FROM projects WHERE path = ?1", params![path])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+//! 提示词组数据访问对象
+//!
+//! 提供提示词组（PromptSet）及其成员顺序的 CRUD 操作。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::prompt::{PromptSet, PromptSetMode};
+use indexmap::IndexMap;
+use rusqlite::params;
+
+impl Database {
+    /// 获取指定应用类型的所有提示词组（含按顺序排列的成员）
+    pub fn get_prompt_sets(&self, app_type: &str) -> Result<IndexMap<String, PromptSet>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, description, mode, selected_prompt_id, enabled, created_at, updated_at
+                 FROM prompt_sets WHERE app_type = ?1
+                 ORDER BY created_at ASC, id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let set_iter = stmt
+            .query_map(params![app_type], |row| {
+                let mode: String = row.get(3)?;
+                Ok(PromptSet {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    description: row.get(2)?,
+                    mode: PromptSetMode::parse(&mode),
+                    prompt_ids: Vec::new(),
+                    selected_prompt_id: row.get(4)?,
+                    enabled: row.get(5)?,
+                    created_at: row.get(6)?,
+                    updated_at: row.get(7)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut sets = IndexMap::new();
+        for set_res in set_iter {
+            let set = set_res.map_err(|e| AppError::Database(e.to_string()))?;
+            sets.insert(set.id.clone(), set);
+        }
+
+        let mut member_stmt = conn
+            .prepare(
+                "SELECT set_id, prompt_id FROM prompt_set_members
+                 WHERE app_type = ?1
+                 ORDER BY set_id ASC, position ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let member_iter = member_stmt
+            .query_map(params![app_type], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        for member_res in member_iter {
+            let (set_id, prompt_id) = member_res.map_err(|e| AppError::Database(e.to_string()))?;
+            if let Some(set) = sets.get_mut(&set_id) {
+                set.prompt_ids.push(prompt_id);
+            }
+        }
+
+        Ok(sets)
+    }
+
+    /// 保存提示词组（成员按 `prompt_ids` 顺序整体替换）
+    pub fn save_prompt_set(&self, app_type: &str, set: &PromptSet) -> Result<(), AppError> {
+        let mut conn = lock_conn!(self.conn);
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        tx.execute(
+            "INSERT INTO prompt_sets (
+                id, app_type, name, description, mode, selected_prompt_id, enabled, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            ON CONFLICT(id, app_type) DO UPDATE SET
+                name = excluded.name,
+                description = excluded.description,
+                mode = excluded.mode,
+                selected_prompt_id = excluded.selected_prompt_id,
+                enabled = excluded.enabled,
+                created_at = excluded.created_at,
+                updated_at = excluded.updated_at",
+            params![
+                set.id,
+                app_type,
+                set.name,
+                set.description,
+                set.mode.as_str(),
+                set.selected_prompt_id,
+                set.enabled,
+                set.created_at,
+                set.updated_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        tx.execute(
+            "DELETE FROM prompt_set_members WHERE set_id = ?1 AND app_type = ?2",
+            params![set.id, app_type],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        // 跳过已删除的提示词（成员外键引用 prompts）
+        for (position, prompt_id) in set.prompt_ids.iter().enumerate() {
+            tx.execute(
+                "INSERT OR IGNORE INTO prompt_set_members (set_id, app_type, prompt_id, position)
+                 SELECT ?1, ?2, ?3, ?4
+                 WHERE EXISTS (SELECT 1 FROM prompts WHERE id = ?3 AND app_type = ?2)",
+                params![set.id, app_type, prompt_id, position as i64],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 删除提示词组（成员随之删除）
+    pub fn delete_prompt_set(&self, app_type: &str, id: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "DELETE FROM prompt_sets WHERE id = ?1 AND app_type = ?2",
+            params![id, app_type],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 将指定应用的所有提示词组标记为未启用
+    pub fn clear_active_prompt_set(&self, app_type: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "UPDATE prompt_sets SET enabled = 0 WHERE app_type = ?1",
+            params![app_type],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 从所有提示词组中移除指定提示词
+    pub fn remove_prompt_from_sets(&self, app_type: &str, prompt_id: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "DELETE FROM prompt_set_members WHERE app_type = ?1 AND prompt_id = ?2",
+            params![app_type, prompt_id],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
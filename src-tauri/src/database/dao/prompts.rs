@@ -14,7 +14,8 @@ impl Database {
         let conn = lock_conn!(self.conn);
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, content, description, enabled, created_at, updated_at
+                "SELECT id, name, content, description, enabled, created_at, updated_at,
+                    tags, target_apps, apps
              FROM prompts WHERE app_type = ?1
              ORDER BY created_at ASC, id ASC",
             )
@@ -29,6 +30,9 @@ impl Database {
                 let enabled: bool = row.get(4)?;
                 let created_at: Option<i64> = row.get(5)?;
                 let updated_at: Option<i64> = row.get(6)?;
+                let tags: String = row.get(7)?;
+                let target_apps: String = row.get(8)?;
+                let apps: String = row.get(9)?;
 
                 Ok((
                     id.clone(),
@@ -38,6 +42,9 @@ impl Database {
                         content,
                         description,
                         enabled,
+                        tags: serde_json::from_str(&tags).unwrap_or_default(),
+                        target_apps: serde_json::from_str(&target_apps).unwrap_or_default(),
+                        apps: serde_json::from_str(&apps).unwrap_or_default(),
                         created_at,
                         updated_at,
                     },
@@ -54,12 +61,25 @@ impl Database {
     }
 
     /// 保存提示词
+    ///
+    /// 使用 UPSERT 而非 REPLACE：REPLACE 会先删除旧行，级联删除其提示词组成员。
     pub fn save_prompt(&self, app_type: &str, prompt: &Prompt) -> Result<(), AppError> {
         let conn = lock_conn!(self.conn);
         conn.execute(
-            "INSERT OR REPLACE INTO prompts (
-                id, app_type, name, content, description, enabled, created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO prompts (
+                id, app_type, name, content, description, enabled, created_at, updated_at,
+                tags, target_apps, apps
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            ON CONFLICT (id, app_type) DO UPDATE SET
+                name = excluded.name,
+                content = excluded.content,
+                description = excluded.description,
+                enabled = excluded.enabled,
+                created_at = excluded.created_at,
+                updated_at = excluded.updated_at,
+                tags = excluded.tags,
+                target_apps = excluded.target_apps,
+                apps = excluded.apps",
             params![
                 prompt.id,
                 app_type,
@@ -69,6 +89,9 @@ impl Database {
                 prompt.enabled,
                 prompt.created_at,
                 prompt.updated_at,
+                serde_json::to_string(&prompt.tags).unwrap(),
+                serde_json::to_string(&prompt.target_apps).unwrap(),
+                serde_json::to_string(&prompt.apps).unwrap(),
             ],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
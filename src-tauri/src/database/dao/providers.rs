@@ -2,9 +2,9 @@
 //!
 //! 提供供应商（Provider）的 CRUD 操作。
 
-use crate::database::{lock_conn, Database};
+use crate::database::{lock_conn, to_json_string, Database};
 use crate::error::AppError;
-use crate::provider::{Provider, ProviderMeta};
+use crate::provider::{Provider, ProviderGroup, ProviderMeta};
 use indexmap::IndexMap;
 use rusqlite::params;
 use std::collections::HashMap;
@@ -21,11 +21,11 @@ impl Database {
         let has_duplicated_fields = conn.prepare("SELECT is_duplicated FROM providers LIMIT 1").is_ok();
         
         let sql = if has_duplicated_fields {
-            "SELECT id, name, settings_config, website_url, category, created_at, sort_index, notes, icon, icon_color, meta, is_pinned, is_duplicated, is_edited_after_duplication
+            "SELECT id, name, settings_config, website_url, category, created_at, sort_index, notes, icon, icon_color, meta, is_pinned, tags, is_duplicated, is_edited_after_duplication
              FROM providers WHERE app_type = ?1
              ORDER BY is_pinned DESC, COALESCE(sort_index, 999999), created_at ASC, id ASC"
         } else {
-            "SELECT id, name, settings_config, website_url, category, created_at, sort_index, notes, icon, icon_color, meta, is_pinned
+            "SELECT id, name, settings_config, website_url, category, created_at, sort_index, notes, icon, icon_color, meta, is_pinned, tags
              FROM providers WHERE app_type = ?1
              ORDER BY is_pinned DESC, COALESCE(sort_index, 999999), created_at ASC, id ASC"
         };
@@ -47,11 +47,12 @@ impl Database {
                 let icon_color: Option<String> = row.get(9)?;
                 let meta_str: String = row.get(10)?;
                 let is_pinned: bool = row.get(11)?;
+                let tags_str: String = row.get(12)?;
                 
                 // 根据是否有这些字段来获取值
                 let (is_duplicated, is_edited_after_duplication) = if has_duplicated_fields {
-                    let is_dup: Option<bool> = row.get(12)?;
-                    let is_edited: Option<bool> = row.get(13)?;
+                    let is_dup: Option<bool> = row.get(13)?;
+                    let is_edited: Option<bool> = row.get(14)?;
                     (is_dup, is_edited)
                 } else {
                     (None, None)
@@ -60,6 +61,7 @@ impl Database {
                 let settings_config =
                     serde_json::from_str(&settings_config_str).unwrap_or(serde_json::Value::Null);
                 let meta: ProviderMeta = serde_json::from_str(&meta_str).unwrap_or_default();
+                let tags: Vec<String> = serde_json::from_str(&tags_str).unwrap_or_default();
 
                 Ok((
                     id,
@@ -78,6 +80,7 @@ impl Database {
                         is_pinned,
                         is_duplicated,
                         is_edited_after_duplication,
+                        tags,
                     },
                 ))
             })
@@ -125,6 +128,18 @@ impl Database {
         Ok(providers)
     }
 
+    /// 获取按分类分组的供应商
+    ///
+    /// 分组顺序取自 `category_order` 设置，组内沿用 [`Self::get_all_providers`] 的排序。
+    pub fn get_grouped_providers(&self, app_type: &str) -> Result<Vec<ProviderGroup>, AppError> {
+        let category_order = self.get_category_order(app_type)?;
+        let providers = self.get_all_providers(app_type)?;
+        Ok(ProviderGroup::group(
+            providers.into_values(),
+            &category_order,
+        ))
+    }
+
     /// 获取当前激活的供应商 ID
     pub fn get_current_provider(&self, app_type: &str) -> Result<Option<String>, AppError> {
         let conn = lock_conn!(self.conn);
@@ -192,8 +207,9 @@ impl Database {
                         is_current = ?11,
                         is_pinned = ?12,
                         is_duplicated = ?13,
-                        is_edited_after_duplication = ?14
-                    WHERE id = ?15 AND app_type = ?16",
+                        is_edited_after_duplication = ?14,
+                        tags = ?15
+                    WHERE id = ?16 AND app_type = ?17",
                     params![
                         provider.name,
                         serde_json::to_string(&provider.settings_config).unwrap(),
@@ -209,6 +225,7 @@ impl Database {
                         provider.is_pinned,
                         provider.is_duplicated,
                         provider.is_edited_after_duplication,
+                        to_json_string(&provider.tags)?,
                         provider.id,
                         app_type,
                     ],
@@ -229,8 +246,9 @@ impl Database {
                         icon_color = ?9,
                         meta = ?10,
                         is_current = ?11,
-                        is_pinned = ?12
-                    WHERE id = ?13 AND app_type = ?14",
+                        is_pinned = ?12,
+                        tags = ?13
+                    WHERE id = ?14 AND app_type = ?15",
                     params![
                         provider.name,
                         serde_json::to_string(&provider.settings_config).unwrap(),
@@ -244,6 +262,7 @@ impl Database {
                         serde_json::to_string(&meta_clone).unwrap(),
                         is_current,
                         provider.is_pinned,
+                        to_json_string(&provider.tags)?,
                         provider.id,
                         app_type,
                     ],
@@ -256,8 +275,8 @@ impl Database {
                 tx.execute(
                     "INSERT INTO providers (
                         id, app_type, name, settings_config, website_url, category,
-                        created_at, sort_index, notes, icon, icon_color, meta, is_current, is_pinned, is_duplicated, is_edited_after_duplication
-                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                        created_at, sort_index, notes, icon, icon_color, meta, is_current, is_pinned, is_duplicated, is_edited_after_duplication, tags
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
                     params![
                         provider.id,
                         app_type,
@@ -275,6 +294,7 @@ impl Database {
                         provider.is_pinned,
                         provider.is_duplicated,
                         provider.is_edited_after_duplication,
+                        to_json_string(&provider.tags)?,
                     ],
                 )
                 .map_err(|e| AppError::Database(e.to_string()))?;
@@ -283,8 +303,8 @@ impl Database {
                 tx.execute(
                     "INSERT INTO providers (
                         id, app_type, name, settings_config, website_url, category,
-                        created_at, sort_index, notes, icon, icon_color, meta, is_current, is_pinned
-                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                        created_at, sort_index, notes, icon, icon_color, meta, is_current, is_pinned, tags
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
                     params![
                         provider.id,
                         app_type,
@@ -300,6 +320,7 @@ impl Database {
                         serde_json::to_string(&meta_clone).unwrap(),
                         is_current,
                         provider.is_pinned,
+                        to_json_string(&provider.tags)?,
                     ],
                 )
                 .map_err(|e| AppError::Database(e.to_string()))?;
@@ -356,6 +377,42 @@ impl Database {
         Ok(())
     }
 
+    /// 设置供应商标签
+    pub fn set_provider_tags(
+        &self,
+        app_type: &str,
+        id: &str,
+        tags: &[String],
+    ) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute(
+                "UPDATE providers SET tags = ?1 WHERE id = ?2 AND app_type = ?3",
+                params![to_json_string(&tags)?, id, app_type],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+
+    /// 设置供应商分类（None 为未分类），可同时更新排序索引
+    pub fn set_provider_category(
+        &self,
+        app_type: &str,
+        id: &str,
+        category: Option<&str>,
+        sort_index: Option<usize>,
+    ) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute(
+                "UPDATE providers SET category = ?1, sort_index = COALESCE(?2, sort_index)
+                 WHERE id = ?3 AND app_type = ?4",
+                params![category, sort_index.map(|i| i as i64), id, app_type],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+
     /// 添加自定义端点
     pub fn add_custom_endpoint(
         &self,
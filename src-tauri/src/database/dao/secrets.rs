@@ -0,0 +1,50 @@
+//! 敏感凭据数据访问对象
+//!
+//! 提供访问令牌等敏感数据的键值存储。
+//! 值以 AES-256-GCM 加密后落库；`secrets` 表的数据不会出现在 SQL 导出中，
+//! 也不会通过普通设置接口暴露给前端。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use rusqlite::params;
+
+impl Database {
+    /// 获取凭据
+    pub fn get_secret(&self, key: &str) -> Result<Option<String>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare("SELECT value FROM secrets WHERE key = ?1")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut rows = stmt
+            .query(params![key])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        if let Some(row) = rows.next().map_err(|e| AppError::Database(e.to_string()))? {
+            let stored: String = row.get(0).map_err(|e| AppError::Database(e.to_string()))?;
+            Ok(Some(self.secret_key.decrypt(&stored)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// 写入凭据（覆盖已有值）
+    pub fn set_secret(&self, key: &str, value: &str) -> Result<(), AppError> {
+        let encrypted = self.secret_key.encrypt(value)?;
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT OR REPLACE INTO secrets (key, value) VALUES (?1, ?2)",
+            params![key, encrypted],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 删除凭据
+    pub fn delete_secret(&self, key: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute("DELETE FROM secrets WHERE key = ?1", params![key])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! 提供键值对形式的通用设置存储。
 
-use crate::database::{lock_conn, Database};
+use crate::database::{lock_conn, to_json_string, Database};
 use crate::error::AppError;
 use rusqlite::params;
 
@@ -62,4 +62,22 @@ impl Database {
             Ok(())
         }
     }
+
+    // --- 供应商分类排序 ---
+
+    /// 获取供应商分类的显示顺序
+    pub fn get_category_order(&self, app_type: &str) -> Result<Vec<String>, AppError> {
+        Ok(self
+            .get_setting(&format!("category_order_{app_type}"))?
+            .and_then(|value| serde_json::from_str(&value).ok())
+            .unwrap_or_default())
+    }
+
+    /// 保存供应商分类的显示顺序
+    pub fn set_category_order(&self, app_type: &str, order: &[String]) -> Result<(), AppError> {
+        self.set_setting(
+            &format!("category_order_{app_type}"),
+            &to_json_string(&order)?,
+        )
+    }
 }
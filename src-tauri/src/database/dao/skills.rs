@@ -4,7 +4,7 @@
 
 use crate::database::{lock_conn, Database};
 use crate::error::AppError;
-use crate::services::skill::{SkillRepo, SkillState};
+use crate::services::skill::{SkillRepo, SkillRepoProvider, SkillState};
 use indexmap::IndexMap;
 use rusqlite::params;
 
@@ -54,52 +54,97 @@ impl Database {
         Ok(())
     }
 
-    /// 获取所有 Skill 仓库
+    /// 获取所有 Skill 仓库（附带 secrets 表中的访问令牌）
     pub fn get_skill_repos(&self) -> Result<Vec<SkillRepo>, AppError> {
         let conn = lock_conn!(self.conn);
+        // 令牌键名需与 SkillRepo::token_secret_key 保持一致
         let mut stmt = conn
             .prepare(
-                "SELECT owner, name, branch, enabled FROM skill_repos ORDER BY owner ASC, name ASC",
+                "SELECT r.owner, r.name, r.branch, r.enabled, r.base_url, s.value, r.provider
+                 FROM skill_repos r
+                 LEFT JOIN secrets s ON s.key = 'skill_repo_token:' || r.owner || '/' || r.name
+                 ORDER BY r.owner ASC, r.name ASC",
             )
             .map_err(|e| AppError::Database(e.to_string()))?;
 
         let repo_iter = stmt
             .query_map([], |row| {
+                let token: Option<String> = row.get(5)?;
+                let provider: String = row.get(6)?;
                 Ok(SkillRepo {
                     owner: row.get(0)?,
                     name: row.get(1)?,
                     branch: row.get(2)?,
                     enabled: row.get(3)?,
+                    base_url: row.get(4)?,
+                    provider: provider.parse::<SkillRepoProvider>().unwrap_or_default(),
+                    has_token: token.is_some(),
+                    token,
                 })
             })
             .map_err(|e| AppError::Database(e.to_string()))?;
 
         let mut repos = Vec::new();
         for repo_res in repo_iter {
-            repos.push(repo_res.map_err(|e| AppError::Database(e.to_string()))?);
+            let mut repo = repo_res.map_err(|e| AppError::Database(e.to_string()))?;
+            if let Some(stored) = repo.token.take() {
+                repo.token = Some(self.secret_key.decrypt(&stored)?);
+            }
+            repos.push(repo);
         }
         Ok(repos)
     }
 
     /// 保存 Skill 仓库
+    ///
+    /// `token` 为空时保留已有令牌；清除令牌请使用 `set_skill_repo_token(.., None)`。
     pub fn save_skill_repo(&self, repo: &SkillRepo) -> Result<(), AppError> {
-        let conn = lock_conn!(self.conn);
-        conn.execute(
-            "INSERT OR REPLACE INTO skill_repos (owner, name, branch, enabled) VALUES (?1, ?2, ?3, ?4)",
-            params![repo.owner, repo.name, repo.branch, repo.enabled],
-        ).map_err(|e| AppError::Database(e.to_string()))?;
+        {
+            let conn = lock_conn!(self.conn);
+            conn.execute(
+                "INSERT OR REPLACE INTO skill_repos (owner, name, branch, enabled, base_url, provider) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    repo.owner,
+                    repo.name,
+                    repo.branch,
+                    repo.enabled,
+                    repo.base_url,
+                    repo.provider.as_str()
+                ],
+            ).map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        if let Some(token) = repo.token.as_deref().filter(|t| !t.trim().is_empty()) {
+            self.set_skill_repo_token(&repo.owner, &repo.name, Some(token))?;
+        }
         Ok(())
     }
 
-    /// 删除 Skill 仓库
+    /// 设置或清除 Skill 仓库的访问令牌
+    pub fn set_skill_repo_token(
+        &self,
+        owner: &str,
+        name: &str,
+        token: Option<&str>,
+    ) -> Result<(), AppError> {
+        let key = SkillRepo::token_secret_key(owner, name);
+        match token.map(str::trim).filter(|t| !t.is_empty()) {
+            Some(token) => self.set_secret(&key, token),
+            None => self.delete_secret(&key),
+        }
+    }
+
+    /// 删除 Skill 仓库（同时删除其访问令牌）
     pub fn delete_skill_repo(&self, owner: &str, name: &str) -> Result<(), AppError> {
-        let conn = lock_conn!(self.conn);
-        conn.execute(
-            "DELETE FROM skill_repos WHERE owner = ?1 AND name = ?2",
-            params![owner, name],
-        )
-        .map_err(|e| AppError::Database(e.to_string()))?;
-        Ok(())
+        {
+            let conn = lock_conn!(self.conn);
+            conn.execute(
+                "DELETE FROM skill_repos WHERE owner = ?1 AND name = ?2",
+                params![owner, name],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        self.delete_secret(&SkillRepo::token_secret_key(owner, name))
     }
 
     /// 初始化默认的 Skill 仓库（首次启动时调用）
@@ -0,0 +1,77 @@
+//! 切换记录数据访问对象
+//!
+//! 记录每次供应商切换，供使用统计计算切换次数与累计使用时长。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::stats::SwitchEvent;
+use rusqlite::params;
+
+impl Database {
+    /// 追加一条切换记录
+    pub fn insert_switch_event(&self, event: &SwitchEvent) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT INTO switch_events (app_type, provider_id, switched_at) VALUES (?1, ?2, ?3)",
+            params![event.app_type, event.provider_id, event.switched_at],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 获取自 `since` 起的切换记录（按时间升序）
+    ///
+    /// 额外包含每个应用在 `since` 之前的最后一条记录，用于计算区间起点仍在使用的供应商。
+    pub fn get_switch_events_since(&self, since: i64) -> Result<Vec<SwitchEvent>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT app_type, provider_id, switched_at FROM (
+                     SELECT id, app_type, provider_id, switched_at
+                     FROM switch_events WHERE switched_at >= ?1
+                     UNION ALL
+                     SELECT id, app_type, provider_id, switched_at
+                     FROM switch_events e
+                     WHERE switched_at < ?1 AND id = (
+                         SELECT id FROM switch_events
+                         WHERE app_type = e.app_type AND switched_at < ?1
+                         ORDER BY switched_at DESC, id DESC LIMIT 1
+                     )
+                 )
+                 ORDER BY switched_at ASC, id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let event_iter = stmt
+            .query_map(params![since], |row| {
+                Ok(SwitchEvent {
+                    app_type: row.get(0)?,
+                    provider_id: row.get(1)?,
+                    switched_at: row.get(2)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut events = Vec::new();
+        for event_res in event_iter {
+            events.push(event_res.map_err(|e| AppError::Database(e.to_string()))?);
+        }
+        Ok(events)
+    }
+
+    /// 统计切换到指定供应商的次数，返回 (次数, 最近切换时间)
+    pub fn count_switch_events(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<(u32, Option<i64>), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            "SELECT COUNT(*), MAX(switched_at) FROM switch_events
+             WHERE app_type = ?1 AND provider_id = ?2",
+            params![app_type, provider_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
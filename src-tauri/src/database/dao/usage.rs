@@ -0,0 +1,151 @@
+//! 用量记录数据访问对象
+//!
+//! 提供用量脚本结果的累计存储与按时间段汇总。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::budget::UsageRecord;
+use rusqlite::{params, Row};
+
+const USAGE_COLUMNS: &str =
+    "provider_id, app_type, recorded_at, tokens, cost, raw, model, input_tokens, output_tokens";
+
+fn usage_record_from_row(row: &Row) -> rusqlite::Result<UsageRecord> {
+    Ok(UsageRecord {
+        provider_id: row.get(0)?,
+        app_type: row.get(1)?,
+        recorded_at: row.get(2)?,
+        tokens: row.get(3)?,
+        cost: row.get(4)?,
+        raw: row.get(5)?,
+        model: row.get(6)?,
+        input_tokens: row.get(7)?,
+        output_tokens: row.get(8)?,
+    })
+}
+
+impl Database {
+    /// 追加一条用量记录
+    pub fn insert_usage_record(&self, record: &UsageRecord) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT INTO usage_history
+                (provider_id, app_type, recorded_at, tokens, cost, raw, model, input_tokens, output_tokens)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                record.provider_id,
+                record.app_type,
+                record.recorded_at,
+                record.tokens,
+                record.cost,
+                record.raw,
+                record.model,
+                record.input_tokens,
+                record.output_tokens,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 汇总指定供应商自 `since` 起的用量，返回 (tokens, cost)
+    pub fn sum_usage_since(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        since: i64,
+    ) -> Result<(i64, f64), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            "SELECT COALESCE(SUM(tokens), 0), COALESCE(SUM(cost), 0)
+             FROM usage_history
+             WHERE app_type = ?1 AND provider_id = ?2 AND recorded_at >= ?3",
+            params![app_type, provider_id, since],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 统计指定供应商的用量记录，返回 (记录数, tokens, cost, 最近记录时间)
+    pub fn summarize_usage_history(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<(u32, i64, f64, Option<i64>), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(tokens), 0), COALESCE(SUM(cost), 0), MAX(recorded_at)
+             FROM usage_history
+             WHERE app_type = ?1 AND provider_id = ?2",
+            params![app_type, provider_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 获取指定供应商的用量记录（按时间倒序）
+    pub fn get_usage_history(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        limit: usize,
+    ) -> Result<Vec<UsageRecord>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {USAGE_COLUMNS}
+                 FROM usage_history
+                 WHERE app_type = ?1 AND provider_id = ?2
+                 ORDER BY recorded_at DESC, id DESC
+                 LIMIT ?3"
+            ))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let record_iter = stmt
+            .query_map(
+                params![app_type, provider_id, limit as i64],
+                usage_record_from_row,
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut records = Vec::new();
+        for record_res in record_iter {
+            records.push(record_res.map_err(|e| AppError::Database(e.to_string()))?);
+        }
+        Ok(records)
+    }
+
+    /// 获取所有供应商自 `since` 起的用量记录（按时间正序，用于费用汇总）
+    pub fn get_usage_records_since(&self, since: i64) -> Result<Vec<UsageRecord>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {USAGE_COLUMNS}
+                 FROM usage_history
+                 WHERE recorded_at >= ?1
+                 ORDER BY recorded_at ASC, id ASC"
+            ))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let record_iter = stmt
+            .query_map(params![since], usage_record_from_row)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut records = Vec::new();
+        for record_res in record_iter {
+            records.push(record_res.map_err(|e| AppError::Database(e.to_string()))?);
+        }
+        Ok(records)
+    }
+
+    /// 删除指定供应商的全部用量记录
+    pub fn clear_usage_history(&self, app_type: &str, provider_id: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "DELETE FROM usage_history WHERE app_type = ?1 AND provider_id = ?2",
+            params![app_type, provider_id],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,297 @@
+//! 选择性导出：按应用与资源类型筛选导出内容，并可脱敏 API 密钥
+//!
+//! 筛选在内存快照上进行（删除未选中的行、改写敏感字段），不影响主库。
+
+use super::Database;
+use crate::error::AppError;
+use rusqlite::{params, Connection};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// 脱敏时判定为敏感字段的键名片段（不区分大小写）
+const SENSITIVE_KEY_PARTS: &[&str] = &[
+    "api_key",
+    "apikey",
+    "token",
+    "secret",
+    "password",
+    "authorization",
+];
+
+/// 导出选项（全部字段缺省时等同完整导出）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExportOptions {
+    /// 仅导出这些应用的供应商与提示词（为空表示全部应用）
+    pub apps: Vec<String>,
+    pub providers: bool,
+    pub mcp: bool,
+    pub prompts: bool,
+    pub skills: bool,
+    pub settings: bool,
+    /// 清空供应商配置与 MCP 配置中的 API 密钥、令牌等敏感字段
+    pub redact_keys: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            apps: Vec::new(),
+            providers: true,
+            mcp: true,
+            prompts: true,
+            skills: true,
+            settings: true,
+            redact_keys: false,
+        }
+    }
+}
+
+impl ExportOptions {
+    /// 生成删除未选中应用数据的 `AND app_type NOT IN (...)` 条件（未限定应用时为空）
+    fn app_filter(&self) -> String {
+        if self.apps.is_empty() {
+            return String::new();
+        }
+        let list = self
+            .apps
+            .iter()
+            .map(|app| format!("'{}'", app.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(" AND app_type NOT IN ({list})")
+    }
+}
+
+impl Database {
+    /// 按选项导出 SQL（用于生成可分享给团队成员的精简文件）
+    pub fn export_sql_with(
+        &self,
+        target_path: &Path,
+        options: &ExportOptions,
+    ) -> Result<(), AppError> {
+        let snapshot = self.snapshot_to_memory()?;
+        Self::filter_export_on_conn(&snapshot, options)?;
+        let dump = Self::dump_sql(&snapshot)?;
+
+        if let Some(parent) = target_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+
+        crate::config::atomic_write(target_path, dump.as_bytes())
+    }
+
+    /// 在快照连接上删除未选中的数据并脱敏
+    pub(crate) fn filter_export_on_conn(
+        conn: &Connection,
+        options: &ExportOptions,
+    ) -> Result<(), AppError> {
+        let app_filter = options.app_filter();
+        let mut statements = Vec::new();
+
+        if options.providers {
+            if !app_filter.is_empty() {
+                for table in [
+                    "providers",
+                    "provider_endpoints",
+                    "provider_attachments",
+                    "usage_history",
+                    "project_providers",
+                    "switch_events",
+                ] {
+                    statements.push(format!("DELETE FROM {table} WHERE 1{app_filter}"));
+                }
+                if !options.apps.iter().any(|app| app == "gemini") {
+                    statements.push("DELETE FROM gemini_oauth_accounts".to_string());
+                }
+            }
+        } else {
+            for table in [
+                "providers",
+                "provider_endpoints",
+                "provider_attachments",
+                "usage_history",
+                "gemini_oauth_accounts",
+                "project_providers",
+                "projects",
+                "switch_events",
+            ] {
+                statements.push(format!("DELETE FROM {table}"));
+            }
+        }
+        if !options.mcp {
+            statements.push("DELETE FROM mcp_servers".to_string());
+        }
+        for table in ["prompts", "prompt_sets", "prompt_set_members"] {
+            if !options.prompts {
+                statements.push(format!("DELETE FROM {table}"));
+            } else if !app_filter.is_empty() {
+                statements.push(format!("DELETE FROM {table} WHERE 1{app_filter}"));
+            }
+        }
+        if !options.skills {
+            statements.push("DELETE FROM skills".to_string());
+            statements.push("DELETE FROM skill_repos".to_string());
+        }
+        if !options.settings {
+            statements.push("DELETE FROM settings".to_string());
+            statements.push("DELETE FROM devices".to_string());
+        }
+
+        for sql in statements {
+            conn.execute(&sql, [])
+                .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        if options.redact_keys {
+            Self::redact_json_column(conn, "providers", "settings_config")?;
+            Self::redact_json_column(conn, "mcp_servers", "server_config")?;
+        }
+        Ok(())
+    }
+
+    /// 将指定 JSON 列中的敏感字段清空
+    fn redact_json_column(conn: &Connection, table: &str, column: &str) -> Result<(), AppError> {
+        let rows: Vec<(i64, String)> = {
+            let mut stmt = conn
+                .prepare(&format!("SELECT rowid, {column} FROM {table}"))
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            let iter = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            iter.collect::<Result<_, _>>()
+                .map_err(|e| AppError::Database(e.to_string()))?
+        };
+
+        for (rowid, raw) in rows {
+            // 无法解析的 JSON 原样保留（健康检查会单独报告）
+            let Ok(mut value) = serde_json::from_str::<Value>(&raw) else {
+                continue;
+            };
+            if !redact_value(&mut value) {
+                continue;
+            }
+            let redacted = super::to_json_string(&value)?;
+            conn.execute(
+                &format!("UPDATE {table} SET {column} = ?1 WHERE rowid = ?2"),
+                params![redacted, rowid],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    // 排除 MAX_OUTPUT_TOKENS 等数量限制配置
+    !key.contains("max") && SENSITIVE_KEY_PARTS.iter().any(|part| key.contains(part))
+}
+
+/// 递归清空敏感字段：字符串置为空串，对象与数组置为 null。返回是否有改动。
+pub(crate) fn redact_value(value: &mut Value) -> bool {
+    let mut changed = false;
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                if is_sensitive_key(key) {
+                    let replacement = match item {
+                        Value::String(s) if s.is_empty() => continue,
+                        Value::String(_) => Value::String(String::new()),
+                        Value::Object(_) | Value::Array(_) => Value::Null,
+                        _ => continue,
+                    };
+                    *item = replacement;
+                    changed = true;
+                } else {
+                    changed |= redact_value(item);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                changed |= redact_value(item);
+            }
+        }
+        _ => {}
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+    use serde_json::json;
+
+    #[test]
+    fn redact_value_clears_nested_credentials() {
+        let mut value = json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-secret",
+                "ANTHROPIC_BASE_URL": "https://api.example.com",
+                "CLAUDE_CODE_MAX_OUTPUT_TOKENS": "8192"
+            },
+            "auth": { "OPENAI_API_KEY": "sk-openai", "tokens": { "access_token": "t" } },
+            "headers": [{ "Authorization": "Bearer x" }]
+        });
+
+        assert!(redact_value(&mut value));
+        assert_eq!(value["env"]["ANTHROPIC_AUTH_TOKEN"], "");
+        assert_eq!(
+            value["env"]["ANTHROPIC_BASE_URL"],
+            "https://api.example.com"
+        );
+        assert_eq!(value["env"]["CLAUDE_CODE_MAX_OUTPUT_TOKENS"], "8192");
+        assert_eq!(value["auth"]["OPENAI_API_KEY"], "");
+        assert!(value["auth"]["tokens"].is_null());
+        assert_eq!(value["headers"][0]["Authorization"], "");
+
+        // 已脱敏的数据不再产生改动
+        assert!(!redact_value(&mut value));
+    }
+
+    #[test]
+    fn filter_export_keeps_selected_apps_and_resources() {
+        let db = Database::memory().expect("memory db");
+        for app in ["claude", "codex"] {
+            db.save_provider(
+                app,
+                &Provider::with_id(
+                    "p".into(),
+                    "P".into(),
+                    json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "sk-secret" } }),
+                    None,
+                ),
+            )
+            .expect("save provider");
+        }
+
+        let snapshot = db.snapshot_to_memory().expect("snapshot");
+        let options = ExportOptions {
+            apps: vec!["claude".into()],
+            mcp: false,
+            redact_keys: true,
+            ..ExportOptions::default()
+        };
+        Database::filter_export_on_conn(&snapshot, &options).expect("filter");
+
+        let apps: Vec<String> = snapshot
+            .prepare("SELECT app_type FROM providers")
+            .expect("prepare")
+            .query_map([], |row| row.get(0))
+            .expect("query")
+            .collect::<Result<_, _>>()
+            .expect("rows");
+        assert_eq!(apps, vec!["claude".to_string()]);
+
+        let config: String = snapshot
+            .query_row("SELECT settings_config FROM providers", [], |row| {
+                row.get(0)
+            })
+            .expect("config");
+        assert!(!config.contains("sk-secret"));
+    }
+}
@@ -0,0 +1,209 @@
+//! 数据库完整性检查与自修复
+//!
+//! 检查项：
+//! - `PRAGMA integrity_check`
+//! - `PRAGMA foreign_key_check`
+//! - 孤立的 `provider_endpoints`（对应供应商已不存在）
+//! - `providers.settings_config` / `providers.meta` 的 JSON 是否可解析
+//!
+//! 修复模式会删除孤立端点、将损坏的 JSON 重置为 `{}`，并返回所有改动项。
+
+use super::{lock_conn, Database};
+use crate::error::AppError;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+/// 孤立的端点记录
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanEndpoint {
+    pub id: i64,
+    pub provider_id: String,
+    pub app_type: String,
+    pub url: String,
+}
+
+/// 无法解析的 JSON 字段
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorruptJsonField {
+    pub provider_id: String,
+    pub app_type: String,
+    pub column: String,
+    pub error: String,
+}
+
+/// 数据库健康检查结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbHealthReport {
+    pub integrity_ok: bool,
+    /// `integrity_check` 输出（正常时为 `["ok"]`）
+    pub integrity_messages: Vec<String>,
+    /// 外键违规（`表名 rowid=… -> 父表`）
+    pub foreign_key_violations: Vec<String>,
+    pub orphan_endpoints: Vec<OrphanEndpoint>,
+    pub corrupt_json: Vec<CorruptJsonField>,
+    /// 是否以修复模式运行
+    pub repaired: bool,
+    /// 修复模式下实际改动的项
+    pub repaired_items: Vec<String>,
+}
+
+impl DbHealthReport {
+    /// 是否没有发现任何问题
+    pub fn is_healthy(&self) -> bool {
+        self.integrity_ok
+            && self.foreign_key_violations.is_empty()
+            && self.orphan_endpoints.is_empty()
+            && self.corrupt_json.is_empty()
+    }
+}
+
+/// 需要校验 JSON 格式的供应商字段
+const JSON_COLUMNS: &[&str] = &["settings_config", "meta"];
+
+impl Database {
+    /// 检查数据库健康状态，`repair` 为 true 时修复可自动处理的问题
+    pub fn check_health(&self, repair: bool) -> Result<DbHealthReport, AppError> {
+        let mut conn = lock_conn!(self.conn);
+
+        let mut report = DbHealthReport {
+            integrity_messages: Self::integrity_messages(&conn)?,
+            foreign_key_violations: Self::foreign_key_violations(&conn)?,
+            orphan_endpoints: Self::orphan_endpoints(&conn)?,
+            corrupt_json: Self::corrupt_json_fields(&conn)?,
+            repaired: repair,
+            ..Default::default()
+        };
+        report.integrity_ok = report.integrity_messages.len() == 1
+            && report.integrity_messages[0].eq_ignore_ascii_case("ok");
+
+        if !repair {
+            return Ok(report);
+        }
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        for endpoint in &report.orphan_endpoints {
+            tx.execute(
+                "DELETE FROM provider_endpoints WHERE id = ?1",
+                params![endpoint.id],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+            report.repaired_items.push(format!(
+                "删除孤立端点 {} ({}/{})",
+                endpoint.url, endpoint.app_type, endpoint.provider_id
+            ));
+        }
+
+        for field in &report.corrupt_json {
+            // 列名来自 JSON_COLUMNS 常量，可安全拼接
+            tx.execute(
+                &format!(
+                    "UPDATE providers SET {} = '{{}}' WHERE id = ?1 AND app_type = ?2",
+                    field.column
+                ),
+                params![field.provider_id, field.app_type],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+            report.repaired_items.push(format!(
+                "重置 {}/{} 的 {} 为 {{}}",
+                field.app_type, field.provider_id, field.column
+            ));
+        }
+
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(report)
+    }
+
+    fn integrity_messages(conn: &Connection) -> Result<Vec<String>, AppError> {
+        let mut stmt = conn
+            .prepare("PRAGMA integrity_check")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    fn foreign_key_violations(conn: &Connection) -> Result<Vec<String>, AppError> {
+        let mut stmt = conn
+            .prepare("PRAGMA foreign_key_check")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                let table: String = row.get(0)?;
+                let rowid: Option<i64> = row.get(1)?;
+                let parent: String = row.get(2)?;
+                Ok(format!(
+                    "{table} rowid={} -> {parent}",
+                    rowid.map(|id| id.to_string()).unwrap_or_default()
+                ))
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    fn orphan_endpoints(conn: &Connection) -> Result<Vec<OrphanEndpoint>, AppError> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT e.id, e.provider_id, e.app_type, e.url
+                 FROM provider_endpoints e
+                 LEFT JOIN providers p ON p.id = e.provider_id AND p.app_type = e.app_type
+                 WHERE p.id IS NULL
+                 ORDER BY e.id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(OrphanEndpoint {
+                    id: row.get(0)?,
+                    provider_id: row.get(1)?,
+                    app_type: row.get(2)?,
+                    url: row.get(3)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    fn corrupt_json_fields(conn: &Connection) -> Result<Vec<CorruptJsonField>, AppError> {
+        let mut stmt = conn
+            .prepare("SELECT id, app_type, settings_config, meta FROM providers")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut corrupt = Vec::new();
+        for row in rows {
+            let (id, app_type, settings_config, meta) =
+                row.map_err(|e| AppError::Database(e.to_string()))?;
+            for (column, raw) in JSON_COLUMNS.iter().zip([settings_config, meta]) {
+                let raw = raw.unwrap_or_default();
+                if let Err(e) = serde_json::from_str::<serde_json::Value>(&raw) {
+                    corrupt.push(CorruptJsonField {
+                        provider_id: id.clone(),
+                        app_type: app_type.clone(),
+                        column: column.to_string(),
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(corrupt)
+    }
+}
@@ -0,0 +1,372 @@
+//! JSON 导出/导入：便于 diff 的规范化数据库文档
+//!
+//! 表按名称排序，行按主键排序，行内字段按列名排序；内容为 JSON 的文本列直接内嵌为
+//! 对象/数组，BLOB 列使用 base64。文档不含生成时间，相同数据总是得到相同输出，
+//! 适合放进 dotfiles 仓库管理。
+//!
+//! ```json
+//! { "format": "cc-switch-db", "version": 1, "userVersion": 1, "tables": { "providers": [...] } }
+//! ```
+
+use super::backup::SCHEMA_ONLY_TABLES;
+use super::export::ExportOptions;
+use super::Database;
+use crate::error::AppError;
+use base64::Engine;
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::{params_from_iter, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// 文档格式标识
+const JSON_FORMAT: &str = "cc-switch-db";
+
+/// 文档格式版本（与数据库 user_version 无关）
+const JSON_FORMAT_VERSION: u32 = 1;
+
+type JsonRow = BTreeMap<String, Value>;
+
+/// 数据库 JSON 文档
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DbJsonDocument {
+    format: String,
+    version: u32,
+    user_version: i64,
+    tables: BTreeMap<String, Vec<JsonRow>>,
+}
+
+/// 表的列信息（名称、声明类型、主键序号）
+struct ColumnInfo {
+    name: String,
+    decl_type: String,
+    pk: i64,
+}
+
+impl ColumnInfo {
+    fn is_blob(&self) -> bool {
+        self.decl_type.eq_ignore_ascii_case("BLOB")
+    }
+}
+
+impl Database {
+    /// 导出为规范化 JSON 文档
+    pub fn export_json(&self, target_path: &Path) -> Result<(), AppError> {
+        self.export_json_with(target_path, &ExportOptions::default())
+    }
+
+    /// 按选项导出 JSON（筛选与脱敏规则同 SQL 导出）
+    pub fn export_json_with(
+        &self,
+        target_path: &Path,
+        options: &ExportOptions,
+    ) -> Result<(), AppError> {
+        let snapshot = self.snapshot_to_memory()?;
+        Self::filter_export_on_conn(&snapshot, options)?;
+        let document = Self::dump_json(&snapshot)?;
+        let mut text = serde_json::to_string_pretty(&document)
+            .map_err(|e| AppError::JsonSerialize { source: e })?;
+        text.push('\n');
+
+        if let Some(parent) = target_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        crate::config::atomic_write(target_path, text.as_bytes())
+    }
+
+    /// 从 JSON 文档导入（整体替换），返回生成的备份 ID（若无备份则为空字符串）
+    ///
+    /// 文档先写入内存临时库并校验，任何表、列或值不合法都会拒绝导入，主库保持不变。
+    pub fn import_json(&self, source_path: &Path) -> Result<String, AppError> {
+        if !source_path.exists() {
+            return Err(AppError::InvalidInput(format!(
+                "JSON 文件不存在: {}",
+                source_path.display()
+            )));
+        }
+        let content = fs::read_to_string(source_path).map_err(|e| AppError::io(source_path, e))?;
+        let document: DbJsonDocument =
+            serde_json::from_str(&content).map_err(|e| AppError::json(source_path, e))?;
+
+        let temp_conn = Self::load_json_into_memory(&document)?;
+        self.replace_with_conn(&temp_conn)
+    }
+
+    fn dump_json(conn: &Connection) -> Result<DbJsonDocument, AppError> {
+        let user_version: i64 = conn
+            .query_row("PRAGMA user_version;", [], |row| row.get(0))
+            .unwrap_or(0);
+
+        let mut tables = BTreeMap::new();
+        for table in Self::user_tables(conn)? {
+            if SCHEMA_ONLY_TABLES.contains(&table.as_str()) {
+                continue;
+            }
+            let columns = Self::column_info(conn, &table)?;
+            if columns.is_empty() {
+                continue;
+            }
+            let rows = Self::dump_table_rows(conn, &table, &columns)?;
+            tables.insert(table, rows);
+        }
+
+        Ok(DbJsonDocument {
+            format: JSON_FORMAT.to_string(),
+            version: JSON_FORMAT_VERSION,
+            user_version,
+            tables,
+        })
+    }
+
+    fn dump_table_rows(
+        conn: &Connection,
+        table: &str,
+        columns: &[ColumnInfo],
+    ) -> Result<Vec<JsonRow>, AppError> {
+        // 按主键排序；无主键时按全部列排序，保证输出稳定
+        let mut keys: Vec<&ColumnInfo> = columns.iter().filter(|c| c.pk > 0).collect();
+        keys.sort_by_key(|c| c.pk);
+        if keys.is_empty() {
+            keys = columns.iter().collect();
+        }
+        let order = keys
+            .iter()
+            .map(|c| format!("\"{}\"", c.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut stmt = conn
+            .prepare(&format!("SELECT * FROM \"{table}\" ORDER BY {order}"))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut rows = stmt
+            .query([])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut result = Vec::new();
+        while let Some(row) = rows.next().map_err(|e| AppError::Database(e.to_string()))? {
+            let mut json_row = JsonRow::new();
+            for (idx, column) in columns.iter().enumerate() {
+                let value = row
+                    .get_ref(idx)
+                    .map_err(|e| AppError::Database(e.to_string()))?;
+                json_row.insert(column.name.clone(), sql_to_json(value)?);
+            }
+            result.push(json_row);
+        }
+        Ok(result)
+    }
+
+    /// 将文档写入带完整表结构的内存库并校验
+    fn load_json_into_memory(document: &DbJsonDocument) -> Result<Connection, AppError> {
+        if document.format != JSON_FORMAT {
+            return Err(AppError::InvalidInput(format!(
+                "不是 CC Switch 数据库 JSON 文档（format: {}）",
+                document.format
+            )));
+        }
+        if document.version > JSON_FORMAT_VERSION {
+            return Err(AppError::InvalidInput(format!(
+                "JSON 文档版本 {} 高于当前支持的版本 {JSON_FORMAT_VERSION}，请升级应用",
+                document.version
+            )));
+        }
+
+        if document.user_version > i64::from(super::SCHEMA_VERSION) {
+            log::warn!(
+                "JSON 文档来自更高的数据库版本 v{}，按当前版本 v{} 导入",
+                document.user_version,
+                super::SCHEMA_VERSION
+            );
+        }
+
+        let conn = Connection::open_in_memory().map_err(|e| AppError::Database(e.to_string()))?;
+        Self::create_tables_on_conn(&conn)?;
+        Self::apply_schema_migrations_on_conn(&conn)?;
+        conn.execute_batch("PRAGMA foreign_keys=OFF;")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let known = Self::user_tables(&conn)?;
+        for (table, rows) in &document.tables {
+            if !known.contains(table) {
+                return Err(AppError::InvalidInput(format!("未知的数据表: {table}")));
+            }
+            if SCHEMA_ONLY_TABLES.contains(&table.as_str()) {
+                log::warn!("JSON 导入跳过仅结构表 {table}");
+                continue;
+            }
+            let columns = Self::column_info(&conn, table)?;
+            for (index, row) in rows.iter().enumerate() {
+                Self::insert_json_row(&conn, table, &columns, row).map_err(|e| {
+                    AppError::InvalidInput(format!("导入 {table} 第 {} 行失败: {e}", index + 1))
+                })?;
+            }
+        }
+
+        Self::validate_basic_state(&conn)?;
+        Ok(conn)
+    }
+
+    fn insert_json_row(
+        conn: &Connection,
+        table: &str,
+        columns: &[ColumnInfo],
+        row: &JsonRow,
+    ) -> Result<(), AppError> {
+        let mut names = Vec::with_capacity(row.len());
+        let mut values = Vec::with_capacity(row.len());
+        for (name, value) in row {
+            let column = columns
+                .iter()
+                .find(|c| &c.name == name)
+                .ok_or_else(|| AppError::InvalidInput(format!("未知的列: {name}")))?;
+            names.push(format!("\"{name}\""));
+            values.push(json_to_sql(column, value)?);
+        }
+        if names.is_empty() {
+            return Err(AppError::InvalidInput("空行".to_string()));
+        }
+
+        let placeholders = (1..=names.len())
+            .map(|i| format!("?{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        conn.execute(
+            &format!(
+                "INSERT INTO \"{table}\" ({}) VALUES ({placeholders})",
+                names.join(", ")
+            ),
+            params_from_iter(values),
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 用户表（不含 SQLite 内部表），按名称排序
+    fn user_tables(conn: &Connection) -> Result<Vec<String>, AppError> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT name FROM sqlite_master
+                 WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+                 ORDER BY name",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let iter = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        iter.collect::<Result<_, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    fn column_info(conn: &Connection, table: &str) -> Result<Vec<ColumnInfo>, AppError> {
+        let mut stmt = conn
+            .prepare(&format!("PRAGMA table_info(\"{table}\")"))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let iter = stmt
+            .query_map([], |row| {
+                Ok(ColumnInfo {
+                    name: row.get(1)?,
+                    decl_type: row.get(2)?,
+                    pk: row.get(5)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        iter.collect::<Result<_, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+/// SQLite 值转 JSON：内容为 JSON 对象/数组的文本直接内嵌
+fn sql_to_json(value: ValueRef<'_>) -> Result<Value, AppError> {
+    Ok(match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::from(i),
+        ValueRef::Real(f) => Value::from(f),
+        ValueRef::Text(t) => {
+            let text = std::str::from_utf8(t)
+                .map_err(|e| AppError::Database(format!("文本字段不是有效的 UTF-8: {e}")))?;
+            match serde_json::from_str::<Value>(text) {
+                Ok(parsed @ (Value::Object(_) | Value::Array(_))) => parsed,
+                _ => Value::String(text.to_string()),
+            }
+        }
+        ValueRef::Blob(bytes) => {
+            Value::String(base64::engine::general_purpose::STANDARD.encode(bytes))
+        }
+    })
+}
+
+/// JSON 转 SQLite 值（按列声明类型解释字符串）
+fn json_to_sql(column: &ColumnInfo, value: &Value) -> Result<SqlValue, AppError> {
+    Ok(match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(i64::from(*b)),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) if column.is_blob() => SqlValue::Blob(
+            base64::engine::general_purpose::STANDARD
+                .decode(s)
+                .map_err(|e| {
+                    AppError::InvalidInput(format!("列 {} 不是有效的 base64: {e}", column.name))
+                })?,
+        ),
+        Value::String(s) => SqlValue::Text(s.clone()),
+        Value::Object(_) | Value::Array(_) => SqlValue::Text(super::to_json_string(value)?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+    use serde_json::json;
+
+    #[test]
+    fn json_round_trip_is_stable() {
+        let db = Database::memory().expect("memory db");
+        db.save_provider(
+            "claude",
+            &Provider::with_id(
+                "p".into(),
+                "P".into(),
+                json!({ "env": { "ANTHROPIC_BASE_URL": "https://a" } }),
+                None,
+            ),
+        )
+        .expect("save provider");
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let first = dir.path().join("first.json");
+        db.export_json(&first).expect("export");
+        let text = fs::read_to_string(&first).expect("read");
+        // JSON 列内嵌为对象而非转义字符串
+        assert!(text.contains("\"ANTHROPIC_BASE_URL\": \"https://a\""));
+
+        // 走导入校验路径（不替换主库，避免在测试中创建备份文件）
+        let document: DbJsonDocument = serde_json::from_str(&text).expect("parse");
+        let restored = Database::load_json_into_memory(&document).expect("load");
+        let mut again =
+            serde_json::to_string_pretty(&Database::dump_json(&restored).expect("dump"))
+                .expect("serialize");
+        again.push('\n');
+        assert_eq!(text, again);
+    }
+
+    #[test]
+    fn import_rejects_unknown_columns() {
+        let document = DbJsonDocument {
+            format: JSON_FORMAT.to_string(),
+            version: JSON_FORMAT_VERSION,
+            user_version: 1,
+            tables: BTreeMap::from([(
+                "providers".to_string(),
+                vec![JsonRow::from([("nope".to_string(), json!(1))])],
+            )]),
+        };
+        assert!(Database::load_json_into_memory(&document).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+//! 写入租约：检测其他进程是否正在写同一个数据库
+//!
+//! 持有者将 `{instance_id, pid, heartbeat_at}` 写入 settings 表并定期续约。
+//! 启动时若发现其他实例的租约仍在有效期内，则以只读模式运行（`PRAGMA query_only`），
+//! 待对方租约过期后由心跳任务自动接管并恢复写入。
+
+use super::{lock_conn, Database};
+use crate::error::AppError;
+use ring::rand::{SecureRandom, SystemRandom};
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 租约在 settings 表中的键名
+const LEASE_KEY: &str = "db_writer_lease";
+/// 心跳超过该时长（秒）未更新即视为持有者已退出
+pub(crate) const LEASE_TTL_SECS: i64 = 30;
+
+/// settings 表中保存的租约
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LeaseRecord {
+    instance_id: String,
+    pid: u32,
+    heartbeat_at: i64,
+}
+
+/// 本实例的租约状态
+#[derive(Debug)]
+pub(crate) struct LeaseState {
+    instance_id: String,
+    read_only: AtomicBool,
+}
+
+impl LeaseState {
+    pub(crate) fn new() -> Result<Self, AppError> {
+        let mut bytes = [0u8; 8];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| AppError::Config("生成实例标识失败".to_string()))?;
+        Ok(Self {
+            instance_id: bytes.iter().map(|b| format!("{b:02x}")).collect(),
+            read_only: AtomicBool::new(false),
+        })
+    }
+}
+
+/// 数据库租约状态（供前端展示）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbLeaseStatus {
+    /// 其他进程持有写入租约，本实例为只读
+    pub read_only: bool,
+    /// 当前租约持有者的进程号
+    pub holder_pid: Option<u32>,
+    /// 持有者最近一次心跳（Unix 秒）
+    pub heartbeat_at: Option<i64>,
+}
+
+impl Database {
+    /// 尝试获取或续约写入租约，返回是否持有租约
+    ///
+    /// 其他实例的租约仍有效时切换为只读；否则写入本实例的租约并恢复写入。
+    pub fn renew_lease(&self) -> Result<bool, AppError> {
+        let now = chrono::Utc::now().timestamp();
+        let mut conn = lock_conn!(self.conn);
+        let held = Self::try_acquire_on_conn(&mut conn, &self.lease.instance_id, now)?;
+
+        let was_read_only = self.lease.read_only.swap(!held, Ordering::SeqCst);
+        if was_read_only == held {
+            if held {
+                log::info!("已获取数据库写入租约");
+            } else {
+                log::warn!("数据库正被其他进程写入，切换为只读模式");
+            }
+        }
+        conn.pragma_update(None, "query_only", !held)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(held)
+    }
+
+    /// 退出前释放租约，便于其他实例立即接管
+    pub fn release_lease(&self) -> Result<(), AppError> {
+        if self.is_read_only() {
+            return Ok(());
+        }
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "DELETE FROM settings WHERE key = ?1 AND json_extract(value, '$.instanceId') = ?2",
+            params![LEASE_KEY, self.lease.instance_id],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 本实例是否处于只读模式
+    pub fn is_read_only(&self) -> bool {
+        self.lease.read_only.load(Ordering::SeqCst)
+    }
+
+    /// 当前租约状态
+    pub fn lease_status(&self) -> Result<DbLeaseStatus, AppError> {
+        let conn = lock_conn!(self.conn);
+        let record = Self::read_lease(&conn)?;
+        Ok(DbLeaseStatus {
+            read_only: self.is_read_only(),
+            holder_pid: record.as_ref().map(|r| r.pid),
+            heartbeat_at: record.map(|r| r.heartbeat_at),
+        })
+    }
+
+    fn read_lease(conn: &Connection) -> Result<Option<LeaseRecord>, AppError> {
+        let raw: Option<String> = conn
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?1",
+                params![LEASE_KEY],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        // 无法解析的租约视为不存在
+        Ok(raw.and_then(|v| serde_json::from_str(&v).ok()))
+    }
+
+    /// 在独占事务中检查并写入租约，避免两个实例同时判定为空闲
+    fn try_acquire_on_conn(
+        conn: &mut Connection,
+        instance_id: &str,
+        now: i64,
+    ) -> Result<bool, AppError> {
+        // 只读模式下需临时允许写入才能尝试接管
+        conn.pragma_update(None, "query_only", false)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let tx = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        if let Some(record) = Self::read_lease(&tx)? {
+            let alive = now - record.heartbeat_at < LEASE_TTL_SECS;
+            if record.instance_id != instance_id && alive {
+                return Ok(false);
+            }
+        }
+
+        let record = LeaseRecord {
+            instance_id: instance_id.to_string(),
+            pid: std::process::id(),
+            heartbeat_at: now,
+        };
+        tx.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            params![LEASE_KEY, super::to_json_string(&record)?],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_instance_waits_for_stale_lease() {
+        let mut conn = Connection::open_in_memory().expect("open");
+        Database::create_tables_on_conn(&conn).expect("tables");
+
+        assert!(Database::try_acquire_on_conn(&mut conn, "a", 100).expect("a acquires"));
+        // 同一实例可续约，其他实例在有效期内无法接管
+        assert!(Database::try_acquire_on_conn(&mut conn, "a", 110).expect("a renews"));
+        assert!(!Database::try_acquire_on_conn(&mut conn, "b", 120).expect("b blocked"));
+        // 心跳过期后其他实例可接管
+        assert!(
+            Database::try_acquire_on_conn(&mut conn, "b", 110 + LEASE_TTL_SECS).expect("b takes")
+        );
+        assert_eq!(
+            Database::read_lease(&conn)
+                .expect("read")
+                .map(|r| r.instance_id)
+                .as_deref(),
+            Some("b")
+        );
+    }
+}
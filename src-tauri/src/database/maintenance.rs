@@ -0,0 +1,78 @@
+//! 数据库维护：VACUUM / ANALYZE 压缩
+
+use super::{lock_conn, Database};
+use crate::error::AppError;
+use rusqlite::Connection;
+use serde::Serialize;
+
+/// 上次压缩时间（settings 表键名）
+const LAST_COMPACTED_KEY: &str = "db_last_compacted_at";
+
+/// 压缩结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactReport {
+    pub size_before: u64,
+    pub size_after: u64,
+    pub reclaimed_bytes: u64,
+    pub compacted_at: i64,
+}
+
+impl Database {
+    /// 执行 VACUUM 与 ANALYZE，返回回收的空间
+    pub fn compact(&self) -> Result<CompactReport, AppError> {
+        let report = {
+            let conn = lock_conn!(self.conn);
+            Self::compact_on_conn(&conn)?
+        };
+        self.set_setting(LAST_COMPACTED_KEY, &report.compacted_at.to_string())?;
+        Ok(report)
+    }
+
+    /// 仅在数据库空闲（未被其他操作占用）时压缩，忙碌时返回 None
+    pub fn compact_if_idle(&self) -> Result<Option<CompactReport>, AppError> {
+        let report = {
+            let Ok(conn) = self.conn.try_lock() else {
+                return Ok(None);
+            };
+            Self::compact_on_conn(&conn)?
+        };
+        self.set_setting(LAST_COMPACTED_KEY, &report.compacted_at.to_string())?;
+        Ok(Some(report))
+    }
+
+    /// 上次压缩时间
+    pub fn last_compacted_at(&self) -> Result<Option<i64>, AppError> {
+        Ok(self
+            .get_setting(LAST_COMPACTED_KEY)?
+            .and_then(|value| value.parse().ok()))
+    }
+
+    fn compact_on_conn(conn: &Connection) -> Result<CompactReport, AppError> {
+        let size_before = Self::database_size(conn)?;
+        conn.execute_batch("VACUUM; ANALYZE;")
+            .map_err(|e| AppError::Database(format!("数据库压缩失败: {e}")))?;
+        // 将 WAL 内容写回主库并截断 WAL 文件（非 WAL 模式下为空操作）
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .map_err(|e| AppError::Database(format!("数据库压缩失败: {e}")))?;
+        let size_after = Self::database_size(conn)?;
+
+        Ok(CompactReport {
+            size_before,
+            size_after,
+            reclaimed_bytes: size_before.saturating_sub(size_after),
+            compacted_at: chrono::Utc::now().timestamp(),
+        })
+    }
+
+    /// 数据库大小（page_count × page_size）
+    fn database_size(conn: &Connection) -> Result<u64, AppError> {
+        let page_count: i64 = conn
+            .query_row("PRAGMA page_count", [], |row| row.get(0))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let page_size: i64 = conn
+            .query_row("PRAGMA page_size", [], |row| row.get(0))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok((page_count.max(0) * page_size.max(0)) as u64)
+    }
+}
@@ -0,0 +1,527 @@
+//! 增量导入：将 SQL 备份中的供应商、MCP 与提示词合并到当前数据库
+//!
+//! 合并只新增或更新条目（按 id 匹配），不会删除本地已有数据。
+//! 先调用预览获取变更列表与指纹，确认后携带指纹提交，指纹不一致时拒绝执行。
+
+use super::{lock_conn, Database};
+use crate::error::AppError;
+use ring::digest::{digest, SHA256};
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// 参与合并的表
+struct MergeTable {
+    kind: MergeKind,
+    table: &'static str,
+    /// 主键列
+    keys: &'static [&'static str],
+    /// 设备级状态列：不参与比较，更新时保留本地值，新增时置为 0
+    local_only: &'static [&'static str],
+}
+
+static MERGE_TABLES: &[MergeTable] = &[
+    MergeTable {
+        kind: MergeKind::Provider,
+        table: "providers",
+        keys: &["id", "app_type"],
+        local_only: &["is_current"],
+    },
+    MergeTable {
+        kind: MergeKind::Mcp,
+        table: "mcp_servers",
+        keys: &["id"],
+        local_only: &[],
+    },
+    MergeTable {
+        kind: MergeKind::Prompt,
+        table: "prompts",
+        keys: &["id", "app_type"],
+        local_only: &["enabled"],
+    },
+];
+
+/// 供应商端点变更在 changed_fields 中的名称
+const ENDPOINTS_FIELD: &str = "endpoints";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeKind {
+    Provider,
+    Mcp,
+    Prompt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeAction {
+    Added,
+    Updated,
+}
+
+/// 单条合并变更
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeChange {
+    pub kind: MergeKind,
+    pub action: MergeAction,
+    pub id: String,
+    /// 供应商与提示词所属应用（MCP 为 None）
+    pub app_type: Option<String>,
+    pub name: Option<String>,
+    /// 发生变化的字段（新增条目为空）
+    pub changed_fields: Vec<String>,
+}
+
+/// 合并预览
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergePreview {
+    /// 预览指纹，提交合并时需原样传回
+    pub fingerprint: String,
+    pub changes: Vec<MergeChange>,
+    pub added: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+}
+
+/// 表中的一行（列顺序与 TablePlan::columns 一致）
+type Row = Vec<Value>;
+
+/// 单表的合并计划
+struct TablePlan {
+    table: &'static MergeTable,
+    columns: Vec<String>,
+    /// (变更, 导入侧的行)
+    rows: Vec<(MergeChange, Row)>,
+}
+
+/// 供应商端点：(provider_id, app_type) -> [(url, added_at)]
+type EndpointMap = HashMap<(String, String), Vec<(String, Option<i64>)>>;
+
+struct MergePlan {
+    tables: Vec<TablePlan>,
+    /// 需要补充到本地的端点
+    endpoints: EndpointMap,
+    unchanged: usize,
+}
+
+impl MergePlan {
+    fn changes(&self) -> Vec<MergeChange> {
+        self.tables
+            .iter()
+            .flat_map(|plan| plan.rows.iter().map(|(change, _)| change.clone()))
+            .collect()
+    }
+
+    fn preview(&self, source: &[u8]) -> MergePreview {
+        let changes = self.changes();
+        let added = changes
+            .iter()
+            .filter(|c| c.action == MergeAction::Added)
+            .count();
+        MergePreview {
+            fingerprint: fingerprint(source, &changes),
+            updated: changes.len() - added,
+            added,
+            unchanged: self.unchanged,
+            changes,
+        }
+    }
+}
+
+impl Database {
+    /// 预览将 SQL 备份合并到当前数据库时的变更（不修改数据）
+    pub fn preview_sql_merge(&self, source_path: &Path) -> Result<MergePreview, AppError> {
+        let source = Self::read_merge_source(source_path)?;
+        let (_temp_file, incoming) = Self::load_sql_into_temp(source_path)?;
+        let plan = self.plan_merge(&incoming)?;
+        Ok(plan.preview(&source))
+    }
+
+    /// 按预览执行合并，返回实际应用的变更与备份 ID（若无备份则为空字符串）
+    ///
+    /// `fingerprint` 必须与预览结果一致；期间文件或本地数据发生变化时拒绝执行。
+    pub fn apply_sql_merge(
+        &self,
+        source_path: &Path,
+        fingerprint: &str,
+    ) -> Result<(MergePreview, String), AppError> {
+        let source = Self::read_merge_source(source_path)?;
+        let (_temp_file, incoming) = Self::load_sql_into_temp(source_path)?;
+        let plan = self.plan_merge(&incoming)?;
+        let preview = plan.preview(&source);
+        if preview.fingerprint != fingerprint {
+            return Err(AppError::localized("import.merge.preview_outdated"));
+        }
+        if preview.changes.is_empty() {
+            return Ok((preview, String::new()));
+        }
+
+        let backup_path = self.backup_database_file()?;
+        self.apply_merge_plan(&plan)?;
+
+        let backup_id = backup_path
+            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap_or_default();
+        Ok((preview, backup_id))
+    }
+
+    fn read_merge_source(source_path: &Path) -> Result<Vec<u8>, AppError> {
+        if !source_path.exists() {
+            return Err(AppError::InvalidInput(format!(
+                "SQL 文件不存在: {}",
+                source_path.display()
+            )));
+        }
+        fs::read(source_path).map_err(|e| AppError::io(source_path, e))
+    }
+
+    /// 对比导入库与当前库，生成合并计划
+    fn plan_merge(&self, incoming: &Connection) -> Result<MergePlan, AppError> {
+        let local = self.snapshot_to_memory()?;
+        let mut tables = Vec::new();
+        let mut unchanged = 0;
+
+        for table in MERGE_TABLES {
+            let incoming_columns = Self::get_table_columns(incoming, table.table)?;
+            let columns: Vec<String> = Self::get_table_columns(&local, table.table)?
+                .into_iter()
+                .filter(|c| incoming_columns.contains(c))
+                .collect();
+
+            let local_rows: HashMap<Vec<String>, Row> = read_rows(&local, table.table, &columns)?
+                .into_iter()
+                .map(|row| (row_key(table, &columns, &row), row))
+                .collect();
+
+            let mut rows = Vec::new();
+            for row in read_rows(incoming, table.table, &columns)? {
+                let key = row_key(table, &columns, &row);
+                let (action, changed_fields) = match local_rows.get(&key) {
+                    None => (MergeAction::Added, Vec::new()),
+                    Some(existing) => {
+                        let changed: Vec<String> = columns
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, c)| !table.local_only.contains(&c.as_str()))
+                            .filter(|(i, _)| existing[*i] != row[*i])
+                            .map(|(_, c)| c.clone())
+                            .collect();
+                        (MergeAction::Updated, changed)
+                    }
+                };
+                rows.push((
+                    MergeChange {
+                        kind: table.kind,
+                        action,
+                        id: key[0].clone(),
+                        app_type: key.get(1).cloned(),
+                        name: column_text(&columns, &row, "name"),
+                        changed_fields,
+                    },
+                    row,
+                ));
+            }
+
+            tables.push(TablePlan {
+                table,
+                columns,
+                rows,
+            });
+        }
+
+        // 供应商端点只做补充：导入侧存在而本地缺失的 URL
+        let local_endpoints = read_endpoints(&local)?;
+        let mut endpoints = EndpointMap::new();
+        for (key, list) in read_endpoints(incoming)? {
+            let known: HashSet<&str> = local_endpoints
+                .get(&key)
+                .map(|l| l.iter().map(|(url, _)| url.as_str()).collect())
+                .unwrap_or_default();
+            let missing: Vec<_> = list
+                .into_iter()
+                .filter(|(url, _)| !known.contains(url.as_str()))
+                .collect();
+            if !missing.is_empty() {
+                endpoints.insert(key, missing);
+            }
+        }
+        if let Some(providers) = tables
+            .iter_mut()
+            .find(|t| t.table.kind == MergeKind::Provider)
+        {
+            for (change, _) in providers.rows.iter_mut() {
+                let key = (
+                    change.id.clone(),
+                    change.app_type.clone().unwrap_or_default(),
+                );
+                if change.action == MergeAction::Updated && endpoints.contains_key(&key) {
+                    change.changed_fields.push(ENDPOINTS_FIELD.to_string());
+                }
+            }
+        }
+        // 导入侧缺少对应供应商的端点不予处理
+        endpoints.retain(|key, _| {
+            tables.iter().any(|t| {
+                t.table.kind == MergeKind::Provider
+                    && t.rows.iter().any(|(c, _)| {
+                        c.id == key.0 && c.app_type.as_deref() == Some(key.1.as_str())
+                    })
+            })
+        });
+
+        for plan in tables.iter_mut() {
+            let before = plan.rows.len();
+            plan.rows.retain(|(change, _)| {
+                change.action == MergeAction::Added || !change.changed_fields.is_empty()
+            });
+            unchanged += before - plan.rows.len();
+        }
+
+        Ok(MergePlan {
+            tables,
+            endpoints,
+            unchanged,
+        })
+    }
+
+    /// 在单个事务中写入合并计划
+    fn apply_merge_plan(&self, plan: &MergePlan) -> Result<(), AppError> {
+        let mut conn = lock_conn!(self.conn);
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        for table_plan in &plan.tables {
+            let table = table_plan.table;
+            let columns = &table_plan.columns;
+            let column_list = columns
+                .iter()
+                .map(|c| format!("\"{c}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let placeholders = vec!["?"; columns.len()].join(", ");
+            let insert_sql = format!(
+                "INSERT INTO \"{}\" ({column_list}) VALUES ({placeholders})",
+                table.table
+            );
+
+            // 更新时跳过主键与设备级状态列
+            let update_columns: Vec<usize> = (0..columns.len())
+                .filter(|&i| {
+                    let c = columns[i].as_str();
+                    !table.keys.contains(&c) && !table.local_only.contains(&c)
+                })
+                .collect();
+            let key_columns: Vec<usize> = table
+                .keys
+                .iter()
+                .filter_map(|k| columns.iter().position(|c| c == k))
+                .collect();
+            let update_sql = format!(
+                "UPDATE \"{}\" SET {} WHERE {}",
+                table.table,
+                update_columns
+                    .iter()
+                    .map(|&i| format!("\"{}\" = ?", columns[i]))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                key_columns
+                    .iter()
+                    .map(|&i| format!("\"{}\" = ?", columns[i]))
+                    .collect::<Vec<_>>()
+                    .join(" AND ")
+            );
+
+            for (change, row) in &table_plan.rows {
+                match change.action {
+                    MergeAction::Added => {
+                        let values = columns.iter().zip(row).map(|(c, v)| {
+                            if table.local_only.contains(&c.as_str()) {
+                                Value::Integer(0)
+                            } else {
+                                v.clone()
+                            }
+                        });
+                        tx.execute(&insert_sql, params_from_iter(values))
+                            .map_err(|e| AppError::Database(e.to_string()))?;
+                    }
+                    MergeAction::Updated => {
+                        let values = update_columns
+                            .iter()
+                            .chain(key_columns.iter())
+                            .map(|&i| row[i].clone());
+                        tx.execute(&update_sql, params_from_iter(values))
+                            .map_err(|e| AppError::Database(e.to_string()))?;
+                    }
+                }
+            }
+        }
+
+        for ((provider_id, app_type), list) in &plan.endpoints {
+            for (url, added_at) in list {
+                tx.execute(
+                    "INSERT INTO provider_endpoints (provider_id, app_type, url, added_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    rusqlite::params![provider_id, app_type, url, added_at],
+                )
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            }
+        }
+
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+/// 读取指定列的全部行
+fn read_rows(conn: &Connection, table: &str, columns: &[String]) -> Result<Vec<Row>, AppError> {
+    let column_list = columns
+        .iter()
+        .map(|c| format!("\"{c}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut stmt = conn
+        .prepare(&format!("SELECT {column_list} FROM \"{table}\""))
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    let rows = stmt
+        .query_map([], |row| {
+            (0..columns.len())
+                .map(|i| row.get::<_, Value>(i))
+                .collect::<Result<Row, _>>()
+        })
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Database(e.to_string()))
+}
+
+fn read_endpoints(conn: &Connection) -> Result<EndpointMap, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT provider_id, app_type, url, added_at FROM provider_endpoints ORDER BY id")
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<i64>>(3)?,
+            ))
+        })
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    let mut map = EndpointMap::new();
+    for row in rows {
+        let (provider_id, app_type, url, added_at) =
+            row.map_err(|e| AppError::Database(e.to_string()))?;
+        map.entry((provider_id, app_type))
+            .or_default()
+            .push((url, added_at));
+    }
+    Ok(map)
+}
+
+/// 行主键（按 MergeTable::keys 的顺序）
+fn row_key(table: &MergeTable, columns: &[String], row: &Row) -> Vec<String> {
+    table
+        .keys
+        .iter()
+        .map(|k| column_text(columns, row, k).unwrap_or_default())
+        .collect()
+}
+
+fn column_text(columns: &[String], row: &Row, column: &str) -> Option<String> {
+    let index = columns.iter().position(|c| c == column)?;
+    match &row[index] {
+        Value::Text(text) => Some(text.clone()),
+        Value::Integer(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// 指纹覆盖导入文件内容与变更列表，任一变化都会使预览失效
+fn fingerprint(source: &[u8], changes: &[MergeChange]) -> String {
+    let mut input = source.to_vec();
+    input.extend(serde_json::to_vec(changes).unwrap_or_default());
+    digest(&SHA256, &input)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+    use serde_json::json;
+
+    fn provider(id: &str, name: &str) -> Provider {
+        Provider::with_id(id.into(), name.into(), json!({ "env": {} }), None)
+    }
+
+    #[test]
+    fn merge_adds_and_updates_without_deleting() {
+        let db = Database::memory().expect("memory db");
+        db.save_provider("claude", &provider("a", "A"))
+            .expect("save a");
+        db.save_provider("claude", &provider("b", "B"))
+            .expect("save b");
+        db.save_provider("claude", &provider("local", "Local"))
+            .expect("save local");
+        db.set_current_provider("claude", "a").expect("set current");
+
+        let source = Database::memory().expect("source db");
+        source
+            .save_provider("claude", &provider("a", "A renamed"))
+            .expect("save a");
+        source
+            .save_provider("claude", &provider("b", "B"))
+            .expect("save b");
+        source
+            .save_provider("claude", &provider("c", "C"))
+            .expect("save c");
+        source
+            .add_custom_endpoint("claude", "c", "https://c.example")
+            .expect("add endpoint");
+        let incoming = source.snapshot_to_memory().expect("snapshot");
+
+        let plan = db.plan_merge(&incoming).expect("plan");
+        let preview = plan.preview(b"source");
+        assert_eq!(
+            (preview.added, preview.updated, preview.unchanged),
+            (1, 1, 1)
+        );
+        let updated = preview
+            .changes
+            .iter()
+            .find(|c| c.action == MergeAction::Updated)
+            .expect("updated change");
+        assert_eq!(updated.id, "a");
+        assert_eq!(updated.changed_fields, vec!["name".to_string()]);
+
+        // 同一输入生成相同指纹，文件内容变化则指纹变化
+        assert_eq!(preview.fingerprint, plan.preview(b"source").fingerprint);
+        assert_ne!(preview.fingerprint, plan.preview(b"other").fingerprint);
+
+        db.apply_merge_plan(&plan).expect("apply");
+
+        let providers = db.get_all_providers("claude").expect("providers");
+        assert_eq!(providers.len(), 4);
+        assert_eq!(providers["a"].name, "A renamed");
+        assert!(providers.contains_key("local"));
+        assert_eq!(
+            db.get_current_provider("claude")
+                .expect("current")
+                .as_deref(),
+            Some("a")
+        );
+
+        let after = db.plan_merge(&incoming).expect("replan");
+        assert!(after.changes().is_empty());
+        assert!(after.endpoints.is_empty());
+    }
+}
@@ -22,6 +22,34 @@ impl Database {
         Ok(())
     }
 
+    /// 用 MultiAppConfig 替换数据库中的供应商、MCP、提示词与 Skills 数据
+    ///
+    /// 用于从旧版 config.json 备份恢复；设置、凭据等其他表保持不变。
+    pub fn replace_from_json(&self, config: &MultiAppConfig) -> Result<(), AppError> {
+        let mut conn = lock_conn!(self.conn);
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        for table in [
+            "provider_endpoints",
+            "providers",
+            "mcp_servers",
+            "prompts",
+            "skills",
+            "skill_repos",
+        ] {
+            tx.execute(&format!("DELETE FROM {table}"), [])
+                .map_err(|e| AppError::Database(format!("Clear {table} failed: {e}")))?;
+        }
+
+        Self::migrate_from_json_tx(&tx, config)?;
+
+        tx.commit()
+            .map_err(|e| AppError::Database(format!("Commit restore failed: {e}")))?;
+        Ok(())
+    }
+
     /// 运行迁移的 dry-run 模式（在内存数据库中验证，不写入磁盘）
     ///
     /// 用于部署前验证迁移逻辑是否正确。
@@ -84,8 +112,8 @@ impl Database {
                 tx.execute(
                     "INSERT OR REPLACE INTO providers (
                         id, app_type, name, settings_config, website_url, category,
-                        created_at, sort_index, notes, icon, icon_color, meta, is_current
-                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                        created_at, sort_index, notes, icon, icon_color, meta, is_current, is_pinned, tags
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
                     params![
                         id,
                         app_type,
@@ -100,6 +128,8 @@ impl Database {
                         provider.icon_color,
                         to_json_string(&meta_clone)?,
                         is_current,
+                        provider.is_pinned,
+                        to_json_string(&provider.tags)?,
                     ],
                 )
                 .map_err(|e| AppError::Database(format!("Migrate provider failed: {e}")))?;
@@ -128,8 +158,9 @@ impl Database {
                 tx.execute(
                     "INSERT OR REPLACE INTO mcp_servers (
                         id, name, server_config, description, homepage, docs, tags,
-                        enabled_claude, enabled_codex, enabled_gemini, enabled_qwen
-                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                        enabled_claude, enabled_codex, enabled_gemini, enabled_grok, enabled_qwen,
+                        app_overrides, enabled_claude_desktop
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
                     params![
                         id,
                         server.name,
@@ -141,7 +172,10 @@ impl Database {
                         server.apps.claude,
                         server.apps.codex,
                         server.apps.gemini,
+                        server.apps.grok,
                         server.apps.qwen,
+                        to_json_string(&server.app_overrides)?,
+                        server.apps.claude_desktop,
                     ],
                 )
                 .map_err(|e| AppError::Database(format!("Migrate mcp server failed: {e}")))?;
@@ -204,8 +238,15 @@ impl Database {
 
         for repo in &config.skills.repos {
             tx.execute(
-                "INSERT OR REPLACE INTO skill_repos (owner, name, branch, enabled) VALUES (?1, ?2, ?3, ?4)",
-                params![repo.owner, repo.name, repo.branch, repo.enabled],
+                "INSERT OR REPLACE INTO skill_repos (owner, name, branch, enabled, base_url, provider) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    repo.owner,
+                    repo.name,
+                    repo.branch,
+                    repo.enabled,
+                    repo.base_url,
+                    repo.provider.as_str()
+                ],
             ).map_err(|e| AppError::Database(format!("Migrate skill repo failed: {e}")))?;
         }
 
@@ -6,6 +6,11 @@
 //! - 提示词管理
 //! - Skills 管理
 //! - 通用设置存储
+//! - 设备级设置（多进程安全）
+//! - 多设备配置（云同步时各设备的目录覆盖与当前供应商）
+//! - 敏感凭据存储（不随 SQL 导出）
+//! - 用量记录（预算统计）
+//! - 供应商附件
 //!
 //! ## 架构设计
 //!
@@ -14,40 +19,80 @@
 //! ├── mod.rs        - Database 结构体 + 初始化
 //! ├── schema.rs     - 表结构定义 + Schema 迁移
 //! ├── backup.rs     - SQL 导入导出 + 快照备份
+//! ├── export.rs     - 选择性导出 + 密钥脱敏
+//! ├── json_export.rs - JSON 导出导入（便于 diff）
+//! ├── crypto.rs     - 敏感凭据加密
+//! ├── health.rs     - 完整性检查 + 自修复
+//! ├── lease.rs      - 写入租约（检测其他写入进程）
+//! ├── maintenance.rs - VACUUM / ANALYZE 压缩
+//! ├── merge.rs      - SQL 增量导入（预览 + 确认合并）
+//! ├── restore.rs    - 按资源类型部分恢复备份
 //! ├── migration.rs  - JSON → SQLite 数据迁移
 //! └── dao/          - 数据访问对象
 //!     ├── providers.rs
+//!     ├── attachments.rs
+//!     ├── device_settings.rs
+//!     ├── devices.rs
+//!     ├── gemini_oauth.rs
+//!     ├── live_state.rs
 //!     ├── mcp.rs
 //!     ├── prompts.rs
+//!     ├── prompt_sets.rs
+//!     ├── projects.rs
 //!     ├── skills.rs
-//!     └── settings.rs
+//!     ├── settings.rs
+//!     ├── secrets.rs
+//!     ├── switch_events.rs
+//!     └── usage.rs
 //! ```
 
 mod backup;
+mod crypto;
 mod dao;
+mod export;
+mod health;
+mod json_export;
+mod lease;
+mod maintenance;
+mod merge;
 mod migration;
+mod restore;
 mod schema;
 
 #[cfg(test)]
-mod test_v1_version;
+mod test_schema_version;
 
 #[cfg(test)]
 mod tests;
 
 use crate::config::get_app_config_dir;
 use crate::error::AppError;
+use crypto::SecretKey;
+use lease::LeaseState;
 use rusqlite::Connection;
 use serde::Serialize;
 use std::sync::Mutex;
+use std::time::Duration;
 
 // DAO 方法通过 impl Database 提供，无需额外导出
-
-/// 数据库备份保留数量
-const DB_BACKUP_RETAIN: usize = 10;
+pub use backup::DbBackupInfo;
+pub use export::ExportOptions;
+pub(crate) use export::{is_sensitive_key, redact_value};
+pub use health::DbHealthReport;
+pub use lease::DbLeaseStatus;
+pub(crate) use lease::LEASE_TTL_SECS;
+pub use maintenance::CompactReport;
+pub use merge::{MergeAction, MergeChange, MergeKind, MergePreview};
+pub use restore::RestoreResource;
+
+/// 数据库被其他连接锁定时的最长等待时间
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// 当前 Schema 版本号
-/// 保持v1以确保兼容性
-pub(crate) const SCHEMA_VERSION: i32 = 1;
+///
+/// v2：JSON 列 CHECK 约束、提示词组成员外键与常用查询索引。
+/// 更高版本的数据库（如官方版本创建）以兼容模式运行，不降级。
+pub(crate) const SCHEMA_VERSION: i32 = 2;
 
 /// 安全地序列化 JSON，避免 unwrap panic
 pub(crate) fn to_json_string<T: Serialize>(value: &T) -> Result<String, AppError> {
@@ -73,6 +118,10 @@ pub(crate) use lock_conn;
 /// rusqlite::Connection 本身不是 Sync 的，因此需要这层包装。
 pub struct Database {
     pub(crate) conn: Mutex<Connection>,
+    /// secrets 表的加密密钥
+    pub(crate) secret_key: SecretKey,
+    /// 写入租约状态（多进程访问检测）
+    pub(crate) lease: LeaseState,
 }
 
 impl Database {
@@ -88,20 +137,54 @@ impl Database {
         }
 
         let conn = Connection::open(&db_path).map_err(|e| AppError::Database(e.to_string()))?;
+        Self::configure_connection(&conn)?;
 
-        // 启用外键约束
-        conn.execute("PRAGMA foreign_keys = ON;", [])
-            .map_err(|e| AppError::Database(e.to_string()))?;
+        let secret_key = SecretKey::load_or_create(&get_app_config_dir().join("secret.key"))?;
 
         let db = Self {
             conn: Mutex::new(conn),
+            secret_key,
+            lease: LeaseState::new()?,
         };
         db.create_tables()?;
         db.apply_schema_migrations()?;
+        // 其他进程正在写入时以只读模式运行，由心跳任务在对方退出后接管
+        if let Err(e) = db.renew_lease() {
+            log::warn!("获取数据库写入租约失败: {e}");
+        }
 
         Ok(db)
     }
 
+    /// 配置文件数据库连接：WAL 日志、忙等待超时与外键约束
+    ///
+    /// journal_mode 会持久化到数据库文件，旧版（rollback journal）数据库首次打开时一次性切换为 WAL。
+    pub(crate) fn configure_connection(conn: &Connection) -> Result<(), AppError> {
+        conn.busy_timeout(BUSY_TIMEOUT)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute("PRAGMA foreign_keys = ON;", [])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let previous: String = conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        if !previous.eq_ignore_ascii_case("wal") {
+            let mode: String = conn
+                .query_row("PRAGMA journal_mode = WAL", [], |row| row.get(0))
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            if mode.eq_ignore_ascii_case("wal") {
+                log::info!("数据库日志模式已从 {previous} 切换为 WAL");
+            } else {
+                log::warn!("无法启用 WAL 日志模式，继续使用 {mode}");
+            }
+        }
+
+        // WAL 模式下 NORMAL 已能保证一致性，且显著减少 fsync
+        conn.execute_batch("PRAGMA synchronous = NORMAL;")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
     /// 创建内存数据库（用于测试）
     pub fn memory() -> Result<Self, AppError> {
         let conn = Connection::open_in_memory().map_err(|e| AppError::Database(e.to_string()))?;
@@ -112,6 +195,8 @@ impl Database {
 
         let db = Self {
             conn: Mutex::new(conn),
+            secret_key: SecretKey::generate()?,
+            lease: LeaseState::new()?,
         };
         db.create_tables()?;
 
@@ -0,0 +1,233 @@
+//! 部分恢复：只从数据库备份恢复选中的资源类型
+//!
+//! 误删提示词后恢复备份，不应连带回滚供应商的修改。选中资源对应的表在同一事务内
+//! 清空并从备份复制（按两边共有的列），其余表保持不变。
+
+use super::{lock_conn, Database};
+use crate::error::AppError;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// 附加备份库时使用的 schema 名
+const SOURCE_SCHEMA: &str = "restore_source";
+
+/// 可单独恢复的资源类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RestoreResource {
+    Providers,
+    Mcp,
+    Prompts,
+    Skills,
+    Settings,
+}
+
+impl RestoreResource {
+    /// 资源对应的表（用量记录与切换历史属于日志，不随供应商恢复）
+    fn tables(self) -> &'static [&'static str] {
+        match self {
+            Self::Providers => &[
+                "providers",
+                "provider_endpoints",
+                "provider_attachments",
+                "gemini_oauth_accounts",
+                "projects",
+                "project_providers",
+            ],
+            Self::Mcp => &["mcp_servers"],
+            Self::Prompts => &["prompts", "prompt_sets", "prompt_set_members"],
+            Self::Skills => &["skills", "skill_repos"],
+            Self::Settings => &["settings", "device_settings", "devices"],
+        }
+    }
+}
+
+impl Database {
+    /// 从数据库备份恢复选中的资源，返回恢复前生成的安全备份 ID
+    ///
+    /// 备份中缺少的表（旧版本备份）会跳过并保留当前数据。
+    pub fn restore_from_backup(
+        &self,
+        backup_id: &str,
+        resources: &[RestoreResource],
+    ) -> Result<String, AppError> {
+        if resources.is_empty() {
+            return Err(AppError::InvalidInput("未选择要恢复的资源".to_string()));
+        }
+        let source_path = Self::db_backup_path(backup_id)?;
+
+        // 恢复前备份当前数据库，便于撤销
+        let safety_backup = self.backup_database_file()?;
+
+        {
+            let mut conn = lock_conn!(self.conn);
+            conn.execute(
+                &format!("ATTACH DATABASE ?1 AS {SOURCE_SCHEMA}"),
+                [source_path.to_string_lossy()],
+            )
+            .map_err(|e| AppError::Database(format!("打开备份失败: {e}")))?;
+            // 外键级联不能在事务内关闭，需在事务外切换
+            let _ = conn.execute_batch("PRAGMA foreign_keys = OFF;");
+
+            let result = Self::copy_resources(&mut conn, resources);
+
+            let _ = conn.execute_batch("PRAGMA foreign_keys = ON;");
+            if let Err(e) = conn.execute_batch(&format!("DETACH DATABASE {SOURCE_SCHEMA};")) {
+                log::warn!("分离备份库失败: {e}");
+            }
+            result?;
+        }
+
+        log::info!("已从备份 {backup_id} 恢复: {resources:?}");
+        Ok(safety_backup
+            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap_or_default())
+    }
+
+    fn copy_resources(
+        conn: &mut Connection,
+        resources: &[RestoreResource],
+    ) -> Result<(), AppError> {
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut tables: Vec<&str> = Vec::new();
+        for table in resources.iter().flat_map(|r| r.tables()) {
+            if !tables.contains(table) {
+                tables.push(table);
+            }
+        }
+        for table in tables {
+            let source_columns = Self::schema_columns(&tx, SOURCE_SCHEMA, table)?;
+            if source_columns.is_empty() {
+                log::warn!("备份中没有 {table} 表，保留当前数据");
+                continue;
+            }
+            let columns = Self::schema_columns(&tx, "main", table)?
+                .into_iter()
+                .filter(|column| source_columns.contains(column))
+                .map(|column| format!("\"{column}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            tx.execute(&format!("DELETE FROM main.\"{table}\""), [])
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            tx.execute(
+                &format!(
+                    "INSERT INTO main.\"{table}\" ({columns}) \
+                     SELECT {columns} FROM {SOURCE_SCHEMA}.\"{table}\""
+                ),
+                [],
+            )
+            .map_err(|e| AppError::Database(format!("恢复 {table} 失败: {e}")))?;
+        }
+
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 指定 schema 下表的列名（表不存在时为空）
+    fn schema_columns(
+        conn: &Connection,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<String>, AppError> {
+        let mut stmt = conn
+            .prepare(&format!("PRAGMA {schema}.table_info(\"{table}\")"))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let iter = stmt
+            .query_map([], |row| row.get::<_, String>(1))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        iter.collect::<Result<_, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+    use serde_json::json;
+
+    #[test]
+    fn copy_resources_only_touches_selected_tables() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let backup_path = dir.path().join("backup.db");
+
+        let backup = Connection::open(&backup_path).expect("open backup");
+        Database::create_tables_on_conn(&backup).expect("create tables");
+        backup
+            .execute(
+                "INSERT INTO prompts (id, app_type, name, content)
+                 VALUES ('p1', 'claude', 'Old', 'x')",
+                [],
+            )
+            .expect("insert prompt");
+        drop(backup);
+
+        let db = Database::memory().expect("memory db");
+        db.save_provider(
+            "claude",
+            &Provider::with_id("new".into(), "New".into(), json!({}), None),
+        )
+        .expect("save provider");
+
+        let mut conn = db.conn.lock().expect("lock");
+        conn.execute(
+            &format!("ATTACH DATABASE ?1 AS {SOURCE_SCHEMA}"),
+            [backup_path.to_string_lossy()],
+        )
+        .expect("attach");
+        Database::copy_resources(&mut conn, &[RestoreResource::Prompts]).expect("restore");
+
+        let prompts: i64 = conn
+            .query_row("SELECT COUNT(*) FROM main.prompts", [], |row| row.get(0))
+            .expect("count prompts");
+        let providers: i64 = conn
+            .query_row("SELECT COUNT(*) FROM main.providers", [], |row| row.get(0))
+            .expect("count providers");
+        assert_eq!(prompts, 1);
+        assert_eq!(providers, 1);
+    }
+
+    #[test]
+    fn settings_restore_includes_device_settings() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let backup_path = dir.path().join("backup.db");
+
+        let backup = Connection::open(&backup_path).expect("open backup");
+        Database::create_tables_on_conn(&backup).expect("create tables");
+        backup
+            .execute(
+                "INSERT INTO device_settings (key, value, revision, updated_at)
+                 VALUES ('language', '\"ja\"', 1, 0)",
+                [],
+            )
+            .expect("insert device setting");
+        drop(backup);
+
+        let db = Database::memory().expect("memory db");
+        let mut conn = db.conn.lock().expect("lock");
+        conn.execute(
+            "INSERT INTO device_settings (key, value, revision, updated_at)
+             VALUES ('language', '\"en\"', 2, 0)",
+            [],
+        )
+        .expect("insert current setting");
+        conn.execute(
+            &format!("ATTACH DATABASE ?1 AS {SOURCE_SCHEMA}"),
+            [backup_path.to_string_lossy()],
+        )
+        .expect("attach");
+        Database::copy_resources(&mut conn, &[RestoreResource::Settings]).expect("restore");
+
+        let language: String = conn
+            .query_row(
+                "SELECT value FROM main.device_settings WHERE key = 'language'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("read language");
+        assert_eq!(language, "\"ja\"");
+    }
+}
@@ -6,6 +6,159 @@ use super::{lock_conn, Database, SCHEMA_VERSION};
 use crate::error::AppError;
 use rusqlite::Connection;
 
+/// v2 起带 CHECK / 外键约束的表：约束无法通过 ALTER TABLE 添加，升级时需重建
+struct TableDef {
+    name: &'static str,
+    columns: &'static str,
+    /// JSON 列及其非法内容的替代值（重建时修正，避免违反 CHECK 约束）
+    json_columns: &'static [(&'static str, &'static str)],
+}
+
+impl TableDef {
+    fn create_sql(&self, name: &str) -> String {
+        format!("CREATE TABLE IF NOT EXISTS {name} ({})", self.columns)
+    }
+}
+
+const PROVIDERS_TABLE: TableDef = TableDef {
+    name: "providers",
+    columns: "
+        id TEXT NOT NULL,
+        app_type TEXT NOT NULL,
+        name TEXT NOT NULL,
+        settings_config TEXT NOT NULL CHECK (json_valid(settings_config)),
+        website_url TEXT,
+        category TEXT,
+        created_at INTEGER,
+        sort_index INTEGER,
+        notes TEXT,
+        icon TEXT,
+        icon_color TEXT,
+        meta TEXT NOT NULL DEFAULT '{}' CHECK (json_valid(meta)),
+        is_current BOOLEAN NOT NULL DEFAULT 0,
+        is_pinned BOOLEAN NOT NULL DEFAULT 0,
+        tags TEXT NOT NULL DEFAULT '[]' CHECK (json_valid(tags)),
+        PRIMARY KEY (id, app_type)
+    ",
+    json_columns: &[("settings_config", "{}"), ("meta", "{}"), ("tags", "[]")],
+};
+
+const MCP_SERVERS_TABLE: TableDef = TableDef {
+    name: "mcp_servers",
+    columns: "
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        server_config TEXT NOT NULL CHECK (json_valid(server_config)),
+        description TEXT,
+        homepage TEXT,
+        docs TEXT,
+        tags TEXT NOT NULL DEFAULT '[]' CHECK (json_valid(tags)),
+        enabled_claude BOOLEAN NOT NULL DEFAULT 0,
+        enabled_codex BOOLEAN NOT NULL DEFAULT 0,
+        enabled_gemini BOOLEAN NOT NULL DEFAULT 0,
+        enabled_grok BOOLEAN NOT NULL DEFAULT 0,
+        enabled_qwen BOOLEAN NOT NULL DEFAULT 0,
+        enabled_claude_desktop BOOLEAN NOT NULL DEFAULT 0,
+        app_overrides TEXT NOT NULL DEFAULT '{}' CHECK (json_valid(app_overrides))
+    ",
+    json_columns: &[
+        ("server_config", "{}"),
+        ("tags", "[]"),
+        ("app_overrides", "{}"),
+    ],
+};
+
+const PROMPTS_TABLE: TableDef = TableDef {
+    name: "prompts",
+    columns: "
+        id TEXT NOT NULL,
+        app_type TEXT NOT NULL,
+        name TEXT NOT NULL,
+        content TEXT NOT NULL,
+        description TEXT,
+        enabled BOOLEAN NOT NULL DEFAULT 1,
+        created_at INTEGER,
+        updated_at INTEGER,
+        tags TEXT NOT NULL DEFAULT '[]' CHECK (json_valid(tags)),
+        target_apps TEXT NOT NULL DEFAULT '[]' CHECK (json_valid(target_apps)),
+        apps TEXT NOT NULL DEFAULT '{}' CHECK (json_valid(apps)),
+        PRIMARY KEY (id, app_type)
+    ",
+    json_columns: &[("tags", "[]"), ("target_apps", "[]"), ("apps", "{}")],
+};
+
+/// 提示词组成员：成员随提示词组或提示词删除
+const PROMPT_SET_MEMBERS_TABLE: TableDef = TableDef {
+    name: "prompt_set_members",
+    columns: "
+        set_id TEXT NOT NULL,
+        app_type TEXT NOT NULL,
+        prompt_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        PRIMARY KEY (set_id, app_type, prompt_id),
+        FOREIGN KEY (set_id, app_type) REFERENCES prompt_sets(id, app_type) ON DELETE CASCADE,
+        FOREIGN KEY (prompt_id, app_type) REFERENCES prompts(id, app_type) ON DELETE CASCADE
+    ",
+    json_columns: &[],
+};
+
+/// v1 -> v2 需要重建的表（按依赖顺序）
+const REBUILT_TABLES: &[&TableDef] = &[
+    &PROVIDERS_TABLE,
+    &MCP_SERVERS_TABLE,
+    &PROMPTS_TABLE,
+    &PROMPT_SET_MEMBERS_TABLE,
+];
+
+/// v2 索引：(表名, 索引名, 定义)
+const INDEXES: &[(&str, &str, &str)] = &[
+    (
+        "providers",
+        "idx_providers_current",
+        "ON providers (app_type, is_current)",
+    ),
+    (
+        "provider_endpoints",
+        "idx_provider_endpoints_provider",
+        "ON provider_endpoints (app_type, provider_id)",
+    ),
+    (
+        "prompt_set_members",
+        "idx_prompt_set_members_prompt",
+        "ON prompt_set_members (app_type, prompt_id)",
+    ),
+    (
+        "mcp_servers",
+        "idx_mcp_servers_enabled_claude",
+        "ON mcp_servers (id) WHERE enabled_claude = 1",
+    ),
+    (
+        "mcp_servers",
+        "idx_mcp_servers_enabled_codex",
+        "ON mcp_servers (id) WHERE enabled_codex = 1",
+    ),
+    (
+        "mcp_servers",
+        "idx_mcp_servers_enabled_gemini",
+        "ON mcp_servers (id) WHERE enabled_gemini = 1",
+    ),
+    (
+        "mcp_servers",
+        "idx_mcp_servers_enabled_grok",
+        "ON mcp_servers (id) WHERE enabled_grok = 1",
+    ),
+    (
+        "mcp_servers",
+        "idx_mcp_servers_enabled_qwen",
+        "ON mcp_servers (id) WHERE enabled_qwen = 1",
+    ),
+    (
+        "mcp_servers",
+        "idx_mcp_servers_enabled_claude_desktop",
+        "ON mcp_servers (id) WHERE enabled_claude_desktop = 1",
+    ),
+];
+
 impl Database {
     /// 创建所有数据库表
     pub(crate) fn create_tables(&self) -> Result<(), AppError> {
@@ -15,27 +168,9 @@ impl Database {
 
     /// 在指定连接上创建表（供迁移和测试使用）
     pub(crate) fn create_tables_on_conn(conn: &Connection) -> Result<(), AppError> {
-        // 1. Providers 表 (v1版本，不包含新字段)
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS providers (
-                id TEXT NOT NULL,
-                app_type TEXT NOT NULL,
-                name TEXT NOT NULL,
-                settings_config TEXT NOT NULL,
-                website_url TEXT,
-                category TEXT,
-                created_at INTEGER,
-                sort_index INTEGER,
-                notes TEXT,
-                icon TEXT,
-                icon_color TEXT,
-                meta TEXT NOT NULL DEFAULT '{}',
-                is_current BOOLEAN NOT NULL DEFAULT 0,
-                PRIMARY KEY (id, app_type)
-            )",
-            [],
-        )
-        .map_err(|e| AppError::Database(e.to_string()))?;
+        // 1. Providers 表
+        conn.execute(&PROVIDERS_TABLE.create_sql(PROVIDERS_TABLE.name), [])
+            .map_err(|e| AppError::Database(e.to_string()))?;
 
         // 2. Provider Endpoints 表
         conn.execute(
@@ -52,33 +187,23 @@ impl Database {
         .map_err(|e| AppError::Database(e.to_string()))?;
 
         // 3. MCP Servers 表
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS mcp_servers (
-                id TEXT PRIMARY KEY,
-                name TEXT NOT NULL,
-                server_config TEXT NOT NULL,
-                description TEXT,
-                homepage TEXT,
-                docs TEXT,
-                tags TEXT NOT NULL DEFAULT '[]',
-                enabled_claude BOOLEAN NOT NULL DEFAULT 0,
-                enabled_codex BOOLEAN NOT NULL DEFAULT 0,
-                enabled_gemini BOOLEAN NOT NULL DEFAULT 0,
-                enabled_qwen BOOLEAN NOT NULL DEFAULT 0
-            )",
-            [],
-        )
-        .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(&MCP_SERVERS_TABLE.create_sql(MCP_SERVERS_TABLE.name), [])
+            .map_err(|e| AppError::Database(e.to_string()))?;
 
         // 4. Prompts 表
+        conn.execute(&PROMPTS_TABLE.create_sql(PROMPTS_TABLE.name), [])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 4.1 Prompt Sets 表 (提示词组，按顺序组合多个提示词写入 live 文件)
         conn.execute(
-            "CREATE TABLE IF NOT EXISTS prompts (
+            "CREATE TABLE IF NOT EXISTS prompt_sets (
                 id TEXT NOT NULL,
                 app_type TEXT NOT NULL,
                 name TEXT NOT NULL,
-                content TEXT NOT NULL,
                 description TEXT,
-                enabled BOOLEAN NOT NULL DEFAULT 1,
+                mode TEXT NOT NULL DEFAULT 'concat',
+                selected_prompt_id TEXT,
+                enabled BOOLEAN NOT NULL DEFAULT 0,
                 created_at INTEGER,
                 updated_at INTEGER,
                 PRIMARY KEY (id, app_type)
@@ -86,6 +211,11 @@ impl Database {
             [],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            &PROMPT_SET_MEMBERS_TABLE.create_sql(PROMPT_SET_MEMBERS_TABLE.name),
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
 
         // 5. Skills 表
         conn.execute(
@@ -105,6 +235,8 @@ impl Database {
                 name TEXT NOT NULL,
                 branch TEXT NOT NULL DEFAULT 'main',
                 enabled BOOLEAN NOT NULL DEFAULT 1,
+                base_url TEXT,
+                provider TEXT NOT NULL DEFAULT 'github',
                 PRIMARY KEY (owner, name)
             )",
             [],
@@ -121,6 +253,237 @@ impl Database {
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        // 8. Usage History 表 (用量记录，用于预算统计)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS usage_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                provider_id TEXT NOT NULL,
+                app_type TEXT NOT NULL,
+                recorded_at INTEGER NOT NULL,
+                tokens INTEGER NOT NULL DEFAULT 0,
+                cost REAL NOT NULL DEFAULT 0,
+                raw TEXT,
+                model TEXT,
+                input_tokens INTEGER NOT NULL DEFAULT 0,
+                output_tokens INTEGER NOT NULL DEFAULT 0
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_usage_history_provider
+                ON usage_history (app_type, provider_id, recorded_at)",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 9. Secrets 表 (敏感凭据，如私有 Skill 仓库的访问令牌；SQL 导出时不包含数据)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS secrets (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 10. Gemini OAuth 账号表 (凭据本身加密存放于 secrets 表)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS gemini_oauth_accounts (
+                id TEXT PRIMARY KEY,
+                label TEXT NOT NULL,
+                email TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 11. Live 文件状态表 (记录最近一次写入的文件哈希，用于检测外部修改；本机数据，SQL 导出时不包含)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS live_state (
+                app_type TEXT NOT NULL,
+                path TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                written_at INTEGER NOT NULL,
+                PRIMARY KEY (app_type, path)
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 12. Provider Attachments 表 (供应商附件，如发票、接入文档；随供应商删除)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_attachments (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                provider_id TEXT NOT NULL,
+                app_type TEXT NOT NULL,
+                file_name TEXT NOT NULL,
+                mime_type TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                data BLOB NOT NULL,
+                created_at INTEGER NOT NULL,
+                FOREIGN KEY (provider_id, app_type) REFERENCES providers(id, app_type) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_provider_attachments_provider
+                ON provider_attachments (app_type, provider_id)",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 13. MCP live 写入记录表 (cc-switch 曾写入各应用 live 配置的服务器，用于识别孤立条目；本机数据，SQL 导出时不包含)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS mcp_live_owned (
+                app_type TEXT NOT NULL,
+                server_id TEXT NOT NULL,
+                written_at INTEGER NOT NULL,
+                PRIMARY KEY (app_type, server_id)
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 14. Projects 表 (项目目录与各应用期望供应商的映射，用于按项目一键切换)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS projects (
+                path TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS project_providers (
+                project_path TEXT NOT NULL,
+                app_type TEXT NOT NULL,
+                provider_id TEXT NOT NULL,
+                PRIMARY KEY (project_path, app_type),
+                FOREIGN KEY (project_path) REFERENCES projects(path) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 15. Switch Events 表 (供应商切换记录，用于使用统计)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS switch_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                app_type TEXT NOT NULL,
+                provider_id TEXT NOT NULL,
+                switched_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_switch_events_time
+                ON switch_events (app_type, switched_at)",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 16. Device Settings 表 (设备级设置，按字段存储以合并多进程写入；本机数据，SQL 导出时不包含)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS device_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL CHECK (json_valid(value)),
+                revision INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 17. Devices 表 (数据库在多台设备间同步时，各设备的目录覆盖与当前供应商)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS devices (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                hostname TEXT NOT NULL DEFAULT '',
+                config_dirs TEXT NOT NULL DEFAULT '{}' CHECK (json_valid(config_dirs)),
+                current_providers TEXT NOT NULL DEFAULT '{}' CHECK (json_valid(current_providers)),
+                last_seen_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Self::ensure_extension_columns(conn)?;
+        Self::create_indexes_on_conn(conn)?;
+
+        Ok(())
+    }
+
+    /// 创建 v2 索引（表不存在时跳过，供建表与迁移使用）
+    fn create_indexes_on_conn(conn: &Connection) -> Result<(), AppError> {
+        for (table, name, definition) in INDEXES {
+            if !Self::table_exists(conn, table)? {
+                continue;
+            }
+            conn.execute(
+                &format!("CREATE INDEX IF NOT EXISTS {name} {definition}"),
+                [],
+            )
+            .map_err(|e| AppError::Database(format!("创建索引 {name} 失败: {e}")))?;
+        }
+        Ok(())
+    }
+
+    /// 补齐 v1 之后新增的扩展列
+    ///
+    /// v2 的建表语句已包含这些列；v1 数据库在升级前仍按需补齐（已存在则跳过）。
+    fn ensure_extension_columns(conn: &Connection) -> Result<(), AppError> {
+        Self::add_column_if_missing(
+            conn,
+            "mcp_servers",
+            "enabled_grok",
+            "BOOLEAN NOT NULL DEFAULT 0",
+        )?;
+        Self::add_column_if_missing(
+            conn,
+            "mcp_servers",
+            "app_overrides",
+            "TEXT NOT NULL DEFAULT '{}'",
+        )?;
+        Self::add_column_if_missing(
+            conn,
+            "mcp_servers",
+            "enabled_claude_desktop",
+            "BOOLEAN NOT NULL DEFAULT 0",
+        )?;
+        Self::add_column_if_missing(conn, "providers", "is_pinned", "BOOLEAN NOT NULL DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "providers", "tags", "TEXT NOT NULL DEFAULT '[]'")?;
+        Self::add_column_if_missing(conn, "prompts", "tags", "TEXT NOT NULL DEFAULT '[]'")?;
+        Self::add_column_if_missing(conn, "prompts", "target_apps", "TEXT NOT NULL DEFAULT '[]'")?;
+        Self::add_column_if_missing(conn, "prompts", "apps", "TEXT NOT NULL DEFAULT '{}'")?;
+        Self::add_column_if_missing(conn, "usage_history", "model", "TEXT")?;
+        Self::add_column_if_missing(
+            conn,
+            "usage_history",
+            "input_tokens",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+        Self::add_column_if_missing(
+            conn,
+            "usage_history",
+            "output_tokens",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+        Self::add_column_if_missing(conn, "skill_repos", "base_url", "TEXT")?;
+        Self::add_column_if_missing(
+            conn,
+            "skill_repos",
+            "provider",
+            "TEXT NOT NULL DEFAULT 'github'",
+        )?;
         Ok(())
     }
 
@@ -131,7 +494,27 @@ impl Database {
     }
 
     /// 在指定连接上应用 Schema 迁移
+    ///
+    /// 迁移期间关闭外键：重建表时 DROP TABLE 会触发级联删除，而该 PRAGMA 在事务内无效。
     pub(crate) fn apply_schema_migrations_on_conn(conn: &Connection) -> Result<(), AppError> {
+        let foreign_keys: bool = conn
+            .query_row("PRAGMA foreign_keys;", [], |row| row.get(0))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        if foreign_keys {
+            conn.execute_batch("PRAGMA foreign_keys = OFF;")
+                .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        let result = Self::apply_schema_migrations_in_savepoint(conn);
+
+        if foreign_keys {
+            conn.execute_batch("PRAGMA foreign_keys = ON;")
+                .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        result
+    }
+
+    fn apply_schema_migrations_in_savepoint(conn: &Connection) -> Result<(), AppError> {
         conn.execute("SAVEPOINT schema_migration;", [])
             .map_err(|e| AppError::Database(format!("开启迁移 savepoint 失败: {e}")))?;
 
@@ -154,7 +537,11 @@ impl Database {
                         Self::migrate_v0_to_v1(conn)?;
                         Self::set_user_version(conn, 1)?;
                     }
-                    // 保持v1版本，不执行进一步迁移
+                    1 => {
+                        log::info!("数据库迁移：v1 -> v2（约束、外键与索引）");
+                        Self::migrate_v1_to_v2(conn)?;
+                        Self::set_user_version(conn, 2)?;
+                    }
                     _ => {
                         return Err(AppError::Database(format!(
                             "未知的数据库版本 {version}，无法迁移到 {SCHEMA_VERSION}"
@@ -246,6 +633,104 @@ impl Database {
         Ok(())
     }
 
+    /// v1 -> v2 迁移：重建带约束的表并创建索引
+    ///
+    /// 非法 JSON 替换为空值（健康检查会在升级前报告这类数据），
+    /// 指向已删除提示词的提示词组成员会被清理。
+    fn migrate_v1_to_v2(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "prompt_set_members")? && Self::table_exists(conn, "prompts")? {
+            let removed = conn
+                .execute(
+                    "DELETE FROM prompt_set_members WHERE NOT EXISTS (
+                        SELECT 1 FROM prompts
+                        WHERE prompts.id = prompt_set_members.prompt_id
+                          AND prompts.app_type = prompt_set_members.app_type
+                    )",
+                    [],
+                )
+                .map_err(|e| AppError::Database(format!("清理提示词组成员失败: {e}")))?;
+            if removed > 0 {
+                log::warn!("已清理 {removed} 个指向不存在提示词的提示词组成员");
+            }
+        }
+
+        for table in REBUILT_TABLES {
+            Self::rebuild_table(conn, table)?;
+        }
+        Self::create_indexes_on_conn(conn)
+    }
+
+    /// 按新定义重建表：建新表、复制两边共有的列、替换旧表
+    fn rebuild_table(conn: &Connection, table: &TableDef) -> Result<(), AppError> {
+        if !Self::table_exists(conn, table.name)? {
+            return Ok(());
+        }
+        let name = table.name;
+        let staging = format!("{name}_v2");
+        conn.execute_batch(&format!(
+            "DROP TABLE IF EXISTS {staging}; {};",
+            table.create_sql(&staging)
+        ))
+        .map_err(|e| AppError::Database(format!("创建 {staging} 失败: {e}")))?;
+
+        let mut columns = Vec::new();
+        let mut values = Vec::new();
+        for column in Self::table_columns(conn, &staging)? {
+            if !Self::has_column(conn, name, &column)? {
+                continue;
+            }
+            match table.json_columns.iter().find(|(json, _)| *json == column) {
+                Some((_, fallback)) => {
+                    let invalid: i64 = conn
+                        .query_row(
+                            &format!(
+                                "SELECT COUNT(*) FROM {name} WHERE NOT json_valid(\"{column}\")"
+                            ),
+                            [],
+                            |row| row.get(0),
+                        )
+                        .map_err(|e| AppError::Database(e.to_string()))?;
+                    if invalid > 0 {
+                        log::warn!(
+                            "{name}.{column} 中有 {invalid} 行不是有效 JSON，已替换为 {fallback}"
+                        );
+                    }
+                    values.push(format!(
+                        "CASE WHEN json_valid(\"{column}\") THEN \"{column}\" ELSE '{fallback}' END"
+                    ));
+                }
+                None => values.push(format!("\"{column}\"")),
+            }
+            columns.push(format!("\"{column}\""));
+        }
+
+        conn.execute_batch(&format!(
+            "INSERT INTO {staging} ({}) SELECT {} FROM {name};
+             DROP TABLE {name};
+             ALTER TABLE {staging} RENAME TO {name};",
+            columns.join(", "),
+            values.join(", ")
+        ))
+        .map_err(|e| AppError::Database(format!("重建表 {name} 失败: {e}")))
+    }
+
+    fn table_columns(conn: &Connection, table: &str) -> Result<Vec<String>, AppError> {
+        let mut stmt = conn
+            .prepare(&format!("PRAGMA table_info(\"{table}\");"))
+            .map_err(|e| AppError::Database(format!("读取表结构失败: {e}")))?;
+        let iter = stmt
+            .query_map([], |row| row.get::<_, String>(1))
+            .map_err(|e| AppError::Database(format!("查询表结构失败: {e}")))?;
+        iter.collect::<Result<_, _>>()
+            .map_err(|e| AppError::Database(format!("读取列名失败: {e}")))
+    }
+
+    /// 当前数据库的 Schema 版本（`PRAGMA user_version`）
+    pub fn schema_version(&self) -> Result<i32, AppError> {
+        let conn = lock_conn!(self.conn);
+        Self::get_user_version(&conn)
+    }
+
     // --- 辅助方法 ---
 
     pub(crate) fn get_user_version(conn: &Connection) -> Result<i32, AppError> {
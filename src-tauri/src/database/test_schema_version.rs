@@ -1,10 +1,10 @@
-//! 测试数据库版本保持在v1
+//! 测试数据库版本升级到 v2
 
 use crate::database::{Database, SCHEMA_VERSION};
 use crate::error::AppError;
 
 #[test]
-fn test_database_version_stays_v1() -> Result<(), AppError> {
+fn test_database_version_is_v2() -> Result<(), AppError> {
     // 创建内存数据库
     let db = Database::memory()?;
 
@@ -15,19 +15,21 @@ fn test_database_version_stays_v1() -> Result<(), AppError> {
     // 获取数据库版本
     let version = Database::get_user_version(&*conn)?;
 
-    // 验证版本保持在1
+    // 验证版本为2
     assert_eq!(version, SCHEMA_VERSION);
-    assert_eq!(version, 1);
+    assert_eq!(version, 2);
 
     // 验证数据库表存在
     assert!(Database::table_exists(&*conn, "providers")?);
 
-    // 验证v2/v3字段不存在（保持v1）
+    // 验证官方版本的 v2/v3 字段不存在
     assert!(!Database::has_column(&*conn, "providers", "is_duplicated")?);
     assert!(!Database::has_column(&*conn, "providers", "is_edited_after_duplication")?);
-    assert!(!Database::has_column(&*conn, "providers", "is_pinned")?);
 
-    println!("✅ 数据库版本成功保持在 v1");
+    // 置顶状态已纳入 v2 建表语句
+    assert!(Database::has_column(&*conn, "providers", "is_pinned")?);
+
+    println!("✅ 数据库版本成功升级到 v2");
 
     Ok(())
-}
\ No newline at end of file
+}
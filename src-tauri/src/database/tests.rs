@@ -254,6 +254,7 @@ fn dry_run_validates_schema_compatibility() {
             is_pinned: false,
             is_duplicated: None,
             is_edited_after_duplication: None,
+            tags: Vec::new(),
         },
     );
 
@@ -281,3 +282,563 @@ fn dry_run_validates_schema_compatibility() {
         "Dry-run should succeed with provider data: {result:?}"
     );
 }
+
+#[test]
+fn skill_repo_token_is_stored_as_secret_and_not_exported() {
+    use crate::services::skill::{SkillRepo, SkillRepoProvider};
+
+    let db = Database::memory().expect("memory db");
+    let mut repo = SkillRepo::new("acme", "internal-skills", "main");
+    repo.base_url = Some("https://git.acme.dev".to_string());
+    repo.token = Some("ghp_secret".to_string());
+    db.save_skill_repo(&repo).expect("save repo");
+
+    let loaded = db.get_skill_repos().expect("load repos");
+    assert_eq!(loaded.len(), 1);
+    assert!(loaded[0].has_token);
+    assert_eq!(loaded[0].token.as_deref(), Some("ghp_secret"));
+    assert_eq!(loaded[0].base_url.as_deref(), Some("https://git.acme.dev"));
+    assert_eq!(loaded[0].provider, SkillRepoProvider::Github);
+
+    let serialized = serde_json::to_string(&loaded[0]).expect("serialize repo");
+    assert!(!serialized.contains("ghp_secret"));
+
+    let snapshot = db.snapshot_to_memory().expect("snapshot");
+    let dump = Database::dump_sql(&snapshot).expect("dump sql");
+    assert!(!dump.contains("ghp_secret"));
+
+    db.delete_skill_repo("acme", "internal-skills")
+        .expect("delete repo");
+    assert!(db
+        .get_secret(&SkillRepo::token_secret_key("acme", "internal-skills"))
+        .expect("read secret")
+        .is_none());
+}
+
+#[test]
+fn import_keeps_local_secrets() {
+    let db = Database::memory().expect("memory db");
+    db.set_secret("app_lock.passcode", "hash")
+        .expect("set secret");
+    let dir = tempfile::tempdir().expect("tempdir");
+    let export_path = dir.path().join("export.sql");
+    db.export_sql(&export_path).expect("export");
+    assert!(!std::fs::read_to_string(&export_path)
+        .unwrap()
+        .contains("app_lock.passcode"));
+
+    db.set_secret("skill_repo.token", "ghp_after_export")
+        .expect("set secret");
+    db.import_sql(&export_path).expect("import sql");
+    assert_eq!(
+        db.get_secret("app_lock.passcode").expect("read").as_deref(),
+        Some("hash")
+    );
+    assert_eq!(
+        db.get_secret("skill_repo.token").expect("read").as_deref(),
+        Some("ghp_after_export")
+    );
+}
+
+#[test]
+fn restore_keeps_local_secrets() {
+    use crate::services::AppLockService;
+
+    let db = Database::memory().expect("memory db");
+    let older = db.snapshot_to_memory().expect("snapshot");
+
+    db.set_secret("app_lock.passcode", "hash")
+        .expect("set secret");
+    db.save_provider(
+        "claude",
+        &Provider::with_id("later".into(), "Later".into(), json!({}), None),
+    )
+    .expect("save provider");
+
+    db.restore_from_conn(&older).expect("restore");
+    assert!(db
+        .get_all_providers("claude")
+        .expect("providers")
+        .is_empty());
+    assert_eq!(
+        db.get_secret("app_lock.passcode").expect("read").as_deref(),
+        Some("hash")
+    );
+    assert!(AppLockService::status(&db).expect("status").enabled);
+}
+
+#[test]
+fn skill_repo_provider_round_trips() {
+    use crate::services::skill::{SkillRepo, SkillRepoProvider};
+
+    let db = Database::memory().expect("memory db");
+    let mut repo = SkillRepo::new("group/sub", "skills", "main");
+    repo.provider = SkillRepoProvider::Gitlab;
+    repo.base_url = Some("https://gitlab.example.com".to_string());
+    db.save_skill_repo(&repo).expect("save repo");
+
+    let loaded = db.get_skill_repos().expect("load repos");
+    assert_eq!(loaded[0].provider, SkillRepoProvider::Gitlab);
+    assert!(!loaded[0].has_token);
+}
+
+#[test]
+fn gemini_oauth_creds_are_encrypted_at_rest() {
+    use crate::services::gemini_oauth::GeminiOAuthAccount;
+
+    let db = Database::memory().expect("memory db");
+    let account = GeminiOAuthAccount {
+        id: "work".to_string(),
+        label: "Work".to_string(),
+        email: Some("me@corp.example".to_string()),
+        created_at: 1,
+        updated_at: 1,
+    };
+    let creds = json!({ "refresh_token": "1//refresh-secret", "token_type": "Bearer" });
+    db.save_gemini_oauth_account(&account, &creds)
+        .expect("save account");
+
+    let raw: String = {
+        let conn = db.conn.lock().expect("lock");
+        conn.query_row(
+            "SELECT value FROM secrets WHERE key = 'gemini_oauth_creds:work'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("raw secret")
+    };
+    assert!(!raw.contains("refresh-secret"));
+
+    assert_eq!(
+        db.get_gemini_oauth_creds("work").expect("read creds"),
+        Some(creds)
+    );
+
+    db.delete_gemini_oauth_account("work").expect("delete");
+    assert!(db.get_gemini_oauth_accounts().expect("list").is_empty());
+    assert!(db.get_gemini_oauth_creds("work").expect("read").is_none());
+}
+
+#[test]
+fn live_file_states_replace_per_app() {
+    let db = Database::memory().expect("memory db");
+
+    db.set_live_file_states(
+        "claude",
+        &[("/tmp/settings.json".to_string(), "aaa".to_string())],
+        1,
+    )
+    .expect("set claude");
+    db.set_live_file_states(
+        "codex",
+        &[
+            ("/tmp/auth.json".to_string(), "bbb".to_string()),
+            ("/tmp/config.toml".to_string(), "ccc".to_string()),
+        ],
+        2,
+    )
+    .expect("set codex");
+    db.set_live_file_states("codex", &[("/tmp/auth.json".to_string(), "ddd".to_string())], 3)
+        .expect("replace codex");
+
+    let states = db.get_live_file_states().expect("list");
+    assert_eq!(states.len(), 2);
+    assert_eq!(states[0].app_type, "claude");
+    assert_eq!(states[1].hash, "ddd");
+    assert_eq!(states[1].written_at, 3);
+
+    let dump = Database::dump_sql(&db.conn.lock().expect("lock")).expect("dump");
+    assert!(!dump.contains("INSERT INTO \"live_state\""));
+}
+
+#[test]
+fn replace_from_json_replaces_existing_providers() {
+    let db = Database::memory().expect("memory db");
+    db.save_provider(
+        "claude",
+        &Provider::with_id("stale".into(), "Stale".into(), json!({}), None),
+    )
+    .expect("save stale");
+
+    let mut manager = ProviderManager::default();
+    manager.providers.insert(
+        "restored".to_string(),
+        Provider::with_id("restored".into(), "Restored".into(), json!({ "env": {} }), None),
+    );
+    manager.current = "restored".to_string();
+    let mut config = MultiAppConfig::default();
+    config.apps.insert("claude".to_string(), manager);
+
+    db.replace_from_json(&config).expect("replace");
+
+    let providers = db.get_all_providers("claude").expect("providers");
+    assert_eq!(providers.len(), 1);
+    assert!(providers.contains_key("restored"));
+    assert_eq!(
+        db.get_current_provider("claude").expect("current").as_deref(),
+        Some("restored")
+    );
+}
+
+#[test]
+fn check_health_detects_and_repairs_problems() {
+    let db = Database::memory().expect("memory db");
+    db.save_provider(
+        "claude",
+        &Provider::with_id("ok".into(), "Ok".into(), json!({}), None),
+    )
+    .expect("save provider");
+
+    {
+        let conn = db.conn.lock().expect("lock");
+        conn.execute_batch(
+            "PRAGMA foreign_keys = OFF;
+             PRAGMA ignore_check_constraints = ON;
+             INSERT INTO provider_endpoints (provider_id, app_type, url, added_at)
+                 VALUES ('gone', 'claude', 'https://orphan.example', 0);
+             UPDATE providers SET meta = '{broken' WHERE id = 'ok';
+             PRAGMA ignore_check_constraints = OFF;
+             PRAGMA foreign_keys = ON;",
+        )
+        .expect("seed problems");
+    }
+
+    let report = db.check_health(false).expect("check");
+    assert!(report.integrity_ok);
+    assert_eq!(report.orphan_endpoints.len(), 1);
+    assert_eq!(report.corrupt_json.len(), 1);
+    assert_eq!(report.corrupt_json[0].column, "meta");
+    assert!(!report.is_healthy());
+    assert!(report.repaired_items.is_empty());
+
+    let repaired = db.check_health(true).expect("repair");
+    assert_eq!(repaired.repaired_items.len(), 2);
+
+    let after = db.check_health(false).expect("recheck");
+    assert!(after.is_healthy(), "{after:?}");
+}
+
+#[test]
+fn compact_records_last_run() {
+    let db = Database::memory().expect("memory db");
+    assert!(db.last_compacted_at().expect("read").is_none());
+
+    let report = db.compact().expect("compact");
+    assert_eq!(
+        report.reclaimed_bytes,
+        report.size_before.saturating_sub(report.size_after)
+    );
+    assert_eq!(
+        db.last_compacted_at().expect("read"),
+        Some(report.compacted_at)
+    );
+}
+
+#[test]
+fn pinned_providers_and_tags_round_trip() {
+    let db = Database::memory().expect("memory db");
+    let mut first = Provider::with_id("a".into(), "A".into(), json!({}), None);
+    first.sort_index = Some(0);
+    let mut second = Provider::with_id("b".into(), "B".into(), json!({}), None);
+    second.sort_index = Some(1);
+    second.is_pinned = true;
+    second.tags = vec!["team-a".into()];
+    db.save_provider("claude", &first).expect("save a");
+    db.save_provider("claude", &second).expect("save b");
+
+    let providers = db.get_all_providers("claude").expect("providers");
+    let ids: Vec<&str> = providers.keys().map(String::as_str).collect();
+    assert_eq!(ids, vec!["b", "a"], "pinned provider should come first");
+    assert!(providers["b"].is_pinned);
+    assert_eq!(providers["b"].tags, vec!["team-a".to_string()]);
+
+    assert!(db
+        .set_provider_tags("claude", "a", &["cheap".to_string()])
+        .expect("set tags"));
+    assert!(!db
+        .set_provider_tags("claude", "missing", &[])
+        .expect("set tags on missing"));
+    let providers = db.get_all_providers("claude").expect("providers");
+    assert_eq!(providers["a"].tags, vec!["cheap".to_string()]);
+}
+
+#[test]
+fn configure_connection_enables_wal_and_foreign_keys() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("legacy.db");
+    {
+        let conn = Connection::open(&path).expect("open legacy");
+        conn.execute_batch("PRAGMA journal_mode = DELETE; CREATE TABLE t (id INTEGER);")
+            .expect("seed legacy");
+    }
+
+    let conn = Connection::open(&path).expect("reopen");
+    Database::configure_connection(&conn).expect("configure");
+
+    let mode: String = conn
+        .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+        .expect("journal mode");
+    assert_eq!(mode.to_lowercase(), "wal");
+    let foreign_keys: i64 = conn
+        .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+        .expect("foreign keys");
+    assert_eq!(foreign_keys, 1);
+
+    // 切换结果持久化到文件，再次打开仍为 WAL
+    drop(conn);
+    let conn = Connection::open(&path).expect("open again");
+    let mode: String = conn
+        .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+        .expect("journal mode");
+    assert_eq!(mode.to_lowercase(), "wal");
+}
+
+#[test]
+fn prompt_sets_round_trip_member_order() {
+    use crate::prompt::{PromptSet, PromptSetMode};
+
+    let db = Database::memory().expect("memory db");
+    for id in ["base", "task", "extra"] {
+        db.save_prompt("claude", &test_prompt(id))
+            .expect("save prompt");
+    }
+    let mut set = PromptSet {
+        id: "stack".to_string(),
+        name: "Base + task".to_string(),
+        description: None,
+        mode: PromptSetMode::Select,
+        prompt_ids: vec!["task".to_string(), "base".to_string()],
+        selected_prompt_id: Some("base".to_string()),
+        enabled: true,
+        created_at: Some(1),
+        updated_at: Some(1),
+    };
+    db.save_prompt_set("claude", &set).expect("save set");
+
+    set.prompt_ids = vec!["base".to_string(), "task".to_string(), "extra".to_string()];
+    db.save_prompt_set("claude", &set).expect("update set");
+    db.remove_prompt_from_sets("claude", "extra")
+        .expect("remove member");
+
+    let sets = db.get_prompt_sets("claude").expect("list sets");
+    let loaded = sets.get("stack").expect("set exists");
+    assert_eq!(loaded.prompt_ids, vec!["base", "task"]);
+    assert_eq!(loaded.mode, PromptSetMode::Select);
+    assert!(loaded.enabled);
+    assert!(db.get_prompt_sets("codex").expect("codex").is_empty());
+
+    db.clear_active_prompt_set("claude").expect("clear active");
+    db.delete_prompt_set("claude", "stack").expect("delete set");
+    assert!(db.get_prompt_sets("claude").expect("list").is_empty());
+}
+
+#[test]
+fn projects_round_trip_and_cascade_providers() {
+    use crate::services::project::Project;
+
+    let db = Database::memory().expect("memory db");
+    let mut project = Project {
+        path: "/work/api".to_string(),
+        name: "API".to_string(),
+        providers: IndexMap::from([
+            ("claude".to_string(), "team".to_string()),
+            ("codex".to_string(), "personal".to_string()),
+        ]),
+        created_at: 1,
+        updated_at: 1,
+    };
+    db.save_project(&project).expect("save project");
+
+    project.providers.shift_remove("codex");
+    project.updated_at = 2;
+    db.save_project(&project).expect("update project");
+
+    let projects = db.get_projects().expect("list projects");
+    let loaded = projects.get("/work/api").expect("project exists");
+    assert_eq!(loaded.providers.len(), 1);
+    assert_eq!(loaded.providers["claude"], "team");
+    assert_eq!(loaded.created_at, 1);
+
+    db.delete_project("/work/api").expect("delete project");
+    assert!(db.get_projects().expect("list").is_empty());
+    let conn = db.conn.lock().expect("lock");
+    let orphans: i64 = conn
+        .query_row("SELECT COUNT(*) FROM project_providers", [], |row| {
+            row.get(0)
+        })
+        .expect("count");
+    assert_eq!(orphans, 0);
+}
+
+#[test]
+fn grouped_providers_follow_category_order() {
+    let db = Database::memory().expect("memory db");
+    for (id, category) in [
+        ("a", Some("official")),
+        ("b", None),
+        ("c", Some("aggregator")),
+        ("d", Some("team")),
+    ] {
+        let mut provider = Provider::with_id(id.to_string(), id.to_string(), json!({}), None);
+        provider.category = category.map(str::to_string);
+        db.save_provider("claude", &provider).expect("save provider");
+    }
+    let order = ["aggregator".to_string(), "official".to_string()];
+    db.set_category_order("claude", &order).expect("save order");
+    assert!(db
+        .set_provider_category("claude", "a", Some("aggregator"), Some(0))
+        .expect("move provider"));
+    assert!(!db
+        .set_provider_category("claude", "missing", None, None)
+        .expect("missing provider"));
+
+    let groups = db.get_grouped_providers("claude").expect("grouped");
+    let layout: Vec<(Option<&str>, Vec<&str>)> = groups
+        .iter()
+        .map(|group| {
+            (
+                group.category.as_deref(),
+                group.providers.iter().map(|p| p.id.as_str()).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        layout,
+        vec![
+            (Some("aggregator"), vec!["a", "c"]),
+            (Some("team"), vec!["d"]),
+            (None, vec!["b"]),
+        ]
+    );
+}
+
+#[test]
+fn switch_events_since_include_last_earlier_event_per_app() {
+    use crate::services::stats::SwitchEvent;
+
+    let db = Database::memory().expect("memory db");
+    for (app, id, at) in [
+        ("claude", "a", 10),
+        ("claude", "b", 20),
+        ("codex", "c", 30),
+        ("claude", "d", 120),
+    ] {
+        db.insert_switch_event(&SwitchEvent {
+            app_type: app.to_string(),
+            provider_id: id.to_string(),
+            switched_at: at,
+        })
+        .expect("insert event");
+    }
+
+    let events = db.get_switch_events_since(100).expect("events");
+    let ids: Vec<&str> = events.iter().map(|e| e.provider_id.as_str()).collect();
+    assert_eq!(ids, vec!["b", "c", "d"]);
+}
+
+fn test_prompt(id: &str) -> crate::prompt::Prompt {
+    crate::prompt::Prompt {
+        id: id.to_string(),
+        name: id.to_string(),
+        content: format!("# {id}"),
+        description: None,
+        enabled: false,
+        tags: Vec::new(),
+        target_apps: Vec::new(),
+        apps: Default::default(),
+        created_at: Some(1),
+        updated_at: Some(1),
+    }
+}
+
+#[test]
+fn migration_v1_to_v2_rebuilds_tables_with_constraints() {
+    let conn = Connection::open_in_memory().expect("open memory db");
+    conn.execute_batch(LEGACY_SCHEMA_SQL)
+        .expect("seed old schema");
+    conn.execute_batch(
+        "INSERT INTO providers (id, app_type, name, settings_config)
+             VALUES ('ok', 'claude', 'Ok', '{\"env\":{}}'), ('bad', 'claude', 'Bad', '{broken');
+         INSERT INTO provider_endpoints (provider_id, app_type, url)
+             VALUES ('ok', 'claude', 'https://ok.example');",
+    )
+    .expect("seed rows");
+
+    Database::apply_schema_migrations_on_conn(&conn).expect("apply migrations");
+    assert_eq!(
+        Database::get_user_version(&conn).expect("version"),
+        SCHEMA_VERSION
+    );
+
+    // 数据保留，非法 JSON 被修正，重建表未级联删除端点
+    let configs: Vec<(String, String)> = conn
+        .prepare("SELECT id, settings_config FROM providers ORDER BY id")
+        .expect("prepare")
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .expect("query")
+        .collect::<Result<_, _>>()
+        .expect("rows");
+    assert_eq!(
+        configs,
+        vec![
+            ("bad".to_string(), "{}".to_string()),
+            ("ok".to_string(), "{\"env\":{}}".to_string()),
+        ]
+    );
+    let endpoints: i64 = conn
+        .query_row("SELECT COUNT(*) FROM provider_endpoints", [], |row| {
+            row.get(0)
+        })
+        .expect("count endpoints");
+    assert_eq!(endpoints, 1);
+    assert!(Database::has_column(&conn, "providers", "is_pinned").expect("column"));
+
+    // 约束与索引生效
+    assert!(conn
+        .execute("UPDATE providers SET meta = '{broken' WHERE id = 'ok'", [])
+        .is_err());
+    let indexes: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master
+             WHERE type = 'index' AND name IN ('idx_providers_current', 'idx_mcp_servers_enabled_claude')",
+            [],
+            |row| row.get(0),
+        )
+        .expect("count indexes");
+    assert_eq!(indexes, 2);
+}
+
+#[test]
+fn deleting_prompt_removes_it_from_prompt_sets() {
+    use crate::prompt::{PromptSet, PromptSetMode};
+
+    let db = Database::memory().expect("memory db");
+    for id in ["base", "task"] {
+        db.save_prompt("claude", &test_prompt(id))
+            .expect("save prompt");
+    }
+    let set = PromptSet {
+        id: "stack".to_string(),
+        name: "Stack".to_string(),
+        description: None,
+        mode: PromptSetMode::Concat,
+        prompt_ids: vec![
+            "base".to_string(),
+            "task".to_string(),
+            "missing".to_string(),
+        ],
+        selected_prompt_id: None,
+        enabled: false,
+        created_at: Some(1),
+        updated_at: Some(1),
+    };
+    db.save_prompt_set("claude", &set).expect("save set");
+
+    // 更新提示词不影响成员，删除提示词级联移除成员
+    db.save_prompt("claude", &test_prompt("base"))
+        .expect("update prompt");
+    db.delete_prompt("claude", "task").expect("delete prompt");
+
+    let sets = db.get_prompt_sets("claude").expect("list sets");
+    assert_eq!(sets.get("stack").expect("set").prompt_ids, vec!["base"]);
+}
@@ -0,0 +1,144 @@
+//! Deep link actions
+//!
+//! Runs `ccswitch://v1/action` links directly in the backend. Actions only
+//! operate on providers that are already configured, so automation tools
+//! (Apple Shortcuts, AutoHotkey, ...) can drive them without confirmation.
+
+use std::str::FromStr;
+
+use indexmap::IndexMap;
+use serde_json::json;
+use tauri::AppHandle;
+
+use super::DeepLinkImportRequest;
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+/// Run a parsed action request and describe what was done
+pub fn run_action(
+    app: &AppHandle,
+    state: &AppState,
+    request: &DeepLinkImportRequest,
+) -> Result<serde_json::Value, AppError> {
+    let action = request.action.as_deref().unwrap_or_default();
+    if action == "show" {
+        crate::tray::handle_tray_menu_event(app, "show_main");
+        return Ok(json!({ "type": "action", "action": action }));
+    }
+
+    let app_type = AppType::from_str(request.app.as_deref().unwrap_or_default())?;
+    let providers = state.db.get_all_providers(app_type.as_str())?;
+    let target = match action {
+        "switch" => {
+            let query = request.provider.as_deref().unwrap_or_default();
+            resolve_provider(&providers, query).ok_or_else(|| {
+                AppError::localized_with(
+                    "deeplink.action.provider_not_found",
+                    &[("provider", query.to_string())],
+                )
+            })?
+        }
+        "next" => {
+            let current = crate::settings::get_effective_current_provider(&state.db, &app_type)?;
+            next_provider(&providers, current.as_deref()).ok_or_else(|| {
+                AppError::localized_with(
+                    "deeplink.action.no_providers",
+                    &[("app", app_type.as_str().to_string())],
+                )
+            })?
+        }
+        _ => {
+            return Err(AppError::InvalidInput(format!(
+                "Unsupported action: {action}"
+            )))
+        }
+    };
+
+    let provider_id = target.id.clone();
+    let provider_name = target.name.clone();
+    crate::tray::switch_provider_internal(app, app_type.clone(), provider_id.clone())?;
+    log::info!(
+        "Deep link action '{action}' switched {} to {provider_name}",
+        app_type.as_str()
+    );
+
+    Ok(json!({
+        "type": "action",
+        "action": action,
+        "app": app_type.as_str(),
+        "providerId": provider_id,
+        "providerName": provider_name,
+    }))
+}
+
+/// Find a provider by exact ID, falling back to a unique case-insensitive name
+fn resolve_provider<'a>(
+    providers: &'a IndexMap<String, Provider>,
+    query: &str,
+) -> Option<&'a Provider> {
+    if let Some(provider) = providers.get(query) {
+        return Some(provider);
+    }
+    let mut matches = providers
+        .values()
+        .filter(|provider| provider.name.trim().eq_ignore_ascii_case(query));
+    let first = matches.next()?;
+    matches.next().is_none().then_some(first)
+}
+
+/// The provider after `current` in list order, wrapping around
+fn next_provider<'a>(
+    providers: &'a IndexMap<String, Provider>,
+    current: Option<&str>,
+) -> Option<&'a Provider> {
+    let next_index = current
+        .and_then(|id| providers.get_index_of(id))
+        .map(|index| (index + 1) % providers.len())
+        .unwrap_or(0);
+    providers
+        .get_index(next_index)
+        .map(|(_, provider)| provider)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn providers(names: &[(&str, &str)]) -> IndexMap<String, Provider> {
+        names
+            .iter()
+            .map(|(id, name)| {
+                let provider = Provider::with_id(id.to_string(), name.to_string(), json!({}), None);
+                (id.to_string(), provider)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resolves_by_id_then_unique_name() {
+        let list = providers(&[("a", "Relay"), ("b", "Official"), ("c", "official")]);
+        assert_eq!(
+            resolve_provider(&list, "a").map(|p| p.id.as_str()),
+            Some("a")
+        );
+        assert_eq!(
+            resolve_provider(&list, "relay").map(|p| p.id.as_str()),
+            Some("a")
+        );
+        // Ambiguous names are rejected rather than guessed
+        assert!(resolve_provider(&list, "Official").is_none());
+        assert!(resolve_provider(&list, "missing").is_none());
+    }
+
+    #[test]
+    fn next_provider_wraps_around() {
+        let list = providers(&[("a", "A"), ("b", "B"), ("c", "C")]);
+        let next = |current| next_provider(&list, current).map(|p| p.id.as_str());
+        assert_eq!(next(Some("a")), Some("b"));
+        assert_eq!(next(Some("c")), Some("a"));
+        assert_eq!(next(None), Some("a"));
+        assert!(next_provider(&IndexMap::new(), None).is_none());
+    }
+}
@@ -137,6 +137,7 @@ pub fn import_mcp_from_deeplink(
                 name: id.clone(),
                 server: server_spec.clone(),
                 apps: target_apps.clone(),
+                app_overrides: Default::default(),
                 description: None,
                 homepage: None,
                 docs: None,
@@ -174,6 +175,7 @@ pub(crate) fn parse_mcp_apps(apps_str: &str) -> Result<McpApps, AppError> {
         gemini: false,
         grok: false,
         qwen: false,
+        claude_desktop: false,
     };
 
     for app in apps_str.split(',') {
@@ -183,6 +185,7 @@ pub(crate) fn parse_mcp_apps(apps_str: &str) -> Result<McpApps, AppError> {
             "gemini" => apps.gemini = true,
             "grok" => apps.grok = true,
             "qwen" => apps.qwen = true,
+            "claude_desktop" | "claudeDesktop" => apps.claude_desktop = true,
             other => {
                 return Err(AppError::InvalidInput(format!(
                     "Invalid app in 'apps': {other}"
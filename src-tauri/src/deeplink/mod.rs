@@ -6,11 +6,18 @@
 //! - MCP server configurations
 //! - Prompts
 //! - Skills
+//! - Project switches (apply a registered project's providers)
+//!
+//! Action links (`ccswitch://v1/action?do=switch&app=claude&provider=Foo`) run
+//! non-sensitive actions directly in the backend, so Shortcuts / AutoHotkey can
+//! drive the app without a confirmation dialog.
 //!
 //! See docs/ccswitch-deeplink-design.md for detailed design.
 
+mod action;
 mod mcp;
 mod parser;
+mod policy;
 mod prompt;
 mod provider;
 mod skill;
@@ -21,9 +28,17 @@ mod tests;
 
 use serde::{Deserialize, Serialize};
 
+use crate::error::AppError;
+use crate::store::AppState;
+
 // Re-export public API
+pub use action::run_action;
 pub use mcp::import_mcp_from_deeplink;
 pub use parser::parse_deeplink_url;
+pub use policy::{
+    decision_log, evaluate as evaluate_policy, record_decision, DeepLinkDecisionRecord,
+    PolicyDecision,
+};
 pub use prompt::import_prompt_from_deeplink;
 pub use provider::{import_provider_from_deeplink, parse_and_merge_config};
 pub use skill::import_skill_from_deeplink;
@@ -37,7 +52,7 @@ pub use skill::import_skill_from_deeplink;
 pub struct DeepLinkImportRequest {
     /// Protocol version (e.g., "v1")
     pub version: String,
-    /// Resource type to import: "provider" | "prompt" | "mcp" | "skill"
+    /// Resource type to import: "provider" | "prompt" | "mcp" | "skill" | "project" | "action"
     pub resource: String,
 
     // ============ Common fields ============
@@ -103,6 +118,19 @@ pub struct DeepLinkImportRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub branch: Option<String>,
 
+    // ============ Project-specific fields ============
+    /// Project directory (or a subdirectory of it) to apply
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    // ============ Action-specific fields ============
+    /// Action verb of `ccswitch://v1/action` links: "switch" | "next" | "show"
+    #[serde(rename = "do", skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    /// Target provider ID or name (switch action)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+
     // ============ Config file fields (v3.8+) ============
     /// Base64 encoded config content
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -114,3 +142,63 @@ pub struct DeepLinkImportRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config_url: Option<String>,
 }
+
+/// Import a parsed request, dispatching on its resource type
+///
+/// Shared by the confirmation dialog and policy-driven auto-accept.
+pub fn import_deeplink_request(
+    state: &AppState,
+    request: DeepLinkImportRequest,
+) -> Result<serde_json::Value, AppError> {
+    log::info!("Importing {} resource from deep link", request.resource);
+
+    match request.resource.as_str() {
+        "provider" => {
+            let provider_id = import_provider_from_deeplink(state, request)?;
+            Ok(serde_json::json!({
+                "type": "provider",
+                "id": provider_id
+            }))
+        }
+        "prompt" => {
+            let prompt_id = import_prompt_from_deeplink(state, request)?;
+            Ok(serde_json::json!({
+                "type": "prompt",
+                "id": prompt_id
+            }))
+        }
+        "mcp" => {
+            let result = import_mcp_from_deeplink(state, request)?;
+            // Add type field to the result
+            Ok(serde_json::json!({
+                "type": "mcp",
+                "importedCount": result.imported_count,
+                "importedIds": result.imported_ids,
+                "failed": result.failed
+            }))
+        }
+        "skill" => {
+            let skill_key = import_skill_from_deeplink(state, request)?;
+            Ok(serde_json::json!({
+                "type": "skill",
+                "key": skill_key
+            }))
+        }
+        "project" => {
+            let path = request
+                .path
+                .ok_or_else(|| AppError::InvalidInput("Missing 'path' for project".to_string()))?;
+            let result = crate::services::ProjectService::apply(state, &path)?;
+            Ok(serde_json::json!({
+                "type": "project",
+                "path": result.project.path,
+                "switched": result.switched,
+                "failed": result.failed
+            }))
+        }
+        _ => Err(AppError::InvalidInput(format!(
+            "Unsupported resource type: {}",
+            request.resource
+        ))),
+    }
+}
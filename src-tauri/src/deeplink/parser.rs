@@ -4,14 +4,17 @@
 
 use super::utils::validate_url;
 use super::DeepLinkImportRequest;
+use crate::app_config::AppType;
 use crate::error::AppError;
 use std::collections::HashMap;
+use std::str::FromStr;
 use url::Url;
 
 /// Parse a ccswitch:// URL into a DeepLinkImportRequest
 ///
 /// Expected format:
 /// ccswitch://v1/import?resource={type}&...
+/// ccswitch://v1/action?do={verb}&...
 pub fn parse_deeplink_url(url_str: &str) -> Result<DeepLinkImportRequest, AppError> {
     // Parse URL
     let url = Url::parse(url_str)
@@ -38,17 +41,21 @@ pub fn parse_deeplink_url(url_str: &str) -> Result<DeepLinkImportRequest, AppErr
         )));
     }
 
-    // Extract path (should be "/import")
+    // Extract path (should be "/import" or "/action")
     let path = url.path();
-    if path != "/import" {
+    if path != "/import" && path != "/action" {
         return Err(AppError::InvalidInput(format!(
-            "Invalid path: expected '/import', got '{path}'"
+            "Invalid path: expected '/import' or '/action', got '{path}'"
         )));
     }
 
     // Parse query parameters
     let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
 
+    if path == "/action" {
+        return parse_action_deeplink(&params, version);
+    }
+
     // Extract and validate resource type
     let resource = params
         .get("resource")
@@ -61,6 +68,7 @@ pub fn parse_deeplink_url(url_str: &str) -> Result<DeepLinkImportRequest, AppErr
         "prompt" => parse_prompt_deeplink(&params, version, resource),
         "mcp" => parse_mcp_deeplink(&params, version, resource),
         "skill" => parse_skill_deeplink(&params, version, resource),
+        "project" => parse_project_deeplink(&params, version, resource),
         _ => Err(AppError::InvalidInput(format!(
             "Unsupported resource type: {resource}"
         ))),
@@ -146,6 +154,9 @@ fn parse_provider_deeplink(
         config,
         config_format,
         config_url,
+        path: None,
+        action: None,
+        provider: None,
     })
 }
 
@@ -206,6 +217,9 @@ fn parse_prompt_deeplink(
         config: None,
         config_format: None,
         config_url: None,
+        path: None,
+        action: None,
+        provider: None,
     })
 }
 
@@ -261,6 +275,9 @@ fn parse_mcp_deeplink(
         directory: None,
         branch: None,
         config_url: None,
+        path: None,
+        action: None,
+        provider: None,
     })
 }
 
@@ -270,6 +287,45 @@ fn parse_skill_deeplink(
     version: String,
     resource: String,
 ) -> Result<DeepLinkImportRequest, AppError> {
+    // Authored skill share link: name + Base64 SKILL.md content, no repo needed
+    if let Some(content) = params.get("content") {
+        let name = params
+            .get("name")
+            .ok_or_else(|| {
+                AppError::InvalidInput("Missing 'name' parameter for skill".to_string())
+            })?
+            .clone();
+
+        return Ok(DeepLinkImportRequest {
+            version,
+            resource,
+            repo: None,
+            directory: None,
+            branch: None,
+            icon: None,
+            app: Some("claude".to_string()), // Skills are Claude-only
+            name: Some(name),
+            enabled: None,
+            homepage: None,
+            endpoint: None,
+            api_key: None,
+            model: None,
+            notes: None,
+            haiku_model: None,
+            sonnet_model: None,
+            opus_model: None,
+            content: Some(content.clone()),
+            description: None,
+            apps: None,
+            config: None,
+            config_format: None,
+            config_url: None,
+            path: None,
+            action: None,
+            provider: None,
+        });
+    }
+
     let repo = params
         .get("repo")
         .ok_or_else(|| AppError::InvalidInput("Missing 'repo' parameter for skill".to_string()))?
@@ -309,5 +365,123 @@ fn parse_skill_deeplink(
         config: None,
         config_format: None,
         config_url: None,
+        path: None,
+        action: None,
+        provider: None,
+    })
+}
+
+/// Parse project deep link parameters
+fn parse_project_deeplink(
+    params: &HashMap<String, String>,
+    version: String,
+    resource: String,
+) -> Result<DeepLinkImportRequest, AppError> {
+    let path = params
+        .get("path")
+        .filter(|path| !path.trim().is_empty())
+        .ok_or_else(|| AppError::InvalidInput("Missing 'path' parameter for project".to_string()))?
+        .clone();
+
+    Ok(DeepLinkImportRequest {
+        version,
+        resource,
+        name: None,
+        path: Some(path),
+        app: None,
+        enabled: None,
+        homepage: None,
+        endpoint: None,
+        api_key: None,
+        icon: None,
+        model: None,
+        notes: None,
+        haiku_model: None,
+        sonnet_model: None,
+        opus_model: None,
+        content: None,
+        description: None,
+        apps: None,
+        repo: None,
+        directory: None,
+        branch: None,
+        config: None,
+        config_format: None,
+        config_url: None,
+        action: None,
+        provider: None,
+    })
+}
+
+/// Parse action deep link parameters
+///
+/// Verbs: `switch` (requires `app` and `provider`), `next` (requires `app`), `show`.
+fn parse_action_deeplink(
+    params: &HashMap<String, String>,
+    version: String,
+) -> Result<DeepLinkImportRequest, AppError> {
+    let action = params
+        .get("do")
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| AppError::InvalidInput("Missing 'do' parameter for action".to_string()))?;
+
+    let app = params
+        .get("app")
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty());
+    let provider = params
+        .get("provider")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+
+    match action.as_str() {
+        "switch" | "next" => {
+            let app = app.as_deref().ok_or_else(|| {
+                AppError::InvalidInput(format!("Missing 'app' parameter for action '{action}'"))
+            })?;
+            AppType::from_str(app)
+                .map_err(|_| AppError::InvalidInput(format!("Invalid app type: '{app}'")))?;
+            if action == "switch" && provider.is_none() {
+                return Err(AppError::InvalidInput(
+                    "Missing 'provider' parameter for action 'switch'".to_string(),
+                ));
+            }
+        }
+        "show" => {}
+        _ => {
+            return Err(AppError::InvalidInput(format!(
+                "Unsupported action: {action}"
+            )))
+        }
+    }
+
+    Ok(DeepLinkImportRequest {
+        version,
+        resource: "action".to_string(),
+        app,
+        name: None,
+        enabled: None,
+        homepage: None,
+        endpoint: None,
+        api_key: None,
+        icon: None,
+        model: None,
+        notes: None,
+        haiku_model: None,
+        sonnet_model: None,
+        opus_model: None,
+        content: None,
+        description: None,
+        apps: None,
+        repo: None,
+        directory: None,
+        branch: None,
+        config: None,
+        config_format: None,
+        config_url: None,
+        path: None,
+        action: Some(action),
+        provider,
     })
 }
@@ -0,0 +1,228 @@
+//! Deep link confirmation policy
+//!
+//! Every parsed ccswitch:// request is evaluated against the user's
+//! [`DeepLinkPolicySettings`] before anything reaches the frontend:
+//! - resource types outside the allowlist are blocked
+//! - links carrying API keys can be blocked outright
+//! - links signed by a trusted Ed25519 key can be imported without confirmation
+//! - action links (switch / show) import nothing and run without confirmation
+//! - everything else falls back to the confirmation dialog
+//!
+//! Each decision is appended to an in-memory log (most recent first) so users can
+//! review what happened to links opened while the window was hidden.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+
+use base64::prelude::*;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde::Serialize;
+use url::Url;
+
+use super::utils::decode_base64_param;
+use super::DeepLinkImportRequest;
+use crate::i18n::tr;
+use crate::settings::{DeepLinkConfirmMode, DeepLinkPolicySettings};
+
+/// Query parameter holding the Base64URL encoded Ed25519 signature
+pub const SIGNATURE_PARAM: &str = "sig";
+
+/// Maximum number of decisions kept in memory
+const MAX_DECISION_LOG: usize = 100;
+
+static DECISION_LOG: OnceLock<Mutex<VecDeque<DeepLinkDecisionRecord>>> = OnceLock::new();
+
+/// Outcome of evaluating a deep link request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyDecision {
+    /// Show the confirmation dialog
+    Ask,
+    /// Import immediately without confirmation
+    AutoAccept,
+    /// Reject the link; the reason is a localized message
+    Block(String),
+}
+
+impl PolicyDecision {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PolicyDecision::Ask => "ask",
+            PolicyDecision::AutoAccept => "autoAccept",
+            PolicyDecision::Block(_) => "block",
+        }
+    }
+}
+
+/// Entry of the decision log
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkDecisionRecord {
+    /// Unix timestamp in milliseconds
+    pub timestamp: i64,
+    /// Where the URL came from (e.g. "on_open_url", "single_instance")
+    pub source: String,
+    pub resource: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Whether the link carried a valid signature from a trusted key
+    pub signed: bool,
+    /// "ask" | "autoAccept" | "block"
+    pub decision: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Evaluate a parsed request against the policy
+///
+/// `url_str` is the raw URL, needed to verify the signature.
+pub fn evaluate(
+    url_str: &str,
+    request: &DeepLinkImportRequest,
+    policy: &DeepLinkPolicySettings,
+) -> (PolicyDecision, bool) {
+    let signed = is_signed_by_trusted_key(url_str, &policy.trusted_keys);
+
+    if !policy.allowed_resources.is_empty()
+        && !policy
+            .allowed_resources
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(&request.resource))
+    {
+        let reason = tr(
+            "deeplink.policy.resource_not_allowed",
+            &[("resource", request.resource.clone())],
+        );
+        return (PolicyDecision::Block(reason), signed);
+    }
+
+    if policy.block_api_key_links && carries_api_key(request) {
+        let reason = tr("deeplink.policy.api_key_blocked", &[]);
+        return (PolicyDecision::Block(reason), signed);
+    }
+
+    // Actions only operate on already configured providers
+    if request.resource == "action" {
+        return (PolicyDecision::AutoAccept, signed);
+    }
+
+    let decision = match policy.confirm_mode {
+        DeepLinkConfirmMode::AutoAcceptSigned if signed => PolicyDecision::AutoAccept,
+        _ => PolicyDecision::Ask,
+    };
+    (decision, signed)
+}
+
+/// Append a decision to the log
+pub fn record_decision(
+    source: &str,
+    request: &DeepLinkImportRequest,
+    decision: &PolicyDecision,
+    signed: bool,
+) {
+    let reason = match decision {
+        PolicyDecision::Block(reason) => Some(reason.clone()),
+        _ => None,
+    };
+    log::info!(
+        "Deep link policy decision: resource={}, name={:?}, signed={signed}, decision={}{}",
+        request.resource,
+        request.name,
+        decision.as_str(),
+        reason
+            .as_deref()
+            .map(|r| format!(", reason={r}"))
+            .unwrap_or_default()
+    );
+
+    let record = DeepLinkDecisionRecord {
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        source: source.to_string(),
+        resource: request.resource.clone(),
+        name: request.name.clone(),
+        signed,
+        decision: decision.as_str().to_string(),
+        reason,
+    };
+    let log = DECISION_LOG.get_or_init(|| Mutex::new(VecDeque::new()));
+    if let Ok(mut log) = log.lock() {
+        log.push_front(record);
+        log.truncate(MAX_DECISION_LOG);
+    }
+}
+
+/// Recent decisions, most recent first
+pub fn decision_log() -> Vec<DeepLinkDecisionRecord> {
+    DECISION_LOG
+        .get()
+        .and_then(|log| log.lock().ok().map(|log| log.iter().cloned().collect()))
+        .unwrap_or_default()
+}
+
+/// Canonical message covered by the signature: the URL with the `sig` parameter
+/// removed and the remaining query re-encoded in original order
+pub fn signing_payload(url_str: &str) -> Option<(String, String)> {
+    let url = Url::parse(url_str).ok()?;
+    let mut signature = None;
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    for (key, value) in url.query_pairs() {
+        if key == SIGNATURE_PARAM {
+            signature = Some(value.into_owned());
+        } else {
+            query.append_pair(&key, &value);
+        }
+    }
+    let payload = format!(
+        "{}://{}{}?{}",
+        url.scheme(),
+        url.host_str().unwrap_or_default(),
+        url.path(),
+        query.finish()
+    );
+    Some((payload, signature?))
+}
+
+/// Whether the URL carries a valid signature from one of the trusted keys
+fn is_signed_by_trusted_key(url_str: &str, trusted_keys: &[String]) -> bool {
+    if trusted_keys.is_empty() {
+        return false;
+    }
+    let Some((payload, signature)) = signing_payload(url_str) else {
+        return false;
+    };
+    let Ok(signature) = BASE64_URL_SAFE_NO_PAD.decode(signature.trim_end_matches('=')) else {
+        return false;
+    };
+
+    trusted_keys.iter().any(|key| {
+        BASE64_STANDARD
+            .decode(key.trim())
+            .or_else(|_| BASE64_URL_SAFE_NO_PAD.decode(key.trim().trim_end_matches('=')))
+            .map(|key| {
+                UnparsedPublicKey::new(&ED25519, key)
+                    .verify(payload.as_bytes(), &signature)
+                    .is_ok()
+            })
+            .unwrap_or(false)
+    })
+}
+
+/// Whether the request would import an API key, either directly or via an
+/// inline config (remote configs are not fetched here)
+fn carries_api_key(request: &DeepLinkImportRequest) -> bool {
+    if request
+        .api_key
+        .as_deref()
+        .is_some_and(|key| !key.trim().is_empty())
+    {
+        return true;
+    }
+    request
+        .config
+        .as_deref()
+        .and_then(|config| decode_base64_param("config", config).ok())
+        .map(|decoded| {
+            let text = String::from_utf8_lossy(&decoded);
+            crate::scrub::scrub_secrets(&text) != text
+        })
+        .unwrap_or(false)
+}
@@ -48,6 +48,11 @@ pub fn import_prompt_from_deeplink(
     let content = String::from_utf8(content)
         .map_err(|e| AppError::InvalidInput(format!("Invalid UTF-8 in content: {e}")))?;
 
+    // Split optional YAML frontmatter; explicit URL parameters take precedence
+    let (meta, body) = crate::prompt_frontmatter::parse(&content);
+    let meta = meta.unwrap_or_default();
+    let content = body.to_string();
+
     // Generate ID
     let timestamp = chrono::Utc::now().timestamp_millis();
     let sanitized_name = name
@@ -65,8 +70,11 @@ pub fn import_prompt_from_deeplink(
         id: id.clone(),
         name: name.clone(),
         content,
-        description: request.description,
+        description: request.description.or(meta.description),
         enabled: false, // Always start as disabled, will be enabled later if needed
+        tags: meta.tags,
+        target_apps: meta.apps,
+        apps: Default::default(),
         created_at: Some(timestamp),
         updated_at: Some(timestamp),
     };
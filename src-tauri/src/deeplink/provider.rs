@@ -32,6 +32,11 @@ pub fn import_provider_from_deeplink(
         )));
     }
 
+    // Machine policy may forbid importing API keys via deep links
+    if crate::policy::current().disable_deeplink_api_keys {
+        return Err(AppError::localized("policy.deeplink_api_keys_disabled"));
+    }
+
     // Step 1: Merge config file if provided (v3.8+)
     let merged_request = parse_and_merge_config(&request)?;
 
@@ -152,6 +157,7 @@ pub(crate) fn build_provider_from_request(
         is_pinned: false,
         is_duplicated: None,
         is_edited_after_duplication: None,
+        tags: Vec::new(),
     };
 
     Ok(provider)
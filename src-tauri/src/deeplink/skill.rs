@@ -1,10 +1,13 @@
 //! Skill import from deep link
 //!
-//! Handles importing skill repository configurations via ccswitch:// URLs.
+//! Handles importing skill repository configurations via ccswitch:// URLs,
+//! as well as authored skills shared with embedded SKILL.md content.
 
+use super::utils::decode_base64_param;
 use super::DeepLinkImportRequest;
 use crate::error::AppError;
 use crate::services::skill::SkillRepo;
+use crate::services::SkillService;
 use crate::store::AppState;
 
 /// Import a skill from deep link request
@@ -20,6 +23,26 @@ pub fn import_skill_from_deeplink(
         )));
     }
 
+    // Authored skill shared by content
+    if let Some(content_b64) = request.content.as_ref() {
+        let name = request
+            .name
+            .as_ref()
+            .ok_or_else(|| AppError::InvalidInput("Missing 'name' field for skill".to_string()))?;
+        let content = decode_base64_param("content", content_b64)?;
+        let content = String::from_utf8(content)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid UTF-8 in content: {e}")))?;
+
+        let service = SkillService::new().map_err(|e| AppError::Message(e.to_string()))?;
+        let skill = service
+            .create_local_skill(name, &content, false)
+            .map_err(|e| AppError::Message(e.to_string()))?;
+
+        log::info!("Successfully imported authored skill '{}'", skill.directory);
+
+        return Ok(skill.key);
+    }
+
     // Parse repo
     let repo_str = request
         .repo
@@ -36,10 +59,12 @@ pub fn import_skill_from_deeplink(
 
     // Create SkillRepo
     let repo = SkillRepo {
-        owner: owner.clone(),
-        name: name.clone(),
-        branch: request.branch.unwrap_or_else(|| "main".to_string()),
         enabled: request.enabled.unwrap_or(true),
+        ..SkillRepo::new(
+            owner.clone(),
+            name.clone(),
+            request.branch.unwrap_or_else(|| "main".to_string()),
+        )
     };
 
     // Save using Database
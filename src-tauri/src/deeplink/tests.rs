@@ -2,10 +2,12 @@
 
 use super::mcp::parse_mcp_apps;
 use super::parser::parse_deeplink_url;
+use super::policy::{evaluate, signing_payload, PolicyDecision};
 use super::prompt::import_prompt_from_deeplink;
 use super::provider::parse_and_merge_config;
 use super::utils::{infer_homepage_from_endpoint, validate_url};
 use super::DeepLinkImportRequest;
+use crate::settings::{DeepLinkConfirmMode, DeepLinkPolicySettings};
 use crate::AppType;
 use crate::{store::AppState, Database};
 use base64::prelude::*;
@@ -145,6 +147,9 @@ fn test_build_gemini_provider_with_model() {
         content: None,
         description: None,
         enabled: None,
+        path: None,
+        action: None,
+        provider: None,
     };
 
     let provider = build_provider_from_request(&AppType::Gemini, &request).unwrap();
@@ -191,6 +196,9 @@ fn test_build_gemini_provider_without_model() {
         content: None,
         description: None,
         enabled: None,
+        path: None,
+        action: None,
+        provider: None,
     };
 
     let provider = build_provider_from_request(&AppType::Gemini, &request).unwrap();
@@ -232,6 +240,9 @@ fn test_parse_and_merge_config_claude() {
         content: None,
         description: None,
         enabled: None,
+        path: None,
+        action: None,
+        provider: None,
     };
 
     let merged = parse_and_merge_config(&request).unwrap();
@@ -275,6 +286,9 @@ fn test_parse_and_merge_config_url_override() {
         content: None,
         description: None,
         enabled: None,
+        path: None,
+        action: None,
+        provider: None,
     };
 
     let merged = parse_and_merge_config(&request).unwrap();
@@ -376,3 +390,135 @@ fn test_parse_skill_deeplink() {
     assert_eq!(request.directory.unwrap(), "skills");
     assert_eq!(request.branch.unwrap(), "dev");
 }
+
+#[test]
+fn test_parse_authored_skill_deeplink() {
+    let url = "ccswitch://v1/import?resource=skill&name=My%20Skill&content=IyBIZWxsbw==";
+    let request = parse_deeplink_url(&url).unwrap();
+
+    assert_eq!(request.resource, "skill");
+    assert!(request.repo.is_none());
+    assert_eq!(request.name.unwrap(), "My Skill");
+    assert_eq!(request.content.unwrap(), "IyBIZWxsbw==");
+}
+
+#[test]
+fn test_parse_action_deeplink() {
+    let url = "ccswitch://v1/action?do=switch&app=claude&provider=My%20Relay";
+    let request = parse_deeplink_url(url).unwrap();
+
+    assert_eq!(request.resource, "action");
+    assert_eq!(request.action.as_deref(), Some("switch"));
+    assert_eq!(request.app.as_deref(), Some("claude"));
+    assert_eq!(request.provider.as_deref(), Some("My Relay"));
+
+    let request = parse_deeplink_url("ccswitch://v1/action?do=show").unwrap();
+    assert_eq!(request.action.as_deref(), Some("show"));
+
+    // Actions never fall back to the confirmation dialog
+    assert_eq!(
+        evaluate(url, &request, &DeepLinkPolicySettings::default()).0,
+        PolicyDecision::AutoAccept
+    );
+}
+
+#[test]
+fn test_parse_action_deeplink_rejects_incomplete_links() {
+    for url in [
+        "ccswitch://v1/action",
+        "ccswitch://v1/action?do=switch&app=claude",
+        "ccswitch://v1/action?do=next",
+        "ccswitch://v1/action?do=next&app=unknown",
+        "ccswitch://v1/action?do=delete&app=claude",
+    ] {
+        assert!(parse_deeplink_url(url).is_err(), "{url}");
+    }
+}
+
+// =============================================================================
+// Policy Tests
+// =============================================================================
+
+fn sign_url(url: &str, key_pair: &ring::signature::Ed25519KeyPair) -> String {
+    let (payload, _) = signing_payload(&format!("{url}&sig=")).unwrap();
+    let signature = BASE64_URL_SAFE_NO_PAD.encode(key_pair.sign(payload.as_bytes()).as_ref());
+    format!("{url}&sig={signature}")
+}
+
+#[test]
+fn test_policy_auto_accepts_only_trusted_signatures() {
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    let rng = ring::rand::SystemRandom::new();
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+
+    let url = sign_url(
+        "ccswitch://v1/import?resource=skill&repo=owner/repo&directory=skills",
+        &key_pair,
+    );
+    let request = parse_deeplink_url(&url).unwrap();
+
+    let mut policy = DeepLinkPolicySettings {
+        confirm_mode: DeepLinkConfirmMode::AutoAcceptSigned,
+        trusted_keys: vec![BASE64_STANDARD.encode(key_pair.public_key().as_ref())],
+        ..Default::default()
+    };
+    assert_eq!(
+        evaluate(&url, &request, &policy),
+        (PolicyDecision::AutoAccept, true)
+    );
+
+    // Tampered link no longer verifies
+    let tampered = url.replace("owner/repo", "evil/repo");
+    assert_eq!(
+        evaluate(&tampered, &request, &policy),
+        (PolicyDecision::Ask, false)
+    );
+
+    // Signed but the user still wants to confirm everything
+    policy.confirm_mode = DeepLinkConfirmMode::AlwaysAsk;
+    assert_eq!(
+        evaluate(&url, &request, &policy),
+        (PolicyDecision::Ask, true)
+    );
+}
+
+#[test]
+fn test_policy_blocks_api_keys_and_disallowed_resources() {
+    let url = "ccswitch://v1/import?resource=provider&app=claude&name=Test&endpoint=https%3A%2F%2Fapi.example.com&apiKey=sk-test-123";
+    let request = parse_deeplink_url(url).unwrap();
+
+    let policy = DeepLinkPolicySettings {
+        block_api_key_links: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        evaluate(url, &request, &policy).0,
+        PolicyDecision::Block(_)
+    ));
+
+    // Keys embedded in an inline MCP config are detected too
+    let config = BASE64_STANDARD.encode(
+        r#"{"mcpServers":{"gh":{"command":"gh","env":{"GITHUB_TOKEN":"ghp_abcdefghijklmnop"}}}}"#,
+    );
+    let mcp_url = format!("ccswitch://v1/import?resource=mcp&apps=claude&config={config}");
+    let mcp_request = parse_deeplink_url(&mcp_url).unwrap();
+    assert!(matches!(
+        evaluate(&mcp_url, &mcp_request, &policy).0,
+        PolicyDecision::Block(_)
+    ));
+
+    let policy = DeepLinkPolicySettings {
+        allowed_resources: vec!["prompt".to_string(), "skill".to_string()],
+        ..Default::default()
+    };
+    assert!(matches!(
+        evaluate(url, &request, &policy).0,
+        PolicyDecision::Block(_)
+    ));
+    assert_eq!(
+        evaluate(url, &request, &DeepLinkPolicySettings::default()),
+        (PolicyDecision::Ask, false)
+    );
+}
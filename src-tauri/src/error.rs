@@ -1,8 +1,12 @@
 use std::path::Path;
 use std::sync::PoisonError;
 
+use serde::{Serialize, Serializer};
+use serde_json::Value;
 use thiserror::Error;
 
+use crate::i18n::{self, Locale};
+
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("配置错误: {0}")]
@@ -44,11 +48,12 @@ pub enum AppError {
     McpValidation(String),
     #[error("{0}")]
     Message(String),
-    #[error("{zh} ({en})")]
+    #[error("{}", crate::i18n::tr(.key, .args))]
     Localized {
         key: &'static str,
         zh: String,
         en: String,
+        args: Vec<(&'static str, String)>,
     },
     #[error("数据库错误: {0}")]
     Database(String),
@@ -76,15 +81,74 @@ impl AppError {
         }
     }
 
-    pub fn localized(key: &'static str, zh: impl Into<String>, en: impl Into<String>) -> Self {
+    /// 多语言错误，消息模板见 `locales/*.json`
+    pub fn localized(key: &'static str) -> Self {
+        Self::localized_with(key, &[])
+    }
+
+    /// 带参数的多语言错误，参数替换模板中的 `{name}`
+    pub fn localized_with(key: &'static str, args: &[(&'static str, String)]) -> Self {
         Self::Localized {
             key,
-            zh: zh.into(),
-            en: en.into(),
+            zh: i18n::translate(Locale::Zh, key, args),
+            en: i18n::translate(Locale::En, key, args),
+            args: args.to_vec(),
+        }
+    }
+
+    /// 稳定的错误码，前端据此分支处理（多语言错误使用其消息 key）
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Config(_) => "config",
+            Self::InvalidInput(_) => "invalid_input",
+            Self::Io { .. } | Self::IoContext { .. } => "io",
+            Self::Json { .. } => "json.parse",
+            Self::JsonSerialize { .. } => "json.serialize",
+            Self::Toml { .. } => "toml.parse",
+            Self::Lock(_) => "lock",
+            Self::McpValidation(_) => "mcp.validation",
+            Self::Message(_) => "message",
+            Self::Localized { key, .. } => *key,
+            Self::Database(_) => "database",
+        }
+    }
+
+    /// 附加信息：相关路径或多语言消息参数
+    fn details(&self) -> Option<Value> {
+        match self {
+            Self::Io { path, .. } | Self::Json { path, .. } | Self::Toml { path, .. } => {
+                Some(serde_json::json!({ "path": path }))
+            }
+            Self::IoContext { context, .. } => Some(serde_json::json!({ "context": context })),
+            Self::Localized { args, .. } if !args.is_empty() => Some(Value::Object(
+                args.iter()
+                    .map(|(name, value)| (name.to_string(), Value::String(value.clone())))
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn to_payload(&self) -> ErrorPayload {
+        let mut details = self.details();
+        if let Some(details) = details.as_mut() {
+            crate::scrub::scrub_value(details);
+        }
+        ErrorPayload {
+            code: self.code().to_string(),
+            // 底层错误可能携带配置片段或 URL，返回前端前统一脱敏
+            message: crate::scrub::scrub_secrets(&self.to_string()),
+            details,
         }
     }
 }
 
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_payload().serialize(serializer)
+    }
+}
+
 impl<T> From<PoisonError<T>> for AppError {
     fn from(err: PoisonError<T>) -> Self {
         Self::Lock(err.to_string())
@@ -97,6 +161,74 @@ impl From<AppError> for String {
     }
 }
 
+/// 命令返回给前端的结构化错误
+///
+/// 前端按 `code` 判断错误类型，`message` 为当前语言的提示文本。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ErrorPayload {
+    pub code: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+impl std::fmt::Display for ErrorPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<AppError> for ErrorPayload {
+    fn from(err: AppError) -> Self {
+        let payload = err.to_payload();
+        crate::services::crash_report::CrashReportService::record_command_error(&payload);
+        payload
+    }
+}
+
+impl From<String> for ErrorPayload {
+    fn from(message: String) -> Self {
+        let message = crate::scrub::scrub_secrets(&message);
+        let payload = Self::from_scrubbed(message);
+        crate::services::crash_report::CrashReportService::record_command_error(&payload);
+        payload
+    }
+}
+
+impl ErrorPayload {
+    /// 由已脱敏的消息构造
+    fn from_scrubbed(message: String) -> Self {
+        // Skill 服务的错误由 format_skill_error 编码为 JSON，这里还原其错误码；
+        // message 保留原始 JSON，前端 skillErrorParser 仍可解析
+        if let Ok(Value::Object(mut obj)) = serde_json::from_str::<Value>(&message) {
+            if let Some(Value::String(code)) = obj.remove("code") {
+                return Self {
+                    code,
+                    message,
+                    details: Some(Value::Object(obj)),
+                };
+            }
+        }
+        Self {
+            code: "message".to_string(),
+            message,
+            details: None,
+        }
+    }
+}
+
+impl From<&str> for ErrorPayload {
+    fn from(message: &str) -> Self {
+        Self::from(message.to_string())
+    }
+}
+
+impl From<anyhow::Error> for ErrorPayload {
+    fn from(err: anyhow::Error) -> Self {
+        Self::from(err.to_string())
+    }
+}
+
 /// 格式化为 JSON 错误字符串，前端可解析为结构化错误
 pub fn format_skill_error(
     code: &str,
@@ -121,3 +253,49 @@ pub fn format_skill_error(
         format!("ERROR:{code}")
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn localized_error_payload_uses_key_as_code() {
+        let err = AppError::localized_with("provider.codex.auth.missing", &[("id", "p1".into())]);
+        let value = serde_json::to_value(&err).expect("serialize error");
+        assert_eq!(value["code"], "provider.codex.auth.missing");
+        assert_eq!(value["message"], err.to_string());
+        assert_eq!(value["details"]["id"], "p1");
+    }
+
+    #[test]
+    fn plain_errors_map_to_stable_codes() {
+        let payload = ErrorPayload::from(AppError::InvalidInput("bad".into()));
+        assert_eq!(payload.code, "invalid_input");
+        assert!(payload.details.is_none());
+
+        let payload = ErrorPayload::from("something failed");
+        assert_eq!(payload.code, "message");
+        assert_eq!(payload.message, "something failed");
+    }
+
+    #[test]
+    fn skill_error_json_keeps_its_code() {
+        let raw = format_skill_error("SKILL_NOT_FOUND", &[("directory", "demo")], None);
+        let payload = ErrorPayload::from(raw.clone());
+        assert_eq!(payload.code, "SKILL_NOT_FOUND");
+        assert_eq!(payload.message, raw);
+        assert_eq!(payload.details.unwrap()["context"]["directory"], "demo");
+    }
+
+    #[test]
+    fn payload_scrubs_secrets() {
+        let payload = ErrorPayload::from(AppError::Message(
+            "请求失败: https://api.example.com?key=1&apiKey=sk-abcdef1234567890".into(),
+        ));
+        assert!(!payload.message.contains("abcdef1234567890"));
+        assert!(payload.message.contains("apiKey=***"));
+
+        let payload = ErrorPayload::from("Bearer abcdefghijkl rejected".to_string());
+        assert_eq!(payload.message, "Bearer *** rejected");
+    }
+}
@@ -87,10 +87,9 @@ pub fn parse_env_file_strict(content: &str) -> Result<HashMap<String, String>, A
 
         // 检查是否包含 =
         if !line.contains('=') {
-            return Err(AppError::localized(
+            return Err(AppError::localized_with(
                 "gemini.env.parse_error.no_equals",
-                format!("Gemini .env 文件格式错误（第 {line_number} 行）：缺少 '=' 分隔符\n行内容: {line}"),
-                format!("Invalid Gemini .env format (line {line_number}): missing '=' separator\nLine: {line}"),
+                &[("line_number", line_number.to_string()), ("line", line.to_string())],
             ));
         }
 
@@ -101,19 +100,17 @@ pub fn parse_env_file_strict(content: &str) -> Result<HashMap<String, String>, A
 
             // 验证 key 不为空
             if key.is_empty() {
-                return Err(AppError::localized(
+                return Err(AppError::localized_with(
                     "gemini.env.parse_error.empty_key",
-                    format!("Gemini .env 文件格式错误（第 {line_number} 行）：环境变量名不能为空\n行内容: {line}"),
-                    format!("Invalid Gemini .env format (line {line_number}): variable name cannot be empty\nLine: {line}"),
+                    &[("line_number", line_number.to_string()), ("line", line.to_string())],
                 ));
             }
 
             // 验证 key 只包含字母、数字和下划线
             if !key.chars().all(|c| c.is_alphanumeric() || c == '_') {
-                return Err(AppError::localized(
+                return Err(AppError::localized_with(
                     "gemini.env.parse_error.invalid_key",
-                    format!("Gemini .env 文件格式错误（第 {line_number} 行）：环境变量名只能包含字母、数字和下划线\n变量名: {key}"),
-                    format!("Invalid Gemini .env format (line {line_number}): variable name can only contain letters, numbers, and underscores\nVariable: {key}"),
+                    &[("line_number", line_number.to_string()), ("key", key.to_string())],
                 ));
             }
 
@@ -228,22 +225,14 @@ pub fn validate_gemini_settings(settings: &Value) -> Result<(), AppError> {
     // 如果有 env 字段，验证它是一个对象
     if let Some(env) = settings.get("env") {
         if !env.is_object() {
-            return Err(AppError::localized(
-                "gemini.validation.invalid_env",
-                "Gemini 配置格式错误: env 必须是对象",
-                "Gemini config invalid: env must be an object",
-            ));
+            return Err(AppError::localized("gemini.validation.invalid_env"));
         }
     }
 
     // 如果有 config 字段，验证它是对象或 null
     if let Some(config) = settings.get("config") {
         if !(config.is_object() || config.is_null()) {
-            return Err(AppError::localized(
-                "gemini.validation.invalid_config",
-                "Gemini 配置格式错误: config 必须是对象",
-                "Gemini config invalid: config must be an object",
-            ));
+            return Err(AppError::localized("gemini.validation.invalid_config"));
         }
     }
 
@@ -267,11 +256,7 @@ pub fn validate_gemini_settings_strict(settings: &Value) -> Result<(), AppError>
 
     // 如果 env 不为空，检查必需字段 GEMINI_API_KEY
     if !env_map.contains_key("GEMINI_API_KEY") {
-        return Err(AppError::localized(
-            "gemini.validation.missing_api_key",
-            "Gemini 配置缺少必需字段: GEMINI_API_KEY",
-            "Gemini config missing required field: GEMINI_API_KEY",
-        ));
+        return Err(AppError::localized("gemini.validation.missing_api_key"));
     }
 
     Ok(())
@@ -372,6 +357,102 @@ pub fn write_google_oauth_settings() -> Result<(), AppError> {
     update_selected_type("oauth-personal")
 }
 
+/// 获取 Gemini OAuth 凭据文件路径（`~/.gemini/oauth_creds.json`）
+pub fn get_gemini_oauth_creds_path() -> PathBuf {
+    get_gemini_dir().join("oauth_creds.json")
+}
+
+/// 获取 Gemini 当前登录账号记录文件路径（`~/.gemini/google_accounts.json`）
+pub fn get_google_accounts_path() -> PathBuf {
+    get_gemini_dir().join("google_accounts.json")
+}
+
+/// 读取当前 OAuth 凭据（文件不存在时返回 None）
+pub fn read_gemini_oauth_creds() -> Result<Option<Value>, AppError> {
+    let path = get_gemini_oauth_creds_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    let value = serde_json::from_str(&content).map_err(|e| AppError::json(&path, e))?;
+    Ok(Some(value))
+}
+
+/// 写入 OAuth 凭据（原子写入，权限 600）
+pub fn write_gemini_oauth_creds(creds: &Value) -> Result<(), AppError> {
+    let path = get_gemini_oauth_creds_path();
+    crate::config::write_json_file(&path, creds)?;
+
+    // 设置文件权限为 600（仅所有者可读写）
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))
+            .map_err(|e| AppError::io(&path, e))?;
+    }
+
+    Ok(())
+}
+
+/// 读取 google_accounts.json 中的当前账号邮箱
+pub fn read_active_google_account() -> Option<String> {
+    let path = get_google_accounts_path();
+    let content = fs::read_to_string(&path).ok()?;
+    let value: Value = serde_json::from_str(&content).ok()?;
+    value
+        .get("active")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// 更新 google_accounts.json 中的当前账号，原账号移入 `old` 列表
+pub fn set_active_google_account(email: &str) -> Result<(), AppError> {
+    let path = get_google_accounts_path();
+    let mut value = if path.exists() {
+        let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+        serde_json::from_str::<Value>(&content).unwrap_or_else(|_| serde_json::json!({}))
+    } else {
+        serde_json::json!({})
+    };
+
+    if !value.is_object() {
+        value = serde_json::json!({});
+    }
+    let obj = value
+        .as_object_mut()
+        .ok_or_else(|| AppError::Config("google_accounts.json 根必须是对象".into()))?;
+
+    let previous = obj
+        .get("active")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let mut old: Vec<String> = obj
+        .get("old")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if let Some(prev) = previous.filter(|p| p != email && !p.is_empty()) {
+        if !old.contains(&prev) {
+            old.push(prev);
+        }
+    }
+    old.retain(|e| e != email);
+
+    obj.insert("active".to_string(), Value::String(email.to_string()));
+    obj.insert(
+        "old".to_string(),
+        Value::Array(old.into_iter().map(Value::String).collect()),
+    );
+
+    crate::config::write_json_file(&path, &value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,394 @@
+//! 切换钩子
+//!
+//! 在切换供应商前后执行设置中配置的用户命令（如切换 Codex 后 `pkill -USR1 codex`、
+//! 切换前运行备份脚本）。命令通过系统 shell 执行，带超时并捕获输出；
+//! 失败时按钩子的策略决定继续还是中止。最近的执行记录保存在内存中供界面查看。
+
+use std::collections::VecDeque;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::settings::{HookFailurePolicy, HookPhase, SwitchHook};
+
+/// 未设置超时时的默认值（秒）
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const MAX_TIMEOUT_SECS: u64 = 600;
+/// 每个输出流保留的最大字节数
+const MAX_OUTPUT_BYTES: usize = 8 * 1024;
+/// 内存中保留的执行记录数
+const MAX_RECENT_RUNS: usize = 50;
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// 命令结束后等待输出读取完成的最长时间
+const OUTPUT_GRACE: Duration = Duration::from_secs(1);
+
+/// 钩子执行的上下文，以环境变量传给命令
+#[derive(Debug, Clone)]
+pub struct HookContext {
+    pub app_type: AppType,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub previous_provider_id: Option<String>,
+}
+
+impl HookContext {
+    fn env(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("CC_SWITCH_APP", self.app_type.as_str().to_string()),
+            ("CC_SWITCH_PROVIDER_ID", self.provider_id.clone()),
+            ("CC_SWITCH_PROVIDER_NAME", self.provider_name.clone()),
+            (
+                "CC_SWITCH_PREVIOUS_PROVIDER_ID",
+                self.previous_provider_id.clone().unwrap_or_default(),
+            ),
+        ]
+    }
+}
+
+/// 一次钩子执行的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookRun {
+    pub name: String,
+    pub phase: HookPhase,
+    pub app: String,
+    pub provider_id: String,
+    /// 开始时间（Unix 秒）
+    pub started_at: i64,
+    pub duration_ms: u64,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+    /// 无法启动命令时的错误
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn recent_runs() -> &'static Mutex<VecDeque<HookRun>> {
+    static RUNS: OnceLock<Mutex<VecDeque<HookRun>>> = OnceLock::new();
+    RUNS.get_or_init(|| Mutex::new(VecDeque::new()))
+}
+
+fn record(run: &HookRun) {
+    if let Ok(mut runs) = recent_runs().lock() {
+        runs.push_front(run.clone());
+        runs.truncate(MAX_RECENT_RUNS);
+    }
+}
+
+/// 最近的钩子执行记录（最新的在前）
+pub fn recent() -> Vec<HookRun> {
+    recent_runs()
+        .lock()
+        .map(|runs| runs.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// 设置中适用于该应用与时机的已启用钩子
+fn hooks_for(hooks: &[SwitchHook], app_type: &AppType, phase: HookPhase) -> Vec<SwitchHook> {
+    hooks
+        .iter()
+        .filter(|hook| hook.enabled && hook.phase == phase && !hook.command.trim().is_empty())
+        .filter(|hook| {
+            hook.app
+                .as_deref()
+                .map(str::trim)
+                .filter(|app| !app.is_empty())
+                .map_or(true, |app| app.eq_ignore_ascii_case(app_type.as_str()))
+        })
+        .cloned()
+        .collect()
+}
+
+/// 执行切换前钩子；策略为 Abort 的钩子失败时返回错误以取消切换
+pub fn run_before(context: &HookContext) -> Result<(), AppError> {
+    let settings = crate::settings::get_settings();
+    for hook in hooks_for(&settings.switch_hooks, &context.app_type, HookPhase::Before) {
+        let run = run_hook(&hook, context);
+        if !run.success && hook.on_failure == HookFailurePolicy::Abort {
+            return Err(AppError::localized_with(
+                "hooks.aborted",
+                &[
+                    ("name", hook.name.clone()),
+                    ("reason", failure_reason(&run)),
+                ],
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 执行切换后钩子（切换已完成，失败只记录日志）
+pub fn run_after(context: &HookContext) {
+    let settings = crate::settings::get_settings();
+    for hook in hooks_for(&settings.switch_hooks, &context.app_type, HookPhase::After) {
+        let run = run_hook(&hook, context);
+        if !run.success && hook.on_failure == HookFailurePolicy::Abort {
+            log::warn!("切换后钩子 '{}' 失败，跳过其余钩子", hook.name);
+            break;
+        }
+    }
+}
+
+/// 在后台线程执行切换后钩子，不阻塞调用方（无适用钩子时不启动线程）
+pub fn spawn_after(context: HookContext) {
+    let settings = crate::settings::get_settings();
+    if hooks_for(&settings.switch_hooks, &context.app_type, HookPhase::After).is_empty() {
+        return;
+    }
+    let spawned = thread::Builder::new()
+        .name("cc-switch-hooks".to_string())
+        .spawn(move || run_after(&context));
+    if let Err(e) = spawned {
+        log::warn!("启动切换后钩子线程失败: {e}");
+    }
+}
+
+/// 执行单个钩子并记录结果
+pub fn run_hook(hook: &SwitchHook, context: &HookContext) -> HookRun {
+    let timeout = match hook.timeout_secs {
+        0 => DEFAULT_TIMEOUT_SECS,
+        secs => secs.min(MAX_TIMEOUT_SECS),
+    };
+    let mut run = HookRun {
+        name: hook.name.clone(),
+        phase: hook.phase,
+        app: context.app_type.as_str().to_string(),
+        provider_id: context.provider_id.clone(),
+        started_at: chrono::Utc::now().timestamp(),
+        duration_ms: 0,
+        success: false,
+        exit_code: None,
+        timed_out: false,
+        stdout: String::new(),
+        stderr: String::new(),
+        error: None,
+    };
+
+    let start = Instant::now();
+    if let Err(e) = execute(
+        &hook.command,
+        context,
+        Duration::from_secs(timeout),
+        &mut run,
+    ) {
+        run.error = Some(e);
+    }
+    run.duration_ms = start.elapsed().as_millis() as u64;
+
+    if run.success {
+        log::info!("钩子 '{}' 执行成功（{} ms）", run.name, run.duration_ms);
+    } else {
+        log::warn!("钩子 '{}' 执行失败: {}", run.name, failure_reason(&run));
+    }
+    record(&run);
+    run
+}
+
+fn shell_command(command: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    }
+    #[cfg(not(windows))]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+/// 启动命令并等待结束；超时后结束进程
+fn execute(
+    command: &str,
+    context: &HookContext,
+    timeout: Duration,
+    run: &mut HookRun,
+) -> Result<(), String> {
+    let mut cmd = shell_command(command);
+    cmd.envs(context.env())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(home) = crate::test_utils::home_dir() {
+        cmd.current_dir(home);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+    // 在独立线程中读取输出，避免管道写满导致子进程阻塞
+    let stdout = child.stdout.take().map(spawn_reader);
+    let stderr = child.stderr.take().map(spawn_reader);
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait().map_err(|e| e.to_string())? {
+            Some(status) => break Some(status),
+            None if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+            None => thread::sleep(POLL_INTERVAL),
+        }
+    };
+
+    // 命令启动的后台进程可能继续持有管道，最多再等一小段时间读取剩余输出
+    let grace = Instant::now() + OUTPUT_GRACE;
+    let readers = [&stdout, &stderr];
+    while Instant::now() < grace
+        && readers
+            .iter()
+            .any(|reader| reader.as_ref().is_some_and(|r| !r.is_finished()))
+    {
+        thread::sleep(POLL_INTERVAL);
+    }
+    run.stdout = stdout.map(OutputReader::take).unwrap_or_default();
+    run.stderr = stderr.map(OutputReader::take).unwrap_or_default();
+    match status {
+        Some(status) => {
+            run.exit_code = status.code();
+            run.success = status.success();
+        }
+        None => run.timed_out = true,
+    }
+    Ok(())
+}
+
+/// 后台读取的输出（只保留开头部分）
+struct OutputReader {
+    buffer: Arc<Mutex<Vec<u8>>>,
+    handle: thread::JoinHandle<()>,
+}
+
+impl OutputReader {
+    fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    fn take(self) -> String {
+        let kept = self
+            .buffer
+            .lock()
+            .map(|buffer| buffer.clone())
+            .unwrap_or_default();
+        crate::scrub::scrub_secrets(String::from_utf8_lossy(&kept).trim())
+    }
+}
+
+fn spawn_reader(mut pipe: impl Read + Send + 'static) -> OutputReader {
+    let buffer = Arc::new(Mutex::new(Vec::new()));
+    let shared = Arc::clone(&buffer);
+    let handle = thread::spawn(move || {
+        let mut buf = [0u8; 4096];
+        while let Ok(n) = pipe.read(&mut buf) {
+            if n == 0 {
+                break;
+            }
+            if let Ok(mut kept) = shared.lock() {
+                let room = MAX_OUTPUT_BYTES.saturating_sub(kept.len());
+                kept.extend_from_slice(&buf[..n.min(room)]);
+            }
+        }
+    });
+    OutputReader { buffer, handle }
+}
+
+fn failure_reason(run: &HookRun) -> String {
+    if let Some(error) = &run.error {
+        return error.clone();
+    }
+    if run.timed_out {
+        return "timed out".to_string();
+    }
+    let detail = run.stderr.lines().next().unwrap_or_default();
+    match run.exit_code {
+        Some(code) if detail.is_empty() => format!("exit code {code}"),
+        Some(code) => format!("exit code {code}: {detail}"),
+        None => "terminated by signal".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(name: &str, app: Option<&str>, phase: HookPhase, command: &str) -> SwitchHook {
+        SwitchHook {
+            name: name.to_string(),
+            app: app.map(str::to_string),
+            phase,
+            command: command.to_string(),
+            timeout_secs: 0,
+            on_failure: HookFailurePolicy::Continue,
+            enabled: true,
+        }
+    }
+
+    fn context() -> HookContext {
+        HookContext {
+            app_type: AppType::Codex,
+            provider_id: "relay".to_string(),
+            provider_name: "Relay".to_string(),
+            previous_provider_id: None,
+        }
+    }
+
+    #[test]
+    fn selects_hooks_by_app_and_phase() {
+        let mut disabled = hook("disabled", None, HookPhase::After, "true");
+        disabled.enabled = false;
+        let hooks = vec![
+            hook("global", None, HookPhase::After, "true"),
+            hook("codex", Some("Codex"), HookPhase::After, "true"),
+            hook("claude", Some("claude"), HookPhase::After, "true"),
+            hook("before", None, HookPhase::Before, "true"),
+            disabled,
+        ];
+        let names: Vec<String> = hooks_for(&hooks, &AppType::Codex, HookPhase::After)
+            .into_iter()
+            .map(|hook| hook.name)
+            .collect();
+        assert_eq!(names, vec!["global", "codex"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn captures_output_exit_code_and_timeout() {
+        let run = run_hook(
+            &hook(
+                "echo",
+                None,
+                HookPhase::After,
+                "echo \"$CC_SWITCH_APP/$CC_SWITCH_PROVIDER_ID\"; echo oops >&2; exit 3",
+            ),
+            &context(),
+        );
+        assert!(!run.success);
+        assert_eq!(run.exit_code, Some(3));
+        assert_eq!(run.stdout, "codex/relay");
+        assert_eq!(failure_reason(&run), "exit code 3: oops");
+
+        let mut slow = hook("slow", None, HookPhase::Before, "sleep 5");
+        slow.timeout_secs = 1;
+        let run = run_hook(&slow, &context());
+        assert!(run.timed_out);
+        assert!(run.duration_ms < 4_000);
+    }
+}
@@ -0,0 +1,170 @@
+//! 后端消息国际化
+//!
+//! 消息模板按 key 存放在 `locales/{zh,en,ja}.json`（编译时嵌入，启动时解析），
+//! 模板中的 `{name}` 由调用方提供的参数替换。当前语言取自 `AppSettings.language`，
+//! 缺失的翻译依次回退到英文、key 本身。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// 支持的语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    Zh,
+    En,
+    Ja,
+}
+
+impl Locale {
+    const ALL: [Locale; 3] = [Locale::Zh, Locale::En, Locale::Ja];
+
+    /// 由设置中的 language 解析，未设置或不支持时使用中文
+    pub fn from_language(language: Option<&str>) -> Self {
+        match language.map(str::trim) {
+            Some("en") => Locale::En,
+            Some("ja") => Locale::Ja,
+            _ => Locale::Zh,
+        }
+    }
+
+    fn catalog_source(self) -> &'static str {
+        match self {
+            Locale::Zh => include_str!("../locales/zh.json"),
+            Locale::En => include_str!("../locales/en.json"),
+            Locale::Ja => include_str!("../locales/ja.json"),
+        }
+    }
+
+    fn index(self) -> u8 {
+        match self {
+            Locale::Zh => 0,
+            Locale::En => 1,
+            Locale::Ja => 2,
+        }
+    }
+}
+
+type Catalog = HashMap<String, String>;
+
+static CATALOGS: OnceLock<HashMap<Locale, Catalog>> = OnceLock::new();
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+fn catalogs() -> &'static HashMap<Locale, Catalog> {
+    CATALOGS.get_or_init(|| {
+        Locale::ALL
+            .into_iter()
+            .map(|locale| {
+                let catalog = serde_json::from_str(locale.catalog_source()).unwrap_or_else(|e| {
+                    log::error!("解析 {locale:?} 语言包失败: {e}");
+                    Catalog::new()
+                });
+                (locale, catalog)
+            })
+            .collect()
+    })
+}
+
+/// 启动时加载语言包并设置当前语言
+pub fn init(language: Option<&str>) {
+    let catalogs = catalogs();
+    let reference = &catalogs[&Locale::Zh];
+    for locale in [Locale::En, Locale::Ja] {
+        let missing = reference
+            .keys()
+            .filter(|key| !catalogs[&locale].contains_key(*key))
+            .count();
+        if missing > 0 {
+            log::warn!("{locale:?} 语言包缺少 {missing} 条翻译");
+        }
+    }
+    set_language(language);
+}
+
+/// 切换当前语言（设置保存后调用）
+pub fn set_language(language: Option<&str>) {
+    CURRENT.store(Locale::from_language(language).index(), Ordering::Relaxed);
+}
+
+pub fn current_locale() -> Locale {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Locale::En,
+        2 => Locale::Ja,
+        _ => Locale::Zh,
+    }
+}
+
+/// 按语言渲染消息
+pub fn translate(locale: Locale, key: &str, args: &[(&str, String)]) -> String {
+    let catalogs = catalogs();
+    let template = catalogs
+        .get(&locale)
+        .and_then(|c| c.get(key))
+        .or_else(|| catalogs.get(&Locale::En).and_then(|c| c.get(key)))
+        .map(String::as_str)
+        .unwrap_or(key);
+
+    let mut message = template.to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{name}}}"), value);
+    }
+    message
+}
+
+/// 按当前语言渲染消息
+pub fn tr(key: &str, args: &[(&str, String)]) -> String {
+    translate(current_locale(), key, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalogs_share_keys_and_placeholders() {
+        let catalogs = catalogs();
+        let placeholders = |s: &str| {
+            let mut names: Vec<String> = s
+                .split('{')
+                .skip(1)
+                .filter_map(|rest| rest.split_once('}').map(|(name, _)| name.to_string()))
+                .filter(|name| name.chars().all(|c| c.is_ascii_lowercase() || c == '_'))
+                .filter(|name| !name.is_empty())
+                .collect();
+            names.sort();
+            names.dedup();
+            names
+        };
+
+        let zh = &catalogs[&Locale::Zh];
+        assert!(!zh.is_empty());
+        for locale in [Locale::En, Locale::Ja] {
+            let other = &catalogs[&locale];
+            assert_eq!(zh.len(), other.len(), "{locale:?} key count differs");
+            for (key, template) in zh {
+                let translated = other
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{locale:?} missing key {key}"));
+                assert_eq!(
+                    placeholders(template),
+                    placeholders(translated),
+                    "{locale:?} placeholders differ for {key}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn translate_substitutes_args_and_falls_back() {
+        let args = [("e", "boom".to_string())];
+        assert_eq!(
+            translate(Locale::Ja, "models.request_failed", &args),
+            "モデル一覧のリクエストに失敗しました: boom"
+        );
+        assert_eq!(
+            translate(Locale::En, "models.request_failed", &args),
+            "Failed to request model list: boom"
+        );
+        assert_eq!(translate(Locale::Ja, "no.such.key", &[]), "no.such.key");
+    }
+}
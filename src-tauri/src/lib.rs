@@ -1,5 +1,6 @@
 mod app_config;
 mod app_store;
+mod auth;
 mod auto_launch;
 mod claude_mcp;
 mod claude_plugin;
@@ -12,18 +13,27 @@ mod error;
 mod gemini_config;
 mod gemini_mcp;
 mod grok_config;
+mod hooks;
+mod i18n;
 mod init_status;
 mod mcp;
+mod network;
+mod notifications;
+mod policy;
 mod prompt;
 mod prompt_files;
+mod prompt_frontmatter;
 mod provider;
 mod provider_defaults;
 mod qwen_config;
+mod scrub;
 mod services;
 mod settings;
 mod store;
 mod test_utils;
 mod tray;
+mod tray_icon;
+mod webhooks;
 
 pub use app_config::{AppType, McpApps, McpServer, MultiAppConfig};
 pub use codex_config::{get_codex_auth_path, get_codex_config_path, write_codex_live_atomic};
@@ -33,7 +43,7 @@ pub use grok_config::{get_grok_dir, get_grok_settings_path, read_grok_settings,
 pub use qwen_config::{get_qwen_dir, get_qwen_settings_path, read_qwen_settings, write_qwen_settings};
 pub use database::Database;
 pub use deeplink::{import_provider_from_deeplink, parse_deeplink_url, DeepLinkImportRequest};
-pub use error::AppError;
+pub use error::{AppError, ErrorPayload};
 pub use mcp::{
     import_from_claude, import_from_codex, import_from_gemini, import_from_grok, remove_server_from_claude,
     remove_server_from_codex, remove_server_from_gemini, remove_server_from_grok, sync_enabled_to_claude,
@@ -42,11 +52,15 @@ pub use mcp::{
 };
 pub use provider::{Provider, ProviderMeta};
 pub use services::{
-    ConfigService, EndpointLatency, McpService, PromptService, ProviderService, SkillService,
-    SpeedtestService,
+    ConfigService, DbBackupService, DbLeaseService, DbMaintenanceService, EndpointLatency,
+    McpService, PromptService, ProviderService, ProvisionService, SkillService, SpeedtestService,
+    UpdateService,
 };
 pub use settings::{update_settings, AppSettings};
 pub use store::AppState;
+use deeplink::PolicyDecision;
+use notifications::NotificationCategory;
+use services::{ImportKind, OnboardingService};
 use tauri_plugin_deep_link::DeepLinkExt;
 use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
 
@@ -71,7 +85,10 @@ fn handle_deeplink_url(
         return false;
     }
 
-    log::info!("✓ Deep link URL detected from {source}: {url_str}");
+    log::info!(
+        "✓ Deep link URL detected from {source}: {}",
+        scrub::scrub_secrets(url_str)
+    );
 
     match crate::deeplink::parse_deeplink_url(url_str) {
         Ok(request) => {
@@ -82,10 +99,37 @@ fn handle_deeplink_url(
                 request.name
             );
 
-            if let Err(e) = app.emit("deeplink-import", &request) {
-                log::error!("✗ Failed to emit deeplink-import event: {e}");
-            } else {
-                log::info!("✓ Emitted deeplink-import event to frontend");
+            // 导入策略：阻止 / 签名链接自动导入 / 弹出确认框
+            let policy = crate::settings::get_settings().deeplink_policy;
+            let (decision, signed) = crate::deeplink::evaluate_policy(url_str, &request, &policy);
+            crate::deeplink::record_decision(source, &request, &decision, signed);
+
+            match decision {
+                PolicyDecision::Block(reason) => {
+                    if let Err(e) = app.emit(
+                        "deeplink-blocked",
+                        serde_json::json!({
+                            "resource": request.resource,
+                            "name": request.name,
+                            "reason": reason
+                        }),
+                    ) {
+                        log::error!("✗ Failed to emit deeplink-blocked event: {e}");
+                    }
+                }
+                // 动作链接（切换供应商等）不导入任何内容，直接在后端执行
+                PolicyDecision::AutoAccept if request.resource == "action" => {
+                    run_deeplink_action(app, request);
+                    return true;
+                }
+                PolicyDecision::AutoAccept => auto_import_deeplink(app, request),
+                PolicyDecision::Ask => {
+                    if let Err(e) = app.emit("deeplink-import", &request) {
+                        log::error!("✗ Failed to emit deeplink-import event: {e}");
+                    } else {
+                        log::info!("✓ Emitted deeplink-import event to frontend");
+                    }
+                }
             }
 
             if focus_main_window {
@@ -98,13 +142,14 @@ fn handle_deeplink_url(
             }
         }
         Err(e) => {
-            log::error!("✗ Failed to parse deep link URL: {e}");
+            let error = scrub::scrub_secrets(&e.to_string());
+            log::error!("✗ Failed to parse deep link URL: {error}");
 
             if let Err(emit_err) = app.emit(
                 "deeplink-error",
                 serde_json::json!({
-                    "url": url_str,
-                    "error": e.to_string()
+                    "url": scrub::scrub_secrets(url_str),
+                    "error": error
                 }),
             ) {
                 log::error!("✗ Failed to emit deeplink-error event: {emit_err}");
@@ -115,6 +160,158 @@ fn handle_deeplink_url(
     true
 }
 
+/// 处理命令行 `--apply-project [path]`：切换到目录所属项目的供应商
+///
+/// 未给出目录时使用 `cwd`；结果通过 `project-applied` / `project-apply-error` 事件通知前端。
+fn handle_project_args(app: &tauri::AppHandle, args: &[String], cwd: &std::path::Path) -> bool {
+    let Some(path) = project_arg(args, cwd) else {
+        return false;
+    };
+    log::info!("✓ Project argument detected: {path}");
+
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(state) = app.try_state::<store::AppState>() else {
+            log::warn!("应用状态尚未就绪，忽略项目参数: {path}");
+            return;
+        };
+        match services::ProjectService::apply(&state, &path) {
+            Ok(result) => commands::notify_project_applied(&app, &state, &result),
+            Err(e) => {
+                log::error!("✗ 应用项目失败: {e}");
+                if let Err(emit_err) = app.emit(
+                    "project-apply-error",
+                    serde_json::json!({ "path": path, "error": e.to_string() }),
+                ) {
+                    log::error!("✗ Failed to emit project-apply-error event: {emit_err}");
+                }
+            }
+        }
+    });
+    true
+}
+
+/// 解析 `--apply-project <path>` / `--apply-project=<path>`，相对路径基于 `cwd`
+fn project_arg(args: &[String], cwd: &std::path::Path) -> Option<String> {
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        let value = if let Some(value) = arg.strip_prefix("--apply-project=") {
+            Some(value.to_string())
+        } else if arg == "--apply-project" {
+            iter.next_if(|next| !next.starts_with("--")).cloned()
+        } else {
+            continue;
+        };
+        let path = match value.filter(|v| !v.trim().is_empty()) {
+            Some(value) => cwd.join(value.trim()),
+            None => cwd.to_path_buf(),
+        };
+        return Some(path.to_string_lossy().to_string());
+    }
+    None
+}
+
+/// 命令行 `--hidden`：启动时不显示主窗口，仅保留托盘图标
+const HIDDEN_ARG: &str = "--hidden";
+
+fn has_hidden_arg(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == HIDDEN_ARG)
+}
+
+/// 启动时显示主窗口；设置了启动时最小化或带 `--hidden` 参数时只保留托盘
+fn show_main_window_on_launch(app: &tauri::AppHandle, args: &[String]) {
+    let settings = crate::settings::get_settings();
+    let start_hidden = settings.start_minimized || has_hidden_arg(args);
+    if start_hidden && settings.show_in_tray {
+        log::info!("启动时最小化到托盘");
+        #[cfg(target_os = "macos")]
+        {
+            tray::apply_tray_policy(app, false);
+        }
+        return;
+    }
+    if start_hidden {
+        // 不显示托盘图标时隐藏窗口将无法再打开主界面
+        log::warn!("未启用托盘图标，忽略启动时最小化");
+    }
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// 直接导入策略允许免确认的深链接，结果通过事件通知前端
+fn auto_import_deeplink(app: &tauri::AppHandle, request: crate::deeplink::DeepLinkImportRequest) {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(state) = app.try_state::<store::AppState>() else {
+            // 应用状态尚未就绪时退回确认流程
+            if let Err(e) = app.emit("deeplink-import", &request) {
+                log::error!("✗ Failed to emit deeplink-import event: {e}");
+            }
+            return;
+        };
+
+        match crate::deeplink::import_deeplink_request(&state, request.clone()) {
+            Ok(result) => {
+                log::info!("✓ Auto-imported {} from signed deep link", request.resource);
+                if request.resource == "project" {
+                    tray::refresh_tray(&app, &state);
+                }
+                if let Err(e) = app.emit(
+                    "deeplink-imported",
+                    serde_json::json!({ "request": request, "result": result }),
+                ) {
+                    log::error!("✗ Failed to emit deeplink-imported event: {e}");
+                }
+            }
+            Err(e) => {
+                let error = scrub::scrub_secrets(&e.to_string());
+                log::error!("✗ Failed to auto-import deep link: {error}");
+                if let Err(emit_err) = app.emit(
+                    "deeplink-error",
+                    serde_json::json!({ "url": "", "error": error }),
+                ) {
+                    log::error!("✗ Failed to emit deeplink-error event: {emit_err}");
+                }
+            }
+        }
+    });
+}
+
+/// 在后端执行动作链接（无需确认，不聚焦主窗口）
+fn run_deeplink_action(app: &tauri::AppHandle, request: crate::deeplink::DeepLinkImportRequest) {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(state) = app.try_state::<store::AppState>() else {
+            log::warn!("✗ App state not ready, dropping deep link action");
+            return;
+        };
+
+        match crate::deeplink::run_action(&app, &state, &request) {
+            Ok(result) => {
+                if let Err(e) = app.emit(
+                    "deeplink-action",
+                    serde_json::json!({ "request": request, "result": result }),
+                ) {
+                    log::error!("✗ Failed to emit deeplink-action event: {e}");
+                }
+            }
+            Err(e) => {
+                let error = scrub::scrub_secrets(&e.to_string());
+                log::error!("✗ Failed to run deep link action: {error}");
+                if let Err(emit_err) = app.emit(
+                    "deeplink-error",
+                    serde_json::json!({ "url": "", "error": error }),
+                ) {
+                    log::error!("✗ Failed to emit deeplink-error event: {emit_err}");
+                }
+            }
+        }
+    });
+}
+
 /// 更新托盘菜单的Tauri命令
 #[tauri::command]
 async fn update_tray_menu(
@@ -126,6 +323,7 @@ async fn update_tray_menu(
             if let Some(tray) = app.tray_by_id("main") {
                 tray.set_menu(Some(new_menu))
                     .map_err(|e| format!("更新托盘菜单失败: {e}"))?;
+                tray_icon::refresh(&app, state.inner());
                 return Ok(true);
             }
             Ok(false)
@@ -139,11 +337,14 @@ async fn update_tray_menu(
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // 崩溃报告开关在 panic 时读取，hook 尽早安装
+    services::CrashReportService::install_panic_hook();
+
     let mut builder = tauri::Builder::default();
 
     #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
     {
-        builder = builder.plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
             log::info!("=== Single Instance Callback Triggered ===");
             log::info!("Args count: {}", args.len());
             for (i, arg) in args.iter().enumerate() {
@@ -163,7 +364,13 @@ pub fn run() {
                 log::info!("ℹ No deep link URL found in args (this is expected on macOS when launched via system)");
             }
 
-            // Show and focus window regardless
+            // Project switch from command line (`cc-switch --apply-project <path>`)
+            handle_project_args(app, &args, std::path::Path::new(&cwd));
+
+            // Show and focus window unless launched with `--hidden` (e.g. autostart)
+            if has_hidden_arg(&args) {
+                return;
+            }
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.unminimize();
                 let _ = window.show();
@@ -175,8 +382,11 @@ pub fn run() {
     let builder = builder
         // 注册 deep-link 插件（处理 macOS AppleEvent 和其他平台的深链接）
         .plugin(tauri_plugin_deep_link::init())
-        // 拦截窗口关闭：根据设置决定是否最小化到托盘
+        // 系统主题变化时更新托盘图标；拦截窗口关闭：根据设置决定是否最小化到托盘
         .on_window_event(|window, event| {
+            if let tauri::WindowEvent::ThemeChanged(_) = event {
+                tray_icon::on_theme_changed(window.app_handle());
+            }
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
                 let settings = crate::settings::get_settings();
 
@@ -198,6 +408,8 @@ pub fn run() {
         })
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .setup(|app| {
@@ -239,18 +451,33 @@ pub fn run() {
                 }
             }
 
-            // 初始化日志
-            if cfg!(debug_assertions) {
-                app.handle().plugin(
-                    tauri_plugin_log::Builder::default()
-                        .level(log::LevelFilter::Info)
-                        .build(),
-                )?;
-            }
-
             // 预先刷新 Store 覆盖配置，确保 AppState 初始化时可读取到最新路径
             app_store::refresh_app_config_dir_override(app.handle());
 
+            // 初始化日志：滚动写入配置目录下的 logs/，调试构建额外输出到控制台
+            {
+                use services::logs;
+                use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+
+                let mut log_builder = tauri_plugin_log::Builder::default()
+                    .level(log::LevelFilter::Info)
+                    .max_file_size(logs::LOG_MAX_FILE_SIZE)
+                    .rotation_strategy(RotationStrategy::KeepSome(logs::LOG_KEEP_FILES))
+                    .clear_targets()
+                    .target(Target::new(TargetKind::Folder {
+                        path: logs::log_dir(),
+                        file_name: Some(logs::LOG_FILE_NAME.to_string()),
+                    }));
+                if cfg!(debug_assertions) {
+                    log_builder = log_builder.target(Target::new(TargetKind::Stdout));
+                }
+                app.handle().plugin(log_builder.build())?;
+            }
+
+            // 加载后端消息语言包
+            crate::i18n::init(crate::settings::get_settings().language.as_deref());
+            crate::notifications::init(app.handle());
+
             // 初始化数据库
             let app_config_dir = crate::config::get_app_config_dir();
             let db_path = app_config_dir.join("cc-switch.db");
@@ -322,6 +549,11 @@ pub fn run() {
                 }
             }
 
+            // 设置改由数据库存储（首次启动时从 settings.json 迁移）
+            if let Err(e) = crate::settings::attach_database(db.clone()) {
+                log::warn!("设置存储切换到数据库失败，继续使用 settings.json: {e}");
+            }
+
             let app_state = AppState::new(db);
 
             // Disable webview context menu to prevent unwanted options
@@ -359,9 +591,18 @@ pub fn run() {
                 ) {
                     Ok(true) => {
                         log::info!("✓ Imported default provider for {}", app.as_str());
+                        OnboardingService::record_startup_import(&app, ImportKind::Provider, Ok(1));
+                    }
+                    Ok(false) => {
+                        // 已有供应商，静默跳过
+                        OnboardingService::record_startup_import(&app, ImportKind::Provider, Ok(0));
                     }
-                    Ok(false) => {} // 已有供应商，静默跳过
                     Err(e) => {
+                        OnboardingService::record_startup_import(
+                            &app,
+                            ImportKind::Provider,
+                            Err(&e),
+                        );
                         log::debug!(
                             "○ No default provider to import for {}: {}",
                             app.as_str(),
@@ -378,33 +619,139 @@ pub fn run() {
                 match crate::services::mcp::McpService::import_from_claude(&app_state) {
                     Ok(count) if count > 0 => {
                         log::info!("✓ Imported {count} MCP server(s) from Claude");
+                        OnboardingService::record_startup_import(
+                            &crate::app_config::AppType::Claude,
+                            ImportKind::Mcp,
+                            Ok(count),
+                        );
+                    }
+                    Ok(_) => {
+                        log::debug!("○ No Claude MCP servers found to import");
+                        OnboardingService::record_startup_import(
+                            &crate::app_config::AppType::Claude,
+                            ImportKind::Mcp,
+                            Ok(0),
+                        );
+                    }
+                    Err(e) => {
+                        log::warn!("✗ Failed to import Claude MCP: {e}");
+                        OnboardingService::record_startup_import(
+                            &crate::app_config::AppType::Claude,
+                            ImportKind::Mcp,
+                            Err(&e),
+                        );
+                        notifications::notify(
+                            NotificationCategory::McpSyncError,
+                            &[("app", "Claude".to_string()), ("e", e.to_string())],
+                        );
                     }
-                    Ok(_) => log::debug!("○ No Claude MCP servers found to import"),
-                    Err(e) => log::warn!("✗ Failed to import Claude MCP: {e}"),
                 }
 
                 match crate::services::mcp::McpService::import_from_codex(&app_state) {
                     Ok(count) if count > 0 => {
                         log::info!("✓ Imported {count} MCP server(s) from Codex");
+                        OnboardingService::record_startup_import(
+                            &crate::app_config::AppType::Codex,
+                            ImportKind::Mcp,
+                            Ok(count),
+                        );
+                    }
+                    Ok(_) => {
+                        log::debug!("○ No Codex MCP servers found to import");
+                        OnboardingService::record_startup_import(
+                            &crate::app_config::AppType::Codex,
+                            ImportKind::Mcp,
+                            Ok(0),
+                        );
+                    }
+                    Err(e) => {
+                        log::warn!("✗ Failed to import Codex MCP: {e}");
+                        OnboardingService::record_startup_import(
+                            &crate::app_config::AppType::Codex,
+                            ImportKind::Mcp,
+                            Err(&e),
+                        );
+                        notifications::notify(
+                            NotificationCategory::McpSyncError,
+                            &[("app", "Codex".to_string()), ("e", e.to_string())],
+                        );
                     }
-                    Ok(_) => log::debug!("○ No Codex MCP servers found to import"),
-                    Err(e) => log::warn!("✗ Failed to import Codex MCP: {e}"),
                 }
 
                 match crate::services::mcp::McpService::import_from_gemini(&app_state) {
                     Ok(count) if count > 0 => {
                         log::info!("✓ Imported {count} MCP server(s) from Gemini");
+                        OnboardingService::record_startup_import(
+                            &crate::app_config::AppType::Gemini,
+                            ImportKind::Mcp,
+                            Ok(count),
+                        );
+                    }
+                    Ok(_) => {
+                        log::debug!("○ No Gemini MCP servers found to import");
+                        OnboardingService::record_startup_import(
+                            &crate::app_config::AppType::Gemini,
+                            ImportKind::Mcp,
+                            Ok(0),
+                        );
+                    }
+                    Err(e) => {
+                        log::warn!("✗ Failed to import Gemini MCP: {e}");
+                        OnboardingService::record_startup_import(
+                            &crate::app_config::AppType::Gemini,
+                            ImportKind::Mcp,
+                            Err(&e),
+                        );
+                        notifications::notify(
+                            NotificationCategory::McpSyncError,
+                            &[("app", "Gemini".to_string()), ("e", e.to_string())],
+                        );
                     }
-                    Ok(_) => log::debug!("○ No Gemini MCP servers found to import"),
-                    Err(e) => log::warn!("✗ Failed to import Gemini MCP: {e}"),
                 }
 
                 match crate::services::mcp::McpService::import_from_grok(&app_state) {
                     Ok(count) if count > 0 => {
                         log::info!("✓ Imported {count} MCP server(s) from Grok");
+                        OnboardingService::record_startup_import(
+                            &crate::app_config::AppType::Grok,
+                            ImportKind::Mcp,
+                            Ok(count),
+                        );
+                    }
+                    Ok(_) => {
+                        log::debug!("○ No Grok MCP servers found to import");
+                        OnboardingService::record_startup_import(
+                            &crate::app_config::AppType::Grok,
+                            ImportKind::Mcp,
+                            Ok(0),
+                        );
+                    }
+                    Err(e) => {
+                        log::warn!("✗ Failed to import Grok MCP: {e}");
+                        OnboardingService::record_startup_import(
+                            &crate::app_config::AppType::Grok,
+                            ImportKind::Mcp,
+                            Err(&e),
+                        );
+                        notifications::notify(
+                            NotificationCategory::McpSyncError,
+                            &[("app", "Grok".to_string()), ("e", e.to_string())],
+                        );
                     }
-                    Ok(_) => log::debug!("○ No Grok MCP servers found to import"),
-                    Err(e) => log::warn!("✗ Failed to import Grok MCP: {e}"),
+                }
+            }
+
+            // 3.1 启动时与 live 配置对账 MCP 服务器（需在设置中开启）
+            if crate::settings::get_settings().mcp_reconcile_on_launch {
+                match McpService::reconcile_with_live(&app_state) {
+                    Ok(report) if !report.is_empty() => log::info!(
+                        "✓ MCP reconcile: {} imported, {} enabled, {} removed externally",
+                        report.imported.len(),
+                        report.enabled.len(),
+                        report.removed_externally.len()
+                    ),
+                    Ok(_) => log::debug!("○ MCP servers already in sync with live configs"),
+                    Err(e) => log::warn!("✗ Failed to reconcile MCP servers: {e}"),
                 }
             }
 
@@ -424,9 +771,28 @@ pub fn run() {
                     ) {
                         Ok(count) if count > 0 => {
                             log::info!("✓ Imported {count} prompt(s) for {}", app.as_str());
+                            OnboardingService::record_startup_import(
+                                &app,
+                                ImportKind::Prompt,
+                                Ok(count),
+                            );
+                        }
+                        Ok(_) => {
+                            log::debug!("○ No prompt file found for {}", app.as_str());
+                            OnboardingService::record_startup_import(
+                                &app,
+                                ImportKind::Prompt,
+                                Ok(0),
+                            );
+                        }
+                        Err(e) => {
+                            log::warn!("✗ Failed to import prompt for {}: {e}", app.as_str());
+                            OnboardingService::record_startup_import(
+                                &app,
+                                ImportKind::Prompt,
+                                Err(&e),
+                            );
                         }
-                        Ok(_) => log::debug!("○ No prompt file found for {}", app.as_str()),
-                        Err(e) => log::warn!("✗ Failed to import prompt for {}: {e}", app.as_str()),
                     }
                 }
             }
@@ -486,7 +852,7 @@ pub fn run() {
 
                     for (i, url) in urls.iter().enumerate() {
                         let url_str = url.as_str();
-                        log::info!("  URL[{i}]: {url_str}");
+                        log::info!("  URL[{i}]: {}", scrub::scrub_secrets(url_str));
 
                         if handle_deeplink_url(&app_handle, url_str, true, "on_open_url") {
                             break; // Process only first ccswitch:// URL
@@ -518,7 +884,7 @@ pub fn run() {
                 })
                 .show_menu_on_left_click(false);
 
-            // 统一使用应用默认图标；待托盘模板图标就绪后再启用
+            // 先使用应用默认图标，随后由 tray_icon::refresh 按系统主题替换
             if let Some(icon) = app.default_window_icon() {
                 tray_builder = tray_builder.icon(icon.clone());
             } else {
@@ -526,9 +892,41 @@ pub fn run() {
             }
 
             let _tray = tray_builder.build(app)?;
+            tray_icon::refresh(app.handle(), &app_state);
+            // 启动数据库定时备份与维护
+            DbBackupService::start_scheduler(app_state.db.clone());
+            DbMaintenanceService::start_scheduler(app_state.db.clone());
+            // 按设置导出脱敏配置快照（配置变化后或每天）
+            services::ConfigSnapshotService::start_scheduler(app_state.db.clone());
+            // 写入租约心跳（检测其他进程同时写入数据库）
+            DbLeaseService::start_heartbeat(app_state.db.clone(), app.handle().clone());
+            // 其他进程修改设置后刷新缓存并通知前端
+            services::SettingsWatchService::start(app.handle().clone());
+            // 监听提示词文件的外部修改
+            services::PromptWatchService::start(app_state.db.clone(), app.handle().clone());
+            // 剪贴板中的 ccswitch:// 链接与 API Key 识别（需在设置中开启）
+            services::ClipboardWatchService::start(app.handle().clone());
+            // 守护需要常驻的 MCP 进程（自动启动、崩溃重启）
+            services::McpSupervisorService::start_monitor(app.handle().clone());
+            // 探测网络连通性（离线模式与恢复联网后的暂存操作）
+            network::start_monitor(app.handle().clone());
+            // 后台定期检查更新
+            UpdateService::start_scheduler(app.handle().clone());
+            // 配置了团队模板地址时，首次启动自动导入
+            ProvisionService::provision_on_launch(app_state.db.clone());
+
             // 将同一个实例注入到全局状态，避免重复创建导致的不一致
             app.manage(app_state);
 
+            // 命令行 `--apply-project [path]`：启动时切换到项目的供应商
+            let args: Vec<String> = std::env::args().collect();
+            if let Ok(cwd) = std::env::current_dir() {
+                handle_project_args(app.handle(), &args, &cwd);
+            }
+
+            // 主窗口默认隐藏创建，按设置与命令行决定是否显示
+            show_main_window_on_launch(app.handle(), &args);
+
             // 初始化 SkillService
             match SkillService::new() {
                 Ok(skill_service) => {
@@ -543,20 +941,40 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             commands::get_providers,
+            commands::reveal_provider_credentials,
             commands::get_current_provider,
             commands::add_provider,
             commands::update_provider,
             commands::delete_provider,
+            commands::analyze_provider_deletion,
             commands::switch_provider,
+            commands::switch_provider_with_resolution,
             commands::import_default_config,
+            commands::parse_pasted_config,
+            commands::detect_other_tools,
+            commands::import_from_other_tool,
+            commands::scan_shell_env_providers,
+            commands::import_shell_env_providers,
+            commands::run_format_migrations,
             commands::get_claude_config_status,
             commands::get_config_status,
+            commands::get_live_drift,
             commands::get_claude_code_config_path,
             commands::get_config_dir,
             commands::open_config_folder,
             commands::pick_directory,
             commands::open_external,
             commands::get_init_error,
+            commands::run_diagnostics,
+            commands::lint_all,
+            commands::get_lint_config,
+            commands::save_lint_config,
+            commands::detect_cli_versions,
+            commands::get_recent_logs,
+            commands::export_logs_zip,
+            commands::list_crash_reports,
+            commands::export_crash_bundle,
+            commands::clear_crash_reports,
             commands::get_migration_result,
             commands::get_app_config_path,
             commands::open_app_config_folder,
@@ -569,20 +987,51 @@ pub fn run() {
             commands::write_grok_settings_command,
             commands::read_live_grok_settings,
             commands::sync_current_grok_provider_live,
+            commands::refresh_grok_models,
+            // Switch hooks
+            commands::get_recent_hook_runs,
+            commands::test_switch_hook,
+            // Network status / offline mode
+            commands::get_network_status,
+            commands::check_network,
             commands::get_settings,
             commands::save_settings,
+            commands::get_biometric_status,
+            commands::get_app_lock_status,
+            commands::set_app_lock_passcode,
+            commands::unlock_session,
+            commands::lock_session,
+            commands::get_effective_settings,
+            commands::test_webhook,
             commands::restart_app,
             commands::is_portable_mode,
             commands::get_claude_plugin_status,
             commands::read_claude_plugin_config,
             commands::apply_claude_plugin_config,
             commands::is_claude_plugin_applied,
+            commands::list_claude_plugins,
+            commands::set_claude_plugin_enabled,
+            commands::set_claude_marketplace_enabled,
+            commands::backup_claude_plugin_config,
             // Claude MCP management
             commands::get_claude_mcp_status,
             commands::read_claude_mcp_config,
             commands::upsert_claude_mcp_server,
             commands::delete_claude_mcp_server,
             commands::validate_mcp_command,
+            commands::validate_mcp_spec,
+            commands::reconcile_mcp_servers,
+            commands::get_mcp_reconcile_report,
+            commands::prune_orphaned_mcp,
+            commands::get_mcp_transport_matrix,
+            commands::get_mcp_bridges,
+            commands::save_mcp_bridge,
+            commands::remove_mcp_bridge,
+            commands::preview_mcp_bridge,
+            commands::get_mcp_process_status,
+            commands::save_mcp_supervised_command,
+            commands::remove_mcp_supervised_command,
+            commands::control_mcp_process,
             // New MCP via config.json (SSOT)
             commands::get_mcp_config,
             commands::upsert_mcp_server_in_config,
@@ -593,6 +1042,14 @@ pub fn run() {
             commands::upsert_mcp_server,
             commands::delete_mcp_server,
             commands::toggle_mcp_app,
+            commands::copy_mcp_enablement,
+            commands::import_from_cursor,
+            commands::import_from_vscode,
+            commands::import_from_windsurf,
+            commands::export_mcp_for,
+            commands::export_mcp_server,
+            commands::import_mcp_server_file,
+            commands::import_from_claude_desktop,
             // Prompt management
             commands::get_prompts,
             commands::upsert_prompt,
@@ -600,6 +1057,17 @@ pub fn run() {
             commands::enable_prompt,
             commands::import_prompt_from_file,
             commands::get_current_prompt_file_content,
+            commands::export_prompt_to_file,
+            commands::get_prompt_live_state,
+            commands::get_prompt_sets,
+            commands::upsert_prompt_set,
+            commands::delete_prompt_set,
+            commands::apply_prompt_set,
+            commands::get_projects,
+            commands::upsert_project,
+            commands::delete_project,
+            commands::get_active_project,
+            commands::apply_project,
             // ours: endpoint speed test + custom endpoint management
             commands::test_api_endpoints,
             commands::get_custom_endpoints,
@@ -612,9 +1080,40 @@ pub fn run() {
             // provider sort order management
             commands::update_providers_sort_order,
             commands::update_provider_pin_status,
+            commands::get_providers_grouped,
+            commands::get_category_order,
+            commands::set_category_order,
+            commands::set_provider_category,
+            commands::get_codex_auth_info,
+            commands::get_codex_auth_kinds,
+            commands::get_codex_profiles,
+            commands::switch_codex_profile,
+            commands::import_codex_profiles,
+            commands::set_provider_tags,
+            commands::get_providers_by_tags,
+            commands::get_provider_tags,
+            commands::verify_provider_key,
+            commands::copy_provider_credential,
+            commands::smoke_test_provider,
+            commands::launch_cli,
+            commands::get_known_models,
+            commands::validate_provider_models,
+            commands::list_remote_models,
             // theirs: config import/export and dialogs
             commands::export_config_to_file,
             commands::import_config_from_file,
+            commands::preview_config_merge,
+            commands::apply_config_merge,
+            commands::provision_from_url,
+            commands::list_db_backups,
+            commands::restore_db_backup,
+            commands::export_config_snapshot,
+            commands::list_backups,
+            commands::preview_backup,
+            commands::restore_backup,
+            commands::check_database_health,
+            commands::compact_database,
+            commands::get_db_lease_status,
             commands::save_file_dialog,
             commands::open_file_dialog,
             commands::sync_current_providers_live,
@@ -623,6 +1122,7 @@ pub fn run() {
             commands::merge_deeplink_config,
             commands::import_from_deeplink,
             commands::import_from_deeplink_unified,
+            commands::get_deeplink_decision_log,
             update_tray_menu,
             // Environment variable management
             commands::check_env_conflicts,
@@ -632,18 +1132,61 @@ pub fn run() {
             commands::set_gemini_proxy_enabled,
             // Skill management
             commands::get_skills,
+            commands::browse_skill_repo,
+            commands::search_skills,
             commands::install_skill,
             commands::uninstall_skill,
             commands::get_skill_repos,
             commands::add_skill_repo,
             commands::remove_skill_repo,
+            commands::set_skill_repo_token,
+            commands::create_local_skill,
+            commands::get_local_skills,
+            commands::install_local_skill,
+            commands::delete_local_skill,
+            commands::export_skill_as_zip,
+            commands::get_skill_share_link,
             // Auto launch
             commands::set_auto_launch,
             commands::get_auto_launch_status,
             // Custom icon management
             commands::save_custom_icon,
             commands::read_custom_icon,
+            commands::list_custom_icons,
             commands::delete_custom_icon,
+            // Budget & usage tracking
+            commands::get_provider_budget_status,
+            commands::get_budget_statuses,
+            commands::record_provider_usage,
+            commands::get_provider_usage_history,
+            commands::clear_provider_usage_history,
+            commands::get_cost_summary,
+            commands::get_model_pricing,
+            // Switch statistics
+            commands::get_switch_stats,
+            // Devices sharing a synced database
+            commands::list_devices,
+            commands::get_onboarding_state,
+            commands::run_guided_import,
+            commands::rename_device,
+            commands::remove_device,
+            // Provider notes & attachments
+            commands::render_provider_notes,
+            commands::export_provider_notes_html,
+            commands::add_provider_attachment,
+            commands::get_provider_attachments,
+            commands::save_provider_attachment,
+            commands::delete_provider_attachment,
+            // Gemini OAuth accounts
+            commands::list_gemini_oauth_accounts,
+            commands::capture_gemini_oauth_account,
+            commands::rename_gemini_oauth_account,
+            commands::delete_gemini_oauth_account,
+            commands::activate_gemini_oauth_account,
+            // App updates
+            commands::check_update,
+            commands::perform_update,
+            commands::skip_update_version,
         ]);
 
     let app = builder
@@ -651,6 +1194,17 @@ pub fn run() {
         .expect("error while running tauri application");
 
     app.run(|app_handle, event| {
+        // 退出前结束守护的 MCP 进程，并释放数据库写入租约，便于其他实例立即接管
+        if let RunEvent::Exit = event {
+            services::McpService::flush_pending_sync();
+            services::McpSupervisorService::stop_all();
+            if let Some(state) = app_handle.try_state::<AppState>() {
+                if let Err(e) = state.db.release_lease() {
+                    log::warn!("释放数据库写入租约失败: {e}");
+                }
+            }
+        }
+
         #[cfg(target_os = "macos")]
         {
             match event {
@@ -671,52 +1225,13 @@ pub fn run() {
                 RunEvent::Opened { urls } => {
                     if let Some(url) = urls.first() {
                         let url_str = url.to_string();
-                        log::info!("RunEvent::Opened with URL: {url_str}");
-
-                        if url_str.starts_with("ccswitch://") {
-                            // 解析并广播深链接事件，复用与 single_instance 相同的逻辑
-                            match crate::deeplink::parse_deeplink_url(&url_str) {
-                                Ok(request) => {
-                                    log::info!(
-                                        "Successfully parsed deep link from RunEvent::Opened: resource={}, app={:?}",
-                                        request.resource,
-                                        request.app
-                                    );
-
-                                    if let Err(e) =
-                                        app_handle.emit("deeplink-import", &request)
-                                    {
-                                        log::error!(
-                                            "Failed to emit deep link event from RunEvent::Opened: {e}"
-                                        );
-                                    }
-                                }
-                                Err(e) => {
-                                    log::error!(
-                                        "Failed to parse deep link URL from RunEvent::Opened: {e}"
-                                    );
-
-                                    if let Err(emit_err) = app_handle.emit(
-                                        "deeplink-error",
-                                        serde_json::json!({
-                                            "url": url_str,
-                                            "error": e.to_string()
-                                        }),
-                                    ) {
-                                        log::error!(
-                                            "Failed to emit deep link error event from RunEvent::Opened: {emit_err}"
-                                        );
-                                    }
-                                }
-                            }
+                        log::info!(
+                            "RunEvent::Opened with URL: {}",
+                            scrub::scrub_secrets(&url_str)
+                        );
 
-                            // 确保主窗口可见
-                            if let Some(window) = app_handle.get_webview_window("main") {
-                                let _ = window.unminimize();
-                                let _ = window.show();
-                                let _ = window.set_focus();
-                            }
-                        }
+                        // 复用与 single_instance 相同的解析与导入策略逻辑
+                        handle_deeplink_url(app_handle, &url_str, true, "RunEvent::Opened");
                     }
                 }
                 _ => {}
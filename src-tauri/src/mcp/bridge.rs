@@ -0,0 +1,270 @@
+//! 远程 MCP 服务器的 stdio 桥接
+//!
+//! 部分应用对 http / sse 传输支持有限（见 `transport`）。为服务器开启桥接后，
+//! 同步到这些应用时写入一个经 `mcp-remote` 或 `supergateway` 转发的 stdio 条目，
+//! 同一份服务器定义即可用于所有应用。桥接配置按服务器保存在 settings 表中。
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+
+use super::transport::supports_transport;
+
+/// 桥接工具
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum McpBridgeTool {
+    /// `npx -y mcp-remote <url>`
+    #[default]
+    McpRemote,
+    /// `npx -y supergateway --sse <url>` / `--streamableHttp <url>`
+    Supergateway,
+}
+
+/// 单个服务器的桥接配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpBridgeConfig {
+    pub server_id: String,
+    #[serde(default)]
+    pub tool: McpBridgeTool,
+    /// 需要桥接的应用；为空时桥接到不支持该服务器传输类型的应用
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub apps: Vec<AppType>,
+}
+
+impl McpBridgeConfig {
+    /// 该应用是否需要写入桥接条目
+    pub fn applies_to(&self, app: &AppType, spec: &Value) -> bool {
+        if self.apps.is_empty() {
+            !supports_transport(app, spec)
+        } else {
+            self.apps.contains(app)
+        }
+    }
+}
+
+/// 服务器的远程传输类型（stdio 服务器返回 None）
+fn remote_transport(spec: &Value) -> Option<&str> {
+    spec.get("type")
+        .and_then(Value::as_str)
+        .filter(|transport| matches!(*transport, "http" | "sse"))
+}
+
+/// 是否为可桥接的远程服务器
+pub fn is_bridgeable(spec: &Value) -> bool {
+    remote_transport(spec).is_some()
+}
+
+/// 请求头取值对应的环境变量名（如 `Authorization` → `MCP_HEADER_AUTHORIZATION`）
+fn header_env_name(name: &str) -> String {
+    let suffix: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("MCP_HEADER_{suffix}")
+}
+
+/// 生成桥接用的 stdio 条目
+///
+/// 请求头取值放入条目的 `env`，参数中只引用变量（`--header "Name:${MCP_HEADER_NAME}"`，
+/// 由 mcp-remote 展开），避免密钥出现在进程命令行中。supergateway 不展开环境变量，
+/// 带请求头时返回错误；stdio 服务器无需桥接，返回错误。
+pub fn bridge_spec(tool: McpBridgeTool, spec: &Value) -> Result<Value, AppError> {
+    let (Some(transport), Some(url)) = (
+        remote_transport(spec),
+        spec.get("url").and_then(Value::as_str),
+    ) else {
+        return Err(AppError::localized("mcp.bridge.not_remote"));
+    };
+
+    let is_sse = transport == "sse";
+    let mut args = vec!["-y".to_string()];
+    match tool {
+        McpBridgeTool::McpRemote => {
+            args.push("mcp-remote".to_string());
+            args.push(url.to_string());
+            args.push("--transport".to_string());
+            args.push(if is_sse { "sse-only" } else { "http-only" }.to_string());
+        }
+        McpBridgeTool::Supergateway => {
+            args.push("supergateway".to_string());
+            args.push(if is_sse { "--sse" } else { "--streamableHttp" }.to_string());
+            args.push(url.to_string());
+        }
+    }
+    let mut env = Map::new();
+    if let Some(headers) = spec.get("headers").and_then(Value::as_object) {
+        for (name, value) in headers {
+            let Some(value) = value.as_str() else {
+                continue;
+            };
+            if tool == McpBridgeTool::Supergateway {
+                return Err(AppError::localized("mcp.bridge.headers_unsupported"));
+            }
+            let var = header_env_name(name);
+            args.push("--header".to_string());
+            args.push(format!("{name}:${{{var}}}"));
+            env.insert(var, Value::String(value.to_string()));
+        }
+    }
+
+    let mut bridged = json!({
+        "type": "stdio",
+        "command": "npx",
+        "args": args,
+    });
+    if !env.is_empty() {
+        bridged["env"] = Value::Object(env);
+    }
+    Ok(bridged)
+}
+
+/// 按桥接配置得到写入该应用的条目（无需桥接时原样返回）
+pub fn apply_bridge(
+    bridges: &[McpBridgeConfig],
+    server_id: &str,
+    app: &AppType,
+    spec: Value,
+) -> Value {
+    let Some(bridge) = bridges
+        .iter()
+        .find(|bridge| bridge.server_id == server_id && bridge.applies_to(app, &spec))
+    else {
+        return spec;
+    };
+    match bridge_spec(bridge.tool, &spec) {
+        Ok(bridged) => bridged,
+        Err(_) => spec,
+    }
+}
+
+/// 按桥接配置得到写入 Claude Desktop 的条目
+///
+/// Claude Desktop 仅支持 stdio，服务器配置了桥接时远程定义总是经桥接写入
+/// （`apps` 只含 CLI 应用，不参与判断）。
+pub fn apply_claude_desktop_bridge(
+    bridges: &[McpBridgeConfig],
+    server_id: &str,
+    spec: Value,
+) -> Value {
+    let Some(bridge) = bridges.iter().find(|bridge| bridge.server_id == server_id) else {
+        return spec;
+    };
+    match bridge_spec(bridge.tool, &spec) {
+        Ok(bridged) => bridged,
+        Err(_) => spec,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_server_is_bridged_only_where_configured() {
+        let spec = json!({
+            "type": "sse",
+            "url": "https://mcp.example.com/sse",
+            "headers": { "Authorization": "Bearer x" }
+        });
+        let bridges = vec![McpBridgeConfig {
+            server_id: "remote".to_string(),
+            tool: McpBridgeTool::McpRemote,
+            apps: Vec::new(),
+        }];
+
+        let codex = apply_bridge(&bridges, "remote", &AppType::Codex, spec.clone());
+        assert_eq!(codex["command"], "npx");
+        assert_eq!(
+            codex["args"],
+            json!([
+                "-y",
+                "mcp-remote",
+                "https://mcp.example.com/sse",
+                "--transport",
+                "sse-only",
+                "--header",
+                "Authorization:${MCP_HEADER_AUTHORIZATION}"
+            ])
+        );
+        assert_eq!(
+            codex["env"],
+            json!({ "MCP_HEADER_AUTHORIZATION": "Bearer x" })
+        );
+        assert_eq!(
+            apply_bridge(&bridges, "remote", &AppType::Claude, spec.clone()),
+            spec
+        );
+        assert_eq!(
+            apply_bridge(&bridges, "other", &AppType::Codex, spec.clone()),
+            spec
+        );
+
+        let supergateway = bridge_spec(
+            McpBridgeTool::Supergateway,
+            &json!({ "type": "http", "url": "https://mcp.example.com/mcp" }),
+        )
+        .unwrap();
+        assert_eq!(
+            supergateway["args"],
+            json!([
+                "-y",
+                "supergateway",
+                "--streamableHttp",
+                "https://mcp.example.com/mcp"
+            ])
+        );
+        assert!(bridge_spec(McpBridgeTool::McpRemote, &json!({ "command": "uvx" })).is_err());
+    }
+
+    #[test]
+    fn header_secrets_stay_out_of_args() {
+        let spec = json!({
+            "type": "http",
+            "url": "https://mcp.example.com/mcp",
+            "headers": { "Authorization": "Bearer sk-secret", "X-Api-Key": "key-secret" }
+        });
+
+        let bridged = bridge_spec(McpBridgeTool::McpRemote, &spec).unwrap();
+        let args = bridged["args"].to_string();
+        assert!(!args.contains("sk-secret"));
+        assert!(!args.contains("key-secret"));
+        assert!(args.contains("X-Api-Key:${MCP_HEADER_X_API_KEY}"));
+        assert_eq!(bridged["env"]["MCP_HEADER_X_API_KEY"], "key-secret");
+
+        let err = bridge_spec(McpBridgeTool::Supergateway, &spec).unwrap_err();
+        assert_eq!(err.to_payload().code, "mcp.bridge.headers_unsupported");
+    }
+
+    #[test]
+    fn claude_desktop_uses_bridge_regardless_of_apps() {
+        let spec = json!({ "type": "http", "url": "https://mcp.example.com/mcp" });
+        let bridges = vec![McpBridgeConfig {
+            server_id: "remote".to_string(),
+            tool: McpBridgeTool::McpRemote,
+            apps: vec![AppType::Codex],
+        }];
+
+        let desktop = apply_claude_desktop_bridge(&bridges, "remote", spec.clone());
+        assert_eq!(desktop["type"], "stdio");
+        assert_eq!(desktop["command"], "npx");
+        assert_eq!(
+            apply_claude_desktop_bridge(&bridges, "other", spec.clone()),
+            spec
+        );
+        let stdio = json!({ "command": "uvx" });
+        assert_eq!(
+            apply_claude_desktop_bridge(&bridges, "remote", stdio.clone()),
+            stdio
+        );
+    }
+}
@@ -84,7 +84,9 @@ pub fn import_from_claude(config: &mut MultiAppConfig) -> Result<usize, AppError
                         gemini: false,
                         grok: false,
                         qwen: false,
+                        claude_desktop: false,
                     },
+                    app_overrides: Default::default(),
                     description: None,
                     homepage: None,
                     docs: None,
@@ -120,6 +122,19 @@ pub fn sync_single_server_to_claude(
     crate::claude_mcp::set_mcp_servers_map(&updated)
 }
 
+/// 批量同步多个 MCP 服务器到 Claude live 配置：一次读取、应用全部变更后只写回一次
+pub fn sync_servers_to_claude(
+    upserts: &HashMap<String, Value>,
+    removals: &[String],
+) -> Result<(), AppError> {
+    let mut current = crate::claude_mcp::read_mcp_servers_map()?;
+    for id in removals {
+        current.remove(id);
+    }
+    current.extend(upserts.iter().map(|(id, spec)| (id.clone(), spec.clone())));
+    crate::claude_mcp::set_mcp_servers_map(&current)
+}
+
 /// 从 Claude live 配置中移除单个 MCP 服务器
 pub fn remove_server_from_claude(id: &str) -> Result<(), AppError> {
     // 读取现有的 MCP 配置
@@ -0,0 +1,289 @@
+//! Claude Desktop MCP 同步和导入模块
+//!
+//! 目标文件为平台相关的 `claude_desktop_config.json`：
+//! - macOS: `~/Library/Application Support/Claude/claude_desktop_config.json`
+//! - Windows: `%APPDATA%\Claude\claude_desktop_config.json`
+//! - Linux: `~/.config/Claude/claude_desktop_config.json`
+//!
+//! Claude Desktop 的配置文件仅支持本地 stdio 服务器。
+
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::app_config::{McpApps, McpServer, MultiAppConfig};
+use crate::config::write_json_file;
+use crate::error::AppError;
+
+use super::editors::get_claude_desktop_config_path;
+use super::validation::validate_server_spec;
+
+fn read_root_at(path: &Path) -> Result<Value, AppError> {
+    if !path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    serde_json::from_str(&content).map_err(|e| AppError::json(path, e))
+}
+
+/// 读取 claude_desktop_config.json 中的 mcpServers 映射
+pub fn read_mcp_servers_map() -> Result<HashMap<String, Value>, AppError> {
+    read_mcp_servers_map_at(&get_claude_desktop_config_path())
+}
+
+fn read_mcp_servers_map_at(path: &Path) -> Result<HashMap<String, Value>, AppError> {
+    let root = read_root_at(path)?;
+    Ok(root
+        .get("mcpServers")
+        .and_then(|v| v.as_object())
+        .map(|obj| obj.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default())
+}
+
+/// 写回 mcpServers 字段，其他字段（如 globalShortcut）保持不变
+fn set_mcp_servers_map_at(path: &Path, servers: &HashMap<String, Value>) -> Result<(), AppError> {
+    let mut root = read_root_at(path)?;
+
+    let mut out: Map<String, Value> = Map::new();
+    for (id, spec) in servers {
+        let mut obj = spec.as_object().cloned().ok_or_else(|| {
+            AppError::McpValidation(format!("MCP 服务器 '{id}' 不是对象"))
+        })?;
+        // Claude Desktop 不使用 type 字段
+        obj.remove("type");
+        out.insert(id.clone(), Value::Object(obj));
+    }
+
+    {
+        let obj = root.as_object_mut().ok_or_else(|| {
+            AppError::Config("claude_desktop_config.json 根必须是对象".into())
+        })?;
+        obj.insert("mcpServers".into(), Value::Object(out));
+    }
+
+    write_json_file(path, &root)
+}
+
+/// 从 Claude Desktop 导入 mcpServers 到统一结构
+/// 已存在的服务器将启用 Claude Desktop，不覆盖其他字段和应用状态
+pub fn import_from_claude_desktop(config: &mut MultiAppConfig) -> Result<usize, AppError> {
+    import_from_path(&get_claude_desktop_config_path(), config)
+}
+
+fn import_from_path(path: &Path, config: &mut MultiAppConfig) -> Result<usize, AppError> {
+    let map = read_mcp_servers_map_at(path)?;
+
+    // 确保新结构存在
+    let servers = config.mcp.servers.get_or_insert_with(HashMap::new);
+
+    let mut changed = 0;
+    let mut errors = Vec::new();
+
+    for (id, spec) in map.iter() {
+        // 校验：单项失败不中止，收集错误继续处理
+        if let Err(e) = validate_server_spec(spec) {
+            log::warn!("跳过无效 MCP 服务器 '{id}': {e}");
+            errors.push(format!("{id}: {e}"));
+            continue;
+        }
+
+        if let Some(existing) = servers.get_mut(id) {
+            // 已存在：仅启用 Claude Desktop
+            if !existing.apps.claude_desktop {
+                existing.apps.claude_desktop = true;
+                changed += 1;
+                log::info!("MCP 服务器 '{id}' 已启用 Claude Desktop");
+            }
+        } else {
+            // 新建服务器：默认仅启用 Claude Desktop
+            servers.insert(
+                id.clone(),
+                McpServer {
+                    id: id.clone(),
+                    name: id.clone(),
+                    server: spec.clone(),
+                    apps: McpApps {
+                        claude_desktop: true,
+                        ..McpApps::default()
+                    },
+                    app_overrides: Default::default(),
+                    description: None,
+                    homepage: None,
+                    docs: None,
+                    tags: Vec::new(),
+                },
+            );
+            changed += 1;
+            log::info!("导入新 MCP 服务器 '{id}'");
+        }
+    }
+
+    if !errors.is_empty() {
+        log::warn!("导入完成，但有 {} 项失败: {:?}", errors.len(), errors);
+    }
+
+    Ok(changed)
+}
+
+/// 将单个 MCP 服务器同步到 Claude Desktop 配置
+pub fn sync_single_server_to_claude_desktop(id: &str, server_spec: &Value) -> Result<(), AppError> {
+    sync_single_server_at(&get_claude_desktop_config_path(), id, server_spec)
+}
+
+fn sync_single_server_at(path: &Path, id: &str, server_spec: &Value) -> Result<(), AppError> {
+    let typ = server_spec
+        .get("type")
+        .and_then(|v| v.as_str())
+        .unwrap_or("stdio");
+    if typ != "stdio" {
+        return Err(AppError::McpValidation(format!(
+            "Claude Desktop 仅支持 stdio 类型的 MCP 服务器，'{id}' 的类型为 {typ}"
+        )));
+    }
+
+    let mut updated = read_mcp_servers_map_at(path)?;
+    updated.insert(id.to_string(), server_spec.clone());
+    set_mcp_servers_map_at(path, &updated)
+}
+
+/// 从 Claude Desktop 配置中移除单个 MCP 服务器
+pub fn remove_server_from_claude_desktop(id: &str) -> Result<(), AppError> {
+    remove_server_at(&get_claude_desktop_config_path(), id)
+}
+
+fn remove_server_at(path: &Path, id: &str) -> Result<(), AppError> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let mut current = read_mcp_servers_map_at(path)?;
+    if current.remove(id).is_none() {
+        return Ok(());
+    }
+    set_mcp_servers_map_at(path, &current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn config_path(dir: &tempfile::TempDir) -> PathBuf {
+        dir.path().join("claude_desktop_config.json")
+    }
+
+    fn read_json(path: &Path) -> Value {
+        serde_json::from_str(&fs::read_to_string(path).expect("read config")).expect("parse config")
+    }
+
+    #[test]
+    fn missing_or_empty_config_reads_as_no_servers() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = config_path(&dir);
+        assert!(read_mcp_servers_map_at(&path).unwrap().is_empty());
+
+        fs::write(&path, "  \n").unwrap();
+        assert!(read_mcp_servers_map_at(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn sync_strips_type_and_keeps_other_root_keys() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = config_path(&dir);
+        fs::write(&path, r#"{"globalShortcut":"Ctrl+Space"}"#).unwrap();
+
+        let spec = json!({"type": "stdio", "command": "npx", "args": ["-y", "server"]});
+        sync_single_server_at(&path, "fetch", &spec).unwrap();
+
+        let root = read_json(&path);
+        assert_eq!(root["globalShortcut"], "Ctrl+Space");
+        assert_eq!(root["mcpServers"]["fetch"]["command"], "npx");
+        assert!(root["mcpServers"]["fetch"].get("type").is_none());
+    }
+
+    #[test]
+    fn sync_rejects_remote_servers() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = config_path(&dir);
+
+        let spec = json!({"type": "http", "url": "https://example.com/mcp"});
+        assert!(sync_single_server_at(&path, "remote", &spec).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn set_map_rejects_non_object_specs() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = config_path(&dir);
+
+        let servers = HashMap::from([("bad".to_string(), json!("npx"))]);
+        assert!(set_mcp_servers_map_at(&path, &servers).is_err());
+    }
+
+    #[test]
+    fn remove_only_touches_the_named_server() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = config_path(&dir);
+        remove_server_at(&path, "absent").unwrap();
+        assert!(!path.exists());
+
+        sync_single_server_at(&path, "a", &json!({"command": "a"})).unwrap();
+        sync_single_server_at(&path, "b", &json!({"command": "b"})).unwrap();
+        remove_server_at(&path, "a").unwrap();
+
+        let servers = read_mcp_servers_map_at(&path).unwrap();
+        assert!(!servers.contains_key("a"));
+        assert_eq!(servers["b"]["command"], "b");
+    }
+
+    #[test]
+    fn import_enables_claude_desktop_and_skips_invalid_entries() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = config_path(&dir);
+        fs::write(
+            &path,
+            r#"{"mcpServers":{
+                "fetch":{"command":"uvx","args":["mcp-server-fetch"]},
+                "existing":{"command":"npx"},
+                "broken":{"args":["missing-command"]}
+            }}"#,
+        )
+        .unwrap();
+
+        let mut config = MultiAppConfig::default();
+        let servers = config.mcp.servers.get_or_insert_with(HashMap::new);
+        servers.insert(
+            "existing".into(),
+            McpServer {
+                id: "existing".into(),
+                name: "Existing".into(),
+                server: json!({"command": "node"}),
+                apps: McpApps {
+                    claude: true,
+                    ..McpApps::default()
+                },
+                app_overrides: Default::default(),
+                description: None,
+                homepage: None,
+                docs: None,
+                tags: Vec::new(),
+            },
+        );
+
+        assert_eq!(import_from_path(&path, &mut config).unwrap(), 2);
+
+        let servers = config.mcp.servers.as_ref().unwrap();
+        assert!(servers["fetch"].apps.claude_desktop);
+        assert!(!servers["fetch"].apps.claude);
+        assert!(!servers.contains_key("broken"));
+
+        let existing = &servers["existing"];
+        assert!(existing.apps.claude && existing.apps.claude_desktop);
+        assert_eq!(existing.name, "Existing");
+        assert_eq!(existing.server["command"], "node");
+    }
+}
@@ -6,7 +6,7 @@
 //! - JSON 到 TOML 的转换逻辑
 
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::app_config::{McpApps, McpConfig, McpServer, MultiAppConfig};
 use crate::error::AppError;
@@ -231,7 +231,9 @@ pub fn import_from_codex(config: &mut MultiAppConfig) -> Result<usize, AppError>
                             gemini: false,
                             grok: false,
                             qwen: false,
+                            claude_desktop: false,
                         },
+                        app_overrides: Default::default(),
                         description: None,
                         homepage: None,
                         docs: None,
@@ -266,6 +268,27 @@ pub fn import_from_codex(config: &mut MultiAppConfig) -> Result<usize, AppError>
     Ok(changed_total)
 }
 
+/// 读取 ~/.codex/config.toml 中已配置的 MCP 服务器 ID（含容错读取的 [mcp.servers]）
+pub fn read_live_server_ids() -> Result<HashSet<String>, AppError> {
+    let text = crate::codex_config::read_and_validate_codex_config_text()?;
+    if text.trim().is_empty() {
+        return Ok(HashSet::new());
+    }
+    let root: toml::Table = toml::from_str(&text)
+        .map_err(|e| AppError::McpValidation(format!("解析 ~/.codex/config.toml 失败: {e}")))?;
+
+    let tables = [
+        root.get("mcp").and_then(|mcp| mcp.get("servers")),
+        root.get("mcp_servers"),
+    ];
+    Ok(tables
+        .into_iter()
+        .flatten()
+        .filter_map(|servers| servers.as_table())
+        .flat_map(|servers| servers.keys().cloned())
+        .collect())
+}
+
 /// 将 config.json 中 Codex 的 enabled==true 项以 TOML 形式写入 ~/.codex/config.toml
 ///
 /// 格式策略：
@@ -419,6 +442,55 @@ pub fn remove_server_from_codex(id: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+/// 批量同步多个 MCP 服务器到 Codex live 配置：一次读取、应用全部变更后只写回一次
+/// 同样只写入 [mcp_servers]，并清理可能存在的错误格式 [mcp.servers]
+pub fn sync_servers_to_codex(
+    upserts: &HashMap<String, Value>,
+    removals: &[String],
+) -> Result<(), AppError> {
+    use toml_edit::Item;
+
+    let config_path = crate::codex_config::get_codex_config_path();
+    if !config_path.exists() && upserts.is_empty() {
+        return Ok(());
+    }
+    let base_text = crate::codex_config::read_and_validate_codex_config_text()?;
+    let mut doc = if base_text.trim().is_empty() {
+        toml_edit::DocumentMut::default()
+    } else {
+        base_text
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|e| AppError::McpValidation(format!("解析 Codex config.toml 失败: {e}")))?
+    };
+
+    if let Some(mcp_item) = doc.get_mut("mcp") {
+        if let Some(tbl) = mcp_item.as_table_like_mut() {
+            if tbl.contains_key("servers") {
+                log::warn!("检测到错误的 MCP 格式 [mcp.servers]，正在清理并迁移到 [mcp_servers]");
+                tbl.remove("servers");
+            }
+        }
+    }
+
+    if let Some(servers) = doc.get_mut("mcp_servers").and_then(|s| s.as_table_mut()) {
+        for id in removals {
+            servers.remove(id);
+        }
+    }
+    if !upserts.is_empty() {
+        if !doc.contains_key("mcp_servers") {
+            doc["mcp_servers"] = toml_edit::table();
+        }
+        let mut ids: Vec<_> = upserts.keys().collect();
+        ids.sort();
+        for id in ids {
+            doc["mcp_servers"][id.as_str()] = Item::Table(json_server_to_toml_table(&upserts[id])?);
+        }
+    }
+
+    crate::config::write_text_file(&config_path, &doc.to_string())
+}
+
 // ============================================================================
 // TOML 转换辅助函数
 // ============================================================================
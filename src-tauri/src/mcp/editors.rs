@@ -0,0 +1,627 @@
+//! 编辑器 MCP 导入导出模块（Cursor / VS Code / Windsurf / Claude Desktop）
+//!
+//! 读取各编辑器的 MCP 配置并转换为统一的 `McpServer` 结构。
+//! 编辑器不属于受管应用，导入的服务器默认不启用任何应用，由用户自行勾选。
+//! 反向导出时将已启用的服务器渲染为各编辑器的 JSON 格式。
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::app_config::{McpApps, McpServer, MultiAppConfig};
+use crate::config::write_json_file;
+use crate::error::AppError;
+
+use super::validation::validate_server_spec;
+
+/// 平台相关的用户配置根目录
+///
+/// - macOS: `~/Library/Application Support`
+/// - Windows: `%APPDATA%`
+/// - Linux: `~/.config`
+pub(crate) fn platform_config_dir() -> PathBuf {
+    #[cfg(target_os = "macos")]
+    {
+        crate::test_utils::home_dir()
+            .expect("无法获取用户主目录")
+            .join("Library")
+            .join("Application Support")
+    }
+    #[cfg(target_os = "windows")]
+    {
+        dirs::config_dir().unwrap_or_else(|| {
+            crate::test_utils::home_dir()
+                .expect("无法获取用户主目录")
+                .join("AppData")
+                .join("Roaming")
+        })
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        crate::test_utils::home_dir()
+            .expect("无法获取用户主目录")
+            .join(".config")
+    }
+}
+
+/// Cursor 全局 MCP 配置路径（~/.cursor/mcp.json）
+pub fn get_cursor_mcp_path() -> PathBuf {
+    crate::test_utils::home_dir()
+        .expect("无法获取用户主目录")
+        .join(".cursor")
+        .join("mcp.json")
+}
+
+/// VS Code 用户目录（settings.json / mcp.json 所在目录）
+pub fn get_vscode_user_dir() -> PathBuf {
+    platform_config_dir().join("Code").join("User")
+}
+
+/// Windsurf MCP 配置路径（~/.codeium/windsurf/mcp_config.json）
+pub fn get_windsurf_mcp_path() -> PathBuf {
+    crate::test_utils::home_dir()
+        .expect("无法获取用户主目录")
+        .join(".codeium")
+        .join("windsurf")
+        .join("mcp_config.json")
+}
+
+/// 去除 JSONC 中的注释与尾随逗号（VS Code settings.json 允许二者）
+pub(crate) fn strip_jsonc(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    let mut in_string = false;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if in_string {
+            out.push(c);
+            if c == '\\' && i + 1 < chars.len() {
+                out.push(chars[i + 1]);
+                i += 2;
+                continue;
+            }
+            if c == '"' {
+                in_string = false;
+            }
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+                i += 1;
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+                    i += 1;
+                }
+                i += 2;
+            }
+            ',' => {
+                // 尾随逗号：跳过空白与注释后第一个字符为 } 或 ] 时丢弃
+                if !matches!(next_significant(&chars, i + 1), Some('}') | Some(']')) {
+                    out.push(c);
+                }
+                i += 1;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+/// 从 `start` 起跳过空白与注释，返回第一个有效字符
+fn next_significant(chars: &[char], start: usize) -> Option<char> {
+    let mut i = start;
+    while i < chars.len() {
+        match chars[i] {
+            c if c.is_whitespace() => i += 1,
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+                    i += 1;
+                }
+                i += 2;
+            }
+            c => return Some(c),
+        }
+    }
+    None
+}
+
+fn read_jsonc_file(path: &Path) -> Result<Option<Value>, AppError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+    if content.trim().is_empty() {
+        return Ok(None);
+    }
+    let value = serde_json::from_str(&strip_jsonc(&content)).map_err(|e| AppError::json(path, e))?;
+    Ok(Some(value))
+}
+
+/// 将编辑器格式的服务器定义规范化为统一 MCP 格式
+///
+/// - Windsurf 的 `serverUrl` → `url`
+/// - 仅有 `url` 无 `type`：路径以 `/sse` 结尾视为 sse，否则视为 http
+/// - 移除编辑器特有的 UI 字段（如 `disabled`）
+fn normalize_editor_spec(spec: &Value) -> Value {
+    let Some(obj) = spec.as_object() else {
+        return spec.clone();
+    };
+    let mut obj = obj.clone();
+
+    if let Some(server_url) = obj.remove("serverUrl") {
+        obj.entry("url".to_string()).or_insert(server_url);
+    }
+
+    if !obj.contains_key("type") {
+        if let Some(url) = obj.get("url").and_then(|v| v.as_str()) {
+            let typ = if url.trim_end_matches('/').ends_with("/sse") {
+                "sse"
+            } else {
+                "http"
+            };
+            obj.insert("type".to_string(), Value::String(typ.to_string()));
+        }
+    }
+
+    obj.remove("disabled");
+    obj.remove("disabledTools");
+    obj.remove("autoApprove");
+
+    Value::Object(obj)
+}
+
+/// 将编辑器中的服务器映射合并到统一结构，返回新增数量
+///
+/// 已存在的服务器保持不变；新服务器默认不启用任何应用，并打上来源标签。
+fn merge_editor_servers(
+    config: &mut MultiAppConfig,
+    map: &Map<String, Value>,
+    source: &str,
+) -> usize {
+    let servers = config.mcp.servers.get_or_insert_with(HashMap::new);
+
+    let mut changed = 0;
+    let mut errors = Vec::new();
+
+    for (id, raw) in map.iter() {
+        let spec = normalize_editor_spec(raw);
+        // 校验：单项失败不中止，收集错误继续处理
+        if let Err(e) = validate_server_spec(&spec) {
+            log::warn!("跳过无效 MCP 服务器 '{id}' ({source}): {e}");
+            errors.push(format!("{id}: {e}"));
+            continue;
+        }
+
+        if servers.contains_key(id) {
+            continue;
+        }
+
+        servers.insert(
+            id.clone(),
+            McpServer {
+                id: id.clone(),
+                name: id.clone(),
+                server: spec,
+                apps: McpApps::default(),
+                app_overrides: Default::default(),
+                description: None,
+                homepage: None,
+                docs: None,
+                tags: vec![source.to_string()],
+            },
+        );
+        changed += 1;
+        log::info!("从 {source} 导入新 MCP 服务器 '{id}'");
+    }
+
+    if !errors.is_empty() {
+        log::warn!("导入完成，但有 {} 项失败: {:?}", errors.len(), errors);
+    }
+
+    changed
+}
+
+/// 从 Cursor 的 ~/.cursor/mcp.json 导入 mcpServers
+pub fn import_from_cursor(config: &mut MultiAppConfig) -> Result<usize, AppError> {
+    let Some(root) = read_jsonc_file(&get_cursor_mcp_path())? else {
+        return Ok(0);
+    };
+    let Some(map) = root.get("mcpServers").and_then(|v| v.as_object()) else {
+        return Ok(0);
+    };
+    Ok(merge_editor_servers(config, map, "cursor"))
+}
+
+/// 从 VS Code 导入 MCP 服务器
+///
+/// 同时读取用户目录下的 `mcp.json`（顶层 `servers`）与 `settings.json` 的 `mcp.servers` 块，
+/// 前者优先。
+pub fn import_from_vscode(config: &mut MultiAppConfig) -> Result<usize, AppError> {
+    let user_dir = get_vscode_user_dir();
+    let mut changed = 0;
+
+    if let Some(root) = read_jsonc_file(&user_dir.join("mcp.json"))? {
+        if let Some(map) = root.get("servers").and_then(|v| v.as_object()) {
+            changed += merge_editor_servers(config, map, "vscode");
+        }
+    }
+
+    if let Some(root) = read_jsonc_file(&user_dir.join("settings.json"))? {
+        let map = root
+            .get("mcp")
+            .and_then(|mcp| mcp.get("servers"))
+            .and_then(|v| v.as_object());
+        if let Some(map) = map {
+            changed += merge_editor_servers(config, map, "vscode");
+        }
+    }
+
+    Ok(changed)
+}
+
+/// 从 Windsurf 的 ~/.codeium/windsurf/mcp_config.json 导入 mcpServers
+pub fn import_from_windsurf(config: &mut MultiAppConfig) -> Result<usize, AppError> {
+    let Some(root) = read_jsonc_file(&get_windsurf_mcp_path())? else {
+        return Ok(0);
+    };
+    let Some(map) = root.get("mcpServers").and_then(|v| v.as_object()) else {
+        return Ok(0);
+    };
+    Ok(merge_editor_servers(config, map, "windsurf"))
+}
+
+// ============================================================================
+// 导出：统一结构 → 编辑器格式
+// ============================================================================
+
+/// MCP 导出目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum McpExportTarget {
+    Cursor,
+    Vscode,
+    ClaudeDesktop,
+}
+
+impl FromStr for McpExportTarget {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace(['-', '_'], "").as_str() {
+            "cursor" => Ok(McpExportTarget::Cursor),
+            "vscode" => Ok(McpExportTarget::Vscode),
+            "claudedesktop" => Ok(McpExportTarget::ClaudeDesktop),
+            other => Err(AppError::InvalidInput(format!(
+                "不支持的导出目标: '{other}'。可选值: cursor, vscode, claudeDesktop"
+            ))),
+        }
+    }
+}
+
+impl McpExportTarget {
+    /// 导出文件路径
+    pub fn config_path(&self) -> PathBuf {
+        match self {
+            McpExportTarget::Cursor => get_cursor_mcp_path(),
+            McpExportTarget::Vscode => get_vscode_user_dir().join("mcp.json"),
+            McpExportTarget::ClaudeDesktop => get_claude_desktop_config_path(),
+        }
+    }
+
+    /// 记录 cc-switch 已导出服务器时使用的键（与 `mcp_live_owned.app_type` 共用）
+    pub fn owner_key(&self) -> &'static str {
+        match self {
+            McpExportTarget::Cursor => "cursor",
+            McpExportTarget::Vscode => "vscode",
+            McpExportTarget::ClaudeDesktop => "claudeDesktop",
+        }
+    }
+
+    /// 服务器映射所在的顶层字段
+    fn servers_key(&self) -> &'static str {
+        match self {
+            McpExportTarget::Vscode => "servers",
+            McpExportTarget::Cursor | McpExportTarget::ClaudeDesktop => "mcpServers",
+        }
+    }
+}
+
+/// Claude Desktop 配置路径（平台相关）
+pub fn get_claude_desktop_config_path() -> PathBuf {
+    platform_config_dir()
+        .join("Claude")
+        .join("claude_desktop_config.json")
+}
+
+/// MCP 导出结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpExportResult {
+    pub target: McpExportTarget,
+    pub path: String,
+    /// 渲染后的完整文件内容（JSON 文本）
+    pub content: String,
+    /// 是否已写入文件
+    pub written: bool,
+    /// 目标不支持而被跳过的服务器 ID
+    pub skipped: Vec<String>,
+    /// 原文件含注释时写入前生成的备份路径
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup: Option<String>,
+}
+
+/// 将单个服务器定义渲染为目标编辑器格式；目标不支持该传输类型时返回 None
+fn render_spec_for(target: McpExportTarget, spec: &Value) -> Option<Value> {
+    let mut obj = spec.as_object()?.clone();
+    let typ = obj
+        .get("type")
+        .and_then(|v| v.as_str())
+        .unwrap_or("stdio")
+        .to_string();
+
+    match target {
+        // VS Code 要求显式 type 字段
+        McpExportTarget::Vscode => {
+            obj.insert("type".to_string(), Value::String(typ));
+        }
+        // Cursor 根据 command / url 推断传输类型
+        McpExportTarget::Cursor => {
+            obj.remove("type");
+        }
+        // Claude Desktop 配置文件仅支持本地 stdio 服务器
+        McpExportTarget::ClaudeDesktop => {
+            if typ != "stdio" {
+                return None;
+            }
+            obj.remove("type");
+        }
+    }
+
+    Some(Value::Object(obj))
+}
+
+/// 将服务器列表按 ID 合并到目标配置中，返回新的根对象与被跳过的 ID
+///
+/// 文件中的其他字段与用户自己添加的服务器保持不变；`owned` 为此前由 cc-switch 导出的
+/// 服务器 ID，其中不在本次导出列表（或被跳过）的条目会被移除。
+pub fn render_export(
+    target: McpExportTarget,
+    servers: &[(String, Value)],
+    owned: &[String],
+    existing_root: Option<Value>,
+) -> Result<(Value, Vec<String>), AppError> {
+    let mut root = existing_root.unwrap_or_else(|| serde_json::json!({}));
+    let obj = root
+        .as_object_mut()
+        .ok_or_else(|| AppError::Config("MCP 配置文件根必须是对象".into()))?;
+    let mut out = match obj.remove(target.servers_key()) {
+        Some(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+
+    let mut skipped = Vec::new();
+    for (id, spec) in servers {
+        match render_spec_for(target, spec) {
+            Some(rendered) => {
+                out.insert(id.clone(), rendered);
+            }
+            None => skipped.push(id.clone()),
+        }
+    }
+    for id in owned {
+        let exported = servers.iter().any(|(server_id, _)| server_id == id);
+        if !exported || skipped.contains(id) {
+            out.remove(id);
+        }
+    }
+
+    obj.insert(target.servers_key().to_string(), Value::Object(out));
+    Ok((root, skipped))
+}
+
+/// 渲染（并可选写入）目标编辑器的 MCP 配置
+///
+/// 原文件含 JSONC 注释或尾随逗号时，写入前另存一份 `.bak` 备份（写回的 JSON 不保留注释）。
+pub fn export_to_editor(
+    target: McpExportTarget,
+    servers: &[(String, Value)],
+    owned: &[String],
+    write: bool,
+) -> Result<McpExportResult, AppError> {
+    export_to_path(target, &target.config_path(), servers, owned, write)
+}
+
+fn export_to_path(
+    target: McpExportTarget,
+    path: &Path,
+    servers: &[(String, Value)],
+    owned: &[String],
+    write: bool,
+) -> Result<McpExportResult, AppError> {
+    let original = if path.exists() {
+        Some(fs::read_to_string(path).map_err(|e| AppError::io(path, e))?)
+    } else {
+        None
+    };
+    let existing = read_jsonc_file(path)?;
+    let (root, skipped) = render_export(target, servers, owned, existing)?;
+
+    let mut backup = None;
+    if write {
+        if let Some(original) = original.filter(|text| strip_jsonc(text) != *text) {
+            let backup_path = path.with_extension("json.bak");
+            fs::write(&backup_path, original).map_err(|e| AppError::io(&backup_path, e))?;
+            log::info!(
+                "{} 含注释，已备份到 {}",
+                path.display(),
+                backup_path.display()
+            );
+            backup = Some(backup_path.to_string_lossy().to_string());
+        }
+        write_json_file(path, &root)?;
+        log::info!(
+            "已导出 {} 个 MCP 服务器到 {}",
+            servers.len() - skipped.len(),
+            path.display()
+        );
+    }
+
+    let content =
+        serde_json::to_string_pretty(&root).map_err(|e| AppError::JsonSerialize { source: e })?;
+
+    Ok(McpExportResult {
+        target,
+        path: path.to_string_lossy().to_string(),
+        content,
+        written: write,
+        skipped,
+        backup,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn strip_jsonc_removes_comments_and_trailing_commas() {
+        let text = r#"{
+            // line comment
+            "a": "http://x//y", /* block */
+            "b": [1, 2,],
+        }"#;
+        let value: Value = serde_json::from_str(&strip_jsonc(text)).expect("valid json");
+        assert_eq!(value, json!({ "a": "http://x//y", "b": [1, 2] }));
+
+        // 尾随逗号后紧跟注释（VS Code / Cursor 设置中常见）
+        let text = "{\n  \"last\": { \"x\": 1 }, // note\n  /* end */\n}";
+        let value: Value = serde_json::from_str(&strip_jsonc(text)).expect("valid json");
+        assert_eq!(value, json!({ "last": { "x": 1 } }));
+    }
+
+    #[test]
+    fn normalize_maps_windsurf_server_url() {
+        let spec = normalize_editor_spec(&json!({ "serverUrl": "https://mcp.example.com/sse" }));
+        assert_eq!(spec["url"], "https://mcp.example.com/sse");
+        assert_eq!(spec["type"], "sse");
+
+        let spec = normalize_editor_spec(&json!({ "url": "https://mcp.example.com/mcp" }));
+        assert_eq!(spec["type"], "http");
+    }
+
+    #[test]
+    fn merge_skips_existing_and_invalid_servers() {
+        let mut config = MultiAppConfig::default();
+        let map = json!({
+            "ok": { "command": "npx", "args": ["-y", "pkg"] },
+            "bad": { "type": "stdio" }
+        });
+        let count = merge_editor_servers(&mut config, map.as_object().unwrap(), "cursor");
+        assert_eq!(count, 1);
+
+        let again = merge_editor_servers(&mut config, map.as_object().unwrap(), "cursor");
+        assert_eq!(again, 0);
+
+        let servers = config.mcp.servers.as_ref().unwrap();
+        let server = servers.get("ok").expect("imported");
+        assert!(server.apps.is_empty());
+        assert_eq!(server.tags, vec!["cursor".to_string()]);
+    }
+
+    #[test]
+    fn render_export_per_target() {
+        let servers = vec![
+            (
+                "local".to_string(),
+                json!({ "type": "stdio", "command": "npx", "args": ["pkg"] }),
+            ),
+            (
+                "remote".to_string(),
+                json!({ "type": "http", "url": "https://mcp.example.com" }),
+            ),
+        ];
+
+        let (root, skipped) = render_export(
+            McpExportTarget::Vscode,
+            &servers,
+            &[],
+            Some(json!({ "inputs": [] })),
+        )
+        .expect("render vscode");
+        assert!(skipped.is_empty());
+        assert_eq!(root["inputs"], json!([]));
+        assert_eq!(root["servers"]["remote"]["type"], "http");
+
+        let (root, _) =
+            render_export(McpExportTarget::Cursor, &servers, &[], None).expect("render cursor");
+        assert!(root["mcpServers"]["local"].get("type").is_none());
+
+        let (root, skipped) = render_export(McpExportTarget::ClaudeDesktop, &servers, &[], None)
+            .expect("render claude desktop");
+        assert_eq!(skipped, vec!["remote".to_string()]);
+        assert!(root["mcpServers"].get("remote").is_none());
+    }
+
+    #[test]
+    fn export_keeps_foreign_servers_and_removes_stale_owned_ones() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("mcp.json");
+        fs::write(
+            &path,
+            r#"{
+  // 用户自己的服务器
+  "mcpServers": {
+    "mine": { "command": "my-tool" },
+    "stale": { "command": "old" }
+  },
+}"#,
+        )
+        .unwrap();
+
+        let servers = vec![("fetch".to_string(), json!({ "command": "uvx" }))];
+        let result = export_to_path(
+            McpExportTarget::Cursor,
+            &path,
+            &servers,
+            &["stale".to_string()],
+            true,
+        )
+        .expect("export");
+        assert!(result.backup.is_some());
+
+        let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            written["mcpServers"]["mine"],
+            json!({ "command": "my-tool" })
+        );
+        assert_eq!(written["mcpServers"]["fetch"], json!({ "command": "uvx" }));
+        assert!(written["mcpServers"].get("stale").is_none());
+    }
+}
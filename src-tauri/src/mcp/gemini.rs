@@ -80,7 +80,9 @@ pub fn import_from_gemini(config: &mut MultiAppConfig) -> Result<usize, AppError
                         gemini: true,
                         grok: false,
                         qwen: false,
+                        claude_desktop: false,
                     },
+                    app_overrides: Default::default(),
                     description: None,
                     homepage: None,
                     docs: None,
@@ -115,6 +117,19 @@ pub fn sync_single_server_to_gemini(
     crate::gemini_mcp::set_mcp_servers_map(&current)
 }
 
+/// 批量同步多个 MCP 服务器到 Gemini live 配置：一次读取、应用全部变更后只写回一次
+pub fn sync_servers_to_gemini(
+    upserts: &HashMap<String, Value>,
+    removals: &[String],
+) -> Result<(), AppError> {
+    let mut current = crate::gemini_mcp::read_mcp_servers_map()?;
+    for id in removals {
+        current.remove(id);
+    }
+    current.extend(upserts.iter().map(|(id, spec)| (id.clone(), spec.clone())));
+    crate::gemini_mcp::set_mcp_servers_map(&current)
+}
+
 /// 从 Gemini live 配置中移除单个 MCP 服务器
 pub fn remove_server_from_gemini(id: &str) -> Result<(), AppError> {
     // 读取现有的 MCP 配置
@@ -55,7 +55,9 @@ pub fn import_from_grok(config: &mut MultiAppConfig) -> Result<usize, AppError>
                         gemini: false,
                         grok: true,
                         qwen: false,
+                        claude_desktop: false,
                     },
+                    app_overrides: Default::default(),
                     description: None,
                     homepage: None,
                     docs: None,
@@ -91,6 +93,19 @@ pub fn sync_single_server_to_grok(
     crate::grok_config::set_mcp_servers_map(&updated)
 }
 
+/// 批量同步多个 MCP 服务器到 Grok live 配置：一次读取、应用全部变更后只写回一次
+pub fn sync_servers_to_grok(
+    upserts: &HashMap<String, Value>,
+    removals: &[String],
+) -> Result<(), AppError> {
+    let mut current = crate::grok_config::read_mcp_servers_map()?;
+    for id in removals {
+        current.remove(id);
+    }
+    current.extend(upserts.iter().map(|(id, spec)| (id.clone(), spec.clone())));
+    crate::grok_config::set_mcp_servers_map(&current)
+}
+
 /// 从 Grok live 配置中移除单个 MCP 服务器
 pub fn remove_server_from_grok(id: &str) -> Result<(), AppError> {
     // 读取现有的 MCP 配置
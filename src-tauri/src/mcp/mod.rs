@@ -4,29 +4,63 @@
 //!
 //! ## 模块结构
 //!
+//! - `preflight` - stdio 命令启动前检查
 //! - `validation` - 服务器配置验证
 //! - `claude` - Claude MCP 同步和导入
+//! - `claude_desktop` - Claude Desktop MCP 同步和导入
 //! - `codex` - Codex MCP 同步和导入（含 TOML 转换）
 //! - `gemini` - Gemini MCP 同步和导入
+//! - `editors` - Cursor / VS Code / Windsurf 配置导入，及编辑器格式导出
+//! - `share` - 单个服务器的分享文件导入导出
+//! - `bridge` - 远程服务器的 stdio 桥接条目生成
+//! - `transport` - 各应用支持的传输类型
 
+mod bridge;
 mod claude;
+mod claude_desktop;
 mod codex;
+mod editors;
 mod gemini;
 mod grok;
+mod preflight;
+mod share;
+mod transport;
 mod validation;
 
 // 重新导出公共 API
+pub use bridge::{
+    apply_bridge, apply_claude_desktop_bridge, bridge_spec, is_bridgeable, McpBridgeConfig,
+    McpBridgeTool,
+};
 pub use claude::{
-    import_from_claude, remove_server_from_claude, sync_enabled_to_claude,
+    import_from_claude, remove_server_from_claude, sync_enabled_to_claude, sync_servers_to_claude,
     sync_single_server_to_claude,
 };
+pub use claude_desktop::{
+    import_from_claude_desktop, remove_server_from_claude_desktop,
+    sync_single_server_to_claude_desktop,
+};
 pub use codex::{
-    import_from_codex, remove_server_from_codex, sync_enabled_to_codex, sync_single_server_to_codex,
+    import_from_codex, read_live_server_ids as read_codex_live_server_ids,
+    remove_server_from_codex, sync_enabled_to_codex, sync_servers_to_codex,
+    sync_single_server_to_codex,
+};
+pub use editors::{
+    export_to_editor, import_from_cursor, import_from_vscode, import_from_windsurf,
+    McpExportResult, McpExportTarget,
 };
 pub use gemini::{
-    import_from_gemini, remove_server_from_gemini, sync_enabled_to_gemini,
+    import_from_gemini, remove_server_from_gemini, sync_enabled_to_gemini, sync_servers_to_gemini,
     sync_single_server_to_gemini,
 };
 pub use grok::{
-    import_from_grok, remove_server_from_grok, sync_enabled_to_grok, sync_single_server_to_grok,
+    import_from_grok, remove_server_from_grok, sync_enabled_to_grok, sync_servers_to_grok,
+    sync_single_server_to_grok,
+};
+pub(crate) use preflight::find_in_path;
+pub use preflight::{ensure_spec_runnable, preflight_command, CommandPreflight};
+pub use share::McpServerShare;
+pub use transport::{
+    ensure_transport_supported, supported_transports, transport_matrix, CLAUDE_DESKTOP_TRANSPORTS,
 };
+pub use validation::{validate_app_overrides, validate_spec_detailed, SpecValidationError};
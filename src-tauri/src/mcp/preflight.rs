@@ -0,0 +1,330 @@
+//! stdio MCP 服务器启动前检查
+//!
+//! 在 PATH 中解析命令（Windows 下按 PATHEXT 匹配 `npx.cmd` 等包装脚本），
+//! 检查文件是否存在且可执行，识别 npx/uvx 等启动器依赖的运行时（Node.js、uv、Python…）
+//! 是否已安装，并给出可操作的修复建议。
+
+use serde::Serialize;
+use serde_json::Value;
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+use crate::i18n::tr;
+
+/// 启动器命令与所需运行时（运行时名称、需存在的可执行文件）
+const RUNTIMES: &[(&[&str], &str, &[&str])] = &[
+    (
+        &["npx", "npm", "node", "pnpm", "pnpx", "yarn"],
+        "node",
+        &["node"],
+    ),
+    (&["uvx", "uv"], "uv", &["uv"]),
+    (
+        &["python", "python3", "pip", "pip3", "pipx"],
+        "python",
+        &["python3", "python"],
+    ),
+    (&["bunx", "bun"], "bun", &["bun"]),
+    (&["deno"], "deno", &["deno"]),
+    (&["docker"], "docker", &["docker"]),
+];
+
+/// 检查发现的问题
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightIssue {
+    /// 稳定标识：empty / notFound / notExecutable / runtimeMissing / windowsWrapper
+    pub code: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// 命令检查结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandPreflight {
+    pub command: String,
+    /// 解析到的可执行文件路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_path: Option<String>,
+    /// 命令所属运行时（node/uv/python/bun/deno/docker）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<String>,
+    /// 是否可以启动（仅有警告时仍为 true）
+    pub runnable: bool,
+    pub issues: Vec<PreflightIssue>,
+}
+
+impl CommandPreflight {
+    fn issue(&mut self, code: &str, message: String, hint: Option<String>) {
+        self.issues.push(PreflightIssue {
+            code: code.to_string(),
+            message,
+            hint,
+        });
+    }
+}
+
+/// 检查命令能否在当前环境中启动（不执行命令）
+pub fn preflight_command(command: &str) -> CommandPreflight {
+    let command = command.trim();
+    let mut result = CommandPreflight {
+        command: command.to_string(),
+        resolved_path: None,
+        runtime: None,
+        runnable: false,
+        issues: Vec::new(),
+    };
+    if command.is_empty() {
+        result.issue("empty", tr("mcp.preflight.empty", &[]), None);
+        return result;
+    }
+
+    let runtime = runtime_for(command);
+    result.runtime = runtime.map(|(name, _)| name.to_string());
+    let cmd_arg = || [("command", command.to_string())];
+
+    let resolved = if command.contains('/') || command.contains('\\') {
+        let path = PathBuf::from(command);
+        path.is_file().then_some(path)
+    } else {
+        find_in_path(command)
+    };
+
+    match resolved {
+        Some(path) => {
+            result.resolved_path = Some(path.display().to_string());
+            if is_executable(&path) {
+                result.runnable = true;
+            } else {
+                result.issue(
+                    "notExecutable",
+                    tr("mcp.preflight.not_executable", &cmd_arg()),
+                    Some(tr("mcp.preflight.hint.chmod", &cmd_arg())),
+                );
+            }
+
+            // Windows 下 npx 等为 .cmd 脚本，部分客户端需通过 `cmd /c` 启动
+            if cfg!(windows) && is_batch_script(&path) {
+                result.issue(
+                    "windowsWrapper",
+                    tr("mcp.preflight.windows_wrapper", &cmd_arg()),
+                    Some(tr("mcp.preflight.hint.cmd_wrapper", &cmd_arg())),
+                );
+            }
+        }
+        None => {
+            let hint = runtime
+                .map(|(name, _)| tr(&format!("mcp.preflight.install.{name}"), &[]))
+                .unwrap_or_else(|| tr("mcp.preflight.hint.absolute_path", &cmd_arg()));
+            result.issue(
+                "notFound",
+                tr("mcp.preflight.not_found", &cmd_arg()),
+                Some(hint),
+            );
+        }
+    }
+
+    // 启动器存在但运行时缺失（如只有 npx 包装脚本而没有 node）
+    if let Some((name, binaries)) = runtime {
+        if result.runnable && !binaries.iter().any(|bin| find_in_path(bin).is_some()) {
+            result.runnable = false;
+            result.issue(
+                "runtimeMissing",
+                tr(
+                    "mcp.preflight.runtime_missing",
+                    &[
+                        ("command", command.to_string()),
+                        ("runtime", name.to_string()),
+                    ],
+                ),
+                Some(tr(&format!("mcp.preflight.install.{name}"), &[])),
+            );
+        }
+    }
+
+    result
+}
+
+/// 启用 stdio 服务器前的检查：命令无法启动时返回错误（http/sse 直接通过）
+pub fn ensure_spec_runnable(id: &str, spec: &Value) -> Result<(), AppError> {
+    let is_stdio = spec
+        .get("type")
+        .and_then(|t| t.as_str())
+        .map(|t| t == "stdio")
+        .unwrap_or(true);
+    if !is_stdio {
+        return Ok(());
+    }
+
+    let command = spec.get("command").and_then(|c| c.as_str()).unwrap_or("");
+    let result = preflight_command(command);
+    if result.runnable {
+        return Ok(());
+    }
+    let detail = result
+        .issues
+        .iter()
+        .map(|issue| match &issue.hint {
+            Some(hint) => format!("{} {hint}", issue.message),
+            None => issue.message.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    Err(AppError::localized_with(
+        "mcp.preflight.failed",
+        &[("id", id.to_string()), ("detail", detail)],
+    ))
+}
+
+fn runtime_for(command: &str) -> Option<(&'static str, &'static [&'static str])> {
+    let stem = Path::new(command)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(command)
+        .to_ascii_lowercase();
+    RUNTIMES
+        .iter()
+        .find(|(commands, _, _)| commands.contains(&stem.as_str()))
+        .map(|(_, name, binaries)| (*name, *binaries))
+}
+
+/// 在 PATH 及常见安装目录中查找命令（Windows 下依次尝试 PATHEXT 中的扩展名）
+pub(crate) fn find_in_path(command: &str) -> Option<PathBuf> {
+    let exts: Vec<String> = if cfg!(windows) {
+        env::var("PATHEXT")
+            .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into())
+            .split(';')
+            .map(|ext| ext.trim().to_string())
+            .filter(|ext| !ext.is_empty())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    search_dirs().into_iter().find_map(|dir| {
+        let candidate = dir.join(command);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        exts.iter()
+            .map(|ext| dir.join(format!("{command}{ext}")))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+/// 命令搜索目录
+///
+/// 从 Finder/桌面环境启动时 PATH 往往只有系统目录，CLI 客户端则从用户 shell 继承完整 PATH，
+/// 因此额外加入 Homebrew、nvm、Volta 等常见安装位置，避免误报找不到命令。
+fn search_dirs() -> Vec<PathBuf> {
+    let mut search: Vec<PathBuf> = env::var_os("PATH")
+        .map(|path| env::split_paths(&path).collect())
+        .unwrap_or_default();
+
+    let home = dirs::home_dir();
+    let mut extra: Vec<PathBuf> = Vec::new();
+    if cfg!(windows) {
+        if let Some(appdata) = env::var_os("APPDATA") {
+            extra.push(PathBuf::from(appdata).join("npm"));
+        }
+    } else {
+        extra.push(PathBuf::from("/opt/homebrew/bin"));
+        extra.push(PathBuf::from("/usr/local/bin"));
+    }
+    if let Some(home) = &home {
+        for sub in [
+            ".local/bin",
+            ".cargo/bin",
+            ".bun/bin",
+            ".deno/bin",
+            ".volta/bin",
+        ] {
+            extra.push(home.join(sub));
+        }
+        // nvm 安装的各版本 Node.js
+        if let Ok(entries) = std::fs::read_dir(home.join(".nvm/versions/node")) {
+            extra.extend(entries.flatten().map(|entry| entry.path().join("bin")));
+        }
+    }
+
+    for dir in extra {
+        if dir.is_dir() && !search.contains(&dir) {
+            search.push(dir);
+        }
+    }
+    search
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|meta| meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn is_batch_script(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("cmd") || ext.eq_ignore_ascii_case("bat"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn missing_commands_are_reported_and_launchers_map_to_runtimes() {
+        let result = preflight_command("npx-definitely-missing-cc-switch");
+        assert!(!result.runnable);
+        assert_eq!(result.issues[0].code, "notFound");
+
+        assert_eq!(
+            runtime_for("npx").map(|(name, _)| name),
+            Some("node"),
+            "npx should map to the node runtime"
+        );
+        assert_eq!(
+            runtime_for("/usr/local/bin/uvx").map(|(n, _)| n),
+            Some("uv")
+        );
+        assert!(runtime_for("my-server").is_none());
+        assert_eq!(preflight_command("  ").issues[0].code, "empty");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn checks_existence_and_executability_of_explicit_paths() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = dir.path().join("server.sh");
+        std::fs::write(&script, "#!/bin/sh\n").expect("write script");
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o644)).expect("chmod");
+
+        let result = preflight_command(script.to_str().unwrap());
+        assert!(!result.runnable);
+        assert_eq!(result.issues[0].code, "notExecutable");
+
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+        assert!(preflight_command(script.to_str().unwrap()).runnable);
+
+        // http/sse 服务器不做命令检查
+        assert!(
+            ensure_spec_runnable("remote", &json!({ "type": "http", "url": "https://x" })).is_ok()
+        );
+        assert!(
+            ensure_spec_runnable("broken", &json!({ "command": "/nonexistent/server" })).is_err()
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! 单个 MCP 服务器的分享文件
+//!
+//! 导出为独立的 JSON 文档，可通过文件或聊天分享，作为深链接之外的分享方式。
+//! 文档只包含服务器定义与元数据，不含本机的应用启用状态；默认清空 env、headers
+//! 中的密钥，导入时据 `redacted` 提示补全。
+//!
+//! ```json
+//! { "format": "cc-switch-mcp-server", "version": 1, "id": "fetch", "server": { ... } }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::app_config::{McpAppOverrides, McpApps, McpServer};
+use crate::database::redact_value;
+use crate::error::AppError;
+
+use super::validation::validate_server_spec;
+
+/// 文档格式标识
+const SHARE_FORMAT: &str = "cc-switch-mcp-server";
+
+/// 文档格式版本
+const SHARE_FORMAT_VERSION: u32 = 1;
+
+/// MCP 服务器分享文档
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpServerShare {
+    pub format: String,
+    pub version: u32,
+    /// 导出时的应用版本
+    #[serde(default)]
+    pub app_version: String,
+    /// 密钥是否已清空
+    #[serde(default)]
+    pub redacted: bool,
+    pub id: String,
+    pub name: String,
+    pub server: Value,
+    #[serde(default, skip_serializing_if = "McpAppOverrides::is_empty")]
+    pub app_overrides: McpAppOverrides,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl McpServerShare {
+    /// 由服务器生成分享文档；`include_secrets` 为 false 时清空密钥字段
+    pub fn from_server(server: &McpServer, include_secrets: bool) -> Self {
+        let mut spec = server.server.clone();
+        let mut app_overrides = server.app_overrides.clone();
+        let mut redacted = false;
+        if !include_secrets {
+            redacted |= redact_value(&mut spec);
+            for value in app_overrides.values_mut() {
+                redacted |= redact_value(value);
+            }
+        }
+        Self {
+            format: SHARE_FORMAT.to_string(),
+            version: SHARE_FORMAT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            redacted,
+            id: server.id.clone(),
+            name: server.name.clone(),
+            server: spec,
+            app_overrides,
+            description: server.description.clone(),
+            homepage: server.homepage.clone(),
+            docs: server.docs.clone(),
+            tags: server.tags.clone(),
+        }
+    }
+
+    /// 渲染为 JSON 文本（对象键按名称排序，相同服务器总是得到相同输出）
+    pub fn to_json(&self) -> Result<String, AppError> {
+        let value =
+            serde_json::to_value(self).map_err(|e| AppError::JsonSerialize { source: e })?;
+        let mut text = serde_json::to_string_pretty(&value)
+            .map_err(|e| AppError::JsonSerialize { source: e })?;
+        text.push('\n');
+        Ok(text)
+    }
+
+    /// 读取并校验分享文件
+    pub fn read_file(path: &Path) -> Result<Self, AppError> {
+        let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+        let share: Self = serde_json::from_str(&content).map_err(|e| AppError::json(path, e))?;
+        if share.format != SHARE_FORMAT {
+            return Err(AppError::localized_with(
+                "mcp.share.invalid_format",
+                &[("path", path.display().to_string())],
+            ));
+        }
+        if share.version > SHARE_FORMAT_VERSION {
+            return Err(AppError::localized_with(
+                "mcp.share.unsupported_version",
+                &[("version", share.version.to_string())],
+            ));
+        }
+        if share.id.trim().is_empty() {
+            return Err(AppError::InvalidInput("MCP 服务器 ID 不能为空".to_string()));
+        }
+        validate_server_spec(&share.server)?;
+        Ok(share)
+    }
+
+    /// 转为待保存的服务器（不启用任何应用，由用户自行勾选）
+    pub fn into_server(self) -> McpServer {
+        McpServer {
+            name: if self.name.trim().is_empty() {
+                self.id.clone()
+            } else {
+                self.name
+            },
+            id: self.id,
+            server: self.server,
+            apps: McpApps::default(),
+            app_overrides: self.app_overrides,
+            description: self.description,
+            homepage: self.homepage,
+            docs: self.docs,
+            tags: self.tags,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn share_round_trip_redacts_secrets_by_default() {
+        let server = McpServer {
+            id: "fetch".to_string(),
+            name: "Fetch".to_string(),
+            server: json!({
+                "type": "stdio",
+                "command": "uvx",
+                "args": ["mcp-server-fetch"],
+                "env": { "FETCH_API_KEY": "sk-secret-value" }
+            }),
+            apps: McpApps {
+                claude: true,
+                ..Default::default()
+            },
+            app_overrides: McpAppOverrides::default(),
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: vec!["web".to_string()],
+        };
+
+        let share = McpServerShare::from_server(&server, false);
+        assert!(share.redacted);
+        let text = share.to_json().expect("render");
+        assert!(!text.contains("sk-secret-value"));
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("fetch.mcp.json");
+        fs::write(&path, &text).unwrap();
+        let imported = McpServerShare::read_file(&path)
+            .expect("read")
+            .into_server();
+        assert_eq!(imported.id, "fetch");
+        assert_eq!(imported.tags, vec!["web".to_string()]);
+        assert!(imported.apps.is_empty());
+
+        let full = McpServerShare::from_server(&server, true);
+        assert!(!full.redacted);
+        assert!(full.to_json().unwrap().contains("sk-secret-value"));
+    }
+}
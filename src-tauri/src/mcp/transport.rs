@@ -0,0 +1,118 @@
+//! 各应用支持的 MCP 传输类型
+//!
+//! 启用服务器前按此校验，避免写入 CLI 无法加载的配置（如 Codex 不接受 sse）；
+//! 不支持时返回结构化错误，并给出可用的 stdio 桥接方式。
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+
+use super::bridge::{is_bridgeable, McpBridgeTool};
+
+/// Claude Desktop 的配置文件仅支持本地 stdio 服务器
+pub const CLAUDE_DESKTOP_TRANSPORTS: &[&str] = &["stdio"];
+
+/// 应用支持的传输类型
+pub fn supported_transports(app: &AppType) -> &'static [&'static str] {
+    match app {
+        AppType::Codex => &["stdio", "http"],
+        AppType::Claude | AppType::Gemini | AppType::Grok | AppType::Qwen => {
+            &["stdio", "http", "sse"]
+        }
+    }
+}
+
+/// 全部应用的传输支持矩阵（键为应用名，含 `claudeDesktop`）
+pub fn transport_matrix() -> BTreeMap<String, Vec<String>> {
+    let to_vec = |transports: &[&str]| -> Vec<String> {
+        transports.iter().map(|t| t.to_string()).collect()
+    };
+    let mut matrix: BTreeMap<String, Vec<String>> = AppType::all()
+        .iter()
+        .map(|app| (app.as_str().to_string(), to_vec(supported_transports(app))))
+        .collect();
+    matrix.insert(
+        "claudeDesktop".to_string(),
+        to_vec(CLAUDE_DESKTOP_TRANSPORTS),
+    );
+    matrix
+}
+
+/// 服务器定义的传输类型（缺省为 stdio）
+pub fn transport_of(spec: &Value) -> &str {
+    spec.get("type").and_then(Value::as_str).unwrap_or("stdio")
+}
+
+/// 应用能否直接使用该服务器定义
+pub fn supports_transport(app: &AppType, spec: &Value) -> bool {
+    supported_transports(app).contains(&transport_of(spec))
+}
+
+/// 校验传输类型，不支持时返回 `mcp.transport.unsupported`
+///
+/// 错误的 details 含 `id`、`app`、`transport`，以及逗号分隔的建议桥接工具 `bridges`。
+pub fn ensure_transport_supported(
+    server_id: &str,
+    app: &str,
+    supported: &[&str],
+    spec: &Value,
+) -> Result<(), AppError> {
+    let transport = transport_of(spec);
+    if supported.contains(&transport) {
+        return Ok(());
+    }
+    let bridges = if is_bridgeable(spec) {
+        [McpBridgeTool::McpRemote, McpBridgeTool::Supergateway]
+            .iter()
+            .filter_map(|tool| serde_json::to_value(tool).ok())
+            .filter_map(|tool| tool.as_str().map(str::to_string))
+            .collect::<Vec<_>>()
+            .join(", ")
+    } else {
+        String::new()
+    };
+    Err(AppError::localized_with(
+        "mcp.transport.unsupported",
+        &[
+            ("id", server_id.to_string()),
+            ("app", app.to_string()),
+            ("transport", transport.to_string()),
+            ("bridges", bridges),
+        ],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn codex_rejects_sse_with_bridge_suggestions() {
+        let sse = json!({ "type": "sse", "url": "https://mcp.example.com/sse" });
+        assert!(supports_transport(&AppType::Claude, &sse));
+        assert!(!supports_transport(&AppType::Codex, &sse));
+        assert!(supports_transport(
+            &AppType::Codex,
+            &json!({ "command": "uvx" })
+        ));
+
+        let err = ensure_transport_supported(
+            "remote",
+            AppType::Codex.as_str(),
+            supported_transports(&AppType::Codex),
+            &sse,
+        )
+        .unwrap_err();
+        let payload = err.to_payload();
+        assert_eq!(payload.code, "mcp.transport.unsupported");
+        let details = payload.details.expect("details");
+        assert_eq!(details["transport"], "sse");
+        assert_eq!(details["bridges"], "mcpRemote, supergateway");
+
+        assert_eq!(transport_matrix()["claudeDesktop"], vec!["stdio"]);
+    }
+}
@@ -1,51 +1,121 @@
 //! MCP 服务器配置验证模块
 
+use jsonschema::Validator;
+use once_cell::sync::Lazy;
+use serde::Serialize;
 use serde_json::Value;
 
+use crate::app_config::{McpServer, McpTarget};
 use crate::error::AppError;
 
-/// 基础校验：允许 stdio/http/sse；或省略 type（视为 stdio）。对应必填字段存在
-pub fn validate_server_spec(spec: &Value) -> Result<(), AppError> {
-    if !spec.is_object() {
-        return Err(AppError::McpValidation(
-            "MCP 服务器连接定义必须为 JSON 对象".into(),
-        ));
+/// 单条校验错误
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecValidationError {
+    /// 出错位置（JSON Pointer，如 `/env/API_KEY`；根对象为空串）
+    pub path: String,
+    pub message: String,
+}
+
+impl SpecValidationError {
+    fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
     }
-    let t_opt = spec.get("type").and_then(|x| x.as_str());
-    // 支持三种：stdio/http/sse；若缺省 type 则按 stdio 处理（与社区常见 .mcp.json 一致）
-    let is_stdio = t_opt.map(|t| t == "stdio").unwrap_or(true);
-    let is_http = t_opt.map(|t| t == "http").unwrap_or(false);
-    let is_sse = t_opt.map(|t| t == "sse").unwrap_or(false);
+}
 
-    if !(is_stdio || is_http || is_sse) {
-        return Err(AppError::McpValidation(
-            "MCP 服务器 type 必须是 'stdio'、'http' 或 'sse'（或省略表示 stdio）".into(),
-        ));
+static STDIO_SCHEMA: Lazy<Validator> =
+    Lazy::new(|| compile_schema(include_str!("schemas/stdio.schema.json")));
+static HTTP_SCHEMA: Lazy<Validator> =
+    Lazy::new(|| compile_schema(include_str!("schemas/http.schema.json")));
+static SSE_SCHEMA: Lazy<Validator> =
+    Lazy::new(|| compile_schema(include_str!("schemas/sse.schema.json")));
+
+fn compile_schema(source: &str) -> Validator {
+    let schema: Value = serde_json::from_str(source).expect("embedded MCP schema is valid JSON");
+    jsonschema::validator_for(&schema).expect("embedded MCP schema compiles")
+}
+
+/// 按内置 JSON Schema 校验连接定义，返回全部错误（为空表示通过）
+///
+/// 按 `type` 选择 stdio/http/sse 对应的 Schema，省略 `type` 视为 stdio。
+pub fn validate_spec_detailed(spec: &Value) -> Vec<SpecValidationError> {
+    if !spec.is_object() {
+        return vec![SpecValidationError::new(
+            "",
+            "MCP 服务器连接定义必须为 JSON 对象",
+        )];
     }
 
-    if is_stdio {
-        let cmd = spec.get("command").and_then(|x| x.as_str()).unwrap_or("");
-        if cmd.trim().is_empty() {
-            return Err(AppError::McpValidation(
-                "stdio 类型的 MCP 服务器缺少 command 字段".into(),
-            ));
+    let validator = match spec.get("type") {
+        None => &*STDIO_SCHEMA,
+        Some(Value::String(t)) if t == "stdio" => &*STDIO_SCHEMA,
+        Some(Value::String(t)) if t == "http" => &*HTTP_SCHEMA,
+        Some(Value::String(t)) if t == "sse" => &*SSE_SCHEMA,
+        Some(_) => {
+            return vec![SpecValidationError::new(
+                "/type",
+                "MCP 服务器 type 必须是 'stdio'、'http' 或 'sse'（或省略表示 stdio）",
+            )]
         }
+    };
+
+    validator
+        .iter_errors(spec)
+        .map(|e| SpecValidationError::new(e.instance_path.to_string(), e.to_string()))
+        .collect()
+}
+
+/// 校验连接定义：允许 stdio/http/sse，或省略 type（视为 stdio）；失败时汇总全部错误
+pub fn validate_server_spec(spec: &Value) -> Result<(), AppError> {
+    let errors = validate_spec_detailed(spec);
+    if errors.is_empty() {
+        return Ok(());
     }
-    if is_http {
-        let url = spec.get("url").and_then(|x| x.as_str()).unwrap_or("");
-        if url.trim().is_empty() {
-            return Err(AppError::McpValidation(
-                "http 类型的 MCP 服务器缺少 url 字段".into(),
-            ));
-        }
+    let summary = errors
+        .iter()
+        .map(|e| {
+            if e.path.is_empty() {
+                e.message.clone()
+            } else {
+                format!("{}: {}", e.path, e.message)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    Err(AppError::McpValidation(summary))
+}
+
+/// 校验分应用覆盖配置：覆盖值必须为对象，且合并后的定义对每个启用的目标都有效
+pub fn validate_app_overrides(server: &McpServer) -> Result<(), AppError> {
+    let mut targets: Vec<McpTarget> = server
+        .apps
+        .enabled_apps()
+        .into_iter()
+        .map(McpTarget::App)
+        .collect();
+    if server.apps.claude_desktop {
+        targets.push(McpTarget::ClaudeDesktop);
     }
-    if is_sse {
-        let url = spec.get("url").and_then(|x| x.as_str()).unwrap_or("");
-        if url.trim().is_empty() {
-            return Err(AppError::McpValidation(
-                "sse 类型的 MCP 服务器缺少 url 字段".into(),
-            ));
+    for target in targets {
+        if let Some(overrides) = server.app_overrides.get(target.clone()) {
+            if !overrides.is_object() {
+                return Err(AppError::McpValidation(format!(
+                    "MCP 服务器 '{}' 的 {} 覆盖配置必须为 JSON 对象",
+                    server.id,
+                    target.as_str()
+                )));
+            }
         }
+        validate_server_spec(&server.spec_for(target.clone())).map_err(|e| {
+            AppError::McpValidation(format!(
+                "MCP 服务器 '{}' 在 {} 下的合并配置无效: {e}",
+                server.id,
+                target.as_str()
+            ))
+        })?;
     }
     Ok(())
 }
@@ -67,3 +137,43 @@ pub fn extract_server_spec(entry: &Value) -> Result<Value, AppError> {
 
     Ok(server.clone())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn detailed_validation_reports_each_invalid_path() {
+        let errors = validate_spec_detailed(&json!({
+            "command": "npx",
+            "args": ["-y", 1],
+            "env": { "API_KEY": 42 }
+        }));
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert!(paths.contains(&"/args/1"), "{errors:?}");
+        assert!(paths.contains(&"/env/API_KEY"), "{errors:?}");
+
+        let errors = validate_spec_detailed(&json!({ "type": "sse" }));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("url"));
+
+        assert_eq!(
+            validate_spec_detailed(&json!({ "type": "ws", "url": "wss://x" }))[0].path,
+            "/type"
+        );
+    }
+
+    #[test]
+    fn valid_specs_pass_and_keep_extra_fields() {
+        for spec in [
+            json!({ "command": "uvx", "args": ["mcp-server-git"], "startup_timeout_sec": 20 }),
+            json!({ "type": "http", "url": "https://mcp.example.com/mcp", "headers": { "Authorization": "Bearer x" } }),
+            json!({ "type": "sse", "url": "${MCP_URL}/sse" }),
+        ] {
+            assert!(validate_server_spec(&spec).is_ok(), "{spec}");
+        }
+        assert!(validate_server_spec(&json!({ "type": "stdio", "command": "  " })).is_err());
+        assert!(validate_server_spec(&json!({ "type": "http", "url": "ftp://x" })).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+//! 系统通知
+//!
+//! 封装 tauri-plugin-notification，按设置中的分类开关发送原生通知，
+//! 用于托盘切换、后台同步等用户看不到界面反馈的场景。
+//! 标题与正文取自语言包 `notification.<分类>.title` / `notification.<分类>.body`。
+
+use std::sync::OnceLock;
+
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::settings::NotificationSettings;
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// 通知分类（对应设置中的开关）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationCategory {
+    SwitchSuccess,
+    SwitchFailure,
+    McpSyncError,
+    UsageAlert,
+    UpdateAvailable,
+    CredentialCopied,
+}
+
+impl NotificationCategory {
+    fn key(self) -> &'static str {
+        match self {
+            Self::SwitchSuccess => "switch_success",
+            Self::SwitchFailure => "switch_failure",
+            Self::McpSyncError => "mcp_sync_error",
+            Self::UsageAlert => "usage_alert",
+            Self::UpdateAvailable => "update_available",
+            Self::CredentialCopied => "credential_copied",
+        }
+    }
+
+    fn is_enabled(self, settings: &NotificationSettings) -> bool {
+        match self {
+            Self::SwitchSuccess => settings.switch_success,
+            Self::SwitchFailure => settings.switch_failure,
+            Self::McpSyncError => settings.mcp_sync_error,
+            Self::UsageAlert => settings.usage_alert,
+            Self::UpdateAvailable => settings.update_available,
+            Self::CredentialCopied => settings.credential_copied,
+        }
+    }
+}
+
+/// 启动时保存 AppHandle，之后可在任意线程发送通知
+pub fn init(app: &AppHandle) {
+    let _ = APP_HANDLE.set(app.clone());
+}
+
+/// 发送通知；分类被关闭或尚未初始化时忽略
+pub fn notify(category: NotificationCategory, args: &[(&str, String)]) {
+    if !category.is_enabled(&crate::settings::get_settings().notifications) {
+        return;
+    }
+    let Some(app) = APP_HANDLE.get() else {
+        log::debug!("通知尚未初始化，跳过: {category:?}");
+        return;
+    };
+
+    let key = category.key();
+    let title = crate::i18n::tr(&format!("notification.{key}.title"), &[]);
+    let body = crate::i18n::tr(&format!("notification.{key}.body"), args);
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        log::warn!("发送系统通知失败: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_category_has_translations() {
+        for category in [
+            NotificationCategory::SwitchSuccess,
+            NotificationCategory::SwitchFailure,
+            NotificationCategory::McpSyncError,
+            NotificationCategory::UsageAlert,
+            NotificationCategory::UpdateAvailable,
+            NotificationCategory::CredentialCopied,
+        ] {
+            for part in ["title", "body"] {
+                let key = format!("notification.{}.{part}", category.key());
+                assert_ne!(crate::i18n::tr(&key, &[]), key);
+            }
+        }
+    }
+
+    #[test]
+    fn switch_success_is_off_by_default() {
+        let settings = NotificationSettings::default();
+        assert!(!NotificationCategory::SwitchSuccess.is_enabled(&settings));
+        assert!(NotificationCategory::SwitchFailure.is_enabled(&settings));
+    }
+}
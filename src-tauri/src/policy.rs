@@ -0,0 +1,202 @@
+//! 机器级策略（MDM）
+//!
+//! 管理员可通过策略文件（Windows 为 HKLM 注册表）强制部分设置，用户无法在界面中修改：
+//! - Linux: `/etc/cc-switch/policy.json`
+//! - macOS: `/Library/Application Support/cc-switch/policy.json`
+//! - Windows: `HKEY_LOCAL_MACHINE\SOFTWARE\Policies\cc-switch`
+//!
+//! 策略在首次访问时加载一次，应用于内存中的设置与保存到磁盘的设置。
+
+use serde::{Deserialize, Serialize};
+#[cfg(not(target_os = "windows"))]
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::settings::{AppSettings, UpdateChannel};
+
+#[cfg(target_os = "windows")]
+use winreg::enums::*;
+#[cfg(target_os = "windows")]
+use winreg::RegKey;
+
+/// Windows 策略注册表路径
+#[cfg(target_os = "windows")]
+const POLICY_REGISTRY_KEY: &str = "SOFTWARE\\Policies\\cc-switch";
+
+/// 机器级策略
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Policy {
+    /// 禁止通过深链接导入携带 API 密钥的供应商
+    pub disable_deeplink_api_keys: bool,
+    /// 锁定各应用配置目录覆盖（强制使用默认目录）
+    pub lock_config_dirs: bool,
+    /// 固定更新通道
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_channel: Option<UpdateChannel>,
+    /// 固定是否后台检查更新
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_check_updates: Option<bool>,
+    /// 固定团队模板清单地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provision_url: Option<String>,
+}
+
+/// 已加载的策略及其来源
+#[derive(Debug, Clone, Default)]
+struct LoadedPolicy {
+    policy: Policy,
+    source: Option<String>,
+}
+
+static POLICY: OnceLock<LoadedPolicy> = OnceLock::new();
+
+fn loaded() -> &'static LoadedPolicy {
+    POLICY.get_or_init(|| {
+        let loaded = load();
+        if let Some(source) = &loaded.source {
+            log::info!("已加载机器策略: {source} ({:?})", loaded.policy);
+        }
+        loaded
+    })
+}
+
+/// 当前生效的策略（未配置时为默认值，即不锁定任何设置）
+pub fn current() -> &'static Policy {
+    &loaded().policy
+}
+
+/// 策略来源（文件路径或注册表路径），未配置策略时为 None
+pub fn source() -> Option<&'static str> {
+    loaded().source.as_deref()
+}
+
+impl Policy {
+    /// 被策略锁定的设置字段（与前端 Settings 字段名一致）
+    pub fn locked_fields(&self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if self.lock_config_dirs {
+            fields.extend([
+                "claudeConfigDir",
+                "codexConfigDir",
+                "geminiConfigDir",
+                "qwenConfigDir",
+                "grokConfigDir",
+            ]);
+        }
+        if self.update_channel.is_some() {
+            fields.push("updateChannel");
+        }
+        if self.auto_check_updates.is_some() {
+            fields.push("autoCheckUpdates");
+        }
+        if self.provision_url.is_some() {
+            fields.push("provisionUrl");
+        }
+        fields
+    }
+
+    /// 将策略强制应用到设置上
+    pub fn apply(&self, settings: &mut AppSettings) {
+        if self.lock_config_dirs {
+            settings.claude_config_dir = None;
+            settings.codex_config_dir = None;
+            settings.gemini_config_dir = None;
+            settings.qwen_config_dir = None;
+            settings.grok_config_dir = None;
+        }
+        if let Some(channel) = self.update_channel {
+            settings.update_channel = channel;
+        }
+        if let Some(enabled) = self.auto_check_updates {
+            settings.auto_check_updates = enabled;
+        }
+        if let Some(url) = &self.provision_url {
+            settings.provision_url = Some(url.clone());
+        }
+    }
+}
+
+/// 策略文件路径（非 Windows 平台）
+#[cfg(not(target_os = "windows"))]
+fn policy_file_path() -> PathBuf {
+    if cfg!(target_os = "macos") {
+        PathBuf::from("/Library/Application Support/cc-switch/policy.json")
+    } else {
+        PathBuf::from("/etc/cc-switch/policy.json")
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn load() -> LoadedPolicy {
+    let path = policy_file_path();
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return LoadedPolicy::default();
+    };
+    match serde_json::from_str::<Policy>(&content) {
+        Ok(policy) => LoadedPolicy {
+            policy,
+            source: Some(path.display().to_string()),
+        },
+        Err(e) => {
+            log::warn!("解析策略文件失败，已忽略: {} ({e})", path.display());
+            LoadedPolicy::default()
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn load() -> LoadedPolicy {
+    let Ok(key) = RegKey::predef(HKEY_LOCAL_MACHINE).open_subkey(POLICY_REGISTRY_KEY) else {
+        return LoadedPolicy::default();
+    };
+    let flag = |name: &str| key.get_value::<u32, _>(name).ok().map(|v| v != 0);
+    let text = |name: &str| {
+        key.get_value::<String, _>(name)
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+
+    let policy = Policy {
+        disable_deeplink_api_keys: flag("DisableDeeplinkApiKeys").unwrap_or(false),
+        lock_config_dirs: flag("LockConfigDirs").unwrap_or(false),
+        update_channel: text("UpdateChannel").and_then(|v| match v.to_lowercase().as_str() {
+            "stable" => Some(UpdateChannel::Stable),
+            "beta" => Some(UpdateChannel::Beta),
+            _ => None,
+        }),
+        auto_check_updates: flag("AutoCheckUpdates"),
+        provision_url: text("ProvisionUrl"),
+    };
+    LoadedPolicy {
+        policy,
+        source: Some(format!("HKEY_LOCAL_MACHINE\\{POLICY_REGISTRY_KEY}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_forces_locked_settings() {
+        let policy: Policy = serde_json::from_str(
+            r#"{ "lockConfigDirs": true, "updateChannel": "stable", "autoCheckUpdates": false }"#,
+        )
+        .expect("parse policy");
+
+        let mut settings = AppSettings {
+            claude_config_dir: Some("/tmp/claude".into()),
+            update_channel: UpdateChannel::Beta,
+            ..AppSettings::default()
+        };
+        policy.apply(&mut settings);
+
+        assert!(settings.claude_config_dir.is_none());
+        assert_eq!(settings.update_channel, UpdateChannel::Stable);
+        assert!(!settings.auto_check_updates);
+        assert!(policy.locked_fields().contains(&"updateChannel"));
+        assert!(!policy.locked_fields().contains(&"provisionUrl"));
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::app_config::AppType;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Prompt {
     pub id: String,
@@ -9,8 +11,119 @@ pub struct Prompt {
     pub description: Option<String>,
     #[serde(default)]
     pub enabled: bool,
+    /// 标签（来自 frontmatter 的 `tags`）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 适用的应用（来自 frontmatter 的 `apps`），仅作元数据保存
+    #[serde(rename = "targetApps", default, skip_serializing_if = "Vec::is_empty")]
+    pub target_apps: Vec<String>,
+    /// 除所属应用外，启用时同时写入的应用（如 Claude 提示词同时写入 AGENTS.md、GEMINI.md）
+    #[serde(default, skip_serializing_if = "PromptApps::is_empty")]
+    pub apps: PromptApps,
     #[serde(rename = "createdAt", skip_serializing_if = "Option::is_none")]
     pub created_at: Option<i64>,
     #[serde(rename = "updatedAt", skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<i64>,
 }
+
+/// 提示词的同步目标应用
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptApps {
+    #[serde(default)]
+    pub claude: bool,
+    #[serde(default)]
+    pub codex: bool,
+    #[serde(default)]
+    pub gemini: bool,
+    #[serde(default)]
+    pub grok: bool,
+    #[serde(default)]
+    pub qwen: bool,
+}
+
+impl PromptApps {
+    pub fn is_enabled_for(&self, app: &AppType) -> bool {
+        match app {
+            AppType::Claude => self.claude,
+            AppType::Codex => self.codex,
+            AppType::Gemini => self.gemini,
+            AppType::Grok => self.grok,
+            AppType::Qwen => self.qwen,
+        }
+    }
+
+    pub fn set_enabled_for(&mut self, app: &AppType, enabled: bool) {
+        match app {
+            AppType::Claude => self.claude = enabled,
+            AppType::Codex => self.codex = enabled,
+            AppType::Gemini => self.gemini = enabled,
+            AppType::Grok => self.grok = enabled,
+            AppType::Qwen => self.qwen = enabled,
+        }
+    }
+
+    /// 除 `owner` 外的同步目标
+    pub fn targets_except(&self, owner: &AppType) -> Vec<AppType> {
+        AppType::all()
+            .into_iter()
+            .filter(|app| app != owner && self.is_enabled_for(app))
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.claude && !self.codex && !self.gemini && !self.grok && !self.qwen
+    }
+}
+
+/// 提示词组的写入方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptSetMode {
+    /// 按顺序拼接全部成员
+    #[default]
+    Concat,
+    /// 仅写入选中的成员（未选中时为第一个）
+    Select,
+}
+
+impl PromptSetMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PromptSetMode::Concat => "concat",
+            PromptSetMode::Select => "select",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "select" => PromptSetMode::Select,
+            _ => PromptSetMode::Concat,
+        }
+    }
+}
+
+/// 提示词组：一组按顺序排列的提示词，启用时组合写入 live 提示词文件
+///
+/// 典型用法是基础系统提示词 + 按任务追加的补充说明。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptSet {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub mode: PromptSetMode,
+    /// 成员提示词 ID（按写入顺序）
+    #[serde(default)]
+    pub prompt_ids: Vec<String>,
+    /// `select` 模式下选中的成员
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected_prompt_id: Option<String>,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+}
@@ -41,10 +41,9 @@ fn get_base_dir_with_fallback(
         .map(|p| p.to_path_buf())
         .or_else(|| crate::test_utils::home_dir().map(|h| h.join(fallback_dir)))
         .ok_or_else(|| {
-            AppError::localized(
+            AppError::localized_with(
                 "home_dir_not_found",
-                format!("无法确定 {fallback_dir} 配置目录：用户主目录不存在"),
-                format!("Cannot determine {fallback_dir} config directory: user home not found"),
+                &[("fallback_dir", fallback_dir.to_string())],
             )
         })
 }
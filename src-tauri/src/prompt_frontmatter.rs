@@ -0,0 +1,124 @@
+//! 提示词 Markdown frontmatter 解析与生成
+//!
+//! 支持形如 awesome-claude-prompts 等仓库中常见的 YAML frontmatter：
+//!
+//! ```markdown
+//! ---
+//! title: Code Reviewer
+//! description: Strict review checklist
+//! tags: [review, rust]
+//! apps: [claude, codex]
+//! ---
+//! 正文……
+//! ```
+//!
+//! 导入时解析为提示词的结构化字段（正文不含 frontmatter），导出时重新生成。
+
+use serde::{Deserialize, Serialize};
+
+use crate::prompt::Prompt;
+
+/// frontmatter 中识别的字段，其他字段忽略
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PromptFrontmatter {
+    #[serde(default, alias = "name", skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(
+        default,
+        alias = "targets",
+        alias = "target_apps",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub apps: Vec<String>,
+}
+
+/// 拆分 frontmatter 与正文
+///
+/// 没有 frontmatter 或 YAML 无法解析时返回 `None` 与原始内容，避免误删正文。
+pub fn parse(content: &str) -> (Option<PromptFrontmatter>, &str) {
+    let text = content.trim_start_matches('\u{feff}');
+    let Some(rest) = text
+        .strip_prefix("---\r\n")
+        .or_else(|| text.strip_prefix("---\n"))
+    else {
+        return (None, content);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            let yaml = &rest[..offset];
+            let body = rest[offset + line.len()..].trim_start_matches(['\r', '\n']);
+            return match serde_yaml::from_str::<Option<PromptFrontmatter>>(yaml) {
+                Ok(meta) => (Some(meta.unwrap_or_default()), body),
+                Err(e) => {
+                    log::warn!("提示词 frontmatter 解析失败，按普通内容处理: {e}");
+                    (None, content)
+                }
+            };
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+/// 将提示词的结构化字段写回 frontmatter，拼接在正文之前
+pub fn render(prompt: &Prompt) -> String {
+    let meta = PromptFrontmatter {
+        title: Some(prompt.name.clone()),
+        description: prompt.description.clone(),
+        tags: prompt.tags.clone(),
+        apps: prompt.target_apps.clone(),
+    };
+    let yaml = serde_yaml::to_string(&meta).unwrap_or_default();
+    format!("---\n{yaml}---\n\n{}", prompt.content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frontmatter_round_trips_through_prompt_fields() {
+        let source = "---\ntitle: Code Reviewer\ndescription: Strict checklist\ntags: [review, rust]\napps:\n  - claude\n  - codex\nauthor: someone\n---\n\n# Review\nBe strict.\n";
+        let (meta, body) = parse(source);
+        let meta = meta.expect("frontmatter parsed");
+        assert_eq!(meta.title.as_deref(), Some("Code Reviewer"));
+        assert_eq!(meta.tags, vec!["review", "rust"]);
+        assert_eq!(meta.apps, vec!["claude", "codex"]);
+        assert_eq!(body, "# Review\nBe strict.\n");
+
+        let prompt = Prompt {
+            id: "reviewer".to_string(),
+            name: meta.title.clone().unwrap(),
+            content: body.to_string(),
+            description: meta.description.clone(),
+            enabled: false,
+            tags: meta.tags.clone(),
+            target_apps: meta.apps.clone(),
+            apps: Default::default(),
+            created_at: None,
+            updated_at: None,
+        };
+        let (reparsed, rebody) = parse(&render(&prompt));
+        assert_eq!(reparsed.map(|m| m.apps), Some(meta.apps));
+        assert_eq!(rebody, body);
+    }
+
+    #[test]
+    fn content_without_valid_frontmatter_is_untouched() {
+        let plain = "# Title\n---\nnot frontmatter";
+        assert_eq!(parse(plain), (None, plain));
+
+        let unclosed = "---\ntitle: x\nbody";
+        assert_eq!(parse(unclosed), (None, unclosed));
+
+        let invalid = "---\ntags: [unclosed\n---\nbody";
+        assert_eq!(parse(invalid), (None, invalid));
+    }
+}
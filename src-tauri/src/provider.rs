@@ -46,6 +46,9 @@ pub struct Provider {
     /// 是否已编辑（用于复制后编辑状态的标记）
     #[serde(rename = "isEditedAfterDuplication", skip_serializing_if = "Option::is_none")]
     pub is_edited_after_duplication: Option<bool>,
+    /// 标签（如团队、费用档位），用于分组筛选
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Provider {
@@ -71,8 +74,14 @@ impl Provider {
             is_pinned: false,
             is_duplicated: None,
             is_edited_after_duplication: None,
+            tags: Vec::new(),
         }
     }
+
+    /// 供应商级配置目录覆盖（`meta.configDir`）
+    pub fn config_dir(&self) -> Option<&str> {
+        self.meta.as_ref()?.config_dir.as_deref()
+    }
 }
 
 /// 供应商管理器
@@ -100,6 +109,41 @@ pub struct ProviderMeta {
     /// 待选模型列表
     #[serde(rename = "candidateModels", skip_serializing_if = "Option::is_none")]
     pub candidate_models: Option<Vec<String>>,
+    /// 月度预算（按 token 数或金额计）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<ProviderBudget>,
+    /// 关联的 Gemini OAuth 账号 ID（切换时同步替换 oauth_creds.json）
+    #[serde(rename = "geminiOauthAccount", skip_serializing_if = "Option::is_none")]
+    pub gemini_oauth_account: Option<String>,
+    /// 供应商级配置目录（覆盖全局目录设置，用于多实例 CLI）
+    #[serde(rename = "configDir", skip_serializing_if = "Option::is_none")]
+    pub config_dir: Option<String>,
+    /// 切换到该 Codex 供应商时选中的 profile（只改写 config.toml 的顶层 `profile` 键）
+    #[serde(rename = "codexProfile", skip_serializing_if = "Option::is_none")]
+    pub codex_profile: Option<String>,
+}
+
+/// 预算计量单位
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetUnit {
+    #[default]
+    Tokens,
+    Currency,
+}
+
+/// 供应商月度预算
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderBudget {
+    /// 每月上限（token 数或金额）
+    #[serde(rename = "monthlyLimit")]
+    pub monthly_limit: f64,
+    /// 计量单位
+    #[serde(default)]
+    pub unit: BudgetUnit,
+    /// 货币符号（仅 currency 单位时使用，如 "USD"）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
 }
 
 impl ProviderManager {
@@ -108,3 +152,44 @@ impl ProviderManager {
         &self.providers
     }
 }
+
+/// 按分类分组的供应商
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderGroup {
+    /// 分类名称（None 表示未分类）
+    pub category: Option<String>,
+    pub providers: Vec<Provider>,
+}
+
+impl ProviderGroup {
+    /// 按分类顺序分组
+    ///
+    /// 分组依次为 `category_order` 中的分类、未列出的分类（按首次出现顺序）、
+    /// 未分类供应商；组内保持传入顺序，空分类不返回。
+    pub fn group(
+        providers: impl IntoIterator<Item = Provider>,
+        category_order: &[String],
+    ) -> Vec<Self> {
+        let mut groups: IndexMap<Option<String>, Vec<Provider>> = category_order
+            .iter()
+            .map(|category| (Some(category.clone()), Vec::new()))
+            .collect();
+        let mut uncategorized = Vec::new();
+        for provider in providers {
+            match provider.category.clone().filter(|c| !c.is_empty()) {
+                Some(category) => groups.entry(Some(category)).or_default().push(provider),
+                None => uncategorized.push(provider),
+            }
+        }
+        groups.insert(None, uncategorized);
+
+        groups
+            .into_iter()
+            .filter(|(_, providers)| !providers.is_empty())
+            .map(|(category, providers)| Self {
+                category,
+                providers,
+            })
+            .collect()
+    }
+}
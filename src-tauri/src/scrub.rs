@@ -0,0 +1,87 @@
+//! 敏感信息脱敏
+//!
+//! 日志、返回给前端的错误信息和导出的诊断数据统一经过这里处理，
+//! 把常见格式的 API Key / Token 替换为 `***`（保留可辨识的前缀，便于排查是哪类密钥）。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+
+const REDACTED: &str = "***";
+
+/// 带固定前缀的密钥：OpenAI/Anthropic（sk-）、Google（AIza）、xAI（xai-）、Groq（gsk_）、
+/// GitHub（ghp_/gho_/github_pat_）以及 Bearer Token
+static TOKEN_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(sk-|xai-|AIza|gsk_|ghp_|gho_|github_pat_|(?i:bearer)\s+)[A-Za-z0-9._\-]{8,}")
+        .expect("valid token regex")
+});
+
+/// `apiKey=...`、`"access_token": "..."` 等键值形式（含深链接 URL 的查询参数）
+static KEY_VALUE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?i)((?:api[_-]?key|auth[_-]?token|access[_-]?token|refresh[_-]?token|secret|password)["']?\s*[:=]\s*["']?)[^\s"'&,;]{4,}"#,
+    )
+    .expect("valid key-value regex")
+});
+
+/// 对文本中的密钥脱敏
+pub fn scrub_secrets(text: &str) -> String {
+    let text = TOKEN_PATTERN.replace_all(text, format!("${{1}}{REDACTED}"));
+    KEY_VALUE_PATTERN
+        .replace_all(&text, format!("${{1}}{REDACTED}"))
+        .into_owned()
+}
+
+/// 递归脱敏 JSON 中的所有字符串
+pub fn scrub_value(value: &mut Value) {
+    match value {
+        Value::String(s) => *s = scrub_secrets(s),
+        Value::Array(items) => items.iter_mut().for_each(scrub_value),
+        Value::Object(map) => map.values_mut().for_each(scrub_value),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn scrub_secrets_masks_known_key_formats() {
+        let line = "ccswitch://v1/import?resource=provider&apiKey=abcd1234efgh&name=x Authorization: Bearer eyJhbGciOi.abc sk-ant-api03-XXXXXXXXXXXX AIzaSyA1234567890abcdefg xai-YYYYYYYYYYYY";
+        let scrubbed = scrub_secrets(line);
+
+        assert!(scrubbed.contains("apiKey=***&name=x"));
+        assert!(scrubbed.contains("Bearer ***"));
+        assert!(scrubbed.contains("sk-***"));
+        assert!(scrubbed.contains("AIza***"));
+        assert!(scrubbed.contains("xai-***"));
+        for secret in [
+            "abcd1234efgh",
+            "XXXXXXXXXXXX",
+            "SyA1234567890",
+            "YYYYYYYYYYYY",
+        ] {
+            assert!(!scrubbed.contains(secret), "{secret} leaked: {scrubbed}");
+        }
+
+        // 普通文本与数量配置不受影响
+        assert_eq!(scrub_secrets("max_tokens=4096"), "max_tokens=4096");
+        assert_eq!(scrub_secrets("risk-assessment-v2"), "risk-assessment-v2");
+    }
+
+    #[test]
+    fn scrub_value_walks_nested_json() {
+        let mut value = json!({
+            "url": "https://api.example.com?api_key=secret-value",
+            "nested": [{ "token": "sk-1234567890abcdef" }],
+            "count": 3
+        });
+        scrub_value(&mut value);
+
+        assert_eq!(value["url"], "https://api.example.com?api_key=***");
+        assert_eq!(value["nested"][0]["token"], "sk-***");
+        assert_eq!(value["count"], 3);
+    }
+}
@@ -0,0 +1,236 @@
+//! 应用锁：共用工作站上用口令保护危险操作
+//!
+//! 设置口令后，删除数据（供应商、MCP、提示词、凭据、环境变量等）、恢复备份、导出含密钥的配置、
+//! 复制 API Key 原文等操作需要先通过 `unlock_session` 解锁；解锁在设置的分钟数后自动失效。
+//! 口令以 PBKDF2-HMAC-SHA256 加盐哈希后存入 `secrets` 表，不保存原文。
+
+use std::num::NonZeroU32;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use base64::prelude::*;
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Serialize;
+
+use crate::database::Database;
+use crate::error::AppError;
+
+/// `secrets` 表中口令哈希的键名
+const PASSCODE_SECRET_KEY: &str = "app_lock.passcode";
+const HASH_PREFIX: &str = "pbkdf2-sha256";
+const PBKDF2_ITERATIONS: u32 = 100_000;
+const SALT_LEN: usize = 16;
+const HASH_LEN: usize = 32;
+const MIN_PASSCODE_LEN: usize = 4;
+/// 连续输错该次数后暂停尝试
+const MAX_FAILED_ATTEMPTS: u32 = 5;
+const RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// 进程内的解锁状态（重启后恢复锁定）
+struct Session {
+    unlocked_until: Option<Instant>,
+    failed_attempts: u32,
+    retry_after: Option<Instant>,
+}
+
+static SESSION: Mutex<Session> = Mutex::new(Session {
+    unlocked_until: None,
+    failed_attempts: 0,
+    retry_after: None,
+});
+
+/// 应用锁状态
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLockStatus {
+    /// 是否已设置口令
+    pub enabled: bool,
+    /// 当前是否可执行受保护的操作（未设置口令时恒为真）
+    pub unlocked: bool,
+    /// 解锁剩余秒数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_in_secs: Option<u64>,
+}
+
+pub struct AppLockService;
+
+impl AppLockService {
+    pub fn status(db: &Database) -> Result<AppLockStatus, AppError> {
+        if !Self::is_enabled(db)? {
+            return Ok(AppLockStatus {
+                enabled: false,
+                unlocked: true,
+                expires_in_secs: None,
+            });
+        }
+        let remaining = Self::remaining();
+        Ok(AppLockStatus {
+            enabled: true,
+            unlocked: remaining.is_some(),
+            expires_in_secs: remaining.map(|left| left.as_secs()),
+        })
+    }
+
+    /// 受保护操作前调用：已设置口令且未解锁时返回 `app_lock.locked` 错误
+    pub fn ensure_unlocked(db: &Database) -> Result<(), AppError> {
+        if Self::is_enabled(db)? && Self::remaining().is_none() {
+            return Err(AppError::localized("app_lock.locked"));
+        }
+        Ok(())
+    }
+
+    /// 设置、修改或清除（`passcode` 为 `None`）口令；已设置口令时需提供当前口令
+    pub fn set_passcode(
+        db: &Database,
+        current: Option<&str>,
+        passcode: Option<&str>,
+    ) -> Result<(), AppError> {
+        if let Some(stored) = db.get_secret(PASSCODE_SECRET_KEY)? {
+            Self::check_attempt(&stored, current.unwrap_or_default())?;
+        }
+        match passcode {
+            Some(passcode) => {
+                if passcode.chars().count() < MIN_PASSCODE_LEN {
+                    return Err(AppError::localized_with(
+                        "app_lock.passcode_too_short",
+                        &[("min", MIN_PASSCODE_LEN.to_string())],
+                    ));
+                }
+                db.set_secret(PASSCODE_SECRET_KEY, &hash_passcode(passcode)?)?;
+                log::info!("已设置应用锁口令");
+            }
+            None => {
+                db.delete_secret(PASSCODE_SECRET_KEY)?;
+                log::info!("已关闭应用锁");
+            }
+        }
+        Self::lock();
+        Ok(())
+    }
+
+    /// 校验口令并解锁，时长由 `appLockTimeoutMinutes` 设置决定
+    pub fn unlock(db: &Database, passcode: &str) -> Result<AppLockStatus, AppError> {
+        let Some(stored) = db.get_secret(PASSCODE_SECRET_KEY)? else {
+            return Self::status(db);
+        };
+        Self::check_attempt(&stored, passcode)?;
+        let minutes = crate::settings::get_settings()
+            .app_lock_timeout_minutes
+            .max(1);
+        SESSION.lock().expect("写入应用锁状态失败").unlocked_until =
+            Some(Instant::now() + Duration::from_secs(u64::from(minutes) * 60));
+        Self::status(db)
+    }
+
+    /// 立即恢复锁定
+    pub fn lock() {
+        SESSION.lock().expect("写入应用锁状态失败").unlocked_until = None;
+    }
+
+    fn is_enabled(db: &Database) -> Result<bool, AppError> {
+        Ok(db.get_secret(PASSCODE_SECRET_KEY)?.is_some())
+    }
+
+    fn remaining() -> Option<Duration> {
+        let session = SESSION.lock().expect("读取应用锁状态失败");
+        session
+            .unlocked_until
+            .and_then(|until| until.checked_duration_since(Instant::now()))
+            .filter(|left| !left.is_zero())
+    }
+
+    /// 校验口令并记录失败次数；连续失败过多时暂停一段时间
+    fn check_attempt(stored: &str, passcode: &str) -> Result<(), AppError> {
+        let mut session = SESSION.lock().expect("写入应用锁状态失败");
+        if let Some(left) = session
+            .retry_after
+            .and_then(|until| until.checked_duration_since(Instant::now()))
+        {
+            return Err(AppError::localized_with(
+                "app_lock.retry_later",
+                &[("secs", left.as_secs().max(1).to_string())],
+            ));
+        }
+        if verify_passcode(stored, passcode) {
+            session.failed_attempts = 0;
+            session.retry_after = None;
+            return Ok(());
+        }
+        session.failed_attempts += 1;
+        if session.failed_attempts >= MAX_FAILED_ATTEMPTS {
+            session.failed_attempts = 0;
+            session.retry_after = Some(Instant::now() + RETRY_DELAY);
+        }
+        log::warn!("应用锁口令校验失败");
+        Err(AppError::localized("app_lock.wrong_passcode"))
+    }
+}
+
+fn iterations() -> NonZeroU32 {
+    NonZeroU32::new(PBKDF2_ITERATIONS).expect("迭代次数非零")
+}
+
+/// 生成 `pbkdf2-sha256$<iterations>$<base64 salt>$<base64 hash>`
+fn hash_passcode(passcode: &str) -> Result<String, AppError> {
+    let mut salt = [0u8; SALT_LEN];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| AppError::Config("生成口令盐值失败".to_string()))?;
+    let mut hash = [0u8; HASH_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations(),
+        &salt,
+        passcode.as_bytes(),
+        &mut hash,
+    );
+    Ok(format!(
+        "{HASH_PREFIX}${PBKDF2_ITERATIONS}${}${}",
+        BASE64_STANDARD.encode(salt),
+        BASE64_STANDARD.encode(hash)
+    ))
+}
+
+fn verify_passcode(stored: &str, passcode: &str) -> bool {
+    let mut parts = stored.split('$');
+    let (Some(HASH_PREFIX), Some(iterations), Some(salt), Some(hash), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return false;
+    };
+    let (Some(iterations), Ok(salt), Ok(hash)) = (
+        iterations.parse().ok().and_then(NonZeroU32::new),
+        BASE64_STANDARD.decode(salt),
+        BASE64_STANDARD.decode(hash),
+    ) else {
+        return false;
+    };
+    pbkdf2::verify(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        &salt,
+        passcode.as_bytes(),
+        &hash,
+    )
+    .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passcode_hash_round_trip() {
+        let stored = hash_passcode("2468").expect("hash passcode");
+        assert!(stored.starts_with("pbkdf2-sha256$100000$"));
+        assert!(verify_passcode(&stored, "2468"));
+        assert!(!verify_passcode(&stored, "1357"));
+        assert!(!verify_passcode("plain-text", "plain-text"));
+        assert_ne!(stored, hash_passcode("2468").unwrap(), "salt must differ");
+    }
+}
@@ -0,0 +1,306 @@
+use chrono::{Datelike, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::{BudgetUnit, Provider};
+use crate::store::AppState;
+
+/// 预算告警阈值（已用比例）
+const WARNING_RATIO: f64 = 0.8;
+const EXCEEDED_RATIO: f64 = 1.0;
+
+/// 单条用量记录（来自用量脚本的查询结果）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageRecord {
+    pub provider_id: String,
+    pub app_type: String,
+    /// 记录时间（Unix 秒）
+    pub recorded_at: i64,
+    #[serde(default)]
+    pub tokens: i64,
+    #[serde(default)]
+    pub cost: f64,
+    /// 输入 / 输出 token（用量脚本提供时按模型价格分别估算费用）
+    #[serde(default)]
+    pub input_tokens: i64,
+    #[serde(default)]
+    pub output_tokens: i64,
+    /// 产生用量的模型（为空时按供应商配置的模型估算费用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// 用量脚本原始输出（可选，便于排查）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+}
+
+/// 一次用量脚本结果中的数值
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageAmount {
+    pub tokens: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost: f64,
+    pub model: Option<String>,
+}
+
+/// 预算告警级别
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetLevel {
+    Ok,
+    Warning,
+    Exceeded,
+}
+
+impl BudgetLevel {
+    fn from_ratio(ratio: f64) -> Self {
+        if ratio >= EXCEEDED_RATIO {
+            BudgetLevel::Exceeded
+        } else if ratio >= WARNING_RATIO {
+            BudgetLevel::Warning
+        } else {
+            BudgetLevel::Ok
+        }
+    }
+}
+
+/// 供应商本月预算状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetStatus {
+    pub provider_id: String,
+    pub provider_name: String,
+    pub app_type: String,
+    pub unit: BudgetUnit,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    pub limit: f64,
+    pub used: f64,
+    /// 已用比例（0.0 - ∞）
+    pub ratio: f64,
+    pub level: BudgetLevel,
+    /// 统计周期起点（本月第一天 00:00，本地时区，Unix 秒）
+    pub period_start: i64,
+}
+
+/// 记录用量后的结果：包含新状态，以及本次是否跨越了告警阈值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageRecordResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<BudgetStatus>,
+    /// 本次记录使预算级别升高时为 true（用于前端/托盘告警）
+    pub crossed_threshold: bool,
+}
+
+/// 预算相关业务逻辑
+pub struct BudgetService;
+
+impl BudgetService {
+    /// 本月第一天 00:00（本地时区）的时间戳
+    pub fn current_period_start() -> i64 {
+        let now = Local::now();
+        Local
+            .with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+            .earliest()
+            .map(|dt| dt.timestamp())
+            .unwrap_or_else(|| now.timestamp())
+    }
+
+    /// 根据已用量与预算计算状态（纯函数，便于测试）
+    fn build_status(
+        app_type: &AppType,
+        provider: &Provider,
+        tokens: i64,
+        cost: f64,
+        period_start: i64,
+    ) -> Option<BudgetStatus> {
+        let budget = provider.meta.as_ref()?.budget.as_ref()?;
+        if budget.monthly_limit <= 0.0 {
+            return None;
+        }
+
+        let used = match budget.unit {
+            BudgetUnit::Tokens => tokens as f64,
+            BudgetUnit::Currency => cost,
+        };
+        let ratio = used / budget.monthly_limit;
+
+        Some(BudgetStatus {
+            provider_id: provider.id.clone(),
+            provider_name: provider.name.clone(),
+            app_type: app_type.as_str().to_string(),
+            unit: budget.unit,
+            currency: budget.currency.clone(),
+            limit: budget.monthly_limit,
+            used,
+            ratio,
+            level: BudgetLevel::from_ratio(ratio),
+            period_start,
+        })
+    }
+
+    /// 获取单个供应商的本月预算状态（未设置预算时返回 None）
+    pub fn get_status(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<Option<BudgetStatus>, AppError> {
+        let providers = state.db.get_all_providers(app_type.as_str())?;
+        let provider = providers
+            .get(provider_id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {provider_id} 不存在")))?;
+
+        let period_start = Self::current_period_start();
+        let (tokens, cost) =
+            state
+                .db
+                .sum_usage_since(app_type.as_str(), provider_id, period_start)?;
+        Ok(Self::build_status(
+            &app_type,
+            provider,
+            tokens,
+            cost,
+            period_start,
+        ))
+    }
+
+    /// 获取指定应用下所有设置了预算的供应商状态
+    pub fn list_statuses(
+        state: &AppState,
+        app_type: AppType,
+    ) -> Result<Vec<BudgetStatus>, AppError> {
+        let providers = state.db.get_all_providers(app_type.as_str())?;
+        let period_start = Self::current_period_start();
+
+        let mut statuses = Vec::new();
+        for provider in providers.values() {
+            if provider
+                .meta
+                .as_ref()
+                .and_then(|m| m.budget.as_ref())
+                .is_none()
+            {
+                continue;
+            }
+            let (tokens, cost) =
+                state
+                    .db
+                    .sum_usage_since(app_type.as_str(), &provider.id, period_start)?;
+            if let Some(status) =
+                Self::build_status(&app_type, provider, tokens, cost, period_start)
+            {
+                statuses.push(status);
+            }
+        }
+        Ok(statuses)
+    }
+
+    /// 累计一次用量脚本结果，并返回更新后的预算状态
+    pub fn record_usage(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        usage: UsageAmount,
+        raw: Option<String>,
+    ) -> Result<UsageRecordResult, AppError> {
+        let UsageAmount {
+            tokens,
+            input_tokens,
+            output_tokens,
+            cost,
+            model,
+        } = usage;
+        if tokens < 0 || input_tokens < 0 || output_tokens < 0 || cost < 0.0 || !cost.is_finite() {
+            return Err(AppError::InvalidInput("用量数值不能为负数".to_string()));
+        }
+
+        let before = Self::get_status(state, app_type.clone(), provider_id)?;
+
+        state.db.insert_usage_record(&UsageRecord {
+            provider_id: provider_id.to_string(),
+            app_type: app_type.as_str().to_string(),
+            recorded_at: chrono::Utc::now().timestamp(),
+            // 只报告了输入 / 输出时以两者之和作为总量
+            tokens: tokens.max(input_tokens + output_tokens),
+            cost,
+            input_tokens,
+            output_tokens,
+            model: model.filter(|model| !model.trim().is_empty()),
+            raw,
+        })?;
+
+        let after = Self::get_status(state, app_type, provider_id)?;
+        let crossed_threshold = match (&before, &after) {
+            (Some(b), Some(a)) => a.level > b.level,
+            (None, Some(a)) => a.level != BudgetLevel::Ok,
+            _ => false,
+        };
+
+        if crossed_threshold {
+            if let Some(status) = &after {
+                log::warn!(
+                    "供应商 {} 预算已达 {:.0}%（{:?}）",
+                    status.provider_id,
+                    status.ratio * 100.0,
+                    status.level
+                );
+            }
+        }
+
+        Ok(UsageRecordResult {
+            status: after,
+            crossed_threshold,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{ProviderBudget, ProviderMeta};
+    use serde_json::json;
+
+    fn provider_with_budget(limit: f64, unit: BudgetUnit) -> Provider {
+        let mut provider = Provider::with_id("p1".into(), "P1".into(), json!({}), None);
+        provider.meta = Some(ProviderMeta {
+            budget: Some(ProviderBudget {
+                monthly_limit: limit,
+                unit,
+                currency: None,
+            }),
+            ..ProviderMeta::default()
+        });
+        provider
+    }
+
+    #[test]
+    fn level_thresholds() {
+        assert_eq!(BudgetLevel::from_ratio(0.5), BudgetLevel::Ok);
+        assert_eq!(BudgetLevel::from_ratio(0.8), BudgetLevel::Warning);
+        assert_eq!(BudgetLevel::from_ratio(1.2), BudgetLevel::Exceeded);
+    }
+
+    #[test]
+    fn build_status_uses_budget_unit() {
+        let tokens = provider_with_budget(1000.0, BudgetUnit::Tokens);
+        let status = BudgetService::build_status(&AppType::Claude, &tokens, 900, 50.0, 0)
+            .expect("status");
+        assert_eq!(status.used, 900.0);
+        assert_eq!(status.level, BudgetLevel::Warning);
+
+        let currency = provider_with_budget(10.0, BudgetUnit::Currency);
+        let status = BudgetService::build_status(&AppType::Claude, &currency, 900, 12.5, 0)
+            .expect("status");
+        assert_eq!(status.used, 12.5);
+        assert_eq!(status.level, BudgetLevel::Exceeded);
+    }
+
+    #[test]
+    fn build_status_skips_provider_without_budget() {
+        let provider = Provider::with_id("p2".into(), "P2".into(), json!({}), None);
+        assert!(BudgetService::build_status(&AppType::Codex, &provider, 1, 1.0, 0).is_none());
+    }
+}
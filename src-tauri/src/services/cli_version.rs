@@ -0,0 +1,322 @@
+//! CLI 版本检测
+//!
+//! 运行各 CLI 的 `--version`（带超时），与已知兼容的版本范围比较；
+//! 版本跨过已知的配置格式变化点时给出提示，检测到升级时迁移已保存的供应商配置。
+
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::i18n::tr;
+use crate::services::provider::run_format_migrations;
+
+/// `--version` 的最长等待时间（部分 CLI 首次运行较慢）
+const VERSION_TIMEOUT: Duration = Duration::from_secs(10);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+type Version = (u64, u64, u64);
+
+/// 已验证兼容的版本范围 `[min, max)`
+struct CompatRange {
+    app: AppType,
+    binary: &'static str,
+    min: Version,
+    max: Version,
+}
+
+static COMPAT_RANGES: [CompatRange; 4] = [
+    CompatRange {
+        app: AppType::Claude,
+        binary: "claude",
+        min: (1, 0, 0),
+        max: (3, 0, 0),
+    },
+    CompatRange {
+        app: AppType::Codex,
+        binary: "codex",
+        min: (0, 2, 0),
+        max: (1, 0, 0),
+    },
+    CompatRange {
+        app: AppType::Gemini,
+        binary: "gemini",
+        min: (0, 1, 0),
+        max: (1, 0, 0),
+    },
+    CompatRange {
+        app: AppType::Grok,
+        binary: "grok",
+        min: (0, 0, 1),
+        max: (1, 0, 0),
+    },
+];
+
+/// 已知的配置格式变化：从低于 `version` 升级到不低于 `version` 时提示
+const FORMAT_CHANGES: [(AppType, Version, &str); 2] = [
+    (AppType::Codex, (0, 2, 0), "cli_version.change.codex_toml"),
+    (AppType::Claude, (2, 0, 0), "cli_version.change.claude_v2"),
+];
+
+/// 兼容性判断结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompatStatus {
+    Compatible,
+    /// 低于最低支持版本
+    TooOld,
+    /// 高于已验证的版本，配置格式可能已变化
+    Untested,
+    /// 无法读取版本号
+    Unknown,
+    NotInstalled,
+}
+
+/// 单个 CLI 的版本检测结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliVersionInfo {
+    pub app: AppType,
+    pub binary_path: Option<String>,
+    pub version: Option<String>,
+    /// 上次检测到的版本（首次检测为空）
+    pub previous_version: Option<String>,
+    pub status: CompatStatus,
+    pub warnings: Vec<String>,
+}
+
+/// 从 `--version` 输出中提取版本号（缺省的补丁号视为 0）
+fn parse_version(output: &str) -> Option<Version> {
+    let re = Regex::new(r"(\d+)\.(\d+)(?:\.(\d+))?").ok()?;
+    let caps = re.captures(output)?;
+    let part = |i: usize| {
+        caps.get(i)
+            .map_or(Some(0), |m| m.as_str().parse::<u64>().ok())
+    };
+    Some((part(1)?, part(2)?, part(3)?))
+}
+
+fn format_version((major, minor, patch): Version) -> String {
+    format!("{major}.{minor}.{patch}")
+}
+
+/// 按兼容范围与上次版本得出状态与提示
+fn evaluate(
+    range: &CompatRange,
+    version: Option<Version>,
+    previous: Option<Version>,
+) -> (CompatStatus, Vec<String>) {
+    let Some(version) = version else {
+        return (CompatStatus::Unknown, vec![tr("cli_version.unparsed", &[])]);
+    };
+
+    let mut warnings = Vec::new();
+    let status = if version < range.min {
+        warnings.push(tr(
+            "cli_version.too_old",
+            &[
+                ("version", format_version(version)),
+                ("min", format_version(range.min)),
+            ],
+        ));
+        CompatStatus::TooOld
+    } else if version >= range.max {
+        warnings.push(tr(
+            "cli_version.untested",
+            &[
+                ("version", format_version(version)),
+                ("max", format_version(range.max)),
+            ],
+        ));
+        CompatStatus::Untested
+    } else {
+        CompatStatus::Compatible
+    };
+
+    if let Some(previous) = previous {
+        for (app, boundary, key) in &FORMAT_CHANGES {
+            if *app == range.app && previous < *boundary && version >= *boundary {
+                warnings.push(tr(
+                    key,
+                    &[
+                        ("from", format_version(previous)),
+                        ("to", format_version(version)),
+                    ],
+                ));
+            }
+        }
+    }
+    (status, warnings)
+}
+
+/// 运行 `<binary> --version`，超时后结束进程
+fn run_version(path: &Path) -> Result<String, String> {
+    let mut cmd = Command::new(path);
+    cmd.arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+    let deadline = Instant::now() + VERSION_TIMEOUT;
+    loop {
+        match child.try_wait().map_err(|e| e.to_string())? {
+            Some(_) => break,
+            None if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(tr(
+                    "cli_version.timeout",
+                    &[("seconds", VERSION_TIMEOUT.as_secs().to_string())],
+                ));
+            }
+            None => thread::sleep(POLL_INTERVAL),
+        }
+    }
+
+    // 版本输出很短，进程结束后再读取不会因管道写满而阻塞
+    let mut output = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        let _ = stdout.read_to_string(&mut output);
+    }
+    if output.trim().is_empty() {
+        if let Some(mut stderr) = child.stderr.take() {
+            let _ = stderr.read_to_string(&mut output);
+        }
+    }
+    Ok(output)
+}
+
+fn version_key(app: &AppType) -> Result<String, AppError> {
+    // 版本随设备而异，数据库同步到其他设备时互不影响
+    Ok(format!(
+        "cli_version_{}_{}",
+        crate::services::device::current_device_id()?,
+        app.as_str()
+    ))
+}
+
+pub struct CliVersionService;
+
+impl CliVersionService {
+    /// 并行检测各 CLI 的版本（会启动子进程，需在阻塞线程中调用）
+    pub fn detect(db: &Database) -> Result<Vec<CliVersionInfo>, AppError> {
+        let outputs: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = COMPAT_RANGES
+                .iter()
+                .map(|range| {
+                    scope.spawn(move || {
+                        crate::mcp::find_in_path(range.binary)
+                            .map(|path| (run_version(&path), path))
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or(None))
+                .collect()
+        });
+
+        let mut results = Vec::new();
+        for (range, output) in COMPAT_RANGES.iter().zip(outputs) {
+            let Some((output, path)) = output else {
+                results.push(CliVersionInfo {
+                    app: range.app.clone(),
+                    binary_path: None,
+                    version: None,
+                    previous_version: None,
+                    status: CompatStatus::NotInstalled,
+                    warnings: Vec::new(),
+                });
+                continue;
+            };
+
+            let key = version_key(&range.app)?;
+            let previous_version = db.get_setting(&key)?;
+            let (version, status, mut warnings) = match output {
+                Ok(output) => {
+                    let version = parse_version(&output);
+                    let previous = previous_version.as_deref().and_then(parse_version);
+                    let (status, warnings) = evaluate(range, version, previous);
+                    (version.map(format_version), status, warnings)
+                }
+                Err(e) => (None, CompatStatus::Unknown, vec![e]),
+            };
+            if let Some(version) = &version {
+                if previous_version.as_ref() != Some(version) {
+                    db.set_setting(&key, version)?;
+                    // CLI 升级后将已保存的供应商配置迁移到新格式
+                    if previous_version.is_some() {
+                        let report = run_format_migrations(db, Some(&range.app), false)?;
+                        if !report.changes.is_empty() {
+                            warnings.push(tr(
+                                "cli_version.migrated",
+                                &[("count", report.changes.len().to_string())],
+                            ));
+                        }
+                    }
+                }
+            }
+            if !warnings.is_empty() {
+                log::warn!("{} 版本检查: {}", range.app.as_str(), warnings.join("; "));
+            }
+
+            results.push(CliVersionInfo {
+                app: range.app.clone(),
+                binary_path: Some(path.to_string_lossy().to_string()),
+                version,
+                previous_version,
+                status,
+                warnings,
+            });
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_from_cli_output() {
+        assert_eq!(parse_version("1.0.72 (Claude Code)"), Some((1, 0, 72)));
+        assert_eq!(parse_version("codex-cli 0.46.0"), Some((0, 46, 0)));
+        assert_eq!(parse_version("v2.1"), Some((2, 1, 0)));
+        assert_eq!(parse_version("unknown"), None);
+    }
+
+    #[test]
+    fn evaluate_flags_ranges_and_format_changes() {
+        let codex = &COMPAT_RANGES[1];
+        assert_eq!(
+            evaluate(codex, Some((0, 46, 0)), Some((0, 46, 0))).0,
+            CompatStatus::Compatible
+        );
+        assert_eq!(
+            evaluate(codex, Some((0, 1, 0)), None).0,
+            CompatStatus::TooOld
+        );
+        assert_eq!(
+            evaluate(codex, Some((1, 2, 0)), None).0,
+            CompatStatus::Untested
+        );
+
+        let (status, warnings) = evaluate(codex, Some((0, 2, 0)), Some((0, 1, 9)));
+        assert_eq!(status, CompatStatus::Compatible);
+        assert_eq!(warnings.len(), 1);
+    }
+}
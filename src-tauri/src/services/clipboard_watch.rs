@@ -0,0 +1,271 @@
+//! 剪贴板监听
+//!
+//! 开启后定期读取剪贴板文本，识别 ccswitch:// 链接与常见格式的 API Key 并提示导入。
+//! 深链接按导入策略评估，被策略阻止的链接不提示；剪贴板原文不落盘、不写日志。
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::deeplink::PolicyDecision;
+use crate::error::AppError;
+use crate::services::provider::mask_credential;
+use crate::settings::DeepLinkPolicySettings;
+
+/// 剪贴板检查间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// 两次提示之间的最短间隔
+const MIN_EMIT_INTERVAL: Duration = Duration::from_secs(10);
+/// 超过该长度的剪贴板内容不做识别
+const MAX_TEXT_LEN: usize = 8 * 1024;
+
+/// cc-switch 自己写入剪贴板的内容哈希（复制凭据后不应再提示导入）
+static OWN_WRITE: Mutex<Option<u64>> = Mutex::new(None);
+
+/// 识别到的剪贴板内容类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ClipboardKind {
+    DeepLink,
+    ApiKey,
+}
+
+/// 剪贴板识别结果（仅随事件发给前端用于提示，不落盘、不写日志）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardDetection {
+    pub kind: ClipboardKind,
+    /// ccswitch:// 链接或 API Key 原文
+    pub value: String,
+    /// 脱敏后的展示文本
+    pub preview: String,
+    /// 根据 Key 前缀推测的应用（sk-ant- → claude，AIza → gemini，sk- → codex）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
+}
+
+/// 剪贴板监听（需在设置中开启）
+///
+/// 识别结果通过 `clipboard-detected` 事件发给前端。只保留上次内容的哈希用于去重，
+/// 提示之间至少间隔 10 秒。
+pub struct ClipboardWatchService;
+
+impl ClipboardWatchService {
+    pub fn start(app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut watcher = ClipboardWatcher::default();
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                let settings = crate::settings::get_settings();
+                if !settings.clipboard_monitor {
+                    watcher = ClipboardWatcher::default();
+                    continue;
+                }
+
+                let handle = app.clone();
+                let text = match tauri::async_runtime::spawn_blocking(move || {
+                    handle.clipboard().read_text().ok()
+                })
+                .await
+                {
+                    Ok(Some(text)) => text,
+                    // 剪贴板为空或不是文本
+                    Ok(None) => continue,
+                    Err(e) => {
+                        log::warn!("读取剪贴板任务异常: {e}");
+                        continue;
+                    }
+                };
+
+                let own_write = OWN_WRITE.lock().ok().and_then(|guard| *guard);
+                if own_write == Some(text_hash(&text)) {
+                    watcher.last_hash = own_write;
+                    continue;
+                }
+                if let Some(detection) =
+                    watcher.observe(&text, &settings.deeplink_policy, Instant::now())
+                {
+                    if let Err(e) = app.emit("clipboard-detected", &detection) {
+                        log::warn!("发送剪贴板识别事件失败: {e}");
+                    }
+                }
+            }
+        });
+    }
+
+    /// 写入剪贴板文本，并让监听跳过这次写入
+    pub fn write_text(app: &AppHandle, text: &str) -> Result<(), AppError> {
+        if let Ok(mut guard) = OWN_WRITE.lock() {
+            *guard = Some(text_hash(text));
+        }
+        app.clipboard()
+            .write_text(text.to_string())
+            .map_err(|e| AppError::Message(format!("写入剪贴板失败: {e}")))
+    }
+}
+
+fn text_hash(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 去重与限频状态
+#[derive(Default)]
+struct ClipboardWatcher {
+    last_hash: Option<u64>,
+    last_emit: Option<Instant>,
+}
+
+impl ClipboardWatcher {
+    /// 处理一次读取结果；首次读取（刚开启时已在剪贴板中的内容）只记录不提示
+    fn observe(
+        &mut self,
+        text: &str,
+        policy: &DeepLinkPolicySettings,
+        now: Instant,
+    ) -> Option<ClipboardDetection> {
+        let hash = text_hash(text);
+        match self.last_hash.replace(hash) {
+            Some(previous) if previous != hash => {}
+            _ => return None,
+        }
+        let detection = detect(text, policy)?;
+        if self
+            .last_emit
+            .is_some_and(|last| now.duration_since(last) < MIN_EMIT_INTERVAL)
+        {
+            return None;
+        }
+        self.last_emit = Some(now);
+        Some(detection)
+    }
+}
+
+/// 识别剪贴板文本：可解析且未被导入策略阻止的 ccswitch:// 链接，或单独一行的 API Key
+fn detect(text: &str, policy: &DeepLinkPolicySettings) -> Option<ClipboardDetection> {
+    let text = text.trim();
+    if text.is_empty() || text.len() > MAX_TEXT_LEN || text.contains(char::is_whitespace) {
+        return None;
+    }
+
+    if text.starts_with("ccswitch://") {
+        let request = crate::deeplink::parse_deeplink_url(text).ok()?;
+        let (decision, _) = crate::deeplink::evaluate_policy(text, &request, policy);
+        if matches!(decision, PolicyDecision::Block(_)) {
+            return None;
+        }
+        return Some(ClipboardDetection {
+            kind: ClipboardKind::DeepLink,
+            value: text.to_string(),
+            preview: crate::scrub::scrub_secrets(text),
+            app: request.app,
+        });
+    }
+
+    let app = api_key_app(text)?;
+    Some(ClipboardDetection {
+        kind: ClipboardKind::ApiKey,
+        value: text.to_string(),
+        preview: mask_credential(text),
+        app: Some(app.to_string()),
+    })
+}
+
+/// 按前缀识别 API Key 并推测所属应用
+fn api_key_app(text: &str) -> Option<&'static str> {
+    let is_key_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    if !text.chars().all(is_key_char) {
+        return None;
+    }
+    if text.starts_with("sk-ant-") && text.len() >= 40 {
+        Some("claude")
+    } else if text.starts_with("AIza") && text.len() == 39 {
+        Some("gemini")
+    } else if text.starts_with("sk-") && text.len() >= 32 {
+        Some("codex")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(text: &str) -> Option<ClipboardDetection> {
+        super::detect(text, &DeepLinkPolicySettings::default())
+    }
+
+    #[test]
+    fn detects_api_keys_by_prefix() {
+        let claude = format!("sk-ant-api03-{}", "a".repeat(40));
+        let detection = detect(&format!("  {claude}\n")).expect("claude key");
+        assert_eq!(detection.kind, ClipboardKind::ApiKey);
+        assert_eq!(detection.app.as_deref(), Some("claude"));
+        assert!(!detection.preview.contains(&claude[12..]));
+
+        let gemini = format!("AIza{}", "B".repeat(35));
+        assert_eq!(detect(&gemini).unwrap().app.as_deref(), Some("gemini"));
+        let openai = format!("sk-proj-{}", "c".repeat(40));
+        assert_eq!(detect(&openai).unwrap().app.as_deref(), Some("codex"));
+
+        assert!(detect("sk-short").is_none());
+        assert!(detect("just some copied text").is_none());
+        assert!(detect("ccswitch://v1/import?resource=unknown").is_none());
+    }
+
+    #[test]
+    fn watcher_skips_initial_content_repeats_and_rate_limits() {
+        let key_a = format!("sk-{}", "a".repeat(40));
+        let key_b = format!("sk-{}", "b".repeat(40));
+        let key_c = format!("sk-{}", "c".repeat(40));
+        let start = Instant::now();
+        let policy = DeepLinkPolicySettings::default();
+        let mut watcher = ClipboardWatcher::default();
+
+        assert!(
+            watcher.observe(&key_a, &policy, start).is_none(),
+            "initial content"
+        );
+        assert!(watcher.observe(&key_b, &policy, start).is_some());
+        assert!(
+            watcher.observe(&key_b, &policy, start).is_none(),
+            "unchanged"
+        );
+        assert!(
+            watcher
+                .observe(&key_c, &policy, start + Duration::from_secs(1))
+                .is_none(),
+            "rate limited"
+        );
+        assert!(watcher
+            .observe(&key_a, &policy, start + MIN_EMIT_INTERVAL)
+            .is_some());
+    }
+
+    #[test]
+    fn skips_deeplinks_blocked_by_policy() {
+        let url = "ccswitch://v1/import?resource=provider&app=claude&name=Test&endpoint=https%3A%2F%2Fapi.example.com&apiKey=sk-test-123";
+        let detection = detect(url).expect("allowed by default policy");
+        assert_eq!(detection.kind, ClipboardKind::DeepLink);
+        assert!(!detection.preview.contains("sk-test-123"));
+
+        let policy = DeepLinkPolicySettings {
+            block_api_key_links: true,
+            ..Default::default()
+        };
+        assert!(super::detect(url, &policy).is_none());
+
+        let policy = DeepLinkPolicySettings {
+            allowed_resources: vec!["prompt".to_string()],
+            ..Default::default()
+        };
+        assert!(super::detect(url, &policy).is_none());
+    }
+}
@@ -1,14 +1,82 @@
 use super::provider::ProviderService;
+use super::sync_report::failures_to_result;
 use crate::app_config::{AppType, MultiAppConfig};
+use crate::config::get_app_config_dir;
+use crate::database::Database;
 use crate::error::AppError;
 use crate::provider::Provider;
+use crate::store::AppState;
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 const MAX_BACKUPS: usize = 10;
 
+/// 备份类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupKind {
+    /// 旧版 config.json 备份（`backups/backup_*.json`）
+    Config,
+    /// 数据库快照备份（`backups/db_backup_*.db`）
+    Database,
+}
+
+/// 备份列表项
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupEntry {
+    pub id: String,
+    pub kind: BackupKind,
+    pub size: u64,
+    pub created_at: i64,
+    /// 各应用的供应商数量（读取失败时为空）
+    pub provider_counts: BTreeMap<String, usize>,
+}
+
+/// 备份中的供应商摘要
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupProvider {
+    pub app_type: String,
+    pub id: String,
+    pub name: String,
+    pub is_current: bool,
+}
+
+/// 备份内容摘要
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupContents {
+    pub providers: Vec<BackupProvider>,
+    pub mcp_servers: Vec<String>,
+    pub prompt_count: usize,
+    pub skill_repo_count: usize,
+}
+
+impl BackupContents {
+    fn provider_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for provider in &self.providers {
+            *counts.entry(provider.app_type.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+/// 备份预览（不执行恢复）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupPreview {
+    #[serde(flatten)]
+    pub entry: BackupEntry,
+    #[serde(flatten)]
+    pub contents: BackupContents,
+}
+
 /// 配置导入导出相关业务逻辑
 pub struct ConfigService;
 
@@ -83,6 +151,148 @@ impl ConfigService {
         Ok(())
     }
 
+    /// 列出 JSON 配置备份与数据库备份（按创建时间倒序）
+    pub fn list_backups() -> Result<Vec<BackupEntry>, AppError> {
+        let mut entries = Vec::new();
+
+        for backup in Database::list_db_backups()? {
+            let provider_counts = Database::read_db_backup_contents(&backup.id)
+                .map(|contents| contents.provider_counts())
+                .unwrap_or_default();
+            entries.push(BackupEntry {
+                id: backup.id,
+                kind: BackupKind::Database,
+                size: backup.size,
+                created_at: backup.created_at,
+                provider_counts,
+            });
+        }
+
+        let backup_dir = get_app_config_dir().join("backups");
+        if let Ok(iter) = fs::read_dir(&backup_dir) {
+            for entry in iter.filter_map(|entry| entry.ok()) {
+                let path = entry.path();
+                if path.extension().map(|ext| ext != "json").unwrap_or(true) {
+                    continue;
+                }
+                let Some(id) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+                    continue;
+                };
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                let provider_counts = Self::read_config_backup(&id)
+                    .map(|config| Self::config_contents(&config).provider_counts())
+                    .unwrap_or_default();
+                entries.push(BackupEntry {
+                    id,
+                    kind: BackupKind::Config,
+                    size: metadata.len(),
+                    created_at: metadata
+                        .modified()
+                        .ok()
+                        .map(|t| chrono::DateTime::<Utc>::from(t).timestamp())
+                        .unwrap_or(0),
+                    provider_counts,
+                });
+            }
+        }
+
+        entries.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        Ok(entries)
+    }
+
+    /// 预览备份内容（不执行恢复）
+    pub fn preview_backup(kind: BackupKind, id: &str) -> Result<BackupPreview, AppError> {
+        let entry = Self::list_backups()?
+            .into_iter()
+            .find(|entry| entry.kind == kind && entry.id == id)
+            .ok_or_else(|| AppError::InvalidInput(format!("备份不存在: {id}")))?;
+
+        let contents = match kind {
+            BackupKind::Database => Database::read_db_backup_contents(id)?,
+            BackupKind::Config => Self::config_contents(&Self::read_config_backup(id)?),
+        };
+
+        Ok(BackupPreview { entry, contents })
+    }
+
+    /// 从备份恢复，返回恢复前生成的安全备份 ID
+    ///
+    /// 恢复后会将当前供应商同步到 live 配置。
+    pub fn restore_backup(state: &AppState, kind: BackupKind, id: &str) -> Result<String, AppError> {
+        let safety_backup_id = match kind {
+            BackupKind::Database => state.db.restore_db_backup(id)?,
+            BackupKind::Config => {
+                let config = Self::read_config_backup(id)?;
+                let safety_backup = state.db.backup_database_file()?;
+                state.db.replace_from_json(&config)?;
+                safety_backup
+                    .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+                    .unwrap_or_default()
+            }
+        };
+
+        if let Err(err) = ProviderService::sync_current_to_live(state)
+            .and_then(|reports| failures_to_result(&reports))
+        {
+            log::warn!("恢复后同步 live 配置失败: {err}");
+        }
+
+        Ok(safety_backup_id)
+    }
+
+    fn config_backup_path(id: &str) -> Result<PathBuf, AppError> {
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid {
+            return Err(AppError::InvalidInput(format!("无效的备份 ID: {id}")));
+        }
+        let path = get_app_config_dir().join("backups").join(format!("{id}.json"));
+        if !path.exists() {
+            return Err(AppError::InvalidInput(format!("备份不存在: {id}")));
+        }
+        Ok(path)
+    }
+
+    fn read_config_backup(id: &str) -> Result<MultiAppConfig, AppError> {
+        let path = Self::config_backup_path(id)?;
+        let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+        serde_json::from_str(&content).map_err(|e| AppError::json(&path, e))
+    }
+
+    fn config_contents(config: &MultiAppConfig) -> BackupContents {
+        let mut providers: Vec<BackupProvider> = config
+            .apps
+            .iter()
+            .flat_map(|(app_type, manager)| {
+                manager.providers.iter().map(move |(id, provider)| BackupProvider {
+                    app_type: app_type.clone(),
+                    id: id.clone(),
+                    name: provider.name.clone(),
+                    is_current: &manager.current == id,
+                })
+            })
+            .collect();
+        providers.sort_by(|a, b| a.app_type.cmp(&b.app_type));
+
+        BackupContents {
+            providers,
+            mcp_servers: config
+                .mcp
+                .servers
+                .as_ref()
+                .map(|servers| servers.values().map(|s| s.name.clone()).collect())
+                .unwrap_or_default(),
+            prompt_count: config.prompts.claude.prompts.len()
+                + config.prompts.codex.prompts.len()
+                + config.prompts.gemini.prompts.len(),
+            skill_repo_count: config.skills.repos.len(),
+        }
+    }
+
     /// 同步当前供应商到对应的 live 配置。
     pub fn sync_current_providers_to_live(config: &mut MultiAppConfig) -> Result<(), AppError> {
         Self::sync_current_provider_for_app(config, &AppType::Claude)?;
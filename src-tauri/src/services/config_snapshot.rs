@@ -0,0 +1,315 @@
+//! 配置快照导出
+//!
+//! 把供应商、MCP 服务器与提示词整理成脱敏的 JSON 与 Markdown 摘要，写入用户
+//! 选择的目录（如 dotfiles 仓库）。快照不含时间戳，内容未变化时不重写文件，
+//! 避免产生无意义的提交。
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::settings::ConfigSnapshotSchedule;
+
+/// 调度器检查间隔（"配置变化后导出"的最大延迟）
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// 每日导出的间隔（秒）
+const NIGHTLY_INTERVAL_SECS: i64 = 24 * 60 * 60;
+/// 上次每日导出时间（settings 表键名）
+const LAST_SNAPSHOT_KEY: &str = "config_snapshot_last_at";
+
+const JSON_FILE_NAME: &str = "cc-switch.json";
+const MARKDOWN_FILE_NAME: &str = "cc-switch.md";
+
+/// 快照导出结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSnapshotResult {
+    pub dir: String,
+    pub files: Vec<String>,
+    /// 是否有文件内容发生变化
+    pub changed: bool,
+}
+
+pub struct ConfigSnapshotService;
+
+impl ConfigSnapshotService {
+    /// 立即导出到设置中的快照目录
+    pub fn export_now(db: &Database) -> Result<ConfigSnapshotResult, AppError> {
+        let dir =
+            snapshot_dir().ok_or_else(|| AppError::localized("config_snapshot.dir_not_set"))?;
+        Self::write_snapshot(db, Path::new(&dir))
+    }
+
+    /// 生成快照并写入指定目录（内容未变化的文件不重写）
+    pub fn write_snapshot(db: &Database, dir: &Path) -> Result<ConfigSnapshotResult, AppError> {
+        let snapshot = build_snapshot(db)?;
+        let json = serde_json::to_string_pretty(&snapshot)
+            .map_err(|e| AppError::JsonSerialize { source: e })?;
+        let markdown = render_markdown(&snapshot);
+
+        let mut changed = false;
+        let mut files = Vec::new();
+        for (name, content) in [
+            (JSON_FILE_NAME, json + "\n"),
+            (MARKDOWN_FILE_NAME, markdown),
+        ] {
+            let path = dir.join(name);
+            if std::fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+                crate::config::write_text_file(&path, &content)?;
+                changed = true;
+            }
+            files.push(path.to_string_lossy().to_string());
+        }
+
+        Ok(ConfigSnapshotResult {
+            dir: dir.to_string_lossy().to_string(),
+            files,
+            changed,
+        })
+    }
+
+    /// 按设置的导出时机判断是否需要导出
+    pub fn run_if_due(db: &Database) -> Result<Option<ConfigSnapshotResult>, AppError> {
+        let schedule = crate::settings::get_settings().config_snapshot_schedule;
+        let Some(dir) = snapshot_dir() else {
+            return Ok(None);
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        match schedule {
+            ConfigSnapshotSchedule::Off => return Ok(None),
+            ConfigSnapshotSchedule::OnChange => {}
+            ConfigSnapshotSchedule::Nightly => {
+                let due = db
+                    .get_setting(LAST_SNAPSHOT_KEY)?
+                    .and_then(|value| value.parse::<i64>().ok())
+                    .map(|last| now - last >= NIGHTLY_INTERVAL_SECS)
+                    .unwrap_or(true);
+                if !due {
+                    return Ok(None);
+                }
+            }
+        }
+
+        let result = Self::write_snapshot(db, Path::new(&dir))?;
+        if schedule == ConfigSnapshotSchedule::Nightly {
+            db.set_setting(LAST_SNAPSHOT_KEY, &now.to_string())?;
+        }
+        if result.changed {
+            log::info!("已导出配置快照: {}", result.dir);
+        }
+        Ok(Some(result))
+    }
+
+    /// 启动后台快照导出任务
+    pub fn start_scheduler(db: Arc<Database>) {
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(CHECK_INTERVAL).await;
+                let db_for_task = db.clone();
+                match tauri::async_runtime::spawn_blocking(move || Self::run_if_due(&db_for_task))
+                    .await
+                {
+                    Ok(Err(e)) => log::warn!("导出配置快照失败: {e}"),
+                    Err(e) => log::warn!("导出配置快照任务异常: {e}"),
+                    Ok(Ok(_)) => {}
+                }
+            }
+        });
+    }
+}
+
+fn snapshot_dir() -> Option<String> {
+    crate::settings::get_settings()
+        .config_snapshot_dir
+        .filter(|dir| !dir.trim().is_empty())
+}
+
+/// 收集供应商、MCP 服务器与提示词，并清除其中的密钥
+fn build_snapshot(db: &Database) -> Result<Value, AppError> {
+    let mut providers = Map::new();
+    let mut prompts = Map::new();
+    for app_type in AppType::all() {
+        let app = app_type.as_str();
+        let current = crate::settings::get_effective_current_provider(db, &app_type)?;
+        let items: Vec<Value> = db
+            .get_all_providers(app)?
+            .into_values()
+            .map(|provider| {
+                json!({
+                    "id": provider.id,
+                    "name": provider.name,
+                    "current": current.as_deref() == Some(provider.id.as_str()),
+                    "category": provider.category,
+                    "websiteUrl": provider.website_url,
+                    "tags": provider.tags,
+                    "settings": provider.settings_config,
+                })
+            })
+            .collect();
+        if !items.is_empty() {
+            providers.insert(app.to_string(), Value::Array(items));
+        }
+
+        let items: Vec<Value> = db
+            .get_prompts(app)?
+            .into_values()
+            .map(|prompt| {
+                json!({
+                    "id": prompt.id,
+                    "name": prompt.name,
+                    "enabled": prompt.enabled,
+                    "description": prompt.description,
+                    "content": prompt.content,
+                })
+            })
+            .collect();
+        if !items.is_empty() {
+            prompts.insert(app.to_string(), Value::Array(items));
+        }
+    }
+
+    let mcp_servers: Vec<Value> = db
+        .get_all_mcp_servers()?
+        .into_values()
+        .map(|server| {
+            let apps: Vec<String> = server
+                .apps
+                .enabled_apps()
+                .iter()
+                .map(|app| app.as_str().to_string())
+                .collect();
+            json!({
+                "id": server.id,
+                "name": server.name,
+                "apps": apps,
+                "description": server.description,
+                "server": server.server,
+            })
+        })
+        .collect();
+
+    let mut snapshot = json!({
+        "providers": providers,
+        "mcpServers": mcp_servers,
+        "prompts": prompts,
+    });
+    crate::database::redact_value(&mut snapshot);
+    crate::scrub::scrub_value(&mut snapshot);
+    Ok(snapshot)
+}
+
+/// 渲染便于阅读的 Markdown 摘要（不含配置细节与提示词正文）
+fn render_markdown(snapshot: &Value) -> String {
+    let mut out = String::from("# CC Switch configuration\n");
+
+    out.push_str("\n## Providers\n");
+    for (app, providers) in entries(&snapshot["providers"]) {
+        out.push_str(&format!(
+            "\n### {app}\n\n| Current | Name | ID | Website |\n|---|---|---|---|\n"
+        ));
+        for provider in providers.as_array().into_iter().flatten() {
+            let current = match provider["current"].as_bool() {
+                Some(true) => "✓",
+                _ => "",
+            };
+            out.push_str(&format!(
+                "| {current} | {} | `{}` | {} |\n",
+                cell(&provider["name"]),
+                cell(&provider["id"]),
+                cell(&provider["websiteUrl"]),
+            ));
+        }
+    }
+
+    out.push_str("\n## MCP servers\n\n| Name | ID | Command / URL | Apps |\n|---|---|---|---|\n");
+    for server in snapshot["mcpServers"].as_array().into_iter().flatten() {
+        let spec = &server["server"];
+        let target = if spec["url"].is_string() {
+            &spec["url"]
+        } else {
+            &spec["command"]
+        };
+        let apps: Vec<&str> = server["apps"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        out.push_str(&format!(
+            "| {} | `{}` | {} | {} |\n",
+            cell(&server["name"]),
+            cell(&server["id"]),
+            cell(target),
+            apps.join(", "),
+        ));
+    }
+
+    out.push_str("\n## Prompts\n");
+    for (app, prompts) in entries(&snapshot["prompts"]) {
+        out.push_str(&format!("\n### {app}\n\n"));
+        for prompt in prompts.as_array().into_iter().flatten() {
+            let enabled = if prompt["enabled"].as_bool() == Some(true) {
+                " (enabled)"
+            } else {
+                ""
+            };
+            out.push_str(&format!("- {}{enabled}\n", cell(&prompt["name"])));
+        }
+    }
+    out
+}
+
+fn entries(value: &Value) -> impl Iterator<Item = (&String, &Value)> {
+    value.as_object().into_iter().flatten()
+}
+
+/// 表格单元格文本（转义竖线，去除换行）
+fn cell(value: &Value) -> String {
+    value
+        .as_str()
+        .unwrap_or_default()
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+
+    #[test]
+    fn snapshot_is_redacted_and_rewritten_only_on_change() {
+        let db = Database::memory().expect("memory db");
+        let provider = Provider::with_id(
+            "relay".to_string(),
+            "Relay | CN".to_string(),
+            json!({ "env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-ant-secret-token-value",
+                "ANTHROPIC_BASE_URL": "https://relay.example.com"
+            }}),
+            Some("https://relay.example.com".to_string()),
+        );
+        db.save_provider("claude", &provider)
+            .expect("save provider");
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let first = ConfigSnapshotService::write_snapshot(&db, dir.path()).expect("write");
+        assert!(first.changed);
+        let json = std::fs::read_to_string(dir.path().join(JSON_FILE_NAME)).expect("json");
+        assert!(!json.contains("secret-token"));
+        assert!(json.contains("https://relay.example.com"));
+        let markdown =
+            std::fs::read_to_string(dir.path().join(MARKDOWN_FILE_NAME)).expect("markdown");
+        assert!(markdown.contains("Relay \\| CN"));
+
+        let second = ConfigSnapshotService::write_snapshot(&db, dir.path()).expect("rewrite");
+        assert!(!second.changed);
+    }
+}
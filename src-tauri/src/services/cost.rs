@@ -0,0 +1,356 @@
+//! 费用估算
+//!
+//! 按模型价格表把用量脚本记录的 token 数换算为费用，并按月、按供应商汇总。
+//! 内置价格表（美元 / 百万 token）可在设置中覆盖或补充。用量脚本直接报告了
+//! 费用的记录按原值计入，不再估算。
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, Local, Months, TimeZone};
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::services::budget::UsageRecord;
+use crate::services::models::ModelService;
+use crate::settings::ModelPrice;
+use crate::store::AppState;
+
+/// 价格表的币种
+pub const PRICING_CURRENCY: &str = "USD";
+/// 默认汇总的月数（含本月）
+pub const DEFAULT_SUMMARY_MONTHS: u32 = 6;
+const MAX_SUMMARY_MONTHS: u32 = 36;
+
+/// 内置价格：(模型前缀, 输入, 输出)，美元 / 百万 token
+const BUILTIN_PRICING: &[(&str, f64, f64)] = &[
+    // Anthropic
+    ("claude-opus-4-5", 5.0, 25.0),
+    ("claude-opus-4-1", 15.0, 75.0),
+    ("claude-opus-4", 15.0, 75.0),
+    ("claude-sonnet-4", 3.0, 15.0),
+    ("claude-3-7-sonnet", 3.0, 15.0),
+    ("claude-haiku-4-5", 1.0, 5.0),
+    ("claude-3-5-haiku", 0.8, 4.0),
+    // OpenAI
+    ("gpt-5.1-codex-mini", 0.25, 2.0),
+    ("gpt-5.1-codex", 1.25, 10.0),
+    ("gpt-5.1", 1.25, 10.0),
+    ("gpt-5-codex-mini", 0.25, 2.0),
+    ("gpt-5-codex", 1.25, 10.0),
+    ("gpt-5-mini", 0.25, 2.0),
+    ("gpt-5-nano", 0.05, 0.4),
+    ("gpt-5", 1.25, 10.0),
+    ("gpt-4.1-mini", 0.4, 1.6),
+    ("gpt-4.1", 2.0, 8.0),
+    ("o3-mini", 1.1, 4.4),
+    ("o3", 2.0, 8.0),
+    ("o4-mini", 1.1, 4.4),
+    // Google
+    ("gemini-3-pro", 2.0, 12.0),
+    ("gemini-2.5-pro", 1.25, 10.0),
+    ("gemini-2.5-flash-lite", 0.1, 0.4),
+    ("gemini-2.5-flash", 0.3, 2.5),
+    ("gemini-2.0-flash", 0.1, 0.4),
+    // xAI
+    ("grok-code-fast-1", 0.2, 1.5),
+    ("grok-4-1-fast", 0.2, 0.5),
+    ("grok-4-fast", 0.2, 0.5),
+    ("grok-4", 3.0, 15.0),
+    ("grok-3-mini", 0.3, 0.5),
+    ("grok-3", 3.0, 15.0),
+    // Qwen
+    ("qwen3-coder-plus", 1.0, 5.0),
+    ("qwen3-coder-flash", 0.3, 1.5),
+    ("qwen-max", 1.6, 6.4),
+    ("qwen-plus", 0.4, 1.2),
+    ("qwen-turbo", 0.05, 0.2),
+];
+
+/// 单个供应商在某月的费用
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderCost {
+    pub app_type: String,
+    pub provider_id: String,
+    /// 供应商已删除时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_name: Option<String>,
+    /// 最近一次用于估算的模型
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub tokens: i64,
+    /// 用量脚本直接报告的费用
+    pub reported_cost: f64,
+    /// 按价格表估算的费用
+    pub estimated_cost: f64,
+    pub total_cost: f64,
+    /// 找不到模型价格、未计入费用的 token 数
+    pub unpriced_tokens: i64,
+}
+
+/// 某月的费用汇总
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyCost {
+    /// `YYYY-MM`（本地时区）
+    pub month: String,
+    pub total_cost: f64,
+    /// 按费用降序
+    pub providers: Vec<ProviderCost>,
+}
+
+/// 费用汇总（最近的月份在前）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostSummary {
+    pub currency: String,
+    pub months: Vec<MonthlyCost>,
+}
+
+/// 供应商信息：名称与配置中的模型
+struct ProviderInfo {
+    name: String,
+    model: Option<String>,
+}
+
+pub struct CostService;
+
+impl CostService {
+    /// 生效的价格表：设置中的条目覆盖同名内置条目
+    pub fn pricing_table() -> Vec<ModelPrice> {
+        merge_pricing(&crate::settings::get_settings().model_pricing)
+    }
+
+    /// 最近 `months` 个月（含本月）的费用汇总
+    pub fn summary(state: &AppState, months: u32) -> Result<CostSummary, AppError> {
+        let months = months.clamp(1, MAX_SUMMARY_MONTHS);
+        let records = state.db.get_usage_records_since(months_start(months))?;
+
+        let mut providers = HashMap::new();
+        for app_type in AppType::all() {
+            for provider in state.db.get_all_providers(app_type.as_str())?.into_values() {
+                let model = ModelService::configured_model(&app_type, &provider.settings_config);
+                providers.insert(
+                    (app_type.as_str().to_string(), provider.id),
+                    ProviderInfo {
+                        name: provider.name,
+                        model,
+                    },
+                );
+            }
+        }
+
+        Ok(CostSummary {
+            currency: PRICING_CURRENCY.to_string(),
+            months: aggregate(&records, &providers, &Self::pricing_table(), month_key),
+        })
+    }
+}
+
+fn merge_pricing(overrides: &[ModelPrice]) -> Vec<ModelPrice> {
+    let mut table: Vec<ModelPrice> = overrides
+        .iter()
+        .filter(|price| !price.model.trim().is_empty())
+        .map(|price| ModelPrice {
+            model: price.model.trim().to_lowercase(),
+            ..price.clone()
+        })
+        .collect();
+    for (model, input, output) in BUILTIN_PRICING {
+        if !table.iter().any(|price| price.model == *model) {
+            table.push(ModelPrice {
+                model: model.to_string(),
+                input: *input,
+                output: *output,
+            });
+        }
+    }
+    table
+}
+
+/// 按最长前缀匹配模型价格（忽略大小写与 `anthropic/` 等路由前缀）
+fn find_price<'a>(table: &'a [ModelPrice], model: &str) -> Option<&'a ModelPrice> {
+    let model = model.trim().to_lowercase();
+    let model = model.rsplit('/').next().unwrap_or(&model);
+    table
+        .iter()
+        .filter(|price| model.starts_with(price.model.as_str()))
+        .max_by_key(|price| price.model.len())
+}
+
+/// 单条记录的费用：(报告的费用, 估算的费用, 未计价 token)
+fn record_cost(record: &UsageRecord, price: Option<&ModelPrice>) -> (f64, f64, i64) {
+    if record.cost > 0.0 {
+        return (record.cost, 0.0, 0);
+    }
+    let Some(price) = price else {
+        return (0.0, 0.0, record.tokens);
+    };
+    // 未区分输入 / 输出的部分按输入价格计（保守估计）
+    let split = record.input_tokens + record.output_tokens;
+    let unsplit = (record.tokens - split).max(0);
+    let cost = ((record.input_tokens + unsplit) as f64 * price.input
+        + record.output_tokens as f64 * price.output)
+        / 1_000_000.0;
+    (0.0, cost, 0)
+}
+
+fn aggregate(
+    records: &[UsageRecord],
+    providers: &HashMap<(String, String), ProviderInfo>,
+    table: &[ModelPrice],
+    month_of: impl Fn(i64) -> String,
+) -> Vec<MonthlyCost> {
+    let mut months: Vec<(String, Vec<ProviderCost>)> = Vec::new();
+    for record in records {
+        let month = month_of(record.recorded_at);
+        let index = match months.iter().position(|(key, _)| *key == month) {
+            Some(index) => index,
+            None => {
+                months.push((month, Vec::new()));
+                months.len() - 1
+            }
+        };
+        let entries = &mut months[index].1;
+
+        let info = providers.get(&(record.app_type.clone(), record.provider_id.clone()));
+        let model = record
+            .model
+            .clone()
+            .or_else(|| info.and_then(|info| info.model.clone()));
+        let price = model.as_deref().and_then(|model| find_price(table, model));
+        let (reported, estimated, unpriced) = record_cost(record, price);
+
+        let entry = match entries.iter().position(|entry| {
+            entry.app_type == record.app_type && entry.provider_id == record.provider_id
+        }) {
+            Some(index) => &mut entries[index],
+            None => {
+                entries.push(ProviderCost {
+                    app_type: record.app_type.clone(),
+                    provider_id: record.provider_id.clone(),
+                    provider_name: info.map(|info| info.name.clone()),
+                    ..Default::default()
+                });
+                entries.last_mut().expect("just pushed")
+            }
+        };
+        entry.tokens += record.tokens;
+        entry.reported_cost += reported;
+        entry.estimated_cost += estimated;
+        entry.total_cost += reported + estimated;
+        entry.unpriced_tokens += unpriced;
+        if model.is_some() {
+            entry.model = model;
+        }
+    }
+
+    let mut result: Vec<MonthlyCost> = months
+        .into_iter()
+        .map(|(month, mut providers)| {
+            providers.sort_by(|a, b| b.total_cost.total_cmp(&a.total_cost));
+            MonthlyCost {
+                month,
+                total_cost: providers.iter().map(|p| p.total_cost).sum(),
+                providers,
+            }
+        })
+        .collect();
+    result.sort_by(|a, b| b.month.cmp(&a.month));
+    result
+}
+
+/// `months` 个月前（含本月）的月初 00:00（本地时区）
+fn months_start(months: u32) -> i64 {
+    let today = Local::now().date_naive();
+    let first = today.with_day(1).unwrap_or(today);
+    let start = first
+        .checked_sub_months(Months::new(months.saturating_sub(1)))
+        .unwrap_or(first);
+    start
+        .and_hms_opt(0, 0, 0)
+        .and_then(|dt| Local.from_local_datetime(&dt).earliest())
+        .map(|dt| dt.timestamp())
+        .unwrap_or(0)
+}
+
+fn month_key(timestamp: i64) -> String {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|dt| dt.format("%Y-%m").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(provider_id: &str, recorded_at: i64, tokens: i64, cost: f64) -> UsageRecord {
+        UsageRecord {
+            provider_id: provider_id.to_string(),
+            app_type: "claude".to_string(),
+            recorded_at,
+            tokens,
+            cost,
+            input_tokens: 0,
+            output_tokens: 0,
+            model: None,
+            raw: None,
+        }
+    }
+
+    #[test]
+    fn matches_longest_prefix_and_applies_overrides() {
+        let table = merge_pricing(&[ModelPrice {
+            model: "Claude-Sonnet-4-5".to_string(),
+            input: 2.0,
+            output: 10.0,
+        }]);
+        let price = find_price(&table, "anthropic/claude-sonnet-4-5-20250929").expect("price");
+        assert_eq!((price.input, price.output), (2.0, 10.0));
+        let price = find_price(&table, "gpt-5.1-codex-mini").expect("price");
+        assert_eq!(price.model, "gpt-5.1-codex-mini");
+        assert!(find_price(&table, "unknown-model").is_none());
+    }
+
+    #[test]
+    fn aggregates_reported_and_estimated_costs_per_month() {
+        let providers = HashMap::from([(
+            ("claude".to_string(), "relay".to_string()),
+            ProviderInfo {
+                name: "Relay".to_string(),
+                model: Some("claude-sonnet-4-5".to_string()),
+            },
+        )]);
+        let mut split = record("relay", 2, 0, 0.0);
+        split.input_tokens = 1_000_000;
+        split.output_tokens = 100_000;
+        split.model = Some("claude-haiku-4-5".to_string());
+        let records = vec![
+            record("relay", 1, 1_000_000, 0.0),
+            split,
+            record("relay", 3, 500, 1.5),
+            record("gone", 4, 1000, 0.0),
+            record("relay", 100, 1_000_000, 0.0),
+        ];
+
+        let month_of = |ts: i64| if ts < 100 { "2026-09" } else { "2026-10" }.to_string();
+        let table = merge_pricing(&[]);
+        let months = aggregate(&records, &providers, &table, month_of);
+
+        assert_eq!(months.len(), 2);
+        assert_eq!(months[0].month, "2026-10");
+        let september = &months[1];
+        let relay = &september.providers[0];
+        assert_eq!(relay.provider_name.as_deref(), Some("Relay"));
+        assert_eq!(relay.reported_cost, 1.5);
+        // sonnet: 1M 输入 = 3.0；haiku: 1M 输入 + 0.1M 输出 = 1.0 + 0.5
+        assert!((relay.estimated_cost - 4.5).abs() < 1e-9);
+        assert_eq!(relay.model.as_deref(), Some("claude-sonnet-4-5"));
+        let gone = &september.providers[1];
+        assert_eq!(gone.unpriced_tokens, 1000);
+        assert!((september.total_cost - 6.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,282 @@
+//! 本地崩溃报告（默认关闭，不联网）
+//!
+//! 开启 `crashReporting` 后，Rust panic 与返回给前端的命令错误写入应用配置目录下的
+//! `crashes/`：每次 panic 一个 JSON 文件，命令错误追加到 `command-errors.jsonl`。
+//! 内容写入前已脱敏；用户可一键打包为 zip 附在 GitHub issue 中。
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::error::{AppError, ErrorPayload};
+use crate::scrub::scrub_secrets;
+use crate::services::diagnostics::DiagnosticReport;
+
+/// 命令错误日志文件名
+const COMMAND_ERRORS_FILE: &str = "command-errors.jsonl";
+/// 命令错误日志超过该大小时滚动为 `.1`，只保留一份旧文件
+const COMMAND_ERRORS_MAX_SIZE: u64 = 512 * 1024;
+/// 最多保留的 panic 报告数量
+const MAX_PANIC_REPORTS: usize = 50;
+
+/// 崩溃报告目录
+pub fn crash_dir() -> PathBuf {
+    crate::config::get_app_config_dir().join("crashes")
+}
+
+/// 崩溃报告类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CrashReportKind {
+    Panic,
+    CommandErrors,
+}
+
+/// 崩溃报告列表项
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReportEntry {
+    pub file_name: String,
+    pub kind: CrashReportKind,
+    /// 修改时间（Unix 秒）
+    pub modified_at: i64,
+    pub size: u64,
+    /// panic 信息（命令错误日志为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+pub struct CrashReportService;
+
+impl CrashReportService {
+    /// 安装 panic hook（保留原有 hook 的输出）
+    pub fn install_panic_hook() {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if crate::settings::crash_reporting_enabled() {
+                if let Err(e) = record_panic(&crash_dir(), info) {
+                    eprintln!("写入崩溃报告失败: {e}");
+                }
+            }
+            previous(info);
+        }));
+    }
+
+    /// 记录返回给前端的命令错误（未开启时忽略，写入失败只记日志）
+    pub fn record_command_error(payload: &ErrorPayload) {
+        if !crate::settings::crash_reporting_enabled() {
+            return;
+        }
+        if let Err(e) = append_command_error(&crash_dir(), payload) {
+            log::warn!("记录命令错误失败: {e}");
+        }
+    }
+
+    /// 列出崩溃报告（按时间从新到旧）
+    pub fn list(dir: &Path) -> Result<Vec<CrashReportEntry>, AppError> {
+        let mut entries = Vec::new();
+        for path in report_files(dir)? {
+            let meta = fs::metadata(&path).map_err(|e| AppError::io(&path, e))?;
+            let file_name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default()
+                .to_string();
+            let kind = if file_name.starts_with("command-errors") {
+                CrashReportKind::CommandErrors
+            } else {
+                CrashReportKind::Panic
+            };
+            let summary = (kind == CrashReportKind::Panic)
+                .then(|| fs::read_to_string(&path).ok())
+                .flatten()
+                .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+                .and_then(|report| report["message"].as_str().map(str::to_string));
+            let modified_at = meta
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |since| since.as_secs() as i64);
+            entries.push(CrashReportEntry {
+                file_name,
+                kind,
+                modified_at,
+                size: meta.len(),
+                summary,
+            });
+        }
+        entries.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+        Ok(entries)
+    }
+
+    /// 将崩溃报告与自检报告打包为 zip
+    pub fn export_bundle(
+        dir: &Path,
+        report: &DiagnosticReport,
+        target: &Path,
+    ) -> Result<PathBuf, AppError> {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        let file = fs::File::create(target).map_err(|e| AppError::io(target, e))?;
+        let mut writer = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        let zip_err =
+            |e: zip::result::ZipError| AppError::Message(format!("写入崩溃报告压缩包失败: {e}"));
+
+        let report_json = serde_json::to_string_pretty(report)
+            .map_err(|source| AppError::JsonSerialize { source })?;
+        writer
+            .start_file("diagnostics.json", options)
+            .map_err(zip_err)?;
+        writer
+            .write_all(scrub_secrets(&report_json).as_bytes())
+            .map_err(|e| AppError::io(target, e))?;
+
+        for path in report_files(dir)? {
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let content = fs::read(&path).map_err(|e| AppError::io(&path, e))?;
+            writer
+                .start_file(format!("crashes/{name}"), options)
+                .map_err(zip_err)?;
+            writer
+                .write_all(&content)
+                .map_err(|e| AppError::io(target, e))?;
+        }
+
+        writer.finish().map_err(zip_err)?;
+        log::info!("已导出崩溃报告压缩包: {}", target.display());
+        Ok(target.to_path_buf())
+    }
+
+    /// 删除全部崩溃报告
+    pub fn clear(dir: &Path) -> Result<usize, AppError> {
+        let files = report_files(dir)?;
+        for path in &files {
+            fs::remove_file(path).map_err(|e| AppError::io(path, e))?;
+        }
+        Ok(files.len())
+    }
+}
+
+fn record_panic(dir: &Path, info: &PanicHookInfo<'_>) -> std::io::Result<()> {
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_string());
+    let now = chrono::Local::now();
+    let report = json!({
+        "kind": "panic",
+        "timestamp": now.to_rfc3339(),
+        "version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "thread": std::thread::current().name().unwrap_or("<unnamed>"),
+        "message": scrub_secrets(&message),
+        "location": info.location().map(|location| location.to_string()),
+        "backtrace": std::backtrace::Backtrace::force_capture().to_string(),
+    });
+
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!(
+        "panic-{}-{}.json",
+        now.format("%Y%m%d-%H%M%S%.3f"),
+        std::process::id()
+    ));
+    fs::write(path, serde_json::to_vec_pretty(&report)?)?;
+    prune_panic_reports(dir)
+}
+
+/// 只保留最近的 `MAX_PANIC_REPORTS` 份 panic 报告（文件名含时间，按名称排序即按时间）
+fn prune_panic_reports(dir: &Path) -> std::io::Result<()> {
+    let mut panics: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("panic-"))
+        })
+        .collect();
+    if panics.len() <= MAX_PANIC_REPORTS {
+        return Ok(());
+    }
+    panics.sort();
+    for path in &panics[..panics.len() - MAX_PANIC_REPORTS] {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+fn append_command_error(dir: &Path, payload: &ErrorPayload) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(COMMAND_ERRORS_FILE);
+    if fs::metadata(&path).is_ok_and(|meta| meta.len() > COMMAND_ERRORS_MAX_SIZE) {
+        fs::rename(&path, dir.join(format!("{COMMAND_ERRORS_FILE}.1")))?;
+    }
+    // payload 的 message 与 details 已在构造时脱敏
+    let line = json!({
+        "timestamp": chrono::Local::now().to_rfc3339(),
+        "version": env!("CARGO_PKG_VERSION"),
+        "code": payload.code,
+        "message": payload.message,
+    });
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{line}")
+}
+
+/// 目录下的崩溃报告文件（目录不存在时为空）
+fn report_files(dir: &Path) -> Result<Vec<PathBuf>, AppError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| AppError::io(dir, e))? {
+        let path = entry.map_err(|e| AppError::io(dir, e))?.path();
+        let is_report = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("panic-") || name.starts_with("command-errors"));
+        if path.is_file() && is_report {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn command_errors_are_listed_and_cleared() {
+        let dir = tempdir().expect("tempdir");
+        let payload = ErrorPayload {
+            code: "message".to_string(),
+            message: "写入失败".to_string(),
+            details: None,
+        };
+        append_command_error(dir.path(), &payload).expect("append");
+        append_command_error(dir.path(), &payload).expect("append");
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let entries = CrashReportService::list(dir.path()).expect("list");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].kind, CrashReportKind::CommandErrors);
+        let raw = fs::read_to_string(dir.path().join(COMMAND_ERRORS_FILE)).unwrap();
+        assert_eq!(raw.lines().count(), 2);
+
+        assert_eq!(CrashReportService::clear(dir.path()).expect("clear"), 1);
+        assert!(CrashReportService::list(dir.path()).unwrap().is_empty());
+    }
+}
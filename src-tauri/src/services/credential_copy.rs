@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::services::provider::mask_credential;
+use crate::services::{AppLockService, ClipboardWatchService, ProviderService};
+use crate::store::AppState;
+
+/// 可复制的供应商凭据
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CredentialField {
+    BaseUrl,
+    /// 脱敏的 API Key（仅用于辨认是哪个 Key）
+    MaskedApiKey,
+    ApiKey,
+}
+
+impl CredentialField {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BaseUrl => "base_url",
+            Self::MaskedApiKey => "masked_api_key",
+            Self::ApiKey => "api_key",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [Self::BaseUrl, Self::MaskedApiKey, Self::ApiKey]
+            .into_iter()
+            .find(|field| field.as_str() == value)
+    }
+}
+
+/// 复制结果（不含 Key 原文）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopiedCredential {
+    pub field: CredentialField,
+    pub provider_id: String,
+    pub provider_name: String,
+    /// 展示文本：地址原文或脱敏后的 Key
+    pub preview: String,
+}
+
+/// 将供应商的地址或 API Key 复制到剪贴板，便于配置其他需要相同凭据的工具
+pub struct CredentialCopyService;
+
+impl CredentialCopyService {
+    /// 复制指定供应商（缺省为当前供应商）的凭据
+    ///
+    /// `confirm` 为真且复制 Key 原文时先弹出确认框；用户取消返回 `None`。
+    /// 会弹出原生对话框，需在阻塞线程中调用。
+    pub fn copy(
+        app: &AppHandle,
+        state: &AppState,
+        app_type: &AppType,
+        provider_id: Option<&str>,
+        field: CredentialField,
+        confirm: bool,
+    ) -> Result<Option<CopiedCredential>, AppError> {
+        let provider_id = match provider_id {
+            Some(id) => id.to_string(),
+            None => crate::settings::get_effective_current_provider(&state.db, app_type)?
+                .ok_or_else(|| AppError::localized("credential_copy.no_current"))?,
+        };
+        let provider_name = state
+            .db
+            .get_all_providers(app_type.as_str())?
+            .get(&provider_id)
+            .map(|provider| provider.name.clone())
+            .unwrap_or_else(|| provider_id.clone());
+
+        let (api_key, base_url) = ProviderService::credentials(state, app_type, &provider_id)?;
+        let (text, preview) = match field {
+            CredentialField::BaseUrl => (base_url.clone(), base_url),
+            CredentialField::MaskedApiKey => (mask_credential(&api_key), mask_credential(&api_key)),
+            CredentialField::ApiKey => (api_key.clone(), mask_credential(&api_key)),
+        };
+        if field == CredentialField::ApiKey {
+            AppLockService::ensure_unlocked(&state.db)?;
+            crate::auth::ensure_key_reveal_allowed(&provider_name)?;
+        }
+        if text.trim().is_empty() {
+            return Err(AppError::localized_with(
+                "credential_copy.empty",
+                &[("provider", provider_name)],
+            ));
+        }
+
+        if confirm && field == CredentialField::ApiKey && !Self::confirm_copy(app, &provider_name) {
+            return Ok(None);
+        }
+
+        ClipboardWatchService::write_text(app, &text)?;
+        log::info!(
+            "已复制 {} 供应商 {provider_id} 的 {}",
+            app_type.as_str(),
+            field.as_str()
+        );
+        Ok(Some(CopiedCredential {
+            field,
+            provider_id,
+            provider_name,
+            preview,
+        }))
+    }
+
+    fn confirm_copy(app: &AppHandle, provider_name: &str) -> bool {
+        let args = [("provider", provider_name.to_string())];
+        app.dialog()
+            .message(crate::i18n::tr("credential_copy.confirm.body", &args))
+            .title(crate::i18n::tr("credential_copy.confirm.title", &[]))
+            .kind(MessageDialogKind::Warning)
+            .buttons(MessageDialogButtons::OkCancelCustom(
+                crate::i18n::tr("credential_copy.confirm.ok", &[]),
+                crate::i18n::tr("credential_copy.confirm.cancel", &[]),
+            ))
+            .blocking_show()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_ids_round_trip() {
+        for field in [
+            CredentialField::BaseUrl,
+            CredentialField::MaskedApiKey,
+            CredentialField::ApiKey,
+        ] {
+            assert_eq!(CredentialField::parse(field.as_str()), Some(field));
+        }
+        assert_eq!(CredentialField::parse("password"), None);
+    }
+}
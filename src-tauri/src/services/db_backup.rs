@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::database::Database;
+use crate::error::AppError;
+
+/// 调度器检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 数据库定时备份
+pub struct DbBackupService;
+
+impl DbBackupService {
+    /// 按设置的频率判断是否需要备份，需要时执行一次备份
+    pub fn run_if_due(db: &Database) -> Result<Option<PathBuf>, AppError> {
+        let settings = crate::settings::get_settings();
+        let Some(interval) = settings.db_backup_schedule.interval_secs() else {
+            return Ok(None);
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        let due = Database::latest_db_backup_at()
+            .map(|last| now - last >= interval)
+            .unwrap_or(true);
+        if !due {
+            return Ok(None);
+        }
+
+        let path = db.backup_database_file()?;
+        if let Some(path) = &path {
+            log::info!("已创建定时数据库备份: {}", path.display());
+        }
+        Ok(path)
+    }
+
+    /// 启动后台定时备份任务（启动时检查一次，之后每小时检查）
+    pub fn start_scheduler(db: Arc<Database>) {
+        tauri::async_runtime::spawn(async move {
+            loop {
+                let db_for_task = db.clone();
+                match tauri::async_runtime::spawn_blocking(move || {
+                    Self::run_if_due(&db_for_task)
+                })
+                .await
+                {
+                    Ok(Err(e)) => log::warn!("定时数据库备份失败: {e}"),
+                    Err(e) => log::warn!("定时数据库备份任务异常: {e}"),
+                    Ok(Ok(_)) => {}
+                }
+                tokio::time::sleep(CHECK_INTERVAL).await;
+            }
+        });
+    }
+}
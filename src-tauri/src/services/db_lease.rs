@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter};
+
+use crate::database::{Database, LEASE_TTL_SECS};
+
+/// 心跳间隔：租约有效期的三分之一，保证持有期间不会过期
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs((LEASE_TTL_SECS / 3) as u64);
+
+/// 数据库写入租约心跳
+pub struct DbLeaseService;
+
+impl DbLeaseService {
+    /// 定期续约；只读状态变化时发射 `db-lease-changed` 事件
+    pub fn start_heartbeat(db: Arc<Database>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut read_only = db.is_read_only();
+            loop {
+                tokio::time::sleep(HEARTBEAT_INTERVAL).await;
+                let db_for_task = db.clone();
+                match tauri::async_runtime::spawn_blocking(move || db_for_task.renew_lease()).await
+                {
+                    Ok(Err(e)) => log::warn!("数据库租约续约失败: {e}"),
+                    Err(e) => log::warn!("数据库租约任务异常: {e}"),
+                    Ok(Ok(_)) => {}
+                }
+
+                if db.is_read_only() != read_only {
+                    read_only = db.is_read_only();
+                    match db.lease_status() {
+                        Ok(status) => {
+                            if let Err(e) = app.emit("db-lease-changed", &status) {
+                                log::warn!("发送数据库租约事件失败: {e}");
+                            }
+                        }
+                        Err(e) => log::warn!("读取数据库租约状态失败: {e}"),
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::database::{CompactReport, Database};
+use crate::error::AppError;
+
+/// 启动后首次检查前的等待时间，避开启动高峰
+const INITIAL_DELAY: Duration = Duration::from_secs(10 * 60);
+/// 调度器检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// 两次自动压缩的最小间隔（秒）
+const COMPACT_INTERVAL_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// 数据库维护（定期 VACUUM / ANALYZE）
+pub struct DbMaintenanceService;
+
+impl DbMaintenanceService {
+    /// 距上次压缩超过一周且数据库空闲时执行压缩
+    pub fn run_if_due(db: &Database) -> Result<Option<CompactReport>, AppError> {
+        let now = chrono::Utc::now().timestamp();
+        let due = db
+            .last_compacted_at()?
+            .map(|last| now - last >= COMPACT_INTERVAL_SECS)
+            .unwrap_or(true);
+        if !due {
+            return Ok(None);
+        }
+
+        let report = db.compact_if_idle()?;
+        match &report {
+            Some(report) => log::info!(
+                "数据库压缩完成，回收 {} 字节（{} → {}）",
+                report.reclaimed_bytes,
+                report.size_before,
+                report.size_after
+            ),
+            None => log::debug!("数据库忙碌，跳过本次压缩"),
+        }
+        Ok(report)
+    }
+
+    /// 启动后台维护任务
+    pub fn start_scheduler(db: Arc<Database>) {
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(INITIAL_DELAY).await;
+            loop {
+                let db_for_task = db.clone();
+                match tauri::async_runtime::spawn_blocking(move || {
+                    Self::run_if_due(&db_for_task)
+                })
+                .await
+                {
+                    Ok(Err(e)) => log::warn!("数据库维护失败: {e}"),
+                    Err(e) => log::warn!("数据库维护任务异常: {e}"),
+                    Ok(Ok(_)) => {}
+                }
+                tokio::time::sleep(CHECK_INTERVAL).await;
+            }
+        });
+    }
+}
@@ -0,0 +1,290 @@
+//! 多设备配置
+//!
+//! 数据库通过云盘在多台设备间同步时，各设备的配置目录不同，当前供应商也各自独立。
+//! 这些字段以 `devices` 表中本设备的行为准，设置中的同名字段只是它的缓存；
+//! 设备 ID 保存在本机 `~/.cc-switch/device-id`，不随数据库同步。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::settings::AppSettings;
+
+/// 设备级设置字段：以设备行为准，不写入各设备共享的 `device_settings` 表
+pub(crate) const DEVICE_SCOPED_FIELDS: &[&str] = &[
+    "claudeConfigDir",
+    "codexConfigDir",
+    "geminiConfigDir",
+    "grokConfigDir",
+    "qwenConfigDir",
+    "currentProviderClaude",
+    "currentProviderCodex",
+    "currentProviderGemini",
+    "currentProviderGrok",
+    "currentProviderQwen",
+];
+
+/// 活跃时间的最小更新间隔（秒），避免每次重载设置都写库
+const LAST_SEEN_INTERVAL_SECS: i64 = 3600;
+
+static DEVICE_ID: RwLock<Option<String>> = RwLock::new(None);
+
+/// 一台设备的配置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceRecord {
+    pub id: String,
+    pub name: String,
+    pub hostname: String,
+    /// 应用 → 配置目录覆盖
+    pub config_dirs: BTreeMap<String, String>,
+    /// 应用 → 当前供应商 ID
+    pub current_providers: BTreeMap<String, String>,
+    pub last_seen_at: i64,
+    pub updated_at: i64,
+}
+
+impl DeviceRecord {
+    fn new(id: String, settings: &AppSettings) -> Self {
+        let hostname = hostname();
+        let now = chrono::Utc::now().timestamp();
+        let mut device = Self {
+            id,
+            name: if hostname.is_empty() {
+                "This device".to_string()
+            } else {
+                hostname.clone()
+            },
+            hostname,
+            last_seen_at: now,
+            updated_at: now,
+            ..Default::default()
+        };
+        device.update_from(settings);
+        device
+    }
+
+    /// 用本设备的值覆盖设置中的设备级字段
+    fn apply_to(&self, settings: &mut AppSettings) {
+        for app in AppType::all() {
+            *settings.config_dir_slot(&app) = self.config_dirs.get(app.as_str()).cloned();
+            *settings.current_provider_slot(&app) =
+                self.current_providers.get(app.as_str()).cloned();
+        }
+    }
+
+    /// 从设置读取设备级字段，返回是否有变化
+    fn update_from(&mut self, settings: &AppSettings) -> bool {
+        let mut settings = settings.clone();
+        let mut config_dirs = BTreeMap::new();
+        let mut current_providers = BTreeMap::new();
+        for app in AppType::all() {
+            if let Some(dir) = settings.config_dir_slot(&app).take() {
+                config_dirs.insert(app.as_str().to_string(), dir);
+            }
+            if let Some(id) = settings.current_provider_slot(&app).take() {
+                current_providers.insert(app.as_str().to_string(), id);
+            }
+        }
+        let changed =
+            config_dirs != self.config_dirs || current_providers != self.current_providers;
+        self.config_dirs = config_dirs;
+        self.current_providers = current_providers;
+        changed
+    }
+}
+
+/// 设备列表项
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceInfo {
+    #[serde(flatten)]
+    pub device: DeviceRecord,
+    /// 是否为当前设备
+    pub current: bool,
+}
+
+fn hostname() -> String {
+    sysinfo::System::host_name().unwrap_or_default()
+}
+
+fn device_id_path() -> PathBuf {
+    // 与 settings.json 同目录：不受应用配置目录覆盖影响，避免随数据库同步
+    crate::test_utils::home_dir()
+        .expect("无法获取用户主目录")
+        .join(".cc-switch")
+        .join("device-id")
+}
+
+fn generate_device_id() -> Result<String, AppError> {
+    let mut bytes = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| AppError::Config("生成设备 ID 失败".to_string()))?;
+    let id: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    crate::config::atomic_write(&device_id_path(), id.as_bytes())?;
+    *DEVICE_ID.write().expect("写入设备 ID 锁失败") = Some(id.clone());
+    Ok(id)
+}
+
+/// 当前设备 ID（首次调用时生成并保存）
+pub fn current_device_id() -> Result<String, AppError> {
+    if let Some(id) = DEVICE_ID.read().expect("读取设备 ID 锁失败").clone() {
+        return Ok(id);
+    }
+    let path = device_id_path();
+    if let Ok(raw) = fs::read_to_string(&path) {
+        let id = raw.trim().to_string();
+        if !id.is_empty() {
+            *DEVICE_ID.write().expect("写入设备 ID 锁失败") = Some(id.clone());
+            return Ok(id);
+        }
+    }
+    generate_device_id()
+}
+
+pub struct DeviceService;
+
+impl DeviceService {
+    /// 将本设备的行与设置合并
+    ///
+    /// 行存在时以行为准；不存在时（首次启用或新设备）用设置中的值创建。
+    /// 行的主机名与本机不同说明设备 ID 随目录被复制到了另一台设备，
+    /// 此时为本机重新生成 ID，不覆盖原设备的配置。
+    pub(crate) fn sync_settings(db: &Database, settings: &mut AppSettings) -> Result<(), AppError> {
+        let mut id = current_device_id()?;
+        let local_host = hostname();
+        match db.get_device(&id)? {
+            Some(mut device)
+                if device.hostname.is_empty()
+                    || local_host.is_empty()
+                    || device.hostname == local_host =>
+            {
+                device.apply_to(settings);
+                let now = chrono::Utc::now().timestamp();
+                if now - device.last_seen_at >= LAST_SEEN_INTERVAL_SECS {
+                    device.last_seen_at = now;
+                    db.save_device(&device)?;
+                }
+                return Ok(());
+            }
+            Some(device) => {
+                log::warn!(
+                    "设备 ID {id} 已属于 {}（{}），为本机重新生成",
+                    device.name,
+                    device.hostname
+                );
+                id = generate_device_id()?;
+            }
+            None => {}
+        }
+
+        let device = DeviceRecord::new(id, settings);
+        db.save_device(&device)?;
+        log::info!("已登记当前设备 {} ({})", device.name, device.id);
+        Ok(())
+    }
+
+    /// 把设置中的设备级字段写入本设备的行
+    pub(crate) fn save_settings(db: &Database, settings: &AppSettings) -> Result<(), AppError> {
+        let id = current_device_id()?;
+        let (mut device, is_new) = match db.get_device(&id)? {
+            Some(device) => (device, false),
+            None => (DeviceRecord::new(id, settings), true),
+        };
+        if device.update_from(settings) {
+            device.updated_at = chrono::Utc::now().timestamp();
+        } else if !is_new {
+            return Ok(());
+        }
+        db.save_device(&device)
+    }
+
+    /// 本设备的行是否已被其他进程修改（与缓存的设置不一致）
+    pub(crate) fn is_stale(db: &Database, settings: &AppSettings) -> Result<bool, AppError> {
+        let Some(device) = db.get_device(&current_device_id()?)? else {
+            return Ok(false);
+        };
+        // 缓存已应用机器策略，比较前对行的值做同样处理
+        let mut expected = settings.clone();
+        device.apply_to(&mut expected);
+        crate::policy::current().apply(&mut expected);
+
+        let mut cached = DeviceRecord::default();
+        cached.update_from(settings);
+        Ok(cached.update_from(&expected))
+    }
+
+    /// 列出所有设备
+    pub fn list(db: &Database) -> Result<Vec<DeviceInfo>, AppError> {
+        let current = current_device_id()?;
+        Ok(db
+            .list_devices()?
+            .into_iter()
+            .map(|device| DeviceInfo {
+                current: device.id == current,
+                device,
+            })
+            .collect())
+    }
+
+    /// 重命名设备
+    pub fn rename(db: &Database, id: &str, name: &str) -> Result<(), AppError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::InvalidInput("设备名称不能为空".to_string()));
+        }
+        if !db.rename_device(id, name)? {
+            return Err(AppError::InvalidInput(format!("设备不存在: {id}")));
+        }
+        Ok(())
+    }
+
+    /// 删除不再使用的设备（不能删除当前设备）
+    pub fn remove(db: &Database, id: &str) -> Result<(), AppError> {
+        if id == current_device_id()? {
+            return Err(AppError::InvalidInput("不能删除当前设备".to_string()));
+        }
+        if !db.delete_device(id)? {
+            return Err(AppError::InvalidInput(format!("设备不存在: {id}")));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn device_values_override_shared_settings() {
+        let mut device = DeviceRecord::default();
+        let local = AppSettings {
+            claude_config_dir: Some("/home/me/.claude".into()),
+            current_provider_codex: Some("work".into()),
+            ..Default::default()
+        };
+        assert!(device.update_from(&local));
+        assert!(!device.update_from(&local));
+
+        // 另一台设备同步过来的共享设置中带有它自己的目录
+        let mut shared = AppSettings {
+            claude_config_dir: Some("C:\\Users\\me\\.claude".into()),
+            ..Default::default()
+        };
+        device.apply_to(&mut shared);
+        assert_eq!(
+            shared.claude_config_dir.as_deref(),
+            Some("/home/me/.claude")
+        );
+        assert_eq!(shared.current_provider_codex.as_deref(), Some("work"));
+        assert!(shared.current_provider_claude.is_none());
+    }
+}
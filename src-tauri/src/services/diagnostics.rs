@@ -0,0 +1,392 @@
+//! 启动自检（Doctor）
+//!
+//! 汇总配置目录权限、各应用 live 配置的存在与解析状态、数据库版本与完整性、
+//! 深链接注册、托盘创建和磁盘空间，生成结构化报告供界面展示或附在问题反馈中。
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::app_config::AppType;
+use crate::database::SCHEMA_VERSION;
+use crate::i18n::tr;
+use crate::services::LiveStateService;
+use crate::store::AppState;
+
+/// 可用空间低于该值时给出警告（字节）
+const DISK_WARNING_BYTES: u64 = 500 * 1024 * 1024;
+/// 可用空间低于该值时视为错误（字节）
+const DISK_ERROR_BYTES: u64 = 50 * 1024 * 1024;
+
+/// 检查结果级别（按严重程度排序）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Skipped,
+    Warning,
+    Error,
+}
+
+/// 检查项分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckCategory {
+    ConfigDir,
+    LiveConfig,
+    Database,
+    DeepLink,
+    Tray,
+    Disk,
+}
+
+/// 单个检查项
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticCheck {
+    /// 稳定标识（如 `configDir.claude`、`liveConfig.codex.auth.json`）
+    pub id: String,
+    pub category: CheckCategory,
+    pub status: CheckStatus,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl DiagnosticCheck {
+    fn new(
+        id: impl Into<String>,
+        category: CheckCategory,
+        status: CheckStatus,
+        message: String,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            category,
+            status,
+            // 解析错误可能带出配置文件中的密钥片段
+            message: crate::scrub::scrub_secrets(&message),
+            path: None,
+        }
+    }
+
+    fn with_path(mut self, path: &Path) -> Self {
+        self.path = Some(path.display().to_string());
+        self
+    }
+}
+
+/// 自检报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticReport {
+    /// 生成时间（Unix 秒）
+    pub generated_at: i64,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    /// 所有检查项中最严重的级别
+    pub overall: CheckStatus,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+/// 只能在界面进程中获取的运行时状态
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuntimeStatus {
+    /// 深链接协议是否已注册（平台不支持查询时为 None）
+    pub deep_link_registered: Option<bool>,
+    pub tray_created: bool,
+}
+
+pub struct DiagnosticsService;
+
+impl DiagnosticsService {
+    /// 执行全部检查
+    pub fn run(state: &AppState, runtime: RuntimeStatus) -> DiagnosticReport {
+        let app_config_dir = crate::config::get_app_config_dir();
+        let mut checks = vec![check_dir("configDir.app", &app_config_dir, true)];
+
+        for (app_type, dir) in app_dirs() {
+            checks.push(check_dir(
+                format!("configDir.{}", app_type.as_str()),
+                &dir,
+                false,
+            ));
+        }
+        for app_type in all_apps() {
+            for path in LiveStateService::live_files(&app_type) {
+                checks.push(check_live_file(&app_type, &path));
+            }
+        }
+
+        checks.extend(check_database(state));
+        checks.push(check_deep_link(runtime.deep_link_registered));
+        checks.push(check_tray(runtime.tray_created));
+        checks.push(check_disk(&app_config_dir));
+
+        let overall = checks
+            .iter()
+            .map(|check| check.status)
+            .max()
+            .unwrap_or(CheckStatus::Ok);
+        DiagnosticReport {
+            generated_at: chrono::Utc::now().timestamp(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            overall,
+            checks,
+        }
+    }
+}
+
+fn all_apps() -> [AppType; 5] {
+    [
+        AppType::Claude,
+        AppType::Codex,
+        AppType::Gemini,
+        AppType::Grok,
+        AppType::Qwen,
+    ]
+}
+
+fn app_dirs() -> Vec<(AppType, PathBuf)> {
+    vec![
+        (AppType::Claude, crate::config::get_claude_config_dir()),
+        (AppType::Codex, crate::codex_config::get_codex_config_dir()),
+        (AppType::Gemini, crate::gemini_config::get_gemini_dir()),
+        (AppType::Grok, crate::grok_config::get_grok_dir()),
+        (AppType::Qwen, crate::qwen_config::get_qwen_dir()),
+    ]
+}
+
+/// 检查目录是否存在且可写（通过创建临时文件验证）
+///
+/// `required` 为 false 时目录缺失只记为跳过（对应 CLI 可能未安装）。
+fn check_dir(id: impl Into<String>, dir: &Path, required: bool) -> DiagnosticCheck {
+    let (status, message) = if !dir.is_dir() {
+        let status = if required {
+            CheckStatus::Error
+        } else {
+            CheckStatus::Skipped
+        };
+        (status, tr("diagnostics.dir.missing", &[]))
+    } else {
+        match tempfile::NamedTempFile::new_in(dir) {
+            Ok(_) => (CheckStatus::Ok, tr("diagnostics.dir.writable", &[])),
+            Err(e) => (
+                CheckStatus::Error,
+                tr("diagnostics.dir.not_writable", &[("e", e.to_string())]),
+            ),
+        }
+    };
+    DiagnosticCheck::new(id, CheckCategory::ConfigDir, status, message).with_path(dir)
+}
+
+/// 检查 live 配置文件是否存在并能按格式解析
+fn check_live_file(app_type: &AppType, path: &Path) -> DiagnosticCheck {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let id = format!("liveConfig.{}.{file_name}", app_type.as_str());
+
+    let (status, message) = match std::fs::read_to_string(path) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            (CheckStatus::Skipped, tr("diagnostics.live.missing", &[]))
+        }
+        Err(e) => (
+            CheckStatus::Error,
+            tr("diagnostics.live.unreadable", &[("e", e.to_string())]),
+        ),
+        Ok(content) => match parse_error(path, &content) {
+            None => (CheckStatus::Ok, tr("diagnostics.live.ok", &[])),
+            Some(e) => (
+                CheckStatus::Error,
+                tr("diagnostics.live.invalid", &[("e", e)]),
+            ),
+        },
+    };
+    DiagnosticCheck::new(id, CheckCategory::LiveConfig, status, message).with_path(path)
+}
+
+/// 按扩展名解析文件内容，返回解析错误（`.env` 等其他格式不做校验）
+fn parse_error(path: &Path, content: &str) -> Option<String> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str::<serde_json::Value>(content)
+            .err()
+            .map(|e| e.to_string()),
+        Some("toml") => toml::from_str::<toml::Table>(content)
+            .err()
+            .map(|e| e.to_string()),
+        _ => None,
+    }
+}
+
+fn check_database(state: &AppState) -> Vec<DiagnosticCheck> {
+    let version = match state.db.schema_version() {
+        Ok(version) if version > SCHEMA_VERSION => DiagnosticCheck::new(
+            "database.schemaVersion",
+            CheckCategory::Database,
+            CheckStatus::Warning,
+            tr(
+                "diagnostics.db.version_newer",
+                &[
+                    ("version", version.to_string()),
+                    ("supported", SCHEMA_VERSION.to_string()),
+                ],
+            ),
+        ),
+        Ok(version) => DiagnosticCheck::new(
+            "database.schemaVersion",
+            CheckCategory::Database,
+            CheckStatus::Ok,
+            tr(
+                "diagnostics.db.version",
+                &[
+                    ("version", version.to_string()),
+                    ("supported", SCHEMA_VERSION.to_string()),
+                ],
+            ),
+        ),
+        Err(e) => DiagnosticCheck::new(
+            "database.schemaVersion",
+            CheckCategory::Database,
+            CheckStatus::Error,
+            e.to_string(),
+        ),
+    };
+
+    let health = match state.db.check_health(false) {
+        Ok(report) if report.is_healthy() => DiagnosticCheck::new(
+            "database.health",
+            CheckCategory::Database,
+            CheckStatus::Ok,
+            tr("diagnostics.db.healthy", &[]),
+        ),
+        Ok(report) => {
+            let integrity_issues = if report.integrity_ok {
+                0
+            } else {
+                report.integrity_messages.len().max(1)
+            };
+            let count = integrity_issues
+                + report.foreign_key_violations.len()
+                + report.orphan_endpoints.len()
+                + report.corrupt_json.len();
+            DiagnosticCheck::new(
+                "database.health",
+                CheckCategory::Database,
+                CheckStatus::Warning,
+                tr("diagnostics.db.issues", &[("count", count.to_string())]),
+            )
+        }
+        Err(e) => DiagnosticCheck::new(
+            "database.health",
+            CheckCategory::Database,
+            CheckStatus::Error,
+            e.to_string(),
+        ),
+    };
+
+    let mut checks = vec![version, health];
+    if state.db.is_read_only() {
+        checks.push(DiagnosticCheck::new(
+            "database.lease",
+            CheckCategory::Database,
+            CheckStatus::Warning,
+            tr("diagnostics.db.read_only", &[]),
+        ));
+    }
+    checks
+}
+
+fn check_deep_link(registered: Option<bool>) -> DiagnosticCheck {
+    let (status, key) = match registered {
+        Some(true) => (CheckStatus::Ok, "diagnostics.deeplink.registered"),
+        Some(false) => (CheckStatus::Warning, "diagnostics.deeplink.not_registered"),
+        None => (CheckStatus::Skipped, "diagnostics.deeplink.unknown"),
+    };
+    DiagnosticCheck::new("deepLink", CheckCategory::DeepLink, status, tr(key, &[]))
+}
+
+fn check_tray(created: bool) -> DiagnosticCheck {
+    let (status, key) = if created {
+        (CheckStatus::Ok, "diagnostics.tray.created")
+    } else {
+        (CheckStatus::Warning, "diagnostics.tray.missing")
+    };
+    DiagnosticCheck::new("tray", CheckCategory::Tray, status, tr(key, &[]))
+}
+
+fn check_disk(dir: &Path) -> DiagnosticCheck {
+    let (status, message) = match fs2::available_space(dir) {
+        Ok(bytes) => {
+            let space = format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0));
+            if bytes < DISK_ERROR_BYTES {
+                (
+                    CheckStatus::Error,
+                    tr("diagnostics.disk.low", &[("space", space)]),
+                )
+            } else if bytes < DISK_WARNING_BYTES {
+                (
+                    CheckStatus::Warning,
+                    tr("diagnostics.disk.low", &[("space", space)]),
+                )
+            } else {
+                (
+                    CheckStatus::Ok,
+                    tr("diagnostics.disk.ok", &[("space", space)]),
+                )
+            }
+        }
+        Err(e) => (
+            CheckStatus::Skipped,
+            tr("diagnostics.disk.unknown", &[("e", e.to_string())]),
+        ),
+    };
+    DiagnosticCheck::new("disk", CheckCategory::Disk, status, message).with_path(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn live_file_check_reports_parse_errors() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let valid = dir.path().join("settings.json");
+        let invalid = dir.path().join("config.toml");
+        std::fs::write(&valid, r#"{ "env": {} }"#).expect("write json");
+        std::fs::write(&invalid, "model = ").expect("write toml");
+
+        assert_eq!(
+            check_live_file(&AppType::Claude, &valid).status,
+            CheckStatus::Ok
+        );
+        let check = check_live_file(&AppType::Codex, &invalid);
+        assert_eq!(check.status, CheckStatus::Error);
+        assert_eq!(check.id, "liveConfig.codex.config.toml");
+        assert_eq!(
+            check_live_file(&AppType::Claude, &dir.path().join("missing.json")).status,
+            CheckStatus::Skipped
+        );
+    }
+
+    #[test]
+    fn missing_optional_dir_is_skipped() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert_eq!(
+            check_dir("configDir.app", dir.path(), true).status,
+            CheckStatus::Ok
+        );
+        let missing = dir.path().join("absent");
+        assert_eq!(
+            check_dir("configDir.claude", &missing, false).status,
+            CheckStatus::Skipped
+        );
+        assert_eq!(
+            check_dir("configDir.app", &missing, true).status,
+            CheckStatus::Error
+        );
+    }
+}
@@ -32,7 +32,7 @@ pub fn check_env_conflicts(app: &str) -> Result<Vec<EnvConflict>, String> {
 }
 
 /// Get relevant keywords for each app
-fn get_keywords_for_app(app: &str) -> Vec<&str> {
+pub(crate) fn get_keywords_for_app(app: &str) -> Vec<&str> {
     match app.to_lowercase().as_str() {
         "claude" => vec!["ANTHROPIC"],
         "codex" => vec!["OPENAI"],
@@ -100,7 +100,7 @@ fn check_system_env(keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
 
 /// Check shell configuration files for environment variable exports (Unix only)
 #[cfg(not(target_os = "windows"))]
-fn check_shell_configs(keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
+pub(crate) fn check_shell_configs(keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
     let mut conflicts = Vec::new();
 
     let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
@@ -151,6 +151,12 @@ fn check_shell_configs(keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
     Ok(conflicts)
 }
 
+/// Shell configuration files are not used on Windows
+#[cfg(target_os = "windows")]
+pub(crate) fn check_shell_configs(_keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
+    Ok(Vec::new())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
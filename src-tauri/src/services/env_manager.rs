@@ -1,6 +1,7 @@
 use super::env_checker::EnvConflict;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -9,6 +10,10 @@ use winreg::enums::*;
 #[cfg(target_os = "windows")]
 use winreg::RegKey;
 
+/// Prefix of shell configuration lines disabled by `comment_out_env_vars`
+#[cfg(not(target_os = "windows"))]
+const DISABLED_PREFIX: &str = "# [cc-switch disabled] ";
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BackupInfo {
@@ -28,6 +33,7 @@ pub fn delete_env_vars(conflicts: Vec<EnvConflict>) -> Result<BackupInfo, String
             Ok(_) => {}
             Err(e) => {
                 // If deletion fails, we keep the backup but return error
+                notify_env_changed();
                 return Err(format!(
                     "删除环境变量失败: {}. 备份已保存到: {}",
                     e, backup_info.backup_path
@@ -36,9 +42,168 @@ pub fn delete_env_vars(conflicts: Vec<EnvConflict>) -> Result<BackupInfo, String
         }
     }
 
+    // Step 3: Let running programs (Explorer, new terminals) pick up the change
+    notify_env_changed();
+
     Ok(backup_info)
 }
 
+/// Update user-level environment variables of an app to the switched-to provider (Windows only)
+///
+/// Only variables that already exist in `HKEY_CURRENT_USER\Environment` and match the
+/// app's keywords are touched: they are set to the value in `values`, or removed when
+/// the provider does not define them, so they stop overriding the live settings.
+/// Machine-level variables need administrator rights and are only reported by
+/// `check_env_conflicts`. Returns the backup of the previous values, or `None` when
+/// nothing changed.
+#[cfg(target_os = "windows")]
+pub fn sync_user_env_vars(
+    app: &str,
+    values: &HashMap<String, String>,
+) -> Result<Option<BackupInfo>, String> {
+    let keywords = super::env_checker::get_keywords_for_app(app);
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey_with_flags("Environment", KEY_ALL_ACCESS)
+        .map_err(|e| format!("打开注册表失败: {}", e))?;
+
+    let changed: Vec<EnvConflict> = hkcu
+        .enum_values()
+        .filter_map(Result::ok)
+        .filter(|(name, _)| keywords.iter().any(|k| name.to_uppercase().contains(k)))
+        .filter(|(name, value)| values.get(name) != Some(&value.to_string()))
+        .map(|(name, value)| EnvConflict {
+            var_name: name,
+            var_value: value.to_string(),
+            source_type: "system".to_string(),
+            source_path: "HKEY_CURRENT_USER\\Environment".to_string(),
+        })
+        .collect();
+    if changed.is_empty() {
+        return Ok(None);
+    }
+
+    let backup_info = create_backup(&changed)?;
+    let result = changed.iter().try_for_each(|conflict| {
+        match values.get(&conflict.var_name) {
+            Some(value) => hkcu.set_value(&conflict.var_name, value),
+            None => hkcu.delete_value(&conflict.var_name),
+        }
+        .map_err(|e| {
+            format!(
+                "更新注册表项 {} 失败: {}. 备份已保存到: {}",
+                conflict.var_name, e, backup_info.backup_path
+            )
+        })
+    });
+    notify_env_changed();
+    result.map(|_| Some(backup_info))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn sync_user_env_vars(
+    _app: &str,
+    _values: &HashMap<String, String>,
+) -> Result<Option<BackupInfo>, String> {
+    Ok(None)
+}
+
+/// Broadcast `WM_SETTINGCHANGE` so other programs reload the environment block
+#[cfg(target_os = "windows")]
+fn notify_env_changed() {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        SendMessageTimeoutW, HWND_BROADCAST, SMTO_ABORTIFHUNG, WM_SETTINGCHANGE,
+    };
+
+    let area: Vec<u16> = "Environment".encode_utf16().chain(Some(0)).collect();
+    let mut result = 0;
+    // A hung top-level window would otherwise block the switch
+    unsafe {
+        SendMessageTimeoutW(
+            HWND_BROADCAST,
+            WM_SETTINGCHANGE,
+            0,
+            area.as_ptr() as isize,
+            SMTO_ABORTIFHUNG,
+            5000,
+            &mut result,
+        );
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn notify_env_changed() {}
+
+/// Comment out environment variable lines in shell configuration files with automatic backup
+///
+/// Unlike `delete_env_vars` the original lines stay in place, prefixed with a marker,
+/// so restoring the backup re-enables them. Entries not coming from a file are ignored.
+#[cfg(not(target_os = "windows"))]
+pub fn comment_out_env_vars(conflicts: Vec<EnvConflict>) -> Result<BackupInfo, String> {
+    let conflicts: Vec<EnvConflict> = conflicts
+        .into_iter()
+        .filter(|conflict| conflict.source_type == "file")
+        .collect();
+    let backup_info = create_backup(&conflicts)?;
+
+    for conflict in &conflicts {
+        comment_out_single_env(conflict).map_err(|e| {
+            format!(
+                "注释环境变量失败: {}. 备份已保存到: {}",
+                e, backup_info.backup_path
+            )
+        })?;
+    }
+
+    Ok(backup_info)
+}
+
+/// Shell configuration files are not used on Windows
+#[cfg(target_os = "windows")]
+pub fn comment_out_env_vars(_conflicts: Vec<EnvConflict>) -> Result<BackupInfo, String> {
+    Err("Windows 系统不支持注释 Shell 配置中的环境变量".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn comment_out_single_env(conflict: &EnvConflict) -> Result<(), String> {
+    // source_path format: "path:line"
+    let (file_path, line_num) = conflict
+        .source_path
+        .rsplit_once(':')
+        .and_then(|(path, line)| Some((path, line.parse::<usize>().ok()?)))
+        .ok_or("无效的文件路径格式")?;
+
+    let content =
+        fs::read_to_string(file_path).map_err(|e| format!("读取文件失败 {file_path}: {e}"))?;
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let line = line_num
+        .checked_sub(1)
+        .and_then(|index| lines.get_mut(index))
+        .ok_or_else(|| format!("文件已变化，找不到第 {line_num} 行: {file_path}"))?;
+    if !sets_env_var(line, &conflict.var_name) {
+        return Err(format!(
+            "文件已变化，第 {line_num} 行不再设置 {}: {file_path}",
+            conflict.var_name
+        ));
+    }
+    *line = format!("{DISABLED_PREFIX}{line}");
+
+    let mut new_content = lines.join("\n");
+    if content.ends_with('\n') {
+        new_content.push('\n');
+    }
+    fs::write(file_path, new_content).map_err(|e| format!("写入文件失败 {file_path}: {e}"))
+}
+
+/// Whether a shell line (`export VAR=value` or `VAR=value`) sets `var_name`
+#[cfg(not(target_os = "windows"))]
+fn sets_env_var(line: &str, var_name: &str) -> bool {
+    let trimmed = line.trim();
+    let export_line = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+    export_line
+        .split_once('=')
+        .is_some_and(|(name, _)| name.trim() == var_name)
+}
+
 /// Create backup file before deletion
 fn create_backup(conflicts: &[EnvConflict]) -> Result<BackupInfo, String> {
     // Get backup directory
@@ -158,11 +323,13 @@ pub fn restore_from_backup(backup_path: String) -> Result<(), String> {
         serde_json::from_str(&content).map_err(|e| format!("解析备份文件失败: {e}"))?;
 
     // Restore each variable
-    for conflict in &backup_info.conflicts {
-        restore_single_env(conflict)?;
-    }
+    let result = backup_info
+        .conflicts
+        .iter()
+        .try_for_each(restore_single_env);
+    notify_env_changed();
 
-    Ok(())
+    result
 }
 
 /// Restore a single environment variable
@@ -212,6 +379,27 @@ fn restore_single_env(conflict: &EnvConflict) -> Result<(), String> {
             let mut content = fs::read_to_string(file_path)
                 .map_err(|e| format!("读取文件失败 {file_path}: {e}"))?;
 
+            // Re-enable lines disabled by comment_out_env_vars
+            let mut uncommented = false;
+            let lines: Vec<String> = content
+                .lines()
+                .map(|line| match line.strip_prefix(DISABLED_PREFIX) {
+                    Some(original) if sets_env_var(original, &conflict.var_name) => {
+                        uncommented = true;
+                        original.to_string()
+                    }
+                    _ => line.to_string(),
+                })
+                .collect();
+            if uncommented {
+                let mut new_content = lines.join("\n");
+                if content.ends_with('\n') {
+                    new_content.push('\n');
+                }
+                return fs::write(file_path, new_content)
+                    .map_err(|e| format!("写入文件失败 {file_path}: {e}"));
+            }
+
             // Append the environment variable line
             let export_line = format!("\nexport {}={}", conflict.var_name, conflict.var_value);
             content.push_str(&export_line);
@@ -237,4 +425,26 @@ mod tests {
         let backup_dir = get_backup_dir();
         assert!(backup_dir.is_ok());
     }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_comment_out_and_restore_line() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let rc = dir.path().join(".zshrc");
+        let original = "alias ll='ls -l'\nexport ANTHROPIC_BASE_URL=https://api.example.com\n";
+        fs::write(&rc, original).expect("write rc");
+        let conflict = EnvConflict {
+            var_name: "ANTHROPIC_BASE_URL".to_string(),
+            var_value: "https://api.example.com".to_string(),
+            source_type: "file".to_string(),
+            source_path: format!("{}:2", rc.display()),
+        };
+
+        comment_out_single_env(&conflict).expect("comment out");
+        let content = fs::read_to_string(&rc).unwrap();
+        assert!(content.contains(&format!("{DISABLED_PREFIX}export ANTHROPIC_BASE_URL=")));
+
+        restore_single_env(&conflict).expect("restore");
+        assert_eq!(fs::read_to_string(&rc).unwrap(), original);
+    }
 }
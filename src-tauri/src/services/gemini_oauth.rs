@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::AppError;
+use crate::gemini_config::{
+    read_active_google_account, read_gemini_oauth_creds, set_active_google_account,
+    write_gemini_oauth_creds, write_google_oauth_settings,
+};
+use crate::provider::Provider;
+use crate::services::AppLockService;
+use crate::store::AppState;
+
+/// Gemini OAuth 账号（不含凭据）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiOAuthAccount {
+    pub id: String,
+    /// 显示名称（如 "个人" / "工作"）
+    pub label: String,
+    /// Google 账号邮箱（取自 google_accounts.json）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    pub created_at: i64,
+    /// 凭据快照最近一次更新时间
+    pub updated_at: i64,
+}
+
+/// 写入 live 配置时使用的凭据快照
+#[derive(Debug, Clone)]
+pub struct GeminiOAuthCredential {
+    pub email: Option<String>,
+    pub creds: Value,
+}
+
+/// Gemini 多 OAuth 账号管理
+pub struct GeminiOAuthService;
+
+impl GeminiOAuthService {
+    /// 列出已保存的账号
+    pub fn list(state: &AppState) -> Result<Vec<GeminiOAuthAccount>, AppError> {
+        state.db.get_gemini_oauth_accounts()
+    }
+
+    /// 将当前 `~/.gemini/oauth_creds.json` 保存为新账号快照
+    ///
+    /// 同一邮箱已存在时更新其凭据，而不是重复创建。
+    pub fn capture_current(state: &AppState, label: &str) -> Result<GeminiOAuthAccount, AppError> {
+        let creds = read_gemini_oauth_creds()?.ok_or_else(|| {
+            AppError::localized("gemini.oauth.creds_missing")
+        })?;
+        Self::validate_creds(&creds)?;
+
+        let email = read_active_google_account();
+        let now = chrono::Utc::now().timestamp();
+        let existing = email.as_ref().and_then(|email| {
+            state
+                .db
+                .get_gemini_oauth_accounts()
+                .ok()?
+                .into_iter()
+                .find(|a| a.email.as_deref() == Some(email.as_str()))
+        });
+
+        let label = label.trim();
+        let account = match existing {
+            Some(mut account) => {
+                if !label.is_empty() {
+                    account.label = label.to_string();
+                }
+                account.updated_at = now;
+                account
+            }
+            None => GeminiOAuthAccount {
+                id: format!("gemini-oauth-{}", chrono::Utc::now().timestamp_millis()),
+                label: if label.is_empty() {
+                    email.clone().unwrap_or_else(|| "Google".to_string())
+                } else {
+                    label.to_string()
+                },
+                email,
+                created_at: now,
+                updated_at: now,
+            },
+        };
+
+        state.db.save_gemini_oauth_account(&account, &creds)?;
+        Ok(account)
+    }
+
+    /// 修改账号显示名称
+    pub fn rename(state: &AppState, id: &str, label: &str) -> Result<bool, AppError> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err(AppError::InvalidInput("账号名称不能为空".to_string()));
+        }
+        state.db.rename_gemini_oauth_account(id, label)
+    }
+
+    /// 删除账号（仍被供应商引用时拒绝删除；受应用锁保护）
+    pub fn delete(state: &AppState, id: &str) -> Result<(), AppError> {
+        AppLockService::ensure_unlocked(&state.db)?;
+        let providers = state.db.get_all_providers("gemini")?;
+        if let Some(provider) = providers
+            .values()
+            .find(|p| Self::linked_account(p) == Some(id))
+        {
+            return Err(AppError::Message(format!(
+                "账号仍被供应商 {} 使用，无法删除",
+                provider.name
+            )));
+        }
+        state.db.delete_gemini_oauth_account(id)
+    }
+
+    /// 直接切换当前 Gemini CLI 使用的 Google 账号（不切换供应商）
+    pub fn activate(state: &AppState, id: &str) -> Result<(), AppError> {
+        let credential = Self::load_credential(state, id)?;
+        Self::apply_credential(&credential)?;
+        write_google_oauth_settings()
+    }
+
+    /// 读取供应商关联账号的凭据快照（未关联时返回 None）
+    pub fn credential_for_provider(
+        state: &AppState,
+        provider: &Provider,
+    ) -> Result<Option<GeminiOAuthCredential>, AppError> {
+        match Self::linked_account(provider) {
+            Some(id) => Self::load_credential(state, id).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// 切换前回填：Gemini CLI 会刷新令牌，将 live 凭据写回当前供应商关联的账号
+    pub fn backfill_from_live(state: &AppState, provider: &Provider) -> Result<(), AppError> {
+        let Some(id) = Self::linked_account(provider) else {
+            return Ok(());
+        };
+        let Some(creds) = read_gemini_oauth_creds()? else {
+            return Ok(());
+        };
+        if Self::validate_creds(&creds).is_err() {
+            return Ok(());
+        }
+
+        let Some(mut account) = state
+            .db
+            .get_gemini_oauth_accounts()?
+            .into_iter()
+            .find(|a| a.id == id)
+        else {
+            return Ok(());
+        };
+
+        // 仅当 live 仍是该账号时回填，避免用户手动登录其他账号后覆盖快照
+        let active = read_active_google_account();
+        if account.email.is_some() && active.is_some() && account.email != active {
+            return Ok(());
+        }
+
+        account.updated_at = chrono::Utc::now().timestamp();
+        state.db.save_gemini_oauth_account(&account, &creds)
+    }
+
+    /// 将凭据快照写入 `~/.gemini`
+    pub(crate) fn apply_credential(credential: &GeminiOAuthCredential) -> Result<(), AppError> {
+        write_gemini_oauth_creds(&credential.creds)?;
+        if let Some(email) = credential.email.as_deref() {
+            set_active_google_account(email)?;
+        }
+        Ok(())
+    }
+
+    fn load_credential(state: &AppState, id: &str) -> Result<GeminiOAuthCredential, AppError> {
+        let account = state
+            .db
+            .get_gemini_oauth_accounts()?
+            .into_iter()
+            .find(|a| a.id == id)
+            .ok_or_else(|| AppError::Message(format!("Gemini OAuth 账号 {id} 不存在")))?;
+        let creds = state.db.get_gemini_oauth_creds(id)?.ok_or_else(|| {
+            AppError::Message(format!("Gemini OAuth 账号 {id} 缺少凭据快照"))
+        })?;
+        Ok(GeminiOAuthCredential {
+            email: account.email,
+            creds,
+        })
+    }
+
+    /// 供应商关联的账号 ID
+    pub(crate) fn linked_account(provider: &Provider) -> Option<&str> {
+        provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.gemini_oauth_account.as_deref())
+            .filter(|id| !id.is_empty())
+    }
+
+    /// 凭据需至少包含 refresh_token 或 access_token
+    fn validate_creds(creds: &Value) -> Result<(), AppError> {
+        let has_token = ["refresh_token", "access_token"].iter().any(|key| {
+            creds
+                .get(key)
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| !s.is_empty())
+        });
+        if has_token {
+            Ok(())
+        } else {
+            Err(AppError::localized("gemini.oauth.creds_invalid"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate_creds_requires_token() {
+        assert!(GeminiOAuthService::validate_creds(&json!({
+            "refresh_token": "1//abc",
+            "token_type": "Bearer"
+        }))
+        .is_ok());
+        assert!(GeminiOAuthService::validate_creds(&json!({ "token_type": "Bearer" })).is_err());
+    }
+
+    #[test]
+    fn delete_is_rejected_while_locked() {
+        let db = std::sync::Arc::new(crate::database::Database::memory().expect("memory db"));
+        db.set_secret("app_lock.passcode", "hash")
+            .expect("set passcode");
+        let state = AppState::new(db);
+
+        let err = GeminiOAuthService::delete(&state, "work").expect_err("locked");
+        assert_eq!(err.code(), "app_lock.locked");
+    }
+}
@@ -0,0 +1,209 @@
+//! Grok 模型列表刷新
+//!
+//! `GrokSettings::default` 内置的模型列表会随 xAI 上新而过时。这里用供应商的
+//! 密钥查询 xAI `/models` 接口，把结果写回供应商配置的 `models` 数组（当前供应商
+//! 同时写入 user-settings.json），并按供应商缓存一段时间，避免频繁请求。
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::services::{ModelService, ProviderService};
+use crate::store::AppState;
+
+/// 缓存有效期（秒）
+const CACHE_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// 按供应商缓存的模型列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedModels {
+    fetched_at: i64,
+    models: Vec<String>,
+}
+
+/// 刷新结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrokModelsRefresh {
+    pub provider_id: String,
+    pub models: Vec<String>,
+    /// 列表获取时间（Unix 秒）
+    pub fetched_at: i64,
+    /// 结果来自未过期的缓存（未请求 API）
+    pub from_cache: bool,
+    /// 供应商配置中的模型列表有变化
+    pub updated: bool,
+}
+
+/// 发起请求所需的信息
+#[derive(Debug, Clone)]
+pub struct GrokModelsRequest {
+    pub provider_id: String,
+    api_key: String,
+    base_url: String,
+    cached: Option<CachedModels>,
+}
+
+pub struct GrokModelsService;
+
+impl GrokModelsService {
+    fn cache_key(provider_id: &str) -> String {
+        format!("grok_models_cache_{provider_id}")
+    }
+
+    fn read_cache(state: &AppState, provider_id: &str) -> Option<CachedModels> {
+        state
+            .db
+            .get_setting(&Self::cache_key(provider_id))
+            .ok()
+            .flatten()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+    }
+
+    /// 确定供应商（默认为当前 Grok 供应商）并读取凭据；`force` 为 false 时带上未过期的缓存
+    pub fn prepare(
+        state: &AppState,
+        provider_id: Option<String>,
+        force: bool,
+    ) -> Result<GrokModelsRequest, AppError> {
+        let provider_id = match provider_id.filter(|id| !id.trim().is_empty()) {
+            Some(id) => id,
+            None => crate::settings::get_effective_current_provider(&state.db, &AppType::Grok)?
+                .ok_or_else(|| AppError::localized("grok.models.no_provider"))?,
+        };
+        let (api_key, base_url) =
+            ProviderService::credentials(state, &AppType::Grok, &provider_id)?;
+        if api_key.trim().is_empty() {
+            return Err(AppError::localized("grok.missing_api_key"));
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let cached = Self::read_cache(state, &provider_id)
+            .filter(|cache| !force && is_fresh(cache.fetched_at, now));
+        Ok(GrokModelsRequest {
+            provider_id,
+            api_key,
+            base_url,
+            cached,
+        })
+    }
+
+    /// 请求 xAI 模型列表（缓存未过期时直接返回缓存）
+    pub async fn fetch(request: &GrokModelsRequest) -> Result<(Vec<String>, i64, bool), AppError> {
+        if let Some(cache) = &request.cached {
+            return Ok((cache.models.clone(), cache.fetched_at, true));
+        }
+        let models =
+            ModelService::fetch_remote(&AppType::Grok, &request.api_key, &request.base_url).await?;
+        let models = chat_models(models);
+        if models.is_empty() {
+            return Err(AppError::localized("grok.models.empty"));
+        }
+        Ok((models, chrono::Utc::now().timestamp(), false))
+    }
+
+    /// 写入缓存并更新供应商配置（当前供应商同时写入 user-settings.json）
+    pub fn apply(
+        state: &AppState,
+        provider_id: &str,
+        models: Vec<String>,
+        fetched_at: i64,
+        from_cache: bool,
+    ) -> Result<GrokModelsRefresh, AppError> {
+        if !from_cache {
+            let cache = CachedModels {
+                fetched_at,
+                models: models.clone(),
+            };
+            let raw =
+                serde_json::to_string(&cache).map_err(|e| AppError::JsonSerialize { source: e })?;
+            state.db.set_setting(&Self::cache_key(provider_id), &raw)?;
+            ModelService::remember(state, &AppType::Grok, &models)?;
+        }
+
+        let mut provider = state
+            .db
+            .get_all_providers(AppType::Grok.as_str())?
+            .shift_remove(provider_id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {provider_id} 不存在")))?;
+        let updated = replace_models(&mut provider.settings_config, &models);
+        if updated {
+            ProviderService::update(state, AppType::Grok, provider)?;
+            log::info!(
+                "已刷新 Grok 供应商 {provider_id} 的模型列表（{} 个）",
+                models.len()
+            );
+        }
+
+        Ok(GrokModelsRefresh {
+            provider_id: provider_id.to_string(),
+            models,
+            fetched_at,
+            from_cache,
+            updated,
+        })
+    }
+}
+
+fn is_fresh(fetched_at: i64, now: i64) -> bool {
+    now >= fetched_at && now - fetched_at < CACHE_TTL_SECS
+}
+
+/// 只保留可用于对话的 grok 模型（排除图像生成等模型），去重保序
+fn chat_models(models: Vec<String>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for model in models {
+        let lower = model.to_lowercase();
+        if !lower.starts_with("grok") || lower.contains("image") || result.contains(&model) {
+            continue;
+        }
+        result.push(model);
+    }
+    result
+}
+
+/// 替换配置中的 `models` 数组（`defaultModel` 保持不变），返回是否有变化
+fn replace_models(settings: &mut Value, models: &[String]) -> bool {
+    let Some(object) = settings.as_object_mut() else {
+        return false;
+    };
+    let new_value = Value::from(models.to_vec());
+    if object.get("models") == Some(&new_value) {
+        return false;
+    }
+    object.insert("models".to_string(), new_value);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn keeps_only_chat_models() {
+        let models = vec![
+            "grok-4".to_string(),
+            "grok-2-image-1212".to_string(),
+            "grok-code-fast-1".to_string(),
+            "grok-4".to_string(),
+            "text-embedding".to_string(),
+        ];
+        assert_eq!(chat_models(models), vec!["grok-4", "grok-code-fast-1"]);
+    }
+
+    #[test]
+    fn replaces_models_and_reports_changes() {
+        let mut settings = json!({ "apiKey": "xai-key", "models": ["grok-3"] });
+        let models = vec!["grok-4".to_string()];
+        assert!(replace_models(&mut settings, &models));
+        assert_eq!(settings["models"], json!(["grok-4"]));
+        assert_eq!(settings["apiKey"], "xai-key");
+        assert!(!replace_models(&mut settings, &models));
+
+        assert!(is_fresh(1_000, 1_000 + CACHE_TTL_SECS - 1));
+        assert!(!is_fresh(1_000, 1_000 + CACHE_TTL_SECS));
+    }
+}
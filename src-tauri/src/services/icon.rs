@@ -0,0 +1,356 @@
+//! 自定义图标库
+//!
+//! 图标存放于应用数据目录下的 `icons/`，每个图标附带同名 `.json` 元数据：
+//! - SVG 经 usvg 解析后重新序列化，脚本、事件属性、外部引用等均被丢弃
+//! - SVG 额外生成同名 `.png` 位图，供托盘等不支持 SVG 的场景使用
+//! - 位图按文件头校验格式
+//! - 内容相同的图标只保存一份（按 SHA-256 去重）
+
+use resvg::{tiny_skia, usvg};
+use ring::digest::{digest, SHA256};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+
+/// 单个图标大小上限（字节）
+pub const MAX_ICON_SIZE: usize = 1024 * 1024;
+/// SVG 位图回退的边长（像素）
+const PNG_FALLBACK_SIZE: u32 = 64;
+/// 元数据中记录内容哈希的字段
+const HASH_FIELD: &str = "sha256";
+
+/// 图标库中的单个图标
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomIcon {
+    pub file_name: String,
+    /// 供前端使用的 `custom://` 路径
+    pub path: String,
+    pub size: u64,
+    /// 是否存在 PNG 位图回退（仅 SVG）
+    pub has_png_fallback: bool,
+    /// 上传时保存的元数据
+    pub metadata: Value,
+}
+
+pub struct IconService;
+
+impl IconService {
+    /// 保存图标，返回 `custom://` 路径
+    ///
+    /// 若图标库中已有内容相同的图标，直接返回已有图标的路径。
+    pub fn save(
+        icons_dir: &Path,
+        file_name: &str,
+        data: &[u8],
+        metadata: Value,
+    ) -> Result<String, AppError> {
+        validate_file_name(file_name)?;
+        if data.len() > MAX_ICON_SIZE {
+            return Err(AppError::localized_with(
+                "icon.too_large",
+                &[("max", format!("{} KB", MAX_ICON_SIZE / 1024))],
+            ));
+        }
+
+        let is_svg = extension_of(file_name) == "svg";
+        let (content, png) = if is_svg {
+            let tree = parse_svg(data)?;
+            let sanitized = tree.to_string(&usvg::WriteOptions::default());
+            let png = render_png(&tree)?;
+            (sanitized.into_bytes(), Some(png))
+        } else {
+            validate_raster(file_name, data)?;
+            (data.to_vec(), None)
+        };
+
+        fs::create_dir_all(icons_dir).map_err(|e| AppError::io(icons_dir, e))?;
+
+        let hash = sha256_hex(&content);
+        if let Some(existing) = Self::find_by_hash(icons_dir, &hash)? {
+            log::info!("图标内容与 {existing} 相同，复用已有图标");
+            return Ok(custom_path(&existing));
+        }
+
+        let file_path = icons_dir.join(file_name);
+        fs::write(&file_path, &content).map_err(|e| AppError::io(&file_path, e))?;
+        if let Some(png) = png {
+            let png_path = png_fallback_path(icons_dir, file_name);
+            fs::write(&png_path, png).map_err(|e| AppError::io(&png_path, e))?;
+        }
+
+        let mut metadata = match metadata {
+            Value::Object(map) => Value::Object(map),
+            _ => Value::Object(Default::default()),
+        };
+        metadata[HASH_FIELD] = Value::String(hash);
+        let metadata_path = metadata_path(icons_dir, file_name);
+        let metadata_str = serde_json::to_string_pretty(&metadata)
+            .map_err(|e| AppError::JsonSerialize { source: e })?;
+        fs::write(&metadata_path, metadata_str).map_err(|e| AppError::io(&metadata_path, e))?;
+
+        Ok(custom_path(file_name))
+    }
+
+    /// 读取图标内容
+    pub fn read(icons_dir: &Path, file_name: &str) -> Result<Vec<u8>, AppError> {
+        validate_file_name(file_name)?;
+        let file_path = icons_dir.join(file_name);
+        if !file_path.is_file() {
+            return Err(AppError::localized_with(
+                "icon.not_found",
+                &[("name", file_name.to_string())],
+            ));
+        }
+        fs::read(&file_path).map_err(|e| AppError::io(&file_path, e))
+    }
+
+    /// 读取托盘可用的位图：SVG 返回其 PNG 回退，其他格式返回原文件
+    pub fn read_raster(icons_dir: &Path, file_name: &str) -> Result<Vec<u8>, AppError> {
+        if extension_of(file_name) == "svg" {
+            validate_file_name(file_name)?;
+            let png_path = png_fallback_path(icons_dir, file_name);
+            if png_path.is_file() {
+                return fs::read(&png_path).map_err(|e| AppError::io(&png_path, e));
+            }
+            // 早期版本未生成回退，按需补齐
+            let png = render_png(&parse_svg(&Self::read(icons_dir, file_name)?)?)?;
+            fs::write(&png_path, &png).map_err(|e| AppError::io(&png_path, e))?;
+            return Ok(png);
+        }
+        Self::read(icons_dir, file_name)
+    }
+
+    /// 列出图标库（按文件名排序）
+    pub fn list(icons_dir: &Path) -> Result<Vec<CustomIcon>, AppError> {
+        let entries = match fs::read_dir(icons_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(AppError::io(icons_dir, e)),
+        };
+
+        let mut icons = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if !path.is_file() || file_name.ends_with(".json") || is_png_fallback(&path) {
+                continue;
+            }
+
+            let metadata = fs::read_to_string(metadata_path(icons_dir, file_name))
+                .ok()
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or(Value::Null);
+            icons.push(CustomIcon {
+                file_name: file_name.to_string(),
+                path: custom_path(file_name),
+                size: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                has_png_fallback: png_fallback_path(icons_dir, file_name).is_file(),
+                metadata,
+            });
+        }
+        icons.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+        Ok(icons)
+    }
+
+    /// 删除图标及其元数据、位图回退
+    pub fn delete(icons_dir: &Path, file_name: &str) -> Result<bool, AppError> {
+        validate_file_name(file_name)?;
+        let mut removed = false;
+        for path in [
+            icons_dir.join(file_name),
+            metadata_path(icons_dir, file_name),
+            png_fallback_path(icons_dir, file_name),
+        ] {
+            if path.exists() {
+                fs::remove_file(&path).map_err(|e| AppError::io(&path, e))?;
+                removed = true;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// 查找内容哈希相同的已有图标
+    fn find_by_hash(icons_dir: &Path, hash: &str) -> Result<Option<String>, AppError> {
+        for icon in Self::list(icons_dir)? {
+            let recorded = icon.metadata.get(HASH_FIELD).and_then(|v| v.as_str());
+            let matches = match recorded {
+                Some(recorded) => recorded == hash,
+                // 早期版本的元数据未记录哈希，直接比较文件内容
+                None => fs::read(icons_dir.join(&icon.file_name))
+                    .map(|bytes| sha256_hex(&bytes) == hash)
+                    .unwrap_or(false),
+            };
+            if matches {
+                return Ok(Some(icon.file_name));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// 拒绝包含路径分隔符或以点开头的文件名，避免越出图标目录
+fn validate_file_name(file_name: &str) -> Result<(), AppError> {
+    let valid = !file_name.is_empty()
+        && !file_name.starts_with('.')
+        && !file_name.contains(['/', '\\', ':'])
+        && !file_name.ends_with(".json");
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::localized_with(
+            "icon.invalid_name",
+            &[("name", file_name.to_string())],
+        ))
+    }
+}
+
+fn extension_of(file_name: &str) -> String {
+    Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+/// 按文件头校验位图格式与扩展名一致
+fn validate_raster(file_name: &str, data: &[u8]) -> Result<(), AppError> {
+    let ext = extension_of(file_name);
+    let valid = match ext.as_str() {
+        "png" => data.starts_with(b"\x89PNG\r\n\x1a\n"),
+        "jpg" | "jpeg" => data.starts_with(&[0xFF, 0xD8, 0xFF]),
+        "gif" => data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a"),
+        "webp" => data.len() > 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP",
+        "ico" => data.starts_with(&[0x00, 0x00, 0x01, 0x00]),
+        _ => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::localized_with(
+            "icon.unsupported_format",
+            &[("name", file_name.to_string())],
+        ))
+    }
+}
+
+/// 解析 SVG；外部图片引用一律忽略（仅保留内联 data URI）
+fn parse_svg(data: &[u8]) -> Result<usvg::Tree, AppError> {
+    let options = usvg::Options {
+        image_href_resolver: usvg::ImageHrefResolver {
+            resolve_data: usvg::ImageHrefResolver::default_data_resolver(),
+            resolve_string: Box::new(|_: &str, _: &usvg::Options<'_>| None),
+        },
+        ..usvg::Options::default()
+    };
+    usvg::Tree::from_data(data, &options)
+        .map_err(|e| AppError::localized_with("icon.invalid_svg", &[("e", e.to_string())]))
+}
+
+/// 将 SVG 等比缩放居中渲染为正方形 PNG
+fn render_png(tree: &usvg::Tree) -> Result<Vec<u8>, AppError> {
+    let size = PNG_FALLBACK_SIZE as f32;
+    let mut pixmap = tiny_skia::Pixmap::new(PNG_FALLBACK_SIZE, PNG_FALLBACK_SIZE)
+        .ok_or_else(|| AppError::Message("创建图标画布失败".to_string()))?;
+
+    let (width, height) = (tree.size().width(), tree.size().height());
+    let scale = (size / width).min(size / height);
+    let transform = tiny_skia::Transform::from_scale(scale, scale)
+        .post_translate((size - width * scale) / 2.0, (size - height * scale) / 2.0);
+    resvg::render(tree, transform, &mut pixmap.as_mut());
+
+    pixmap
+        .encode_png()
+        .map_err(|e| AppError::Message(format!("生成 PNG 图标失败: {e}")))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    digest(&SHA256, bytes)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn custom_path(file_name: &str) -> String {
+    format!("custom://{file_name}")
+}
+
+fn metadata_path(icons_dir: &Path, file_name: &str) -> PathBuf {
+    icons_dir.join(format!("{file_name}.json"))
+}
+
+fn png_fallback_path(icons_dir: &Path, file_name: &str) -> PathBuf {
+    icons_dir.join(format!("{file_name}.png"))
+}
+
+/// `xxx.svg.png` 且同目录存在 `xxx.svg` 时视为位图回退
+fn is_png_fallback(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    name.strip_suffix(".png")
+        .filter(|base| extension_of(base) == "svg")
+        .is_some_and(|base| path.with_file_name(base).is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" viewBox="0 0 10 10" onload="alert(1)">
+        <script>alert(2)</script>
+        <image xlink:href="https://evil.example.com/track.png" width="10" height="10"/>
+        <rect width="10" height="10" fill="#f00"/>
+    </svg>"##;
+
+    #[test]
+    fn save_sanitizes_svg_and_generates_png_fallback() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path =
+            IconService::save(dir.path(), "logo.svg", SVG.as_bytes(), json!({})).expect("save svg");
+        assert_eq!(path, "custom://logo.svg");
+
+        let saved = String::from_utf8(IconService::read(dir.path(), "logo.svg").expect("read"))
+            .expect("utf8");
+        assert!(!saved.contains("script"));
+        assert!(!saved.contains("onload"));
+        assert!(!saved.contains("evil.example.com"));
+
+        let png = IconService::read_raster(dir.path(), "logo.svg").expect("png");
+        assert!(png.starts_with(b"\x89PNG"));
+
+        let icons = IconService::list(dir.path()).expect("list");
+        assert_eq!(icons.len(), 1);
+        assert!(icons[0].has_png_fallback);
+    }
+
+    #[test]
+    fn save_deduplicates_identical_content() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        IconService::save(dir.path(), "a.svg", SVG.as_bytes(), json!({})).expect("save a");
+        let path =
+            IconService::save(dir.path(), "b.svg", SVG.as_bytes(), json!({})).expect("save b");
+        assert_eq!(path, "custom://a.svg");
+        assert_eq!(IconService::list(dir.path()).expect("list").len(), 1);
+
+        assert!(IconService::delete(dir.path(), "a.svg").expect("delete"));
+        assert!(IconService::list(dir.path()).expect("list").is_empty());
+        assert!(!dir.path().join("a.svg.png").exists());
+    }
+
+    #[test]
+    fn save_rejects_traversal_and_fake_raster() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(
+            IconService::save(dir.path(), "../x.png", b"\x89PNG\r\n\x1a\n", json!({})).is_err()
+        );
+        assert!(IconService::save(dir.path(), "x.png", b"<svg/>", json!({})).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+//! API Key 有效性检查
+//!
+//! 对供应商端点发起一次最小的鉴权请求（模型列表），不消耗 token。
+
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::network::NetworkFeature;
+use crate::services::models::ModelService;
+
+/// 检查结果分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyCheckStatus {
+    /// 鉴权通过
+    Valid,
+    /// 401 / 403：Key 无效或无权限
+    Unauthorized,
+    /// 429：被限流或额度用尽
+    RateLimited,
+    /// 端点不提供模型列表接口，无法确认 Key（但网络可达）
+    Unverified,
+    /// 其他 HTTP 错误
+    HttpError,
+    /// 连接失败 / 超时
+    NetworkError,
+    /// 处于离线模式，未发起请求
+    Offline,
+}
+
+/// Key 检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyCheckResult {
+    pub status: KeyCheckStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// API Key 检查服务
+pub struct KeyCheckService;
+
+impl KeyCheckService {
+    /// 使用给定凭据请求模型列表并分类结果
+    ///
+    /// 网络错误等也作为结果返回；只有配置本身不可用（如缺少地址）时才返回 Err。
+    pub async fn verify(
+        app_type: &AppType,
+        api_key: &str,
+        base_url: &str,
+    ) -> Result<KeyCheckResult, AppError> {
+        if api_key.trim().is_empty() {
+            return Err(AppError::localized("key_check.api_key_missing"));
+        }
+        if !crate::network::is_online() {
+            return Ok(KeyCheckResult {
+                status: KeyCheckStatus::Offline,
+                http_status: None,
+                latency_ms: None,
+                message: None,
+            });
+        }
+
+        let client = ModelService::build_client()?;
+        let request = ModelService::models_request(&client, app_type, api_key, base_url)?;
+
+        let start = Instant::now();
+        let result = match crate::network::send_with_retry(NetworkFeature::Models, request).await {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                let message = if response.status().is_success() {
+                    None
+                } else {
+                    response
+                        .text()
+                        .await
+                        .ok()
+                        .map(|body| body.chars().take(200).collect::<String>())
+                        .filter(|body| !body.trim().is_empty())
+                };
+                KeyCheckResult {
+                    status: classify_status(status),
+                    http_status: Some(status),
+                    latency_ms: Some(start.elapsed().as_millis() as u64),
+                    message,
+                }
+            }
+            Err(err) => KeyCheckResult {
+                status: KeyCheckStatus::NetworkError,
+                http_status: None,
+                latency_ms: None,
+                message: Some(if err.is_timeout() {
+                    "请求超时".to_string()
+                } else if err.is_connect() {
+                    "连接失败".to_string()
+                } else {
+                    err.to_string()
+                }),
+            },
+        };
+        Ok(result)
+    }
+}
+
+fn classify_status(status: u16) -> KeyCheckStatus {
+    match status {
+        200..=299 => KeyCheckStatus::Valid,
+        401 | 403 => KeyCheckStatus::Unauthorized,
+        429 => KeyCheckStatus::RateLimited,
+        404 | 405 | 501 => KeyCheckStatus::Unverified,
+        _ => KeyCheckStatus::HttpError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_http_statuses() {
+        assert_eq!(classify_status(200), KeyCheckStatus::Valid);
+        assert_eq!(classify_status(401), KeyCheckStatus::Unauthorized);
+        assert_eq!(classify_status(403), KeyCheckStatus::Unauthorized);
+        assert_eq!(classify_status(429), KeyCheckStatus::RateLimited);
+        assert_eq!(classify_status(404), KeyCheckStatus::Unverified);
+        assert_eq!(classify_status(500), KeyCheckStatus::HttpError);
+    }
+}
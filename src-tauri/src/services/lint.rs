@@ -0,0 +1,407 @@
+//! 配置检查
+//!
+//! 按可配置的规则检查供应商与提示词中的常见配置问题（未配置模型、base URL 使用 http、
+//! API Key 疑似被截断、提示词过长等），只返回警告，不阻止保存。保存供应商或提示词后
+//! 检查对应条目，也可通过 `lint_all` 检查全部配置；规则配置保存在 settings 表中。
+
+use serde::{Deserialize, Serialize};
+use url::{Host, Url};
+
+use crate::app_config::AppType;
+use crate::database::{to_json_string, Database};
+use crate::error::AppError;
+use crate::i18n::tr;
+use crate::prompt::Prompt;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+use super::{ModelService, ProviderService};
+
+/// 规则配置（settings 表键名）
+const LINT_CONFIG_KEY: &str = "lint_rules";
+/// 提示词默认的 token 上限
+const DEFAULT_PROMPT_MAX_TOKENS: u32 = 8000;
+/// 短于该长度的 API Key 视为被截断
+const MIN_API_KEY_LEN: usize = 16;
+
+/// 检查规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LintRule {
+    /// 供应商未配置模型（使用 CLI 默认模型）
+    MissingModel,
+    /// base URL 使用 http（本机地址除外）
+    InsecureBaseUrl,
+    /// API Key 过短或含省略号，疑似复制了脱敏后的值
+    TruncatedApiKey,
+    /// 提示词估算 token 数超过上限
+    PromptTooLong,
+}
+
+impl LintRule {
+    fn severity(self) -> LintSeverity {
+        match self {
+            LintRule::MissingModel => LintSeverity::Info,
+            _ => LintSeverity::Warning,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LintSeverity {
+    Info,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LintTargetKind {
+    Provider,
+    Prompt,
+}
+
+/// 规则配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintConfig {
+    /// 关闭的规则
+    #[serde(default)]
+    pub disabled_rules: Vec<LintRule>,
+    /// 提示词的 token 上限（按字符数估算）
+    #[serde(default = "default_prompt_max_tokens")]
+    pub prompt_max_tokens: u32,
+}
+
+fn default_prompt_max_tokens() -> u32 {
+    DEFAULT_PROMPT_MAX_TOKENS
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            disabled_rules: Vec::new(),
+            prompt_max_tokens: DEFAULT_PROMPT_MAX_TOKENS,
+        }
+    }
+}
+
+impl LintConfig {
+    fn enabled(&self, rule: LintRule) -> bool {
+        !self.disabled_rules.contains(&rule)
+    }
+}
+
+/// 单条检查警告
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintWarning {
+    pub rule: LintRule,
+    pub severity: LintSeverity,
+    pub kind: LintTargetKind,
+    pub app: String,
+    pub target_id: String,
+    pub target_name: String,
+    /// 按当前界面语言生成的说明
+    pub message: String,
+}
+
+/// 单个条目的检查结果（保存后通过 `lint-warnings` 事件发送，警告为空时前端清除标记）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub kind: LintTargetKind,
+    pub app: String,
+    pub target_id: String,
+    pub warnings: Vec<LintWarning>,
+}
+
+/// 估算文本的 token 数：ASCII 约 4 个字符一个 token，其他字符（如中日文）按一字一个计
+pub fn estimate_tokens(text: &str) -> usize {
+    let ascii = text.chars().filter(char::is_ascii).count();
+    let other = text.chars().count() - ascii;
+    ascii.div_ceil(4) + other
+}
+
+/// base URL 是否为非本机的 http 地址
+fn is_insecure_url(base_url: &str) -> bool {
+    let Ok(url) = Url::parse(base_url.trim()) else {
+        return false;
+    };
+    if url.scheme() != "http" {
+        return false;
+    }
+    match url.host() {
+        Some(Host::Domain(domain)) => !domain.eq_ignore_ascii_case("localhost"),
+        Some(Host::Ipv4(ip)) => !ip.is_loopback(),
+        Some(Host::Ipv6(ip)) => !ip.is_loopback(),
+        None => false,
+    }
+}
+
+/// API Key 是否疑似被截断（环境变量引用不检查）
+fn looks_truncated(api_key: &str) -> bool {
+    let key = api_key.trim();
+    if key.is_empty() || key.starts_with('$') {
+        return false;
+    }
+    if key.contains('…') || key.contains("...") || key.contains('*') {
+        return true;
+    }
+    if key.starts_with("AIza") {
+        return key.len() != 39;
+    }
+    if key.starts_with("sk-ant-") {
+        return key.len() < 40;
+    }
+    key.len() < MIN_API_KEY_LEN
+}
+
+/// 配置检查服务
+pub struct LintService;
+
+impl LintService {
+    /// 当前规则配置
+    pub fn get_config(db: &Database) -> Result<LintConfig, AppError> {
+        Ok(db
+            .get_setting(LINT_CONFIG_KEY)?
+            .and_then(|value| serde_json::from_str(&value).ok())
+            .unwrap_or_default())
+    }
+
+    pub fn save_config(db: &Database, config: &LintConfig) -> Result<(), AppError> {
+        if config.prompt_max_tokens == 0 {
+            return Err(AppError::InvalidInput(
+                "提示词 token 上限必须大于 0".to_string(),
+            ));
+        }
+        db.set_setting(LINT_CONFIG_KEY, &to_json_string(config)?)
+    }
+
+    /// 检查所有应用的供应商与提示词
+    pub fn lint_all(state: &AppState) -> Result<Vec<LintWarning>, AppError> {
+        let config = Self::get_config(&state.db)?;
+        let mut warnings = Vec::new();
+        for app in AppType::all() {
+            for provider in state.db.get_all_providers(app.as_str())?.values() {
+                warnings.extend(Self::lint_provider(&config, &app, provider));
+            }
+            for prompt in state.db.get_prompts(app.as_str())?.values() {
+                warnings.extend(Self::lint_prompt(&config, &app, prompt));
+            }
+        }
+        Ok(warnings)
+    }
+
+    /// 检查已保存的供应商（读取数据库中的值，避免把前端的脱敏 Key 误判为截断）
+    pub fn lint_saved_provider(
+        state: &AppState,
+        app: &AppType,
+        id: &str,
+    ) -> Result<LintReport, AppError> {
+        let config = Self::get_config(&state.db)?;
+        let warnings = state
+            .db
+            .get_all_providers(app.as_str())?
+            .get(id)
+            .map(|provider| Self::lint_provider(&config, app, provider))
+            .unwrap_or_default();
+        Ok(LintReport {
+            kind: LintTargetKind::Provider,
+            app: app.as_str().to_string(),
+            target_id: id.to_string(),
+            warnings,
+        })
+    }
+
+    /// 检查已保存的提示词
+    pub fn lint_saved_prompt(
+        state: &AppState,
+        app: &AppType,
+        id: &str,
+    ) -> Result<LintReport, AppError> {
+        let config = Self::get_config(&state.db)?;
+        let warnings = state
+            .db
+            .get_prompts(app.as_str())?
+            .get(id)
+            .map(|prompt| Self::lint_prompt(&config, app, prompt))
+            .unwrap_or_default();
+        Ok(LintReport {
+            kind: LintTargetKind::Prompt,
+            app: app.as_str().to_string(),
+            target_id: id.to_string(),
+            warnings,
+        })
+    }
+
+    pub fn lint_provider(
+        config: &LintConfig,
+        app: &AppType,
+        provider: &Provider,
+    ) -> Vec<LintWarning> {
+        let mut found = Vec::new();
+
+        if config.enabled(LintRule::MissingModel)
+            && ModelService::configured_model(app, &provider.settings_config).is_none()
+        {
+            found.push((LintRule::MissingModel, tr("lint.missing_model", &[])));
+        }
+
+        // 凭证不完整时由切换时的校验报错，这里不重复提示
+        if let Ok((api_key, base_url)) = ProviderService::extract_credentials(provider, app) {
+            if config.enabled(LintRule::InsecureBaseUrl) && is_insecure_url(&base_url) {
+                found.push((
+                    LintRule::InsecureBaseUrl,
+                    tr("lint.insecure_base_url", &[("url", base_url.clone())]),
+                ));
+            }
+            if config.enabled(LintRule::TruncatedApiKey) && looks_truncated(&api_key) {
+                found.push((
+                    LintRule::TruncatedApiKey,
+                    tr(
+                        "lint.truncated_api_key",
+                        &[("length", api_key.trim().chars().count().to_string())],
+                    ),
+                ));
+            }
+        }
+
+        found
+            .into_iter()
+            .map(|(rule, message)| LintWarning {
+                rule,
+                severity: rule.severity(),
+                kind: LintTargetKind::Provider,
+                app: app.as_str().to_string(),
+                target_id: provider.id.clone(),
+                target_name: provider.name.clone(),
+                message,
+            })
+            .collect()
+    }
+
+    pub fn lint_prompt(config: &LintConfig, app: &AppType, prompt: &Prompt) -> Vec<LintWarning> {
+        if !config.enabled(LintRule::PromptTooLong) {
+            return Vec::new();
+        }
+        let tokens = estimate_tokens(&prompt.content);
+        if tokens <= config.prompt_max_tokens as usize {
+            return Vec::new();
+        }
+        vec![LintWarning {
+            rule: LintRule::PromptTooLong,
+            severity: LintRule::PromptTooLong.severity(),
+            kind: LintTargetKind::Prompt,
+            app: app.as_str().to_string(),
+            target_id: prompt.id.clone(),
+            target_name: prompt.name.clone(),
+            message: tr(
+                "lint.prompt_too_long",
+                &[
+                    ("tokens", tokens.to_string()),
+                    ("limit", config.prompt_max_tokens.to_string()),
+                ],
+            ),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rules(warnings: &[LintWarning]) -> Vec<LintRule> {
+        warnings.iter().map(|warning| warning.rule).collect()
+    }
+
+    #[test]
+    fn provider_rules_flag_common_misconfigurations() {
+        let config = LintConfig::default();
+        let provider = Provider::with_id(
+            "relay".into(),
+            "Relay".into(),
+            json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "sk-abc…wxyz",
+                    "ANTHROPIC_BASE_URL": "http://relay.example.com"
+                }
+            }),
+            None,
+        );
+        assert_eq!(
+            rules(&LintService::lint_provider(
+                &config,
+                &AppType::Claude,
+                &provider
+            )),
+            vec![
+                LintRule::MissingModel,
+                LintRule::InsecureBaseUrl,
+                LintRule::TruncatedApiKey
+            ]
+        );
+
+        let local = Provider::with_id(
+            "local".into(),
+            "Local".into(),
+            json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "sk-0123456789abcdef0123",
+                    "ANTHROPIC_BASE_URL": "http://127.0.0.1:8080",
+                    "ANTHROPIC_MODEL": "claude-sonnet-4-5"
+                }
+            }),
+            None,
+        );
+        assert!(LintService::lint_provider(&config, &AppType::Claude, &local).is_empty());
+
+        let disabled = LintConfig {
+            disabled_rules: vec![LintRule::MissingModel, LintRule::TruncatedApiKey],
+            ..LintConfig::default()
+        };
+        assert_eq!(
+            rules(&LintService::lint_provider(
+                &disabled,
+                &AppType::Claude,
+                &provider
+            )),
+            vec![LintRule::InsecureBaseUrl]
+        );
+    }
+
+    #[test]
+    fn prompt_over_token_limit_is_flagged() {
+        assert_eq!(estimate_tokens("abcdefgh"), 2);
+        assert_eq!(estimate_tokens("提示词"), 3);
+
+        let config = LintConfig {
+            prompt_max_tokens: 10,
+            ..LintConfig::default()
+        };
+        let mut prompt = Prompt {
+            id: "long".to_string(),
+            name: "Long".to_string(),
+            content: "word ".repeat(8),
+            description: None,
+            enabled: false,
+            tags: Vec::new(),
+            target_apps: Vec::new(),
+            apps: Default::default(),
+            created_at: None,
+            updated_at: None,
+        };
+        assert!(LintService::lint_prompt(&config, &AppType::Claude, &prompt).is_empty());
+
+        prompt.content = "word ".repeat(20);
+        assert_eq!(
+            rules(&LintService::lint_prompt(
+                &config,
+                &AppType::Claude,
+                &prompt
+            )),
+            vec![LintRule::PromptTooLong]
+        );
+    }
+}
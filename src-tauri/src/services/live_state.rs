@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::AppType;
+use crate::codex_config::{get_codex_auth_path, get_codex_config_path};
+use crate::config::{get_claude_local_settings_path, get_claude_settings_path};
+use crate::error::AppError;
+use crate::gemini_config::{get_gemini_env_path, get_gemini_settings_path};
+use crate::grok_config::get_grok_settings_path;
+use crate::qwen_config::get_qwen_settings_path;
+use crate::store::AppState;
+
+/// 外部修改类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LiveFileChange {
+    /// 内容与最近一次写入不一致
+    Modified,
+    /// 文件已被删除
+    Missing,
+}
+
+/// 单个文件的外部修改
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveFileDrift {
+    pub path: String,
+    pub change: LiveFileChange,
+}
+
+/// 单个应用的 live 配置漂移状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveDrift {
+    pub app_type: String,
+    pub drifted: bool,
+    /// 最近一次由 cc-switch 写入的时间
+    pub written_at: i64,
+    pub files: Vec<LiveFileDrift>,
+}
+
+/// Live 配置文件状态（检测外部修改）
+pub struct LiveStateService;
+
+impl LiveStateService {
+    /// 各应用由 cc-switch 写入的 live 配置文件
+    ///
+    /// Gemini 的 `oauth_creds.json` 会被 CLI 自行刷新，不纳入检测。
+    pub(crate) fn live_files(app_type: &AppType) -> Vec<PathBuf> {
+        match app_type {
+            AppType::Claude => vec![get_claude_settings_path(), get_claude_local_settings_path()],
+            AppType::Codex => vec![get_codex_auth_path(), get_codex_config_path()],
+            AppType::Gemini => vec![get_gemini_env_path(), get_gemini_settings_path()],
+            AppType::Grok => vec![get_grok_settings_path()],
+            AppType::Qwen => vec![get_qwen_settings_path()],
+        }
+    }
+
+    fn hash_file(path: &PathBuf) -> Option<String> {
+        let bytes = std::fs::read(path).ok()?;
+        let hash = digest(&SHA256, &bytes);
+        Some(hash.as_ref().iter().map(|b| format!("{b:02x}")).collect())
+    }
+
+    /// 记录应用 live 文件的当前哈希（在 cc-switch 写入后调用）
+    pub fn record(state: &AppState, app_type: &AppType) -> Result<(), AppError> {
+        let files: Vec<(String, String)> = Self::live_files(app_type)
+            .into_iter()
+            .filter_map(|path| {
+                let hash = Self::hash_file(&path)?;
+                Some((path.to_string_lossy().to_string(), hash))
+            })
+            .collect();
+        state.db.set_live_file_states(
+            app_type.as_str(),
+            &files,
+            chrono::Utc::now().timestamp(),
+        )
+    }
+
+    /// 记录多个应用，失败仅记录日志（不影响写入流程）
+    pub fn record_quietly(state: &AppState, app_types: &[AppType]) {
+        for app_type in app_types {
+            if let Err(e) = Self::record(state, app_type) {
+                log::warn!("记录 {} live 文件状态失败: {e}", app_type.as_str());
+            }
+        }
+    }
+
+    /// 对比当前文件与最近一次写入的哈希，返回各应用的漂移状态
+    ///
+    /// 没有写入记录的应用不会出现在结果中。
+    pub fn drift(state: &AppState) -> Result<Vec<LiveDrift>, AppError> {
+        let mut by_app: HashMap<String, LiveDrift> = HashMap::new();
+        let mut order = Vec::new();
+
+        for file in state.db.get_live_file_states()? {
+            let entry = by_app.entry(file.app_type.clone()).or_insert_with(|| {
+                order.push(file.app_type.clone());
+                LiveDrift {
+                    app_type: file.app_type.clone(),
+                    drifted: false,
+                    written_at: file.written_at,
+                    files: Vec::new(),
+                }
+            });
+
+            let path = PathBuf::from(&file.path);
+            let change = match Self::hash_file(&path) {
+                None if !path.exists() => Some(LiveFileChange::Missing),
+                Some(hash) if hash == file.hash => None,
+                _ => Some(LiveFileChange::Modified),
+            };
+            if let Some(change) = change {
+                entry.drifted = true;
+                entry.files.push(LiveFileDrift {
+                    path: file.path,
+                    change,
+                });
+            }
+        }
+
+        Ok(order
+            .into_iter()
+            .filter_map(|app| by_app.remove(&app))
+            .collect())
+    }
+
+    /// 应用的 live 文件是否被外部修改（无记录时返回 None）
+    pub fn has_drifted(state: &AppState, app_type: &AppType) -> Option<bool> {
+        Self::drift(state)
+            .ok()?
+            .into_iter()
+            .find(|d| d.app_type == app_type.as_str())
+            .map(|d| d.drifted)
+    }
+}
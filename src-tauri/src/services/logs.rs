@@ -0,0 +1,148 @@
+//! 日志文件管理
+//!
+//! 日志按大小滚动写入应用配置目录下的 `logs/`，仅保留最近几个文件。
+//! 读取与导出时会对 API Key、Bearer Token 等敏感内容脱敏，便于直接附在问题反馈中。
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+use crate::scrub::scrub_secrets;
+use crate::services::diagnostics::DiagnosticReport;
+
+/// 日志文件名（不含扩展名），滚动后的文件为 `cc-switch_<时间>.log`
+pub const LOG_FILE_NAME: &str = "cc-switch";
+/// 单个日志文件大小上限（字节）
+pub const LOG_MAX_FILE_SIZE: u128 = 2 * 1024 * 1024;
+/// 保留的日志文件数量
+pub const LOG_KEEP_FILES: usize = 5;
+/// `get_recent_logs` 单次最多返回的行数
+pub const MAX_RECENT_LINES: usize = 5000;
+
+/// 日志目录
+pub fn log_dir() -> PathBuf {
+    crate::config::get_app_config_dir().join("logs")
+}
+
+pub struct LogService;
+
+impl LogService {
+    /// 读取最近的 `lines` 行日志（跨滚动文件，按时间顺序，已脱敏）
+    pub fn recent_lines(dir: &Path, lines: usize) -> Result<Vec<String>, AppError> {
+        let lines = lines.min(MAX_RECENT_LINES);
+        let mut collected: Vec<String> = Vec::new();
+
+        // 从最新的文件往前读，直到凑够行数
+        for path in Self::log_files(dir)?.into_iter().rev() {
+            if collected.len() >= lines {
+                break;
+            }
+            let content = fs::read(&path).map_err(|e| AppError::io(&path, e))?;
+            let content = String::from_utf8_lossy(&content);
+            let remaining = lines - collected.len();
+            let mut chunk: Vec<String> = content
+                .lines()
+                .rev()
+                .take(remaining)
+                .map(scrub_secrets)
+                .collect();
+            chunk.reverse();
+            chunk.append(&mut collected);
+            collected = chunk;
+        }
+
+        Ok(collected)
+    }
+
+    /// 将日志与自检报告打包为 zip（均已脱敏）
+    pub fn export_zip(
+        dir: &Path,
+        report: &DiagnosticReport,
+        target: &Path,
+    ) -> Result<PathBuf, AppError> {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        let file = fs::File::create(target).map_err(|e| AppError::io(target, e))?;
+        let mut writer = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        let zip_err =
+            |e: zip::result::ZipError| AppError::Message(format!("写入日志压缩包失败: {e}"));
+
+        let report_json = serde_json::to_string_pretty(report)
+            .map_err(|source| AppError::JsonSerialize { source })?;
+        writer
+            .start_file("diagnostics.json", options)
+            .map_err(zip_err)?;
+        writer
+            .write_all(scrub_secrets(&report_json).as_bytes())
+            .map_err(|e| AppError::io(target, e))?;
+
+        for path in Self::log_files(dir)? {
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let content = fs::read(&path).map_err(|e| AppError::io(&path, e))?;
+            writer
+                .start_file(format!("logs/{name}"), options)
+                .map_err(zip_err)?;
+            writer
+                .write_all(scrub_secrets(&String::from_utf8_lossy(&content)).as_bytes())
+                .map_err(|e| AppError::io(target, e))?;
+        }
+
+        writer.finish().map_err(zip_err)?;
+        log::info!("已导出日志压缩包: {}", target.display());
+        Ok(target.to_path_buf())
+    }
+
+    /// 目录下的日志文件，按修改时间从旧到新排序（目录不存在时为空）
+    fn log_files(dir: &Path) -> Result<Vec<PathBuf>, AppError> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut files = Vec::new();
+        for entry in fs::read_dir(dir).map_err(|e| AppError::io(dir, e))? {
+            let entry = entry.map_err(|e| AppError::io(dir, e))?;
+            let path = entry.path();
+            if !path.is_file() || path.extension().and_then(|ext| ext.to_str()) != Some("log") {
+                continue;
+            }
+            let modified = entry
+                .metadata()
+                .and_then(|meta| meta.modified())
+                .unwrap_or(std::time::UNIX_EPOCH);
+            files.push((modified, path));
+        }
+        files.sort();
+        Ok(files.into_iter().map(|(_, path)| path).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_lines_spans_rotated_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let old = dir.path().join("cc-switch_2026-01-01.log");
+        let current = dir.path().join("cc-switch.log");
+        fs::write(&old, "a\nb\nc\n").expect("write old log");
+        let older = std::time::SystemTime::now() - std::time::Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(&old)
+            .and_then(|f| f.set_modified(older))
+            .expect("set mtime");
+        fs::write(&current, "d\ne\n").expect("write current log");
+
+        let lines = LogService::recent_lines(dir.path(), 3).expect("read logs");
+        assert_eq!(lines, vec!["c", "d", "e"]);
+        assert!(LogService::recent_lines(&dir.path().join("missing"), 10)
+            .expect("missing dir")
+            .is_empty());
+    }
+}
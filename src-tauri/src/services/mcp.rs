@@ -1,10 +1,200 @@
 use indexmap::IndexMap;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
 
-use crate::app_config::{AppType, McpServer};
+use crate::app_config::{AppType, McpServer, McpTarget};
+use crate::database::Database;
 use crate::error::AppError;
 use crate::mcp;
+use crate::services::live_state::LiveStateService;
+use crate::services::mcp_supervisor::McpSupervisorService;
 use crate::store::AppState;
+use tauri::{AppHandle, Emitter};
+
+/// 跨应用复制 MCP 启用状态的结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpCopyResult {
+    /// 本次新启用到目标应用的服务器 ID
+    pub copied: Vec<String>,
+    /// 目标应用已启用，无需处理的服务器 ID
+    pub skipped: Vec<String>,
+    /// 同步失败的服务器（ID 与错误信息），不会标记为启用
+    pub failed: Vec<(String, String)>,
+}
+
+/// 去抖写入失败（`mcp-sync-failed` 事件），涉及的服务器已回滚启用状态
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpSyncFailure {
+    pub app: String,
+    pub server_ids: Vec<String>,
+    pub error: String,
+}
+
+/// 对账涉及的单个服务器
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpReconcileEntry {
+    pub app: String,
+    pub id: String,
+}
+
+/// MCP 服务器与 live 配置对账结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpReconcileReport {
+    /// 在 live 配置中新发现并导入数据库的服务器
+    pub imported: Vec<McpReconcileEntry>,
+    /// 数据库中已存在、live 中已配置但未标记启用的服务器（已补齐启用状态）
+    pub enabled: Vec<McpReconcileEntry>,
+    /// 标记为启用但已从 live 配置中被外部删除的服务器（已取消启用，不会在下次同步时被写回）
+    pub removed_externally: Vec<McpReconcileEntry>,
+}
+
+impl McpReconcileReport {
+    pub fn is_empty(&self) -> bool {
+        self.imported.is_empty() && self.enabled.is_empty() && self.removed_externally.is_empty()
+    }
+}
+
+/// 孤立 MCP 条目清理结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpPruneResult {
+    pub app: String,
+    /// 为 true 时仅预览，未修改 live 配置
+    pub dry_run: bool,
+    /// 孤立的服务器 ID（非预览模式下已被移除）
+    pub orphaned: Vec<String>,
+}
+
+/// 单个应用的对账计划
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ReconcilePlan {
+    import: Vec<String>,
+    enable: Vec<String>,
+    disable: Vec<String>,
+}
+
+/// 对比 live 配置中的服务器 ID 与数据库记录，得出需导入、补齐启用、取消启用的服务器
+fn plan_reconcile(
+    app: &AppType,
+    live_ids: &HashSet<String>,
+    servers: &IndexMap<String, McpServer>,
+) -> ReconcilePlan {
+    let mut plan = ReconcilePlan::default();
+    let mut live: Vec<&String> = live_ids.iter().collect();
+    live.sort();
+    for id in live {
+        match servers.get(id) {
+            None => plan.import.push(id.clone()),
+            Some(server) if !server.apps.is_enabled_for(app) => plan.enable.push(id.clone()),
+            Some(_) => {}
+        }
+    }
+    plan.disable = servers
+        .iter()
+        .filter(|(id, server)| server.apps.is_enabled_for(app) && !live_ids.contains(*id))
+        .map(|(id, _)| id.clone())
+        .collect();
+    plan
+}
+
+/// 找出孤立条目：cc-switch 曾写入、仍在 live 中，但数据库中已不存在或未对该应用启用
+fn find_orphans(
+    app: &AppType,
+    live_ids: &HashSet<String>,
+    owned: &[String],
+    servers: &IndexMap<String, McpServer>,
+) -> Vec<String> {
+    let mut orphans: Vec<String> = owned
+        .iter()
+        .filter(|id| live_ids.contains(*id))
+        .filter(|id| {
+            !servers
+                .get(*id)
+                .is_some_and(|server| server.apps.is_enabled_for(app))
+        })
+        .cloned()
+        .collect();
+    orphans.sort();
+    orphans
+}
+
+static LAST_RECONCILE: OnceLock<RwLock<Option<McpReconcileReport>>> = OnceLock::new();
+
+fn reconcile_cell() -> &'static RwLock<Option<McpReconcileReport>> {
+    LAST_RECONCILE.get_or_init(|| RwLock::new(None))
+}
+
+/// 启用切换的去抖窗口：窗口内的多次切换合并为每个应用一次 live 写入
+const SYNC_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 每个 (应用, 服务器) 最近一次写入 live 的配置指纹，未变化的服务器同步时跳过
+static SYNCED: OnceLock<Mutex<HashMap<(String, String), u64>>> = OnceLock::new();
+
+/// 去抖窗口内尚未写入 live 的启用切换
+static PENDING_SYNC: Mutex<Option<PendingSync>> = Mutex::new(None);
+
+struct PendingSync {
+    db: Arc<Database>,
+    /// 写入失败时用于通知前端
+    handle: Option<AppHandle>,
+    deadline: Instant,
+    /// (应用, 服务器 ID) → 目标配置；`None` 表示从该应用移除，同一服务器以最后一次切换为准
+    changes: IndexMap<(String, String), (AppType, Option<Value>)>,
+}
+
+/// 远程服务器的 stdio 桥接配置（settings 表键名）
+const BRIDGES_KEY: &str = "mcp_bridges";
+
+/// 单个应用的批量变更
+struct AppChanges {
+    app: AppType,
+    upserts: HashMap<String, Value>,
+    removals: Vec<String>,
+}
+
+fn synced() -> &'static Mutex<HashMap<(String, String), u64>> {
+    SYNCED.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn fingerprint(spec: &Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    spec.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 将待写入的切换按应用分组
+fn group_changes(changes: IndexMap<(String, String), (AppType, Option<Value>)>) -> Vec<AppChanges> {
+    let mut grouped: Vec<AppChanges> = Vec::new();
+    for ((_, id), (app, spec)) in changes {
+        let index = match grouped.iter().position(|group| group.app == app) {
+            Some(index) => index,
+            None => {
+                grouped.push(AppChanges {
+                    app,
+                    upserts: HashMap::new(),
+                    removals: Vec::new(),
+                });
+                grouped.len() - 1
+            }
+        };
+        match spec {
+            Some(spec) => {
+                grouped[index].upserts.insert(id, spec);
+            }
+            None => grouped[index].removals.push(id),
+        }
+    }
+    grouped
+}
 
 /// MCP 相关业务逻辑（v3.7.0 统一结构）
 pub struct McpService;
@@ -17,16 +207,21 @@ impl McpService {
 
     /// 添加或更新 MCP 服务器
     pub fn upsert_server(state: &AppState, server: McpServer) -> Result<(), AppError> {
+        mcp::validate_app_overrides(&server)?;
+        Self::flush_pending_sync();
         state.db.save_mcp_server(&server)?;
 
         // 同步到各个启用的应用
         Self::sync_server_to_apps(state, &server)?;
+        LiveStateService::record_quietly(state, &server.apps.enabled_apps());
 
         Ok(())
     }
 
     /// 删除 MCP 服务器
     pub fn delete_server(state: &AppState, id: &str) -> Result<bool, AppError> {
+        // 先写入窗口内的切换，避免之后被写回已删除的服务器
+        Self::flush_pending_sync();
         let server = state.db.get_all_mcp_servers()?.shift_remove(id);
 
         if let Some(server) = server {
@@ -34,6 +229,9 @@ impl McpService {
 
             // 从所有应用的 live 配置中移除
             Self::remove_server_from_all_apps(state, id, &server)?;
+            LiveStateService::record_quietly(state, &server.apps.enabled_apps());
+            // 同时结束并移除其守护进程
+            McpSupervisorService::remove_command(&state.db, id)?;
             Ok(true)
         } else {
             Ok(false)
@@ -47,55 +245,367 @@ impl McpService {
         app: AppType,
         enabled: bool,
     ) -> Result<(), AppError> {
+        Self::flush_pending_sync();
+        if let Some(server) = Self::set_app_enabled(state, server_id, &app, enabled)? {
+            // 同步到对应应用
+            if enabled {
+                Self::sync_server_to_app(state, &server, &app)?;
+            } else {
+                Self::remove_server_from_app(state, server_id, &app)?;
+            }
+            LiveStateService::record_quietly(state, std::slice::from_ref(&app));
+        }
+
+        Ok(())
+    }
+
+    /// 切换指定应用的启用状态，live 配置的写入经去抖合并
+    ///
+    /// 数据库立即更新；窗口内的多次切换合并为每个应用一次文件写入。
+    /// 写入失败时回滚涉及服务器的启用状态，并向前端发射 `mcp-sync-failed` 事件
+    /// （同时触发 `McpSyncFailed` webhook）。
+    pub fn toggle_app_batched(
+        state: &AppState,
+        handle: AppHandle,
+        server_id: &str,
+        app: AppType,
+        enabled: bool,
+    ) -> Result<(), AppError> {
+        if let Some(server) = Self::set_app_enabled(state, server_id, &app, enabled)? {
+            let spec = enabled.then(|| Self::live_spec(&state.db, &server, &app));
+            Self::schedule_sync(state, Some(handle), app, server_id, spec);
+        }
+        Ok(())
+    }
+
+    /// 更新数据库中的启用状态，服务器不存在时返回 None
+    fn set_app_enabled(
+        state: &AppState,
+        server_id: &str,
+        app: &AppType,
+        enabled: bool,
+    ) -> Result<Option<McpServer>, AppError> {
         let mut servers = state.db.get_all_mcp_servers()?;
+        let Some(mut server) = servers.shift_remove(server_id) else {
+            return Ok(None);
+        };
+        // 启用前检查应用是否支持该传输类型、stdio 命令能否启动，避免写入一个必然失败的服务器
+        if enabled {
+            let spec = Self::live_spec(&state.db, &server, app);
+            mcp::ensure_transport_supported(
+                server_id,
+                app.as_str(),
+                mcp::supported_transports(app),
+                &spec,
+            )?;
+            mcp::ensure_spec_runnable(server_id, &spec)?;
+        }
+        server.apps.set_enabled_for(app, enabled);
+        state.db.save_mcp_server(&server)?;
+        Ok(Some(server))
+    }
 
-        if let Some(server) = servers.get_mut(server_id) {
-            server.apps.set_enabled_for(&app, enabled);
-            state.db.save_mcp_server(server)?;
+    /// 登记一次待写入的切换，去抖窗口结束后由后台线程统一写入
+    fn schedule_sync(
+        state: &AppState,
+        handle: Option<AppHandle>,
+        app: AppType,
+        server_id: &str,
+        spec: Option<Value>,
+    ) {
+        let key = (app.as_str().to_string(), server_id.to_string());
+        let deadline = Instant::now() + SYNC_DEBOUNCE;
+        let mut pending = PENDING_SYNC.lock().expect("获取 MCP 同步队列锁失败");
+        if let Some(pending) = pending.as_mut() {
+            pending.deadline = deadline;
+            pending.changes.insert(key, (app, spec));
+            if handle.is_some() {
+                pending.handle = handle;
+            }
+            return;
+        }
 
-            // 同步到对应应用
+        let mut changes = IndexMap::new();
+        changes.insert(key, (app, spec));
+        *pending = Some(PendingSync {
+            db: state.db.clone(),
+            handle,
+            deadline,
+            changes,
+        });
+        std::thread::spawn(|| loop {
+            let wait = match PENDING_SYNC
+                .lock()
+                .expect("获取 MCP 同步队列锁失败")
+                .as_ref()
+            {
+                Some(pending) => pending.deadline.saturating_duration_since(Instant::now()),
+                None => return,
+            };
+            if wait.is_zero() {
+                Self::flush_pending_sync();
+                return;
+            }
+            std::thread::sleep(wait);
+        });
+    }
+
+    /// 立即写入去抖窗口内尚未写入的切换（同步全部服务器、退出前调用）
+    pub fn flush_pending_sync() {
+        let Some(pending) = PENDING_SYNC.lock().expect("获取 MCP 同步队列锁失败").take()
+        else {
+            return;
+        };
+
+        let mut apps = Vec::new();
+        for changes in group_changes(pending.changes) {
+            if let Err(e) = Self::apply_app_changes(&pending.db, &changes) {
+                log::warn!("批量同步 {} 的 MCP 配置失败: {e}", changes.app.as_str());
+                let failure = Self::rollback_changes(&pending.db, &changes, &e);
+                if let Some(handle) = &pending.handle {
+                    if let Err(e) = handle.emit("mcp-sync-failed", &failure) {
+                        log::error!("发射 mcp-sync-failed 事件失败: {e}");
+                    }
+                }
+            }
+            apps.push(changes.app);
+        }
+        LiveStateService::record_quietly(&AppState::new(pending.db), &apps);
+    }
+
+    /// 写入失败后恢复数据库中的启用状态，使其与 live 配置一致
+    fn rollback_changes(db: &Database, changes: &AppChanges, error: &AppError) -> McpSyncFailure {
+        let reverts = changes
+            .upserts
+            .keys()
+            .map(|id| (id, false))
+            .chain(changes.removals.iter().map(|id| (id, true)));
+        let mut server_ids = Vec::new();
+        for (id, enabled) in reverts {
+            server_ids.push(id.clone());
+            let result = db.get_all_mcp_servers().and_then(|mut servers| {
+                match servers.get_mut(id) {
+                    Some(server) => {
+                        server.apps.set_enabled_for(&changes.app, enabled);
+                        db.save_mcp_server(server)
+                    }
+                    None => Ok(()),
+                }
+            });
+            if let Err(e) = result {
+                log::warn!("回滚 MCP 服务器 '{id}' 的启用状态失败: {e}");
+            }
+        }
+        server_ids.sort();
+        McpSyncFailure {
+            app: changes.app.as_str().to_string(),
+            server_ids,
+            error: error.to_string(),
+        }
+    }
+
+    /// 将一个应用的全部变更一次写入 live 配置，并更新写入指纹与归属记录
+    fn apply_app_changes(db: &Database, changes: &AppChanges) -> Result<(), AppError> {
+        let AppChanges {
+            app,
+            upserts,
+            removals,
+        } = changes;
+        if upserts.is_empty() && removals.is_empty() {
+            return Ok(());
+        }
+
+        let result = match app {
+            AppType::Claude => mcp::sync_servers_to_claude(upserts, removals),
+            AppType::Codex => mcp::sync_servers_to_codex(upserts, removals),
+            AppType::Gemini => mcp::sync_servers_to_gemini(upserts, removals),
+            AppType::Grok => mcp::sync_servers_to_grok(upserts, removals),
+            AppType::Qwen => {
+                // Qwen MCP 同步逻辑（暂时为空实现）
+                Ok(())
+            }
+        };
+
+        let mut synced = synced().lock().expect("获取 MCP 同步指纹锁失败");
+        for id in upserts.keys().chain(removals) {
+            synced.remove(&(app.as_str().to_string(), id.clone()));
+        }
+        if let Err(e) = result {
+            drop(synced);
+            for id in upserts.keys() {
+                crate::webhooks::emit(crate::webhooks::WebhookEvent::McpSyncFailed {
+                    app: app.as_str().to_string(),
+                    server_id: id.clone(),
+                    error: e.to_string(),
+                });
+            }
+            return Err(e);
+        }
+        for (id, spec) in upserts {
+            synced.insert((app.as_str().to_string(), id.clone()), fingerprint(spec));
+        }
+        drop(synced);
+
+        for id in upserts.keys() {
+            if let Err(e) = db.mark_mcp_live_owned(app.as_str(), id) {
+                log::warn!("记录 MCP 服务器 '{id}' 写入状态失败: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    /// 切换 Claude Desktop 的启用状态
+    pub fn toggle_claude_desktop(
+        state: &AppState,
+        server_id: &str,
+        enabled: bool,
+    ) -> Result<(), AppError> {
+        let mut servers = state.db.get_all_mcp_servers()?;
+
+        if let Some(server) = servers.get_mut(server_id) {
             if enabled {
-                Self::sync_server_to_app(state, server, &app)?;
+                // 远程服务器配置了桥接时按桥接后的 stdio 条目校验与写入
+                let spec = Self::claude_desktop_spec(&state.db, server);
+                mcp::ensure_transport_supported(
+                    server_id,
+                    "claudeDesktop",
+                    mcp::CLAUDE_DESKTOP_TRANSPORTS,
+                    &spec,
+                )?;
+                mcp::ensure_spec_runnable(server_id, &spec)?;
+                // 先同步再保存，避免不支持的传输类型被标记为启用
+                mcp::sync_single_server_to_claude_desktop(server_id, &spec)?;
             } else {
-                Self::remove_server_from_app(state, server_id, &app)?;
+                mcp::remove_server_from_claude_desktop(server_id)?;
             }
+            server.apps.claude_desktop = enabled;
+            state.db.save_mcp_server(server)?;
         }
 
         Ok(())
     }
 
+    /// 将源应用已启用的全部 MCP 服务器一并启用到目标应用
+    ///
+    /// 每个服务器通过目标应用自己的同步函数写入 live 配置（Codex 会转换为 TOML，
+    /// Gemini 会转换 httpUrl 等字段）。单个服务器同步失败不会中止整体流程，
+    /// 失败项不会被标记为启用，并在结果中返回错误信息。
+    pub fn copy_enablement(
+        state: &AppState,
+        from_app: AppType,
+        to_app: AppType,
+    ) -> Result<McpCopyResult, AppError> {
+        if from_app == to_app {
+            return Err(AppError::InvalidInput(
+                "源应用与目标应用不能相同".to_string(),
+            ));
+        }
+
+        let servers = state.db.get_all_mcp_servers()?;
+        let mut result = McpCopyResult::default();
+
+        for (id, server) in servers {
+            if !server.apps.is_enabled_for(&from_app) {
+                continue;
+            }
+            if server.apps.is_enabled_for(&to_app) {
+                result.skipped.push(id);
+                continue;
+            }
+
+            let mut updated = server;
+            updated.apps.set_enabled_for(&to_app, true);
+
+            match Self::sync_server_to_app(state, &updated, &to_app) {
+                Ok(()) => {
+                    state.db.save_mcp_server(&updated)?;
+                    result.copied.push(id);
+                }
+                Err(e) => {
+                    log::warn!(
+                        "复制 MCP 服务器 '{id}' 到 {} 失败: {e}",
+                        to_app.as_str()
+                    );
+                    result.failed.push((id, e.to_string()));
+                }
+            }
+        }
+
+        log::info!(
+            "已将 {} 个 MCP 服务器从 {} 复制到 {}（跳过 {}，失败 {}）",
+            result.copied.len(),
+            from_app.as_str(),
+            to_app.as_str(),
+            result.skipped.len(),
+            result.failed.len()
+        );
+
+        Ok(result)
+    }
+
     /// 将 MCP 服务器同步到所有启用的应用
-    fn sync_server_to_apps(_state: &AppState, server: &McpServer) -> Result<(), AppError> {
+    fn sync_server_to_apps(state: &AppState, server: &McpServer) -> Result<(), AppError> {
         for app in server.apps.enabled_apps() {
-            Self::sync_server_to_app_no_config(server, &app)?;
+            Self::sync_server_to_app(state, server, &app)?;
+        }
+
+        // Claude Desktop 不属于 AppType，单独同步
+        if server.apps.claude_desktop {
+            let spec = Self::claude_desktop_spec(&state.db, server);
+            mcp::sync_single_server_to_claude_desktop(&server.id, &spec)?;
         }
 
         Ok(())
     }
 
-    /// 将 MCP 服务器同步到指定应用
+    /// 将 MCP 服务器同步到指定应用，并记录为 cc-switch 写入的条目
     fn sync_server_to_app(
-        _state: &AppState,
+        state: &AppState,
         server: &McpServer,
         app: &AppType,
     ) -> Result<(), AppError> {
-        Self::sync_server_to_app_no_config(server, app)
+        let spec = Self::live_spec(&state.db, server, app);
+        if let Err(e) = Self::sync_server_to_app_no_config(&server.id, app, &spec) {
+            crate::webhooks::emit(crate::webhooks::WebhookEvent::McpSyncFailed {
+                app: app.as_str().to_string(),
+                server_id: server.id.clone(),
+                error: e.to_string(),
+            });
+            return Err(e);
+        }
+        if let Err(e) = state.db.mark_mcp_live_owned(app.as_str(), &server.id) {
+            log::warn!("记录 MCP 服务器 '{}' 写入状态失败: {e}", server.id);
+        }
+        synced().lock().expect("获取 MCP 同步指纹锁失败").insert(
+            (app.as_str().to_string(), server.id.clone()),
+            fingerprint(&spec),
+        );
+        Ok(())
+    }
+
+    /// 写入应用的服务器定义：合并该应用的覆盖配置（如不同的 env 取值），需要时转换为 stdio 桥接
+    fn live_spec(db: &Database, server: &McpServer, app: &AppType) -> Value {
+        let bridges = Self::list_bridges(db).unwrap_or_else(|e| {
+            log::warn!("读取 MCP 桥接配置失败: {e}");
+            Vec::new()
+        });
+        mcp::apply_bridge(&bridges, &server.id, app, server.spec_for(app))
     }
 
-    fn sync_server_to_app_no_config(server: &McpServer, app: &AppType) -> Result<(), AppError> {
+    fn sync_server_to_app_no_config(id: &str, app: &AppType, spec: &Value) -> Result<(), AppError> {
         match app {
             AppType::Claude => {
-                mcp::sync_single_server_to_claude(&Default::default(), &server.id, &server.server)?;
+                mcp::sync_single_server_to_claude(&Default::default(), id, spec)?;
             }
             AppType::Codex => {
                 // Codex uses TOML format, must use the correct function
-                mcp::sync_single_server_to_codex(&Default::default(), &server.id, &server.server)?;
+                mcp::sync_single_server_to_codex(&Default::default(), id, spec)?;
             }
             AppType::Gemini => {
-                mcp::sync_single_server_to_gemini(&Default::default(), &server.id, &server.server)?;
+                mcp::sync_single_server_to_gemini(&Default::default(), id, spec)?;
             }
             AppType::Grok => {
-                mcp::sync_single_server_to_grok(&Default::default(), &server.id, &server.server)?;
+                mcp::sync_single_server_to_grok(&Default::default(), id, spec)?;
             }
             AppType::Qwen => {
                 // Qwen MCP 同步逻辑（暂时为空实现）
@@ -115,10 +625,17 @@ impl McpService {
         for app in server.apps.enabled_apps() {
             Self::remove_server_from_app(state, id, &app)?;
         }
+        if server.apps.claude_desktop {
+            mcp::remove_server_from_claude_desktop(id)?;
+        }
         Ok(())
     }
 
     fn remove_server_from_app(_state: &AppState, id: &str, app: &AppType) -> Result<(), AppError> {
+        synced()
+            .lock()
+            .expect("获取 MCP 同步指纹锁失败")
+            .remove(&(app.as_str().to_string(), id.to_string()));
         match app {
             AppType::Claude => mcp::remove_server_from_claude(id)?,
             AppType::Codex => mcp::remove_server_from_codex(id)?,
@@ -132,15 +649,209 @@ impl McpService {
         Ok(())
     }
 
-    /// 手动同步所有启用的 MCP 服务器到对应的应用
+    /// 同步所有启用的 MCP 服务器到对应的应用
+    ///
+    /// 仅写入自上次写入后配置有变化、或已不在 live 配置中的服务器，每个应用最多写一次文件；
+    /// 单个应用写入失败不影响其他应用，返回第一个错误。
     pub fn sync_all_enabled(state: &AppState) -> Result<(), AppError> {
+        Self::flush_pending_sync();
         let servers = Self::get_all_servers(state)?;
+        let bridges = Self::list_bridges(&state.db)?;
 
-        for server in servers.values() {
-            Self::sync_server_to_apps(state, server)?;
+        let mut first_error = None;
+        for app in AppType::all() {
+            // live 配置不存在或无法读取时全部视为需要写入
+            let live_ids = Self::live_server_ids(&app).unwrap_or_else(|e| {
+                log::warn!("读取 {} 的 MCP live 配置失败: {e}", app.as_str());
+                None
+            });
+            let upserts: HashMap<String, Value> = {
+                let synced = synced().lock().expect("获取 MCP 同步指纹锁失败");
+                servers
+                    .values()
+                    .filter(|server| server.apps.is_enabled_for(&app))
+                    .map(|server| {
+                        let spec =
+                            mcp::apply_bridge(&bridges, &server.id, &app, server.spec_for(&app));
+                        (server.id.clone(), spec)
+                    })
+                    .filter(|(id, spec)| {
+                        let in_live = live_ids.as_ref().is_some_and(|ids| ids.contains(id));
+                        let key = (app.as_str().to_string(), id.clone());
+                        !in_live || synced.get(&key) != Some(&fingerprint(spec))
+                    })
+                    .collect()
+            };
+            if upserts.is_empty() {
+                continue;
+            }
+
+            let changes = AppChanges {
+                app,
+                upserts,
+                removals: Vec::new(),
+            };
+            if let Err(e) = Self::apply_app_changes(&state.db, &changes) {
+                log::warn!("同步 {} 的 MCP 配置失败: {e}", changes.app.as_str());
+                first_error.get_or_insert(e);
+            }
         }
 
-        Ok(())
+        // Claude Desktop 不属于 AppType，单独同步
+        for server in servers.values().filter(|server| server.apps.claude_desktop) {
+            let spec = mcp::apply_claude_desktop_bridge(
+                &bridges,
+                &server.id,
+                server.spec_for(McpTarget::ClaudeDesktop),
+            );
+            mcp::sync_single_server_to_claude_desktop(&server.id, &spec)?;
+        }
+
+        LiveStateService::record_quietly(state, &AppType::all());
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// 将数据库中的 MCP 服务器与 Claude/Codex/Gemini 的 live 配置对账
+    ///
+    /// - live 中新增的服务器导入数据库（仅启用对应应用）
+    /// - live 中存在但未标记启用的服务器补齐启用状态
+    /// - 标记为启用但已从 live 中删除的服务器取消该应用的启用，避免下次同步时被写回
+    ///
+    /// live 配置文件不存在的应用会被跳过，不会把其全部服务器视为已删除。
+    pub fn reconcile_with_live(state: &AppState) -> Result<McpReconcileReport, AppError> {
+        let mut report = McpReconcileReport::default();
+        let mut servers = state.db.get_all_mcp_servers()?;
+
+        for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            let Some(live_ids) = Self::live_server_ids(&app)? else {
+                continue;
+            };
+
+            let plan = plan_reconcile(&app, &live_ids, &servers);
+            let entry = |id: &String| McpReconcileEntry {
+                app: app.as_str().to_string(),
+                id: id.clone(),
+            };
+
+            if !plan.import.is_empty() {
+                // 复用原有导入逻辑完成格式转换与校验，只保存本次新发现的服务器
+                let mut temp_config = crate::app_config::MultiAppConfig::default();
+                match app {
+                    AppType::Claude => mcp::import_from_claude(&mut temp_config)?,
+                    AppType::Codex => mcp::import_from_codex(&mut temp_config)?,
+                    _ => mcp::import_from_gemini(&mut temp_config)?,
+                };
+                let imported = temp_config.mcp.servers.unwrap_or_default();
+                for id in &plan.import {
+                    let Some(server) = imported.get(id) else {
+                        continue;
+                    };
+                    state.db.save_mcp_server(server)?;
+                    servers.insert(id.clone(), server.clone());
+                    report.imported.push(entry(id));
+                }
+            }
+
+            for (ids, enabled) in [(&plan.enable, true), (&plan.disable, false)] {
+                for id in ids {
+                    let Some(server) = servers.get_mut(id) else {
+                        continue;
+                    };
+                    server.apps.set_enabled_for(&app, enabled);
+                    state.db.save_mcp_server(server)?;
+                    if enabled {
+                        report.enabled.push(entry(id));
+                    } else {
+                        log::warn!("MCP 服务器 '{id}' 已从 {} 配置中被外部删除", app.as_str());
+                        report.removed_externally.push(entry(id));
+                    }
+                }
+            }
+        }
+
+        log::info!(
+            "MCP 对账完成：导入 {}，补齐启用 {}，外部删除 {}",
+            report.imported.len(),
+            report.enabled.len(),
+            report.removed_externally.len()
+        );
+        if let Ok(mut last) = reconcile_cell().write() {
+            *last = Some(report.clone());
+        }
+        Ok(report)
+    }
+
+    /// 清理指定应用 live 配置中的孤立 MCP 条目
+    ///
+    /// 孤立条目指 cc-switch 曾写入、但数据库中已删除或已对该应用取消启用的服务器
+    /// （例如删除时配置文件正被其他编辑器修改，旧内容又被写回）。
+    /// 用户手动添加、从未经 cc-switch 写入的条目不受影响。`dry_run` 为 true 时仅返回预览。
+    pub fn prune_orphaned(
+        state: &AppState,
+        app: AppType,
+        dry_run: bool,
+    ) -> Result<McpPruneResult, AppError> {
+        if !matches!(app, AppType::Claude | AppType::Codex | AppType::Gemini) {
+            return Err(AppError::InvalidInput(format!(
+                "{} 不支持清理 MCP 孤立条目",
+                app.as_str()
+            )));
+        }
+
+        let live_ids = Self::live_server_ids(&app)?.unwrap_or_default();
+        let owned = state.db.get_mcp_live_owned(app.as_str())?;
+        let servers = state.db.get_all_mcp_servers()?;
+        let orphaned = find_orphans(&app, &live_ids, &owned, &servers);
+
+        if !dry_run {
+            for id in &orphaned {
+                Self::remove_server_from_app(state, id, &app)?;
+                state.db.unmark_mcp_live_owned(app.as_str(), id)?;
+                log::info!("已从 {} 配置中移除孤立的 MCP 服务器 '{id}'", app.as_str());
+            }
+            // 已不在 live 中的写入记录一并清理
+            for id in owned.iter().filter(|id| !live_ids.contains(*id)) {
+                state.db.unmark_mcp_live_owned(app.as_str(), id)?;
+            }
+            if !orphaned.is_empty() {
+                LiveStateService::record_quietly(state, std::slice::from_ref(&app));
+            }
+        }
+
+        Ok(McpPruneResult {
+            app: app.as_str().to_string(),
+            dry_run,
+            orphaned,
+        })
+    }
+
+    /// 读取应用 live 配置中的 MCP 服务器 ID，配置文件不存在时返回 None
+    fn live_server_ids(app: &AppType) -> Result<Option<HashSet<String>>, AppError> {
+        let live_path = match app {
+            AppType::Claude => crate::config::get_claude_mcp_path(),
+            AppType::Codex => crate::codex_config::get_codex_config_path(),
+            AppType::Gemini => crate::gemini_config::get_gemini_settings_path(),
+            _ => return Ok(None),
+        };
+        if !live_path.exists() {
+            log::debug!("{} 不存在，跳过 MCP live 配置读取", live_path.display());
+            return Ok(None);
+        }
+        let ids = match app {
+            AppType::Claude => crate::claude_mcp::read_mcp_servers_map()?
+                .into_keys()
+                .collect(),
+            AppType::Codex => mcp::read_codex_live_server_ids()?,
+            _ => crate::gemini_mcp::read_mcp_servers_map()?
+                .into_keys()
+                .collect(),
+        };
+        Ok(Some(ids))
+    }
+
+    /// 最近一次对账结果（本次运行内）
+    pub fn last_reconcile_report() -> Option<McpReconcileReport> {
+        reconcile_cell().read().ok().and_then(|last| last.clone())
     }
 
     // ========================================================================
@@ -158,7 +869,7 @@ impl McpService {
 
         for (id, server) in all_servers {
             if server.apps.is_enabled_for(&app) {
-                result.insert(id, server.server);
+                result.insert(id, server.spec_for(&app));
             }
         }
 
@@ -278,4 +989,419 @@ impl McpService {
 
         Ok(count)
     }
+
+    /// 从 Cursor 导入 MCP（新服务器默认不启用任何应用）
+    pub fn import_from_cursor(state: &AppState) -> Result<usize, AppError> {
+        let mut temp_config = crate::app_config::MultiAppConfig::default();
+        crate::mcp::import_from_cursor(&mut temp_config)?;
+        Self::save_new_imported_servers(state, &temp_config)
+    }
+
+    /// 从 VS Code 导入 MCP（新服务器默认不启用任何应用）
+    pub fn import_from_vscode(state: &AppState) -> Result<usize, AppError> {
+        let mut temp_config = crate::app_config::MultiAppConfig::default();
+        crate::mcp::import_from_vscode(&mut temp_config)?;
+        Self::save_new_imported_servers(state, &temp_config)
+    }
+
+    /// 从 Windsurf 导入 MCP（新服务器默认不启用任何应用）
+    pub fn import_from_windsurf(state: &AppState) -> Result<usize, AppError> {
+        let mut temp_config = crate::app_config::MultiAppConfig::default();
+        crate::mcp::import_from_windsurf(&mut temp_config)?;
+        Self::save_new_imported_servers(state, &temp_config)
+    }
+
+    /// 仅保存数据库中尚不存在的服务器，避免覆盖用户已有配置
+    /// 读取应用 live 配置中的 MCP 服务器（不写库）；Qwen 暂无 MCP 配置
+    fn read_live_servers(app: &AppType) -> Result<crate::app_config::MultiAppConfig, AppError> {
+        let mut temp_config = crate::app_config::MultiAppConfig::default();
+        match app {
+            AppType::Claude => mcp::import_from_claude(&mut temp_config)?,
+            AppType::Codex => mcp::import_from_codex(&mut temp_config)?,
+            AppType::Gemini => mcp::import_from_gemini(&mut temp_config)?,
+            AppType::Grok => mcp::import_from_grok(&mut temp_config)?,
+            AppType::Qwen => 0,
+        };
+        Ok(temp_config)
+    }
+
+    /// 应用 live 配置中尚未导入数据库的 MCP 服务器数量
+    pub fn count_importable(state: &AppState, app: &AppType) -> Result<usize, AppError> {
+        let temp_config = Self::read_live_servers(app)?;
+        let existing = state.db.get_all_mcp_servers()?;
+        Ok(temp_config
+            .mcp
+            .servers
+            .as_ref()
+            .map(|servers| {
+                servers
+                    .keys()
+                    .filter(|id| !existing.contains_key(*id))
+                    .count()
+            })
+            .unwrap_or(0))
+    }
+
+    /// 从应用 live 配置导入数据库中尚不存在的 MCP 服务器（已有服务器保持不变）
+    pub fn import_new_from_app(state: &AppState, app: &AppType) -> Result<usize, AppError> {
+        let temp_config = Self::read_live_servers(app)?;
+        Self::save_new_imported_servers(state, &temp_config)
+    }
+
+    fn save_new_imported_servers(
+        state: &AppState,
+        temp_config: &crate::app_config::MultiAppConfig,
+    ) -> Result<usize, AppError> {
+        let Some(servers) = &temp_config.mcp.servers else {
+            return Ok(0);
+        };
+        let existing = state.db.get_all_mcp_servers()?;
+
+        let mut count = 0;
+        for (id, server) in servers {
+            if existing.contains_key(id) {
+                continue;
+            }
+            state.db.save_mcp_server(server)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// 将当前已启用（至少一个应用）的服务器导出为编辑器格式
+    ///
+    /// `write` 为 true 时按 ID 合并写入目标编辑器的配置文件：保留其他字段与用户自己的服务器，
+    /// 仅移除此前由 cc-switch 导出、现已不再导出的条目；否则仅返回渲染后的内容。
+    pub fn export_for(
+        state: &AppState,
+        target: mcp::McpExportTarget,
+        write: bool,
+    ) -> Result<mcp::McpExportResult, AppError> {
+        let bridges = Self::list_bridges(&state.db)?;
+        let servers: Vec<(String, Value)> = Self::get_all_servers(state)?
+            .into_iter()
+            .filter(|(_, server)| !server.apps.is_empty())
+            .map(|(id, server)| {
+                let spec = Self::editor_spec(&bridges, &server, target);
+                (id, spec)
+            })
+            .collect();
+
+        let owner = target.owner_key();
+        let owned = state.db.get_mcp_live_owned(owner)?;
+        let result = mcp::export_to_editor(target, &servers, &owned, write)?;
+
+        if write {
+            for (id, _) in &servers {
+                if !result.skipped.contains(id) {
+                    state.db.mark_mcp_live_owned(owner, id)?;
+                }
+            }
+            for id in &owned {
+                let exported = servers.iter().any(|(server_id, _)| server_id == id);
+                if !exported || result.skipped.contains(id) {
+                    state.db.unmark_mcp_live_owned(owner, id)?;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// 导出到编辑器的服务器定义
+    ///
+    /// Cursor / VS Code 没有单独的覆盖配置，使用基础定义；Claude Desktop 合并其覆盖配置，
+    /// 且仅支持 stdio，远程服务器配置了桥接时导出桥接条目。
+    fn editor_spec(
+        bridges: &[mcp::McpBridgeConfig],
+        server: &McpServer,
+        target: mcp::McpExportTarget,
+    ) -> Value {
+        if target != mcp::McpExportTarget::ClaudeDesktop {
+            return server.server.clone();
+        }
+        mcp::apply_claude_desktop_bridge(
+            bridges,
+            &server.id,
+            server.spec_for(McpTarget::ClaudeDesktop),
+        )
+    }
+
+    /// 写入 Claude Desktop 的服务器定义：合并覆盖配置，配置了桥接的远程服务器写入桥接条目
+    fn claude_desktop_spec(db: &Database, server: &McpServer) -> Value {
+        let bridges = Self::list_bridges(db).unwrap_or_else(|e| {
+            log::warn!("读取 MCP 桥接配置失败，使用原始定义: {e}");
+            Vec::new()
+        });
+        mcp::apply_claude_desktop_bridge(
+            &bridges,
+            &server.id,
+            server.spec_for(McpTarget::ClaudeDesktop),
+        )
+    }
+
+    /// 将单个服务器导出为分享文档（JSON 文本）
+    ///
+    /// `include_secrets` 为 false 时清空 env、headers 中的密钥；`target` 非空时同时写入文件。
+    pub fn export_server(
+        state: &AppState,
+        id: &str,
+        include_secrets: bool,
+        target: Option<&std::path::Path>,
+    ) -> Result<String, AppError> {
+        let server = Self::find_server(state, id)?;
+        let text = mcp::McpServerShare::from_server(&server, include_secrets).to_json()?;
+        if let Some(target) = target {
+            crate::config::write_text_file(target, &text)?;
+        }
+        Ok(text)
+    }
+
+    /// 从分享文件导入服务器（不启用任何应用）；同 ID 的服务器已存在时拒绝导入
+    pub fn import_server_file(
+        state: &AppState,
+        path: &std::path::Path,
+    ) -> Result<McpServer, AppError> {
+        let server = mcp::McpServerShare::read_file(path)?.into_server();
+        if state.db.get_all_mcp_servers()?.contains_key(&server.id) {
+            return Err(AppError::localized_with(
+                "mcp.share.already_exists",
+                &[("id", server.id.clone())],
+            ));
+        }
+        state.db.save_mcp_server(&server)?;
+        Ok(server)
+    }
+
+    /// 远程服务器的 stdio 桥接配置
+    pub fn list_bridges(db: &Database) -> Result<Vec<mcp::McpBridgeConfig>, AppError> {
+        Ok(db
+            .get_setting(BRIDGES_KEY)?
+            .and_then(|value| serde_json::from_str(&value).ok())
+            .unwrap_or_default())
+    }
+
+    /// 新增或更新桥接配置，并重新同步该服务器到已启用的应用
+    pub fn save_bridge(state: &AppState, bridge: mcp::McpBridgeConfig) -> Result<(), AppError> {
+        let server = Self::find_server(state, &bridge.server_id)?;
+        // 无法生成桥接条目（如 supergateway 不支持请求头）时不保存
+        mcp::bridge_spec(bridge.tool, &server.server)?;
+
+        let mut bridges = Self::list_bridges(&state.db)?;
+        match bridges
+            .iter_mut()
+            .find(|existing| existing.server_id == bridge.server_id)
+        {
+            Some(existing) => *existing = bridge,
+            None => bridges.push(bridge),
+        }
+        state
+            .db
+            .set_setting(BRIDGES_KEY, &crate::database::to_json_string(&bridges)?)?;
+
+        Self::flush_pending_sync();
+        Self::sync_server_to_apps(state, &server)
+    }
+
+    /// 删除桥接配置，已启用的应用恢复写入原始定义
+    pub fn remove_bridge(state: &AppState, server_id: &str) -> Result<bool, AppError> {
+        let mut bridges = Self::list_bridges(&state.db)?;
+        let before = bridges.len();
+        bridges.retain(|bridge| bridge.server_id != server_id);
+        if bridges.len() == before {
+            return Ok(false);
+        }
+        state
+            .db
+            .set_setting(BRIDGES_KEY, &crate::database::to_json_string(&bridges)?)?;
+
+        if let Some(server) = state.db.get_all_mcp_servers()?.shift_remove(server_id) {
+            Self::flush_pending_sync();
+            Self::sync_server_to_apps(state, &server)?;
+        }
+        Ok(true)
+    }
+
+    /// 预览服务器经指定工具桥接后的 stdio 条目（不保存）
+    pub fn preview_bridge(
+        state: &AppState,
+        server_id: &str,
+        tool: mcp::McpBridgeTool,
+    ) -> Result<Value, AppError> {
+        let server = Self::find_server(state, server_id)?;
+        mcp::bridge_spec(tool, &server.server)
+    }
+
+    fn find_server(state: &AppState, id: &str) -> Result<McpServer, AppError> {
+        state
+            .db
+            .get_all_mcp_servers()?
+            .shift_remove(id)
+            .ok_or_else(|| {
+                AppError::localized_with(
+                    "mcp.supervisor.server_not_found",
+                    &[("id", id.to_string())],
+                )
+            })
+    }
+
+    /// 从 Claude Desktop 导入 MCP
+    pub fn import_from_claude_desktop(state: &AppState) -> Result<usize, AppError> {
+        let mut temp_config = crate::app_config::MultiAppConfig::default();
+        let count = crate::mcp::import_from_claude_desktop(&mut temp_config)?;
+
+        if count > 0 {
+            let existing = state.db.get_all_mcp_servers()?;
+            if let Some(servers) = &temp_config.mcp.servers {
+                for (id, server) in servers {
+                    // 已存在的服务器仅追加 Claude Desktop 启用状态
+                    let to_save = match existing.get(id) {
+                        Some(current) => {
+                            let mut merged = current.clone();
+                            merged.apps.claude_desktop = true;
+                            merged
+                        }
+                        None => server.clone(),
+                    };
+                    state.db.save_mcp_server(&to_save)?;
+                }
+            }
+        }
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn server(id: &str, claude: bool) -> McpServer {
+        McpServer {
+            id: id.to_string(),
+            name: id.to_string(),
+            server: json!({ "command": "echo" }),
+            apps: crate::app_config::McpApps {
+                claude,
+                ..Default::default()
+            },
+            app_overrides: Default::default(),
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn plan_reconcile_imports_backfills_and_flags_removed() {
+        let mut servers = IndexMap::new();
+        for (id, enabled) in [("kept", true), ("disabled", false), ("deleted", true)] {
+            servers.insert(id.to_string(), server(id, enabled));
+        }
+        let live: HashSet<String> = ["kept", "disabled", "new-server"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let plan = plan_reconcile(&AppType::Claude, &live, &servers);
+        assert_eq!(plan.import, vec!["new-server"]);
+        assert_eq!(plan.enable, vec!["disabled"]);
+        assert_eq!(plan.disable, vec!["deleted"]);
+    }
+
+    #[test]
+    fn find_orphans_only_targets_owned_untracked_entries() {
+        let mut servers = IndexMap::new();
+        servers.insert("tracked".to_string(), server("tracked", true));
+        servers.insert("disabled".to_string(), server("disabled", false));
+        let live: HashSet<String> = ["tracked", "disabled", "deleted", "user-added"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let owned: Vec<String> = ["tracked", "disabled", "deleted", "gone"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let orphans = find_orphans(&AppType::Claude, &live, &owned, &servers);
+        assert_eq!(orphans, vec!["deleted", "disabled"]);
+    }
+
+    #[test]
+    fn group_changes_batches_per_app_with_last_toggle_winning() {
+        let mut changes = IndexMap::new();
+        let spec = json!({ "command": "echo" });
+        changes.insert(
+            ("claude".to_string(), "a".to_string()),
+            (AppType::Claude, Some(spec.clone())),
+        );
+        changes.insert(
+            ("codex".to_string(), "a".to_string()),
+            (AppType::Codex, Some(spec.clone())),
+        );
+        changes.insert(
+            ("claude".to_string(), "b".to_string()),
+            (AppType::Claude, Some(spec.clone())),
+        );
+        // 同一服务器在窗口内再次切换，覆盖之前的变更
+        changes.insert(
+            ("claude".to_string(), "a".to_string()),
+            (AppType::Claude, None),
+        );
+
+        let grouped = group_changes(changes);
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[0].app, AppType::Claude);
+        assert_eq!(grouped[0].removals, vec!["a".to_string()]);
+        assert_eq!(grouped[0].upserts.keys().collect::<Vec<_>>(), vec!["b"]);
+        assert_eq!(grouped[1].app, AppType::Codex);
+        assert!(grouped[1].upserts.contains_key("a"));
+
+        assert_eq!(
+            fingerprint(&spec),
+            fingerprint(&json!({ "command": "echo" }))
+        );
+        assert_ne!(
+            fingerprint(&spec),
+            fingerprint(&json!({ "command": "npx" }))
+        );
+    }
+
+    #[test]
+    fn failed_batch_rolls_back_enablement() {
+        let db = Database::memory().expect("memory db");
+        for (id, enabled) in [("added", true), ("removed", false)] {
+            let mut server = McpServer {
+                id: id.to_string(),
+                name: id.to_string(),
+                server: json!({ "command": "echo" }),
+                apps: Default::default(),
+                app_overrides: Default::default(),
+                description: None,
+                homepage: None,
+                docs: None,
+                tags: Vec::new(),
+            };
+            server.apps.claude = enabled;
+            db.save_mcp_server(&server).expect("save server");
+        }
+
+        let changes = AppChanges {
+            app: AppType::Claude,
+            upserts: HashMap::from([("added".to_string(), json!({ "command": "echo" }))]),
+            removals: vec!["removed".to_string()],
+        };
+        let failure = McpService::rollback_changes(
+            &db,
+            &changes,
+            &AppError::Message("disk full".to_string()),
+        );
+
+        assert_eq!(failure.app, "claude");
+        assert_eq!(failure.server_ids, vec!["added", "removed"]);
+        let servers = db.get_all_mcp_servers().expect("servers");
+        assert!(!servers["added"].apps.claude);
+        assert!(servers["removed"].apps.claude);
+    }
 }
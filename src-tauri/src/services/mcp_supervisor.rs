@@ -0,0 +1,467 @@
+//! MCP 进程守护
+//!
+//! 部分 MCP 服务器依赖需要常驻的本地进程（如 SSE 桥接）。守护器按用户指定的命令
+//! 启动、停止、重启这些进程并记录 PID，进程意外退出时按退避间隔自动重启，连续
+//! 崩溃过多则放弃。守护配置保存在 settings 表中，进程输出追加到日志目录下的
+//! `mcp-<id>.log`。
+
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::database::{to_json_string, Database};
+use crate::error::AppError;
+use crate::store::AppState;
+
+/// 守护配置（settings 表键名）
+const SUPERVISED_KEY: &str = "mcp_supervised_commands";
+/// 进程状态检查间隔
+const MONITOR_INTERVAL: Duration = Duration::from_secs(2);
+/// 自动重启的最长等待
+const MAX_RESTART_DELAY_SECS: u64 = 60;
+/// 连续崩溃超过该次数后不再自动重启
+const MAX_CONSECUTIVE_CRASHES: u32 = 5;
+/// 运行超过该时长视为稳定，清零连续崩溃计数
+const STABLE_RUN: Duration = Duration::from_secs(60);
+
+/// 需要守护的 MCP 进程命令
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupervisedCommand {
+    /// 关联的 MCP 服务器 ID
+    pub server_id: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// 应用启动时自动启动
+    #[serde(default)]
+    pub auto_start: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum McpProcessState {
+    Stopped,
+    Running,
+    /// 意外退出，等待自动重启
+    Restarting,
+    /// 连续崩溃，已停止自动重启
+    Failed,
+}
+
+/// 守护进程状态
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpProcessStatus {
+    pub server_id: String,
+    /// MCP 服务器名称（服务器已删除时为 ID）
+    pub name: String,
+    pub state: McpProcessState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    /// 本次启动时间（毫秒时间戳）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<i64>,
+    /// 自动重启次数
+    pub restarts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub log_path: String,
+}
+
+/// 单个进程的运行时状态
+#[derive(Default)]
+struct ProcessEntry {
+    child: Option<Child>,
+    started: Option<Instant>,
+    started_at: Option<i64>,
+    /// 用户期望进程保持运行
+    wanted: bool,
+    failed: bool,
+    crashes: u32,
+    restarts: u32,
+    restart_at: Option<Instant>,
+    last_error: Option<String>,
+}
+
+impl ProcessEntry {
+    fn state(&self) -> McpProcessState {
+        if self.child.is_some() {
+            McpProcessState::Running
+        } else if self.failed {
+            McpProcessState::Failed
+        } else if self.wanted {
+            McpProcessState::Restarting
+        } else {
+            McpProcessState::Stopped
+        }
+    }
+
+    fn launch(&mut self, command: &SupervisedCommand) -> Result<(), AppError> {
+        let child = spawn(command)?;
+        log::info!("已启动 MCP 进程 {} (pid {})", command.server_id, child.id());
+        self.child = Some(child);
+        self.started = Some(Instant::now());
+        self.started_at = Some(chrono::Utc::now().timestamp_millis());
+        self.wanted = true;
+        self.failed = false;
+        self.restart_at = None;
+        Ok(())
+    }
+
+    fn kill(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            if let Err(e) = child.kill() {
+                log::warn!("结束 MCP 进程 {} 失败: {e}", child.id());
+            }
+            let _ = child.wait();
+        }
+        self.started = None;
+        self.started_at = None;
+    }
+
+    /// 记录一次意外退出或启动失败，安排重启或放弃
+    fn record_crash(&mut self, error: String) {
+        if self
+            .started
+            .is_some_and(|started| started.elapsed() >= STABLE_RUN)
+        {
+            self.crashes = 0;
+        }
+        self.child = None;
+        self.started = None;
+        self.started_at = None;
+        self.crashes += 1;
+        self.last_error = Some(error);
+        if self.crashes > MAX_CONSECUTIVE_CRASHES {
+            self.wanted = false;
+            self.failed = true;
+            self.restart_at = None;
+        } else {
+            self.restart_at = Some(Instant::now() + restart_delay(self.crashes));
+        }
+    }
+}
+
+static PROCESSES: OnceLock<Mutex<HashMap<String, ProcessEntry>>> = OnceLock::new();
+
+fn processes() -> std::sync::MutexGuard<'static, HashMap<String, ProcessEntry>> {
+    PROCESSES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+pub struct McpSupervisorService;
+
+impl McpSupervisorService {
+    /// 所有守护配置
+    pub fn list_commands(db: &Database) -> Result<Vec<SupervisedCommand>, AppError> {
+        Ok(db
+            .get_setting(SUPERVISED_KEY)?
+            .and_then(|value| serde_json::from_str(&value).ok())
+            .unwrap_or_default())
+    }
+
+    /// 新增或更新守护配置（运行中的进程在下次重启时使用新配置）
+    pub fn save_command(db: &Database, command: SupervisedCommand) -> Result<(), AppError> {
+        if command.command.trim().is_empty() {
+            return Err(AppError::localized("mcp.preflight.empty"));
+        }
+        if !db.get_all_mcp_servers()?.contains_key(&command.server_id) {
+            return Err(AppError::localized_with(
+                "mcp.supervisor.server_not_found",
+                &[("id", command.server_id.clone())],
+            ));
+        }
+
+        let mut commands = Self::list_commands(db)?;
+        match commands
+            .iter_mut()
+            .find(|existing| existing.server_id == command.server_id)
+        {
+            Some(existing) => *existing = command,
+            None => commands.push(command),
+        }
+        db.set_setting(SUPERVISED_KEY, &to_json_string(&commands)?)
+    }
+
+    /// 删除守护配置并结束对应进程
+    pub fn remove_command(db: &Database, server_id: &str) -> Result<bool, AppError> {
+        Self::stop(server_id);
+        processes().remove(server_id);
+
+        let mut commands = Self::list_commands(db)?;
+        let before = commands.len();
+        commands.retain(|command| command.server_id != server_id);
+        if commands.len() == before {
+            return Ok(false);
+        }
+        db.set_setting(SUPERVISED_KEY, &to_json_string(&commands)?)?;
+        Ok(true)
+    }
+
+    /// 启动进程（已在运行时不做处理）
+    pub fn start(db: &Database, server_id: &str) -> Result<(), AppError> {
+        let command = Self::find_command(db, server_id)?;
+        let mut processes = processes();
+        let entry = processes.entry(server_id.to_string()).or_default();
+        if entry.child.is_some() {
+            return Ok(());
+        }
+        entry.crashes = 0;
+        entry.last_error = None;
+        entry.launch(&command)
+    }
+
+    /// 结束进程，不再自动重启
+    pub fn stop(server_id: &str) {
+        if let Some(entry) = processes().get_mut(server_id) {
+            entry.wanted = false;
+            entry.failed = false;
+            entry.restart_at = None;
+            entry.kill();
+            log::info!("已停止 MCP 进程 {server_id}");
+        }
+    }
+
+    pub fn restart(db: &Database, server_id: &str) -> Result<(), AppError> {
+        Self::stop(server_id);
+        Self::start(db, server_id)
+    }
+
+    /// 结束所有守护进程（应用退出时调用）
+    pub fn stop_all() {
+        for entry in processes().values_mut() {
+            entry.wanted = false;
+            entry.kill();
+        }
+    }
+
+    /// 所有守护配置对应的进程状态
+    pub fn status(db: &Database) -> Result<Vec<McpProcessStatus>, AppError> {
+        let servers = db.get_all_mcp_servers()?;
+        let mut processes = processes();
+        Ok(Self::list_commands(db)?
+            .into_iter()
+            .map(|command| {
+                let entry = processes.entry(command.server_id.clone()).or_default();
+                McpProcessStatus {
+                    name: servers
+                        .get(&command.server_id)
+                        .map(|server| server.name.clone())
+                        .unwrap_or_else(|| command.server_id.clone()),
+                    state: entry.state(),
+                    pid: entry.child.as_ref().map(Child::id),
+                    started_at: entry.started_at,
+                    restarts: entry.restarts,
+                    last_error: entry.last_error.clone(),
+                    log_path: log_path(&command.server_id).to_string_lossy().to_string(),
+                    server_id: command.server_id,
+                }
+            })
+            .collect())
+    }
+
+    /// 检查进程是否退出，按需重启；返回状态是否有变化
+    pub fn poll(db: &Database) -> Result<bool, AppError> {
+        let commands = Self::list_commands(db)?;
+        let now = Instant::now();
+        let mut changed = false;
+
+        let mut processes = processes();
+        for command in &commands {
+            let Some(entry) = processes.get_mut(&command.server_id) else {
+                continue;
+            };
+
+            if let Some(child) = entry.child.as_mut() {
+                match child.try_wait() {
+                    Ok(Some(exit)) => {
+                        log::warn!("MCP 进程 {} 意外退出: {exit}", command.server_id);
+                        entry.record_crash(exit.to_string());
+                        changed = true;
+                    }
+                    Ok(None) => {}
+                    Err(e) => log::warn!("检查 MCP 进程 {} 失败: {e}", command.server_id),
+                }
+                continue;
+            }
+
+            if entry.wanted && entry.restart_at.is_some_and(|at| at <= now) {
+                entry.restarts += 1;
+                if let Err(e) = entry.launch(command) {
+                    log::warn!("重启 MCP 进程 {} 失败: {e}", command.server_id);
+                    entry.record_crash(e.to_string());
+                }
+                changed = true;
+            }
+        }
+        Ok(changed)
+    }
+
+    /// 启动守护任务：先启动标记为自动启动的进程，之后定期检查
+    ///
+    /// 状态变化时刷新托盘并发射 `mcp-process-changed` 事件。
+    pub fn start_monitor(app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut auto_started = false;
+            loop {
+                tokio::time::sleep(MONITOR_INTERVAL).await;
+                let handle = app.clone();
+                let result = tauri::async_runtime::spawn_blocking(move || {
+                    let state = handle.try_state::<AppState>()?;
+                    let mut changed = false;
+                    if !auto_started {
+                        changed |= Self::auto_start(&state.db);
+                    }
+                    match Self::poll(&state.db) {
+                        Ok(polled) => changed |= polled,
+                        Err(e) => log::warn!("检查 MCP 进程失败: {e}"),
+                    }
+                    if changed {
+                        crate::tray::refresh_tray(&handle, &state);
+                        if let Err(e) = handle.emit("mcp-process-changed", ()) {
+                            log::warn!("发射 MCP 进程状态事件失败: {e}");
+                        }
+                    }
+                    Some(())
+                })
+                .await;
+                match result {
+                    Ok(Some(())) => auto_started = true,
+                    Ok(None) => {}
+                    Err(e) => log::warn!("MCP 进程守护任务异常: {e}"),
+                }
+            }
+        });
+    }
+
+    fn auto_start(db: &Database) -> bool {
+        let commands = match Self::list_commands(db) {
+            Ok(commands) => commands,
+            Err(e) => {
+                log::warn!("读取 MCP 守护配置失败: {e}");
+                return false;
+            }
+        };
+        let mut started = false;
+        for command in commands.iter().filter(|command| command.auto_start) {
+            match Self::start(db, &command.server_id) {
+                Ok(()) => started = true,
+                Err(e) => log::warn!("自动启动 MCP 进程 {} 失败: {e}", command.server_id),
+            }
+        }
+        started
+    }
+
+    fn find_command(db: &Database, server_id: &str) -> Result<SupervisedCommand, AppError> {
+        Self::list_commands(db)?
+            .into_iter()
+            .find(|command| command.server_id == server_id)
+            .ok_or_else(|| {
+                AppError::localized_with(
+                    "mcp.supervisor.not_configured",
+                    &[("id", server_id.to_string())],
+                )
+            })
+    }
+}
+
+/// 第 n 次连续崩溃后的重启等待：1s、2s、4s … 最长 60s
+fn restart_delay(crashes: u32) -> Duration {
+    let secs = 1u64
+        .checked_shl(crashes.saturating_sub(1))
+        .unwrap_or(u64::MAX)
+        .min(MAX_RESTART_DELAY_SECS);
+    Duration::from_secs(secs)
+}
+
+fn log_path(server_id: &str) -> PathBuf {
+    let name: String = server_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    crate::services::logs::log_dir().join(format!("mcp-{name}.log"))
+}
+
+fn spawn(command: &SupervisedCommand) -> Result<Child, AppError> {
+    let path = log_path(&command.server_id);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+    }
+    let stdout = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| AppError::io(&path, e))?;
+    let stderr = stdout.try_clone().map_err(|e| AppError::io(&path, e))?;
+
+    let mut process = Command::new(&command.command);
+    process
+        .args(&command.args)
+        .envs(&command.env)
+        .stdin(Stdio::null())
+        .stdout(stdout)
+        .stderr(stderr);
+    if let Some(cwd) = command.cwd.as_deref().filter(|cwd| !cwd.trim().is_empty()) {
+        process.current_dir(cwd);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // CREATE_NO_WINDOW：不弹出控制台窗口
+        process.creation_flags(0x0800_0000);
+    }
+
+    process.spawn().map_err(|e| {
+        AppError::localized_with(
+            "mcp.supervisor.spawn_failed",
+            &[("command", command.command.clone()), ("e", e.to_string())],
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart_delay_backs_off_up_to_a_minute() {
+        assert_eq!(restart_delay(1), Duration::from_secs(1));
+        assert_eq!(restart_delay(3), Duration::from_secs(4));
+        assert_eq!(restart_delay(10), Duration::from_secs(60));
+        assert_eq!(restart_delay(200), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn repeated_crashes_give_up() {
+        let mut entry = ProcessEntry {
+            wanted: true,
+            ..Default::default()
+        };
+        for _ in 0..MAX_CONSECUTIVE_CRASHES {
+            entry.record_crash("exit status: 1".to_string());
+            assert_eq!(entry.state(), McpProcessState::Restarting);
+        }
+        entry.record_crash("exit status: 1".to_string());
+        assert_eq!(entry.state(), McpProcessState::Failed);
+        assert!(entry.restart_at.is_none());
+    }
+}
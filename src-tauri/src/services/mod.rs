@@ -1,15 +1,90 @@
+pub mod app_lock;
+pub mod budget;
+pub mod cli_version;
+pub mod clipboard_watch;
 pub mod config;
+pub mod config_snapshot;
+pub mod cost;
+pub mod crash_report;
+pub mod credential_copy;
+pub mod db_backup;
+pub mod db_lease;
+pub mod db_maintenance;
+pub mod device;
+pub mod diagnostics;
 pub mod env_checker;
 pub mod env_manager;
+pub mod gemini_oauth;
+pub mod grok_models;
+pub mod icon;
+pub mod key_check;
+pub mod lint;
+pub mod live_state;
+pub mod logs;
 pub mod mcp;
+pub mod mcp_supervisor;
+pub mod models;
+pub mod onboarding;
+pub mod process_check;
+pub mod project;
 pub mod prompt;
+pub mod prompt_watch;
 pub mod provider;
+pub mod provider_notes;
+pub mod provision;
+pub mod settings_watch;
 pub mod skill;
+pub mod smoke_test;
 pub mod speedtest;
+pub mod stats;
+pub mod sync_report;
+pub mod terminal;
+pub mod update;
 
+pub use app_lock::{AppLockService, AppLockStatus};
+pub use budget::BudgetService;
+pub use cli_version::{CliVersionInfo, CliVersionService};
+pub use clipboard_watch::ClipboardWatchService;
 pub use config::ConfigService;
+pub use config_snapshot::ConfigSnapshotService;
+pub use cost::{CostService, CostSummary};
+pub use crash_report::{CrashReportEntry, CrashReportService};
+pub use credential_copy::{CopiedCredential, CredentialCopyService, CredentialField};
+pub use db_backup::DbBackupService;
+pub use db_lease::DbLeaseService;
+pub use db_maintenance::DbMaintenanceService;
+pub use device::{DeviceInfo, DeviceRecord, DeviceService};
+pub use diagnostics::DiagnosticsService;
+pub use gemini_oauth::GeminiOAuthService;
+pub use grok_models::{GrokModelsRefresh, GrokModelsService};
+pub use icon::IconService;
+pub use key_check::{KeyCheckResult, KeyCheckService};
+pub use lint::{LintConfig, LintReport, LintService, LintWarning};
+pub use live_state::{LiveDrift, LiveStateService};
+pub use logs::LogService;
 pub use mcp::McpService;
+pub use mcp_supervisor::{
+    McpProcessState, McpProcessStatus, McpSupervisorService, SupervisedCommand,
+};
+pub use models::ModelService;
+pub use onboarding::{
+    GuidedImportResult, ImportKind, ImportSelection, OnboardingService, OnboardingState,
+};
+pub use process_check::{ProcessCheckService, RunningCli};
+pub use project::{ProjectApplyResult, ProjectService};
 pub use prompt::PromptService;
-pub use provider::{ProviderService, ProviderSortUpdate};
-pub use skill::{Skill, SkillRepo, SkillService};
+pub use prompt_watch::PromptWatchService;
+pub use provider::{
+    BackfillResolution, CodexAuthInfo, ProviderDraft, ProviderService, ProviderSortUpdate,
+    SwitchOutcome, SwitchPreparation,
+};
+pub use provider_notes::{ProviderAttachment, ProviderNotesService};
+pub use provision::{ProvisionResult, ProvisionService};
+pub use settings_watch::SettingsWatchService;
+pub use skill::{Skill, SkillPreview, SkillRepo, SkillService};
+pub use smoke_test::{SmokeTestResult, SmokeTestService};
 pub use speedtest::{EndpointLatency, SpeedtestService};
+pub use stats::{StatsRange, StatsService, SwitchStats};
+pub use sync_report::{SyncReport, SyncStatus};
+pub use terminal::{CliLaunch, TerminalService};
+pub use update::{UpdateInfo, UpdateService};
@@ -0,0 +1,474 @@
+//! 模型目录
+//!
+//! 维护各应用已知的模型 ID（内置列表 + 用户/远端补充），
+//! 并检查供应商配置中的模型字段，对未知模型名给出警告（不阻止保存）。
+
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::grok_config::GrokSettings;
+use crate::network::NetworkFeature;
+use crate::store::AppState;
+
+const CLAUDE_MODELS: &[&str] = &[
+    "claude-opus-4-5",
+    "claude-opus-4-5-20251101",
+    "claude-opus-4-1",
+    "claude-opus-4-1-20250805",
+    "claude-opus-4-0",
+    "claude-opus-4-20250514",
+    "claude-sonnet-4-5",
+    "claude-sonnet-4-5-20250929",
+    "claude-sonnet-4-0",
+    "claude-sonnet-4-20250514",
+    "claude-3-7-sonnet-latest",
+    "claude-3-7-sonnet-20250219",
+    "claude-haiku-4-5",
+    "claude-haiku-4-5-20251001",
+    "claude-3-5-haiku-latest",
+    "claude-3-5-haiku-20241022",
+    // Claude Code 内置别名
+    "opus",
+    "sonnet",
+    "haiku",
+    "opusplan",
+];
+
+const CODEX_MODELS: &[&str] = &[
+    "gpt-5.1-codex-max",
+    "gpt-5.1-codex",
+    "gpt-5.1-codex-mini",
+    "gpt-5.1",
+    "gpt-5-codex",
+    "gpt-5-codex-mini",
+    "gpt-5",
+    "gpt-5-mini",
+    "o3",
+    "o4-mini",
+    "gpt-4.1",
+];
+
+const GEMINI_MODELS: &[&str] = &[
+    "gemini-3-pro-preview",
+    "gemini-2.5-pro",
+    "gemini-2.5-flash",
+    "gemini-2.5-flash-lite",
+    "gemini-2.0-flash",
+];
+
+const QWEN_MODELS: &[&str] = &[
+    "qwen3-coder-plus",
+    "qwen3-coder-flash",
+    "qwen3-coder-480b-a35b-instruct",
+    "qwen-max",
+    "qwen-plus",
+    "qwen-turbo",
+    "coder-model",
+    "vision-model",
+];
+
+/// Claude 配置中承载模型名的环境变量
+const CLAUDE_MODEL_KEYS: &[&str] = &[
+    "ANTHROPIC_MODEL",
+    "ANTHROPIC_DEFAULT_HAIKU_MODEL",
+    "ANTHROPIC_DEFAULT_SONNET_MODEL",
+    "ANTHROPIC_DEFAULT_OPUS_MODEL",
+    "ANTHROPIC_SMALL_FAST_MODEL",
+];
+
+/// 未知模型警告
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelWarning {
+    /// 配置中的字段（如 `env.ANTHROPIC_MODEL`、`config.model`）
+    pub field: String,
+    pub model: String,
+    /// 可能想写的已知模型（如 `claude-sonnet-4.5` → `claude-sonnet-4-5`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+/// 模型目录服务
+pub struct ModelService;
+
+impl ModelService {
+    /// 内置模型列表
+    pub fn builtin_models(app_type: &AppType) -> Vec<String> {
+        match app_type {
+            AppType::Claude => to_strings(CLAUDE_MODELS),
+            AppType::Codex => to_strings(CODEX_MODELS),
+            AppType::Gemini => to_strings(GEMINI_MODELS),
+            AppType::Grok => GrokSettings::default().models,
+            AppType::Qwen => to_strings(QWEN_MODELS),
+        }
+    }
+
+    fn extra_key(app_type: &AppType) -> String {
+        format!("known_models_{}", app_type.as_str())
+    }
+
+    fn extra_models(state: &AppState, app_type: &AppType) -> Vec<String> {
+        state
+            .db
+            .get_setting(&Self::extra_key(app_type))
+            .ok()
+            .flatten()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    /// 已知模型（内置 + 已记录的补充模型，去重保序）
+    pub fn known_models(state: &AppState, app_type: &AppType) -> Vec<String> {
+        let mut models = Self::builtin_models(app_type);
+        for model in Self::extra_models(state, app_type) {
+            if !models.contains(&model) {
+                models.push(model);
+            }
+        }
+        models
+    }
+
+    /// 记录补充模型（例如从供应商 /models 接口获取到的列表）
+    pub fn remember(
+        state: &AppState,
+        app_type: &AppType,
+        models: &[String],
+    ) -> Result<(), AppError> {
+        let builtin = Self::builtin_models(app_type);
+        let mut extra = Self::extra_models(state, app_type);
+        let before = extra.len();
+        for model in models {
+            let model = model.trim();
+            if model.is_empty()
+                || builtin.iter().any(|m| m == model)
+                || extra.iter().any(|m| m == model)
+            {
+                continue;
+            }
+            extra.push(model.to_string());
+        }
+        if extra.len() == before {
+            return Ok(());
+        }
+        let raw =
+            serde_json::to_string(&extra).map_err(|e| AppError::JsonSerialize { source: e })?;
+        state.db.set_setting(&Self::extra_key(app_type), &raw)
+    }
+
+    /// 检查供应商配置中的模型字段，返回未知模型
+    pub fn validate(state: &AppState, app_type: &AppType, settings: &Value) -> Vec<ModelWarning> {
+        let known = Self::known_models(state, app_type);
+        check_models(&known, extract_model_fields(app_type, settings))
+    }
+
+    /// 配置中的首个模型字段（Claude 优先 `ANTHROPIC_MODEL`，Codex 为 `model`）
+    pub(crate) fn configured_model(app_type: &AppType, settings: &Value) -> Option<String> {
+        extract_model_fields(app_type, settings)
+            .into_iter()
+            .next()
+            .map(|(_, model)| model)
+    }
+
+    /// 检查并记录警告日志
+    pub fn warn_unknown(
+        state: &AppState,
+        app_type: &AppType,
+        provider_name: &str,
+        settings: &Value,
+    ) {
+        for warning in Self::validate(state, app_type, settings) {
+            match &warning.suggestion {
+                Some(suggestion) => log::warn!(
+                    "供应商 '{provider_name}' 的 {} 使用了未知模型 '{}'，是否应为 '{suggestion}'？",
+                    warning.field,
+                    warning.model
+                ),
+                None => log::warn!(
+                    "供应商 '{provider_name}' 的 {} 使用了未知模型 '{}'",
+                    warning.field,
+                    warning.model
+                ),
+            }
+        }
+    }
+}
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+impl ModelService {
+    /// 构造供应商模型列表请求（Anthropic / OpenAI 兼容 / Gemini 各自的鉴权方式）
+    pub(crate) fn models_request(
+        client: &Client,
+        app_type: &AppType,
+        api_key: &str,
+        base_url: &str,
+    ) -> Result<RequestBuilder, AppError> {
+        let url = models_url(app_type, base_url)?;
+        let request = match app_type {
+            AppType::Claude => client
+                .get(url)
+                .header("x-api-key", api_key)
+                .header("anthropic-version", ANTHROPIC_VERSION)
+                // 多数中转只认 Bearer
+                .bearer_auth(api_key),
+            AppType::Gemini => client.get(url).header("x-goog-api-key", api_key),
+            _ => client.get(url).bearer_auth(api_key),
+        };
+        Ok(request)
+    }
+
+    pub(crate) fn build_client() -> Result<Client, AppError> {
+        crate::network::client_builder(NetworkFeature::Models)
+            .redirect(reqwest::redirect::Policy::limited(5))
+            .user_agent(concat!("cc-switch/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| {
+                AppError::localized_with("models.client_create_failed", &[("e", e.to_string())])
+            })
+    }
+
+    /// 从供应商的 /models 接口获取可用模型 ID
+    pub async fn fetch_remote(
+        app_type: &AppType,
+        api_key: &str,
+        base_url: &str,
+    ) -> Result<Vec<String>, AppError> {
+        crate::network::ensure_online()?;
+        let client = Self::build_client()?;
+        let request = Self::models_request(&client, app_type, api_key, base_url)?;
+        let response = crate::network::send_with_retry(NetworkFeature::Models, request)
+            .await
+            .map_err(|e| {
+                AppError::localized_with("models.request_failed", &[("e", e.to_string())])
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(AppError::localized_with(
+                "models.request_status",
+                &[("status", status.to_string())],
+            ));
+        }
+
+        let body: Value = response.json().await.map_err(|e| {
+            AppError::localized_with("models.response_invalid", &[("e", e.to_string())])
+        })?;
+        Ok(parse_model_ids(&body))
+    }
+}
+
+/// 拼接模型列表地址：已带版本段（`/v1`、`/v1beta`）时直接追加 `/models`
+fn models_url(app_type: &AppType, base_url: &str) -> Result<Url, AppError> {
+    let base = match (app_type, base_url.trim().trim_end_matches('/')) {
+        (AppType::Grok, "") => "https://api.x.ai/v1",
+        (AppType::Gemini, "") => "https://generativelanguage.googleapis.com",
+        (AppType::Claude, "") => "https://api.anthropic.com",
+        (_, "") => {
+            return Err(AppError::localized("models.base_url_missing"))
+        }
+        (_, base) => base,
+    };
+
+    let has_version = base.rsplit('/').next().is_some_and(|seg| {
+        seg.strip_prefix('v')
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+    });
+    let url = match (has_version, app_type) {
+        (true, _) => format!("{base}/models"),
+        (false, AppType::Gemini) => format!("{base}/v1beta/models"),
+        (false, _) => format!("{base}/v1/models"),
+    };
+
+    Url::parse(&url).map_err(|e| {
+        AppError::localized_with("models.base_url_invalid", &[("e", e.to_string())])
+    })
+}
+
+/// 解析 `{"data":[{"id":..}]}`（OpenAI / Anthropic）或 `{"models":[{"name":"models/.."}]}`（Gemini）
+fn parse_model_ids(body: &Value) -> Vec<String> {
+    let entries = body
+        .get("data")
+        .or_else(|| body.get("models"))
+        .and_then(Value::as_array);
+    let Some(entries) = entries else {
+        return Vec::new();
+    };
+
+    let mut ids: Vec<String> = entries
+        .iter()
+        .filter_map(|entry| {
+            entry
+                .get("id")
+                .or_else(|| entry.get("name"))
+                .and_then(Value::as_str)
+        })
+        .map(|id| id.strip_prefix("models/").unwrap_or(id).to_string())
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+fn to_strings(models: &[&str]) -> Vec<String> {
+    models.iter().map(|m| m.to_string()).collect()
+}
+
+/// 提取配置中的模型字段：(字段路径, 模型名)
+fn extract_model_fields(app_type: &AppType, settings: &Value) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    let mut push = |field: String, value: Option<&Value>| {
+        if let Some(model) = value.and_then(Value::as_str).map(str::trim) {
+            if !model.is_empty() {
+                fields.push((field, model.to_string()));
+            }
+        }
+    };
+
+    match app_type {
+        AppType::Claude => {
+            let env = settings.get("env");
+            for key in CLAUDE_MODEL_KEYS {
+                push(format!("env.{key}"), env.and_then(|e| e.get(*key)));
+            }
+        }
+        AppType::Codex => {
+            let config = settings
+                .get("config")
+                .and_then(Value::as_str)
+                .and_then(|text| toml::from_str::<toml::Table>(text).ok());
+            if let Some(config) = config {
+                if let Some(model) = config.get("model").and_then(|v| v.as_str()) {
+                    push("config.model".to_string(), Some(&Value::from(model)));
+                }
+                if let Some(profiles) = config.get("profiles").and_then(|v| v.as_table()) {
+                    for (name, profile) in profiles {
+                        if let Some(model) = profile.get("model").and_then(|v| v.as_str()) {
+                            push(
+                                format!("config.profiles.{name}.model"),
+                                Some(&Value::from(model)),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        AppType::Gemini => {
+            push(
+                "env.GEMINI_MODEL".to_string(),
+                settings.get("env").and_then(|e| e.get("GEMINI_MODEL")),
+            );
+        }
+        AppType::Grok => {
+            push("defaultModel".to_string(), settings.get("defaultModel"));
+        }
+        AppType::Qwen => {
+            push(
+                "model.name".to_string(),
+                settings.get("model").and_then(|m| m.get("name")),
+            );
+        }
+    }
+    fields
+}
+
+/// 比较时忽略大小写，并把 `.`/`_` 视为 `-`
+fn normalize_model(model: &str) -> String {
+    model.trim().to_lowercase().replace(['.', '_'], "-")
+}
+
+fn check_models(known: &[String], fields: Vec<(String, String)>) -> Vec<ModelWarning> {
+    fields
+        .into_iter()
+        .filter(|(_, model)| !known.iter().any(|k| k == model))
+        .map(|(field, model)| {
+            let normalized = normalize_model(&model);
+            let suggestion = known
+                .iter()
+                .find(|k| normalize_model(k) == normalized)
+                .cloned();
+            ModelWarning {
+                field,
+                model,
+                suggestion,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn flags_unknown_claude_models_with_suggestion() {
+        let known = ModelService::builtin_models(&AppType::Claude);
+        let settings = json!({
+            "env": {
+                "ANTHROPIC_MODEL": "claude-sonnet-4.5",
+                "ANTHROPIC_DEFAULT_HAIKU_MODEL": "claude-haiku-4-5",
+                "ANTHROPIC_DEFAULT_OPUS_MODEL": "my-relay-model"
+            }
+        });
+        let warnings = check_models(&known, extract_model_fields(&AppType::Claude, &settings));
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].field, "env.ANTHROPIC_MODEL");
+        assert_eq!(warnings[0].suggestion.as_deref(), Some("claude-sonnet-4-5"));
+        assert_eq!(warnings[1].model, "my-relay-model");
+        assert!(warnings[1].suggestion.is_none());
+    }
+
+    #[test]
+    fn builds_models_url_per_vendor() {
+        let url = |app: AppType, base: &str| models_url(&app, base).unwrap().to_string();
+        assert_eq!(
+            url(AppType::Claude, "https://relay.example/"),
+            "https://relay.example/v1/models"
+        );
+        assert_eq!(
+            url(AppType::Codex, "https://relay.example/v1"),
+            "https://relay.example/v1/models"
+        );
+        assert_eq!(
+            url(AppType::Gemini, ""),
+            "https://generativelanguage.googleapis.com/v1beta/models"
+        );
+        assert!(models_url(&AppType::Codex, "  ").is_err());
+    }
+
+    #[test]
+    fn parses_openai_and_gemini_model_lists() {
+        let openai = json!({ "data": [{ "id": "gpt-5" }, { "id": "o3" }, { "id": "gpt-5" }] });
+        assert_eq!(parse_model_ids(&openai), vec!["gpt-5", "o3"]);
+
+        let gemini = json!({ "models": [{ "name": "models/gemini-2.5-pro" }] });
+        assert_eq!(parse_model_ids(&gemini), vec!["gemini-2.5-pro"]);
+
+        assert!(parse_model_ids(&json!({ "error": "nope" })).is_empty());
+    }
+
+    #[test]
+    fn extracts_codex_models_from_toml() {
+        let settings = json!({
+            "config": "model = \"gpt-5-codex\"\n\n[profiles.fast]\nmodel = \"gpt-5-turbo\"\n"
+        });
+        let fields = extract_model_fields(&AppType::Codex, &settings);
+        assert_eq!(
+            fields,
+            vec![
+                ("config.model".to_string(), "gpt-5-codex".to_string()),
+                (
+                    "config.profiles.fast.model".to_string(),
+                    "gpt-5-turbo".to_string()
+                ),
+            ]
+        );
+        let known = ModelService::builtin_models(&AppType::Codex);
+        let warnings = check_models(&known, fields);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].model, "gpt-5-turbo");
+    }
+}
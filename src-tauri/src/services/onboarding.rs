@@ -0,0 +1,313 @@
+//! 首次运行引导
+//!
+//! 检测本机安装了哪些 CLI、哪些已有配置可以导入，并记录启动时自动导入的内容，
+//! 供前端构建设置向导；向导中勾选的项目通过 `run_guided_import` 导入。
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::services::sync_report::SyncReport;
+use crate::services::{McpService, PromptService, ProviderService};
+use crate::store::AppState;
+
+/// 启动时自动导入的记录
+static AUTO_IMPORTED: Mutex<Vec<ImportRecord>> = Mutex::new(Vec::new());
+
+/// 启动导入各阶段的逐应用结果
+static STARTUP_REPORTS: Mutex<Vec<StartupImportReport>> = Mutex::new(Vec::new());
+
+/// 可导入的内容类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportKind {
+    Provider,
+    Mcp,
+    Prompt,
+}
+
+/// 一次导入的结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRecord {
+    pub app: AppType,
+    pub kind: ImportKind,
+    pub count: usize,
+}
+
+/// 导入失败项
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportFailure {
+    pub app: AppType,
+    pub kind: ImportKind,
+    pub error: String,
+}
+
+/// 启动导入中单个应用、单类内容的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupImportReport {
+    pub kind: ImportKind,
+    #[serde(flatten)]
+    pub report: SyncReport,
+}
+
+/// 尚未导入的现有配置
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportableConfig {
+    /// live 配置可导入为默认供应商（该应用尚无供应商）
+    pub provider: bool,
+    /// live 配置中尚未导入的 MCP 服务器数量
+    pub mcp_servers: usize,
+    /// 提示词文件可导入（该应用尚无提示词）
+    pub prompt: bool,
+}
+
+/// 单个 CLI 的安装与配置情况
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliOnboardingStatus {
+    pub app: AppType,
+    /// PATH 中找到的可执行文件
+    pub binary_path: Option<String>,
+    pub config_dir: String,
+    pub config_dir_exists: bool,
+    /// 找到可执行文件或配置目录
+    pub installed: bool,
+    pub importable: ImportableConfig,
+}
+
+/// 首次运行引导状态
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingState {
+    /// 是否已完成引导
+    pub completed: bool,
+    pub clis: Vec<CliOnboardingStatus>,
+    /// 本次启动时自动导入的内容
+    pub auto_imported: Vec<ImportRecord>,
+    /// 本次启动导入的逐应用结果（含失败项）
+    pub startup_imports: Vec<StartupImportReport>,
+}
+
+/// 向导中为单个应用勾选的导入项
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSelection {
+    pub app: AppType,
+    #[serde(default)]
+    pub provider: bool,
+    #[serde(default)]
+    pub mcp: bool,
+    #[serde(default)]
+    pub prompt: bool,
+}
+
+/// 引导导入结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GuidedImportResult {
+    pub imported: Vec<ImportRecord>,
+    pub failed: Vec<ImportFailure>,
+}
+
+fn binary_name(app: &AppType) -> &'static str {
+    match app {
+        AppType::Claude => "claude",
+        AppType::Codex => "codex",
+        AppType::Gemini => "gemini",
+        AppType::Grok => "grok",
+        AppType::Qwen => "qwen",
+    }
+}
+
+fn config_dir(app: &AppType) -> PathBuf {
+    match app {
+        AppType::Claude => crate::config::get_claude_config_dir(),
+        AppType::Codex => crate::codex_config::get_codex_config_dir(),
+        AppType::Gemini => crate::gemini_config::get_gemini_dir(),
+        AppType::Grok => crate::grok_config::get_grok_dir(),
+        AppType::Qwen => crate::qwen_config::get_qwen_dir(),
+    }
+}
+
+/// 导入默认供应商所需的 live 配置文件
+fn live_provider_file(app: &AppType) -> PathBuf {
+    match app {
+        AppType::Claude => crate::config::get_claude_settings_path(),
+        AppType::Codex => crate::codex_config::get_codex_auth_path(),
+        AppType::Gemini => crate::gemini_config::get_gemini_env_path(),
+        AppType::Grok => crate::grok_config::get_grok_settings_path(),
+        AppType::Qwen => crate::qwen_config::get_qwen_settings_path(),
+    }
+}
+
+pub struct OnboardingService;
+
+impl OnboardingService {
+    /// 记录启动时自动导入的内容（数量为 0 时忽略）
+    pub fn record_auto_import(app: &AppType, kind: ImportKind, count: usize) {
+        if count == 0 {
+            return;
+        }
+        AUTO_IMPORTED
+            .lock()
+            .expect("写入自动导入记录锁失败")
+            .push(ImportRecord {
+                app: app.clone(),
+                kind,
+                count,
+            });
+    }
+
+    /// 记录启动导入中单个应用的结果，导入数量大于 0 时同时计入自动导入记录
+    ///
+    /// 供应商导入在 live 配置文件不存在时报错，这种情况记为跳过而非失败。
+    pub fn record_startup_import(
+        app: &AppType,
+        kind: ImportKind,
+        result: Result<usize, &AppError>,
+    ) {
+        let report = match result {
+            Ok(0) => SyncReport::skipped(app.clone()),
+            Ok(count) => {
+                Self::record_auto_import(app, kind, count);
+                SyncReport::success(app.clone())
+            }
+            Err(_) if kind == ImportKind::Provider && !live_provider_file(app).is_file() => {
+                SyncReport::skipped(app.clone())
+            }
+            Err(e) => SyncReport::failed(app.clone(), e),
+        };
+        STARTUP_REPORTS
+            .lock()
+            .expect("写入启动导入结果锁失败")
+            .push(StartupImportReport { kind, report });
+    }
+
+    /// 检测各 CLI 的安装情况与可导入的配置
+    pub fn state(state: &AppState) -> Result<OnboardingState, AppError> {
+        let clis = AppType::all()
+            .into_iter()
+            .map(|app| Self::cli_status(state, app))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(OnboardingState {
+            completed: crate::settings::get_settings().onboarding_completed,
+            clis,
+            auto_imported: AUTO_IMPORTED
+                .lock()
+                .expect("读取自动导入记录锁失败")
+                .clone(),
+            startup_imports: STARTUP_REPORTS
+                .lock()
+                .expect("读取启动导入结果锁失败")
+                .clone(),
+        })
+    }
+
+    fn cli_status(state: &AppState, app: AppType) -> Result<CliOnboardingStatus, AppError> {
+        let binary_path = crate::mcp::find_in_path(binary_name(&app))
+            .map(|path| path.to_string_lossy().to_string());
+        let dir = config_dir(&app);
+        let config_dir_exists = dir.is_dir();
+
+        let provider = state.db.get_all_providers(app.as_str())?.is_empty()
+            && live_provider_file(&app).is_file();
+        let mcp_servers = McpService::count_importable(state, &app).unwrap_or_else(|e| {
+            log::warn!("读取 {} 的 MCP 配置失败: {e}", app.as_str());
+            0
+        });
+        let prompt = state.db.get_prompts(app.as_str())?.is_empty()
+            && crate::prompt_files::prompt_file_path(&app)
+                .ok()
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .is_some_and(|content| !content.trim().is_empty());
+
+        Ok(CliOnboardingStatus {
+            installed: binary_path.is_some() || config_dir_exists,
+            binary_path,
+            config_dir: dir.to_string_lossy().to_string(),
+            config_dir_exists,
+            importable: ImportableConfig {
+                provider,
+                mcp_servers,
+                prompt,
+            },
+            app,
+        })
+    }
+
+    /// 按向导中的选择导入现有配置，完成后标记引导已完成
+    ///
+    /// 单项失败不中止其余导入，失败项在结果中返回。
+    pub fn run_guided_import(
+        state: &AppState,
+        selections: &[ImportSelection],
+    ) -> Result<GuidedImportResult, AppError> {
+        let mut result = GuidedImportResult::default();
+        for selection in selections {
+            let app = &selection.app;
+            let kinds = [
+                (ImportKind::Provider, selection.provider),
+                (ImportKind::Mcp, selection.mcp),
+                (ImportKind::Prompt, selection.prompt),
+            ];
+            for (kind, selected) in kinds {
+                if !selected {
+                    continue;
+                }
+                let imported = match kind {
+                    ImportKind::Provider => {
+                        ProviderService::import_default_config(state, app.clone()).map(usize::from)
+                    }
+                    ImportKind::Mcp => McpService::import_new_from_app(state, app),
+                    ImportKind::Prompt => {
+                        PromptService::import_from_file_on_first_launch(state, app.clone())
+                    }
+                };
+                match imported {
+                    Ok(0) => {}
+                    Ok(count) => result.imported.push(ImportRecord {
+                        app: app.clone(),
+                        kind,
+                        count,
+                    }),
+                    Err(e) => {
+                        log::warn!("引导导入 {} 失败: {e}", app.as_str());
+                        result.failed.push(ImportFailure {
+                            app: app.clone(),
+                            kind,
+                            error: e.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut settings = crate::settings::get_settings();
+        if !settings.onboarding_completed {
+            settings.onboarding_completed = true;
+            crate::settings::update_settings(settings)?;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_defaults_to_nothing() {
+        let selection: ImportSelection =
+            serde_json::from_str(r#"{"app":"codex","mcp":true}"#).expect("parse selection");
+        assert_eq!(selection.app, AppType::Codex);
+        assert!(selection.mcp);
+        assert!(!selection.provider && !selection.prompt);
+    }
+}
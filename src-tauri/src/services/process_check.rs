@@ -0,0 +1,151 @@
+//! 检测正在运行的 CLI 进程
+//!
+//! `claude`、`codex` 等 CLI 会话进行中时切换供应商，会改写其正在读取的配置，
+//! 可能导致会话状态错乱。切换前列出目标应用仍在运行的 CLI 进程（含 PID），
+//! 默认只在切换结果中提示，开启 `blockSwitchWhenCliRunning` 后阻止切换。
+//!
+//! 既匹配原生可执行文件（`codex`、`claude.exe`），也匹配由 node / bun 等运行时
+//! 启动的 npm 包（命令行中出现包路径或 CLI 脚本名）。
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sysinfo::{ProcessRefreshKind, System, UpdateKind};
+
+use crate::app_config::AppType;
+use crate::services::TerminalService;
+
+/// 以脚本方式运行 CLI 的运行时
+const SCRIPT_RUNTIMES: &[&str] = &["node", "bun", "deno"];
+
+/// 命令行中的展示长度上限
+const MAX_COMMAND_LEN: usize = 200;
+
+/// 正在运行的 CLI 进程
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningCli {
+    pub pid: u32,
+    pub name: String,
+    /// 命令行（已脱敏、截断）
+    pub command: String,
+}
+
+pub struct ProcessCheckService;
+
+impl ProcessCheckService {
+    /// 列出目标应用正在运行的 CLI 进程（按 PID 排序，不含自身）
+    pub fn running_clis(app_type: &AppType) -> Vec<RunningCli> {
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessRefreshKind::new().with_cmd(UpdateKind::OnlyIfNotSet),
+        );
+        let own_pid = sysinfo::get_current_pid().ok();
+
+        let mut running: Vec<RunningCli> = system
+            .processes()
+            .iter()
+            .filter(|(pid, _)| Some(**pid) != own_pid)
+            .filter(|(_, process)| is_cli_process(app_type, process.name(), process.cmd()))
+            .map(|(pid, process)| RunningCli {
+                pid: pid.as_u32(),
+                name: process.name().to_string(),
+                command: display_command(process.cmd()),
+            })
+            .collect();
+        running.sort_by_key(|process| process.pid);
+        running
+    }
+}
+
+/// npm 包路径片段
+fn package_markers(app_type: &AppType) -> &'static [&'static str] {
+    match app_type {
+        AppType::Claude => &["@anthropic-ai/claude-code"],
+        AppType::Codex => &["@openai/codex"],
+        AppType::Gemini => &["@google/gemini-cli"],
+        AppType::Grok => &["@vibe-kit/grok-cli"],
+        AppType::Qwen => &["@qwen-code/qwen-code"],
+    }
+}
+
+/// 去掉目录与 `.exe` 后缀
+fn executable_stem(value: &str) -> &str {
+    let name = Path::new(value)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(value);
+    match name
+        .len()
+        .checked_sub(4)
+        .and_then(|end| name.split_at_checked(end))
+    {
+        Some((stem, ext)) if ext.eq_ignore_ascii_case(".exe") => stem,
+        _ => name,
+    }
+}
+
+fn is_cli_process(app_type: &AppType, name: &str, cmd: &[String]) -> bool {
+    let cli = TerminalService::cli_name(app_type);
+    if executable_stem(name) == cli {
+        return true;
+    }
+
+    let runtime = cmd
+        .first()
+        .map(|program| executable_stem(program))
+        .unwrap_or_else(|| executable_stem(name));
+    if !SCRIPT_RUNTIMES.contains(&runtime) {
+        return false;
+    }
+    let markers = package_markers(app_type);
+    cmd.iter().skip(1).any(|arg| {
+        let normalized = arg.replace('\\', "/");
+        executable_stem(&normalized) == cli
+            || markers.iter().any(|marker| normalized.contains(marker))
+    })
+}
+
+fn display_command(cmd: &[String]) -> String {
+    let line = crate::scrub::scrub_secrets(&cmd.join(" "));
+    if line.chars().count() <= MAX_COMMAND_LEN {
+        return line;
+    }
+    let truncated: String = line.chars().take(MAX_COMMAND_LEN).collect();
+    format!("{truncated}…")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn matches_native_executables() {
+        assert!(is_cli_process(&AppType::Codex, "codex", &args(&["codex"])));
+        assert!(is_cli_process(&AppType::Claude, "claude.exe", &[]));
+        assert!(!is_cli_process(&AppType::Claude, "Claude", &[]));
+        assert!(!is_cli_process(&AppType::Claude, "codex", &[]));
+    }
+
+    #[test]
+    fn matches_scripts_run_by_node() {
+        let cmd = args(&[
+            "/usr/bin/node",
+            "/usr/lib/node_modules/@google/gemini-cli/dist/index.js",
+        ]);
+        assert!(is_cli_process(&AppType::Gemini, "node", &cmd));
+        assert!(!is_cli_process(&AppType::Qwen, "node", &cmd));
+
+        let cmd = args(&["node.exe", r"C:\Users\me\AppData\Roaming\npm\claude"]);
+        assert!(is_cli_process(&AppType::Claude, "node.exe", &cmd));
+        assert!(!is_cli_process(
+            &AppType::Claude,
+            "python3",
+            &args(&["python3", "claude"])
+        ));
+    }
+}
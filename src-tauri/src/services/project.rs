@@ -0,0 +1,237 @@
+//! 项目感知：按项目目录切换各应用的当前供应商
+//!
+//! 每个项目记录一个目录及各应用期望使用的供应商；`apply` 按目录（含子目录）
+//! 匹配项目并逐个切换，最近应用的项目记为当前项目，供托盘显示。
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+/// 最近应用的项目目录（settings 表键名）
+const ACTIVE_PROJECT_KEY: &str = "active_project";
+
+/// 项目：目录与各应用期望供应商的映射
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    /// 项目目录（绝对路径，作为唯一标识）
+    pub path: String,
+    pub name: String,
+    /// 应用类型 -> 供应商 ID
+    #[serde(default)]
+    pub providers: IndexMap<String, String>,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+/// 应用项目时单个应用切换失败的详情
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSwitchFailure {
+    pub app: String,
+    pub provider_id: String,
+    pub error: String,
+}
+
+/// 应用项目的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectApplyResult {
+    pub project: Project,
+    /// 实际切换了供应商的应用
+    pub switched: Vec<String>,
+    /// 已是期望供应商、无需切换的应用
+    pub unchanged: Vec<String>,
+    pub failed: Vec<ProjectSwitchFailure>,
+}
+
+pub struct ProjectService;
+
+impl ProjectService {
+    /// 获取所有项目
+    pub fn list(state: &AppState) -> Result<IndexMap<String, Project>, AppError> {
+        state.db.get_projects()
+    }
+
+    /// 新增或更新项目，返回保存后的项目
+    pub fn upsert(state: &AppState, mut project: Project) -> Result<Project, AppError> {
+        project.path = normalize_project_path(&project.path)?;
+        for app in project.providers.keys() {
+            AppType::from_str(app)?;
+        }
+        project.providers.retain(|_, id| !id.trim().is_empty());
+        if project.name.trim().is_empty() {
+            project.name = Path::new(&project.path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| project.path.clone());
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        project.created_at = state
+            .db
+            .get_projects()?
+            .get(&project.path)
+            .map(|existing| existing.created_at)
+            .unwrap_or(now);
+        project.updated_at = now;
+
+        state.db.save_project(&project)?;
+        Ok(project)
+    }
+
+    /// 删除项目（若为当前项目则一并清除）
+    pub fn delete(state: &AppState, path: &str) -> Result<(), AppError> {
+        let path = normalize_project_path(path)?;
+        state.db.delete_project(&path)?;
+        if state.db.get_setting(ACTIVE_PROJECT_KEY)?.as_deref() == Some(path.as_str()) {
+            state.db.set_setting(ACTIVE_PROJECT_KEY, "")?;
+        }
+        Ok(())
+    }
+
+    /// 将 `path` 所在项目的供应商应用到各应用
+    ///
+    /// `path` 可以是项目目录或其子目录（匹配最深的项目）。单个应用切换失败
+    /// 不影响其他应用，失败详情记录在结果中。
+    pub fn apply(state: &AppState, path: &str) -> Result<ProjectApplyResult, AppError> {
+        let path = normalize_project_path(path)?;
+        let projects = state.db.get_projects()?;
+        let project = find_project(&projects, &path)
+            .cloned()
+            .ok_or_else(|| AppError::localized_with("project.not_found", &[("path", path)]))?;
+
+        let mut result = ProjectApplyResult {
+            project: project.clone(),
+            switched: Vec::new(),
+            unchanged: Vec::new(),
+            failed: Vec::new(),
+        };
+        for (app, provider_id) in &project.providers {
+            let outcome = AppType::from_str(app).and_then(|app_type| {
+                let current =
+                    crate::settings::get_effective_current_provider(&state.db, &app_type)?;
+                if current.as_deref() == Some(provider_id.as_str()) {
+                    return Ok(false);
+                }
+                ProviderService::switch(state, app_type, provider_id).map(|_| true)
+            });
+            match outcome {
+                Ok(true) => result.switched.push(app.clone()),
+                Ok(false) => result.unchanged.push(app.clone()),
+                Err(e) => {
+                    log::warn!("项目 {} 切换 {app} 供应商失败: {e}", project.name);
+                    result.failed.push(ProjectSwitchFailure {
+                        app: app.clone(),
+                        provider_id: provider_id.clone(),
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        state.db.set_setting(ACTIVE_PROJECT_KEY, &project.path)?;
+        log::info!(
+            "已应用项目 {}：切换 {} 个应用，失败 {} 个",
+            project.name,
+            result.switched.len(),
+            result.failed.len()
+        );
+        Ok(result)
+    }
+
+    /// 当前项目：最近应用且各应用仍使用其供应商的项目
+    pub fn active(state: &AppState) -> Result<Option<Project>, AppError> {
+        let Some(path) = state
+            .db
+            .get_setting(ACTIVE_PROJECT_KEY)?
+            .filter(|path| !path.is_empty())
+        else {
+            return Ok(None);
+        };
+        let Some(project) = state.db.get_projects()?.shift_remove(&path) else {
+            return Ok(None);
+        };
+
+        for (app, provider_id) in &project.providers {
+            let Ok(app_type) = AppType::from_str(app) else {
+                continue;
+            };
+            let current = crate::settings::get_effective_current_provider(&state.db, &app_type)?;
+            if current.as_deref() != Some(provider_id.as_str()) {
+                return Ok(None);
+            }
+        }
+        Ok(Some(project))
+    }
+}
+
+/// 规范化项目目录：要求绝对路径，去除 `.` 与末尾分隔符
+fn normalize_project_path(raw: &str) -> Result<String, AppError> {
+    let path = Path::new(raw.trim());
+    if !path.is_absolute() {
+        return Err(AppError::localized_with(
+            "project.path.not_absolute",
+            &[("path", raw.to_string())],
+        ));
+    }
+    let normalized: PathBuf = path.components().collect();
+    Ok(normalized.to_string_lossy().to_string())
+}
+
+/// 查找包含 `path` 的最深项目
+fn find_project<'a>(projects: &'a IndexMap<String, Project>, path: &str) -> Option<&'a Project> {
+    let path = Path::new(path);
+    projects
+        .values()
+        .filter(|project| path.starts_with(&project.path))
+        .max_by_key(|project| Path::new(&project.path).components().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(path: &str) -> Project {
+        Project {
+            path: path.to_string(),
+            name: path.to_string(),
+            providers: IndexMap::new(),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_project_prefers_deepest_match() {
+        let projects: IndexMap<String, Project> = ["/work", "/work/api", "/work/apix"]
+            .into_iter()
+            .map(|path| (path.to_string(), project(path)))
+            .collect();
+
+        let found = find_project(&projects, "/work/api/src").expect("match");
+        assert_eq!(found.path, "/work/api");
+        let found = find_project(&projects, "/work/web").expect("match");
+        assert_eq!(found.path, "/work");
+        assert!(find_project(&projects, "/home").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn normalize_project_path_requires_absolute_path() {
+        assert_eq!(
+            normalize_project_path(" /work/api/./ ").expect("normalize"),
+            "/work/api"
+        );
+        assert!(normalize_project_path("work/api").is_err());
+    }
+}
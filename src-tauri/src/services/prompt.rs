@@ -1,10 +1,14 @@
 use indexmap::IndexMap;
+use serde::Serialize;
+use std::path::Path;
 
 use crate::app_config::AppType;
 use crate::config::write_text_file;
 use crate::error::AppError;
-use crate::prompt::Prompt;
+use crate::prompt::{Prompt, PromptSet, PromptSetMode};
 use crate::prompt_files::prompt_file_path;
+use crate::prompt_frontmatter;
+use crate::settings::PromptWatchMode;
 use crate::store::AppState;
 
 /// 安全地获取当前 Unix 时间戳
@@ -15,6 +19,69 @@ fn get_unix_timestamp() -> Result<i64, AppError> {
         .map_err(|e| AppError::Message(format!("Failed to get system time: {e}")))
 }
 
+/// 按提示词组的模式组合成员内容
+///
+/// `concat` 模式按顺序以空行拼接全部成员；`select` 模式仅取选中的成员（未选中或已失效时取第一个）。
+/// 已删除的成员会被跳过。
+pub fn compose_prompt_set(
+    set: &PromptSet,
+    prompts: &IndexMap<String, Prompt>,
+) -> Result<String, AppError> {
+    let members: Vec<&Prompt> = set
+        .prompt_ids
+        .iter()
+        .filter_map(|id| prompts.get(id))
+        .collect();
+
+    let content = match set.mode {
+        PromptSetMode::Concat => members
+            .iter()
+            .map(|prompt| prompt.content.trim_end())
+            .filter(|content| !content.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        PromptSetMode::Select => set
+            .selected_prompt_id
+            .as_ref()
+            .and_then(|id| members.iter().find(|prompt| &prompt.id == id))
+            .or(members.first())
+            .map(|prompt| prompt.content.clone())
+            .unwrap_or_default(),
+    };
+
+    if content.trim().is_empty() {
+        return Err(AppError::InvalidInput(format!(
+            "提示词组 {} 没有可写入的内容",
+            set.id
+        )));
+    }
+    Ok(content)
+}
+
+/// 应用提示词文件的当前来源
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptLiveState {
+    pub app: String,
+    /// "prompt" | "set"
+    pub kind: String,
+    /// 提示词所属应用（多目标提示词写入其他应用时与 `app` 不同）
+    pub source_app: String,
+    pub id: String,
+    pub name: String,
+}
+
+/// 提示词文件外部修改的处理结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptFileSync {
+    pub app: String,
+    /// "backfill" | "revision"
+    pub action: String,
+    /// 被更新或新建的提示词 ID
+    pub prompt_id: String,
+}
+
 pub struct PromptService;
 
 impl PromptService {
@@ -40,6 +107,16 @@ impl PromptService {
         if is_enabled {
             let target_path = prompt_file_path(&app)?;
             write_text_file(&target_path, &prompt.content)?;
+
+            // 同步目标中未被其他提示词占用的应用一并更新
+            for target in prompt.apps.targets_except(&app) {
+                if Self::native_live(state, &target)?.is_none() {
+                    write_text_file(&prompt_file_path(&target)?, &prompt.content)?;
+                }
+            }
+        } else {
+            // 属于已启用提示词组的成员时，重新组合写入
+            Self::refresh_active_set(state, &app, &prompt.id)?;
         }
 
         Ok(())
@@ -55,12 +132,247 @@ impl PromptService {
         }
 
         state.db.delete_prompt(app.as_str(), id)?;
+        Self::refresh_active_set(state, &app, id)?;
+        state.db.remove_prompt_from_sets(app.as_str(), id)?;
         Ok(())
     }
 
     pub fn enable_prompt(state: &AppState, app: AppType, id: &str) -> Result<(), AppError> {
         // 回填当前 live 文件内容到已启用的提示词，或创建备份
+        Self::backfill_live_content(state, &app)?;
         let target_path = prompt_file_path(&app)?;
+
+        // 启用目标提示词并写入文件
+        let mut prompts = state.db.get_prompts(app.as_str())?;
+
+        for prompt in prompts.values_mut() {
+            prompt.enabled = false;
+        }
+
+        let enabled_prompt = if let Some(prompt) = prompts.get_mut(id) {
+            prompt.enabled = true;
+            write_text_file(&target_path, &prompt.content)?; // 原子写入
+            state.db.save_prompt(app.as_str(), prompt)?;
+            prompt.clone()
+        } else {
+            return Err(AppError::InvalidInput(format!("提示词 {id} 不存在")));
+        };
+
+        // Save all prompts to disable others
+        for (_, prompt) in prompts.iter() {
+            state.db.save_prompt(app.as_str(), prompt)?;
+        }
+        state.db.clear_active_prompt_set(app.as_str())?;
+        Self::unlink_target(state, &app, &app, id)?;
+
+        // 多目标提示词：接管每个同步目标应用的提示词文件
+        for target in enabled_prompt.apps.targets_except(&app) {
+            Self::backfill_live_content(state, &target)?;
+            let mut target_prompts = state.db.get_prompts(target.as_str())?;
+            for prompt in target_prompts.values_mut().filter(|p| p.enabled) {
+                prompt.enabled = false;
+                state.db.save_prompt(target.as_str(), prompt)?;
+            }
+            state.db.clear_active_prompt_set(target.as_str())?;
+            Self::unlink_target(state, &target, &app, id)?;
+            write_text_file(&prompt_file_path(&target)?, &enabled_prompt.content)?;
+            log::info!("提示词 {id} 已同步写入 {} 的提示词文件", target.as_str());
+        }
+
+        Ok(())
+    }
+
+    /// live 提示词文件被外部修改后同步回数据库
+    ///
+    /// 文件内容与当前启用的提示词不一致时：`Backfill` 模式回填到已启用的提示词，
+    /// `Revision` 模式（或内容来自提示词组、其他应用的多目标提示词时）另存为带时间戳的新提示词。
+    /// 没有启用任何提示词时不处理，由下次启用时的回填逻辑负责备份。
+    pub fn sync_from_live_file(
+        state: &AppState,
+        app: &AppType,
+        mode: PromptWatchMode,
+    ) -> Result<Option<PromptFileSync>, AppError> {
+        if mode == PromptWatchMode::Off {
+            return Ok(None);
+        }
+        let path = prompt_file_path(app)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let live_content = std::fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+        if live_content.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let mut prompts = state.db.get_prompts(app.as_str())?;
+        let (expected, source_name, backfill_id) =
+            if let Some(prompt) = prompts.values().find(|p| p.enabled) {
+                (
+                    prompt.content.clone(),
+                    prompt.name.clone(),
+                    Some(prompt.id.clone()),
+                )
+            } else if let Some(set) = state
+                .db
+                .get_prompt_sets(app.as_str())?
+                .into_values()
+                .find(|set| set.enabled)
+            {
+                let content = compose_prompt_set(&set, &prompts).unwrap_or_default();
+                (content, set.name, None)
+            } else if let Some((_, prompt)) = Self::linked_prompt(state, app)? {
+                (prompt.content, prompt.name, None)
+            } else {
+                return Ok(None);
+            };
+        if expected.trim() == live_content.trim() {
+            return Ok(None);
+        }
+
+        let timestamp = get_unix_timestamp()?;
+        let (action, prompt_id) = match (mode, backfill_id) {
+            (PromptWatchMode::Backfill, Some(id)) => {
+                let Some(mut prompt) = prompts.shift_remove(&id) else {
+                    return Ok(None);
+                };
+                prompt.content = live_content;
+                prompt.updated_at = Some(timestamp);
+                // 经由 upsert 写回，多目标提示词的其他应用文件一并更新
+                Self::upsert_prompt(state, app.clone(), &id, prompt)?;
+                log::info!("{} 提示词文件已被外部修改，回填到: {id}", app.as_str());
+                ("backfill", id)
+            }
+            _ => {
+                if prompts
+                    .values()
+                    .any(|p| p.content.trim() == live_content.trim())
+                {
+                    return Ok(None);
+                }
+                let id = format!("revision-{timestamp}");
+                let revision = Prompt {
+                    id: id.clone(),
+                    name: format!(
+                        "{source_name} 修订 {}",
+                        chrono::Local::now().format("%Y-%m-%d %H:%M")
+                    ),
+                    content: live_content,
+                    description: Some("提示词文件被外部修改时自动保存".to_string()),
+                    enabled: false,
+                    tags: Vec::new(),
+                    target_apps: Vec::new(),
+                    apps: Default::default(),
+                    created_at: Some(timestamp),
+                    updated_at: Some(timestamp),
+                };
+                state.db.save_prompt(app.as_str(), &revision)?;
+                log::info!("{} 提示词文件已被外部修改，另存为: {id}", app.as_str());
+                ("revision", id)
+            }
+        };
+
+        Ok(Some(PromptFileSync {
+            app: app.as_str().to_string(),
+            action: action.to_string(),
+            prompt_id,
+        }))
+    }
+
+    /// 各应用当前写入 live 文件的提示词或提示词组（统一的启用状态）
+    pub fn live_state(state: &AppState) -> Result<Vec<PromptLiveState>, AppError> {
+        let mut result = Vec::new();
+        for app in AppType::all() {
+            let live = match Self::native_live(state, &app)? {
+                Some(live) => Some(live),
+                None => Self::linked_prompt(state, &app)?.map(|(source, prompt)| PromptLiveState {
+                    app: app.as_str().to_string(),
+                    kind: "prompt".to_string(),
+                    source_app: source.as_str().to_string(),
+                    id: prompt.id,
+                    name: prompt.name,
+                }),
+            };
+            result.extend(live);
+        }
+        Ok(result)
+    }
+
+    /// 应用自身已启用的提示词或提示词组
+    fn native_live(state: &AppState, app: &AppType) -> Result<Option<PromptLiveState>, AppError> {
+        let entry = |kind: &str, id: &str, name: &str| PromptLiveState {
+            app: app.as_str().to_string(),
+            kind: kind.to_string(),
+            source_app: app.as_str().to_string(),
+            id: id.to_string(),
+            name: name.to_string(),
+        };
+        if let Some(prompt) = state
+            .db
+            .get_prompts(app.as_str())?
+            .values()
+            .find(|p| p.enabled)
+        {
+            return Ok(Some(entry("prompt", &prompt.id, &prompt.name)));
+        }
+        Ok(state
+            .db
+            .get_prompt_sets(app.as_str())?
+            .values()
+            .find(|set| set.enabled)
+            .map(|set| entry("set", &set.id, &set.name)))
+    }
+
+    /// 其他应用中已启用、且同步目标包含 `target` 的提示词
+    fn linked_prompt(
+        state: &AppState,
+        target: &AppType,
+    ) -> Result<Option<(AppType, Prompt)>, AppError> {
+        for source in AppType::all().into_iter().filter(|app| app != target) {
+            if let Some(prompt) = state
+                .db
+                .get_prompts(source.as_str())?
+                .into_values()
+                .find(|p| p.enabled && p.apps.is_enabled_for(target))
+            {
+                return Ok(Some((source, prompt)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 从其他已启用提示词的同步目标中移除 `target`，保证每个应用只有一个 live 来源
+    fn unlink_target(
+        state: &AppState,
+        target: &AppType,
+        keep_app: &AppType,
+        keep_id: &str,
+    ) -> Result<(), AppError> {
+        for source in AppType::all().into_iter().filter(|app| app != target) {
+            for mut prompt in state.db.get_prompts(source.as_str())?.into_values() {
+                if !prompt.enabled
+                    || !prompt.apps.is_enabled_for(target)
+                    || (&source == keep_app && prompt.id == keep_id)
+                {
+                    continue;
+                }
+                prompt.apps.set_enabled_for(target, false);
+                state.db.save_prompt(source.as_str(), &prompt)?;
+                log::info!(
+                    "提示词 {}（{}）不再同步到 {}",
+                    prompt.id,
+                    source.as_str(),
+                    target.as_str()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// 回填当前 live 文件内容到已启用的提示词；没有已启用项时创建一次备份
+    ///
+    /// 已启用提示词组时 live 内容由组成员组合而来，无需备份。
+    fn backfill_live_content(state: &AppState, app: &AppType) -> Result<(), AppError> {
+        let target_path = prompt_file_path(app)?;
         if target_path.exists() {
             if let Ok(live_content) = std::fs::read_to_string(&target_path) {
                 if !live_content.trim().is_empty() {
@@ -77,7 +389,9 @@ impl PromptService {
                         enabled_prompt.updated_at = Some(timestamp);
                         log::info!("回填 live 提示词内容到已启用项: {enabled_id}");
                         state.db.save_prompt(app.as_str(), enabled_prompt)?;
-                    } else {
+                    } else if !Self::has_active_set(state, app)?
+                        && Self::linked_prompt(state, app)?.is_none()
+                    {
                         // 没有已启用的提示词，则创建一次备份（避免重复备份）
                         let content_exists = prompts
                             .values()
@@ -97,6 +411,9 @@ impl PromptService {
                                 content: live_content,
                                 description: Some("自动备份的原始提示词".to_string()),
                                 enabled: false,
+                                tags: Vec::new(),
+                                target_apps: Vec::new(),
+                                apps: Default::default(),
                                 created_at: Some(timestamp),
                                 updated_at: Some(timestamp),
                             };
@@ -107,30 +424,129 @@ impl PromptService {
                 }
             }
         }
+        Ok(())
+    }
 
-        // 启用目标提示词并写入文件
-        let mut prompts = state.db.get_prompts(app.as_str())?;
+    pub fn get_prompt_sets(
+        state: &AppState,
+        app: AppType,
+    ) -> Result<IndexMap<String, PromptSet>, AppError> {
+        state.db.get_prompt_sets(app.as_str())
+    }
 
-        for prompt in prompts.values_mut() {
-            prompt.enabled = false;
+    /// 新增或更新提示词组；已启用的组会立即重新写入 live 文件
+    pub fn upsert_prompt_set(
+        state: &AppState,
+        app: AppType,
+        mut set: PromptSet,
+    ) -> Result<(), AppError> {
+        if set.name.trim().is_empty() {
+            return Err(AppError::InvalidInput("提示词组名称不能为空".to_string()));
+        }
+        let prompts = state.db.get_prompts(app.as_str())?;
+        if let Some(missing) = set.prompt_ids.iter().find(|id| !prompts.contains_key(*id)) {
+            return Err(AppError::InvalidInput(format!("提示词 {missing} 不存在")));
         }
 
-        if let Some(prompt) = prompts.get_mut(id) {
-            prompt.enabled = true;
-            write_text_file(&target_path, &prompt.content)?; // 原子写入
-            state.db.save_prompt(app.as_str(), prompt)?;
-        } else {
-            return Err(AppError::InvalidInput(format!("提示词 {id} 不存在")));
+        let timestamp = get_unix_timestamp()?;
+        let existing = state.db.get_prompt_sets(app.as_str())?;
+        let current = existing.get(&set.id);
+        set.created_at = current
+            .and_then(|s| s.created_at)
+            .or(set.created_at)
+            .or(Some(timestamp));
+        set.updated_at = Some(timestamp);
+        set.enabled = current.map(|s| s.enabled).unwrap_or(false);
+
+        if set.enabled {
+            let content = compose_prompt_set(&set, &prompts)?;
+            write_text_file(&prompt_file_path(&app)?, &content)?;
         }
+        state.db.save_prompt_set(app.as_str(), &set)
+    }
 
-        // Save all prompts to disable others
-        for (_, prompt) in prompts.iter() {
+    pub fn delete_prompt_set(state: &AppState, app: AppType, id: &str) -> Result<(), AppError> {
+        let sets = state.db.get_prompt_sets(app.as_str())?;
+        if sets.get(id).is_some_and(|set| set.enabled) {
+            return Err(AppError::InvalidInput(
+                "无法删除已启用的提示词组".to_string(),
+            ));
+        }
+        state.db.delete_prompt_set(app.as_str(), id)
+    }
+
+    /// 启用提示词组：按顺序组合成员（或写入选中的成员）到 live 提示词文件
+    ///
+    /// `selected_prompt_id` 用于 `select` 模式下快速切换成员。
+    /// 单个提示词与提示词组互斥，启用组后所有提示词均标记为未启用。
+    pub fn apply_prompt_set(
+        state: &AppState,
+        app: AppType,
+        id: &str,
+        selected_prompt_id: Option<String>,
+    ) -> Result<(), AppError> {
+        let mut sets = state.db.get_prompt_sets(app.as_str())?;
+        let Some(mut set) = sets.shift_remove(id) else {
+            return Err(AppError::InvalidInput(format!("提示词组 {id} 不存在")));
+        };
+        if let Some(selected) = selected_prompt_id {
+            if !set.prompt_ids.contains(&selected) {
+                return Err(AppError::InvalidInput(format!(
+                    "提示词 {selected} 不在提示词组 {id} 中"
+                )));
+            }
+            set.selected_prompt_id = Some(selected);
+        }
+
+        Self::backfill_live_content(state, &app)?;
+
+        let mut prompts = state.db.get_prompts(app.as_str())?;
+        let content = compose_prompt_set(&set, &prompts)?;
+        write_text_file(&prompt_file_path(&app)?, &content)?;
+
+        for prompt in prompts.values_mut().filter(|p| p.enabled) {
+            prompt.enabled = false;
             state.db.save_prompt(app.as_str(), prompt)?;
         }
+        state.db.clear_active_prompt_set(app.as_str())?;
+        set.enabled = true;
+        state.db.save_prompt_set(app.as_str(), &set)?;
 
+        log::info!("已启用提示词组 {id}（{}）", app.as_str());
         Ok(())
     }
 
+    fn has_active_set(state: &AppState, app: &AppType) -> Result<bool, AppError> {
+        Ok(state
+            .db
+            .get_prompt_sets(app.as_str())?
+            .values()
+            .any(|set| set.enabled))
+    }
+
+    /// 已启用提示词组的成员 `prompt_id` 变化后重新写入 live 文件
+    fn refresh_active_set(
+        state: &AppState,
+        app: &AppType,
+        prompt_id: &str,
+    ) -> Result<(), AppError> {
+        let sets = state.db.get_prompt_sets(app.as_str())?;
+        let Some(set) = sets
+            .values()
+            .find(|set| set.enabled && set.prompt_ids.iter().any(|id| id == prompt_id))
+        else {
+            return Ok(());
+        };
+        let prompts = state.db.get_prompts(app.as_str())?;
+        match compose_prompt_set(set, &prompts) {
+            Ok(content) => write_text_file(&prompt_file_path(app)?, &content),
+            Err(e) => {
+                log::warn!("提示词组 {} 无法组合，保留当前 live 文件: {e}", set.id);
+                Ok(())
+            }
+        }
+    }
+
     pub fn import_from_file(state: &AppState, app: AppType) -> Result<String, AppError> {
         let file_path = prompt_file_path(&app)?;
 
@@ -142,16 +558,27 @@ impl PromptService {
             std::fs::read_to_string(&file_path).map_err(|e| AppError::io(&file_path, e))?;
         let timestamp = get_unix_timestamp()?;
 
+        // 解析可选的 YAML frontmatter（title / description / tags / apps）
+        let (meta, body) = prompt_frontmatter::parse(&content);
+        let meta = meta.unwrap_or_default();
+
         let id = format!("imported-{timestamp}");
         let prompt = Prompt {
             id: id.clone(),
-            name: format!(
-                "导入的提示词 {}",
-                chrono::Local::now().format("%Y-%m-%d %H:%M")
-            ),
-            content,
-            description: Some("从现有配置文件导入".to_string()),
+            name: meta.title.unwrap_or_else(|| {
+                format!(
+                    "导入的提示词 {}",
+                    chrono::Local::now().format("%Y-%m-%d %H:%M")
+                )
+            }),
+            content: body.to_string(),
+            description: meta
+                .description
+                .or_else(|| Some("从现有配置文件导入".to_string())),
             enabled: false,
+            tags: meta.tags,
+            target_apps: meta.apps,
+            apps: Default::default(),
             created_at: Some(timestamp),
             updated_at: Some(timestamp),
         };
@@ -160,6 +587,22 @@ impl PromptService {
         Ok(id)
     }
 
+    /// 将提示词连同 frontmatter 元数据导出为 Markdown 文件
+    pub fn export_to_file(
+        state: &AppState,
+        app: AppType,
+        id: &str,
+        target: &Path,
+    ) -> Result<(), AppError> {
+        let prompts = state.db.get_prompts(app.as_str())?;
+        let prompt = prompts
+            .get(id)
+            .ok_or_else(|| AppError::InvalidInput(format!("提示词 {id} 不存在")))?;
+        write_text_file(target, &prompt_frontmatter::render(prompt))?;
+        log::info!("已导出提示词 {id} 到 {}", target.display());
+        Ok(())
+    }
+
     pub fn get_current_file_content(app: AppType) -> Result<Option<String>, AppError> {
         let file_path = prompt_file_path(&app)?;
         if !file_path.exists() {
@@ -217,6 +660,9 @@ impl PromptService {
             content,
             description: Some("Automatically imported on first launch".to_string()),
             enabled: true, // 首次导入时自动启用
+            tags: Vec::new(),
+            target_apps: Vec::new(),
+            apps: Default::default(),
             created_at: Some(timestamp),
             updated_at: Some(timestamp),
         };
@@ -228,3 +674,76 @@ impl PromptService {
         Ok(1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(id: &str, content: &str) -> Prompt {
+        Prompt {
+            id: id.to_string(),
+            name: id.to_string(),
+            content: content.to_string(),
+            description: None,
+            enabled: false,
+            tags: Vec::new(),
+            target_apps: Vec::new(),
+            apps: Default::default(),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn compose_prompt_set_concats_in_order_or_selects_member() {
+        let mut prompts = IndexMap::new();
+        prompts.insert("base".to_string(), prompt("base", "# Base\n"));
+        prompts.insert("task".to_string(), prompt("task", "Task addendum"));
+
+        let mut set = PromptSet {
+            id: "stack".to_string(),
+            name: "Stack".to_string(),
+            description: None,
+            mode: PromptSetMode::Concat,
+            prompt_ids: vec!["task".into(), "missing".into(), "base".into()],
+            selected_prompt_id: None,
+            enabled: false,
+            created_at: None,
+            updated_at: None,
+        };
+        assert_eq!(
+            compose_prompt_set(&set, &prompts).unwrap(),
+            "Task addendum\n\n# Base"
+        );
+
+        set.mode = PromptSetMode::Select;
+        assert_eq!(compose_prompt_set(&set, &prompts).unwrap(), "Task addendum");
+        set.selected_prompt_id = Some("base".into());
+        assert_eq!(compose_prompt_set(&set, &prompts).unwrap(), "# Base\n");
+
+        set.prompt_ids = vec!["missing".into()];
+        assert!(compose_prompt_set(&set, &prompts).is_err());
+    }
+
+    #[test]
+    fn prompt_apps_targets_exclude_owner_and_round_trip() {
+        let mut shared = prompt("shared", "Shared rules");
+        shared.apps.set_enabled_for(&AppType::Claude, true);
+        shared.apps.set_enabled_for(&AppType::Codex, true);
+        shared.apps.set_enabled_for(&AppType::Gemini, true);
+
+        assert_eq!(
+            shared.apps.targets_except(&AppType::Claude),
+            vec![AppType::Codex, AppType::Gemini]
+        );
+
+        let value = serde_json::to_value(&shared).unwrap();
+        assert_eq!(value["apps"]["codex"], true);
+        let parsed: Prompt = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.apps, shared.apps);
+
+        // 未设置同步目标时不输出 apps 字段
+        let plain = serde_json::to_value(prompt("plain", "x")).unwrap();
+        assert!(plain.get("apps").is_none());
+    }
+}
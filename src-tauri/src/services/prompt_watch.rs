@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use tauri::{AppHandle, Emitter};
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::prompt_files::prompt_file_path;
+use crate::services::prompt::{PromptFileSync, PromptService};
+use crate::settings::PromptWatchMode;
+use crate::store::AppState;
+
+/// 提示词文件检查间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// 各提示词文件最近一次观察到的修改时间
+type SeenFiles = HashMap<PathBuf, SystemTime>;
+
+/// 提示词文件监听
+///
+/// 定期检查各应用的提示词文件（CLAUDE.md、AGENTS.md 等），修改时间变化时
+/// 按设置回填到已启用的提示词或另存为修订，并发射 `prompt-file-synced` 事件。
+pub struct PromptWatchService;
+
+impl PromptWatchService {
+    pub fn start(db: Arc<Database>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut seen = SeenFiles::new();
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                let mode = crate::settings::get_settings().prompt_watch_mode;
+                // 只读模式下由持有写入租约的进程负责回填
+                if mode == PromptWatchMode::Off || db.is_read_only() {
+                    continue;
+                }
+
+                let db_for_task = db.clone();
+                let task = tauri::async_runtime::spawn_blocking(move || {
+                    let synced = Self::poll(&db_for_task, &mut seen, mode);
+                    (seen, synced)
+                });
+                match task.await {
+                    Ok((files, synced)) => {
+                        seen = files;
+                        for result in synced {
+                            if let Err(e) = app.emit("prompt-file-synced", &result) {
+                                log::warn!("发送提示词同步事件失败: {e}");
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        log::warn!("提示词文件检查任务异常: {e}");
+                        seen = SeenFiles::new();
+                    }
+                }
+            }
+        });
+    }
+
+    /// 检查一轮；首次观察到的文件只记录修改时间，不做处理
+    fn poll(
+        db: &Arc<Database>,
+        seen: &mut SeenFiles,
+        mode: PromptWatchMode,
+    ) -> Vec<PromptFileSync> {
+        let state = AppState::new(db.clone());
+        let mut synced = Vec::new();
+
+        for app in AppType::all() {
+            let Ok(path) = prompt_file_path(&app) else {
+                continue;
+            };
+            let Ok(modified) = path.metadata().and_then(|meta| meta.modified()) else {
+                seen.remove(&path);
+                continue;
+            };
+            match seen.insert(path, modified) {
+                Some(previous) if previous != modified => {}
+                _ => continue,
+            }
+
+            match PromptService::sync_from_live_file(&state, &app, mode) {
+                Ok(Some(result)) => synced.push(result),
+                Ok(None) => {}
+                Err(e) => log::warn!("同步 {} 提示词文件失败: {e}", app.as_str()),
+            }
+        }
+        synced
+    }
+}
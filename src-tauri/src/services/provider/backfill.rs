@@ -0,0 +1,251 @@
+//! Backfill conflict detection
+//!
+//! Before switching, live config is backfilled into the current provider. To avoid
+//! silently overwriting edits made elsewhere, a baseline is recorded every time the
+//! current provider is written to live:
+//! - `stored`: the provider config stored in the database at write time
+//! - `live`: the live config read back right after writing (includes MCP sync etc.)
+//!
+//! Three-way comparison against the baseline tells who changed what:
+//! - only live changed → take live (regular backfill)
+//! - only stored changed → keep stored (live is just our previous write)
+//! - both changed → conflict, let the user decide
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::live_state::LiveStateService;
+use crate::services::process_check::RunningCli;
+use crate::store::AppState;
+
+use super::live::read_provider_live_for;
+
+/// How to resolve a backfill conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BackfillResolution {
+    /// Keep the stored provider config, discard live changes
+    KeepMine,
+    /// Overwrite the stored provider config with live
+    TakeLive,
+    /// Three-way merge; live wins where both sides changed the same value
+    Merge,
+}
+
+/// Backfill conflict details returned to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackfillConflict {
+    pub app_type: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    /// Provider config currently stored in the database
+    pub stored: Value,
+    /// Current live config
+    pub live: Value,
+    /// Result of `Merge`, for preview
+    pub merged: Value,
+}
+
+/// Result of a provider switch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum SwitchOutcome {
+    /// `running` lists target CLI processes that may still hold the previous config
+    Switched {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        running: Vec<RunningCli>,
+    },
+    /// Switch was not performed; call again with a resolution
+    Conflict(BackfillConflict),
+    /// Switch was not performed because the target CLI is running
+    /// (`blockSwitchWhenCliRunning`); call again with `force` to switch anyway
+    CliRunning { processes: Vec<RunningCli> },
+}
+
+/// Baseline recorded after writing the current provider to live
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LiveBaseline {
+    provider_id: String,
+    stored: Value,
+    live: Value,
+    written_at: i64,
+}
+
+/// What the backfill step should do
+#[derive(Debug, Clone)]
+pub(crate) enum BackfillPlan {
+    /// Nothing to backfill (live unavailable or unchanged)
+    Skip,
+    /// Save this config into the current provider
+    Save(Value),
+    /// Both sides changed and no resolution was given
+    Conflict(BackfillConflict),
+}
+
+fn baseline_key(app_type: &AppType) -> String {
+    format!("live_baseline_{}", app_type.as_str())
+}
+
+fn load_baseline(state: &AppState, app_type: &AppType) -> Option<LiveBaseline> {
+    let raw = state.db.get_setting(&baseline_key(app_type)).ok()??;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Record the baseline for the provider just written to live
+///
+/// Failures are only logged: the baseline is an optimization for conflict detection,
+/// missing it falls back to the regular backfill.
+pub(crate) fn record_baseline(state: &AppState, app_type: &AppType, provider: &Provider) {
+    let live = match read_provider_live_for(app_type.clone(), provider) {
+        Ok(live) => live,
+        Err(e) => {
+            log::debug!("读取 {} live 配置失败，跳过基线记录: {e}", app_type.as_str());
+            return;
+        }
+    };
+    let baseline = LiveBaseline {
+        provider_id: provider.id.clone(),
+        stored: provider.settings_config.clone(),
+        live,
+        written_at: chrono::Utc::now().timestamp(),
+    };
+    let result = serde_json::to_string(&baseline)
+        .map_err(|e| AppError::JsonSerialize { source: e })
+        .and_then(|raw| state.db.set_setting(&baseline_key(app_type), &raw));
+    if let Err(e) = result {
+        log::warn!("记录 {} live 基线失败: {e}", app_type.as_str());
+    }
+}
+
+/// Decide how to backfill live config into the current provider
+pub(crate) fn plan_backfill(
+    state: &AppState,
+    app_type: &AppType,
+    current: &Provider,
+    resolution: Option<BackfillResolution>,
+) -> BackfillPlan {
+    let Ok(live) = read_provider_live_for(app_type.clone(), current) else {
+        return BackfillPlan::Skip;
+    };
+    let stored = &current.settings_config;
+    if &live == stored {
+        return BackfillPlan::Skip;
+    }
+    // Live files are exactly what cc-switch last wrote: nothing to backfill
+    if LiveStateService::has_drifted(state, app_type) == Some(false) {
+        return BackfillPlan::Skip;
+    }
+
+    // No baseline for this provider (older versions / first run): regular backfill
+    let Some(baseline) = load_baseline(state, app_type).filter(|b| b.provider_id == current.id)
+    else {
+        return BackfillPlan::Save(live);
+    };
+
+    let live_changed = live != baseline.live;
+    let stored_changed = stored != &baseline.stored;
+    match (stored_changed, live_changed) {
+        (_, false) => BackfillPlan::Skip,
+        (false, true) => BackfillPlan::Save(live),
+        (true, true) => {
+            let merged = merge3(&baseline.stored, stored, &baseline.live, &live);
+            match resolution {
+                Some(BackfillResolution::KeepMine) => BackfillPlan::Skip,
+                Some(BackfillResolution::TakeLive) => BackfillPlan::Save(live),
+                Some(BackfillResolution::Merge) => BackfillPlan::Save(merged),
+                None => BackfillPlan::Conflict(BackfillConflict {
+                    app_type: app_type.as_str().to_string(),
+                    provider_id: current.id.clone(),
+                    provider_name: current.name.clone(),
+                    stored: stored.clone(),
+                    live,
+                    merged,
+                }),
+            }
+        }
+    }
+}
+
+/// Three-way merge of JSON values
+///
+/// Each side is compared against its own base; objects are merged key by key,
+/// other values take the changed side, and live wins when both sides changed.
+fn merge3(mine_base: &Value, mine: &Value, live_base: &Value, live: &Value) -> Value {
+    if mine == live {
+        return mine.clone();
+    }
+    let mine_changed = mine != mine_base;
+    let live_changed = live != live_base;
+    if !live_changed {
+        return mine.clone();
+    }
+    if !mine_changed {
+        return live.clone();
+    }
+
+    match (mine, live) {
+        (Value::Object(mine_obj), Value::Object(live_obj)) => {
+            let empty = Map::new();
+            let mine_base_obj = mine_base.as_object().unwrap_or(&empty);
+            let live_base_obj = live_base.as_object().unwrap_or(&empty);
+
+            let mut keys: Vec<&String> = mine_obj.keys().collect();
+            keys.extend(live_obj.keys().filter(|k| !mine_obj.contains_key(*k)));
+
+            let mut merged = Map::new();
+            for key in keys {
+                let pick = |obj: &Map<String, Value>| obj.get(key).cloned().unwrap_or(Value::Null);
+                let value = merge3(
+                    &pick(mine_base_obj),
+                    &pick(mine_obj),
+                    &pick(live_base_obj),
+                    &pick(live_obj),
+                );
+                if !value.is_null() || mine_obj.get(key).is_some_and(Value::is_null) {
+                    merged.insert(key.clone(), value);
+                }
+            }
+            Value::Object(merged)
+        }
+        _ => live.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge3_keeps_both_sides_changes() {
+        let base = json!({ "env": { "KEY": "a", "URL": "u1" }, "model": "m1" });
+        let mine = json!({ "env": { "KEY": "b", "URL": "u1" }, "model": "m1" });
+        let live = json!({ "env": { "KEY": "a", "URL": "u2" }, "model": "m1", "extra": true });
+        let merged = merge3(&base, &mine, &base, &live);
+        assert_eq!(
+            merged,
+            json!({ "env": { "KEY": "b", "URL": "u2" }, "model": "m1", "extra": true })
+        );
+    }
+
+    #[test]
+    fn merge3_live_wins_on_same_key() {
+        let base = json!({ "model": "m1" });
+        let mine = json!({ "model": "m2" });
+        let live = json!({ "model": "m3" });
+        assert_eq!(merge3(&base, &mine, &base, &live), json!({ "model": "m3" }));
+    }
+
+    #[test]
+    fn merge3_respects_removed_keys() {
+        let base = json!({ "a": 1, "b": 2 });
+        let mine = json!({ "a": 1, "b": 3 });
+        let live = json!({ "b": 2 });
+        assert_eq!(merge3(&base, &mine, &base, &live), json!({ "b": 3 }));
+    }
+}
@@ -0,0 +1,238 @@
+//! Layered Claude settings
+//!
+//! Claude Code merges `settings.json`, `settings.local.json` and the enterprise
+//! `managed-settings.json`, later layers taking precedence. cc-switch never writes the
+//! managed file and drops the keys it enforces before writing: they would be ignored by
+//! the CLI anyway and show up as drift on the next read.
+
+use serde_json::{Map, Value};
+
+use crate::config::{
+    get_claude_local_settings_path, get_claude_managed_settings_path, get_claude_settings_path,
+    read_json_file, write_json_file,
+};
+use crate::error::AppError;
+use crate::settings::ClaudeEnvTarget;
+
+/// The three settings files of the current Claude config directory
+#[derive(Debug, Default)]
+pub(crate) struct ClaudeLayers {
+    pub settings: Option<Value>,
+    pub local: Option<Value>,
+    pub managed: Option<Value>,
+}
+
+impl ClaudeLayers {
+    pub(crate) fn read() -> Result<Self, AppError> {
+        Ok(Self {
+            settings: read_optional(&get_claude_settings_path())?,
+            local: read_optional(&get_claude_local_settings_path())?,
+            managed: read_managed(),
+        })
+    }
+
+    /// Whether neither user-writable file exists
+    pub(crate) fn is_empty(&self) -> bool {
+        self.settings.is_none() && self.local.is_none()
+    }
+
+    /// Settings as Claude Code sees them (managed keys win)
+    pub(crate) fn effective(&self) -> Value {
+        let mut merged = Value::Object(Map::new());
+        for layer in [&self.settings, &self.local, &self.managed]
+            .into_iter()
+            .flatten()
+        {
+            merge_into(&mut merged, layer);
+        }
+        merged
+    }
+
+    /// The part of the live settings owned by the current provider
+    ///
+    /// With [`ClaudeEnvTarget::Local`] the `env` written to `settings.local.json`
+    /// is folded back in, so it round-trips with what was written.
+    pub(crate) fn provider_view(&self, target: ClaudeEnvTarget) -> Value {
+        let mut view = self
+            .settings
+            .clone()
+            .unwrap_or_else(|| Value::Object(Map::new()));
+        if target == ClaudeEnvTarget::Local {
+            if let Some(env) = self.local.as_ref().and_then(|local| local.get("env")) {
+                if let Some(obj) = view.as_object_mut() {
+                    obj.insert("env".to_string(), env.clone());
+                }
+            }
+        }
+        view
+    }
+}
+
+fn read_optional(path: &std::path::Path) -> Result<Option<Value>, AppError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    read_json_file(path).map(Some)
+}
+
+/// Managed settings are outside our control: an unreadable file is logged, not fatal
+fn read_managed() -> Option<Value> {
+    let path = get_claude_managed_settings_path();
+    match read_optional(&path) {
+        Ok(value) => value,
+        Err(e) => {
+            log::warn!("读取 Claude 托管配置失败 {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Recursively merge `overlay` into `base` (objects merge, everything else replaces)
+fn merge_into(base: &mut Value, overlay: &Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(key) {
+                    Some(existing) => merge_into(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
+
+/// Remove every key enforced by `managed`, recursing into objects present on both sides
+fn strip_managed(config: &mut Value, managed: &Value) -> Vec<String> {
+    let mut stripped = Vec::new();
+    strip_managed_at(config, managed, "", &mut stripped);
+    stripped
+}
+
+fn strip_managed_at(config: &mut Value, managed: &Value, prefix: &str, out: &mut Vec<String>) {
+    let (Some(config), Some(managed)) = (config.as_object_mut(), managed.as_object()) else {
+        return;
+    };
+    for (key, enforced) in managed {
+        let path = format!("{prefix}{key}");
+        match config.get_mut(key) {
+            Some(value) if value.is_object() && enforced.is_object() => {
+                strip_managed_at(value, enforced, &format!("{path}."), out);
+            }
+            Some(_) => {
+                config.remove(key);
+                out.push(path);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Write a provider's Claude settings into the layered files
+///
+/// `settings.json` receives the provider config; with [`ClaudeEnvTarget::Local`] its
+/// `env` goes to `settings.local.json` instead, leaving the other local keys untouched.
+pub(crate) fn write_claude_layers(
+    settings_config: &Value,
+    target: ClaudeEnvTarget,
+) -> Result<(), AppError> {
+    let mut config = settings_config.clone();
+    if let Some(managed) = read_managed() {
+        let stripped = strip_managed(&mut config, &managed);
+        if !stripped.is_empty() {
+            log::info!("跳过 Claude 托管配置强制的字段: {}", stripped.join(", "));
+        }
+    }
+
+    let local_path = get_claude_local_settings_path();
+    match target {
+        ClaudeEnvTarget::Settings => {
+            let local = read_optional(&local_path)?;
+            let overridden: Vec<&str> = match (
+                local
+                    .as_ref()
+                    .and_then(|local| local.get("env")?.as_object()),
+                config.get("env").and_then(Value::as_object),
+            ) {
+                (Some(local_env), Some(env)) => env
+                    .keys()
+                    .map(String::as_str)
+                    .filter(|key| local_env.contains_key(*key))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            if !overridden.is_empty() {
+                log::warn!(
+                    "settings.local.json 中的 env 会覆盖供应商配置: {}",
+                    overridden.join(", ")
+                );
+            }
+        }
+        ClaudeEnvTarget::Local => {
+            let env = config.as_object_mut().and_then(|obj| obj.remove("env"));
+            let mut local =
+                read_optional(&local_path)?.unwrap_or_else(|| Value::Object(Map::new()));
+            if let Some(obj) = local.as_object_mut() {
+                match env {
+                    Some(env) => {
+                        obj.insert("env".to_string(), env);
+                    }
+                    None => {
+                        obj.remove("env");
+                    }
+                }
+            }
+            write_json_file(&local_path, &local)?;
+        }
+    }
+
+    write_json_file(&get_claude_settings_path(), &config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn effective_view_applies_layers_in_order() {
+        let layers = ClaudeLayers {
+            settings: Some(json!({ "env": { "A": "1", "B": "1" }, "model": "opus" })),
+            local: Some(json!({ "env": { "B": "2" }, "permissions": { "allow": ["Bash"] } })),
+            managed: Some(json!({ "model": "sonnet" })),
+        };
+        assert_eq!(
+            layers.effective(),
+            json!({
+                "env": { "A": "1", "B": "2" },
+                "model": "sonnet",
+                "permissions": { "allow": ["Bash"] }
+            })
+        );
+        assert_eq!(
+            layers.provider_view(ClaudeEnvTarget::Local)["env"],
+            json!({ "B": "2" })
+        );
+    }
+
+    #[test]
+    fn strip_managed_removes_enforced_keys_only() {
+        let mut config = json!({
+            "env": { "ANTHROPIC_BASE_URL": "https://a", "ANTHROPIC_AUTH_TOKEN": "t" },
+            "model": "opus",
+            "permissions": { "deny": [] }
+        });
+        let managed = json!({
+            "env": { "ANTHROPIC_BASE_URL": "https://corp" },
+            "permissions": "locked"
+        });
+        let stripped = strip_managed(&mut config, &managed);
+        assert_eq!(stripped, vec!["env.ANTHROPIC_BASE_URL", "permissions"]);
+        assert_eq!(
+            config,
+            json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "t" }, "model": "opus" })
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Codex authentication kind detection
+//!
+//! Codex `auth.json` comes in two shapes:
+//! - API key: `{ "OPENAI_API_KEY": "sk-..." }` (relays and OpenAI API)
+//! - ChatGPT login: `{ "OPENAI_API_KEY": null, "tokens": { "id_token", "access_token", "refresh_token", "account_id" }, "last_refresh": "..." }`
+
+use base64::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::AppError;
+
+/// Codex authentication kind (used for the provider badge)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CodexAuthKind {
+    /// `OPENAI_API_KEY` is set
+    ApiKey,
+    /// ChatGPT OAuth tokens
+    ChatgptOauth,
+    /// Neither an API key nor OAuth tokens
+    Unknown,
+}
+
+/// Codex authentication summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexAuthInfo {
+    pub kind: CodexAuthKind,
+    /// Access token expiry (Unix seconds), decoded from the JWT `exp` claim
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+    /// Access token already expired (Codex refreshes it with the refresh token on next use)
+    pub expired: bool,
+    /// Whether a refresh token is available
+    pub refreshable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_refresh: Option<String>,
+}
+
+fn non_empty_str<'a>(value: Option<&'a Value>) -> Option<&'a str> {
+    value.and_then(|v| v.as_str()).filter(|s| !s.trim().is_empty())
+}
+
+/// Decode the payload (claims) of a JWT without verifying the signature
+fn decode_jwt_claims(token: &str) -> Option<Value> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = BASE64_URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Detect authentication kind and token expiry from an `auth.json` value
+pub(crate) fn detect_codex_auth(auth: &Value) -> CodexAuthInfo {
+    let tokens = auth.get("tokens").filter(|v| v.is_object());
+    let api_key = non_empty_str(auth.get("OPENAI_API_KEY"));
+
+    let access_token = tokens.and_then(|t| non_empty_str(t.get("access_token")));
+    let refresh_token = tokens.and_then(|t| non_empty_str(t.get("refresh_token")));
+    let id_token = tokens.and_then(|t| non_empty_str(t.get("id_token")));
+
+    let kind = if api_key.is_some() {
+        CodexAuthKind::ApiKey
+    } else if access_token.is_some() || refresh_token.is_some() {
+        CodexAuthKind::ChatgptOauth
+    } else {
+        CodexAuthKind::Unknown
+    };
+
+    let mut info = CodexAuthInfo {
+        kind,
+        expires_at: None,
+        expired: false,
+        refreshable: refresh_token.is_some(),
+        email: None,
+        account_id: tokens
+            .and_then(|t| non_empty_str(t.get("account_id")))
+            .map(str::to_string),
+        last_refresh: non_empty_str(auth.get("last_refresh")).map(str::to_string),
+    };
+
+    if kind != CodexAuthKind::ChatgptOauth {
+        return info;
+    }
+
+    let access_claims = access_token.and_then(decode_jwt_claims);
+    let id_claims = id_token.and_then(decode_jwt_claims);
+
+    info.expires_at = access_claims
+        .as_ref()
+        .or(id_claims.as_ref())
+        .and_then(|c| c.get("exp"))
+        .and_then(|v| v.as_i64());
+    info.expired = info
+        .expires_at
+        .is_some_and(|exp| exp <= chrono::Utc::now().timestamp());
+    info.email = id_claims
+        .as_ref()
+        .and_then(|c| non_empty_str(c.get("email")))
+        .map(str::to_string);
+
+    info
+}
+
+/// Validate the `auth` object shape (API key or ChatGPT OAuth)
+pub(crate) fn validate_codex_auth(auth: &Value, provider_id: &str) -> Result<(), AppError> {
+    if let Some(key) = auth.get("OPENAI_API_KEY") {
+        if !(key.is_string() || key.is_null()) {
+            return Err(AppError::localized_with(
+                "provider.codex.api_key.invalid_type",
+                &[("provider_id", provider_id.to_string())],
+            ));
+        }
+    }
+
+    if let Some(tokens) = auth.get("tokens") {
+        if tokens.is_null() {
+            return Ok(());
+        }
+        let obj = tokens.as_object().ok_or_else(|| {
+            AppError::localized_with(
+                "provider.codex.tokens.not_object",
+                &[("provider_id", provider_id.to_string())],
+            )
+        })?;
+
+        for key in ["id_token", "access_token", "refresh_token", "account_id"] {
+            if let Some(value) = obj.get(key) {
+                if !(value.is_string() || value.is_null()) {
+                    return Err(AppError::localized_with(
+                        "provider.codex.tokens.invalid_field",
+                        &[("provider_id", provider_id.to_string()), ("key", key.to_string())],
+                    ));
+                }
+            }
+        }
+
+        if non_empty_str(obj.get("access_token")).is_none()
+            && non_empty_str(obj.get("refresh_token")).is_none()
+        {
+            return Err(AppError::localized_with(
+                "provider.codex.tokens.missing",
+                &[("provider_id", provider_id.to_string())],
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn jwt(claims: Value) -> String {
+        format!(
+            "eyJhbGciOiJub25lIn0.{}.sig",
+            BASE64_URL_SAFE_NO_PAD.encode(claims.to_string())
+        )
+    }
+
+    #[test]
+    fn detects_api_key_auth() {
+        let info = detect_codex_auth(&json!({ "OPENAI_API_KEY": "sk-test" }));
+        assert_eq!(info.kind, CodexAuthKind::ApiKey);
+        assert!(!info.expired);
+    }
+
+    #[test]
+    fn detects_chatgpt_oauth_and_expiry() {
+        let auth = json!({
+            "OPENAI_API_KEY": null,
+            "tokens": {
+                "id_token": jwt(json!({ "email": "me@example.com", "exp": 1 })),
+                "access_token": jwt(json!({ "exp": 1 })),
+                "refresh_token": "rt",
+                "account_id": "acc-1"
+            },
+            "last_refresh": "2025-01-01T00:00:00Z"
+        });
+        let info = detect_codex_auth(&auth);
+        assert_eq!(info.kind, CodexAuthKind::ChatgptOauth);
+        assert_eq!(info.expires_at, Some(1));
+        assert!(info.expired);
+        assert!(info.refreshable);
+        assert_eq!(info.email.as_deref(), Some("me@example.com"));
+        assert_eq!(info.account_id.as_deref(), Some("acc-1"));
+        assert!(validate_codex_auth(&auth, "p").is_ok());
+    }
+
+    #[test]
+    fn rejects_malformed_tokens() {
+        assert!(validate_codex_auth(&json!({ "tokens": "bad" }), "p").is_err());
+        assert!(validate_codex_auth(&json!({ "tokens": {} }), "p").is_err());
+        assert!(validate_codex_auth(&json!({ "OPENAI_API_KEY": 1 }), "p").is_err());
+        assert!(validate_codex_auth(&json!({ "OPENAI_API_KEY": "sk" }), "p").is_ok());
+    }
+}
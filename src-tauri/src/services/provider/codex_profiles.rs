@@ -0,0 +1,133 @@
+//! Codex profiles (`[profiles.*]` in `config.toml`)
+//!
+//! Users with hand-crafted Codex configs keep several named profiles in one file.
+//! Each profile can be imported as a provider bound to it (`meta.codexProfile`):
+//! switching to such a provider writes the same config and only changes the
+//! top-level `profile` selection.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::codex_config::{
+    get_codex_auth_path, read_and_validate_codex_config_text, CodexProfiles,
+};
+use crate::config::read_json_file;
+use crate::error::AppError;
+use crate::provider::{Provider, ProviderMeta};
+use crate::services::live_state::LiveStateService;
+use crate::store::AppState;
+
+use super::external::{sanitize_key, ImportedProvider};
+use super::ProviderService;
+
+/// Result of importing Codex profiles as providers
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexProfileImportResult {
+    pub imported: Vec<ImportedProvider>,
+    /// Profiles that already have a bound provider
+    pub skipped: Vec<String>,
+}
+
+/// `meta.configDir` of the current Codex provider (live files are read and written there)
+fn current_config_dir(state: &AppState) -> Result<Option<String>, AppError> {
+    let Some(id) = crate::settings::get_effective_current_provider(&state.db, &AppType::Codex)?
+    else {
+        return Ok(None);
+    };
+    Ok(state
+        .db
+        .get_all_providers(AppType::Codex.as_str())?
+        .get(&id)
+        .and_then(|provider| provider.config_dir().map(str::to_string)))
+}
+
+/// Profiles defined in the live `config.toml`
+pub(crate) fn codex_profiles(state: &AppState) -> Result<CodexProfiles, AppError> {
+    let dir = current_config_dir(state)?;
+    crate::settings::with_provider_config_dir(
+        dir.as_deref(),
+        crate::codex_config::read_codex_profiles,
+    )
+}
+
+/// Select a profile in the live `config.toml` without rewriting the rest of the file
+pub(crate) fn switch_codex_profile(
+    state: &AppState,
+    profile: Option<&str>,
+) -> Result<CodexProfiles, AppError> {
+    let dir = current_config_dir(state)?;
+    crate::settings::with_provider_config_dir(dir.as_deref(), || {
+        let profiles = crate::codex_config::switch_codex_profile(profile)?;
+        // Our own edit, not external drift
+        LiveStateService::record_quietly(state, &[AppType::Codex]);
+        log::info!("已切换 Codex profile: {}", profile.unwrap_or("(none)"));
+        Ok(profiles)
+    })
+}
+
+/// Import every profile of the live `config.toml` that has no bound provider yet
+pub(crate) fn import_codex_profiles(
+    state: &AppState,
+) -> Result<CodexProfileImportResult, AppError> {
+    let dir = current_config_dir(state)?;
+    let (config_text, auth) = crate::settings::with_provider_config_dir(dir.as_deref(), || {
+        let config_text = read_and_validate_codex_config_text()?;
+        let auth_path = get_codex_auth_path();
+        let auth: Value = if auth_path.exists() {
+            read_json_file(&auth_path)?
+        } else {
+            json!({})
+        };
+        Ok::<_, AppError>((config_text, auth))
+    })?;
+    let profiles = crate::codex_config::parse_codex_profiles(&config_text)?;
+
+    let existing = state.db.get_all_providers(AppType::Codex.as_str())?;
+    let bound: Vec<&str> = existing
+        .values()
+        .filter_map(|provider| provider.meta.as_ref()?.codex_profile.as_deref())
+        .collect();
+
+    let mut result = CodexProfileImportResult {
+        imported: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let timestamp = chrono::Utc::now().timestamp_millis();
+    for (index, profile) in profiles.profiles.into_iter().enumerate() {
+        if bound.contains(&profile.name.as_str()) {
+            result.skipped.push(profile.name);
+            continue;
+        }
+
+        let id = format!(
+            "codex-profile-{}-{}",
+            sanitize_key(&profile.name),
+            timestamp + index as i64
+        );
+        let mut provider = Provider::with_id(
+            id.clone(),
+            profile.name.clone(),
+            json!({ "auth": auth, "config": config_text }),
+            None,
+        );
+        provider.meta = Some(ProviderMeta {
+            codex_profile: Some(profile.name.clone()),
+            ..Default::default()
+        });
+        ProviderService::add(state, AppType::Codex, provider)?;
+        result.imported.push(ImportedProvider {
+            app: AppType::Codex.as_str().to_string(),
+            id,
+            name: profile.name,
+        });
+    }
+
+    log::info!(
+        "已导入 {} 个 Codex profile，跳过 {} 个",
+        result.imported.len(),
+        result.skipped.len()
+    );
+    Ok(result)
+}
@@ -0,0 +1,164 @@
+//! Provider deletion impact analysis
+//!
+//! Collects everything that still points at a provider so the confirm dialog can
+//! show what deleting it actually affects. Nothing is modified here.
+
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::services::{DeviceInfo, DeviceService, ProjectService};
+use crate::store::AppState;
+
+/// A project that maps the provider to its directory
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectReference {
+    pub path: String,
+    pub name: String,
+}
+
+/// Another device whose current provider is this one
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceReference {
+    pub id: String,
+    pub name: String,
+    pub last_seen_at: i64,
+}
+
+/// Recorded history that is kept after deletion but no longer shown under a name
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHistory {
+    pub usage_records: u32,
+    pub tokens: i64,
+    pub cost: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_usage_at: Option<i64>,
+    pub switch_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_switched_at: Option<i64>,
+}
+
+/// What deleting a provider affects
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderDeletionImpact {
+    pub app: AppType,
+    pub provider_id: String,
+    pub provider_name: String,
+    /// Current provider in this device's settings
+    pub current_local: bool,
+    /// Current provider flag in the database
+    pub current_in_db: bool,
+    /// Deletion is refused while the provider is current (`delete_provider` errors)
+    pub blocked: bool,
+    pub projects: Vec<ProjectReference>,
+    pub other_devices: Vec<DeviceReference>,
+    pub history: ProviderHistory,
+}
+
+pub(crate) fn analyze_provider_deletion(
+    state: &AppState,
+    app_type: AppType,
+    id: &str,
+) -> Result<ProviderDeletionImpact, AppError> {
+    let provider = state
+        .db
+        .get_all_providers(app_type.as_str())?
+        .shift_remove(id)
+        .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
+
+    let current_local = crate::settings::get_current_provider(&app_type).as_deref() == Some(id);
+    let current_in_db = state.db.get_current_provider(app_type.as_str())?.as_deref() == Some(id);
+
+    let projects = ProjectService::list(state)?
+        .into_values()
+        .filter(|project| project.providers.get(app_type.as_str()).map(String::as_str) == Some(id))
+        .map(|project| ProjectReference {
+            path: project.path,
+            name: project.name,
+        })
+        .collect();
+
+    let other_devices = other_device_references(DeviceService::list(&state.db)?, &app_type, id);
+
+    let (usage_records, tokens, cost, last_usage_at) =
+        state.db.summarize_usage_history(app_type.as_str(), id)?;
+    let (switch_count, last_switched_at) = state.db.count_switch_events(app_type.as_str(), id)?;
+
+    Ok(ProviderDeletionImpact {
+        provider_id: id.to_string(),
+        provider_name: provider.name,
+        current_local,
+        current_in_db,
+        blocked: current_local || current_in_db,
+        projects,
+        other_devices,
+        history: ProviderHistory {
+            usage_records,
+            tokens,
+            cost,
+            last_usage_at,
+            switch_count,
+            last_switched_at,
+        },
+        app: app_type,
+    })
+}
+
+/// Devices other than this one whose saved current provider for `app_type` is `id`
+fn other_device_references(
+    devices: Vec<DeviceInfo>,
+    app_type: &AppType,
+    id: &str,
+) -> Vec<DeviceReference> {
+    devices
+        .into_iter()
+        .filter(|info| !info.current)
+        .filter(|info| {
+            info.device
+                .current_providers
+                .get(app_type.as_str())
+                .map(String::as_str)
+                == Some(id)
+        })
+        .map(|info| DeviceReference {
+            id: info.device.id,
+            name: info.device.name,
+            last_seen_at: info.device.last_seen_at,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::DeviceRecord;
+
+    fn device(id: &str, current: bool, provider: &str) -> DeviceInfo {
+        let mut device = DeviceRecord {
+            id: id.to_string(),
+            name: format!("{id}-name"),
+            ..Default::default()
+        };
+        device
+            .current_providers
+            .insert("claude".to_string(), provider.to_string());
+        DeviceInfo { device, current }
+    }
+
+    #[test]
+    fn only_other_devices_on_the_provider_are_reported() {
+        let devices = vec![
+            device("this", true, "p1"),
+            device("laptop", false, "p1"),
+            device("desktop", false, "p2"),
+        ];
+        let refs = other_device_references(devices.clone(), &AppType::Claude, "p1");
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].id, "laptop");
+        assert!(other_device_references(devices, &AppType::Codex, "p1").is_empty());
+    }
+}
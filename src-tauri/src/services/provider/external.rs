@@ -0,0 +1,376 @@
+//! Import from other switchers
+//!
+//! Reads the provider lists of tools with a similar purpose and maps their
+//! endpoint entries into cc-switch providers. Supported formats:
+//! claude-code-router (`ccr`, `~/.claude-code-router/config.json`).
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+use super::ProviderService;
+
+/// A switcher whose configuration can be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OtherTool {
+    ClaudeCodeRouter,
+}
+
+impl OtherTool {
+    const ALL: [OtherTool; 1] = [OtherTool::ClaudeCodeRouter];
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            OtherTool::ClaudeCodeRouter => "claude-code-router",
+        }
+    }
+
+    /// Default config file location
+    fn default_config_path(self) -> Option<PathBuf> {
+        let home = dirs::home_dir()?;
+        match self {
+            OtherTool::ClaudeCodeRouter => {
+                Some(home.join(".claude-code-router").join("config.json"))
+            }
+        }
+    }
+
+    fn parse(self, config: &Value) -> Vec<ForeignEntry> {
+        match self {
+            OtherTool::ClaudeCodeRouter => parse_claude_code_router(config),
+        }
+    }
+}
+
+/// A tool found on this machine
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectedTool {
+    pub tool: OtherTool,
+    pub name: String,
+    pub config_path: String,
+    /// Number of provider entries in its config
+    pub provider_count: usize,
+}
+
+/// Why an entry was not imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SkipReason {
+    MissingEndpoint,
+    /// Missing, or refers to an environment variable that is not set
+    MissingApiKey,
+    /// A provider with the same endpoint and key already exists
+    Duplicate,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedProvider {
+    pub app: String,
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedEntry {
+    pub name: String,
+    pub reason: SkipReason,
+}
+
+/// Result of importing another tool's configuration
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtherToolImportResult {
+    pub tool: OtherTool,
+    pub imported: Vec<ImportedProvider>,
+    pub skipped: Vec<SkippedEntry>,
+}
+
+/// Provider entry read from another tool, before mapping
+#[derive(Debug, Clone)]
+struct ForeignEntry {
+    name: String,
+    app_type: AppType,
+    base_url: String,
+    api_key: String,
+    model: Option<String>,
+}
+
+/// Scan the known config locations of other switchers
+pub(crate) fn detect_other_tools() -> Vec<DetectedTool> {
+    OtherTool::ALL
+        .into_iter()
+        .filter_map(|tool| {
+            let path = tool.default_config_path()?;
+            let config = read_config(&path).ok()?;
+            Some(DetectedTool {
+                tool,
+                name: tool.display_name().to_string(),
+                config_path: path.to_string_lossy().to_string(),
+                provider_count: tool.parse(&config).len(),
+            })
+        })
+        .collect()
+}
+
+/// Import providers from another tool's config (`path` defaults to its usual location)
+pub(crate) fn import_other_tool(
+    state: &AppState,
+    tool: OtherTool,
+    path: Option<&str>,
+) -> Result<OtherToolImportResult, AppError> {
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => tool.default_config_path().ok_or_else(|| {
+            AppError::localized_with(
+                "provider.external.not_found",
+                &[("tool", tool.display_name().to_string())],
+            )
+        })?,
+    };
+    let config = read_config(&path)?;
+
+    let mut result = OtherToolImportResult {
+        tool,
+        imported: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let timestamp = chrono::Utc::now().timestamp_millis();
+    for (index, entry) in tool.parse(&config).into_iter().enumerate() {
+        if let Some(reason) = check_entry(state, &entry)? {
+            result.skipped.push(SkippedEntry {
+                name: entry.name,
+                reason,
+            });
+            continue;
+        }
+
+        let id = format!("{}-{}", sanitize_key(&entry.name), timestamp + index as i64);
+        let mut provider =
+            Provider::with_id(id.clone(), entry.name.clone(), build_settings(&entry), None);
+        provider.tags = vec![tool.display_name().to_string()];
+        ProviderService::add(state, entry.app_type.clone(), provider)?;
+        result.imported.push(ImportedProvider {
+            app: entry.app_type.as_str().to_string(),
+            id,
+            name: entry.name,
+        });
+    }
+
+    log::info!(
+        "已从 {} 导入 {} 个供应商，跳过 {} 个",
+        tool.display_name(),
+        result.imported.len(),
+        result.skipped.len()
+    );
+    Ok(result)
+}
+
+fn read_config(path: &Path) -> Result<Value, AppError> {
+    let content = std::fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+    serde_json::from_str(&content).map_err(|e| AppError::json(path, e))
+}
+
+/// Entries that cannot be imported, or duplicate an existing provider
+fn check_entry(state: &AppState, entry: &ForeignEntry) -> Result<Option<SkipReason>, AppError> {
+    if entry.base_url.is_empty() {
+        return Ok(Some(SkipReason::MissingEndpoint));
+    }
+    if entry.api_key.is_empty() {
+        return Ok(Some(SkipReason::MissingApiKey));
+    }
+
+    let duplicate = state
+        .db
+        .get_all_providers(entry.app_type.as_str())?
+        .values()
+        .filter_map(|provider| ProviderService::extract_credentials(provider, &entry.app_type).ok())
+        .any(|(api_key, base_url)| {
+            api_key == entry.api_key && base_url.trim_end_matches('/') == entry.base_url
+        });
+    Ok(duplicate.then_some(SkipReason::Duplicate))
+}
+
+/// claude-code-router: `Providers[] { name, api_base_url, api_key, models[] }`
+///
+/// Endpoints speaking the Anthropic Messages API become Claude providers, the
+/// OpenAI-compatible chat completion endpoints become Codex providers.
+fn parse_claude_code_router(config: &Value) -> Vec<ForeignEntry> {
+    let providers = config
+        .get("Providers")
+        .or_else(|| config.get("providers"))
+        .and_then(Value::as_array);
+    // `Router.default` is "<provider>,<model>"
+    let default_route = config
+        .pointer("/Router/default")
+        .and_then(Value::as_str)
+        .and_then(|route| route.split_once(','));
+
+    providers
+        .into_iter()
+        .flatten()
+        .filter_map(|provider| {
+            let name = provider.get("name")?.as_str()?.trim().to_string();
+            let url = provider
+                .get("api_base_url")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let (app_type, base_url) = classify_endpoint(url);
+            let api_key = provider
+                .get("api_key")
+                .and_then(Value::as_str)
+                .map(resolve_env_reference)
+                .unwrap_or_default();
+            let model = default_route
+                .filter(|(route_provider, _)| route_provider.trim() == name)
+                .map(|(_, model)| model.trim().to_string())
+                .or_else(|| {
+                    provider
+                        .pointer("/models/0")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                });
+            Some(ForeignEntry {
+                name,
+                app_type,
+                base_url,
+                api_key,
+                model,
+            })
+        })
+        .collect()
+}
+
+/// Pick the app for a full endpoint URL and strip the API path from it
+fn classify_endpoint(url: &str) -> (AppType, String) {
+    let url = url.trim().trim_end_matches('/');
+    if let Some(base) = url.strip_suffix("/v1/messages") {
+        return (AppType::Claude, base.to_string());
+    }
+    if url.contains("/anthropic") {
+        return (AppType::Claude, url.to_string());
+    }
+    let base = url.strip_suffix("/chat/completions").unwrap_or(url);
+    (AppType::Codex, base.to_string())
+}
+
+/// Resolve `$VAR` / `${VAR}` references (empty when the variable is not set)
+pub(super) fn resolve_env_reference(value: &str) -> String {
+    let value = value.trim();
+    let Some(name) = value.strip_prefix('$') else {
+        return value.to_string();
+    };
+    let name = name
+        .strip_prefix('{')
+        .and_then(|n| n.strip_suffix('}'))
+        .unwrap_or(name);
+    std::env::var(name).unwrap_or_default()
+}
+
+fn build_settings(entry: &ForeignEntry) -> Value {
+    match entry.app_type {
+        AppType::Codex => {
+            let key = sanitize_key(&entry.name);
+            let mut config = format!("model_provider = \"{key}\"\n");
+            if let Some(model) = &entry.model {
+                config.push_str(&format!("model = {}\n", toml::Value::from(model.as_str())));
+            }
+            config.push_str(&format!(
+                "\n[model_providers.{key}]\nname = \"{key}\"\nbase_url = {}\nwire_api = \"chat\"\nrequires_openai_auth = true\n",
+                toml::Value::from(entry.base_url.as_str())
+            ));
+            json!({
+                "auth": { "OPENAI_API_KEY": entry.api_key },
+                "config": config,
+            })
+        }
+        _ => {
+            let mut env = json!({
+                "ANTHROPIC_BASE_URL": entry.base_url,
+                "ANTHROPIC_AUTH_TOKEN": entry.api_key,
+            });
+            if let Some(model) = &entry.model {
+                env["ANTHROPIC_MODEL"] = json!(model);
+            }
+            json!({ "env": env })
+        }
+    }
+}
+
+/// Lowercase identifier for provider IDs and Codex provider keys
+pub(super) fn sanitize_key(name: &str) -> String {
+    let key: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let key = key.trim_matches('_');
+    if key.is_empty() {
+        "custom".to_string()
+    } else {
+        key.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_claude_code_router_providers() {
+        let config = json!({
+            "Providers": [
+                {
+                    "name": "openrouter",
+                    "api_base_url": "https://openrouter.ai/api/v1/chat/completions",
+                    "api_key": "sk-or-key",
+                    "models": ["anthropic/claude-sonnet-4", "google/gemini-2.5-pro"]
+                },
+                {
+                    "name": "relay",
+                    "api_base_url": "https://relay.example.com/v1/messages",
+                    "api_key": "$CC_SWITCH_TEST_UNSET_KEY",
+                    "models": ["claude-sonnet-4"]
+                }
+            ],
+            "Router": { "default": "openrouter,google/gemini-2.5-pro" }
+        });
+
+        let entries = parse_claude_code_router(&config);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].app_type, AppType::Codex);
+        assert_eq!(entries[0].base_url, "https://openrouter.ai/api/v1");
+        assert_eq!(entries[0].model.as_deref(), Some("google/gemini-2.5-pro"));
+        assert_eq!(entries[1].app_type, AppType::Claude);
+        assert_eq!(entries[1].base_url, "https://relay.example.com");
+        assert!(entries[1].api_key.is_empty(), "unset env reference");
+    }
+
+    #[test]
+    fn codex_settings_are_valid_toml() {
+        let entry = ForeignEntry {
+            name: "Open Router".to_string(),
+            app_type: AppType::Codex,
+            base_url: "https://openrouter.ai/api/v1".to_string(),
+            api_key: "sk-or-key".to_string(),
+            model: Some("openai/gpt-5".to_string()),
+        };
+        let settings = build_settings(&entry);
+        let config = settings["config"].as_str().expect("config");
+        let parsed: toml::Table = toml::from_str(config).expect("valid toml");
+        assert_eq!(parsed["model_provider"].as_str(), Some("open_router"));
+        assert_eq!(
+            parsed["model_providers"]["open_router"]["base_url"].as_str(),
+            Some("https://openrouter.ai/api/v1")
+        );
+    }
+}
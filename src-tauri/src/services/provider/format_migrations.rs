@@ -0,0 +1,185 @@
+//! Provider settings format migrations
+//!
+//! When a CLI changes its settings schema between versions, provider snapshots stored
+//! in the database keep the old keys. Each migration here rewrites one such change in
+//! place and reports whether anything changed, so the registry can be re-run safely.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::i18n::tr;
+
+use super::normalize_claude_models_in_value;
+
+/// One schema change of an app's provider settings
+pub(crate) struct FormatMigration {
+    pub id: &'static str,
+    pub app: AppType,
+    /// Rewrites `settings_config` in place; returns whether it changed
+    pub apply: fn(&mut Value) -> bool,
+}
+
+/// Migration registry, applied in order
+pub(crate) static FORMAT_MIGRATIONS: [FormatMigration; 2] = [
+    FormatMigration {
+        id: "claude_default_model_env",
+        app: AppType::Claude,
+        apply: normalize_claude_models_in_value,
+    },
+    FormatMigration {
+        id: "codex_approval_policy_untrusted",
+        app: AppType::Codex,
+        apply: migrate_codex_approval_policy,
+    },
+];
+
+/// Codex renamed the `unless-allow-listed` approval policy to `untrusted`
+/// (top level and inside `[profiles.*]`)
+fn migrate_codex_approval_policy(settings: &mut Value) -> bool {
+    let Some(text) = settings.get("config").and_then(Value::as_str) else {
+        return false;
+    };
+    let Ok(mut doc) = text.parse::<toml_edit::DocumentMut>() else {
+        return false;
+    };
+
+    let mut changed = false;
+    let mut rename = |item: &mut toml_edit::Item| {
+        if item.as_str() == Some("unless-allow-listed") {
+            *item = toml_edit::value("untrusted");
+            changed = true;
+        }
+    };
+    if let Some(item) = doc.get_mut("approval_policy") {
+        rename(item);
+    }
+    if let Some(profiles) = doc.get_mut("profiles").and_then(|p| p.as_table_like_mut()) {
+        for (_, profile) in profiles.iter_mut() {
+            if let Some(item) = profile
+                .as_table_like_mut()
+                .and_then(|t| t.get_mut("approval_policy"))
+            {
+                rename(item);
+            }
+        }
+    }
+
+    if changed {
+        settings["config"] = Value::String(doc.to_string());
+    }
+    changed
+}
+
+/// A provider changed by format migrations
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatMigrationChange {
+    pub app: AppType,
+    pub provider_id: String,
+    pub provider_name: String,
+    /// Ids of the migrations that changed this provider
+    pub migrations: Vec<String>,
+}
+
+/// A migration that changed at least one provider
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatMigrationInfo {
+    pub id: String,
+    /// Localized description
+    pub description: String,
+}
+
+/// Result of running format migrations over stored providers
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatMigrationReport {
+    /// When true nothing was saved
+    pub dry_run: bool,
+    pub changes: Vec<FormatMigrationChange>,
+    pub migrations: Vec<FormatMigrationInfo>,
+}
+
+/// Apply the registered migrations of `app` (all apps when `None`) to every stored provider
+pub(crate) fn run_format_migrations(
+    db: &Database,
+    app: Option<&AppType>,
+    dry_run: bool,
+) -> Result<FormatMigrationReport, AppError> {
+    let mut report = FormatMigrationReport {
+        dry_run,
+        ..Default::default()
+    };
+    for app_type in AppType::all() {
+        if app.is_some_and(|app| *app != app_type) {
+            continue;
+        }
+        let migrations: Vec<&FormatMigration> = FORMAT_MIGRATIONS
+            .iter()
+            .filter(|migration| migration.app == app_type)
+            .collect();
+        if migrations.is_empty() {
+            continue;
+        }
+
+        for (_, mut provider) in db.get_all_providers(app_type.as_str())? {
+            let applied: Vec<String> = migrations
+                .iter()
+                .filter(|migration| (migration.apply)(&mut provider.settings_config))
+                .map(|migration| migration.id.to_string())
+                .collect();
+            if applied.is_empty() {
+                continue;
+            }
+            if !dry_run {
+                db.save_provider(app_type.as_str(), &provider)?;
+            }
+            log::info!(
+                "Provider {}/{} format migrated: {}",
+                app_type.as_str(),
+                provider.id,
+                applied.join(", ")
+            );
+            for id in &applied {
+                if !report.migrations.iter().any(|info| &info.id == id) {
+                    report.migrations.push(FormatMigrationInfo {
+                        id: id.clone(),
+                        description: tr(&format!("format_migration.{id}"), &[]),
+                    });
+                }
+            }
+            report.changes.push(FormatMigrationChange {
+                app: app_type.clone(),
+                provider_id: provider.id,
+                provider_name: provider.name,
+                migrations: applied,
+            });
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn codex_approval_policy_is_renamed_everywhere() {
+        let mut settings = json!({
+            "auth": {},
+            "config": "approval_policy = \"unless-allow-listed\"\n\n[profiles.work]\napproval_policy = \"unless-allow-listed\"\nmodel = \"o3\"\n"
+        });
+        assert!(migrate_codex_approval_policy(&mut settings));
+        let config = settings["config"].as_str().unwrap();
+        assert!(!config.contains("unless-allow-listed"));
+        assert_eq!(config.matches("\"untrusted\"").count(), 2);
+        assert!(config.contains("model = \"o3\""));
+
+        // Re-running is a no-op
+        assert!(!migrate_codex_approval_policy(&mut settings));
+    }
+}
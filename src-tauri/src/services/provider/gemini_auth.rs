@@ -4,6 +4,7 @@
 
 use crate::error::AppError;
 use crate::provider::Provider;
+use crate::services::gemini_oauth::GeminiOAuthService;
 
 /// Gemini authentication type enumeration
 ///
@@ -35,6 +36,11 @@ const PACKYCODE_KEYWORDS: [&str; 3] = ["packycode", "packyapi", "packy"];
 /// - `GeminiAuthType::Packycode`: PackyCode provider, uses API Key
 /// - `GeminiAuthType::Generic`: Other generic providers, uses API Key
 pub(crate) fn detect_gemini_auth_type(provider: &Provider) -> GeminiAuthType {
+    // Priority 0: Linked OAuth account snapshot always means OAuth mode
+    if GeminiOAuthService::linked_account(provider).is_some() {
+        return GeminiAuthType::GoogleOfficial;
+    }
+
     // Priority 1: Check partner_promotion_key (most reliable)
     if let Some(key) = provider
         .meta
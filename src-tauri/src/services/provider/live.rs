@@ -8,14 +8,21 @@ use serde_json::{json, Value};
 
 use crate::app_config::AppType;
 use crate::codex_config::{get_codex_auth_path, get_codex_config_path};
-use crate::config::{delete_file, get_claude_settings_path, read_json_file, write_json_file};
+use crate::config::{
+    delete_file, ensure_writable, get_claude_local_settings_path, get_claude_settings_path,
+    read_json_file, write_json_file,
+};
 use crate::error::AppError;
 use crate::grok_config::get_grok_settings_path;
 use crate::provider::Provider;
 use crate::qwen_config::get_qwen_settings_path;
+use crate::services::gemini_oauth::{GeminiOAuthCredential, GeminiOAuthService};
+use crate::services::live_state::LiveStateService;
 use crate::services::mcp::McpService;
+use crate::services::sync_report::SyncReport;
 use crate::store::AppState;
 
+use super::claude_layers::{write_claude_layers, ClaudeLayers};
 use super::gemini_auth::{
     detect_gemini_auth_type, ensure_google_oauth_security_flag, GeminiAuthType,
 };
@@ -106,11 +113,60 @@ impl LiveSnapshot {
 }
 
 /// Write live configuration snapshot for a provider
+///
+/// Honors the provider-level `meta.configDir` override over the global directory settings.
+/// Every target path is checked for write permission first, so an unwritable directory
+/// fails before any file is touched.
 pub(crate) fn write_live_snapshot(app_type: &AppType, provider: &Provider) -> Result<(), AppError> {
+    crate::settings::with_provider_config_dir(provider.config_dir(), || {
+        preflight_live_paths(app_type)?;
+        write_live_files(app_type, provider)
+    })
+}
+
+/// Live files written for an app, under the currently effective config directory
+fn live_target_paths(app_type: &AppType) -> Vec<std::path::PathBuf> {
+    match app_type {
+        AppType::Claude => {
+            let mut paths = vec![get_claude_settings_path()];
+            if crate::settings::get_settings().claude_env_target
+                == crate::settings::ClaudeEnvTarget::Local
+            {
+                paths.push(get_claude_local_settings_path());
+            }
+            paths
+        }
+        AppType::Codex => vec![get_codex_auth_path(), get_codex_config_path()],
+        AppType::Gemini => vec![
+            crate::gemini_config::get_gemini_env_path(),
+            crate::gemini_config::get_gemini_settings_path(),
+        ],
+        AppType::Grok => vec![get_grok_settings_path()],
+        AppType::Qwen => vec![get_qwen_settings_path()],
+    }
+}
+
+/// Check write permission on every live file of an app before writing any of them
+fn preflight_live_paths(app_type: &AppType) -> Result<(), AppError> {
+    live_target_paths(app_type)
+        .iter()
+        .try_for_each(|path| ensure_writable(path))
+}
+
+/// Whether a write failed the permission preflight (the app is skipped rather than failed)
+fn is_permission_error(err: &AppError) -> bool {
+    matches!(
+        err,
+        AppError::Localized { key, .. }
+            if *key == "config.dir_not_writable" || *key == "config.file_not_writable"
+    )
+}
+
+fn write_live_files(app_type: &AppType, provider: &Provider) -> Result<(), AppError> {
     match app_type {
         AppType::Claude => {
-            let path = get_claude_settings_path();
-            write_json_file(&path, &provider.settings_config)?;
+            let target = crate::settings::get_settings().claude_env_target;
+            write_claude_layers(&provider.settings_config, target)?;
         }
         AppType::Codex => {
             let obj = provider
@@ -124,14 +180,24 @@ pub(crate) fn write_live_snapshot(app_type: &AppType, provider: &Provider) -> Re
                 AppError::Config("Codex 供应商配置缺少 'config' 字段或不是字符串".to_string())
             })?;
 
+            // Providers bound to a profile only change the top-level `profile` selection
+            let profile = provider
+                .meta
+                .as_ref()
+                .and_then(|meta| meta.codex_profile.as_deref());
+            let config_text = match profile {
+                Some(_) => crate::codex_config::set_active_codex_profile(config_str, profile)?,
+                None => config_str.to_string(),
+            };
+
             let auth_path = get_codex_auth_path();
             write_json_file(&auth_path, auth)?;
             let config_path = get_codex_config_path();
-            std::fs::write(&config_path, config_str).map_err(|e| AppError::io(&config_path, e))?;
+            std::fs::write(&config_path, config_text).map_err(|e| AppError::io(&config_path, e))?;
         }
         AppType::Gemini => {
             // Delegate to write_gemini_live which handles env file writing correctly
-            write_gemini_live(provider)?;
+            write_gemini_live(provider, None)?;
         }
         AppType::Grok => {
             use crate::grok_config::write_grok_settings;
@@ -147,59 +213,164 @@ pub(crate) fn write_live_snapshot(app_type: &AppType, provider: &Provider) -> Re
     Ok(())
 }
 
+/// Write live configuration snapshot, resolving state-backed credentials
+///
+/// Gemini providers linked to an OAuth account also swap `oauth_creds.json`.
+/// File states are recorded under the provider's `meta.configDir` when set.
+pub(crate) fn write_live_with_state(
+    state: &AppState,
+    app_type: &AppType,
+    provider: &Provider,
+) -> Result<(), AppError> {
+    crate::settings::with_provider_config_dir(provider.config_dir(), || {
+        preflight_live_paths(app_type)?;
+        if matches!(app_type, AppType::Gemini) {
+            let credential = GeminiOAuthService::credential_for_provider(state, provider)?;
+            write_gemini_live(provider, credential.as_ref())?;
+        } else {
+            write_live_files(app_type, provider)?;
+        }
+        LiveStateService::record_quietly(state, std::slice::from_ref(app_type));
+        Ok(())
+    })
+}
+
 /// Sync current provider to live configuration
 ///
 /// 使用有效的当前供应商 ID（验证过存在性）。
 /// 优先从本地 settings 读取，验证后 fallback 到数据库的 is_current 字段。
 /// 这确保了配置导入后无效 ID 会自动 fallback 到数据库。
-pub fn sync_current_to_live(state: &AppState) -> Result<(), AppError> {
-    for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini, AppType::Grok, AppType::Qwen] {
-        // Use validated effective current provider
-        let current_id =
-            match crate::settings::get_effective_current_provider(&state.db, &app_type)? {
-                Some(id) => id,
-                None => continue,
-            };
-
-        let providers = state.db.get_all_providers(app_type.as_str())?;
-        if let Some(provider) = providers.get(&current_id) {
-            write_live_snapshot(&app_type, provider)?;
-        }
-        // Note: get_effective_current_provider already validates existence,
-        // so providers.get() should always succeed here
-    }
+///
+/// 各应用的 live 文件互相独立，并行写入；某个应用失败不会中止或拖慢其他应用，
+/// 结果按应用返回。配置目录无写入权限的应用记为跳过并附带原因。仅 MCP 同步失败时返回错误。
+pub fn sync_current_to_live(state: &AppState) -> Result<Vec<SyncReport>, AppError> {
+    let results = write_current_live_parallel(state);
 
     // MCP sync
-    McpService::sync_all_enabled(state)?;
-    Ok(())
+    let mcp_result = McpService::sync_all_enabled(state);
+
+    // Baselines are recorded after MCP sync so they match the final live files
+    let reports = results
+        .into_iter()
+        .map(|(app_type, result)| match result {
+            Ok(Some(provider)) => {
+                super::backfill::record_baseline(state, &app_type, &provider);
+                SyncReport::success(app_type)
+            }
+            Ok(None) => SyncReport::skipped(app_type),
+            Err(e) if is_permission_error(&e) => {
+                log::warn!("跳过 {} 的 live 配置同步: {e}", app_type.as_str());
+                SyncReport::skipped_with(app_type, &e)
+            }
+            Err(e) => {
+                log::warn!("同步 {} 的 live 配置失败: {e}", app_type.as_str());
+                SyncReport::failed(app_type, &e)
+            }
+        })
+        .collect();
+    mcp_result?;
+    Ok(reports)
+}
+
+/// Write each app's current provider to its live files, one thread per app
+///
+/// Returns the written provider per app (`None` when the app has no current provider).
+fn write_current_live_parallel(
+    state: &AppState,
+) -> Vec<(AppType, Result<Option<Provider>, AppError>)> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = AppType::all()
+            .into_iter()
+            .map(|app_type| {
+                let handle = scope.spawn({
+                    let app_type = app_type.clone();
+                    move || write_current_live(state, &app_type)
+                });
+                (app_type, handle)
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|(app_type, handle)| {
+                let result = handle.join().unwrap_or_else(|_| {
+                    Err(AppError::Message(format!(
+                        "写入 {} 的 live 配置时线程崩溃",
+                        app_type.as_str()
+                    )))
+                });
+                (app_type, result)
+            })
+            .collect()
+    })
+}
+
+fn write_current_live(state: &AppState, app_type: &AppType) -> Result<Option<Provider>, AppError> {
+    // Use validated effective current provider
+    let Some(current_id) = crate::settings::get_effective_current_provider(&state.db, app_type)?
+    else {
+        return Ok(None);
+    };
+
+    // get_effective_current_provider already validates existence,
+    // so providers.get() should always succeed here
+    let providers = state.db.get_all_providers(app_type.as_str())?;
+    let Some(provider) = providers.get(&current_id) else {
+        return Ok(None);
+    };
+    write_live_with_state(state, app_type, provider)?;
+    Ok(Some(provider.clone()))
+}
+
+/// Read live settings from the directory a provider is written to
+///
+/// Uses the provider-level `meta.configDir` when set, the global directory otherwise.
+pub fn read_live_settings_for(app_type: AppType, provider: &Provider) -> Result<Value, AppError> {
+    crate::settings::with_provider_config_dir(provider.config_dir(), || {
+        read_live_settings(app_type)
+    })
+}
+
+/// Read the live settings a provider owns, for backfill and baselines
+///
+/// Same as [`read_live_settings_for`] except for Claude, where the effective view would
+/// pull `settings.local.json` and managed keys into the provider.
+pub(crate) fn read_provider_live_for(
+    app_type: AppType,
+    provider: &Provider,
+) -> Result<Value, AppError> {
+    if !matches!(app_type, AppType::Claude) {
+        return read_live_settings_for(app_type, provider);
+    }
+    crate::settings::with_provider_config_dir(provider.config_dir(), || {
+        let layers = ClaudeLayers::read()?;
+        if layers.is_empty() {
+            return Err(AppError::localized("claude.live.missing"));
+        }
+        Ok(layers.provider_view(crate::settings::get_settings().claude_env_target))
+    })
 }
 
 /// Read current live settings for an app type
+///
+/// For Claude this is the effective view merged from `settings.json`,
+/// `settings.local.json` and managed settings.
 pub fn read_live_settings(app_type: AppType) -> Result<Value, AppError> {
     match app_type {
         AppType::Codex => {
             let auth_path = get_codex_auth_path();
             if !auth_path.exists() {
-                return Err(AppError::localized(
-                    "codex.auth.missing",
-                    "Codex 配置文件不存在：缺少 auth.json",
-                    "Codex configuration missing: auth.json not found",
-                ));
+                return Err(AppError::localized("codex.auth.missing"));
             }
             let auth: Value = read_json_file(&auth_path)?;
             let cfg_text = crate::codex_config::read_and_validate_codex_config_text()?;
             Ok(json!({ "auth": auth, "config": cfg_text }))
         }
         AppType::Claude => {
-            let path = get_claude_settings_path();
-            if !path.exists() {
-                return Err(AppError::localized(
-                    "claude.live.missing",
-                    "Claude Code 配置文件不存在",
-                    "Claude settings file is missing",
-                ));
+            let layers = ClaudeLayers::read()?;
+            if layers.is_empty() {
+                return Err(AppError::localized("claude.live.missing"));
             }
-            read_json_file(&path)
+            Ok(layers.effective())
         }
         AppType::Gemini => {
             use crate::gemini_config::{
@@ -209,11 +380,7 @@ pub fn read_live_settings(app_type: AppType) -> Result<Value, AppError> {
             // Read .env file (environment variables)
             let env_path = get_gemini_env_path();
             if !env_path.exists() {
-                return Err(AppError::localized(
-                    "gemini.env.missing",
-                    "Gemini .env 文件不存在",
-                    "Gemini .env file not found",
-                ));
+                return Err(AppError::localized("gemini.env.missing"));
             }
 
             let env_map = read_gemini_env()?;
@@ -237,11 +404,7 @@ pub fn read_live_settings(app_type: AppType) -> Result<Value, AppError> {
         AppType::Grok => {
             let path = get_grok_settings_path();
             if !path.exists() {
-                return Err(AppError::localized(
-                    "grok.live.missing",
-                    "Grok 配置文件不存在",
-                    "Grok settings file is missing",
-                ));
+                return Err(AppError::localized("grok.live.missing"));
             }
             read_json_file(&path)
         }
@@ -249,11 +412,7 @@ pub fn read_live_settings(app_type: AppType) -> Result<Value, AppError> {
             // Qwen 配置读取逻辑
             let path = get_qwen_settings_path();
             if !path.exists() {
-                return Err(AppError::localized(
-                    "qwen.live.missing",
-                    "Qwen 配置文件不存在",
-                    "Qwen settings file is missing",
-                ));
+                return Err(AppError::localized("qwen.live.missing"));
             }
             read_json_file(&path)
         }
@@ -276,11 +435,7 @@ pub fn import_default_config(state: &AppState, app_type: AppType) -> Result<bool
         AppType::Codex => {
             let auth_path = get_codex_auth_path();
             if !auth_path.exists() {
-                return Err(AppError::localized(
-                    "codex.live.missing",
-                    "Codex 配置文件不存在",
-                    "Codex configuration file is missing",
-                ));
+                return Err(AppError::localized("codex.live.missing"));
             }
             let auth: Value = read_json_file(&auth_path)?;
             let config_str = crate::codex_config::read_and_validate_codex_config_text()?;
@@ -289,11 +444,7 @@ pub fn import_default_config(state: &AppState, app_type: AppType) -> Result<bool
         AppType::Claude => {
             let settings_path = get_claude_settings_path();
             if !settings_path.exists() {
-                return Err(AppError::localized(
-                    "claude.live.missing",
-                    "Claude Code 配置文件不存在",
-                    "Claude settings file is missing",
-                ));
+                return Err(AppError::localized("claude.live.missing"));
             }
             let mut v = read_json_file::<Value>(&settings_path)?;
             let _ = normalize_claude_models_in_value(&mut v);
@@ -307,11 +458,7 @@ pub fn import_default_config(state: &AppState, app_type: AppType) -> Result<bool
             // Read .env file (environment variables)
             let env_path = get_gemini_env_path();
             if !env_path.exists() {
-                return Err(AppError::localized(
-                    "gemini.live.missing",
-                    "Gemini 配置文件不存在",
-                    "Gemini configuration file is missing",
-                ));
+                return Err(AppError::localized("gemini.live.missing"));
             }
 
             let env_map = read_gemini_env()?;
@@ -335,11 +482,7 @@ pub fn import_default_config(state: &AppState, app_type: AppType) -> Result<bool
         AppType::Grok => {
             let path = get_grok_settings_path();
             if !path.exists() {
-                return Err(AppError::localized(
-                    "grok.live.missing",
-                    "Grok 配置文件不存在",
-                    "Grok settings file is missing",
-                ));
+                return Err(AppError::localized("grok.live.missing"));
             }
             read_json_file(&path)?
         }
@@ -347,11 +490,7 @@ pub fn import_default_config(state: &AppState, app_type: AppType) -> Result<bool
             // Qwen 配置读取逻辑
             let path = get_qwen_settings_path();
             if !path.exists() {
-                return Err(AppError::localized(
-                    "qwen.live.missing",
-                    "Qwen 配置文件不存在",
-                    "Qwen configuration file is missing",
-                ));
+                return Err(AppError::localized("qwen.live.missing"));
             }
             read_json_file(&path)?
         }
@@ -374,7 +513,13 @@ pub fn import_default_config(state: &AppState, app_type: AppType) -> Result<bool
 }
 
 /// Write Gemini live configuration with authentication handling
-pub(crate) fn write_gemini_live(provider: &Provider) -> Result<(), AppError> {
+///
+/// When `oauth` is provided (provider linked to a saved Google account),
+/// `oauth_creds.json` is swapped alongside `.env` and `settings.json`.
+pub(crate) fn write_gemini_live(
+    provider: &Provider,
+    oauth: Option<&GeminiOAuthCredential>,
+) -> Result<(), AppError> {
     use crate::gemini_config::{
         get_gemini_settings_path, json_to_env, validate_gemini_settings_strict,
         write_gemini_env_atomic,
@@ -411,11 +556,7 @@ pub(crate) fn write_gemini_live(provider: &Provider) -> Result<(), AppError> {
             }
             config_to_write = Some(merged);
         } else if !config_value.is_null() {
-            return Err(AppError::localized(
-                "gemini.validation.invalid_config",
-                "Gemini 配置格式错误: config 必须是对象或 null",
-                "Gemini config invalid: config must be an object or null",
-            ));
+            return Err(AppError::localized("gemini.validation.invalid_config"));
         }
         // config is null: don't modify existing settings.json (preserve mcpServers etc.)
     }
@@ -430,6 +571,9 @@ pub(crate) fn write_gemini_live(provider: &Provider) -> Result<(), AppError> {
             // Google official uses OAuth, clear env
             env_map.clear();
             write_gemini_env_atomic(&env_map)?;
+            if let Some(credential) = oauth {
+                GeminiOAuthService::apply_credential(credential)?;
+            }
         }
         GeminiAuthType::Packycode => {
             // PackyCode provider, uses API Key (strict validation on switch)
@@ -2,9 +2,21 @@
 //!
 //! Handles provider CRUD operations, switching, and configuration management.
 
+mod backfill;
+mod claude_layers;
+mod codex_auth;
+mod codex_profiles;
+mod deletion;
 mod endpoints;
+mod external;
+mod format_migrations;
 mod gemini_auth;
 mod live;
+mod paste;
+mod redaction;
+mod shell_env;
+
+use std::collections::HashMap;
 
 use indexmap::IndexMap;
 use regex::Regex;
@@ -12,19 +24,39 @@ use serde::Deserialize;
 use serde_json::Value;
 
 use crate::app_config::AppType;
+use crate::codex_config::CodexProfiles;
 use crate::error::AppError;
-use crate::provider::Provider;
+use crate::provider::{Provider, ProviderGroup};
+use crate::services::app_lock::AppLockService;
+use crate::services::gemini_oauth::GeminiOAuthService;
 use crate::services::mcp::McpService;
+use crate::services::models::ModelService;
+use crate::services::process_check::{ProcessCheckService, RunningCli};
+use crate::services::stats::StatsService;
+use crate::services::sync_report::SyncReport;
 use crate::settings::CustomEndpoint;
 use crate::store::AppState;
 
 // Re-export sub-module functions for external access
-pub use live::{import_default_config, read_live_settings, sync_current_to_live};
+pub use backfill::{BackfillConflict, BackfillResolution, SwitchOutcome};
+pub use codex_auth::{CodexAuthInfo, CodexAuthKind};
+pub use codex_profiles::CodexProfileImportResult;
+pub use deletion::{DeviceReference, ProjectReference, ProviderDeletionImpact, ProviderHistory};
+pub use external::{DetectedTool, OtherTool, OtherToolImportResult};
+pub use format_migrations::{FormatMigrationChange, FormatMigrationInfo, FormatMigrationReport};
+pub use live::{
+    import_default_config, read_live_settings, read_live_settings_for, sync_current_to_live,
+};
+pub use paste::ProviderDraft;
+pub use shell_env::{ShellEnvImportResult, ShellEnvProvider, ShellEnvSelection};
 
 // Internal re-exports (pub(crate))
-pub(crate) use live::write_live_snapshot;
+pub(crate) use format_migrations::run_format_migrations;
+pub(crate) use live::write_live_with_state;
+pub(crate) use redaction::mask_credential;
 
 // Internal re-exports
+use backfill::BackfillPlan;
 use live::write_gemini_live;
 
 /// Provider business logic service
@@ -69,6 +101,88 @@ mod tests {
         assert_eq!(api_key, "token");
         assert_eq!(base_url, "https://claude.example");
     }
+
+    #[test]
+    fn provider_env_values_cover_codex_auth_and_base_url() {
+        let provider = Provider::with_id(
+            "codex".into(),
+            "Codex".into(),
+            json!({
+                "auth": { "OPENAI_API_KEY": "sk-test" },
+                "config": "model_provider = \"vendor\"\n\n[model_providers.vendor]\nbase_url = \"https://api.example.com/v1\"\n"
+            }),
+            None,
+        );
+        let values = ProviderService::provider_env_values(&AppType::Codex, &provider);
+        assert_eq!(values["OPENAI_API_KEY"], "sk-test");
+        assert_eq!(values["OPENAI_BASE_URL"], "https://api.example.com/v1");
+    }
+
+    #[test]
+    fn write_live_snapshot_honors_provider_config_dir() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let settings = json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "token" } });
+        let mut provider =
+            Provider::with_id("claude".into(), "Claude".into(), settings.clone(), None);
+        provider.meta = Some(crate::provider::ProviderMeta {
+            config_dir: Some(dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        });
+
+        live::write_live_snapshot(&AppType::Claude, &provider).expect("write live");
+        let live = read_live_settings_for(AppType::Claude, &provider).expect("read live");
+        assert_eq!(live, settings);
+        assert!(dir.path().join("settings.json").exists());
+        assert!(crate::settings::get_claude_override_dir() != Some(dir.path().to_path_buf()));
+    }
+
+    #[test]
+    fn update_with_masked_credentials_keeps_stored_key() {
+        let db = std::sync::Arc::new(crate::database::Database::memory().expect("db"));
+        let state = AppState::new(db.clone());
+        let stored = Provider::with_id(
+            "masked-update".into(),
+            "Relay".into(),
+            json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "sk-ant-REDACTED",
+                    "ANTHROPIC_BASE_URL": "https://api.example.com"
+                }
+            }),
+            None,
+        );
+        db.save_provider(AppType::Claude.as_str(), &stored).unwrap();
+
+        let mut edited = stored.clone();
+        ProviderService::mask_credentials(&mut edited);
+        edited.name = "Relay (edited)".into();
+        ProviderService::update(&state, AppType::Claude, edited).expect("update");
+
+        let saved = &db.get_all_providers(AppType::Claude.as_str()).unwrap()["masked-update"];
+        assert_eq!(saved.name, "Relay (edited)");
+        assert_eq!(
+            saved.settings_config["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "sk-ant-REDACTED"
+        );
+
+        // Duplicating under a new id cannot restore the masked key
+        let mut copy = stored.clone();
+        ProviderService::mask_credentials(&mut copy);
+        copy.id = "masked-copy".into();
+        assert!(ProviderService::add(&state, AppType::Claude, copy).is_err());
+    }
+
+    #[test]
+    fn normalize_tags_trims_and_dedupes() {
+        let tags = normalize_tags(vec![
+            " team-a ".into(),
+            "".into(),
+            "Cheap".into(),
+            "TEAM-A".into(),
+            "  ".into(),
+        ]);
+        assert_eq!(tags, vec!["team-a".to_string(), "Cheap".to_string()]);
+    }
 }
 
 impl ProviderService {
@@ -102,9 +216,18 @@ impl ProviderService {
     /// Add a new provider
     pub fn add(state: &AppState, app_type: AppType, provider: Provider) -> Result<bool, AppError> {
         let mut provider = provider;
+        // Credentials the frontend only saw masked are kept as stored
+        redaction::restore_or_reject(
+            state
+                .db
+                .get_all_providers(app_type.as_str())?
+                .get(&provider.id),
+            &mut provider,
+        )?;
         // Normalize Claude model keys
         Self::normalize_provider_if_claude(&app_type, &mut provider);
         Self::validate_provider_settings(&app_type, &provider)?;
+        ModelService::warn_unknown(state, &app_type, &provider.name, &provider.settings_config);
 
         // Save to database
         state.db.save_provider(app_type.as_str(), &provider)?;
@@ -116,7 +239,8 @@ impl ProviderService {
             state
                 .db
                 .set_current_provider(app_type.as_str(), &provider.id)?;
-            write_live_snapshot(&app_type, &provider)?;
+            write_live_with_state(state, &app_type, &provider)?;
+            backfill::record_baseline(state, &app_type, &provider);
         }
 
         Ok(true)
@@ -129,9 +253,18 @@ impl ProviderService {
         provider: Provider,
     ) -> Result<bool, AppError> {
         let mut provider = provider;
+        // Credentials the frontend only saw masked (reveal failed or was cancelled) are kept as stored
+        redaction::restore_or_reject(
+            state
+                .db
+                .get_all_providers(app_type.as_str())?
+                .get(&provider.id),
+            &mut provider,
+        )?;
         // Normalize Claude model keys
         Self::normalize_provider_if_claude(&app_type, &mut provider);
         Self::validate_provider_settings(&app_type, &provider)?;
+        ModelService::warn_unknown(state, &app_type, &provider.name, &provider.settings_config);
 
         // Check if this is current provider (use effective current, not just DB)
         let effective_current =
@@ -142,9 +275,10 @@ impl ProviderService {
         state.db.save_provider(app_type.as_str(), &provider)?;
 
         if is_current {
-            write_live_snapshot(&app_type, &provider)?;
+            write_live_with_state(state, &app_type, &provider)?;
             // Sync MCP
             McpService::sync_all_enabled(state)?;
+            backfill::record_baseline(state, &app_type, &provider);
         }
 
         Ok(true)
@@ -167,56 +301,317 @@ impl ProviderService {
         state.db.delete_provider(app_type.as_str(), id)
     }
 
+    /// Mask the credentials in a provider's settings before it is sent to the webview
+    pub fn mask_credentials(provider: &mut Provider) {
+        redaction::mask_provider(provider);
+    }
+
+    /// Full `settings_config` of a provider, for the edit form
+    ///
+    /// Subject to the app lock and, when enabled, system authentication; the latter
+    /// shows a native dialog, so call this on a blocking thread.
+    pub fn reveal_credentials(
+        state: &AppState,
+        app_type: &AppType,
+        id: &str,
+    ) -> Result<Value, AppError> {
+        let provider = state
+            .db
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
+        AppLockService::ensure_unlocked(&state.db)?;
+        crate::auth::ensure_key_reveal_allowed(&provider.name)?;
+        Ok(provider.settings_config)
+    }
+
+    /// Report what still refers to a provider before it is deleted
+    ///
+    /// Covers current-provider flags, projects, other devices and recorded history.
+    pub fn analyze_deletion(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+    ) -> Result<ProviderDeletionImpact, AppError> {
+        deletion::analyze_provider_deletion(state, app_type, id)
+    }
+
     /// Switch to a provider
     ///
+    /// Non-interactive callers (tray, deep links): backfill conflicts are resolved by
+    /// taking live, same as before conflict detection existed.
+    /// A running target CLI only fails the switch when `blockSwitchWhenCliRunning` is on.
+    pub fn switch(state: &AppState, app_type: AppType, id: &str) -> Result<(), AppError> {
+        let outcome = Self::switch_with_resolution(
+            state,
+            app_type,
+            id,
+            Some(BackfillResolution::TakeLive),
+            false,
+        )?;
+        match outcome {
+            SwitchOutcome::CliRunning { processes } => {
+                let pids = processes
+                    .iter()
+                    .map(|process| process.pid.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Err(AppError::localized_with(
+                    "provider.cli_running",
+                    &[("pids", pids)],
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Switch to a provider, reporting backfill conflicts
+    ///
     /// Switch flow:
-    /// 1. Validate target provider exists
+    /// 1. Validate target provider exists and look for running target CLI processes
+    ///    - With `blockSwitchWhenCliRunning` on, return `SwitchOutcome::CliRunning`
+    ///      without switching unless `force` is set; otherwise they are reported
+    ///      as a warning in `SwitchOutcome::Switched`
     /// 2. **Backfill mechanism**: Backfill current live config to current provider, protect user manual modifications
-    /// 3. Update local settings current_provider_xxx (device-level)
-    /// 4. Update database is_current (as default for new devices)
-    /// 5. Write target provider config to live files
-    /// 6. Sync MCP configuration
-    pub fn switch(state: &AppState, app_type: AppType, id: &str) -> Result<(), AppError> {
+    ///    - If both the stored config and live changed since the last write, return
+    ///      `SwitchOutcome::Conflict` without switching unless `resolution` is given
+    /// 3. Run "before" switch hooks (an aborting hook failure cancels the switch)
+    /// 4. Update local settings current_provider_xxx (device-level)
+    /// 5. Update database is_current (as default for new devices)
+    /// 6. Write target provider config to live files
+    /// 7. Sync MCP configuration
+    /// 8. Run "after" switch hooks in the background
+    ///
+    /// Async callers should use `prepare_switch` / `finish_switch` instead and run the
+    /// "before" hooks in between, so slow hooks don't hold a database task.
+    pub fn switch_with_resolution(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        resolution: Option<BackfillResolution>,
+        force: bool,
+    ) -> Result<SwitchOutcome, AppError> {
+        let pending = match Self::prepare_switch(state, app_type, id, resolution, force)? {
+            SwitchPreparation::Done(outcome) => return Ok(outcome),
+            SwitchPreparation::Ready(pending) => pending,
+        };
+        crate::hooks::run_before(pending.hook_context())?;
+        Self::finish_switch(state, pending)
+    }
+
+    /// Switch steps 1-2: checks and backfill, up to the "before" hooks
+    pub fn prepare_switch(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        resolution: Option<BackfillResolution>,
+        force: bool,
+    ) -> Result<SwitchPreparation, AppError> {
         // Check if provider exists
         let providers = state.db.get_all_providers(app_type.as_str())?;
         let provider = providers
             .get(id)
             .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
 
+        // Running CLI sessions may be reading the live config we are about to rewrite
+        let running = ProcessCheckService::running_clis(&app_type);
+        if !running.is_empty() {
+            if crate::settings::get_settings().block_switch_when_cli_running && !force {
+                return Ok(SwitchPreparation::Done(SwitchOutcome::CliRunning {
+                    processes: running,
+                }));
+            }
+            log::warn!(
+                "切换 {} 供应商时仍有 {} 个 CLI 进程在运行",
+                app_type.as_str(),
+                running.len()
+            );
+        }
+
         // Backfill: Backfill current live config to current provider
         // Use effective current provider (validated existence) to ensure backfill targets valid provider
         let current_id = crate::settings::get_effective_current_provider(&state.db, &app_type)?;
+        let previous_id = current_id.clone();
 
         if let Some(current_id) = current_id {
             if current_id != id {
                 // Only backfill when switching to a different provider
-                if let Ok(live_config) = read_live_settings(app_type.clone()) {
-                    if let Some(mut current_provider) = providers.get(&current_id).cloned() {
-                        current_provider.settings_config = live_config;
-                        // Ignore backfill failure, don't affect switch flow
-                        let _ = state.db.save_provider(app_type.as_str(), &current_provider);
+                if let Some(current_provider) = providers.get(&current_id) {
+                    match backfill::plan_backfill(state, &app_type, current_provider, resolution) {
+                        BackfillPlan::Conflict(conflict) => {
+                            return Ok(SwitchPreparation::Done(SwitchOutcome::Conflict(conflict)));
+                        }
+                        BackfillPlan::Save(config) => {
+                            let mut current_provider = current_provider.clone();
+                            current_provider.settings_config = config;
+                            // Ignore backfill failure, don't affect switch flow
+                            let _ = state.db.save_provider(app_type.as_str(), &current_provider);
+                        }
+                        BackfillPlan::Skip => {}
+                    }
+                }
+
+                // Gemini OAuth: refreshed tokens are written back to the linked account snapshot
+                if matches!(app_type, AppType::Gemini) {
+                    if let Some(current_provider) = providers.get(&current_id) {
+                        if let Err(e) =
+                            GeminiOAuthService::backfill_from_live(state, current_provider)
+                        {
+                            log::warn!("回填 Gemini OAuth 凭据失败: {e}");
+                        }
                     }
                 }
             }
         }
 
+        // Codex ChatGPT login: warn when the token has expired and cannot be refreshed
+        if matches!(app_type, AppType::Codex) {
+            if let Some(auth) = provider.settings_config.get("auth") {
+                let info = codex_auth::detect_codex_auth(auth);
+                if info.kind == CodexAuthKind::ChatgptOauth && info.expired && !info.refreshable {
+                    log::warn!("Codex 供应商 {id} 的 ChatGPT 登录令牌已过期，需要重新登录");
+                }
+            }
+        }
+
+        // User hooks: a failing "abort" hook cancels the switch before live files are touched
+        let hook_context = crate::hooks::HookContext {
+            app_type: app_type.clone(),
+            provider_id: id.to_string(),
+            provider_name: provider.name.clone(),
+            previous_provider_id: previous_id,
+        };
+        Ok(SwitchPreparation::Ready(PendingSwitch {
+            provider: provider.clone(),
+            running,
+            hook_context,
+        }))
+    }
+
+    /// Switch steps 4-8: runs after the "before" hooks have passed
+    pub fn finish_switch(
+        state: &AppState,
+        pending: PendingSwitch,
+    ) -> Result<SwitchOutcome, AppError> {
+        let PendingSwitch {
+            provider,
+            running,
+            hook_context,
+        } = pending;
+        let app_type = hook_context.app_type.clone();
+        let id = hook_context.provider_id.as_str();
+
         // Update local settings (device-level, takes priority)
         crate::settings::set_current_provider(&app_type, Some(id))?;
 
         // Update database is_current (as default for new devices)
         state.db.set_current_provider(app_type.as_str(), id)?;
 
-        // Sync to live (write_gemini_live handles security flag internally for Gemini)
-        write_live_snapshot(&app_type, provider)?;
+        // Sync to live (write_gemini_live handles security flag and OAuth credential for Gemini)
+        write_live_with_state(state, &app_type, &provider)?;
+
+        // Windows user-level env vars override settings.json; update them when the user opted in
+        if crate::settings::get_settings().sync_windows_env_on_switch {
+            let values = Self::provider_env_values(&app_type, &provider);
+            match crate::services::env_manager::sync_user_env_vars(app_type.as_str(), &values) {
+                Ok(Some(backup)) => log::info!(
+                    "已同步 {} 的用户环境变量，原值备份: {}",
+                    app_type.as_str(),
+                    backup.backup_path
+                ),
+                Ok(None) => {}
+                Err(e) => log::warn!("同步 {} 的用户环境变量失败: {e}", app_type.as_str()),
+            }
+        }
 
         // Sync MCP
         McpService::sync_all_enabled(state)?;
 
-        Ok(())
+        backfill::record_baseline(state, &app_type, &provider);
+        StatsService::record_switch(state, &app_type, id);
+        crate::webhooks::emit(crate::webhooks::WebhookEvent::ProviderSwitched {
+            app: app_type.as_str().to_string(),
+            provider_id: hook_context.provider_id.clone(),
+            provider_name: hook_context.provider_name.clone(),
+            previous_provider_id: hook_context.previous_provider_id.clone(),
+        });
+        // The switch is done; "after" hooks must not delay the caller
+        crate::hooks::spawn_after(hook_context);
+
+        Ok(SwitchOutcome::Switched { running })
+    }
+
+    /// Codex auth kind of a provider (`None` reads the live `~/.codex/auth.json`)
+    pub fn codex_auth_info(
+        state: &AppState,
+        provider_id: Option<&str>,
+    ) -> Result<CodexAuthInfo, AppError> {
+        let auth = match provider_id {
+            Some(id) => {
+                let providers = state.db.get_all_providers(AppType::Codex.as_str())?;
+                let provider = providers
+                    .get(id)
+                    .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
+                provider
+                    .settings_config
+                    .get("auth")
+                    .cloned()
+                    .unwrap_or(Value::Null)
+            }
+            None => {
+                let path = crate::codex_config::get_codex_auth_path();
+                if path.exists() {
+                    crate::config::read_json_file(&path)?
+                } else {
+                    Value::Null
+                }
+            }
+        };
+        Ok(codex_auth::detect_codex_auth(&auth))
+    }
+
+    /// Codex auth kinds of all providers (for list badges)
+    pub fn codex_auth_kinds(state: &AppState) -> Result<IndexMap<String, CodexAuthInfo>, AppError> {
+        let providers = state.db.get_all_providers(AppType::Codex.as_str())?;
+        Ok(providers
+            .iter()
+            .map(|(id, provider)| {
+                let auth = provider
+                    .settings_config
+                    .get("auth")
+                    .cloned()
+                    .unwrap_or(Value::Null);
+                (id.clone(), codex_auth::detect_codex_auth(&auth))
+            })
+            .collect())
+    }
+
+    /// Codex profiles (`[profiles.*]`) defined in the live `config.toml`
+    pub fn codex_profiles(state: &AppState) -> Result<CodexProfiles, AppError> {
+        codex_profiles::codex_profiles(state)
+    }
+
+    /// Select a Codex profile, editing only the top-level `profile` key of `config.toml`
+    ///
+    /// `None` clears the selection.
+    pub fn switch_codex_profile(
+        state: &AppState,
+        profile: Option<&str>,
+    ) -> Result<CodexProfiles, AppError> {
+        codex_profiles::switch_codex_profile(state, profile)
+    }
+
+    /// Import Codex profiles as providers bound to them (`meta.codexProfile`)
+    ///
+    /// Profiles that already have a bound provider are skipped.
+    pub fn import_codex_profiles(state: &AppState) -> Result<CodexProfileImportResult, AppError> {
+        codex_profiles::import_codex_profiles(state)
     }
 
     /// Sync current provider to live configuration (re-export)
-    pub fn sync_current_to_live(state: &AppState) -> Result<(), AppError> {
+    ///
+    /// Returns one report per app; a failing app does not stop the others.
+    pub fn sync_current_to_live(state: &AppState) -> Result<Vec<SyncReport>, AppError> {
         sync_current_to_live(state)
     }
 
@@ -227,11 +622,83 @@ impl ProviderService {
         import_default_config(state, app_type)
     }
 
+    /// Build a provider draft from pasted settings.json / config.toml + auth.json / .env content
+    ///
+    /// Nothing is saved; credentials that cannot be extracted are reported as warnings.
+    pub fn parse_pasted_config(app_type: AppType, text: &str) -> Result<ProviderDraft, AppError> {
+        paste::parse_pasted_config(&app_type, text)
+    }
+
+    /// Rewrite stored provider snapshots to the current CLI settings format
+    ///
+    /// Runs the registered migrations of `app_type` (all apps when `None`);
+    /// with `dry_run` nothing is saved and the report lists what would change.
+    pub fn run_format_migrations(
+        state: &AppState,
+        app_type: Option<&AppType>,
+        dry_run: bool,
+    ) -> Result<FormatMigrationReport, AppError> {
+        run_format_migrations(&state.db, app_type, dry_run)
+    }
+
+    /// Find other switchers (e.g. claude-code-router) whose configuration can be imported
+    pub fn detect_other_tools() -> Vec<DetectedTool> {
+        external::detect_other_tools()
+    }
+
+    /// Import the providers of another switcher
+    ///
+    /// Entries without endpoint or key, and ones matching an existing provider, are skipped.
+    pub fn import_other_tool(
+        state: &AppState,
+        tool: OtherTool,
+        path: Option<&str>,
+    ) -> Result<OtherToolImportResult, AppError> {
+        external::import_other_tool(state, tool, path)
+    }
+
+    /// Find provider variables (e.g. `ANTHROPIC_BASE_URL`) exported in shell configuration files
+    pub fn scan_shell_env(state: &AppState) -> Result<Vec<ShellEnvProvider>, AppError> {
+        shell_env::scan_shell_env_providers(state)
+    }
+
+    /// Import the selected shell variable groups as providers
+    ///
+    /// With `comment_out` the imported lines are commented out (with backup) so they no
+    /// longer override the live configuration.
+    pub fn import_shell_env(
+        state: &AppState,
+        selections: &[ShellEnvSelection],
+        comment_out: bool,
+    ) -> Result<ShellEnvImportResult, AppError> {
+        shell_env::import_shell_env_providers(state, selections, comment_out)
+    }
+
     /// Read current live settings (re-export)
     pub fn read_live_settings(app_type: AppType) -> Result<Value, AppError> {
         read_live_settings(app_type)
     }
 
+    /// Read live settings from the current provider's directory
+    ///
+    /// Falls back to the global directory when there is no current provider.
+    pub fn read_current_live_settings(
+        state: &AppState,
+        app_type: AppType,
+    ) -> Result<Value, AppError> {
+        let current = match crate::settings::get_effective_current_provider(&state.db, &app_type)? {
+            Some(id) => state
+                .db
+                .get_all_providers(app_type.as_str())?
+                .shift_remove(&id),
+            None => None,
+        };
+        match current {
+            Some(provider) => read_live_settings_for(app_type, &provider),
+            None => read_live_settings(app_type),
+        }
+    }
+
     /// Get custom endpoints list (re-export)
     pub fn get_custom_endpoints(
         state: &AppState,
@@ -293,6 +760,69 @@ impl ProviderService {
         Ok(true)
     }
 
+    /// Get providers grouped by category, in the persisted category order
+    pub fn list_grouped(
+        state: &AppState,
+        app_type: AppType,
+    ) -> Result<Vec<ProviderGroup>, AppError> {
+        state.db.get_grouped_providers(app_type.as_str())
+    }
+
+    /// Get the persisted category order
+    pub fn category_order(state: &AppState, app_type: AppType) -> Result<Vec<String>, AppError> {
+        state.db.get_category_order(app_type.as_str())
+    }
+
+    /// Persist the category order (trimmed, empty and duplicate names dropped)
+    pub fn set_category_order(
+        state: &AppState,
+        app_type: AppType,
+        order: Vec<String>,
+    ) -> Result<Vec<String>, AppError> {
+        let order = normalize_tags(order);
+        state.db.set_category_order(app_type.as_str(), &order)?;
+        Ok(order)
+    }
+
+    /// Move a provider to another category
+    ///
+    /// 空分类视为未分类；已有同名分类（忽略大小写）时沿用其写法，新分类追加到分类顺序末尾。
+    /// `sort_index` 为供应商在新分类中的排序索引，缺省时保持不变。
+    pub fn set_category(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        category: Option<String>,
+        sort_index: Option<usize>,
+    ) -> Result<bool, AppError> {
+        let mut order = state.db.get_category_order(app_type.as_str())?;
+        let (category, is_new) = match category.as_deref().map(str::trim) {
+            None | Some("") => (None, false),
+            Some(name) => match order.iter().find(|c| c.eq_ignore_ascii_case(name)) {
+                Some(existing) => (Some(existing.clone()), false),
+                None => (Some(name.to_string()), true),
+            },
+        };
+
+        if !state.db.set_provider_category(
+            app_type.as_str(),
+            provider_id,
+            category.as_deref(),
+            sort_index,
+        )? {
+            return Err(AppError::Database(format!(
+                "Provider not found: {}",
+                provider_id
+            )));
+        }
+
+        if let Some(category) = category.filter(|_| is_new) {
+            order.push(category);
+            state.db.set_category_order(app_type.as_str(), &order)?;
+        }
+        Ok(true)
+    }
+
     /// Update provider pin status
     pub fn update_pin_status(
         state: &AppState,
@@ -311,55 +841,99 @@ impl ProviderService {
         }
     }
 
+    /// Set provider tags (trimmed, empty and duplicate tags dropped)
+    pub fn set_tags(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        tags: Vec<String>,
+    ) -> Result<Vec<String>, AppError> {
+        let tags = normalize_tags(tags);
+        if !state
+            .db
+            .set_provider_tags(app_type.as_str(), provider_id, &tags)?
+        {
+            return Err(AppError::Database(format!(
+                "Provider not found: {}",
+                provider_id
+            )));
+        }
+        Ok(tags)
+    }
+
+    /// List providers carrying the given tags
+    ///
+    /// `match_all = true` 时要求包含全部标签，否则包含任一即可；标签比较忽略大小写。
+    pub fn list_by_tags(
+        state: &AppState,
+        app_type: AppType,
+        tags: &[String],
+        match_all: bool,
+    ) -> Result<IndexMap<String, Provider>, AppError> {
+        let wanted = normalize_tags(tags.to_vec());
+        let providers = state.db.get_all_providers(app_type.as_str())?;
+        if wanted.is_empty() {
+            return Ok(providers);
+        }
+
+        Ok(providers
+            .into_iter()
+            .filter(|(_, provider)| {
+                let has = |tag: &String| provider.tags.iter().any(|t| t.eq_ignore_ascii_case(tag));
+                if match_all {
+                    wanted.iter().all(has)
+                } else {
+                    wanted.iter().any(has)
+                }
+            })
+            .collect())
+    }
+
+    /// All tags used by providers of the app, sorted
+    pub fn all_tags(state: &AppState, app_type: AppType) -> Result<Vec<String>, AppError> {
+        let providers = state.db.get_all_providers(app_type.as_str())?;
+        let tags = providers
+            .values()
+            .flat_map(|p| p.tags.iter().cloned())
+            .collect::<Vec<_>>();
+        let mut tags = normalize_tags(tags);
+        tags.sort_by_key(|t| t.to_lowercase());
+        Ok(tags)
+    }
+
     pub(crate) fn write_gemini_live(provider: &Provider) -> Result<(), AppError> {
-        write_gemini_live(provider)
+        write_gemini_live(provider, None)
     }
 
     fn validate_provider_settings(app_type: &AppType, provider: &Provider) -> Result<(), AppError> {
         match app_type {
             AppType::Claude => {
                 if !provider.settings_config.is_object() {
-                    return Err(AppError::localized(
-                        "provider.claude.settings.not_object",
-                        "Claude 配置必须是 JSON 对象",
-                        "Claude configuration must be a JSON object",
-                    ));
+                    return Err(AppError::localized("provider.claude.settings.not_object"));
                 }
             }
             AppType::Codex => {
                 let settings = provider.settings_config.as_object().ok_or_else(|| {
-                    AppError::localized(
-                        "provider.codex.settings.not_object",
-                        "Codex 配置必须是 JSON 对象",
-                        "Codex configuration must be a JSON object",
-                    )
+                    AppError::localized("provider.codex.settings.not_object")
                 })?;
 
                 let auth = settings.get("auth").ok_or_else(|| {
-                    AppError::localized(
+                    AppError::localized_with(
                         "provider.codex.auth.missing",
-                        format!("供应商 {} 缺少 auth 配置", provider.id),
-                        format!("Provider {} is missing auth configuration", provider.id),
+                        &[("id", provider.id.clone())],
                     )
                 })?;
                 if !auth.is_object() {
-                    return Err(AppError::localized(
+                    return Err(AppError::localized_with(
                         "provider.codex.auth.not_object",
-                        format!("供应商 {} 的 auth 配置必须是 JSON 对象", provider.id),
-                        format!(
-                            "Provider {} auth configuration must be a JSON object",
-                            provider.id
-                        ),
+                        &[("id", provider.id.clone())],
                     ));
                 }
+                codex_auth::validate_codex_auth(auth, &provider.id)?;
 
                 if let Some(config_value) = settings.get("config") {
                     if !(config_value.is_string() || config_value.is_null()) {
-                        return Err(AppError::localized(
-                            "provider.codex.config.invalid_type",
-                            "Codex config 字段必须是字符串",
-                            "Codex config field must be a string",
-                        ));
+                        return Err(AppError::localized("provider.codex.config.invalid_type"));
                     }
                     if let Some(cfg_text) = config_value.as_str() {
                         crate::codex_config::validate_config_toml(cfg_text)?;
@@ -385,7 +959,48 @@ impl ProviderService {
     }
 
     #[allow(dead_code)]
-    fn extract_credentials(
+    /// Extract (api_key, base_url) of a stored provider, for requests against its endpoint
+    pub fn credentials(
+        state: &AppState,
+        app_type: &AppType,
+        provider_id: &str,
+    ) -> Result<(String, String), AppError> {
+        let providers = state.db.get_all_providers(app_type.as_str())?;
+        let provider = providers
+            .get(provider_id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {provider_id} 不存在")))?;
+        Self::extract_credentials(provider, app_type)
+    }
+
+    /// Environment variables a provider sets for its CLI (used to update user-level variables)
+    fn provider_env_values(app_type: &AppType, provider: &Provider) -> HashMap<String, String> {
+        match app_type {
+            AppType::Codex => {
+                let mut values = HashMap::new();
+                if let Ok((api_key, base_url)) = Self::extract_credentials(provider, app_type) {
+                    values.insert("OPENAI_API_KEY".to_string(), api_key);
+                    values.insert("OPENAI_BASE_URL".to_string(), base_url);
+                } else if let Some(api_key) = provider
+                    .settings_config
+                    .pointer("/auth/OPENAI_API_KEY")
+                    .and_then(Value::as_str)
+                {
+                    values.insert("OPENAI_API_KEY".to_string(), api_key.to_string());
+                }
+                values
+            }
+            _ => provider
+                .settings_config
+                .get("env")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn extract_credentials(
         provider: &Provider,
         app_type: &AppType,
     ) -> Result<(String, String), AppError> {
@@ -395,37 +1010,19 @@ impl ProviderService {
                     .settings_config
                     .get("env")
                     .and_then(|v| v.as_object())
-                    .ok_or_else(|| {
-                        AppError::localized(
-                            "provider.claude.env.missing",
-                            "配置格式错误: 缺少 env",
-                            "Invalid configuration: missing env section",
-                        )
-                    })?;
+                    .ok_or_else(|| AppError::localized("provider.claude.env.missing"))?;
 
                 let api_key = env
                     .get("ANTHROPIC_AUTH_TOKEN")
                     .or_else(|| env.get("ANTHROPIC_API_KEY"))
                     .and_then(|v| v.as_str())
-                    .ok_or_else(|| {
-                        AppError::localized(
-                            "provider.claude.api_key.missing",
-                            "缺少 API Key",
-                            "API key is missing",
-                        )
-                    })?
+                    .ok_or_else(|| AppError::localized("provider.claude.api_key.missing"))?
                     .to_string();
 
                 let base_url = env
                     .get("ANTHROPIC_BASE_URL")
                     .and_then(|v| v.as_str())
-                    .ok_or_else(|| {
-                        AppError::localized(
-                            "provider.claude.base_url.missing",
-                            "缺少 ANTHROPIC_BASE_URL 配置",
-                            "Missing ANTHROPIC_BASE_URL configuration",
-                        )
-                    })?
+                    .ok_or_else(|| AppError::localized("provider.claude.base_url.missing"))?
                     .to_string();
 
                 Ok((api_key, base_url))
@@ -436,23 +1033,16 @@ impl ProviderService {
                     .get("auth")
                     .and_then(|v| v.as_object())
                     .ok_or_else(|| {
-                        AppError::localized(
+                        AppError::localized_with(
                             "provider.codex.auth.missing",
-                            "配置格式错误: 缺少 auth",
-                            "Invalid configuration: missing auth section",
+                            &[("id", provider.id.clone())],
                         )
                     })?;
 
                 let api_key = auth
                     .get("OPENAI_API_KEY")
                     .and_then(|v| v.as_str())
-                    .ok_or_else(|| {
-                        AppError::localized(
-                            "provider.codex.api_key.missing",
-                            "缺少 API Key",
-                            "API key is missing",
-                        )
-                    })?
+                    .ok_or_else(|| AppError::localized("provider.codex.api_key.missing"))?
                     .to_string();
 
                 let config_toml = provider
@@ -463,28 +1053,17 @@ impl ProviderService {
 
                 let base_url = if config_toml.contains("base_url") {
                     let re = Regex::new(r#"base_url\s*=\s*["']([^"']+)["']"#).map_err(|e| {
-                        AppError::localized(
+                        AppError::localized_with(
                             "provider.regex_init_failed",
-                            format!("正则初始化失败: {e}"),
-                            format!("Failed to initialize regex: {e}"),
+                            &[("e", e.to_string())],
                         )
                     })?;
                     re.captures(config_toml)
                         .and_then(|caps| caps.get(1))
                         .map(|m| m.as_str().to_string())
-                        .ok_or_else(|| {
-                            AppError::localized(
-                                "provider.codex.base_url.invalid",
-                                "config.toml 中 base_url 格式错误",
-                                "base_url in config.toml has invalid format",
-                            )
-                        })?
+                        .ok_or_else(|| AppError::localized("provider.codex.base_url.invalid"))?
                 } else {
-                    return Err(AppError::localized(
-                        "provider.codex.base_url.missing",
-                        "config.toml 中缺少 base_url 配置",
-                        "base_url is missing from config.toml",
-                    ));
+                    return Err(AppError::localized("provider.codex.base_url.missing"));
                 };
 
                 Ok((api_key, base_url))
@@ -495,11 +1074,7 @@ impl ProviderService {
                 let env_map = json_to_env(&provider.settings_config)?;
 
                 let api_key = env_map.get("GEMINI_API_KEY").cloned().ok_or_else(|| {
-                    AppError::localized(
-                        "gemini.missing_api_key",
-                        "缺少 GEMINI_API_KEY",
-                        "Missing GEMINI_API_KEY",
-                    )
+                    AppError::localized("gemini.missing_api_key")
                 })?;
 
                 let base_url = env_map
@@ -513,11 +1088,7 @@ impl ProviderService {
                 use crate::grok_config::GrokSettings;
                 let settings = GrokSettings::from_json_value(&provider.settings_config)?;
                 let api_key = settings.api_key.ok_or_else(|| {
-                    AppError::localized(
-                        "grok.missing_api_key",
-                        "缺少 API Key",
-                        "Missing API Key",
-                    )
+                    AppError::localized("grok.missing_api_key")
                 })?;
                 let base_url = settings.base_url.unwrap_or_default();
                 Ok((api_key, base_url))
@@ -531,6 +1102,19 @@ impl ProviderService {
     }
 }
 
+/// Trim tags and drop empty / case-insensitive duplicates, keeping first occurrence
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim();
+        if tag.is_empty() || result.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            continue;
+        }
+        result.push(tag.to_string());
+    }
+    result
+}
+
 /// Normalize Claude model keys in a JSON value
 ///
 /// Reads old key (ANTHROPIC_SMALL_FAST_MODEL), writes new keys (DEFAULT_*), and deletes old key.
@@ -622,6 +1206,29 @@ pub(crate) fn normalize_claude_models_in_value(settings: &mut Value) -> bool {
     changed
 }
 
+/// Result of `ProviderService::prepare_switch`
+#[derive(Debug)]
+pub enum SwitchPreparation {
+    /// The switch ended early (conflict or running CLI)
+    Done(SwitchOutcome),
+    /// Ready to switch once the "before" hooks pass
+    Ready(PendingSwitch),
+}
+
+/// A prepared switch waiting for its "before" hooks
+#[derive(Debug)]
+pub struct PendingSwitch {
+    provider: Provider,
+    running: Vec<RunningCli>,
+    hook_context: crate::hooks::HookContext,
+}
+
+impl PendingSwitch {
+    pub fn hook_context(&self) -> &crate::hooks::HookContext {
+        &self.hook_context
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ProviderSortUpdate {
     pub id: String,
@@ -0,0 +1,292 @@
+//! Pasted configuration parsing
+//!
+//! Turns raw settings.json / config.toml + auth.json / .env content copied from
+//! vendor docs into a pre-filled provider draft.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::gemini_config::{env_to_json, parse_env_file};
+use crate::provider::Provider;
+
+use super::ProviderService;
+
+/// Format detected in the pasted text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PastedFormat {
+    Json,
+    Env,
+    /// config.toml, optionally followed or preceded by an auth.json block
+    Toml,
+}
+
+/// Provider draft built from pasted configuration (not saved)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderDraft {
+    /// Draft provider with an empty ID; the caller assigns one when saving
+    pub provider: Provider,
+    pub format: PastedFormat,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Non-fatal problems, e.g. credentials that could not be extracted
+    pub warnings: Vec<String>,
+}
+
+/// Parse pasted configuration text for an app type into a provider draft
+pub(crate) fn parse_pasted_config(
+    app_type: &AppType,
+    text: &str,
+) -> Result<ProviderDraft, AppError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(AppError::localized("provider.paste.empty"));
+    }
+
+    let (settings_config, format) = match app_type {
+        AppType::Claude | AppType::Gemini => parse_env_settings(app_type, text)?,
+        AppType::Codex => parse_codex(text)?,
+        AppType::Grok | AppType::Qwen => (parse_json_object(text)?, PastedFormat::Json),
+    };
+
+    let mut provider = Provider::with_id(String::new(), String::new(), settings_config, None);
+    ProviderService::normalize_provider_if_claude(app_type, &mut provider);
+    ProviderService::validate_provider_settings(app_type, &provider)?;
+
+    let mut warnings = Vec::new();
+    let (api_key, base_url) = match ProviderService::extract_credentials(&provider, app_type) {
+        Ok((api_key, base_url)) => (non_empty(api_key), non_empty(base_url)),
+        Err(e) => {
+            warnings.push(e.to_string());
+            (None, None)
+        }
+    };
+
+    let endpoint = base_url.as_deref().and_then(|u| url::Url::parse(u).ok());
+    let host = endpoint
+        .as_ref()
+        .and_then(|u| u.host_str().map(str::to_string));
+    provider.name = host
+        .clone()
+        .unwrap_or_else(|| app_type.as_str().to_string());
+    provider.website_url = endpoint
+        .as_ref()
+        .zip(host)
+        .map(|(url, host)| format!("{}://{host}", url.scheme()));
+
+    Ok(ProviderDraft {
+        provider,
+        format,
+        api_key,
+        base_url,
+        warnings,
+    })
+}
+
+/// Claude / Gemini: settings JSON, a bare env object, or `.env` lines
+fn parse_env_settings(app_type: &AppType, text: &str) -> Result<(Value, PastedFormat), AppError> {
+    if text.starts_with('{') {
+        let obj = parse_json_object(text)?;
+        let settings = if obj.get("env").is_some() || !looks_like_env(&obj) {
+            obj
+        } else {
+            json!({ "env": obj })
+        };
+        return Ok((settings, PastedFormat::Json));
+    }
+
+    let env = parse_env_lines(text);
+    if env.is_empty() {
+        return Err(AppError::localized_with(
+            "provider.paste.unrecognized",
+            &[("app", app_type.as_str().to_string())],
+        ));
+    }
+    Ok((env_to_json(&env), PastedFormat::Env))
+}
+
+/// Codex: `{ "auth", "config" }` JSON, auth.json alone, or config.toml with an auth.json block
+fn parse_codex(text: &str) -> Result<(Value, PastedFormat), AppError> {
+    if let Ok(value @ Value::Object(_)) = serde_json::from_str::<Value>(text) {
+        let settings = if value.get("auth").is_some() {
+            let mut value = value;
+            if value.get("config").is_none() {
+                value["config"] = json!("");
+            }
+            value
+        } else {
+            json!({ "auth": value, "config": "" })
+        };
+        return Ok((settings, PastedFormat::Json));
+    }
+
+    let (auth, config) = split_json_block(text);
+    let auth = match auth {
+        Some(auth) => auth,
+        // `OPENAI_API_KEY=...` lines are accepted in place of auth.json
+        None => match parse_env_lines(text).remove("OPENAI_API_KEY") {
+            Some(key) => json!({ "OPENAI_API_KEY": key }),
+            None => json!({}),
+        },
+    };
+    let config: String = config
+        .lines()
+        .filter(|line| !is_env_assignment(line, "OPENAI_API_KEY"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let config = config.trim().to_string();
+    crate::codex_config::validate_config_toml(&config)?;
+
+    Ok((
+        json!({ "auth": auth, "config": config }),
+        PastedFormat::Toml,
+    ))
+}
+
+fn parse_json_object(text: &str) -> Result<Value, AppError> {
+    let value: Value = serde_json::from_str(text).map_err(|e| {
+        AppError::localized_with("provider.paste.invalid_json", &[("error", e.to_string())])
+    })?;
+    if !value.is_object() {
+        return Err(AppError::localized("provider.paste.not_object"));
+    }
+    Ok(value)
+}
+
+/// A JSON object whose keys are all environment variable names with string values
+fn looks_like_env(value: &Value) -> bool {
+    value.as_object().is_some_and(|obj| {
+        !obj.is_empty()
+            && obj.iter().all(|(key, value)| {
+                value.is_string()
+                    && key
+                        .chars()
+                        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            })
+    })
+}
+
+/// Parse `.env` lines, accepting `export KEY=VALUE` and quoted values
+fn parse_env_lines(text: &str) -> HashMap<String, String> {
+    let normalized = text
+        .lines()
+        .map(|line| line.trim().strip_prefix("export ").unwrap_or(line.trim()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    parse_env_file(&normalized)
+        .into_iter()
+        .map(|(key, value)| {
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                .unwrap_or(&value)
+                .to_string();
+            (key, value)
+        })
+        .collect()
+}
+
+fn is_env_assignment(line: &str, key: &str) -> bool {
+    let line = line.trim();
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    line.split_once('=')
+        .is_some_and(|(name, _)| name.trim() == key)
+}
+
+/// Split the first top-level JSON object (a line starting with `{`) out of mixed text
+///
+/// Returns the parsed object and the remaining text.
+fn split_json_block(text: &str) -> (Option<Value>, String) {
+    let lines: Vec<&str> = text.lines().collect();
+    let Some(start) = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with('{'))
+    else {
+        return (None, text.to_string());
+    };
+    for (end, line) in lines.iter().enumerate().skip(start) {
+        if !line.trim_end().ends_with('}') {
+            continue;
+        }
+        let block = lines[start..=end].join("\n");
+        if let Ok(value @ Value::Object(_)) = serde_json::from_str::<Value>(&block) {
+            let rest = lines[..start]
+                .iter()
+                .chain(&lines[end + 1..])
+                .copied()
+                .collect::<Vec<_>>()
+                .join("\n");
+            return (Some(value), rest);
+        }
+    }
+    (None, text.to_string())
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_claude_env_lines() {
+        let draft = parse_pasted_config(
+            &AppType::Claude,
+            "export ANTHROPIC_BASE_URL=\"https://api.vendor.example/anthropic\"\nexport ANTHROPIC_AUTH_TOKEN=sk-test\n",
+        )
+        .expect("parse");
+        assert_eq!(draft.format, PastedFormat::Env);
+        assert_eq!(draft.api_key.as_deref(), Some("sk-test"));
+        assert_eq!(draft.provider.name, "api.vendor.example");
+        assert_eq!(
+            draft.provider.website_url.as_deref(),
+            Some("https://api.vendor.example")
+        );
+        assert!(draft.warnings.is_empty());
+    }
+
+    #[test]
+    fn parses_codex_toml_with_auth_block() {
+        let text = r#"
+model_provider = "vendor"
+
+[model_providers.vendor]
+name = "vendor"
+base_url = "https://codex.vendor.example/v1"
+wire_api = "responses"
+
+{
+  "OPENAI_API_KEY": "sk-codex"
+}
+"#;
+        let draft = parse_pasted_config(&AppType::Codex, text).expect("parse");
+        assert_eq!(draft.format, PastedFormat::Toml);
+        assert_eq!(draft.api_key.as_deref(), Some("sk-codex"));
+        assert_eq!(
+            draft.base_url.as_deref(),
+            Some("https://codex.vendor.example/v1")
+        );
+        let config = draft.provider.settings_config["config"].as_str().unwrap();
+        assert!(!config.contains("OPENAI_API_KEY"));
+    }
+
+    #[test]
+    fn missing_credentials_become_warnings() {
+        let draft = parse_pasted_config(&AppType::Claude, r#"{"env": {"ANTHROPIC_MODEL": "m"}}"#)
+            .expect("parse");
+        assert_eq!(draft.format, PastedFormat::Json);
+        assert!(draft.api_key.is_none());
+        assert_eq!(draft.warnings.len(), 1);
+    }
+}
@@ -0,0 +1,175 @@
+//! Credential masking for provider data sent to the webview
+//!
+//! List endpoints return providers with every credential in `settings_config` masked,
+//! so full keys only reach the frontend through `reveal_provider_credentials`.
+//! Sensitive fields are detected by key name, same as redacted exports. Updates
+//! that send a masked value back unchanged keep the stored credential; a masked
+//! value that cannot be restored is rejected rather than saved.
+
+use serde_json::Value;
+
+use crate::database::is_sensitive_key;
+use crate::error::AppError;
+use crate::provider::Provider;
+
+const MASK: char = '…';
+
+/// Mask a credential, keeping a short prefix and suffix for long values
+pub(crate) fn mask_credential(value: &str) -> String {
+    let len = value.chars().count();
+    if len == 0 {
+        return String::new();
+    }
+    if len < 16 {
+        return MASK.to_string();
+    }
+    let prefix: String = value.chars().take(6).collect();
+    let suffix: String = value.chars().skip(len - 4).collect();
+    format!("{prefix}{MASK}{suffix}")
+}
+
+pub(crate) fn mask_provider(provider: &mut Provider) {
+    mask_value(&mut provider.settings_config, false);
+}
+
+/// Mask string credentials; inside a sensitive key every string is masked
+fn mask_value(value: &mut Value, sensitive: bool) {
+    match value {
+        Value::String(s) if sensitive => *s = mask_credential(s),
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                mask_value(item, sensitive || is_sensitive_key(key));
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| mask_value(item, sensitive)),
+        _ => {}
+    }
+}
+
+/// Put stored credentials back where `incoming` still holds their masked form
+pub(crate) fn restore_masked(stored: &Value, incoming: &mut Value) {
+    restore_value(stored, incoming, false);
+}
+
+fn restore_value(stored: &Value, incoming: &mut Value, sensitive: bool) {
+    match (stored, incoming) {
+        (Value::String(original), Value::String(masked)) if sensitive => {
+            if !original.is_empty() && *masked == mask_credential(original) {
+                *masked = original.clone();
+            }
+        }
+        (Value::Object(stored_map), Value::Object(map)) => {
+            for (key, item) in map.iter_mut() {
+                if let Some(original) = stored_map.get(key) {
+                    restore_value(original, item, sensitive || is_sensitive_key(key));
+                }
+            }
+        }
+        (Value::Array(stored_items), Value::Array(items)) => {
+            for (original, item) in stored_items.iter().zip(items.iter_mut()) {
+                restore_value(original, item, sensitive);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Path of the first credential still in masked form (e.g. `env.ANTHROPIC_AUTH_TOKEN`)
+pub(crate) fn find_masked(value: &Value) -> Option<String> {
+    find_masked_at(value, "", false)
+}
+
+fn find_masked_at(value: &Value, path: &str, sensitive: bool) -> Option<String> {
+    match value {
+        Value::String(s) if sensitive && s.contains(MASK) => Some(path.to_string()),
+        Value::Object(map) => map.iter().find_map(|(key, item)| {
+            let child = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{path}.{key}")
+            };
+            find_masked_at(item, &child, sensitive || is_sensitive_key(key))
+        }),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .find_map(|(i, item)| find_masked_at(item, &format!("{path}[{i}]"), sensitive)),
+        _ => None,
+    }
+}
+
+/// Restore masked credentials from the stored provider, rejecting any that remain masked
+pub(crate) fn restore_or_reject(
+    stored: Option<&Provider>,
+    provider: &mut Provider,
+) -> Result<(), AppError> {
+    if let Some(stored) = stored {
+        restore_masked(&stored.settings_config, &mut provider.settings_config);
+    }
+    match find_masked(&provider.settings_config) {
+        Some(field) => Err(AppError::localized_with(
+            "provider.credential_masked",
+            &[("field", field)],
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn masked_credentials_round_trip() {
+        let stored = json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-ant-REDACTED",
+                "ANTHROPIC_BASE_URL": "https://api.example.com",
+                "CLAUDE_CODE_MAX_OUTPUT_TOKENS": "8192"
+            },
+            "auth": { "tokens": { "access_token": "short" } }
+        });
+        let mut provider = Provider::with_id("p".into(), "P".into(), stored.clone(), None);
+        mask_provider(&mut provider);
+        let masked = provider.settings_config;
+        assert_eq!(masked["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-ant…mnop");
+        assert_eq!(
+            masked["env"]["ANTHROPIC_BASE_URL"],
+            "https://api.example.com"
+        );
+        assert_eq!(masked["env"]["CLAUDE_CODE_MAX_OUTPUT_TOKENS"], "8192");
+        assert_eq!(masked["auth"]["tokens"]["access_token"], "…");
+
+        let mut incoming = masked.clone();
+        incoming["env"]["ANTHROPIC_BASE_URL"] = json!("https://new.example.com");
+        restore_masked(&stored, &mut incoming);
+        assert_eq!(
+            incoming["env"]["ANTHROPIC_AUTH_TOKEN"],
+            stored["env"]["ANTHROPIC_AUTH_TOKEN"]
+        );
+        assert_eq!(incoming["auth"], stored["auth"]);
+        assert_eq!(
+            incoming["env"]["ANTHROPIC_BASE_URL"],
+            "https://new.example.com"
+        );
+
+        // A newly typed key is kept as is
+        let mut replaced = masked;
+        replaced["env"]["ANTHROPIC_AUTH_TOKEN"] = json!("sk-new");
+        restore_masked(&stored, &mut replaced);
+        assert_eq!(replaced["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-new");
+
+        // A masked value with no stored original cannot be restored
+        let mut copy = Provider::with_id(
+            "copy".into(),
+            "Copy".into(),
+            json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "sk-ant…mnop" } }),
+            None,
+        );
+        let err = restore_or_reject(None, &mut copy).expect_err("masked key rejected");
+        assert_eq!(err.to_payload().code, "provider.credential_masked");
+    }
+}
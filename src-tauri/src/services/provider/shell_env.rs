@@ -0,0 +1,327 @@
+//! Import providers from shell configuration files
+//!
+//! Many users point the CLIs at a vendor by exporting variables such as
+//! `ANTHROPIC_BASE_URL` in `~/.zshrc` or `~/.bashrc` instead of editing the CLI's
+//! settings file. The scanner groups the known provider variables of each app per
+//! file; a group can be imported as a provider and its lines optionally commented
+//! out (with backup) so they no longer override the provider that cc-switch writes.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::env_checker::{check_shell_configs, EnvConflict};
+use crate::services::env_manager::{comment_out_env_vars, BackupInfo};
+use crate::store::AppState;
+
+use super::external::{
+    resolve_env_reference, sanitize_key, ImportedProvider, SkipReason, SkippedEntry,
+};
+use super::ProviderService;
+
+/// Provider variables recognised per app; the first entry of each list is the endpoint
+const PROVIDER_ENV_VARS: [(AppType, &[&str]); 3] = [
+    (
+        AppType::Claude,
+        &[
+            "ANTHROPIC_BASE_URL",
+            "ANTHROPIC_AUTH_TOKEN",
+            "ANTHROPIC_API_KEY",
+            "ANTHROPIC_MODEL",
+            "ANTHROPIC_SMALL_FAST_MODEL",
+            "ANTHROPIC_DEFAULT_HAIKU_MODEL",
+            "ANTHROPIC_DEFAULT_SONNET_MODEL",
+            "ANTHROPIC_DEFAULT_OPUS_MODEL",
+        ],
+    ),
+    (AppType::Codex, &["OPENAI_BASE_URL", "OPENAI_API_KEY"]),
+    (
+        AppType::Gemini,
+        &["GOOGLE_GEMINI_BASE_URL", "GEMINI_API_KEY", "GEMINI_MODEL"],
+    ),
+];
+
+/// Provider variables of one app found in one shell configuration file
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellEnvProvider {
+    pub app: AppType,
+    pub source_file: String,
+    /// Suggested provider name (endpoint host, or the file name)
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    pub has_api_key: bool,
+    /// Set when the provider cannot be imported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_reason: Option<SkipReason>,
+    /// The matching lines, in the format used by `check_env_conflicts`
+    pub vars: Vec<EnvConflict>,
+}
+
+/// A scanned group picked for import
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellEnvSelection {
+    pub app: AppType,
+    pub source_file: String,
+}
+
+/// Result of importing shell environment providers
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellEnvImportResult {
+    pub imported: Vec<ImportedProvider>,
+    pub skipped: Vec<SkippedEntry>,
+    /// Backup of the lines that were commented out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<BackupInfo>,
+}
+
+/// Scan shell configuration files for provider variables (always empty on Windows)
+pub(crate) fn scan_shell_env_providers(
+    state: &AppState,
+) -> Result<Vec<ShellEnvProvider>, AppError> {
+    let mut found = Vec::new();
+    for (app_type, names) in PROVIDER_ENV_VARS {
+        let lines = check_shell_configs(names).map_err(AppError::Message)?;
+        // Keep file order; `check_shell_configs` matches by substring, so filter exact names
+        let mut by_file: Vec<(String, Vec<EnvConflict>)> = Vec::new();
+        for line in lines
+            .into_iter()
+            .filter(|line| names.contains(&line.var_name.as_str()))
+        {
+            let file = source_file(&line.source_path).to_string();
+            match by_file.iter_mut().find(|(path, _)| *path == file) {
+                Some((_, vars)) => vars.push(line),
+                None => by_file.push((file, vec![line])),
+            }
+        }
+
+        for (source_file, vars) in by_file {
+            found.push(build_candidate(state, &app_type, source_file, vars)?);
+        }
+    }
+    Ok(found)
+}
+
+/// Import the selected groups; with `comment_out` their lines are disabled afterwards
+///
+/// Lines of groups skipped as duplicates are commented out as well, since the provider
+/// they describe already exists.
+pub(crate) fn import_shell_env_providers(
+    state: &AppState,
+    selections: &[ShellEnvSelection],
+    comment_out: bool,
+) -> Result<ShellEnvImportResult, AppError> {
+    let mut result = ShellEnvImportResult {
+        imported: Vec::new(),
+        skipped: Vec::new(),
+        backup: None,
+    };
+    let mut disable = Vec::new();
+    let timestamp = chrono::Utc::now().timestamp_millis();
+    let candidates = scan_shell_env_providers(state)?;
+    for (index, candidate) in candidates
+        .into_iter()
+        .filter(|candidate| {
+            selections.iter().any(|selection| {
+                selection.app == candidate.app && selection.source_file == candidate.source_file
+            })
+        })
+        .enumerate()
+    {
+        if let Some(reason) = candidate.skip_reason {
+            if reason == SkipReason::Duplicate {
+                disable.extend(candidate.vars);
+            }
+            result.skipped.push(SkippedEntry {
+                name: candidate.name,
+                reason,
+            });
+            continue;
+        }
+
+        let id = format!(
+            "{}-{}",
+            sanitize_key(&candidate.name),
+            timestamp + index as i64
+        );
+        let mut provider = Provider::with_id(
+            id.clone(),
+            candidate.name.clone(),
+            build_settings(&candidate.app, &candidate.vars),
+            None,
+        );
+        provider.tags = vec![file_name(&candidate.source_file).to_string()];
+        ProviderService::add(state, candidate.app.clone(), provider)?;
+        result.imported.push(ImportedProvider {
+            app: candidate.app.as_str().to_string(),
+            id,
+            name: candidate.name,
+        });
+        disable.extend(candidate.vars);
+    }
+
+    if comment_out && !disable.is_empty() {
+        result.backup = Some(comment_out_env_vars(disable).map_err(AppError::Message)?);
+    }
+
+    log::info!(
+        "已从 Shell 配置导入 {} 个供应商，跳过 {} 个",
+        result.imported.len(),
+        result.skipped.len()
+    );
+    Ok(result)
+}
+
+fn build_candidate(
+    state: &AppState,
+    app_type: &AppType,
+    source_file: String,
+    vars: Vec<EnvConflict>,
+) -> Result<ShellEnvProvider, AppError> {
+    let env = resolved_env(&vars);
+    let endpoint_var = PROVIDER_ENV_VARS
+        .iter()
+        .find(|(app, _)| app == app_type)
+        .map(|(_, names)| names[0])
+        .unwrap_or_default();
+    let base_url = env
+        .get(endpoint_var)
+        .map(|url| url.trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty());
+    let api_key = env
+        .iter()
+        .find(|(name, value)| {
+            (name.ends_with("_API_KEY") || name.ends_with("_AUTH_TOKEN")) && !value.is_empty()
+        })
+        .map(|(_, value)| value.clone());
+
+    let name = base_url
+        .as_deref()
+        .and_then(|url| url::Url::parse(url).ok())
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| file_name(&source_file).to_string());
+
+    let skip_reason = match &api_key {
+        // Unset, or a `$VAR` reference to a variable that is not set
+        None => Some(SkipReason::MissingApiKey),
+        Some(api_key) => {
+            let base_url = base_url.clone().unwrap_or_default();
+            let duplicate = state
+                .db
+                .get_all_providers(app_type.as_str())?
+                .values()
+                .filter_map(|provider| {
+                    ProviderService::extract_credentials(provider, app_type).ok()
+                })
+                .any(|(key, url)| &key == api_key && url.trim_end_matches('/') == base_url);
+            duplicate.then_some(SkipReason::Duplicate)
+        }
+    };
+
+    Ok(ShellEnvProvider {
+        app: app_type.clone(),
+        source_file,
+        name,
+        has_api_key: api_key.is_some(),
+        base_url,
+        skip_reason,
+        vars,
+    })
+}
+
+/// Variable values with `$VAR` references resolved; later lines win
+fn resolved_env(vars: &[EnvConflict]) -> BTreeMap<String, String> {
+    vars.iter()
+        .map(|var| (var.var_name.clone(), resolve_env_reference(&var.var_value)))
+        .collect()
+}
+
+fn build_settings(app_type: &AppType, vars: &[EnvConflict]) -> Value {
+    let env = resolved_env(vars);
+    match app_type {
+        AppType::Codex => {
+            let config = match env.get("OPENAI_BASE_URL").filter(|url| !url.is_empty()) {
+                Some(base_url) => format!(
+                    "model_provider = \"shell\"\n\n[model_providers.shell]\nname = \"shell\"\nbase_url = {}\nwire_api = \"responses\"\nrequires_openai_auth = true\n",
+                    toml::Value::from(base_url.trim_end_matches('/'))
+                ),
+                None => String::new(),
+            };
+            json!({
+                "auth": { "OPENAI_API_KEY": env.get("OPENAI_API_KEY").cloned().unwrap_or_default() },
+                "config": config,
+            })
+        }
+        _ => json!({ "env": env }),
+    }
+}
+
+/// File part of a `path:line` source
+fn source_file(source_path: &str) -> &str {
+    source_path
+        .rsplit_once(':')
+        .map_or(source_path, |(path, _)| path)
+}
+
+fn file_name(path: &str) -> &str {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn var(name: &str, value: &str, line: usize) -> EnvConflict {
+        EnvConflict {
+            var_name: name.to_string(),
+            var_value: value.to_string(),
+            source_type: "file".to_string(),
+            source_path: format!("/home/user/.zshrc:{line}"),
+        }
+    }
+
+    #[test]
+    fn builds_settings_from_shell_vars() {
+        let claude = build_settings(
+            &AppType::Claude,
+            &[
+                var("ANTHROPIC_BASE_URL", "https://old.example.com", 3),
+                var("ANTHROPIC_AUTH_TOKEN", "sk-ant", 4),
+                var("ANTHROPIC_BASE_URL", "https://api.example.com", 9),
+            ],
+        );
+        assert_eq!(
+            claude["env"]["ANTHROPIC_BASE_URL"],
+            "https://api.example.com"
+        );
+        assert_eq!(claude["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-ant");
+
+        let codex = build_settings(
+            &AppType::Codex,
+            &[
+                var("OPENAI_BASE_URL", "https://api.example.com/v1/", 1),
+                var("OPENAI_API_KEY", "sk-openai", 2),
+            ],
+        );
+        assert_eq!(codex["auth"]["OPENAI_API_KEY"], "sk-openai");
+        let config: toml::Value = codex["config"].as_str().unwrap().parse().unwrap();
+        assert_eq!(
+            config["model_providers"]["shell"]["base_url"].as_str(),
+            Some("https://api.example.com/v1")
+        );
+
+        assert_eq!(source_file("/home/user/.zshrc:12"), "/home/user/.zshrc");
+        assert_eq!(file_name("/home/user/.zshrc"), ".zshrc");
+    }
+}
@@ -0,0 +1,343 @@
+//! 供应商备注与附件
+//!
+//! - 备注按 Markdown 渲染为 HTML：原始 HTML 一律转义，链接仅保留 http/https/mailto 等安全协议
+//! - 附件（发票、接入文档等小文件）存放于数据库，单个文件与每个供应商的总量均有上限
+
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+/// 单个附件大小上限（字节）
+pub const MAX_ATTACHMENT_SIZE: u64 = 5 * 1024 * 1024;
+/// 单个供应商附件总大小上限（字节）
+pub const MAX_PROVIDER_ATTACHMENTS_SIZE: u64 = 20 * 1024 * 1024;
+
+/// 链接允许的协议（相对链接与页内锚点始终允许）
+const SAFE_LINK_SCHEMES: &[&str] = &["http", "https", "mailto"];
+/// 图片允许的协议
+const SAFE_IMAGE_SCHEMES: &[&str] = &["http", "https"];
+
+/// 附件元数据（不含内容）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderAttachment {
+    pub id: i64,
+    pub provider_id: String,
+    pub app_type: String,
+    pub file_name: String,
+    pub mime_type: String,
+    /// 文件大小（字节）
+    pub size: i64,
+    /// 添加时间（Unix 秒）
+    pub created_at: i64,
+}
+
+pub struct ProviderNotesService;
+
+impl ProviderNotesService {
+    /// 将供应商备注渲染为经过清理的 HTML 片段（无备注时为空串）
+    pub fn render_notes(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<String, AppError> {
+        let provider = Self::find_provider(state, &app_type, provider_id)?;
+        Ok(render_markdown(provider.notes.as_deref().unwrap_or("")))
+    }
+
+    /// 将供应商备注导出为独立 HTML 文件
+    pub fn export_notes_html(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        target: &Path,
+    ) -> Result<(), AppError> {
+        let provider = Self::find_provider(state, &app_type, provider_id)?;
+        let body = render_markdown(provider.notes.as_deref().unwrap_or(""));
+        let document = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n{body}</body>\n</html>\n",
+            escape_html(&provider.name),
+            escape_html(&provider.name),
+        );
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        crate::config::atomic_write(target, document.as_bytes())
+    }
+
+    /// 从本地文件添加附件
+    pub fn add_attachment(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        source: &Path,
+    ) -> Result<ProviderAttachment, AppError> {
+        Self::find_provider(state, &app_type, provider_id)?;
+
+        let file_name = source
+            .file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| !name.trim().is_empty())
+            .ok_or_else(|| AppError::InvalidInput(format!("无效的附件路径: {}", source.display())))?
+            .to_string();
+
+        // 先检查文件大小，避免把超大文件读入内存
+        let size = fs::metadata(source)
+            .map_err(|e| AppError::io(source, e))?
+            .len();
+        let existing = state
+            .db
+            .provider_attachments_size(app_type.as_str(), provider_id)?
+            .max(0) as u64;
+        check_size_limits(size, existing)?;
+
+        let data = fs::read(source).map_err(|e| AppError::io(source, e))?;
+        // 文件在检查后被改写时以实际读取的内容为准
+        check_size_limits(data.len() as u64, existing)?;
+
+        let mime_type = guess_mime_type(&file_name);
+        let id = state.db.insert_provider_attachment(
+            app_type.as_str(),
+            provider_id,
+            &file_name,
+            mime_type,
+            &data,
+        )?;
+        log::info!(
+            "已为供应商 {provider_id} ({}) 添加附件 {file_name} ({} 字节)",
+            app_type.as_str(),
+            data.len()
+        );
+
+        state
+            .db
+            .get_provider_attachments(app_type.as_str(), provider_id)?
+            .into_iter()
+            .find(|attachment| attachment.id == id)
+            .ok_or_else(|| AppError::Database(format!("附件写入后未找到: {id}")))
+    }
+
+    /// 获取供应商附件列表
+    pub fn list_attachments(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<Vec<ProviderAttachment>, AppError> {
+        state
+            .db
+            .get_provider_attachments(app_type.as_str(), provider_id)
+    }
+
+    /// 将附件另存到本地文件
+    pub fn save_attachment(state: &AppState, id: i64, target: &Path) -> Result<(), AppError> {
+        let (_, data) = state.db.get_provider_attachment_data(id)?.ok_or_else(|| {
+            AppError::localized_with("provider.attachment.not_found", &[("id", id.to_string())])
+        })?;
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        crate::config::atomic_write(target, &data)
+    }
+
+    /// 删除附件
+    pub fn delete_attachment(state: &AppState, id: i64) -> Result<bool, AppError> {
+        state.db.delete_provider_attachment(id)
+    }
+
+    fn find_provider(
+        state: &AppState,
+        app_type: &AppType,
+        provider_id: &str,
+    ) -> Result<Provider, AppError> {
+        state
+            .db
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(provider_id)
+            .ok_or_else(|| {
+                AppError::localized_with("provider.not_found", &[("id", provider_id.to_string())])
+            })
+    }
+}
+
+/// 校验新附件大小与供应商附件总量
+fn check_size_limits(size: u64, existing: u64) -> Result<(), AppError> {
+    if size > MAX_ATTACHMENT_SIZE {
+        return Err(AppError::localized_with(
+            "provider.attachment.too_large",
+            &[("max", format_mib(MAX_ATTACHMENT_SIZE))],
+        ));
+    }
+    if existing + size > MAX_PROVIDER_ATTACHMENTS_SIZE {
+        return Err(AppError::localized_with(
+            "provider.attachment.quota_exceeded",
+            &[("max", format_mib(MAX_PROVIDER_ATTACHMENTS_SIZE))],
+        ));
+    }
+    Ok(())
+}
+
+fn format_mib(bytes: u64) -> String {
+    format!("{} MB", bytes / (1024 * 1024))
+}
+
+/// 将 Markdown 渲染为可安全嵌入页面的 HTML
+pub fn render_markdown(markdown: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let events = Parser::new_ext(markdown, options).map(|event| match event {
+        // 原始 HTML 作为普通文本输出（渲染时会被转义）
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: sanitize_url(dest_url, SAFE_LINK_SCHEMES),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: sanitize_url(dest_url, SAFE_IMAGE_SCHEMES),
+            title,
+            id,
+        }),
+        other => other,
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, events);
+    output
+}
+
+/// 不安全协议（如 `javascript:`）的链接替换为 `#`
+fn sanitize_url<'a>(url: CowStr<'a>, allowed: &[&str]) -> CowStr<'a> {
+    // 浏览器解析协议时会忽略空白与控制字符（如 "java\tscript:"）
+    let normalized: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let scheme = match normalized.find([':', '/', '?', '#']) {
+        Some(idx) if normalized[idx..].starts_with(':') => &normalized[..idx],
+        // 无协议的相对链接
+        _ => return url,
+    };
+    if allowed.contains(&scheme) {
+        url
+    } else {
+        CowStr::Borrowed("#")
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 根据扩展名推断 MIME 类型
+fn guess_mime_type(file_name: &str) -> &'static str {
+    let ext = Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "zip" => "application/zip",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn render_markdown_escapes_html_and_unsafe_links() {
+        let html = render_markdown(
+            "**Key** <script>alert(1)</script>\n\n[ok](https://example.com) [bad](JavaScript:alert(1)) ![x](data:image/png;base64,AA)",
+        );
+
+        assert!(html.contains("<strong>Key</strong>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("href=\"https://example.com\""));
+        assert!(html.contains("<a href=\"#\">bad</a>"));
+        assert!(!html.contains("alert(1)\""));
+        assert!(!html.contains("data:image"));
+    }
+
+    #[test]
+    fn attachments_respect_size_limits_and_cascade() {
+        let db = Arc::new(Database::memory().expect("memory db"));
+        db.save_provider(
+            "claude",
+            &Provider::with_id("p".into(), "P".into(), json!({}), None),
+        )
+        .expect("save provider");
+        let state = AppState::new(db.clone());
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let invoice = dir.path().join("invoice.pdf");
+        fs::write(&invoice, b"%PDF-1.4").expect("write file");
+
+        let attachment =
+            ProviderNotesService::add_attachment(&state, AppType::Claude, "p", &invoice)
+                .expect("add attachment");
+        assert_eq!(attachment.file_name, "invoice.pdf");
+        assert_eq!(attachment.mime_type, "application/pdf");
+        assert_eq!(attachment.size, 8);
+
+        assert!(check_size_limits(MAX_ATTACHMENT_SIZE + 1, 0).is_err());
+        assert!(check_size_limits(1, MAX_PROVIDER_ATTACHMENTS_SIZE).is_err());
+
+        // 未知供应商不能添加附件
+        assert!(
+            ProviderNotesService::add_attachment(&state, AppType::Claude, "missing", &invoice)
+                .is_err()
+        );
+
+        db.delete_provider("claude", "p").expect("delete provider");
+        assert!(db
+            .get_provider_attachments("claude", "p")
+            .expect("list")
+            .is_empty());
+    }
+}
@@ -0,0 +1,480 @@
+//! 团队模板下发
+//!
+//! 从管理员托管的 HTTPS 地址下载 JSON 清单（不含密钥的供应商、MCP 服务器、提示词、技能仓库），
+//! 校验后幂等导入：已存在的同 id 条目保持不变，不会覆盖用户本地的修改与密钥。
+//! 清单中的密钥字段会被清空；MCP 服务器与提示词只新增不启用，由用户确认后自行开启。
+//! 配置了 `CC_SWITCH_PROVISION_URL` 环境变量或设置项时，首次启动自动导入（同一地址只导入一次）。
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::app_config::{AppType, McpApps, McpServer};
+use crate::database::{redact_value, Database};
+use crate::error::AppError;
+use crate::network::{self, NetworkFeature};
+use crate::prompt::Prompt;
+use crate::provider::Provider;
+use crate::services::skill::SkillRepoProvider;
+use crate::services::{McpService, ProviderService, SkillRepo};
+use crate::store::AppState;
+
+/// 指定清单地址的环境变量（优先于设置项）
+pub const PROVISION_URL_ENV: &str = "CC_SWITCH_PROVISION_URL";
+/// 已导入的清单地址（settings 表键名）
+const PROVISIONED_URL_KEY: &str = "provisioned_from_url";
+/// 支持的最高清单版本
+const MANIFEST_VERSION: u32 = 1;
+/// 清单大小上限
+const MAX_MANIFEST_BYTES: usize = 2 * 1024 * 1024;
+
+/// 清单中的技能仓库
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestSkillRepo {
+    pub owner: String,
+    pub name: String,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub provider: SkillRepoProvider,
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+/// 团队模板清单
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvisionManifest {
+    #[serde(default = "default_manifest_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 应用 -> 供应商列表
+    #[serde(default)]
+    pub providers: BTreeMap<String, Vec<Provider>>,
+    #[serde(default)]
+    pub mcp_servers: Vec<McpServer>,
+    /// 应用 -> 提示词列表
+    #[serde(default)]
+    pub prompts: BTreeMap<String, Vec<Prompt>>,
+    #[serde(default)]
+    pub skill_repos: Vec<ManifestSkillRepo>,
+}
+
+fn default_manifest_version() -> u32 {
+    MANIFEST_VERSION
+}
+
+/// 单条导入失败
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvisionFailure {
+    pub kind: String,
+    pub id: String,
+    pub error: String,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvisionResult {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub added_providers: Vec<String>,
+    pub added_mcp_servers: Vec<String>,
+    pub added_prompts: Vec<String>,
+    pub added_skill_repos: Vec<String>,
+    /// 本地已存在而跳过的条目数
+    pub skipped: usize,
+    pub failed: Vec<ProvisionFailure>,
+}
+
+pub struct ProvisionService;
+
+impl ProvisionService {
+    /// 当前生效的清单地址：机器策略优先，其次为环境变量与设置项
+    pub fn configured_url() -> Option<String> {
+        crate::policy::current()
+            .provision_url
+            .clone()
+            .or_else(|| std::env::var(PROVISION_URL_ENV).ok())
+            .or_else(|| crate::settings::get_settings().provision_url)
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+    }
+
+    /// 下载清单并导入；`url` 为空时使用已配置的地址
+    pub async fn provision(
+        state: &AppState,
+        url: Option<String>,
+    ) -> Result<ProvisionResult, AppError> {
+        let url = url
+            .map(|u| u.trim().to_string())
+            .filter(|u| !u.is_empty())
+            .or_else(Self::configured_url)
+            .ok_or_else(|| AppError::localized("provision.not_configured"))?;
+
+        let manifest = Self::fetch_manifest(&url).await?;
+        state
+            .run(move |state| {
+                let result = Self::apply_manifest(state, &url, manifest)?;
+                state.db.set_setting(PROVISIONED_URL_KEY, &url)?;
+                Ok(result)
+            })
+            .await
+    }
+
+    /// 启动时若配置了清单地址且尚未从该地址导入，则在后台导入（离线时等恢复联网后导入）
+    pub fn provision_on_launch(db: Arc<Database>) {
+        let Some(url) = Self::configured_url() else {
+            return;
+        };
+        match db.get_setting(PROVISIONED_URL_KEY) {
+            Ok(Some(done)) if done == url => return,
+            Ok(_) => {}
+            Err(e) => {
+                log::warn!("读取团队模板导入记录失败: {e}");
+                return;
+            }
+        }
+
+        if !network::is_online() {
+            network::defer(format!("provision: {url}"), move || {
+                Self::provision_on_launch(db)
+            });
+            return;
+        }
+
+        tauri::async_runtime::spawn(async move {
+            let state = AppState::new(db);
+            match Self::provision(&state, Some(url.clone())).await {
+                Ok(result) => log::info!(
+                    "已从 {url} 导入团队模板：供应商 {}，MCP {}，提示词 {}，技能仓库 {}，失败 {}",
+                    result.added_providers.len(),
+                    result.added_mcp_servers.len(),
+                    result.added_prompts.len(),
+                    result.added_skill_repos.len(),
+                    result.failed.len()
+                ),
+                Err(e) => log::warn!("导入团队模板失败（{url}）: {e}"),
+            }
+        });
+    }
+
+    async fn fetch_manifest(url: &str) -> Result<ProvisionManifest, AppError> {
+        network::ensure_online()?;
+        let parsed = url::Url::parse(url).map_err(|_| {
+            AppError::localized_with("provision.invalid_url", &[("url", url.to_string())])
+        })?;
+        if parsed.scheme() != "https" {
+            return Err(AppError::localized_with(
+                "provision.invalid_url",
+                &[("url", url.to_string())],
+            ));
+        }
+
+        let request_failed = |e: reqwest::Error| {
+            AppError::localized_with("provision.request_failed", &[("e", e.to_string())])
+        };
+        let client = network::client_builder(NetworkFeature::Provision)
+            .https_only(true)
+            .user_agent(concat!("cc-switch/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(request_failed)?;
+        let response = network::send_with_retry(NetworkFeature::Provision, client.get(parsed))
+            .await
+            .map_err(request_failed)?;
+        if !response.status().is_success() {
+            return Err(AppError::localized_with(
+                "provision.http_status",
+                &[("status", response.status().as_u16().to_string())],
+            ));
+        }
+        let bytes = response.bytes().await.map_err(request_failed)?;
+        if bytes.len() > MAX_MANIFEST_BYTES {
+            return Err(AppError::localized_with(
+                "provision.invalid_manifest",
+                &[("e", format!("manifest exceeds {MAX_MANIFEST_BYTES} bytes"))],
+            ));
+        }
+
+        let manifest: ProvisionManifest = serde_json::from_slice(&bytes).map_err(|e| {
+            AppError::localized_with("provision.invalid_manifest", &[("e", e.to_string())])
+        })?;
+        validate_manifest(&manifest)?;
+        Ok(manifest)
+    }
+
+    /// 幂等导入清单：仅新增本地不存在的条目
+    pub fn apply_manifest(
+        state: &AppState,
+        url: &str,
+        manifest: ProvisionManifest,
+    ) -> Result<ProvisionResult, AppError> {
+        validate_manifest(&manifest)?;
+        let mut manifest = manifest;
+        let stripped = strip_secrets(&mut manifest);
+        if !stripped.is_empty() {
+            log::warn!("团队模板中的密钥字段已清空: {}", stripped.join(", "));
+        }
+        let mut result = ProvisionResult {
+            url: url.to_string(),
+            name: manifest.name.clone(),
+            ..Default::default()
+        };
+
+        for (app, providers) in manifest.providers {
+            let app_type = AppType::from_str(&app)?;
+            let existing = state.db.get_all_providers(app_type.as_str())?;
+            for provider in providers {
+                if existing.contains_key(&provider.id) {
+                    result.skipped += 1;
+                    continue;
+                }
+                let id = provider.id.clone();
+                match ProviderService::add(state, app_type.clone(), provider) {
+                    Ok(_) => result
+                        .added_providers
+                        .push(format!("{}:{id}", app_type.as_str())),
+                    Err(e) => result.failed.push(failure("provider", &id, e)),
+                }
+            }
+        }
+
+        // MCP 服务器只新增不启用，避免未经确认就把远程下发的命令写入各应用的 live 配置
+        let existing = state.db.get_all_mcp_servers()?;
+        for mut server in manifest.mcp_servers {
+            if existing.contains_key(&server.id) {
+                result.skipped += 1;
+                continue;
+            }
+            server.apps = McpApps::default();
+            let id = server.id.clone();
+            match McpService::upsert_server(state, server) {
+                Ok(()) => result.added_mcp_servers.push(id),
+                Err(e) => result.failed.push(failure("mcp", &id, e)),
+            }
+        }
+
+        // 提示词只新增不启用，避免覆盖用户当前使用的提示词文件
+        for (app, prompts) in manifest.prompts {
+            let app_type = AppType::from_str(&app)?;
+            let existing = state.db.get_prompts(app_type.as_str())?;
+            for mut prompt in prompts {
+                if existing.contains_key(&prompt.id) {
+                    result.skipped += 1;
+                    continue;
+                }
+                prompt.enabled = false;
+                let id = prompt.id.clone();
+                match state.db.save_prompt(app_type.as_str(), &prompt) {
+                    Ok(()) => result
+                        .added_prompts
+                        .push(format!("{}:{id}", app_type.as_str())),
+                    Err(e) => result.failed.push(failure("prompt", &id, e)),
+                }
+            }
+        }
+
+        let existing = state.db.get_skill_repos()?;
+        for repo in manifest.skill_repos {
+            let key = format!("{}/{}", repo.owner, repo.name);
+            if existing.iter().any(|r| {
+                r.owner.eq_ignore_ascii_case(&repo.owner) && r.name.eq_ignore_ascii_case(&repo.name)
+            }) {
+                result.skipped += 1;
+                continue;
+            }
+            let mut skill_repo = SkillRepo::new(
+                repo.owner,
+                repo.name,
+                repo.branch.unwrap_or_else(|| "main".to_string()),
+            );
+            skill_repo.provider = repo.provider;
+            skill_repo.base_url = repo.base_url;
+            match state.db.save_skill_repo(&skill_repo) {
+                Ok(()) => result.added_skill_repos.push(key),
+                Err(e) => result.failed.push(failure("skillRepo", &key, e)),
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+fn failure(kind: &str, id: &str, error: AppError) -> ProvisionFailure {
+    ProvisionFailure {
+        kind: kind.to_string(),
+        id: id.to_string(),
+        error: error.to_string(),
+    }
+}
+
+/// 清空供应商与 MCP 服务器中的密钥字段，返回被清理的条目
+fn strip_secrets(manifest: &mut ProvisionManifest) -> Vec<String> {
+    let mut stripped = Vec::new();
+    for (app, providers) in manifest.providers.iter_mut() {
+        for provider in providers {
+            if redact_value(&mut provider.settings_config) {
+                stripped.push(format!("provider {app}:{}", provider.id));
+            }
+        }
+    }
+    for server in manifest.mcp_servers.iter_mut() {
+        let mut changed = redact_value(&mut server.server);
+        for value in server.app_overrides.values_mut() {
+            changed |= redact_value(value);
+        }
+        if changed {
+            stripped.push(format!("mcp {}", server.id));
+        }
+    }
+    stripped
+}
+
+/// 校验清单版本、应用名与条目 id
+fn validate_manifest(manifest: &ProvisionManifest) -> Result<(), AppError> {
+    let invalid = |e: String| AppError::localized_with("provision.invalid_manifest", &[("e", e)]);
+
+    if manifest.version == 0 || manifest.version > MANIFEST_VERSION {
+        return Err(invalid(format!(
+            "unsupported manifest version {}",
+            manifest.version
+        )));
+    }
+    for app in manifest.providers.keys().chain(manifest.prompts.keys()) {
+        AppType::from_str(app)?;
+    }
+
+    let ids = manifest
+        .providers
+        .values()
+        .flatten()
+        .map(|p| ("provider", p.id.as_str(), p.name.as_str()))
+        .chain(
+            manifest
+                .mcp_servers
+                .iter()
+                .map(|s| ("mcp", s.id.as_str(), s.name.as_str())),
+        )
+        .chain(
+            manifest
+                .prompts
+                .values()
+                .flatten()
+                .map(|p| ("prompt", p.id.as_str(), p.name.as_str())),
+        )
+        .chain(
+            manifest
+                .skill_repos
+                .iter()
+                .map(|r| ("skillRepo", r.owner.as_str(), r.name.as_str())),
+        );
+    for (kind, id, name) in ids {
+        if id.trim().is_empty() || name.trim().is_empty() {
+            return Err(invalid(format!("{kind} entry is missing id or name")));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn manifest(value: serde_json::Value) -> ProvisionManifest {
+        serde_json::from_value(value).expect("parse manifest")
+    }
+
+    #[test]
+    fn apply_manifest_is_idempotent() {
+        let db = Arc::new(Database::memory().expect("memory db"));
+        let state = AppState::new(db.clone());
+        db.save_provider(
+            "claude",
+            &Provider::with_id("team".into(), "Local".into(), json!({ "env": {} }), None),
+        )
+        .expect("save provider");
+
+        let value = json!({
+            "version": 1,
+            "providers": {
+                "claude": [
+                    { "id": "team", "name": "Team", "settingsConfig": { "env": {} } }
+                ]
+            },
+            "prompts": {
+                "claude": [{ "id": "style", "name": "Style", "content": "Be brief", "enabled": true }]
+            },
+            "skillRepos": [{ "owner": "acme", "name": "skills" }]
+        });
+
+        let first = ProvisionService::apply_manifest(&state, "https://x", manifest(value.clone()))
+            .expect("apply");
+        assert!(first.added_providers.is_empty());
+        assert_eq!(first.added_prompts, vec!["claude:style".to_string()]);
+        assert_eq!(first.added_skill_repos, vec!["acme/skills".to_string()]);
+        assert_eq!(first.skipped, 1);
+
+        // 已存在的供应商保持本地版本，导入的提示词不启用
+        let providers = db.get_all_providers("claude").expect("providers");
+        assert_eq!(providers["team"].name, "Local");
+        assert!(!db.get_prompts("claude").expect("prompts")["style"].enabled);
+
+        let second = ProvisionService::apply_manifest(&state, "https://x", manifest(value))
+            .expect("apply again");
+        assert!(second.added_prompts.is_empty() && second.added_skill_repos.is_empty());
+        assert_eq!(second.skipped, 3);
+    }
+
+    #[test]
+    fn apply_manifest_strips_secrets_and_leaves_mcp_disabled() {
+        let db = Arc::new(Database::memory().expect("memory db"));
+        let state = AppState::new(db.clone());
+        let value = json!({
+            "mcpServers": [{
+                "id": "tool",
+                "name": "Tool",
+                "server": { "command": "tool", "env": { "TOOL_API_KEY": "leaked" } },
+                "apps": { "claude": true, "codex": true }
+            }]
+        });
+
+        let result =
+            ProvisionService::apply_manifest(&state, "https://x", manifest(value)).expect("apply");
+        assert_eq!(result.added_mcp_servers, vec!["tool".to_string()]);
+        let server = &db.get_all_mcp_servers().expect("servers")["tool"];
+        assert!(server.apps.is_empty());
+        assert_eq!(server.server["env"]["TOOL_API_KEY"], "");
+
+        let mut with_key = manifest(json!({
+            "providers": {
+                "claude": [{
+                    "id": "team",
+                    "name": "Team",
+                    "settingsConfig": { "env": { "ANTHROPIC_AUTH_TOKEN": "sk-leaked" } }
+                }]
+            }
+        }));
+        assert_eq!(strip_secrets(&mut with_key), vec!["provider claude:team"]);
+        assert_eq!(
+            with_key.providers["claude"][0].settings_config["env"]["ANTHROPIC_AUTH_TOKEN"],
+            ""
+        );
+    }
+
+    #[test]
+    fn validate_manifest_rejects_bad_input() {
+        assert!(validate_manifest(&manifest(json!({ "version": 2 }))).is_err());
+        assert!(validate_manifest(&manifest(json!({ "providers": { "vim": [] } }))).is_err());
+        assert!(validate_manifest(&manifest(json!({
+            "mcpServers": [{ "id": "", "name": "x", "server": {}, "apps": {} }]
+        })))
+        .is_err());
+        assert!(validate_manifest(&manifest(json!({}))).is_ok());
+    }
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter};
+
+/// 检查设置变化的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 设备设置变化通知
+pub struct SettingsWatchService;
+
+impl SettingsWatchService {
+    /// 定期检查设置是否被其他进程（CLI、深链接等）修改并重新加载；
+    /// 缓存变化后发射 `settings-changed` 事件
+    pub fn start(app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut last_generation = crate::settings::sync_from_database().ok().flatten();
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                let generation =
+                    match tauri::async_runtime::spawn_blocking(crate::settings::sync_from_database)
+                        .await
+                    {
+                        Ok(Ok(generation)) => generation,
+                        Ok(Err(e)) => {
+                            log::warn!("检查设置变化失败: {e}");
+                            continue;
+                        }
+                        Err(e) => {
+                            log::warn!("设置检查任务异常: {e}");
+                            continue;
+                        }
+                    };
+
+                if generation.is_some() && generation != last_generation {
+                    last_generation = generation;
+                    let settings = crate::settings::get_settings();
+                    if let Err(e) = app.emit("settings-changed", &settings) {
+                        log::warn!("发送设置变化事件失败: {e}");
+                    }
+                }
+            }
+        });
+    }
+}
@@ -8,6 +8,7 @@ use std::path::{Path, PathBuf};
 use tokio::time::timeout;
 
 use crate::error::format_skill_error;
+use crate::network::NetworkFeature;
 
 /// 技能对象
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,10 +37,68 @@ pub struct Skill {
     pub repo_branch: Option<String>,
 }
 
+/// 技能预览（浏览仓库时返回，附带 SKILL.md 正文摘要）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillPreview {
+    #[serde(flatten)]
+    pub skill: Skill,
+    /// SKILL.md 正文（去除 front matter 后截断）
+    pub readme: Option<String>,
+}
+
+/// 预览正文的最大字符数
+const PREVIEW_MAX_CHARS: usize = 2000;
+
+/// 仓库托管平台
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillRepoProvider {
+    #[default]
+    Github,
+    Gitlab,
+    Gitea,
+}
+
+impl SkillRepoProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SkillRepoProvider::Github => "github",
+            SkillRepoProvider::Gitlab => "gitlab",
+            SkillRepoProvider::Gitea => "gitea",
+        }
+    }
+
+    /// 未配置自定义地址时使用的公共实例
+    fn default_web_base(&self) -> &'static str {
+        match self {
+            SkillRepoProvider::Github => "https://github.com",
+            SkillRepoProvider::Gitlab => "https://gitlab.com",
+            SkillRepoProvider::Gitea => "https://gitea.com",
+        }
+    }
+}
+
+impl std::str::FromStr for SkillRepoProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "github" => Ok(SkillRepoProvider::Github),
+            "gitlab" => Ok(SkillRepoProvider::Gitlab),
+            "gitea" | "forgejo" => Ok(SkillRepoProvider::Gitea),
+            other => Err(anyhow!(format_skill_error(
+                "UNSUPPORTED_REPO_PROVIDER",
+                &[("provider", other)],
+                None,
+            ))),
+        }
+    }
+}
+
 /// 仓库配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkillRepo {
-    /// GitHub 用户/组织名
+    /// 用户/组织名（GitLab 可包含子组，如 "group/subgroup"）
     pub owner: String,
     /// 仓库名称
     pub name: String,
@@ -47,6 +106,149 @@ pub struct SkillRepo {
     pub branch: String,
     /// 是否启用
     pub enabled: bool,
+    /// 托管平台（默认 GitHub）
+    #[serde(default)]
+    pub provider: SkillRepoProvider,
+    /// 自托管实例地址（如 GitHub Enterprise / 自建 GitLab、Gitea），为空时使用平台公共实例
+    #[serde(rename = "baseUrl", default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// 私有仓库访问令牌：仅从前端接收，存储于 secrets 表，不会序列化返回
+    #[serde(default, skip_serializing)]
+    pub token: Option<String>,
+    /// 是否已配置访问令牌（只读，供前端展示）
+    #[serde(rename = "hasToken", default)]
+    pub has_token: bool,
+}
+
+impl SkillRepo {
+    /// 公共 GitHub 仓库的快捷构造
+    pub fn new(owner: impl Into<String>, name: impl Into<String>, branch: impl Into<String>) -> Self {
+        Self {
+            owner: owner.into(),
+            name: name.into(),
+            branch: branch.into(),
+            enabled: true,
+            provider: SkillRepoProvider::Github,
+            base_url: None,
+            token: None,
+            has_token: false,
+        }
+    }
+
+    /// secrets 表中访问令牌的键名
+    pub fn token_secret_key(owner: &str, name: &str) -> String {
+        format!("skill_repo_token:{owner}/{name}")
+    }
+
+    fn token(&self) -> Option<&str> {
+        self.token.as_deref().filter(|t| !t.is_empty())
+    }
+
+    /// 网页地址根（用于 README 链接与匿名归档下载）
+    pub fn web_base(&self) -> String {
+        match self.base_url.as_deref().map(str::trim) {
+            Some(base) if !base.is_empty() => base.trim_end_matches('/').to_string(),
+            _ => self.provider.default_web_base().to_string(),
+        }
+    }
+
+    /// REST API 根
+    ///
+    /// - GitHub: api.github.com，GitHub Enterprise 使用 {base}/api/v3
+    /// - GitLab: {base}/api/v4
+    /// - Gitea: {base}/api/v1
+    pub fn api_base(&self) -> String {
+        let web = self.web_base();
+        match self.provider {
+            SkillRepoProvider::Github => {
+                if web == "https://github.com" {
+                    "https://api.github.com".to_string()
+                } else if web.contains("api.github.com") || web.ends_with("/api/v3") {
+                    web
+                } else {
+                    format!("{web}/api/v3")
+                }
+            }
+            SkillRepoProvider::Gitlab => format!("{web}/api/v4"),
+            SkillRepoProvider::Gitea => format!("{web}/api/v1"),
+        }
+    }
+
+    /// 指定分支的归档下载地址
+    ///
+    /// GitHub 配置了令牌时走 API 的 zipball 接口（支持私有仓库鉴权），否则使用网页归档地址；
+    /// GitLab / Gitea 统一使用 API 归档接口。
+    pub fn archive_url(&self, branch: &str) -> String {
+        match self.provider {
+            SkillRepoProvider::Github if self.token().is_some() => format!(
+                "{}/repos/{}/{}/zipball/{}",
+                self.api_base(),
+                self.owner,
+                self.name,
+                branch
+            ),
+            SkillRepoProvider::Github => format!(
+                "{}/{}/{}/archive/refs/heads/{}.zip",
+                self.web_base(),
+                self.owner,
+                self.name,
+                branch
+            ),
+            SkillRepoProvider::Gitlab => {
+                let project: String = url::form_urlencoded::byte_serialize(
+                    format!("{}/{}", self.owner, self.name).as_bytes(),
+                )
+                .collect();
+                let sha: String = url::form_urlencoded::byte_serialize(branch.as_bytes()).collect();
+                format!(
+                    "{}/projects/{}/repository/archive.zip?sha={}",
+                    self.api_base(),
+                    project,
+                    sha
+                )
+            }
+            SkillRepoProvider::Gitea => format!(
+                "{}/repos/{}/{}/archive/{}.zip",
+                self.api_base(),
+                self.owner,
+                self.name,
+                branch
+            ),
+        }
+    }
+
+    /// 仓库内目录的网页地址
+    pub fn tree_url(&self, branch: &str, path: &str) -> String {
+        let web = self.web_base();
+        match self.provider {
+            SkillRepoProvider::Github => {
+                format!("{web}/{}/{}/tree/{branch}/{path}", self.owner, self.name)
+            }
+            SkillRepoProvider::Gitlab => {
+                format!("{web}/{}/{}/-/tree/{branch}/{path}", self.owner, self.name)
+            }
+            SkillRepoProvider::Gitea => format!(
+                "{web}/{}/{}/src/branch/{branch}/{path}",
+                self.owner, self.name
+            ),
+        }
+    }
+
+    /// 按平台约定为下载请求附加访问令牌
+    fn apply_auth(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let Some(token) = self.token() else {
+            return request;
+        };
+        match self.provider {
+            SkillRepoProvider::Github => request
+                .bearer_auth(token)
+                .header(reqwest::header::ACCEPT, "application/vnd.github+json"),
+            SkillRepoProvider::Gitlab => request.header("PRIVATE-TOKEN", token),
+            SkillRepoProvider::Gitea => {
+                request.header(reqwest::header::AUTHORIZATION, format!("token {token}"))
+            }
+        }
+    }
 }
 
 /// 技能安装状态
@@ -73,24 +275,9 @@ impl Default for SkillStore {
         SkillStore {
             skills: HashMap::new(),
             repos: vec![
-                SkillRepo {
-                    owner: "ComposioHQ".to_string(),
-                    name: "awesome-claude-skills".to_string(),
-                    branch: "main".to_string(),
-                    enabled: true,
-                },
-                SkillRepo {
-                    owner: "anthropics".to_string(),
-                    name: "skills".to_string(),
-                    branch: "main".to_string(),
-                    enabled: true,
-                },
-                SkillRepo {
-                    owner: "cexll".to_string(),
-                    name: "myclaude".to_string(),
-                    branch: "master".to_string(),
-                    enabled: true,
-                },
+                SkillRepo::new("ComposioHQ", "awesome-claude-skills", "main"),
+                SkillRepo::new("anthropics", "skills", "main"),
+                SkillRepo::new("cexll", "myclaude", "master"),
             ],
         }
     }
@@ -104,8 +291,9 @@ pub struct SkillMetadata {
 }
 
 pub struct SkillService {
-    http_client: Client,
     install_dir: PathBuf,
+    /// 用户自建技能的存放目录（~/.cc-switch/skills）
+    local_dir: PathBuf,
 }
 
 impl SkillService {
@@ -116,15 +304,23 @@ impl SkillService {
         fs::create_dir_all(&install_dir)?;
 
         Ok(Self {
-            http_client: Client::builder()
-                .user_agent("cc-switch")
-                // 将单次请求超时时间控制在 10 秒以内，避免无效链接导致长时间卡住
-                .timeout(std::time::Duration::from_secs(10))
-                .build()?,
             install_dir,
+            local_dir: Self::get_local_skills_dir(),
         })
     }
 
+    /// 按网络策略构造 HTTP 客户端（默认单次请求 10 秒超时，避免无效链接导致长时间卡住）
+    fn http_client() -> Result<Client> {
+        Ok(crate::network::client_builder(NetworkFeature::Skills)
+            .user_agent("cc-switch")
+            .build()?)
+    }
+
+    /// 用户自建技能目录，与安装目录分离，避免卸载时丢失源文件
+    pub fn get_local_skills_dir() -> PathBuf {
+        crate::config::get_app_config_dir().join("skills")
+    }
+
     fn get_install_dir() -> Result<PathBuf> {
         let home = crate::test_utils::home_dir().context(format_skill_error(
             "GET_HOME_DIR_FAILED",
@@ -142,7 +338,13 @@ impl SkillService {
         let mut skills = Vec::new();
 
         // 仅使用启用的仓库，并行获取技能列表，避免单个无效仓库拖慢整体刷新
-        let enabled_repos: Vec<SkillRepo> = repos.into_iter().filter(|repo| repo.enabled).collect();
+        // 离线时只列出本地技能，不等待远程仓库超时
+        let enabled_repos: Vec<SkillRepo> = if crate::network::is_online() {
+            repos.into_iter().filter(|repo| repo.enabled).collect()
+        } else {
+            log::info!("当前离线，跳过远程技能仓库");
+            Vec::new()
+        };
 
         let fetch_tasks = enabled_repos
             .iter()
@@ -167,6 +369,105 @@ impl SkillService {
         Ok(skills)
     }
 
+    /// 浏览单个仓库中的候选技能（不要求仓库已添加），附带 SKILL.md 正文预览
+    pub async fn browse_repo(&self, repo: &SkillRepo) -> Result<Vec<SkillPreview>> {
+        let temp_dir = timeout(std::time::Duration::from_secs(60), self.download_repo(repo))
+            .await
+            .map_err(|_| {
+                anyhow!(format_skill_error(
+                    "DOWNLOAD_TIMEOUT",
+                    &[
+                        ("owner", &repo.owner),
+                        ("name", &repo.name),
+                        ("timeout", "60")
+                    ],
+                    Some("checkNetwork"),
+                ))
+            })??;
+
+        let mut skills = Vec::new();
+        let scan_result = self.scan_dir_recursive(&temp_dir, &temp_dir, repo, &mut skills);
+
+        let previews = scan_result.map(|_| {
+            skills
+                .into_iter()
+                .map(|mut skill| {
+                    // 根目录技能的 directory 为仓库名，对应的 SKILL.md 位于解压根目录
+                    let nested = temp_dir.join(&skill.directory).join("SKILL.md");
+                    let skill_md = if nested.exists() {
+                        nested
+                    } else {
+                        temp_dir.join("SKILL.md")
+                    };
+                    let readme = Self::read_skill_body(&skill_md);
+                    skill.installed = self.install_dir.join(&skill.directory).exists();
+                    SkillPreview { skill, readme }
+                })
+                .collect::<Vec<_>>()
+        });
+
+        // 清理临时目录
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let mut previews = previews?;
+        previews.sort_by(|a, b| {
+            a.skill
+                .name
+                .to_lowercase()
+                .cmp(&b.skill.name.to_lowercase())
+        });
+        Ok(previews)
+    }
+
+    /// 在已配置的仓库（及本地已安装技能）中按关键字搜索
+    pub async fn search_skills(&self, repos: Vec<SkillRepo>, keyword: &str) -> Result<Vec<Skill>> {
+        let skills = self.list_skills(repos).await?;
+        Ok(skills
+            .into_iter()
+            .filter(|skill| Self::skill_matches(skill, keyword))
+            .collect())
+    }
+
+    /// 关键字匹配：名称、描述、目录与仓库名，忽略大小写；多个关键字需全部命中
+    fn skill_matches(skill: &Skill, keyword: &str) -> bool {
+        let haystack = format!(
+            "{} {} {} {}/{}",
+            skill.name,
+            skill.description,
+            skill.directory,
+            skill.repo_owner.as_deref().unwrap_or_default(),
+            skill.repo_name.as_deref().unwrap_or_default()
+        )
+        .to_lowercase();
+
+        keyword
+            .split_whitespace()
+            .all(|term| haystack.contains(&term.to_lowercase()))
+    }
+
+    /// 读取 SKILL.md 正文（去除 front matter），用于预览
+    fn read_skill_body(path: &Path) -> Option<String> {
+        let content = fs::read_to_string(path).ok()?;
+        let content = content.trim_start_matches('\u{feff}');
+
+        let body = if content.trim_start().starts_with("---") {
+            let parts: Vec<&str> = content.splitn(3, "---").collect();
+            if parts.len() < 3 {
+                content
+            } else {
+                parts[2]
+            }
+        } else {
+            content
+        };
+
+        let body = body.trim();
+        if body.is_empty() {
+            return None;
+        }
+        Some(body.chars().take(PREVIEW_MAX_CHARS).collect())
+    }
+
     /// 从仓库获取技能列表
     async fn fetch_repo_skills(&self, repo: &SkillRepo) -> Result<Vec<Skill>> {
         // 为单个仓库加载增加整体超时，避免无效链接长时间阻塞
@@ -265,10 +566,7 @@ impl SkillService {
             name: meta.name.unwrap_or_else(|| directory.to_string()),
             description: meta.description.unwrap_or_default(),
             directory: directory.to_string(),
-            readme_url: Some(format!(
-                "https://github.com/{}/{}/tree/{}/{}",
-                repo.owner, repo.name, repo.branch, readme_path
-            )),
+            readme_url: Some(repo.tree_url(&repo.branch, &readme_path)),
             installed: false,
             repo_owner: Some(repo.owner.clone()),
             repo_name: Some(repo.name.clone()),
@@ -413,6 +711,7 @@ impl SkillService {
 
     /// 下载仓库
     async fn download_repo(&self, repo: &SkillRepo) -> Result<PathBuf> {
+        crate::network::ensure_online()?;
         let temp_dir = tempfile::tempdir()?;
         let temp_path = temp_dir.path().to_path_buf();
         let _ = temp_dir.keep(); // 保持临时目录，稍后手动清理
@@ -426,12 +725,9 @@ impl SkillService {
 
         let mut last_error = None;
         for branch in branches {
-            let url = format!(
-                "https://github.com/{}/{}/archive/refs/heads/{}.zip",
-                repo.owner, repo.name, branch
-            );
+            let url = repo.archive_url(branch);
 
-            match self.download_and_extract(&url, &temp_path).await {
+            match self.download_and_extract(&url, repo, &temp_path).await {
                 Ok(_) => {
                     return Ok(temp_path);
                 }
@@ -446,15 +742,17 @@ impl SkillService {
     }
 
     /// 下载并解压 ZIP
-    async fn download_and_extract(&self, url: &str, dest: &Path) -> Result<()> {
-        // 下载 ZIP
-        let response = self.http_client.get(url).send().await?;
+    async fn download_and_extract(&self, url: &str, repo: &SkillRepo, dest: &Path) -> Result<()> {
+        // 下载 ZIP（私有仓库携带访问令牌）
+        let request = repo.apply_auth(Self::http_client()?.get(url));
+        let response = crate::network::send_with_retry(NetworkFeature::Skills, request).await?;
         if !response.status().is_success() {
             let status = response.status().as_u16().to_string();
             return Err(anyhow::anyhow!(format_skill_error(
                 "DOWNLOAD_FAILED",
                 &[("status", &status)],
                 match status.as_str() {
+                    "401" => Some("http401"),
                     "403" => Some("http403"),
                     "404" => Some("http404"),
                     "429" => Some("http429"),
@@ -468,8 +766,18 @@ impl SkillService {
         // 解压
         let cursor = std::io::Cursor::new(bytes);
         let mut archive = zip::ZipArchive::new(cursor)?;
+        Self::extract_archive(&mut archive, dest)
+    }
 
-        // 获取根目录名称 (GitHub 的 zip 会有一个根目录)
+    /// 解压归档到 `dest`，去掉归档的根目录
+    ///
+    /// 归档可能来自任意自建 GitLab / Gitea 主机，条目路径必须全部为普通路径段，
+    /// 含 `..`、绝对路径等的条目会使整个归档被拒绝，防止写出技能目录。
+    fn extract_archive<R: std::io::Read + std::io::Seek>(
+        archive: &mut zip::ZipArchive<R>,
+        dest: &Path,
+    ) -> Result<()> {
+        // 获取根目录名称 (GitHub / GitLab / Gitea 的归档都会有一个根目录)
         let root_name = if !archive.is_empty() {
             let first_file = archive.by_index(0)?;
             let name = first_file.name();
@@ -498,6 +806,13 @@ impl SkillService {
             if relative_path.is_empty() {
                 continue;
             }
+            if !Self::is_safe_directory(relative_path.trim_end_matches('/')) {
+                return Err(anyhow::anyhow!(format_skill_error(
+                    "UNSAFE_ARCHIVE_PATH",
+                    &[("path", file_path)],
+                    Some("checkRepoUrl"),
+                )));
+            }
 
             let outpath = dest.join(relative_path);
 
@@ -598,6 +913,243 @@ impl SkillService {
         Ok(())
     }
 
+    /// 创建一个自建技能，写入 SKILL.md
+    ///
+    /// 若内容未包含 front matter，会自动补充 name/description 字段。
+    /// 同名技能已存在时，`overwrite` 为 false 则返回 `SKILL_ALREADY_EXISTS`（如深链接导入），
+    /// 为 true 则覆盖（编辑器保存）。
+    pub fn create_local_skill(&self, name: &str, content: &str, overwrite: bool) -> Result<Skill> {
+        let name = name.trim();
+        let directory = Self::sanitize_skill_dir(name).ok_or_else(|| {
+            anyhow!(format_skill_error(
+                "INVALID_SKILL_NAME",
+                &[("name", name)],
+                None,
+            ))
+        })?;
+
+        let skill_dir = self.local_dir.join(&directory);
+        let skill_md = skill_dir.join("SKILL.md");
+        if !overwrite && skill_md.exists() {
+            return Err(anyhow!(format_skill_error(
+                "SKILL_ALREADY_EXISTS",
+                &[("directory", &directory)],
+                None,
+            )));
+        }
+
+        fs::create_dir_all(&skill_dir).context(format_skill_error(
+            "CREATE_DIR_FAILED",
+            &[("path", &skill_dir.display().to_string())],
+            Some("checkPermission"),
+        ))?;
+        fs::write(&skill_md, Self::ensure_front_matter(name, content))?;
+
+        self.build_authored_skill(&skill_md, &directory)
+    }
+
+    /// 列出所有自建技能
+    pub fn list_authored_skills(&self) -> Result<Vec<Skill>> {
+        let mut skills = Vec::new();
+        if !self.local_dir.exists() {
+            return Ok(skills);
+        }
+
+        for entry in fs::read_dir(&self.local_dir)? {
+            let path = entry?.path();
+            let skill_md = path.join("SKILL.md");
+            if !skill_md.exists() {
+                continue;
+            }
+            let directory = path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            if let Ok(skill) = self.build_authored_skill(&skill_md, &directory) {
+                skills.push(skill);
+            }
+        }
+
+        skills.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        Ok(skills)
+    }
+
+    /// 将自建技能安装到 ~/.claude/skills（已安装时覆盖为最新内容）
+    pub fn install_authored_skill(&self, directory: &str) -> Result<()> {
+        let source = self.authored_skill_dir(directory)?;
+        let dest = self.install_dir.join(directory);
+
+        if dest.exists() {
+            fs::remove_dir_all(&dest)?;
+        }
+        Self::copy_dir_recursive(&source, &dest)
+    }
+
+    /// 删除自建技能源文件（不影响已安装副本）
+    pub fn delete_authored_skill(&self, directory: &str) -> Result<()> {
+        let source = self.authored_skill_dir(directory)?;
+        fs::remove_dir_all(&source)?;
+        Ok(())
+    }
+
+    /// 将技能打包为 zip（优先自建目录，其次已安装目录）
+    ///
+    /// zip 内包含一层以目录名命名的根目录，与 GitHub 归档结构一致。
+    pub fn export_skill_as_zip(&self, directory: &str, dest: &Path) -> Result<PathBuf> {
+        let source = self.resolve_skill_source(directory)?;
+
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = fs::File::create(dest).context(format_skill_error(
+            "WRITE_FILE_FAILED",
+            &[("path", &dest.display().to_string())],
+            Some("checkPermission"),
+        ))?;
+
+        let mut writer = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        let root = Self::last_segment(directory);
+        Self::zip_dir_recursive(&mut writer, &source, &root, options)?;
+        writer.finish()?;
+
+        Ok(dest.to_path_buf())
+    }
+
+    /// 生成技能分享链接（ccswitch://，SKILL.md 以 Base64 内嵌）
+    pub fn build_share_link(&self, directory: &str) -> Result<String> {
+        use base64::prelude::*;
+
+        let source = self.resolve_skill_source(directory)?;
+        let skill_md = source.join("SKILL.md");
+        let content = fs::read_to_string(&skill_md)?;
+        let meta = self.parse_skill_metadata(&skill_md)?;
+        let name = meta
+            .name
+            .unwrap_or_else(|| Self::last_segment(directory));
+
+        let mut url = url::Url::parse("ccswitch://v1/import")?;
+        url.query_pairs_mut()
+            .append_pair("resource", "skill")
+            .append_pair("name", &name)
+            .append_pair("content", &BASE64_STANDARD.encode(content.as_bytes()));
+        Ok(url.to_string())
+    }
+
+    fn authored_skill_dir(&self, directory: &str) -> Result<PathBuf> {
+        let path = self.local_dir.join(directory);
+        if Self::is_safe_directory(directory) && path.join("SKILL.md").exists() {
+            Ok(path)
+        } else {
+            Err(anyhow!(format_skill_error(
+                "SKILL_NOT_FOUND",
+                &[("directory", directory)],
+                None,
+            )))
+        }
+    }
+
+    fn resolve_skill_source(&self, directory: &str) -> Result<PathBuf> {
+        if let Ok(path) = self.authored_skill_dir(directory) {
+            return Ok(path);
+        }
+        let installed = self.install_dir.join(directory);
+        if Self::is_safe_directory(directory) && installed.join("SKILL.md").exists() {
+            return Ok(installed);
+        }
+        Err(anyhow!(format_skill_error(
+            "SKILL_NOT_FOUND",
+            &[("directory", directory)],
+            None,
+        )))
+    }
+
+    fn build_authored_skill(&self, skill_md: &Path, directory: &str) -> Result<Skill> {
+        let meta = self.parse_skill_metadata(skill_md)?;
+        Ok(Skill {
+            key: format!("authored:{directory}"),
+            name: meta.name.unwrap_or_else(|| directory.to_string()),
+            description: meta.description.unwrap_or_default(),
+            directory: directory.to_string(),
+            readme_url: None,
+            installed: self.install_dir.join(directory).exists(),
+            repo_owner: None,
+            repo_name: None,
+            repo_branch: None,
+        })
+    }
+
+    fn zip_dir_recursive<W: std::io::Write + std::io::Seek>(
+        writer: &mut zip::ZipWriter<W>,
+        dir: &Path,
+        prefix: &str,
+        options: zip::write::SimpleFileOptions,
+    ) -> Result<()> {
+        writer.add_directory(format!("{prefix}/"), options)?;
+
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let name = format!("{prefix}/{}", entry.file_name().to_string_lossy());
+
+            if path.is_dir() {
+                Self::zip_dir_recursive(writer, &path, &name, options)?;
+            } else {
+                writer.start_file(name, options)?;
+                let mut file = fs::File::open(&path)?;
+                std::io::copy(&mut file, writer)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 由技能名称生成目录名：小写，空白转为 '-'，仅保留字母数字与 '-'/'_'
+    fn sanitize_skill_dir(name: &str) -> Option<String> {
+        let mut out = String::new();
+        for c in name.trim().chars() {
+            if c.is_alphanumeric() || c == '_' {
+                out.extend(c.to_lowercase());
+            } else if (c.is_whitespace() || c == '-') && !out.is_empty() && !out.ends_with('-') {
+                out.push('-');
+            }
+        }
+        let out = out.trim_end_matches('-').to_string();
+        if out.is_empty() {
+            None
+        } else {
+            Some(out)
+        }
+    }
+
+    /// 目录名不得包含路径穿越
+    fn is_safe_directory(directory: &str) -> bool {
+        !directory.is_empty()
+            && Path::new(directory)
+                .components()
+                .all(|c| matches!(c, std::path::Component::Normal(_)))
+    }
+
+    fn last_segment(directory: &str) -> String {
+        Path::new(directory)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| directory.to_string())
+    }
+
+    /// 内容缺少 front matter 时自动补充
+    fn ensure_front_matter(name: &str, content: &str) -> String {
+        let trimmed = content.trim_start_matches('\u{feff}');
+        if trimmed.trim_start().starts_with("---") {
+            return trimmed.to_string();
+        }
+        // JSON 字符串同时也是合法的 YAML 标量，可安全转义特殊字符
+        let quoted = serde_json::to_string(name).unwrap_or_else(|_| format!("\"{name}\""));
+        format!("---\nname: {quoted}\ndescription: \"\"\n---\n\n{trimmed}")
+    }
+
     /// 列出仓库
     pub fn list_repos(&self, store: &SkillStore) -> Vec<SkillRepo> {
         store.repos.clone()
@@ -628,3 +1180,193 @@ impl SkillService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_skill() -> Skill {
+        Skill {
+            key: "anthropics/skills:pdf".to_string(),
+            name: "PDF Toolkit".to_string(),
+            description: "Extract text and tables from PDF files".to_string(),
+            directory: "pdf".to_string(),
+            readme_url: None,
+            installed: false,
+            repo_owner: Some("anthropics".to_string()),
+            repo_name: Some("skills".to_string()),
+            repo_branch: Some("main".to_string()),
+        }
+    }
+
+    #[test]
+    fn skill_matches_all_terms_case_insensitive() {
+        let skill = sample_skill();
+        assert!(SkillService::skill_matches(&skill, "pdf"));
+        assert!(SkillService::skill_matches(&skill, "TABLES anthropics"));
+        assert!(SkillService::skill_matches(&skill, ""));
+        assert!(!SkillService::skill_matches(&skill, "pdf excel"));
+    }
+
+    #[test]
+    fn archive_url_switches_to_api_with_token() {
+        let mut repo = SkillRepo::new("acme", "skills", "main");
+        assert_eq!(
+            repo.archive_url("main"),
+            "https://github.com/acme/skills/archive/refs/heads/main.zip"
+        );
+
+        repo.base_url = Some("https://git.acme.dev/".to_string());
+        assert_eq!(
+            repo.archive_url("dev"),
+            "https://git.acme.dev/acme/skills/archive/refs/heads/dev.zip"
+        );
+
+        repo.token = Some("ghp_test".to_string());
+        assert_eq!(
+            repo.archive_url("dev"),
+            "https://git.acme.dev/api/v3/repos/acme/skills/zipball/dev"
+        );
+
+        repo.base_url = None;
+        assert_eq!(
+            repo.archive_url("main"),
+            "https://api.github.com/repos/acme/skills/zipball/main"
+        );
+    }
+
+    #[test]
+    fn archive_and_tree_urls_follow_provider() {
+        let mut gitlab = SkillRepo::new("group/sub", "skills", "main");
+        gitlab.provider = SkillRepoProvider::Gitlab;
+        assert_eq!(
+            gitlab.archive_url("main"),
+            "https://gitlab.com/api/v4/projects/group%2Fsub%2Fskills/repository/archive.zip?sha=main"
+        );
+        assert_eq!(
+            gitlab.tree_url("main", "pdf"),
+            "https://gitlab.com/group/sub/skills/-/tree/main/pdf"
+        );
+
+        let mut gitea = SkillRepo::new("team", "skills", "dev");
+        gitea.provider = SkillRepoProvider::Gitea;
+        gitea.base_url = Some("https://code.example.com/".to_string());
+        assert_eq!(
+            gitea.archive_url("dev"),
+            "https://code.example.com/api/v1/repos/team/skills/archive/dev.zip"
+        );
+        assert_eq!(
+            gitea.tree_url("dev", "pdf"),
+            "https://code.example.com/team/skills/src/branch/dev/pdf"
+        );
+    }
+
+    #[test]
+    fn provider_parses_from_str() {
+        use std::str::FromStr;
+        assert_eq!(
+            SkillRepoProvider::from_str("GitLab").unwrap(),
+            SkillRepoProvider::Gitlab
+        );
+        assert_eq!(
+            SkillRepoProvider::from_str("forgejo").unwrap(),
+            SkillRepoProvider::Gitea
+        );
+        assert!(SkillRepoProvider::from_str("bitbucket").is_err());
+    }
+
+    #[test]
+    fn create_local_skill_refuses_to_overwrite_unless_asked() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let service = SkillService {
+            install_dir: dir.path().join("installed"),
+            local_dir: dir.path().join("local"),
+        };
+        let skill_md = dir.path().join("local").join("my-skill").join("SKILL.md");
+
+        service
+            .create_local_skill("My Skill", "original", false)
+            .expect("create");
+        let err = service
+            .create_local_skill("My Skill", "shared", false)
+            .unwrap_err();
+        assert!(err.to_string().contains("SKILL_ALREADY_EXISTS"));
+        assert!(fs::read_to_string(&skill_md).unwrap().contains("original"));
+
+        service
+            .create_local_skill("My Skill", "edited", true)
+            .expect("overwrite");
+        assert!(fs::read_to_string(&skill_md).unwrap().contains("edited"));
+    }
+
+    #[test]
+    fn sanitize_skill_dir_builds_slug() {
+        assert_eq!(
+            SkillService::sanitize_skill_dir("  My Cool Skill! ").as_deref(),
+            Some("my-cool-skill")
+        );
+        assert_eq!(
+            SkillService::sanitize_skill_dir("../etc/passwd").as_deref(),
+            Some("etcpasswd")
+        );
+        assert!(SkillService::sanitize_skill_dir("!!!").is_none());
+    }
+
+    #[test]
+    fn ensure_front_matter_adds_header_once() {
+        let generated = SkillService::ensure_front_matter("Demo: v1", "# Body");
+        assert!(generated.starts_with("---\nname: \"Demo: v1\"\n"));
+        assert!(generated.ends_with("# Body"));
+
+        let existing = "---\nname: keep\n---\nbody";
+        assert_eq!(SkillService::ensure_front_matter("x", existing), existing);
+    }
+
+    #[test]
+    fn read_skill_body_strips_front_matter() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("SKILL.md");
+        fs::write(
+            &path,
+            "---\nname: demo\ndescription: test\n---\n\n# Demo\nUsage notes\n",
+        )
+        .expect("write");
+        assert_eq!(
+            SkillService::read_skill_body(&path).as_deref(),
+            Some("# Demo\nUsage notes")
+        );
+
+        fs::write(&path, "---\nname: empty\n---\n").expect("write");
+        assert!(SkillService::read_skill_body(&path).is_none());
+    }
+
+    fn zip_with(entries: &[&str]) -> zip::ZipArchive<std::io::Cursor<Vec<u8>>> {
+        use std::io::Write;
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        for name in entries {
+            writer.start_file(*name, options).expect("start entry");
+            writer.write_all(b"content").expect("write entry");
+        }
+        let cursor = writer.finish().expect("finish zip");
+        zip::ZipArchive::new(cursor).expect("read zip")
+    }
+
+    #[test]
+    fn extract_archive_strips_root_and_rejects_traversal() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let dest = dir.path().join("skills-main");
+
+        let mut archive = zip_with(&["skills-main/pdf/SKILL.md", "skills-main/README.md"]);
+        SkillService::extract_archive(&mut archive, &dest).expect("extract");
+        assert!(dest.join("pdf").join("SKILL.md").exists());
+        assert!(dest.join("README.md").exists());
+
+        let mut archive = zip_with(&["skills-main/SKILL.md", "skills-main/../../escaped.md"]);
+        let err = SkillService::extract_archive(&mut archive, &dest).expect_err("unsafe entry");
+        assert!(err.to_string().contains("UNSAFE_ARCHIVE_PATH"));
+        assert!(!dir.path().join("escaped.md").exists());
+        assert!(!dir.path().parent().unwrap().join("escaped.md").exists());
+    }
+}
@@ -0,0 +1,323 @@
+//! 供应商冒烟测试
+//!
+//! 通过供应商配置的端点与模型发送一次极小的真实补全请求，返回延迟、HTTP 状态
+//! 与回复开头，用于在切换前确认中转确实可用（会消耗少量 token）。
+
+use reqwest::RequestBuilder;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::time::Instant;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::network::NetworkFeature;
+use crate::services::models::ModelService;
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+const MAX_OUTPUT_TOKENS: u32 = 32;
+/// 返回的回复开头最大字符数
+const REPLY_PREVIEW_CHARS: usize = 100;
+const PROMPT: &str = "Reply with the single word: pong";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// 请求格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WireApi {
+    /// Anthropic Messages（`/v1/messages`）
+    Messages,
+    /// OpenAI Chat Completions（`/chat/completions`）
+    Chat,
+    /// OpenAI Responses（`/responses`）
+    Responses,
+    /// Gemini `generateContent`
+    Gemini,
+}
+
+/// 一次冒烟测试所需的信息（从数据库读取，不含网络操作）
+#[derive(Debug, Clone)]
+pub struct SmokeTestPlan {
+    wire_api: WireApi,
+    api_key: String,
+    base_url: String,
+    model: String,
+}
+
+/// 冒烟测试结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmokeTestResult {
+    /// 收到 2xx 响应
+    pub success: bool,
+    pub model: String,
+    /// 实际请求的地址
+    pub endpoint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    /// 从发出请求到读完响应的耗时
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// 回复的开头
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply: Option<String>,
+    /// 错误信息（失败时的响应正文开头或网络错误）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+pub struct SmokeTestService;
+
+impl SmokeTestService {
+    /// 读取供应商凭据、端点与模型（`model` 为空时使用配置中的模型）
+    pub fn prepare(
+        state: &AppState,
+        app_type: &AppType,
+        provider_id: &str,
+        model: Option<String>,
+    ) -> Result<SmokeTestPlan, AppError> {
+        let providers = state.db.get_all_providers(app_type.as_str())?;
+        let provider = providers
+            .get(provider_id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {provider_id} 不存在")))?;
+        let (api_key, base_url) = ProviderService::credentials(state, app_type, provider_id)?;
+        if api_key.trim().is_empty() {
+            return Err(AppError::localized("key_check.api_key_missing"));
+        }
+
+        let settings = &provider.settings_config;
+        let model = model
+            .map(|model| model.trim().to_string())
+            .filter(|model| !model.is_empty())
+            .or_else(|| ModelService::configured_model(app_type, settings))
+            .unwrap_or_else(|| default_model(app_type).to_string());
+        let wire_api = match app_type {
+            AppType::Claude => WireApi::Messages,
+            AppType::Gemini => WireApi::Gemini,
+            AppType::Codex => codex_wire_api(settings),
+            AppType::Grok | AppType::Qwen => WireApi::Chat,
+        };
+        let base_url = match (app_type, base_url.trim().trim_end_matches('/')) {
+            (AppType::Grok, "") => "https://api.x.ai/v1".to_string(),
+            (_, "") => return Err(AppError::localized("models.base_url_missing")),
+            (_, base) => base.to_string(),
+        };
+
+        Ok(SmokeTestPlan {
+            wire_api,
+            api_key,
+            base_url,
+            model,
+        })
+    }
+
+    /// 发送测试请求；网络错误与非 2xx 响应也作为结果返回
+    pub async fn run(plan: SmokeTestPlan) -> Result<SmokeTestResult, AppError> {
+        let client = ModelService::build_client()?;
+        let endpoint = endpoint_url(&plan);
+        // 补全请求可能较慢，使用单独放宽的超时（不重试，避免重复计费）
+        let request = build_request(client.post(&endpoint), &plan)
+            .timeout(crate::network::policy(NetworkFeature::SmokeTest).timeout);
+
+        let mut result = SmokeTestResult {
+            success: false,
+            model: plan.model.clone(),
+            endpoint,
+            http_status: None,
+            latency_ms: None,
+            reply: None,
+            message: None,
+        };
+
+        let start = Instant::now();
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(err) => {
+                result.message = Some(network_error_message(&err));
+                return Ok(result);
+            }
+        };
+        let status = response.status();
+        let body = response.text().await;
+        result.latency_ms = Some(start.elapsed().as_millis() as u64);
+        result.http_status = Some(status.as_u16());
+        result.success = status.is_success();
+
+        match body {
+            Ok(body) if status.is_success() => {
+                result.reply = serde_json::from_str::<Value>(&body)
+                    .ok()
+                    .and_then(|value| extract_reply(plan.wire_api, &value))
+                    .map(|reply| preview(&reply));
+            }
+            Ok(body) => {
+                result.message = Some(preview(&crate::scrub::scrub_secrets(&body)))
+                    .filter(|message| !message.is_empty());
+            }
+            Err(err) => result.message = Some(network_error_message(&err)),
+        }
+        Ok(result)
+    }
+}
+
+fn default_model(app_type: &AppType) -> &'static str {
+    match app_type {
+        AppType::Claude => "claude-haiku-4-5",
+        AppType::Codex => "gpt-5-codex-mini",
+        AppType::Gemini => "gemini-2.5-flash",
+        AppType::Grok => "grok-code-fast-1",
+        AppType::Qwen => "qwen3-coder-flash",
+    }
+}
+
+/// Codex 当前 model_provider 的 `wire_api`（默认 responses）
+fn codex_wire_api(settings: &Value) -> WireApi {
+    let config = settings
+        .get("config")
+        .and_then(Value::as_str)
+        .and_then(|text| toml::from_str::<toml::Table>(text).ok());
+    let wire_api = config.as_ref().and_then(|config| {
+        let provider = config.get("model_provider")?.as_str()?;
+        config
+            .get("model_providers")?
+            .get(provider)?
+            .get("wire_api")?
+            .as_str()
+    });
+    match wire_api {
+        Some("chat") => WireApi::Chat,
+        _ => WireApi::Responses,
+    }
+}
+
+/// Claude / Gemini 的地址不含版本段（由 CLI 追加），OpenAI 兼容地址已含 `/v1`
+fn endpoint_url(plan: &SmokeTestPlan) -> String {
+    let base = &plan.base_url;
+    match plan.wire_api {
+        WireApi::Messages => format!("{base}/v1/messages"),
+        WireApi::Chat => format!("{base}/chat/completions"),
+        WireApi::Responses => format!("{base}/responses"),
+        WireApi::Gemini => format!("{base}/v1beta/models/{}:generateContent", plan.model),
+    }
+}
+
+fn build_request(request: RequestBuilder, plan: &SmokeTestPlan) -> RequestBuilder {
+    let model = &plan.model;
+    match plan.wire_api {
+        WireApi::Messages => request
+            .header("x-api-key", plan.api_key.as_str())
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            // 多数中转只认 Bearer
+            .bearer_auth(&plan.api_key)
+            .json(&json!({
+                "model": model,
+                "max_tokens": MAX_OUTPUT_TOKENS,
+                "messages": [{ "role": "user", "content": PROMPT }],
+            })),
+        WireApi::Chat => request.bearer_auth(&plan.api_key).json(&json!({
+            "model": model,
+            "max_tokens": MAX_OUTPUT_TOKENS,
+            "messages": [{ "role": "user", "content": PROMPT }],
+        })),
+        WireApi::Responses => request.bearer_auth(&plan.api_key).json(&json!({
+            "model": model,
+            "max_output_tokens": MAX_OUTPUT_TOKENS,
+            "input": PROMPT,
+        })),
+        WireApi::Gemini => request
+            .header("x-goog-api-key", plan.api_key.as_str())
+            .json(&json!({
+                "contents": [{ "role": "user", "parts": [{ "text": PROMPT }] }],
+                "generationConfig": { "maxOutputTokens": MAX_OUTPUT_TOKENS },
+            })),
+    }
+}
+
+/// 从各格式的响应中提取文本
+fn extract_reply(wire_api: WireApi, body: &Value) -> Option<String> {
+    let texts: Vec<&str> = match wire_api {
+        WireApi::Messages => body
+            .get("content")?
+            .as_array()?
+            .iter()
+            .filter_map(|block| block.get("text")?.as_str())
+            .collect(),
+        WireApi::Chat => vec![body.pointer("/choices/0/message/content")?.as_str()?],
+        WireApi::Responses => match body.get("output_text").and_then(Value::as_str) {
+            Some(text) => vec![text],
+            None => body
+                .get("output")?
+                .as_array()?
+                .iter()
+                .filter_map(|item| item.get("content")?.as_array())
+                .flatten()
+                .filter_map(|part| part.get("text")?.as_str())
+                .collect(),
+        },
+        WireApi::Gemini => body
+            .pointer("/candidates/0/content/parts")?
+            .as_array()?
+            .iter()
+            .filter_map(|part| part.get("text")?.as_str())
+            .collect(),
+    };
+    let text = texts.concat().trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+fn preview(text: &str) -> String {
+    text.trim()
+        .chars()
+        .take(REPLY_PREVIEW_CHARS)
+        .collect::<String>()
+        .replace(['\r', '\n'], " ")
+}
+
+fn network_error_message(err: &reqwest::Error) -> String {
+    if err.is_timeout() {
+        "请求超时".to_string()
+    } else if err.is_connect() {
+        "连接失败".to_string()
+    } else {
+        err.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_reply_from_each_format() {
+        let messages = json!({ "content": [{ "type": "text", "text": "pong" }] });
+        let chat = json!({ "choices": [{ "message": { "content": "pong" } }] });
+        let responses = json!({ "output": [
+            { "type": "reasoning", "summary": [] },
+            { "type": "message", "content": [{ "type": "output_text", "text": "pong" }] }
+        ]});
+        let gemini = json!({ "candidates": [{ "content": { "parts": [{ "text": "pong\n" }] } }] });
+
+        for (wire_api, body) in [
+            (WireApi::Messages, messages),
+            (WireApi::Chat, chat),
+            (WireApi::Responses, responses),
+            (WireApi::Gemini, gemini),
+        ] {
+            assert_eq!(
+                extract_reply(wire_api, &body).as_deref(),
+                Some("pong"),
+                "{wire_api:?}"
+            );
+        }
+        assert_eq!(
+            extract_reply(WireApi::Chat, &json!({ "choices": [] })),
+            None
+        );
+    }
+
+    #[test]
+    fn codex_wire_api_follows_active_model_provider() {
+        let settings = json!({ "config": "model_provider = \"relay\"\n\n[model_providers.relay]\nbase_url = \"https://relay.example.com/v1\"\nwire_api = \"chat\"\n" });
+        assert_eq!(codex_wire_api(&settings), WireApi::Chat);
+        assert_eq!(codex_wire_api(&json!({})), WireApi::Responses);
+    }
+}
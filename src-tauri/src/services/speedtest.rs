@@ -4,6 +4,7 @@ use serde::Serialize;
 use std::time::{Duration, Instant};
 
 use crate::error::AppError;
+use crate::network::{self, NetworkFeature};
 
 const DEFAULT_TIMEOUT_SECS: u64 = 8;
 const MAX_TIMEOUT_SECS: u64 = 30;
@@ -30,8 +31,13 @@ impl SpeedtestService {
         if urls.is_empty() {
             return Ok(vec![]);
         }
+        network::ensure_online()?;
 
-        let timeout = Self::sanitize_timeout(timeout_secs);
+        // 调用方未指定时使用网络策略中的超时；测速不重试，避免影响延迟结果
+        let timeout = Self::sanitize_timeout(
+            timeout_secs
+                .or_else(|| Some(network::policy(NetworkFeature::Speedtest).timeout.as_secs())),
+        );
         let client = Self::build_client(timeout)?;
 
         let tasks = urls.into_iter().map(|raw_url| {
@@ -96,17 +102,13 @@ impl SpeedtestService {
     }
 
     fn build_client(timeout_secs: u64) -> Result<Client, AppError> {
-        Client::builder()
+        network::client_builder(NetworkFeature::Speedtest)
             .timeout(Duration::from_secs(timeout_secs))
             .redirect(reqwest::redirect::Policy::limited(5))
             .user_agent("cc-switch-speedtest/1.0")
             .build()
             .map_err(|e| {
-                AppError::localized(
-                    "speedtest.client_create_failed",
-                    format!("创建 HTTP 客户端失败: {e}"),
-                    format!("Failed to create HTTP client: {e}"),
-                )
+                AppError::localized_with("speedtest.client_create_failed", &[("e", e.to_string())])
             })
     }
 
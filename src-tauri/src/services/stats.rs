@@ -0,0 +1,214 @@
+//! 使用统计：供应商切换次数与累计使用时长
+//!
+//! 每次切换供应商记录一条切换事件；某应用的供应商从切换时刻起一直计为"使用中"，
+//! 直到该应用下一次切换为止。
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::store::AppState;
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+/// 单条切换记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchEvent {
+    pub app_type: String,
+    pub provider_id: String,
+    /// 切换时间（Unix 秒）
+    pub switched_at: i64,
+}
+
+/// 统计时间范围（截至当前时刻）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsRange {
+    Day,
+    Week,
+    #[default]
+    Month,
+    All,
+}
+
+impl StatsRange {
+    fn since(self, now: i64) -> i64 {
+        match self {
+            StatsRange::Day => now - DAY_SECS,
+            StatsRange::Week => now - 7 * DAY_SECS,
+            StatsRange::Month => now - 30 * DAY_SECS,
+            StatsRange::All => 0,
+        }
+    }
+}
+
+/// 单个供应商在统计范围内的使用情况
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderUsageStats {
+    pub app_type: String,
+    pub provider_id: String,
+    /// 供应商名称（供应商已删除时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_name: Option<String>,
+    /// 切换到该供应商的次数
+    pub switch_count: u32,
+    /// 累计使用时长（秒）
+    pub active_seconds: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_switched_at: Option<i64>,
+}
+
+/// 切换统计结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchStats {
+    pub range: StatsRange,
+    pub since: i64,
+    pub until: i64,
+    pub total_switches: u32,
+    /// 按切换次数降序，次数相同时按使用时长降序
+    pub providers: Vec<ProviderUsageStats>,
+}
+
+pub struct StatsService;
+
+impl StatsService {
+    /// 记录一次供应商切换（失败只记日志，不影响切换流程）
+    pub fn record_switch(state: &AppState, app_type: &AppType, provider_id: &str) {
+        let event = SwitchEvent {
+            app_type: app_type.as_str().to_string(),
+            provider_id: provider_id.to_string(),
+            switched_at: chrono::Utc::now().timestamp(),
+        };
+        if let Err(e) = state.db.insert_switch_event(&event) {
+            log::warn!("记录供应商切换事件失败: {e}");
+        }
+    }
+
+    /// 统计范围内的切换次数、最常用供应商及各供应商累计使用时长
+    pub fn get_switch_stats(state: &AppState, range: StatsRange) -> Result<SwitchStats, AppError> {
+        let now = chrono::Utc::now().timestamp();
+        let since = range.since(now);
+        let events = state.db.get_switch_events_since(since)?;
+        let mut stats = compute_stats(range, &events, since, now);
+
+        let mut names: HashMap<String, HashMap<String, String>> = HashMap::new();
+        for entry in &mut stats.providers {
+            if !names.contains_key(&entry.app_type) {
+                let providers = state.db.get_all_providers(&entry.app_type)?;
+                let app_names = providers
+                    .into_iter()
+                    .map(|(id, provider)| (id, provider.name))
+                    .collect();
+                names.insert(entry.app_type.clone(), app_names);
+            }
+            entry.provider_name = names[&entry.app_type].get(&entry.provider_id).cloned();
+        }
+        Ok(stats)
+    }
+}
+
+/// 根据按时间升序的切换记录计算 `[since, now]` 区间内的统计
+///
+/// `since` 之前的记录只用于确定区间起点正在使用的供应商，不计入切换次数。
+fn compute_stats(range: StatsRange, events: &[SwitchEvent], since: i64, now: i64) -> SwitchStats {
+    let mut usage: HashMap<(&str, &str), ProviderUsageStats> = HashMap::new();
+    let mut active: HashMap<&str, (&str, i64)> = HashMap::new();
+    let mut total_switches = 0;
+
+    for event in events {
+        let app = event.app_type.as_str();
+        let id = event.provider_id.as_str();
+        let start = event.switched_at.clamp(since, now);
+        if let Some((previous, previous_start)) = active.insert(app, (id, start)) {
+            usage_entry(&mut usage, app, previous).active_seconds += start - previous_start;
+        }
+
+        let entry = usage_entry(&mut usage, app, id);
+        if event.switched_at >= since {
+            entry.switch_count += 1;
+            entry.last_switched_at = Some(event.switched_at);
+            total_switches += 1;
+        }
+    }
+    for (app, (id, start)) in active {
+        usage_entry(&mut usage, app, id).active_seconds += now - start;
+    }
+
+    let mut providers: Vec<ProviderUsageStats> = usage.into_values().collect();
+    providers.sort_by(|a, b| {
+        b.switch_count
+            .cmp(&a.switch_count)
+            .then(b.active_seconds.cmp(&a.active_seconds))
+            .then_with(|| a.app_type.cmp(&b.app_type))
+            .then_with(|| a.provider_id.cmp(&b.provider_id))
+    });
+
+    SwitchStats {
+        range,
+        since,
+        until: now,
+        total_switches,
+        providers,
+    }
+}
+
+fn usage_entry<'m, 'a>(
+    usage: &'m mut HashMap<(&'a str, &'a str), ProviderUsageStats>,
+    app: &'a str,
+    id: &'a str,
+) -> &'m mut ProviderUsageStats {
+    usage
+        .entry((app, id))
+        .or_insert_with(|| ProviderUsageStats {
+            app_type: app.to_string(),
+            provider_id: id.to_string(),
+            provider_name: None,
+            switch_count: 0,
+            active_seconds: 0,
+            last_switched_at: None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(app: &str, id: &str, at: i64) -> SwitchEvent {
+        SwitchEvent {
+            app_type: app.to_string(),
+            provider_id: id.to_string(),
+            switched_at: at,
+        }
+    }
+
+    #[test]
+    fn compute_stats_counts_switches_and_clips_durations() {
+        let events = [
+            // 区间开始前已在使用 relay，只计时长不计次数
+            event("claude", "relay", 50),
+            event("codex", "openai", 120),
+            event("claude", "official", 150),
+            event("claude", "relay", 180),
+        ];
+        let stats = compute_stats(StatsRange::Day, &events, 100, 200);
+
+        assert_eq!(stats.total_switches, 3);
+        let find = |app: &str, id: &str| {
+            stats
+                .providers
+                .iter()
+                .find(|p| p.app_type == app && p.provider_id == id)
+                .expect("provider stats")
+        };
+        assert_eq!(find("claude", "relay").switch_count, 1);
+        assert_eq!(find("claude", "relay").active_seconds, 50 + 20);
+        assert_eq!(find("claude", "official").active_seconds, 30);
+        assert_eq!(find("codex", "openai").active_seconds, 80);
+        assert_eq!(stats.providers[0].provider_id, "openai");
+    }
+}
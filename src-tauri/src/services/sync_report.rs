@@ -0,0 +1,111 @@
+//! 多应用操作的逐应用结果
+//!
+//! 同步 live 配置、启动导入等操作对每个应用独立执行，单个应用失败不影响其他应用；
+//! 结果按应用返回，前端据此展示部分成功。
+
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+
+/// 单个应用的执行状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncStatus {
+    Success,
+    /// 无需处理（如该应用没有当前供应商）
+    Skipped,
+    Failed,
+}
+
+/// 单个应用的执行结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    pub app: AppType,
+    pub status: SyncStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SyncReport {
+    pub fn success(app: AppType) -> Self {
+        Self {
+            app,
+            status: SyncStatus::Success,
+            error: None,
+        }
+    }
+
+    pub fn skipped(app: AppType) -> Self {
+        Self {
+            app,
+            status: SyncStatus::Skipped,
+            error: None,
+        }
+    }
+
+    /// 跳过并说明原因（如配置目录无写入权限）
+    pub fn skipped_with(app: AppType, reason: &AppError) -> Self {
+        Self {
+            app,
+            status: SyncStatus::Skipped,
+            error: Some(reason.to_string()),
+        }
+    }
+
+    pub fn failed(app: AppType, error: &AppError) -> Self {
+        Self {
+            app,
+            status: SyncStatus::Failed,
+            error: Some(error.to_string()),
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.status == SyncStatus::Failed
+    }
+}
+
+/// 汇总失败项为一个错误，供只关心整体成败的调用方使用
+pub fn failures_to_result(reports: &[SyncReport]) -> Result<(), AppError> {
+    let failures: Vec<String> = reports
+        .iter()
+        .filter(|report| report.is_failed())
+        .map(|report| {
+            format!(
+                "{}: {}",
+                report.app.as_str(),
+                report.error.as_deref().unwrap_or_default()
+            )
+        })
+        .collect();
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Message(failures.join("; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_are_joined_per_app() {
+        let reports = vec![
+            SyncReport::success(AppType::Claude),
+            SyncReport::failed(AppType::Gemini, &AppError::Message("bad .env".into())),
+            SyncReport::skipped(AppType::Qwen),
+        ];
+        let err = failures_to_result(&reports).expect_err("gemini failed");
+        assert_eq!(err.to_string(), "gemini: bad .env");
+        assert!(failures_to_result(&reports[..1]).is_ok());
+
+        let json = serde_json::to_value(&reports[0]).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "app": "claude", "status": "success" })
+        );
+    }
+}
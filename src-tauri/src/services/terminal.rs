@@ -0,0 +1,392 @@
+//! 在终端中启动 CLI
+//!
+//! 打开用户的终端并运行对应应用的 CLI（`claude`、`codex`、`gemini` 等），
+//! 让“切换供应商 + 开始会话”一步完成。终端可在设置中指定内置名称，
+//! 或提供含 `{cmd}` 占位符的命令模板；未设置时按平台自动选择。
+//! 应用配置目录被覆盖（全局设置或当前供应商的 `meta.configDir`）时，通过
+//! `CLAUDE_CONFIG_DIR` / `CODEX_HOME` 让 CLI 读取同一份配置。
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::store::AppState;
+
+/// Linux 终端及其“执行命令”参数（其后追加 `sh -c <命令>`）
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const LINUX_TERMINALS: &[(&str, &[&str])] = &[
+    ("x-terminal-emulator", &["-e"]),
+    ("gnome-terminal", &["--"]),
+    ("konsole", &["-e"]),
+    ("xfce4-terminal", &["-x"]),
+    ("kitty", &[]),
+    ("alacritty", &["-e"]),
+    ("wezterm", &["start", "--"]),
+    ("foot", &[]),
+    ("xterm", &["-e"]),
+];
+
+/// 启动结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliLaunch {
+    pub app: String,
+    /// 使用的终端
+    pub terminal: String,
+    /// 在终端中执行的命令
+    pub command: String,
+}
+
+/// 要在终端中执行的命令
+#[derive(Debug, Clone)]
+struct LaunchCommand {
+    cli: PathBuf,
+    cwd: PathBuf,
+    env: Vec<(&'static str, String)>,
+}
+
+pub struct TerminalService;
+
+impl TerminalService {
+    /// CLI 可执行文件名
+    pub fn cli_name(app_type: &AppType) -> &'static str {
+        match app_type {
+            AppType::Claude => "claude",
+            AppType::Codex => "codex",
+            AppType::Gemini => "gemini",
+            AppType::Grok => "grok",
+            AppType::Qwen => "qwen",
+        }
+    }
+
+    /// 在终端中启动应用的 CLI（`cwd` 为空时使用主目录）
+    pub fn launch(
+        state: &AppState,
+        app_type: &AppType,
+        cwd: Option<&str>,
+    ) -> Result<CliLaunch, AppError> {
+        let name = Self::cli_name(app_type);
+        let cli = crate::mcp::find_in_path(name).ok_or_else(|| {
+            AppError::localized_with("terminal.cli_not_found", &[("cli", name.to_string())])
+        })?;
+        let cwd = match cwd.map(str::trim).filter(|dir| !dir.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => crate::test_utils::home_dir()
+                .ok_or_else(|| AppError::Config("无法获取用户主目录".into()))?,
+        };
+        if !cwd.is_dir() {
+            return Err(AppError::localized_with(
+                "terminal.cwd_invalid",
+                &[("path", cwd.display().to_string())],
+            ));
+        }
+
+        let provider_dir = Self::current_config_dir(state, app_type)?;
+        let command = LaunchCommand {
+            cli,
+            cwd,
+            env: config_env(app_type, provider_dir.as_deref()),
+        };
+        let configured = crate::settings::get_settings()
+            .terminal
+            .map(|terminal| terminal.trim().to_string())
+            .filter(|terminal| !terminal.is_empty());
+        let (terminal, mut process) = match configured {
+            Some(template) if template.contains("{cmd}") => {
+                ("custom".to_string(), from_template(&template, &command)?)
+            }
+            configured => platform_terminal(configured.as_deref(), &command)?,
+        };
+
+        process.spawn().map_err(|e| {
+            AppError::localized_with(
+                "terminal.launch_failed",
+                &[("terminal", terminal.clone()), ("e", e.to_string())],
+            )
+        })?;
+        log::info!("已在 {terminal} 中启动 {name}");
+
+        Ok(CliLaunch {
+            app: app_type.as_str().to_string(),
+            terminal,
+            command: shell_line(&command),
+        })
+    }
+
+    /// 当前供应商的 `meta.configDir`（live 配置写入的位置）
+    fn current_config_dir(
+        state: &AppState,
+        app_type: &AppType,
+    ) -> Result<Option<String>, AppError> {
+        let Some(id) = crate::settings::get_effective_current_provider(&state.db, app_type)? else {
+            return Ok(None);
+        };
+        Ok(state
+            .db
+            .get_all_providers(app_type.as_str())?
+            .get(&id)
+            .and_then(|provider| provider.config_dir())
+            .map(str::to_string))
+    }
+}
+
+/// 覆盖了配置目录时传给 CLI 的环境变量（供应商级目录优先于全局设置）
+fn config_env(app_type: &AppType, provider_dir: Option<&str>) -> Vec<(&'static str, String)> {
+    crate::settings::with_provider_config_dir(provider_dir, || {
+        let dir = match app_type {
+            AppType::Claude => {
+                crate::settings::get_claude_override_dir().map(|dir| ("CLAUDE_CONFIG_DIR", dir))
+            }
+            AppType::Codex => {
+                crate::settings::get_codex_override_dir().map(|dir| ("CODEX_HOME", dir))
+            }
+            _ => None,
+        };
+        dir.map(|(key, dir)| (key, dir.to_string_lossy().to_string()))
+            .into_iter()
+            .collect()
+    })
+}
+
+/// POSIX shell 单引号转义
+fn sh_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// cmd.exe 双引号包裹
+fn cmd_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', ""))
+}
+
+/// 当前平台 shell 中执行的命令行
+fn shell_line(command: &LaunchCommand) -> String {
+    let cli = command.cli.to_string_lossy();
+    let cwd = command.cwd.to_string_lossy();
+    if cfg!(windows) {
+        let mut parts = vec![format!("cd /d {}", cmd_quote(&cwd))];
+        parts.extend(
+            command
+                .env
+                .iter()
+                .map(|(key, value)| format!("set \"{key}={}\"", value.replace('"', ""))),
+        );
+        parts.push(cmd_quote(&cli));
+        parts.join(" && ")
+    } else {
+        let env: String = command
+            .env
+            .iter()
+            .map(|(key, value)| format!("{key}={} ", sh_quote(value)))
+            .collect();
+        format!("cd {} && {env}{}", sh_quote(&cwd), sh_quote(&cli))
+    }
+}
+
+/// CLI 退出后保留终端窗口（回到用户 shell）
+fn keep_open_line(command: &LaunchCommand) -> String {
+    format!("{}; exec \"${{SHELL:-sh}}\"", shell_line(command))
+}
+
+/// 按模板构造命令：按空白切分，`{cmd}` 替换为完整命令行，`{cwd}` 替换为工作目录
+fn from_template(template: &str, command: &LaunchCommand) -> Result<Command, AppError> {
+    let line = if cfg!(windows) {
+        shell_line(command)
+    } else {
+        keep_open_line(command)
+    };
+    let cwd = command.cwd.to_string_lossy();
+    let mut args = template_args(template, &line, &cwd).into_iter();
+    let program = args
+        .next()
+        .ok_or_else(|| AppError::localized("terminal.not_found"))?;
+    let mut process = Command::new(program);
+    process.args(args).current_dir(&command.cwd);
+    Ok(process)
+}
+
+fn template_args(template: &str, line: &str, cwd: &str) -> Vec<String> {
+    template
+        .split_whitespace()
+        .map(|token| token.replace("{cmd}", line).replace("{cwd}", cwd))
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn platform_terminal(
+    configured: Option<&str>,
+    command: &LaunchCommand,
+) -> Result<(String, Command), AppError> {
+    let line = applescript_string(&shell_line(command));
+    let terminal = configured.unwrap_or("Terminal");
+    let script = match terminal.to_ascii_lowercase().as_str() {
+        "terminal" => format!(
+            "tell application \"Terminal\"\nactivate\ndo script {line}\nend tell"
+        ),
+        "iterm" | "iterm2" => format!(
+            "tell application \"iTerm\"\nactivate\nset newWindow to (create window with default profile)\ntell current session of newWindow to write text {line}\nend tell"
+        ),
+        _ => {
+            return Err(AppError::localized_with(
+                "terminal.unsupported",
+                &[("terminal", terminal.to_string())],
+            ))
+        }
+    };
+    let mut process = Command::new("osascript");
+    process.arg("-e").arg(script);
+    Ok((terminal.to_string(), process))
+}
+
+#[cfg(target_os = "macos")]
+fn applescript_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(target_os = "linux")]
+fn platform_terminal(
+    configured: Option<&str>,
+    command: &LaunchCommand,
+) -> Result<(String, Command), AppError> {
+    let candidates: Vec<(&str, &[&str])> = match configured {
+        Some(name) => {
+            let args = LINUX_TERMINALS
+                .iter()
+                .find(|(known, _)| *known == name)
+                .map(|(_, args)| *args)
+                .unwrap_or(&["-e"]);
+            vec![(name, args)]
+        }
+        None => LINUX_TERMINALS.to_vec(),
+    };
+    let (name, path, args) = candidates
+        .into_iter()
+        .find_map(|(name, args)| crate::mcp::find_in_path(name).map(|path| (name, path, args)))
+        .ok_or_else(|| AppError::localized("terminal.not_found"))?;
+
+    let mut process = Command::new(path);
+    process
+        .args(linux_args(args, &keep_open_line(command)))
+        .current_dir(&command.cwd);
+    Ok((name.to_string(), process))
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn linux_args(exec_args: &[&str], line: &str) -> Vec<String> {
+    exec_args
+        .iter()
+        .map(|arg| arg.to_string())
+        .chain(["sh".to_string(), "-c".to_string(), line.to_string()])
+        .collect()
+}
+
+#[cfg(windows)]
+fn platform_terminal(
+    configured: Option<&str>,
+    command: &LaunchCommand,
+) -> Result<(String, Command), AppError> {
+    use std::os::windows::process::CommandExt;
+
+    let line = shell_line(command);
+    let use_wt = match configured.map(str::to_ascii_lowercase).as_deref() {
+        Some("wt") | Some("windows terminal") => true,
+        Some("cmd") => false,
+        Some(other) => {
+            return Err(AppError::localized_with(
+                "terminal.unsupported",
+                &[("terminal", other.to_string())],
+            ))
+        }
+        None => crate::mcp::find_in_path("wt").is_some(),
+    };
+
+    // /S /K "..."：cmd 去掉首尾引号后原样执行其中的命令行
+    let (name, mut process) = if use_wt {
+        let mut process = Command::new("wt.exe");
+        process.raw_arg(format!(
+            "-d {} cmd /S /K \"{line}\"",
+            cmd_quote(&command.cwd.to_string_lossy())
+        ));
+        ("wt", process)
+    } else {
+        let mut process = Command::new("cmd");
+        process.raw_arg(format!("/C start \"\" cmd /S /K \"{line}\""));
+        ("cmd", process)
+    };
+    process.current_dir(&command.cwd);
+    Ok((name.to_string(), process))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn platform_terminal(
+    configured: Option<&str>,
+    _command: &LaunchCommand,
+) -> Result<(String, Command), AppError> {
+    Err(AppError::localized_with(
+        "terminal.unsupported",
+        &[("terminal", configured.unwrap_or_default().to_string())],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn builds_quoted_shell_line() {
+        let command = LaunchCommand {
+            cli: PathBuf::from("/usr/local/bin/claude"),
+            cwd: PathBuf::from("/home/me/it's here"),
+            env: vec![("CLAUDE_CONFIG_DIR", "/home/me/.claude-work".to_string())],
+        };
+        assert_eq!(
+            shell_line(&command),
+            "cd '/home/me/it'\\''s here' && CLAUDE_CONFIG_DIR='/home/me/.claude-work' '/usr/local/bin/claude'"
+        );
+        assert!(keep_open_line(&command).ends_with("; exec \"${SHELL:-sh}\""));
+    }
+
+    #[test]
+    fn config_env_prefers_provider_config_dir() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let provider_dir = dir.path().to_string_lossy().to_string();
+
+        let env = config_env(&AppType::Claude, Some(&provider_dir));
+        assert_eq!(env, vec![("CLAUDE_CONFIG_DIR", provider_dir.clone())]);
+        assert_ne!(config_env(&AppType::Claude, None), env, "global override");
+        assert_eq!(
+            config_env(&AppType::Codex, Some(&provider_dir)),
+            vec![("CODEX_HOME", provider_dir.clone())]
+        );
+        assert!(config_env(&AppType::Gemini, Some(&provider_dir)).is_empty());
+    }
+
+    #[test]
+    fn expands_template_placeholders() {
+        let args = template_args(
+            "wezterm start --cwd {cwd} -- sh -c {cmd}",
+            "claude; exec sh",
+            "/tmp",
+        );
+        assert_eq!(
+            args,
+            vec![
+                "wezterm",
+                "start",
+                "--cwd",
+                "/tmp",
+                "--",
+                "sh",
+                "-c",
+                "claude; exec sh"
+            ]
+        );
+        assert_eq!(
+            linux_args(&["--"], "claude"),
+            vec!["--", "sh", "-c", "claude"]
+        );
+    }
+}
@@ -0,0 +1,744 @@
+//! 应用更新
+//!
+//! 基于 GitHub Releases 检查新版本：查询最新发布、按语义化版本比较，
+//! 下载当前平台的安装包并校验 SHA-256 与 minisign 签名，校验通过后交给系统安装程序。
+//! 签名公钥在发布构建时通过 `CC_SWITCH_UPDATE_PUBKEY` 注入，未注入的构建拒绝安装更新。
+//! 支持 stable / beta 通道、跳过指定版本，以及后台定期检查并发射 `update-available` 事件。
+
+use base64::prelude::*;
+use minisign_verify::{PublicKey, Signature};
+use reqwest::Client;
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppError;
+use crate::network::{self, NetworkFeature};
+use crate::notifications::NotificationCategory;
+use crate::settings::UpdateChannel;
+
+/// beta 通道一次拉取的发布数量
+const BETA_RELEASES_PER_PAGE: u32 = 20;
+/// 启动后首次后台检查前的等待时间
+const INITIAL_DELAY: Duration = Duration::from_secs(60);
+/// 后台检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// 更新签名公钥（minisign 公钥文件或其 Base64，与 `tauri signer generate` 输出一致）
+const UPDATE_PUBLIC_KEY: Option<&str> = option_env!("CC_SWITCH_UPDATE_PUBKEY");
+
+/// GitHub Release 中的附件
+#[derive(Debug, Clone, Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    browser_download_url: String,
+    #[serde(default)]
+    size: u64,
+}
+
+/// GitHub Release（仅保留用到的字段）
+#[derive(Debug, Clone, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    body: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    published_at: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    assets: Vec<ReleaseAsset>,
+}
+
+/// 当前平台可用的安装包
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAsset {
+    pub name: String,
+    pub url: String,
+    pub size: u64,
+}
+
+/// 发布说明中的一个分节（如 "Features"、"Bug Fixes"）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseNoteSection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub items: Vec<String>,
+}
+
+/// 检查更新结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub available: bool,
+    /// 最新版本已被用户跳过
+    #[serde(default)]
+    pub skipped: bool,
+    pub channel: UpdateChannel,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_notes: Option<String>,
+    /// 从发布说明解析出的条目
+    #[serde(default)]
+    pub notes: Vec<ReleaseNoteSection>,
+    pub release_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset: Option<UpdateAsset>,
+}
+
+/// 更新服务
+pub struct UpdateService;
+
+impl UpdateService {
+    pub fn current_version() -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    /// 按设置中的通道查询最新发布并与当前版本比较
+    pub async fn check() -> Result<UpdateInfo, AppError> {
+        network::ensure_online()?;
+        let settings = crate::settings::get_settings();
+        let client = build_client(NetworkFeature::Updates)?;
+        let release = fetch_latest_release(&client, settings.update_channel).await?;
+        Ok(Self::to_info(
+            &release,
+            settings.update_channel,
+            settings.skipped_update_version.as_deref(),
+        ))
+    }
+
+    /// 跳过指定版本（None 表示取消跳过）
+    pub fn skip_version(version: Option<String>) -> Result<(), AppError> {
+        let mut settings = crate::settings::get_settings();
+        settings.skipped_update_version = version
+            .map(|v| v.trim().trim_start_matches(['v', 'V']).to_string())
+            .filter(|v| !v.is_empty());
+        crate::settings::update_settings(settings)
+    }
+
+    /// 启动后台定期检查；发现未被跳过的新版本时发射 `update-available` 事件（同一版本只提示一次）
+    pub fn start_scheduler(app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(INITIAL_DELAY).await;
+            let mut notified: Option<String> = None;
+            loop {
+                // 离线时跳过本轮检查，不把“离线”当作失败记录
+                let due =
+                    crate::settings::get_settings().auto_check_updates && network::is_online();
+                if due {
+                    match Self::check().await {
+                        Ok(info) if info.available && !info.skipped => {
+                            if notified.as_deref() != Some(info.latest_version.as_str()) {
+                                log::info!("发现新版本: {}", info.latest_version);
+                                if let Err(e) = app.emit("update-available", &info) {
+                                    log::error!("发射 update-available 事件失败: {e}");
+                                }
+                                crate::notifications::notify(
+                                    NotificationCategory::UpdateAvailable,
+                                    &[("version", info.latest_version.clone())],
+                                );
+                                notified = Some(info.latest_version);
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(e) => log::warn!("后台检查更新失败: {e}"),
+                    }
+                }
+                tokio::time::sleep(CHECK_INTERVAL).await;
+            }
+        });
+    }
+
+    /// 下载当前平台的安装包并校验，返回本地文件路径
+    ///
+    /// 发布中必须附带 `<安装包>.sha256` 或 `SHA256SUMS` 等校验文件，以及由固定公钥签名的
+    /// `<安装包>.sig`；任一缺失或不匹配时拒绝安装。
+    pub async fn download(target_dir: &Path) -> Result<PathBuf, AppError> {
+        let public_key =
+            UPDATE_PUBLIC_KEY.ok_or_else(|| AppError::localized("update.signature_key_missing"))?;
+        network::ensure_online()?;
+        let channel = crate::settings::get_settings().update_channel;
+        let client = build_client(NetworkFeature::Updates)?;
+        let release = fetch_latest_release(&client, channel).await?;
+        let info = Self::to_info(&release, channel, None);
+        if !info.available {
+            return Err(AppError::localized_with(
+                "update.already_latest",
+                &[("version", info.current_version)],
+            ));
+        }
+        let asset = select_asset(&release.assets)
+            .ok_or_else(|| AppError::localized("update.asset_missing"))?;
+
+        let expected = fetch_checksum(&client, &release.assets, &asset.name).await?;
+        let signature = fetch_signature(&client, &release.assets, &asset.name).await?;
+
+        let download_client = build_client(NetworkFeature::Downloads)?;
+        let bytes = get_bytes(&download_client, &asset.browser_download_url).await?;
+        let actual = hex_encode(digest(&SHA256, &bytes).as_ref());
+        if !actual.eq_ignore_ascii_case(&expected) {
+            return Err(AppError::localized_with(
+                "update.checksum_mismatch",
+                &[("name", asset.name.clone())],
+            ));
+        }
+        verify_signature(public_key, &bytes, &signature, &asset.name)?;
+
+        std::fs::create_dir_all(target_dir).map_err(|e| AppError::io(target_dir, e))?;
+        let path = target_dir.join(&asset.name);
+        std::fs::write(&path, &bytes).map_err(|e| AppError::io(&path, e))?;
+        log::info!("已下载并校验更新包: {}", path.display());
+        Ok(path)
+    }
+
+    fn to_info(release: &Release, channel: UpdateChannel, skipped: Option<&str>) -> UpdateInfo {
+        let current = Self::current_version().to_string();
+        let latest = release.tag_name.trim_start_matches(['v', 'V']).to_string();
+        let available = compare_versions(&latest, &current) == Some(Ordering::Greater);
+        let skipped =
+            skipped.is_some_and(|v| compare_versions(v, &latest) == Some(Ordering::Equal));
+        let body = release.body.clone().filter(|s| !s.trim().is_empty());
+        UpdateInfo {
+            current_version: current,
+            latest_version: latest,
+            available,
+            skipped,
+            channel,
+            prerelease: release.prerelease,
+            release_name: release.name.clone().filter(|s| !s.trim().is_empty()),
+            notes: body.as_deref().map(parse_release_notes).unwrap_or_default(),
+            release_notes: body,
+            release_url: release.html_url.clone(),
+            published_at: release.published_at.clone(),
+            asset: select_asset(&release.assets).map(|a| UpdateAsset {
+                name: a.name.clone(),
+                url: a.browser_download_url.clone(),
+                size: a.size,
+            }),
+        }
+    }
+}
+
+/// 由 Cargo.toml 的 repository 字段推导 Releases API 地址
+fn releases_api_url() -> String {
+    let repo = env!("CARGO_PKG_REPOSITORY")
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .trim_start_matches("https://github.com/");
+    format!("https://api.github.com/repos/{repo}/releases")
+}
+
+fn build_client(feature: NetworkFeature) -> Result<Client, AppError> {
+    network::client_builder(feature)
+        .redirect(reqwest::redirect::Policy::limited(10))
+        .user_agent(concat!("cc-switch/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| AppError::localized_with("update.request_failed", &[("e", e.to_string())]))
+}
+
+/// stable 通道取 `/releases/latest`；beta 通道在最近的发布（含预发布）中取版本号最高者
+async fn fetch_latest_release(
+    client: &Client,
+    channel: UpdateChannel,
+) -> Result<Release, AppError> {
+    match channel {
+        UpdateChannel::Stable => {
+            let release: Release =
+                get_json(client, &format!("{}/latest", releases_api_url())).await?;
+            if release.draft || release.prerelease {
+                return Err(AppError::localized("update.no_release"));
+            }
+            Ok(release)
+        }
+        UpdateChannel::Beta => {
+            let url = format!("{}?per_page={BETA_RELEASES_PER_PAGE}", releases_api_url());
+            let releases: Vec<Release> = get_json(client, &url).await?;
+            pick_newest(releases).ok_or_else(|| AppError::localized("update.no_release"))
+        }
+    }
+}
+
+fn pick_newest(releases: Vec<Release>) -> Option<Release> {
+    releases
+        .into_iter()
+        .filter(|r| !r.draft && parse_version(&r.tag_name).is_some())
+        .max_by(|a, b| compare_versions(&a.tag_name, &b.tag_name).unwrap_or(Ordering::Equal))
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(
+    client: &Client,
+    url: &str,
+) -> Result<T, AppError> {
+    let request = client
+        .get(url)
+        .header("Accept", "application/vnd.github+json");
+    let response = network::send_with_retry(NetworkFeature::Updates, request)
+        .await
+        .map_err(|e| AppError::localized_with("update.request_failed", &[("e", e.to_string())]))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err(AppError::localized("update.no_release"));
+    }
+    if !status.is_success() {
+        return Err(AppError::localized_with(
+            "update.request_status",
+            &[("status", status.to_string())],
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| AppError::localized_with("update.response_invalid", &[("e", e.to_string())]))
+}
+
+async fn get_bytes(client: &Client, url: &str) -> Result<Vec<u8>, AppError> {
+    let response = network::send_with_retry(NetworkFeature::Downloads, client.get(url))
+        .await
+        .map_err(|e| AppError::localized_with("update.download_failed", &[("e", e.to_string())]))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AppError::localized_with(
+            "update.download_failed",
+            &[("e", status.to_string())],
+        ));
+    }
+    response
+        .bytes()
+        .await
+        .map(|b| b.to_vec())
+        .map_err(|e| AppError::localized_with("update.download_failed", &[("e", e.to_string())]))
+}
+
+/// 查找并下载安装包对应的 SHA-256
+async fn fetch_checksum(
+    client: &Client,
+    assets: &[ReleaseAsset],
+    asset_name: &str,
+) -> Result<String, AppError> {
+    let per_file = format!("{asset_name}.sha256").to_lowercase();
+    let checksum_asset = assets
+        .iter()
+        .find(|a| a.name.to_lowercase() == per_file)
+        .or_else(|| {
+            assets.iter().find(|a| {
+                let name = a.name.to_lowercase();
+                name == "sha256sums" || name == "sha256sums.txt" || name == "checksums.txt"
+            })
+        })
+        .ok_or_else(|| {
+            AppError::localized_with("update.checksum_missing", &[("name", asset_name.into())])
+        })?;
+
+    let bytes = get_bytes(client, &checksum_asset.browser_download_url).await?;
+    let text = String::from_utf8_lossy(&bytes);
+    parse_checksum(&text, asset_name).ok_or_else(|| {
+        AppError::localized_with("update.checksum_missing", &[("name", asset_name.into())])
+    })
+}
+
+/// 下载安装包对应的 minisign 签名（`<安装包>.sig` 或 `<安装包>.minisig`）
+async fn fetch_signature(
+    client: &Client,
+    assets: &[ReleaseAsset],
+    asset_name: &str,
+) -> Result<String, AppError> {
+    let signature_asset = find_signature_asset(assets, asset_name).ok_or_else(|| {
+        AppError::localized_with("update.signature_missing", &[("name", asset_name.into())])
+    })?;
+    let bytes = get_bytes(client, &signature_asset.browser_download_url).await?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn find_signature_asset<'a>(
+    assets: &'a [ReleaseAsset],
+    asset_name: &str,
+) -> Option<&'a ReleaseAsset> {
+    let candidates = [
+        format!("{asset_name}.sig").to_lowercase(),
+        format!("{asset_name}.minisig").to_lowercase(),
+    ];
+    candidates
+        .iter()
+        .find_map(|name| assets.iter().find(|a| a.name.to_lowercase() == *name))
+}
+
+/// 用固定公钥校验安装包签名
+///
+/// 公钥与签名都接受 minisign 原始文本或其整体 Base64（Tauri 发布产物使用后者）。
+fn verify_signature(
+    public_key: &str,
+    data: &[u8],
+    signature: &str,
+    asset_name: &str,
+) -> Result<(), AppError> {
+    let key_text = decode_minisign_text(public_key);
+    let key = PublicKey::decode(&key_text)
+        .or_else(|_| PublicKey::from_base64(key_text.trim()))
+        .map_err(|e| AppError::Config(format!("更新签名公钥无效: {e}")))?;
+
+    let invalid =
+        || AppError::localized_with("update.signature_invalid", &[("name", asset_name.into())]);
+    let signature = Signature::decode(&decode_minisign_text(signature)).map_err(|_| invalid())?;
+    key.verify(data, &signature, false).map_err(|_| invalid())
+}
+
+fn decode_minisign_text(raw: &str) -> String {
+    let raw = raw.trim();
+    if raw.starts_with("untrusted comment:") {
+        return raw.to_string();
+    }
+    BASE64_STANDARD
+        .decode(raw)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .unwrap_or_else(|| raw.to_string())
+}
+
+/// 解析 `sha256sum` 格式（`<hex>  <文件名>`），单文件校验可只有哈希
+fn parse_checksum(text: &str, asset_name: &str) -> Option<String> {
+    let is_hash = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    for line in &lines {
+        let mut parts = line.split_whitespace();
+        let (Some(hash), Some(name)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        if is_hash(hash) && name.trim_start_matches('*') == asset_name {
+            return Some(hash.to_lowercase());
+        }
+    }
+
+    match lines.as_slice() {
+        [single] => single
+            .split_whitespace()
+            .next()
+            .filter(|hash| is_hash(hash))
+            .map(str::to_lowercase),
+        _ => None,
+    }
+}
+
+/// 将 Markdown 发布说明解析为分节条目
+///
+/// `#` 标题开启新分节，`-` / `*` / `+` / 数字编号开头的行作为条目，其余段落文本也作为条目保留。
+fn parse_release_notes(body: &str) -> Vec<ReleaseNoteSection> {
+    let mut sections: Vec<ReleaseNoteSection> = Vec::new();
+    let mut in_comment = false;
+
+    for raw in body.lines() {
+        let line = raw.trim();
+        if in_comment {
+            in_comment = !line.contains("-->");
+            continue;
+        }
+        if line.starts_with("<!--") {
+            in_comment = !line.contains("-->");
+            continue;
+        }
+        if line.is_empty() || line.chars().all(|c| matches!(c, '-' | '*' | '_' | '=')) {
+            continue;
+        }
+
+        if line.starts_with('#') {
+            let title = line.trim_start_matches('#').trim().to_string();
+            sections.push(ReleaseNoteSection {
+                title: (!title.is_empty()).then_some(title),
+                items: Vec::new(),
+            });
+            continue;
+        }
+
+        let item = strip_list_marker(line);
+        if item.is_empty() {
+            continue;
+        }
+        if sections.is_empty() {
+            sections.push(ReleaseNoteSection {
+                title: None,
+                items: Vec::new(),
+            });
+        }
+        if let Some(section) = sections.last_mut() {
+            section.items.push(item.to_string());
+        }
+    }
+
+    sections.retain(|s| !s.items.is_empty());
+    sections
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            return rest.trim();
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        if let Some(rest) = line[digits..].strip_prefix(". ") {
+            return rest.trim();
+        }
+    }
+    line
+}
+
+/// 按平台与架构挑选安装包
+fn select_asset(assets: &[ReleaseAsset]) -> Option<&ReleaseAsset> {
+    let suffixes: &[&str] = if cfg!(target_os = "windows") {
+        &[".msi", "-setup.exe", ".exe"]
+    } else if cfg!(target_os = "macos") {
+        &[".dmg"]
+    } else {
+        &[".appimage", ".deb", ".rpm"]
+    };
+    let arch_hints: &[&str] = if cfg!(target_arch = "aarch64") {
+        &["aarch64", "arm64"]
+    } else {
+        &["x86_64", "x64", "amd64"]
+    };
+    let foreign_arch: &[&str] = if cfg!(target_arch = "aarch64") {
+        &["x86_64", "x64", "amd64"]
+    } else {
+        &["aarch64", "arm64"]
+    };
+
+    for suffix in suffixes {
+        let candidates: Vec<&ReleaseAsset> = assets
+            .iter()
+            .filter(|a| a.name.to_lowercase().ends_with(suffix))
+            .filter(|a| {
+                let name = a.name.to_lowercase();
+                !foreign_arch.iter().any(|hint| name.contains(hint))
+            })
+            .collect();
+        let preferred = candidates.iter().find(|a| {
+            let name = a.name.to_lowercase();
+            arch_hints.iter().any(|hint| name.contains(hint))
+        });
+        if let Some(asset) = preferred.or_else(|| candidates.first()) {
+            return Some(*asset);
+        }
+    }
+    None
+}
+
+/// 比较两个语义化版本（忽略构建元数据）；无法解析时返回 None
+pub(crate) fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let (core_a, pre_a) = parse_version(a)?;
+    let (core_b, pre_b) = parse_version(b)?;
+    match core_a.cmp(&core_b) {
+        Ordering::Equal => {}
+        other => return Some(other),
+    }
+    // 预发布版本低于同号正式版本
+    Some(match (pre_a, pre_b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(x), Some(y)) => compare_prerelease(&x, &y),
+    })
+}
+
+fn parse_version(raw: &str) -> Option<([u64; 3], Option<String>)> {
+    let raw = raw.trim().trim_start_matches(['v', 'V']);
+    let raw = raw.split('+').next()?;
+    let (core, pre) = match raw.split_once('-') {
+        Some((core, pre)) => (core, Some(pre.to_string())),
+        None => (raw, None),
+    };
+    let mut parts = [0u64; 3];
+    let mut count = 0;
+    for (i, part) in core.split('.').enumerate() {
+        if i >= 3 {
+            return None;
+        }
+        parts[i] = part.parse().ok()?;
+        count += 1;
+    }
+    (count > 0).then_some((parts, pre))
+}
+
+fn compare_prerelease(a: &str, b: &str) -> Ordering {
+    let mut left = a.split('.');
+    let mut right = b.split('.');
+    loop {
+        match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => {
+                let ord = match (x.parse::<u64>(), y.parse::<u64>()) {
+                    (Ok(x), Ok(y)) => x.cmp(&y),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => x.cmp(y),
+                };
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+        }
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(name: &str) -> ReleaseAsset {
+        ReleaseAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{name}"),
+            size: 1,
+        }
+    }
+
+    #[test]
+    fn compares_semver_with_prereleases() {
+        assert_eq!(
+            compare_versions("3.10.0", "3.9.16"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(compare_versions("v3.9.16", "3.9.16"), Some(Ordering::Equal));
+        assert_eq!(
+            compare_versions("3.9.16-beta.1", "3.9.16"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_versions("3.9.16-beta.10", "3.9.16-beta.2"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(compare_versions("not-a-version", "3.9.16"), None);
+    }
+
+    #[test]
+    fn parses_release_notes_into_sections() {
+        let body = "<!-- generated -->\n## Features\n- Add update channels\n* Skip versions\n\n## Fixes\n1. Fix tray icon\n---\n";
+        let notes = parse_release_notes(body);
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].title.as_deref(), Some("Features"));
+        assert_eq!(notes[0].items, vec!["Add update channels", "Skip versions"]);
+        assert_eq!(notes[1].items, vec!["Fix tray icon"]);
+
+        let plain = parse_release_notes("Bug fixes and improvements");
+        assert_eq!(plain[0].title, None);
+        assert_eq!(plain[0].items, vec!["Bug fixes and improvements"]);
+    }
+
+    #[test]
+    fn beta_channel_picks_highest_version() {
+        let release = |tag: &str, prerelease: bool, draft: bool| Release {
+            tag_name: tag.to_string(),
+            name: None,
+            body: None,
+            html_url: String::new(),
+            published_at: None,
+            draft,
+            prerelease,
+            assets: Vec::new(),
+        };
+        let newest = pick_newest(vec![
+            release("v3.9.16", false, false),
+            release("v3.10.0-beta.2", true, false),
+            release("v3.10.0-beta.3", true, true),
+            release("nightly", true, false),
+        ])
+        .expect("newest release");
+        assert_eq!(newest.tag_name, "v3.10.0-beta.2");
+    }
+
+    #[test]
+    fn parses_checksum_files() {
+        let hash = "a".repeat(64);
+        let sums = format!("{hash}  CC-Switch.msi\n{}  other.dmg\n", "b".repeat(64));
+        assert_eq!(parse_checksum(&sums, "CC-Switch.msi"), Some(hash.clone()));
+        assert_eq!(parse_checksum(&sums, "missing.msi"), None);
+        assert_eq!(
+            parse_checksum(&format!("{hash}\n"), "CC-Switch.msi"),
+            Some(hash)
+        );
+    }
+
+    /// 测试用密钥对生成的公钥与 `CC-Switch.msi` 签名（非发布密钥）
+    const TEST_PUBLIC_KEY: &str = "untrusted comment: minisign public key 0123456789ABCDEF\nRWQBI0VniavN7wOhB7/zzhC+HXDdGOdLwJln5NYwm6UNXx3chmQSVTG4\n";
+    const TEST_SIGNATURE: &str = "untrusted comment: signature from cc-switch test key\nRUQBI0VniavN71XcBfVaxcgG7yFMazTA6biWO9dRQITTt8LfAqa7jqCLjgnwFZQ0mKrpsOkT5z+NEL+ZCUwOGnNQAFHhJM4VrgI=\ntrusted comment: timestamp:1760000000\tfile:CC-Switch.msi\nhIS0NJQzdm+rYGas0M93VaUpNB7t7Y4gJY7c6yOFSRAMo+wYqfVPpR7+CKh3aBupzrMHSGLV97S7ocTOxzMNBQ==\n";
+    const TEST_PAYLOAD: &[u8] = b"cc-switch installer";
+
+    #[test]
+    fn verifies_minisign_signatures() {
+        verify_signature(
+            TEST_PUBLIC_KEY,
+            TEST_PAYLOAD,
+            TEST_SIGNATURE,
+            "CC-Switch.msi",
+        )
+        .expect("raw minisign text");
+
+        // Tauri 发布产物中的 .sig 与公钥都是整体 Base64
+        let key = BASE64_STANDARD.encode(TEST_PUBLIC_KEY);
+        let signature = BASE64_STANDARD.encode(TEST_SIGNATURE);
+        verify_signature(&key, TEST_PAYLOAD, &signature, "CC-Switch.msi").expect("base64 form");
+
+        assert!(
+            verify_signature(&key, b"tampered installer", &signature, "CC-Switch.msi").is_err()
+        );
+        assert!(verify_signature(&key, TEST_PAYLOAD, "not a signature", "CC-Switch.msi").is_err());
+    }
+
+    #[test]
+    fn finds_signature_asset() {
+        let assets = vec![
+            asset("CC-Switch.msi"),
+            asset("CC-Switch.msi.sha256"),
+            asset("CC-Switch.msi.sig"),
+        ];
+        assert_eq!(
+            find_signature_asset(&assets, "CC-Switch.msi").map(|a| a.name.as_str()),
+            Some("CC-Switch.msi.sig")
+        );
+        assert!(find_signature_asset(&assets, "CC-Switch.dmg").is_none());
+    }
+
+    #[test]
+    fn selects_platform_asset() {
+        let assets = vec![
+            asset("CC-Switch_3.10.0_x64_en-US.msi"),
+            asset("CC-Switch_3.10.0_arm64_en-US.msi"),
+            asset("CC-Switch_3.10.0_aarch64.dmg"),
+            asset("CC-Switch_3.10.0_x64.dmg"),
+            asset("CC-Switch_3.10.0_amd64.AppImage"),
+            asset("CC-Switch_3.10.0_aarch64.AppImage"),
+            asset("CC-Switch_3.10.0_amd64.deb"),
+            asset("SHA256SUMS"),
+        ];
+        let selected = select_asset(&assets).expect("asset for current platform");
+        assert_ne!(selected.name, "SHA256SUMS");
+    }
+}
@@ -1,10 +1,16 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
-use std::sync::{OnceLock, RwLock};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
 
 use crate::app_config::AppType;
+use crate::database::Database;
 use crate::error::AppError;
+use crate::services::device::{DeviceService, DEVICE_SCOPED_FIELDS};
 
 /// 自定义端点配置（历史兼容，实际存储在 provider.meta.custom_endpoints）
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +22,268 @@ pub struct CustomEndpoint {
     pub last_used: Option<i64>,
 }
 
+/// 提示词文件被外部修改时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptWatchMode {
+    /// 不监听
+    Off,
+    /// 回填到已启用的提示词
+    #[default]
+    Backfill,
+    /// 保存为带时间戳的新提示词，不修改已启用项
+    Revision,
+}
+
+/// 数据库自动备份频率
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DbBackupSchedule {
+    Off,
+    #[default]
+    Daily,
+    Weekly,
+}
+
+impl DbBackupSchedule {
+    /// 两次自动备份的间隔（秒），关闭时为 None
+    pub fn interval_secs(&self) -> Option<i64> {
+        match self {
+            DbBackupSchedule::Off => None,
+            DbBackupSchedule::Daily => Some(24 * 60 * 60),
+            DbBackupSchedule::Weekly => Some(7 * 24 * 60 * 60),
+        }
+    }
+}
+
+/// 配置快照导出时机
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigSnapshotSchedule {
+    #[default]
+    Off,
+    /// 配置变化后导出
+    OnChange,
+    /// 每天导出一次
+    Nightly,
+}
+
+/// 模型价格（美元 / 百万 token）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPrice {
+    /// 模型 ID 或前缀（最长前缀匹配，如 `claude-sonnet-4-5` 匹配带日期的版本）
+    pub model: String,
+    pub input: f64,
+    pub output: f64,
+}
+
+/// 切换钩子的执行时机
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HookPhase {
+    /// 写入 live 配置之前
+    Before,
+    /// 切换完成之后
+    #[default]
+    After,
+}
+
+/// 钩子失败（非零退出码、超时或无法启动）时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HookFailurePolicy {
+    /// 记录日志后继续
+    #[default]
+    Continue,
+    /// before 钩子取消本次切换；after 钩子跳过其余 after 钩子
+    Abort,
+}
+
+/// 切换供应商时执行的用户命令
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchHook {
+    /// 显示名称（日志与错误信息中使用）
+    pub name: String,
+    /// 限定应用（claude/codex/...），为空表示所有应用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
+    #[serde(default)]
+    pub phase: HookPhase,
+    /// 通过系统 shell 执行（Unix 为 `sh -c`，Windows 为 `cmd /C`）
+    pub command: String,
+    /// 超时秒数（0 表示使用默认值）
+    #[serde(default)]
+    pub timeout_secs: u64,
+    #[serde(default)]
+    pub on_failure: HookFailurePolicy,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 事件 Webhook
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WebhookSettings {
+    /// 接收事件的地址（HTTPS），为空表示关闭
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// HMAC-SHA256 签名密钥（为空时不签名）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// 订阅的事件（如 `provider.switched`），为空表示全部
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+}
+
+/// Claude 供应商 `env` 的写入位置
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClaudeEnvTarget {
+    /// 写入 `settings.json`
+    #[default]
+    Settings,
+    /// 写入 `settings.local.json`（其余字段仍写入 `settings.json`）
+    Local,
+}
+
+/// 配置文件为符号链接时的写入方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymlinkWriteMode {
+    /// 写入链接指向的文件，保留符号链接（如指向 dotfiles 仓库）
+    #[default]
+    Follow,
+    /// 以普通文件替换符号链接，并记录警告
+    Warn,
+}
+
+/// 托盘图标样式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrayIconStyle {
+    /// 单色剪影：macOS 使用模板图标，其他平台按系统主题选择深浅色
+    #[default]
+    Auto,
+    /// 应用彩色图标
+    Color,
+}
+
+/// 网络模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkMode {
+    /// 自动探测连通性
+    #[default]
+    Auto,
+    /// 始终视为在线（不探测）
+    Online,
+    /// 离线：网络功能直接返回离线状态
+    Offline,
+}
+
+/// 超时与重试参数（为空时沿用上一级或功能自身的默认值）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NetworkTimeouts {
+    /// 建立连接超时（秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    /// 整个请求（含读取响应）超时（秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// 连接失败、超时或 429/502/503/504 时的重试次数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    /// 首次重试前的等待（毫秒），之后每次翻倍
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backoff_ms: Option<u64>,
+}
+
+/// 网络请求策略：全局参数 + 按功能覆盖
+///
+/// 覆盖项的键为功能名：`speedtest`、`models`、`smokeTest`、`skills`、`updates`、
+/// `downloads`、`provision`、`webhook`。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NetworkPolicy {
+    #[serde(flatten)]
+    pub defaults: NetworkTimeouts,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub overrides: BTreeMap<String, NetworkTimeouts>,
+}
+
+/// 更新通道
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// 仅正式版本
+    #[default]
+    Stable,
+    /// 包含预发布版本
+    Beta,
+}
+
+/// 深链接导入确认方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DeepLinkConfirmMode {
+    /// 始终弹出确认框
+    #[default]
+    AlwaysAsk,
+    /// 受信任密钥签名的链接直接导入，其余仍需确认
+    AutoAcceptSigned,
+}
+
+/// 深链接导入策略
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DeepLinkPolicySettings {
+    pub confirm_mode: DeepLinkConfirmMode,
+    /// 拒绝携带 API Key 的链接（包括签名链接）
+    pub block_api_key_links: bool,
+    /// 允许导入的资源类型（provider/prompt/mcp/skill），为空表示全部允许
+    pub allowed_resources: Vec<String>,
+    /// 受信任的 Ed25519 公钥（Base64）
+    pub trusted_keys: Vec<String>,
+}
+
+/// 系统通知分类开关
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    /// 切换供应商成功（托盘切换等没有界面反馈的场景）
+    pub switch_success: bool,
+    /// 切换供应商失败
+    pub switch_failure: bool,
+    /// MCP 同步失败
+    pub mcp_sync_error: bool,
+    /// 用量预算告警
+    pub usage_alert: bool,
+    /// 发现新版本
+    pub update_available: bool,
+    /// 从托盘复制了供应商凭据
+    pub credential_copied: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            switch_success: false,
+            switch_failure: true,
+            mcp_sync_error: true,
+            usage_alert: true,
+            update_available: true,
+            credential_copied: true,
+        }
+    }
+}
+
 /// 应用设置结构
 ///
 /// 存储设备级别设置，保存在本地 `~/.cc-switch/settings.json`，不随数据库同步。
@@ -28,14 +296,127 @@ pub struct AppSettings {
     pub show_in_tray: bool,
     #[serde(default = "default_minimize_to_tray_on_close")]
     pub minimize_to_tray_on_close: bool,
+    /// 托盘图标叠加当前 Claude 供应商徽标
+    #[serde(default = "default_dynamic_tray_icon")]
+    pub dynamic_tray_icon: bool,
+    /// 托盘图标样式
+    #[serde(default)]
+    pub tray_icon_style: TrayIconStyle,
     /// 是否启用 Claude 插件联动
     #[serde(default)]
     pub enable_claude_plugin_integration: bool,
+    /// Claude 供应商的 `env` 写入 `settings.json` 还是 `settings.local.json`
+    #[serde(default)]
+    pub claude_env_target: ClaudeEnvTarget,
+    /// 写入的配置文件是符号链接时，写入其指向的文件还是替换链接并警告
+    #[serde(default)]
+    pub symlink_write_mode: SymlinkWriteMode,
     /// 是否开机自启
     #[serde(default)]
     pub launch_on_startup: bool,
+    /// 启动时不显示主窗口，仅保留托盘图标（需启用托盘）
+    #[serde(default)]
+    pub start_minimized: bool,
+    /// 是否已完成首次运行引导
+    #[serde(default)]
+    pub onboarding_completed: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
+    /// 系统通知分类开关
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+    /// 深链接导入策略
+    #[serde(default)]
+    pub deeplink_policy: DeepLinkPolicySettings,
+    /// 启动时将 MCP 服务器与各应用 live 配置对账（导入新增、标记外部删除）
+    #[serde(default)]
+    pub mcp_reconcile_on_launch: bool,
+    /// 提示词文件被外部修改（如 Claude 自行编辑 CLAUDE.md）时的处理方式
+    #[serde(default)]
+    pub prompt_watch_mode: PromptWatchMode,
+    /// 启动 CLI 使用的终端：内置名称（Terminal、iTerm、gnome-terminal、wt 等）
+    /// 或含 `{cmd}`（可选 `{cwd}`）占位符的命令模板；为空时自动选择
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal: Option<String>,
+    /// 目标 CLI 正在运行时阻止切换（默认只在切换结果中提示）
+    #[serde(default)]
+    pub block_switch_when_cli_running: bool,
+    /// 切换供应商时同步更新 Windows 用户级环境变量（HKCU\Environment，仅更新已存在的变量）
+    #[serde(default)]
+    pub sync_windows_env_on_switch: bool,
+    /// 监听剪贴板中的 ccswitch:// 链接与 API Key 并提示导入（默认关闭，不保存剪贴板内容）
+    #[serde(default)]
+    pub clipboard_monitor: bool,
+    /// 从托盘复制 API Key 原文前弹出确认框
+    #[serde(default = "default_true")]
+    pub confirm_credential_copy: bool,
+    /// 返回 API Key 原文前要求 Touch ID / Windows Hello 验证（仅 macOS、Windows）
+    #[serde(default)]
+    pub biometric_key_reveal: bool,
+    /// 将 panic 与命令错误写入本地崩溃报告（默认关闭，不上传）
+    #[serde(default)]
+    pub crash_reporting: bool,
+    /// 应用锁解锁后的有效时长（分钟），口令本身保存在 secrets 表
+    #[serde(default = "default_app_lock_timeout_minutes")]
+    pub app_lock_timeout_minutes: u32,
+
+    // ===== 数据库自动备份 =====
+    /// 自动备份频率
+    #[serde(default)]
+    pub db_backup_schedule: DbBackupSchedule,
+    /// 最多保留的数据库备份数量
+    #[serde(default = "default_db_backup_retain_count")]
+    pub db_backup_retain_count: usize,
+    /// 数据库备份最长保留天数（0 表示不按时间清理）
+    #[serde(default = "default_db_backup_retain_days")]
+    pub db_backup_retain_days: u32,
+
+    // ===== 配置快照导出 =====
+    /// 脱敏配置快照（JSON + Markdown）的导出目录，如 dotfiles 仓库
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_snapshot_dir: Option<String>,
+    /// 配置快照导出时机
+    #[serde(default)]
+    pub config_snapshot_schedule: ConfigSnapshotSchedule,
+
+    // ===== 费用估算 =====
+    /// 覆盖或补充内置模型价格表（同名模型以此为准）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub model_pricing: Vec<ModelPrice>,
+
+    // ===== 切换钩子 =====
+    /// 切换供应商前后执行的命令（按顺序执行）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub switch_hooks: Vec<SwitchHook>,
+
+    // ===== 事件 Webhook =====
+    /// 将切换、MCP 同步失败、用量告警等事件 POST 到外部地址
+    #[serde(default)]
+    pub webhook: WebhookSettings,
+
+    // ===== 网络 =====
+    /// 离线模式：`auto` 自动探测，`offline` 时网络功能立即返回离线
+    #[serde(default)]
+    pub network_mode: NetworkMode,
+    /// 网络请求的超时与重试策略（可按功能覆盖）
+    #[serde(default)]
+    pub network_policy: NetworkPolicy,
+
+    // ===== 应用更新 =====
+    /// 更新通道
+    #[serde(default)]
+    pub update_channel: UpdateChannel,
+    /// 是否在后台定期检查更新
+    #[serde(default = "default_auto_check_updates")]
+    pub auto_check_updates: bool,
+    /// 用户选择跳过的版本（不再提示该版本）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skipped_update_version: Option<String>,
+
+    // ===== 团队模板 =====
+    /// 团队模板清单地址（HTTPS），首次启动时自动导入；环境变量 CC_SWITCH_PROVISION_URL 优先
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provision_url: Option<String>,
 
     // ===== 设备级目录覆盖 =====
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -50,19 +431,19 @@ pub struct AppSettings {
     pub grok_config_dir: Option<String>,
 
     // ===== 当前供应商 ID（设备级）=====
-    /// 当前 Claude 供应商 ID（本地存储，优先于数据库 is_current）
+    /// 当前 Claude 供应商 ID（设备级，以 devices 表中本设备的行为准，优先于数据库 is_current）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub current_provider_claude: Option<String>,
-    /// 当前 Codex 供应商 ID（本地存储，优先于数据库 is_current）
+    /// 当前 Codex 供应商 ID（设备级，以 devices 表中本设备的行为准，优先于数据库 is_current）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub current_provider_codex: Option<String>,
-    /// 当前 Gemini 供应商 ID（本地存储，优先于数据库 is_current）
+    /// 当前 Gemini 供应商 ID（设备级，以 devices 表中本设备的行为准，优先于数据库 is_current）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub current_provider_gemini: Option<String>,
-    /// 当前 Grok 供应商 ID（本地存储，优先于数据库 is_current）
+    /// 当前 Grok 供应商 ID（设备级，以 devices 表中本设备的行为准，优先于数据库 is_current）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub current_provider_grok: Option<String>,
-    /// 当前 Qwen 供应商 ID（本地存储，优先于数据库 is_current）
+    /// 当前 Qwen 供应商 ID（设备级，以 devices 表中本设备的行为准，优先于数据库 is_current）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub current_provider_qwen: Option<String>,
 }
@@ -75,14 +456,66 @@ fn default_minimize_to_tray_on_close() -> bool {
     true
 }
 
+fn default_dynamic_tray_icon() -> bool {
+    true
+}
+
+fn default_auto_check_updates() -> bool {
+    true
+}
+
+fn default_app_lock_timeout_minutes() -> u32 {
+    5
+}
+
+fn default_db_backup_retain_count() -> usize {
+    10
+}
+
+fn default_db_backup_retain_days() -> u32 {
+    30
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
             show_in_tray: true,
             minimize_to_tray_on_close: true,
+            dynamic_tray_icon: default_dynamic_tray_icon(),
+            tray_icon_style: TrayIconStyle::default(),
             enable_claude_plugin_integration: false,
+            claude_env_target: ClaudeEnvTarget::default(),
+            symlink_write_mode: SymlinkWriteMode::default(),
             launch_on_startup: false,
+            start_minimized: false,
+            onboarding_completed: false,
             language: None,
+            notifications: NotificationSettings::default(),
+            deeplink_policy: DeepLinkPolicySettings::default(),
+            mcp_reconcile_on_launch: false,
+            prompt_watch_mode: PromptWatchMode::default(),
+            terminal: None,
+            block_switch_when_cli_running: false,
+            sync_windows_env_on_switch: false,
+            clipboard_monitor: false,
+            confirm_credential_copy: true,
+            biometric_key_reveal: false,
+            crash_reporting: false,
+            app_lock_timeout_minutes: default_app_lock_timeout_minutes(),
+            db_backup_schedule: DbBackupSchedule::default(),
+            db_backup_retain_count: default_db_backup_retain_count(),
+            db_backup_retain_days: default_db_backup_retain_days(),
+            config_snapshot_dir: None,
+            config_snapshot_schedule: ConfigSnapshotSchedule::default(),
+            model_pricing: Vec::new(),
+            switch_hooks: Vec::new(),
+            webhook: WebhookSettings::default(),
+            network_mode: NetworkMode::default(),
+            network_policy: NetworkPolicy::default(),
+            update_channel: UpdateChannel::default(),
+            auto_check_updates: default_auto_check_updates(),
+            skipped_update_version: None,
+            provision_url: None,
             claude_config_dir: None,
             codex_config_dir: None,
             gemini_config_dir: None,
@@ -150,7 +583,35 @@ impl AppSettings {
             .map(|s| s.to_string());
     }
 
+    /// 指定应用的配置目录覆盖字段
+    pub(crate) fn config_dir_slot(&mut self, app_type: &AppType) -> &mut Option<String> {
+        match app_type {
+            AppType::Claude => &mut self.claude_config_dir,
+            AppType::Codex => &mut self.codex_config_dir,
+            AppType::Gemini => &mut self.gemini_config_dir,
+            AppType::Grok => &mut self.grok_config_dir,
+            AppType::Qwen => &mut self.qwen_config_dir,
+        }
+    }
+
+    /// 指定应用的当前供应商字段
+    pub(crate) fn current_provider_slot(&mut self, app_type: &AppType) -> &mut Option<String> {
+        match app_type {
+            AppType::Claude => &mut self.current_provider_claude,
+            AppType::Codex => &mut self.current_provider_codex,
+            AppType::Gemini => &mut self.current_provider_gemini,
+            AppType::Grok => &mut self.current_provider_grok,
+            AppType::Qwen => &mut self.current_provider_qwen,
+        }
+    }
+
     fn load_from_file() -> Self {
+        let mut settings = Self::load_raw_from_file();
+        crate::policy::current().apply(&mut settings);
+        settings
+    }
+
+    fn load_raw_from_file() -> Self {
         let path = Self::settings_path();
         if let Ok(content) = fs::read_to_string(&path) {
             match serde_json::from_str::<AppSettings>(&content) {
@@ -190,11 +651,126 @@ fn save_settings_file(settings: &AppSettings) -> Result<(), AppError> {
 
 static SETTINGS_STORE: OnceLock<RwLock<AppSettings>> = OnceLock::new();
 
+/// 设备设置数据库：接入后设置以 `device_settings` 表为准，settings.json 仅作迁移来源与镜像
+static DEVICE_DB: OnceLock<Arc<Database>> = OnceLock::new();
+
+/// 内存缓存对应的 `device_settings` 版本号
+static SETTINGS_REVISION: AtomicI64 = AtomicI64::new(0);
+
+/// 内存缓存被替换的次数，用于变化通知
+static SETTINGS_GENERATION: AtomicU64 = AtomicU64::new(0);
+
 fn settings_store() -> &'static RwLock<AppSettings> {
     SETTINGS_STORE.get_or_init(|| RwLock::new(AppSettings::load_from_file()))
 }
 
+/// 将设置存储切换到数据库
+///
+/// 表为空时（首次启用或整库导入后）以当前设置（来自 settings.json）填充，
+/// 之后各进程按字段写入，互不覆盖。
+pub fn attach_database(db: Arc<Database>) -> Result<(), AppError> {
+    if DEVICE_DB.set(db).is_err() {
+        return Ok(());
+    }
+    reload_settings()
+}
+
+/// 各设备共享的设置字段（设备级字段存于 `devices` 表）
+fn settings_to_fields(settings: &AppSettings) -> Result<Map<String, Value>, AppError> {
+    match serde_json::to_value(settings).map_err(|e| AppError::JsonSerialize { source: e })? {
+        Value::Object(mut fields) => {
+            for key in DEVICE_SCOPED_FIELDS {
+                fields.remove(*key);
+            }
+            Ok(fields)
+        }
+        _ => Err(AppError::Config("设置序列化结果不是对象".to_string())),
+    }
+}
+
+fn copy_device_fields(from: &AppSettings, to: &mut AppSettings) {
+    let mut from = from.clone();
+    for app in AppType::all() {
+        *to.config_dir_slot(&app) = from.config_dir_slot(&app).take();
+        *to.current_provider_slot(&app) = from.current_provider_slot(&app).take();
+    }
+}
+
+fn settings_from_fields(fields: Map<String, Value>) -> AppSettings {
+    match serde_json::from_value::<AppSettings>(Value::Object(fields)) {
+        Ok(mut settings) => {
+            settings.normalize_paths();
+            settings
+        }
+        Err(e) => {
+            log::warn!("解析数据库中的设备设置失败，将使用默认设置: {e}");
+            AppSettings::default()
+        }
+    }
+}
+
+/// `previous` 到 `next` 之间变化的字段（被移除的可选字段记为 null）
+fn changed_fields(
+    previous: &AppSettings,
+    next: &AppSettings,
+) -> Result<Map<String, Value>, AppError> {
+    let previous = settings_to_fields(previous)?;
+    let next = settings_to_fields(next)?;
+    let mut changes: Map<String, Value> = next
+        .iter()
+        .filter(|(key, value)| previous.get(*key) != Some(*value))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    for key in previous.keys().filter(|key| !next.contains_key(*key)) {
+        changes.insert(key.clone(), Value::Null);
+    }
+    Ok(changes)
+}
+
+/// 从数据库读取设置；表为空时用 `seed` 填充，本设备尚未登记时用 `seed` 的设备级字段登记
+fn load_from_database(db: &Database, seed: &AppSettings) -> Result<AppSettings, AppError> {
+    let (fields, mut revision) = db.get_device_settings()?;
+    let mut settings = if revision == 0 {
+        revision = db.update_device_settings(&settings_to_fields(seed)?)?;
+        log::info!("已将设备设置写入数据库（版本 {revision}）");
+        seed.clone()
+    } else {
+        let mut settings = settings_from_fields(fields);
+        copy_device_fields(seed, &mut settings);
+        settings
+    };
+    // 目录覆盖与当前供应商以本设备的行为准
+    if let Err(e) = DeviceService::sync_settings(db, &mut settings) {
+        log::warn!("同步当前设备配置失败: {e}");
+    }
+    crate::policy::current().apply(&mut settings);
+    SETTINGS_REVISION.store(revision, Ordering::SeqCst);
+    Ok(settings)
+}
+
+/// 其他进程修改了设置（含本设备的目录覆盖与当前供应商）时重新加载
+///
+/// 返回缓存的更新代数，每次缓存被替换都会变化；未接入数据库时为 `None`。
+pub fn sync_from_database() -> Result<Option<u64>, AppError> {
+    let Some(db) = DEVICE_DB.get() else {
+        return Ok(None);
+    };
+    if db.device_settings_revision()? != SETTINGS_REVISION.load(Ordering::SeqCst)
+        || DeviceService::is_stale(db, &get_settings())?
+    {
+        reload_settings()?;
+    }
+    Ok(Some(SETTINGS_GENERATION.load(Ordering::SeqCst)))
+}
+
+/// 解析目录覆盖路径
+///
+/// 支持按平台给出备选（`macos=...|windows=...|linux=...`，无前缀的一项作为默认），
+/// 以及 `${HOME}`、`${DOCUMENTS}`、`${CONFIG}`（系统应用配置目录，Windows 为 `%APPDATA%`）
+/// 和同名环境变量；数据库在不同系统的设备间同步时，同一设置可在各设备上解析为本机路径。
 fn resolve_override_path(raw: &str) -> PathBuf {
+    let expanded = expand_path_variables(select_platform_alternative(raw));
+    let raw = expanded.as_str();
     if raw == "~" {
         if let Some(home) = crate::test_utils::home_dir() {
             return home;
@@ -212,29 +788,163 @@ fn resolve_override_path(raw: &str) -> PathBuf {
     PathBuf::from(raw)
 }
 
+/// 平台备选前缀
+const PLATFORM_PREFIXES: [&str; 3] = ["macos=", "windows=", "linux="];
+
+/// 选出当前平台的备选路径：优先匹配平台前缀，否则取第一项无前缀的路径
+fn select_platform_alternative(raw: &str) -> &str {
+    if !raw.contains('|') {
+        return raw;
+    }
+    let current = format!("{}=", std::env::consts::OS);
+    let alternatives: Vec<&str> = raw.split('|').map(str::trim).collect();
+    alternatives
+        .iter()
+        .find_map(|alt| alt.strip_prefix(current.as_str()))
+        .or_else(|| {
+            alternatives.iter().copied().find(|alt| {
+                !PLATFORM_PREFIXES
+                    .iter()
+                    .any(|prefix| alt.starts_with(prefix))
+            })
+        })
+        .unwrap_or(raw)
+}
+
+/// 替换 `${NAME}` 变量；无法解析的变量原样保留
+fn expand_path_variables(raw: &str) -> String {
+    let mut result = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        result.push_str(&rest[..start]);
+        match path_variable(name) {
+            Some(value) => result.push_str(&value),
+            None => {
+                log::warn!("目录覆盖路径中的变量无法解析: ${{{name}}}");
+                result.push_str(&rest[start..start + 3 + len]);
+            }
+        }
+        rest = &rest[start + 3 + len..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn path_variable(name: &str) -> Option<String> {
+    let dir = match name {
+        "HOME" => crate::test_utils::home_dir(),
+        "DOCUMENTS" => dirs::document_dir(),
+        "CONFIG" => dirs::config_dir(),
+        _ => return std::env::var(name).ok().filter(|value| !value.is_empty()),
+    };
+    dir.map(|dir| dir.to_string_lossy().into_owned())
+}
+
 pub fn get_settings() -> AppSettings {
     settings_store().read().expect("读取设置锁失败").clone()
 }
 
+/// 是否开启本地崩溃报告
+///
+/// 供 panic hook 调用：设置尚未加载或锁被占用（可能正是 panic 的线程持有）时视为关闭，避免死锁。
+pub fn crash_reporting_enabled() -> bool {
+    SETTINGS_STORE
+        .get()
+        .and_then(|store| store.try_read().ok())
+        .is_some_and(|settings| settings.crash_reporting)
+}
+
 pub fn update_settings(mut new_settings: AppSettings) -> Result<(), AppError> {
     new_settings.normalize_paths();
-    save_settings_file(&new_settings)?;
+    // 被机器策略锁定的字段始终以策略为准
+    crate::policy::current().apply(&mut new_settings);
+
+    let Some(db) = DEVICE_DB.get() else {
+        save_settings_file(&new_settings)?;
+        crate::i18n::set_language(new_settings.language.as_deref());
+        let mut guard = settings_store().write().expect("写入设置锁失败");
+        *guard = new_settings;
+        SETTINGS_GENERATION.fetch_add(1, Ordering::SeqCst);
+        return Ok(());
+    };
+
+    // 只写入本次变化的字段，再读回合并了其他进程修改的完整设置
+    let changes = if db.device_settings_revision()? == 0 {
+        settings_to_fields(&new_settings)?
+    } else {
+        changed_fields(&get_settings(), &new_settings)?
+    };
+    db.update_device_settings(&changes)?;
+    DeviceService::save_settings(db, &new_settings)?;
+    let merged = load_from_database(db, &new_settings)?;
+
+    // settings.json 保留为镜像，供数据库接入前的启动阶段与旧版本读取
+    if let Err(e) = save_settings_file(&merged) {
+        log::warn!("同步设置镜像文件失败: {e}");
+    }
+    crate::i18n::set_language(merged.language.as_deref());
 
     let mut guard = settings_store().write().expect("写入设置锁失败");
-    *guard = new_settings;
+    *guard = merged;
+    SETTINGS_GENERATION.fetch_add(1, Ordering::SeqCst);
     Ok(())
 }
 
-/// 从文件重新加载设置到内存缓存
-/// 用于导入配置等场景，确保内存缓存与文件同步
+/// 重新加载设置到内存缓存
+/// 用于导入配置、其他进程修改等场景；接入数据库后从数据库读取，否则从文件读取
 pub fn reload_settings() -> Result<(), AppError> {
-    let fresh_settings = AppSettings::load_from_file();
+    let fresh_settings = match DEVICE_DB.get() {
+        Some(db) => load_from_database(db, &get_settings())?,
+        None => AppSettings::load_from_file(),
+    };
+    crate::i18n::set_language(fresh_settings.language.as_deref());
     let mut guard = settings_store().write().expect("写入设置锁失败");
     *guard = fresh_settings;
+    SETTINGS_GENERATION.fetch_add(1, Ordering::SeqCst);
     Ok(())
 }
 
+thread_local! {
+    /// 当前线程上生效的供应商级配置目录（优先于全局覆盖）
+    static PROVIDER_CONFIG_DIR: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+/// 在供应商级配置目录下执行 `f`
+///
+/// 期间各应用的 `get_*_override_dir` 均返回该目录，用于把 live 文件写入/读取
+/// 重定向到供应商 `meta.configDir` 指定的位置；`dir` 为空时沿用全局设置。
+pub fn with_provider_config_dir<T>(dir: Option<&str>, f: impl FnOnce() -> T) -> T {
+    let Some(dir) = dir.map(str::trim).filter(|d| !d.is_empty()) else {
+        return f();
+    };
+    let previous = PROVIDER_CONFIG_DIR.with(|cell| cell.replace(Some(resolve_override_path(dir))));
+    // `f` panic 时也要恢复，避免线程池中的线程残留其他供应商的目录
+    let _restore = RestoreProviderConfigDir(previous);
+    f()
+}
+
+/// 离开作用域时恢复之前的供应商级配置目录
+struct RestoreProviderConfigDir(Option<PathBuf>);
+
+impl Drop for RestoreProviderConfigDir {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        PROVIDER_CONFIG_DIR.with(|cell| *cell.borrow_mut() = previous);
+    }
+}
+
+fn provider_config_dir() -> Option<PathBuf> {
+    PROVIDER_CONFIG_DIR.with(|cell| cell.borrow().clone())
+}
+
 pub fn get_claude_override_dir() -> Option<PathBuf> {
+    if let Some(dir) = provider_config_dir() {
+        return Some(dir);
+    }
     let settings = settings_store().read().ok()?;
     settings
         .claude_config_dir
@@ -243,6 +953,9 @@ pub fn get_claude_override_dir() -> Option<PathBuf> {
 }
 
 pub fn get_codex_override_dir() -> Option<PathBuf> {
+    if let Some(dir) = provider_config_dir() {
+        return Some(dir);
+    }
     let settings = settings_store().read().ok()?;
     settings
         .codex_config_dir
@@ -251,6 +964,9 @@ pub fn get_codex_override_dir() -> Option<PathBuf> {
 }
 
 pub fn get_gemini_override_dir() -> Option<PathBuf> {
+    if let Some(dir) = provider_config_dir() {
+        return Some(dir);
+    }
     let settings = settings_store().read().ok()?;
     settings
         .gemini_config_dir
@@ -259,6 +975,9 @@ pub fn get_gemini_override_dir() -> Option<PathBuf> {
 }
 
 pub fn get_grok_override_dir() -> Option<PathBuf> {
+    if let Some(dir) = provider_config_dir() {
+        return Some(dir);
+    }
     let settings = settings_store().read().ok()?;
     settings
         .grok_config_dir
@@ -267,6 +986,9 @@ pub fn get_grok_override_dir() -> Option<PathBuf> {
 }
 
 pub fn get_qwen_override_dir() -> Option<PathBuf> {
+    if let Some(dir) = provider_config_dir() {
+        return Some(dir);
+    }
     let settings = settings_store().read().ok()?;
     settings
         .qwen_config_dir
@@ -276,9 +998,9 @@ pub fn get_qwen_override_dir() -> Option<PathBuf> {
 
 // ===== 当前供应商管理函数 =====
 
-/// 获取指定应用类型的当前供应商 ID（从本地 settings 读取）
+/// 获取指定应用类型的当前供应商 ID（从设置缓存读取）
 ///
-/// 这是设备级别的设置，不随数据库同步。
+/// 这是设备级别的设置：接入数据库后存于 `devices` 表中本设备的行，各设备互不影响。
 /// 如果本地没有设置，调用者应该 fallback 到数据库的 `is_current` 字段。
 pub fn get_current_provider(app_type: &AppType) -> Option<String> {
     let settings = settings_store().read().ok()?;
@@ -291,9 +1013,9 @@ pub fn get_current_provider(app_type: &AppType) -> Option<String> {
     }
 }
 
-/// 设置指定应用类型的当前供应商 ID（保存到本地 settings）
+/// 设置指定应用类型的当前供应商 ID（保存到本设备的配置）
 ///
-/// 这是设备级别的设置，不随数据库同步。
+/// 这是设备级别的设置，只影响当前设备。
 /// 传入 `None` 会清除当前供应商设置。
 pub fn set_current_provider(app_type: &AppType, id: Option<&str>) -> Result<(), AppError> {
     let mut settings = get_settings();
@@ -343,3 +1065,53 @@ pub fn get_effective_current_provider(
     // Fallback 到数据库的 is_current
     db.get_current_provider(app_type.as_str())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn platform_alternative_prefers_current_os_then_default() {
+        let current = format!("{}=/current", std::env::consts::OS);
+        assert_eq!(
+            select_platform_alternative(&format!("/default|{current}")),
+            "/current"
+        );
+        let other = PLATFORM_PREFIXES
+            .iter()
+            .find(|prefix| !prefix.starts_with(std::env::consts::OS))
+            .unwrap();
+        assert_eq!(
+            select_platform_alternative(&format!("{other}/other|/default")),
+            "/default"
+        );
+        assert_eq!(select_platform_alternative("/plain"), "/plain");
+    }
+
+    #[test]
+    fn provider_config_dir_is_restored_after_panic() {
+        let result = std::panic::catch_unwind(|| {
+            with_provider_config_dir(Some("/tmp/cc-switch-provider"), || {
+                assert_eq!(
+                    provider_config_dir(),
+                    Some(PathBuf::from("/tmp/cc-switch-provider"))
+                );
+                panic!("live write failed");
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(provider_config_dir(), None);
+    }
+
+    #[test]
+    fn unknown_path_variables_are_kept() {
+        assert_eq!(
+            expand_path_variables("/data/${CC_SWITCH_UNSET_TEST_VAR}/claude"),
+            "/data/${CC_SWITCH_UNSET_TEST_VAR}/claude"
+        );
+        assert_eq!(
+            expand_path_variables("/data/${unclosed"),
+            "/data/${unclosed"
+        );
+    }
+}
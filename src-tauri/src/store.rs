@@ -1,4 +1,5 @@
 use crate::database::Database;
+use crate::error::AppError;
 use std::sync::Arc;
 
 /// 全局应用状态
@@ -11,4 +12,18 @@ impl AppState {
     pub fn new(db: Arc<Database>) -> Self {
         Self { db }
     }
+
+    /// 在阻塞线程池中执行操作（供 async 命令使用，避免阻塞运行时）
+    ///
+    /// 每次调用独立占用一个阻塞线程，互不排队；数据库访问由连接锁串行化
+    pub async fn run<T, F>(&self, f: F) -> Result<T, AppError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&AppState) -> Result<T, AppError> + Send + 'static,
+    {
+        let state = AppState::new(self.db.clone());
+        tauri::async_runtime::spawn_blocking(move || f(&state))
+            .await
+            .map_err(|e| AppError::Message(format!("后台任务执行失败: {e}")))?
+    }
 }
@@ -7,13 +7,22 @@ use tauri::{Emitter, Manager};
 
 use crate::app_config::AppType;
 use crate::error::AppError;
+use crate::notifications::{self, NotificationCategory};
+use crate::services::{
+    CredentialCopyService, CredentialField, McpProcessState, McpProcessStatus, McpSupervisorService,
+};
 use crate::store::AppState;
 
 /// 托盘菜单文本（国际化）
 #[derive(Clone, Copy)]
 pub struct TrayTexts {
     pub show_main: &'static str,
+    pub active_project: &'static str,
     pub no_provider_hint: &'static str,
+    pub mcp_processes: &'static str,
+    pub copy_base_url: &'static str,
+    pub copy_api_key_masked: &'static str,
+    pub copy_api_key: &'static str,
     pub quit: &'static str,
 }
 
@@ -22,24 +31,45 @@ impl TrayTexts {
         match language {
             "en" => Self {
                 show_main: "Open main window",
+                active_project: "Project: ",
                 no_provider_hint: "  (No providers yet, please add them from the main window)",
+                mcp_processes: "MCP processes",
+                copy_base_url: "Copy base URL",
+                copy_api_key_masked: "Copy API key (masked)",
+                copy_api_key: "Copy API key",
                 quit: "Quit",
             },
             "ja" => Self {
                 show_main: "メインウィンドウを開く",
+                active_project: "プロジェクト: ",
                 no_provider_hint:
                     "  (プロバイダーがまだありません。メイン画面から追加してください)",
+                mcp_processes: "MCP プロセス",
+                copy_base_url: "ベース URL をコピー",
+                copy_api_key_masked: "API キーをコピー（マスク）",
+                copy_api_key: "API キーをコピー",
                 quit: "終了",
             },
             _ => Self {
                 show_main: "打开主界面",
+                active_project: "当前项目：",
                 no_provider_hint: "  (无供应商，请在主界面添加)",
+                mcp_processes: "MCP 进程",
+                copy_base_url: "复制请求地址",
+                copy_api_key_masked: "复制 API Key（脱敏）",
+                copy_api_key: "复制 API Key",
                 quit: "退出",
             },
         }
     }
 }
 
+/// 守护进程菜单项 ID 前缀
+const MCP_PROCESS_PREFIX: &str = "mcp_process_";
+
+/// 复制凭据菜单项 ID 前缀（完整 ID 形如 `copy_credential_api_key:claude`）
+const COPY_CREDENTIAL_PREFIX: &str = "copy_credential_";
+
 /// 托盘应用分区配置
 pub struct TrayAppSection {
     pub app_type: AppType,
@@ -93,6 +123,7 @@ fn create_provider_submenu<'a>(
     manager: Option<&crate::provider::ProviderManager>,
     section: &TrayAppSection,
     tray_texts: &TrayTexts,
+    credentials: Option<&(String, String)>,
 ) -> Result<Submenu<tauri::Wry>, AppError> {
     let mut submenu_builder = SubmenuBuilder::new(app, section.header_label);
 
@@ -128,6 +159,11 @@ fn create_provider_submenu<'a>(
 
     let mut sorted_providers: Vec<_> = manager.providers.iter().collect();
     sorted_providers.sort_by(|(_, a), (_, b)| {
+        // 置顶的供应商排在最前
+        if a.is_pinned != b.is_pinned {
+            return b.is_pinned.cmp(&a.is_pinned);
+        }
+
         match (a.sort_index, b.sort_index) {
             (Some(idx_a), Some(idx_b)) => return idx_a.cmp(&idx_b),
             (Some(_), None) => return std::cmp::Ordering::Less,
@@ -159,11 +195,88 @@ fn create_provider_submenu<'a>(
         submenu_builder = submenu_builder.item(&item);
     }
 
+    // 当前供应商的凭据复制（缺少对应字段时置灰）
+    if let Some((api_key, base_url)) = credentials {
+        submenu_builder = submenu_builder.separator();
+        for (field, label, enabled) in [
+            (
+                CredentialField::BaseUrl,
+                tray_texts.copy_base_url,
+                !base_url.is_empty(),
+            ),
+            (
+                CredentialField::MaskedApiKey,
+                tray_texts.copy_api_key_masked,
+                !api_key.is_empty(),
+            ),
+            (
+                CredentialField::ApiKey,
+                tray_texts.copy_api_key,
+                !api_key.is_empty(),
+            ),
+        ] {
+            let item = MenuItem::with_id(
+                app,
+                format!(
+                    "{COPY_CREDENTIAL_PREFIX}{}:{}",
+                    field.as_str(),
+                    section.app_type.as_str()
+                ),
+                label,
+                enabled,
+                None::<&str>,
+            )
+            .map_err(|e| AppError::Message(format!("创建{}复制菜单失败: {e}", section.log_name)))?;
+            submenu_builder = submenu_builder.item(&item);
+        }
+    }
+
     submenu_builder
         .build()
         .map_err(|e| AppError::Message(format!("构建{}子菜单失败: {e}", section.log_name)))
 }
 
+/// 处理复制凭据托盘事件
+fn handle_copy_credential_tray_event(app: &tauri::AppHandle, event_id: &str) -> bool {
+    let Some((field, app_type)) = event_id
+        .strip_prefix(COPY_CREDENTIAL_PREFIX)
+        .and_then(|rest| rest.split_once(':'))
+    else {
+        return false;
+    };
+    let (Some(field), Ok(app_type)) = (CredentialField::parse(field), app_type.parse::<AppType>())
+    else {
+        return false;
+    };
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(app_state) = app_handle.try_state::<AppState>() else {
+            return;
+        };
+        let confirm = crate::settings::get_settings().confirm_credential_copy;
+        match CredentialCopyService::copy(
+            &app_handle,
+            app_state.inner(),
+            &app_type,
+            None,
+            field,
+            confirm,
+        ) {
+            Ok(Some(copied)) => notifications::notify(
+                NotificationCategory::CredentialCopied,
+                &[
+                    ("app", app_type.as_str().to_string()),
+                    ("provider", copied.provider_name),
+                    ("preview", copied.preview),
+                ],
+            ),
+            Ok(None) => {}
+            Err(e) => log::warn!("复制{}凭据失败: {e}", app_type.as_str()),
+        }
+    });
+    true
+}
+
 /// 处理供应商托盘事件
 pub fn handle_provider_tray_event(app: &tauri::AppHandle, event_id: &str) -> bool {
     for section in TRAY_SECTIONS.iter() {
@@ -173,8 +286,25 @@ pub fn handle_provider_tray_event(app: &tauri::AppHandle, event_id: &str) -> boo
             let provider_id = provider_id.to_string();
             let app_type = section.app_type.clone();
             tauri::async_runtime::spawn_blocking(move || {
-                if let Err(e) = switch_provider_internal(&app_handle, app_type, provider_id) {
-                    log::error!("切换{}供应商失败: {e}", section.log_name);
+                let provider_name = provider_display_name(&app_handle, &app_type, &provider_id);
+                match switch_provider_internal(&app_handle, app_type, provider_id) {
+                    Ok(()) => notifications::notify(
+                        NotificationCategory::SwitchSuccess,
+                        &[
+                            ("app", section.header_label.to_string()),
+                            ("provider", provider_name),
+                        ],
+                    ),
+                    Err(e) => {
+                        log::error!("切换{}供应商失败: {e}", section.log_name);
+                        notifications::notify(
+                            NotificationCategory::SwitchFailure,
+                            &[
+                                ("app", section.header_label.to_string()),
+                                ("e", e.to_string()),
+                            ],
+                        );
+                    }
                 }
             });
             return true;
@@ -183,6 +313,73 @@ pub fn handle_provider_tray_event(app: &tauri::AppHandle, event_id: &str) -> boo
     false
 }
 
+/// 供应商名称（用于通知），查不到时返回 ID
+fn provider_display_name(app: &tauri::AppHandle, app_type: &AppType, provider_id: &str) -> String {
+    app.try_state::<AppState>()
+        .and_then(|state| state.db.get_all_providers(app_type.as_str()).ok())
+        .and_then(|providers| providers.get(provider_id).map(|p| p.name.clone()))
+        .unwrap_or_else(|| provider_id.to_string())
+}
+
+fn create_mcp_process_submenu(
+    app: &tauri::AppHandle,
+    processes: &[McpProcessStatus],
+    tray_texts: &TrayTexts,
+) -> Result<Submenu<tauri::Wry>, AppError> {
+    let mut submenu_builder = SubmenuBuilder::new(app, tray_texts.mcp_processes);
+    for process in processes {
+        let label = match process.state {
+            McpProcessState::Restarting => format!("{} …", process.name),
+            McpProcessState::Failed => format!("{} ⚠", process.name),
+            _ => process.name.clone(),
+        };
+        let item = CheckMenuItem::with_id(
+            app,
+            format!("{MCP_PROCESS_PREFIX}{}", process.server_id),
+            label,
+            true,
+            process.state == McpProcessState::Running,
+            None::<&str>,
+        )
+        .map_err(|e| AppError::Message(format!("创建 MCP 进程菜单项失败: {e}")))?;
+        submenu_builder = submenu_builder.item(&item);
+    }
+    submenu_builder
+        .build()
+        .map_err(|e| AppError::Message(format!("构建 MCP 进程子菜单失败: {e}")))
+}
+
+/// 处理守护进程托盘事件：运行中则停止，否则启动
+fn handle_mcp_process_tray_event(app: &tauri::AppHandle, event_id: &str) -> bool {
+    let Some(server_id) = event_id.strip_prefix(MCP_PROCESS_PREFIX) else {
+        return false;
+    };
+    let app_handle = app.clone();
+    let server_id = server_id.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(app_state) = app_handle.try_state::<AppState>() else {
+            return;
+        };
+        let running = McpSupervisorService::status(&app_state.db)
+            .map(|processes| {
+                processes.iter().any(|process| {
+                    process.server_id == server_id && process.state == McpProcessState::Running
+                })
+            })
+            .unwrap_or(false);
+        if running {
+            McpSupervisorService::stop(&server_id);
+        } else if let Err(e) = McpSupervisorService::start(&app_state.db, &server_id) {
+            log::error!("启动 MCP 进程 {server_id} 失败: {e}");
+        }
+        refresh_tray(&app_handle, app_state.inner());
+        if let Err(e) = app_handle.emit("mcp-process-changed", ()) {
+            log::warn!("发射 MCP 进程状态事件失败: {e}");
+        }
+    });
+    true
+}
+
 /// 创建动态托盘菜单
 pub fn create_tray_menu(
     app: &tauri::AppHandle,
@@ -197,7 +394,20 @@ pub fn create_tray_menu(
     let show_main_item =
         MenuItem::with_id(app, "show_main", tray_texts.show_main, true, None::<&str>)
             .map_err(|e| AppError::Message(format!("创建打开主界面菜单失败: {e}")))?;
-    menu_builder = menu_builder.item(&show_main_item).separator();
+    menu_builder = menu_builder.item(&show_main_item);
+
+    // 当前项目（仅展示，不可点击）
+    match crate::services::ProjectService::active(app_state) {
+        Ok(Some(project)) => {
+            let label = format!("{}{}", tray_texts.active_project, project.name);
+            let project_item = MenuItem::with_id(app, "active_project", label, false, None::<&str>)
+                .map_err(|e| AppError::Message(format!("创建当前项目菜单失败: {e}")))?;
+            menu_builder = menu_builder.item(&project_item);
+        }
+        Ok(None) => {}
+        Err(e) => log::warn!("读取当前项目失败: {e}"),
+    }
+    menu_builder = menu_builder.separator();
 
     // 为每个应用创建子菜单
     for section in TRAY_SECTIONS.iter() {
@@ -214,10 +424,37 @@ pub fn create_tray_menu(
             current: current_id,
         };
 
-        let submenu = create_provider_submenu(app, Some(&manager), section, &tray_texts)?;
+        let credentials = if manager.current.is_empty() {
+            None
+        } else {
+            crate::services::ProviderService::credentials(
+                app_state,
+                &section.app_type,
+                &manager.current,
+            )
+            .ok()
+        };
+
+        let submenu = create_provider_submenu(
+            app,
+            Some(&manager),
+            section,
+            &tray_texts,
+            credentials.as_ref(),
+        )?;
         menu_builder = menu_builder.item(&submenu);
     }
 
+    // 守护的 MCP 进程（勾选表示运行中，点击启动或停止）
+    match McpSupervisorService::status(&app_state.db) {
+        Ok(processes) if !processes.is_empty() => {
+            let submenu = create_mcp_process_submenu(app, &processes, &tray_texts)?;
+            menu_builder = menu_builder.separator().item(&submenu);
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("读取 MCP 进程状态失败: {e}"),
+    }
+
     // 分隔符和退出菜单
     let quit_item = MenuItem::with_id(app, "quit", tray_texts.quit, true, None::<&str>)
         .map_err(|e| AppError::Message(format!("创建退出菜单失败: {e}")))?;
@@ -273,7 +510,10 @@ pub fn handle_tray_menu_event(app: &tauri::AppHandle, event_id: &str) {
             app.exit(0);
         }
         _ => {
-            if handle_provider_tray_event(app, event_id) {
+            if handle_copy_credential_tray_event(app, event_id)
+                || handle_provider_tray_event(app, event_id)
+                || handle_mcp_process_tray_event(app, event_id)
+            {
                 return;
             }
             log::warn!("未处理的菜单事件: {event_id}");
@@ -281,6 +521,18 @@ pub fn handle_tray_menu_event(app: &tauri::AppHandle, event_id: &str) {
     }
 }
 
+/// 重新创建托盘菜单并刷新图标（供应商或项目变化后调用）
+pub fn refresh_tray(app: &tauri::AppHandle, app_state: &AppState) {
+    if let Ok(new_menu) = create_tray_menu(app, app_state) {
+        if let Some(tray) = app.tray_by_id("main") {
+            if let Err(e) = tray.set_menu(Some(new_menu)) {
+                log::error!("更新托盘菜单失败: {e}");
+            }
+        }
+    }
+    crate::tray_icon::refresh(app, app_state);
+}
+
 /// 内部切换供应商函数
 pub fn switch_provider_internal(
     app: &tauri::AppHandle,
@@ -290,24 +542,16 @@ pub fn switch_provider_internal(
     if let Some(app_state) = app.try_state::<AppState>() {
         // 在使用前先保存需要的值
         let app_type_str = app_type.as_str().to_string();
-        let provider_id_clone = provider_id.clone();
 
-        crate::commands::switch_provider(app_state.clone(), app_type_str.clone(), provider_id)
-            .map_err(AppError::Message)?;
+        crate::services::ProviderService::switch(app_state.inner(), app_type, &provider_id)?;
 
         // 切换成功后重新创建托盘菜单
-        if let Ok(new_menu) = create_tray_menu(app, app_state.inner()) {
-            if let Some(tray) = app.tray_by_id("main") {
-                if let Err(e) = tray.set_menu(Some(new_menu)) {
-                    log::error!("更新托盘菜单失败: {e}");
-                }
-            }
-        }
+        refresh_tray(app, app_state.inner());
 
         // 发射事件到前端，通知供应商已切换
         let event_data = serde_json::json!({
             "appType": app_type_str,
-            "providerId": provider_id_clone
+            "providerId": provider_id
         });
         if let Err(e) = app.emit("provider-switched", event_data) {
             log::error!("发射供应商切换事件失败: {e}");
@@ -0,0 +1,367 @@
+//! 动态托盘图标
+//!
+//! 在应用图标右下角叠加当前 Claude 供应商的彩色徽标（颜色取自供应商 `icon_color`，
+//! 并绘制名称首字母），每次切换后重新生成，无需打开菜单即可分辨当前供应商。
+//!
+//! 默认使用应用图标的单色剪影：macOS 上作为模板图标交由系统按菜单栏外观着色，
+//! 其他平台按系统主题选择深色或浅色，主题变化时重新生成。
+
+use tauri::image::Image;
+use tauri::{Manager, Theme};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::settings::TrayIconStyle;
+use crate::store::AppState;
+
+/// 未设置 `icon_color` 时按供应商 ID 从中挑选徽标颜色
+const FALLBACK_COLORS: [[u8; 3]; 6] = [
+    [0xD4, 0x91, 0x5D],
+    [0x1E, 0x88, 0xE5],
+    [0x43, 0xA0, 0x47],
+    [0x8E, 0x24, 0xAA],
+    [0xE5, 0x39, 0x35],
+    [0x00, 0x89, 0x7B],
+];
+
+/// 徽标半径占图标边长的比例
+const BADGE_RADIUS_RATIO: f32 = 0.3;
+
+/// 浅色任务栏/菜单栏上的剪影颜色
+const MONO_ON_LIGHT: [u8; 3] = [0x1F, 0x1F, 0x1F];
+/// 深色任务栏/菜单栏上的剪影颜色
+const MONO_ON_DARK: [u8; 3] = [0xFF, 0xFF, 0xFF];
+
+/// 5x7 点阵字形（每行低 5 位有效，高位在左），覆盖 A-Z 与 0-9
+const GLYPHS: [(char, [u8; 7]); 36] = [
+    ('A', [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11]),
+    ('B', [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E]),
+    ('C', [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E]),
+    ('D', [0x1E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1E]),
+    ('E', [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F]),
+    ('F', [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10]),
+    ('G', [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F]),
+    ('H', [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11]),
+    ('I', [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E]),
+    ('J', [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C]),
+    ('K', [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11]),
+    ('L', [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F]),
+    ('M', [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11]),
+    ('N', [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11]),
+    ('O', [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E]),
+    ('P', [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10]),
+    ('Q', [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D]),
+    ('R', [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11]),
+    ('S', [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E]),
+    ('T', [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04]),
+    ('U', [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E]),
+    ('V', [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04]),
+    ('W', [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A]),
+    ('X', [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11]),
+    ('Y', [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04]),
+    ('Z', [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F]),
+    ('0', [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E]),
+    ('1', [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E]),
+    ('2', [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F]),
+    ('3', [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E]),
+    ('4', [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02]),
+    ('5', [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E]),
+    ('6', [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E]),
+    ('7', [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08]),
+    ('8', [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E]),
+    ('9', [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C]),
+];
+
+/// 托盘徽标
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrayBadge {
+    pub color: [u8; 3],
+    /// 供应商名称首字母（非 ASCII 字母数字时不绘制）
+    pub initial: Option<char>,
+}
+
+/// 托盘底图
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BaseVariant {
+    /// 应用彩色图标
+    Color,
+    /// macOS 模板图标（黑色剪影，由系统按菜单栏外观着色）
+    Template,
+    /// 指定颜色的单色剪影
+    Mono([u8; 3]),
+}
+
+/// 按样式、系统主题与是否叠加徽标选择底图
+fn select_variant(style: TrayIconStyle, theme: Theme, badged: bool) -> BaseVariant {
+    if style == TrayIconStyle::Color {
+        return BaseVariant::Color;
+    }
+    // 模板图标会被系统整体着色，叠加彩色徽标时改用按主题着色的剪影
+    if cfg!(target_os = "macos") && !badged {
+        return BaseVariant::Template;
+    }
+    match theme {
+        Theme::Dark => BaseVariant::Mono(MONO_ON_DARK),
+        _ => BaseVariant::Mono(MONO_ON_LIGHT),
+    }
+}
+
+/// 托盘所在区域（任务栏/菜单栏）的主题
+fn tray_theme(app: &tauri::AppHandle) -> Theme {
+    // Windows 任务栏的明暗与应用主题分开设置
+    #[cfg(target_os = "windows")]
+    {
+        use winreg::enums::HKEY_CURRENT_USER;
+        use winreg::RegKey;
+
+        let light: Option<u32> = RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize")
+            .and_then(|key| key.get_value("SystemUsesLightTheme"))
+            .ok();
+        if let Some(light) = light {
+            return if light == 0 {
+                Theme::Dark
+            } else {
+                Theme::Light
+            };
+        }
+    }
+
+    app.get_webview_window("main")
+        .and_then(|window| window.theme().ok())
+        .unwrap_or(Theme::Light)
+}
+
+/// 按当前 Claude 供应商与系统主题重新生成托盘图标（关闭动态图标时不叠加徽标）
+pub fn refresh(app: &tauri::AppHandle, app_state: &AppState) {
+    let Some(tray) = app.tray_by_id("main") else {
+        return;
+    };
+    let Some(base) = app.default_window_icon() else {
+        log::warn!("Failed to get default window icon for tray");
+        return;
+    };
+
+    let settings = crate::settings::get_settings();
+    let badge = if settings.dynamic_tray_icon {
+        current_badge(app_state).unwrap_or_else(|e| {
+            log::warn!("读取当前供应商失败，托盘使用默认图标: {e}");
+            None
+        })
+    } else {
+        None
+    };
+
+    let variant = select_variant(settings.tray_icon_style, tray_theme(app), badge.is_some());
+    let (width, height) = (base.width(), base.height());
+    let mut pixels = match variant {
+        BaseVariant::Color => base.rgba().to_vec(),
+        BaseVariant::Template => render_monochrome(base.rgba(), [0, 0, 0]),
+        BaseVariant::Mono(color) => render_monochrome(base.rgba(), color),
+    };
+    if let Some(badge) = badge {
+        pixels = render_badged_icon(&pixels, width, height, &badge);
+    }
+
+    if let Err(e) = tray.set_icon(Some(Image::new_owned(pixels, width, height))) {
+        log::warn!("更新托盘图标失败: {e}");
+    }
+    if let Err(e) = tray.set_icon_as_template(variant == BaseVariant::Template) {
+        log::warn!("设置托盘模板图标失败: {e}");
+    }
+}
+
+/// 系统主题变化时按新主题重新生成托盘图标
+pub fn on_theme_changed(app: &tauri::AppHandle) {
+    if let Some(state) = app.try_state::<AppState>() {
+        refresh(app, state.inner());
+    }
+}
+
+/// 当前 Claude 供应商对应的徽标（未设置当前供应商时为 None）
+fn current_badge(app_state: &AppState) -> Result<Option<TrayBadge>, AppError> {
+    let Some(current_id) =
+        crate::settings::get_effective_current_provider(&app_state.db, &AppType::Claude)?
+    else {
+        return Ok(None);
+    };
+    let providers = app_state.db.get_all_providers(AppType::Claude.as_str())?;
+    Ok(providers.get(&current_id).map(|provider| TrayBadge {
+        color: provider
+            .icon_color
+            .as_deref()
+            .and_then(parse_hex_color)
+            .unwrap_or_else(|| fallback_color(&provider.id)),
+        initial: provider
+            .name
+            .chars()
+            .find(|c| c.is_alphanumeric())
+            .map(|c| c.to_ascii_uppercase())
+            .filter(|c| c.is_ascii_alphanumeric()),
+    }))
+}
+
+/// 解析 `#RGB` / `#RRGGBB` 颜色
+fn parse_hex_color(value: &str) -> Option<[u8; 3]> {
+    let hex = value.trim().strip_prefix('#')?;
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    match hex.len() {
+        3 => {
+            let mut rgb = [0u8; 3];
+            for (slot, c) in rgb.iter_mut().zip(hex.chars()) {
+                *slot = c.to_digit(16)? as u8 * 17;
+            }
+            Some(rgb)
+        }
+        6 => Some([
+            channel(hex.get(0..2)?)?,
+            channel(hex.get(2..4)?)?,
+            channel(hex.get(4..6)?)?,
+        ]),
+        _ => None,
+    }
+}
+
+fn fallback_color(provider_id: &str) -> [u8; 3] {
+    let sum = provider_id
+        .bytes()
+        .fold(0usize, |acc, b| acc.wrapping_add(b as usize));
+    FALLBACK_COLORS[sum % FALLBACK_COLORS.len()]
+}
+
+/// 用单一颜色绘制图标剪影（保留原 alpha）
+fn render_monochrome(rgba: &[u8], color: [u8; 3]) -> Vec<u8> {
+    let mut pixels = rgba.to_vec();
+    for pixel in pixels.chunks_exact_mut(4) {
+        pixel[..3].copy_from_slice(&color);
+    }
+    pixels
+}
+
+/// 在图标右下角叠加圆形徽标（输入输出均为非预乘 RGBA）
+pub fn render_badged_icon(rgba: &[u8], width: u32, height: u32, badge: &TrayBadge) -> Vec<u8> {
+    let mut pixels = rgba.to_vec();
+    let radius = width.min(height) as f32 * BADGE_RADIUS_RATIO;
+    let (cx, cy) = (width as f32 - radius, height as f32 - radius);
+
+    // 深色徽标上使用白色字母，浅色徽标上使用黑色字母
+    let [r, g, b] = badge.color;
+    let luminance = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+    let text_color = if luminance > 160.0 {
+        [0, 0, 0]
+    } else {
+        [255, 255, 255]
+    };
+
+    let glyph = badge
+        .initial
+        .and_then(|c| GLYPHS.iter().find(|(g, _)| *g == c))
+        .map(|(_, rows)| rows);
+    // 字形按整数倍放大，高度约为徽标直径的 60%
+    let scale = ((radius * 2.0 * 0.6) / 7.0).floor().max(1.0);
+    let glyph_left = cx - 2.5 * scale;
+    let glyph_top = cy - 3.5 * scale;
+
+    for y in 0..height {
+        for x in 0..width {
+            let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+            let distance = ((px - cx).powi(2) + (py - cy).powi(2)).sqrt();
+            let coverage = (radius + 0.5 - distance).clamp(0.0, 1.0);
+            if coverage <= 0.0 {
+                continue;
+            }
+
+            let mut color = badge.color;
+            if let Some(rows) = glyph {
+                let gx = ((px - glyph_left) / scale).floor();
+                let gy = ((py - glyph_top) / scale).floor();
+                if (0.0..5.0).contains(&gx) && (0.0..7.0).contains(&gy) {
+                    let bit = 4 - gx as u32;
+                    if (rows[gy as usize] >> bit) & 1 == 1 {
+                        color = text_color;
+                    }
+                }
+            }
+
+            let idx = ((y * width + x) * 4) as usize;
+            blend_over(&mut pixels[idx..idx + 4], color, coverage);
+        }
+    }
+    pixels
+}
+
+/// 按 alpha 将纯色叠加到像素上
+fn blend_over(pixel: &mut [u8], color: [u8; 3], alpha: f32) {
+    let dst_alpha = pixel[3] as f32 / 255.0;
+    let out_alpha = alpha + dst_alpha * (1.0 - alpha);
+    if out_alpha <= 0.0 {
+        return;
+    }
+    for (channel, src) in pixel[..3].iter_mut().zip(color) {
+        let blended =
+            (src as f32 * alpha + *channel as f32 * dst_alpha * (1.0 - alpha)) / out_alpha;
+        *channel = blended.round() as u8;
+    }
+    pixel[3] = (out_alpha * 255.0).round() as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hex_color_accepts_short_and_long_forms() {
+        assert_eq!(parse_hex_color("#D4915D"), Some([0xD4, 0x91, 0x5D]));
+        assert_eq!(parse_hex_color("#fff"), Some([255, 255, 255]));
+        assert_eq!(parse_hex_color("red"), None);
+        assert_eq!(parse_hex_color("#12345"), None);
+    }
+
+    #[test]
+    fn variant_follows_style_theme_and_badge() {
+        assert_eq!(
+            select_variant(TrayIconStyle::Color, Theme::Dark, false),
+            BaseVariant::Color
+        );
+        assert_eq!(
+            select_variant(TrayIconStyle::Auto, Theme::Dark, true),
+            BaseVariant::Mono(MONO_ON_DARK)
+        );
+        assert_eq!(
+            select_variant(TrayIconStyle::Auto, Theme::Light, true),
+            BaseVariant::Mono(MONO_ON_LIGHT)
+        );
+        let unbadged = select_variant(TrayIconStyle::Auto, Theme::Dark, false);
+        if cfg!(target_os = "macos") {
+            assert_eq!(unbadged, BaseVariant::Template);
+        } else {
+            assert_eq!(unbadged, BaseVariant::Mono(MONO_ON_DARK));
+        }
+
+        let pixels = render_monochrome(&[10, 20, 30, 128, 0, 0, 0, 0], MONO_ON_DARK);
+        assert_eq!(pixels, vec![255, 255, 255, 128, 255, 255, 255, 0]);
+    }
+
+    #[test]
+    fn badge_is_drawn_in_bottom_right_corner() {
+        let (width, height) = (32, 32);
+        let base = vec![0u8; (width * height * 4) as usize];
+        let badge = TrayBadge {
+            color: [0x1E, 0x88, 0xE5],
+            initial: None,
+        };
+        let pixels = render_badged_icon(&base, width, height, &badge);
+
+        let pixel = |x: u32, y: u32| {
+            let idx = ((y * width + x) * 4) as usize;
+            [
+                pixels[idx],
+                pixels[idx + 1],
+                pixels[idx + 2],
+                pixels[idx + 3],
+            ]
+        };
+        assert_eq!(pixel(0, 0), [0, 0, 0, 0]);
+        assert_eq!(pixel(24, 24), [0x1E, 0x88, 0xE5, 255]);
+    }
+}
@@ -0,0 +1,252 @@
+//! 事件 Webhook
+//!
+//! 把 cc-switch 的关键事件（切换供应商、MCP 同步失败、用量越过预算阈值）以 JSON
+//! POST 到设置中的地址，便于团队看板或 Slack 集成观察。配置了密钥时用 HMAC-SHA256
+//! 对请求体签名，放在 `X-CC-Switch-Signature: sha256=<hex>` 头中。
+//! 发送在后台进行，失败只记录日志，不影响触发事件的操作。
+
+use ring::hmac;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::error::AppError;
+use crate::network::{self, NetworkFeature};
+use crate::settings::WebhookSettings;
+
+const SIGNATURE_HEADER: &str = "X-CC-Switch-Signature";
+const EVENT_HEADER: &str = "X-CC-Switch-Event";
+
+/// Webhook 事件
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookEvent {
+    ProviderSwitched {
+        app: String,
+        provider_id: String,
+        provider_name: String,
+        previous_provider_id: Option<String>,
+    },
+    McpSyncFailed {
+        app: String,
+        server_id: String,
+        error: String,
+    },
+    UsageThreshold {
+        app: String,
+        provider_id: String,
+        provider_name: String,
+        /// `warning` / `exceeded`
+        level: String,
+        ratio: f64,
+    },
+    /// 设置页面的测试事件
+    Test,
+}
+
+impl WebhookEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ProviderSwitched { .. } => "provider.switched",
+            Self::McpSyncFailed { .. } => "mcp.sync_failed",
+            Self::UsageThreshold { .. } => "usage.threshold_crossed",
+            Self::Test => "test",
+        }
+    }
+
+    fn data(&self) -> Value {
+        match self {
+            Self::ProviderSwitched {
+                app,
+                provider_id,
+                provider_name,
+                previous_provider_id,
+            } => json!({
+                "app": app,
+                "providerId": provider_id,
+                "providerName": provider_name,
+                "previousProviderId": previous_provider_id,
+            }),
+            Self::McpSyncFailed {
+                app,
+                server_id,
+                error,
+            } => json!({
+                "app": app,
+                "serverId": server_id,
+                "error": crate::scrub::scrub_secrets(error),
+            }),
+            Self::UsageThreshold {
+                app,
+                provider_id,
+                provider_name,
+                level,
+                ratio,
+            } => json!({
+                "app": app,
+                "providerId": provider_id,
+                "providerName": provider_name,
+                "level": level,
+                "ratio": ratio,
+            }),
+            Self::Test => json!({}),
+        }
+    }
+}
+
+/// 一次发送的结果（测试 Webhook 时返回给界面）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    pub event: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// 设置中已配置且订阅了该事件时返回地址
+fn target_url(settings: &WebhookSettings, event: &str) -> Option<String> {
+    let url = settings.url.as_deref().map(str::trim).unwrap_or_default();
+    if url.is_empty() {
+        return None;
+    }
+    let subscribed = settings.events.is_empty()
+        || settings
+            .events
+            .iter()
+            .any(|name| name.trim().eq_ignore_ascii_case(event));
+    subscribed.then(|| url.to_string())
+}
+
+fn payload(event: &WebhookEvent) -> Value {
+    json!({
+        "event": event.name(),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "source": "cc-switch",
+        "version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "data": event.data(),
+    })
+}
+
+/// HMAC-SHA256 签名（十六进制）
+fn sign(secret: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::sign(&key, body)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// 在后台发送事件（未配置或未订阅时忽略，离线时暂存到恢复联网后发送）
+pub fn emit(event: WebhookEvent) {
+    let settings = crate::settings::get_settings().webhook;
+    let Some(url) = target_url(&settings, event.name()) else {
+        return;
+    };
+    if !network::is_online() {
+        network::defer(format!("webhook: {}", event.name()), move || emit(event));
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let delivery = deliver(&url, settings.secret.as_deref(), &event).await;
+        if !delivery.success {
+            log::warn!(
+                "发送 Webhook 事件 {} 失败: {}",
+                delivery.event,
+                delivery.message.unwrap_or_default()
+            );
+        }
+    });
+}
+
+/// 发送测试事件并等待结果
+pub async fn send_test() -> Result<WebhookDelivery, AppError> {
+    let settings = crate::settings::get_settings().webhook;
+    let url = settings
+        .url
+        .as_deref()
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .ok_or_else(|| AppError::localized("webhook.url_missing"))?;
+    network::ensure_online()?;
+    Ok(deliver(url, settings.secret.as_deref(), &WebhookEvent::Test).await)
+}
+
+async fn deliver(url: &str, secret: Option<&str>, event: &WebhookEvent) -> WebhookDelivery {
+    let mut delivery = WebhookDelivery {
+        event: event.name().to_string(),
+        success: false,
+        http_status: None,
+        message: None,
+    };
+    let body = match serde_json::to_vec(&payload(event)) {
+        Ok(body) => body,
+        Err(e) => {
+            delivery.message = Some(e.to_string());
+            return delivery;
+        }
+    };
+    let client = match network::client_builder(NetworkFeature::Webhook)
+        .user_agent(concat!("cc-switch/", env!("CARGO_PKG_VERSION")))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            delivery.message = Some(e.to_string());
+            return delivery;
+        }
+    };
+
+    let mut request = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, event.name());
+    if let Some(secret) = secret.filter(|secret| !secret.is_empty()) {
+        request = request.header(SIGNATURE_HEADER, format!("sha256={}", sign(secret, &body)));
+    }
+
+    match network::send_with_retry(NetworkFeature::Webhook, request.body(body)).await {
+        Ok(response) => {
+            let status = response.status();
+            delivery.http_status = Some(status.as_u16());
+            delivery.success = status.is_success();
+            if !status.is_success() {
+                delivery.message = Some(format!("HTTP {status}"));
+            }
+        }
+        Err(e) => delivery.message = Some(e.to_string()),
+    }
+    delivery
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_by_url_and_subscribed_events() {
+        let mut settings = WebhookSettings::default();
+        assert!(target_url(&settings, "provider.switched").is_none());
+
+        settings.url = Some(" https://hooks.example.com/cc ".to_string());
+        assert_eq!(
+            target_url(&settings, "mcp.sync_failed").as_deref(),
+            Some("https://hooks.example.com/cc")
+        );
+
+        settings.events = vec!["provider.switched".to_string()];
+        assert!(target_url(&settings, "provider.switched").is_some());
+        assert!(target_url(&settings, "mcp.sync_failed").is_none());
+    }
+
+    #[test]
+    fn signs_body_with_hmac_sha256() {
+        // RFC 4231 测试用例 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -560,7 +560,9 @@ command = "echo"
                 gemini: false,
                 grok: false,
                 qwen: false,
+                claude_desktop: false,
             },
+            app_overrides: Default::default(),
             description: None,
             homepage: None,
             docs: None,
@@ -684,7 +686,9 @@ fn import_from_claude_merges_into_config() {
                 gemini: false,
                 grok: false,
                 qwen: false,
+                claude_desktop: false,
             },
+            app_overrides: Default::default(),
             description: None,
             homepage: None,
             docs: None,
@@ -1024,7 +1028,9 @@ fn sync_grok_enabled_mcp_from_unified_structure() {
                 gemini: false,
                 grok: true, // 启用 Grok
                 qwen: false,
+                claude_desktop: false,
             },
+            app_overrides: Default::default(),
             description: None,
             homepage: None,
             docs: None,
@@ -1047,7 +1053,9 @@ fn sync_grok_enabled_mcp_from_unified_structure() {
                 gemini: false,
                 grok: false, // 未启用 Grok
                 qwen: false,
+                claude_desktop: false,
             },
+            app_overrides: Default::default(),
             description: None,
             homepage: None,
             docs: None,
@@ -216,7 +216,9 @@ fn set_mcp_enabled_for_codex_writes_live_config() {
                 gemini: false,
                 grok: false,
                 qwen: false,
+                claude_desktop: false,
             },
+            app_overrides: Default::default(),
             description: None,
             homepage: None,
             docs: None,
@@ -248,3 +250,61 @@ fn set_mcp_enabled_for_codex_writes_live_config() {
         "codex config should include the enabled server definition"
     );
 }
+
+#[test]
+fn copy_mcp_enablement_from_claude_to_codex_writes_toml() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let codex_dir = home.join(".codex");
+    fs::create_dir_all(&codex_dir).expect("create codex dir");
+    fs::write(codex_dir.join("config.toml"), "").expect("create empty config.toml");
+
+    let mut config = MultiAppConfig::default();
+    config.mcp.servers = Some(HashMap::new());
+    for (id, claude, codex) in [("copy-me", true, false), ("already", true, true)] {
+        config.mcp.servers.as_mut().unwrap().insert(
+            id.into(),
+            McpServer {
+                id: id.to_string(),
+                name: id.to_string(),
+                server: json!({
+                    "type": "stdio",
+                    "command": "echo"
+                }),
+                apps: McpApps {
+                    claude,
+                    codex,
+                    gemini: false,
+                    grok: false,
+                    qwen: false,
+                    claude_desktop: false,
+                },
+                app_overrides: Default::default(),
+                description: None,
+                homepage: None,
+                docs: None,
+                tags: Vec::new(),
+            },
+        );
+    }
+
+    let state = create_test_state_with_config(&config).expect("create test state");
+
+    let result = McpService::copy_enablement(&state, AppType::Claude, AppType::Codex)
+        .expect("copy enablement should succeed");
+    assert_eq!(result.copied, vec!["copy-me".to_string()]);
+    assert_eq!(result.skipped, vec!["already".to_string()]);
+    assert!(result.failed.is_empty());
+
+    let servers = state.db.get_all_mcp_servers().expect("get all mcp servers");
+    assert!(servers.get("copy-me").expect("server exists").apps.codex);
+
+    let toml_text =
+        fs::read_to_string(cc_switch_lib::get_codex_config_path()).expect("read codex config");
+    assert!(
+        toml_text.contains("copy-me"),
+        "codex config should include the copied server"
+    );
+}
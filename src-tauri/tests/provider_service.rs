@@ -90,7 +90,9 @@ command = "say"
                 gemini: false,
                 grok: false,
                 qwen: false,
+                claude_desktop: false,
             },
+            app_overrides: Default::default(),
             description: None,
             homepage: None,
             docs: None,
@@ -2,6 +2,7 @@ import { useEffect, useMemo, useState, useRef, useCallback } from "react";
 import { useTranslation } from "react-i18next";
 import { toast } from "sonner";
 import { invoke } from "@tauri-apps/api/core";
+import { useQueryClient } from "@tanstack/react-query";
 import {
   Plus,
   Settings,
@@ -127,6 +128,27 @@ function App() {
     };
   }, [activeApp, refetch]);
 
+  // 设置被其他进程修改后刷新缓存
+  const queryClient = useQueryClient();
+  useEffect(() => {
+    let unsubscribe: (() => void) | undefined;
+
+    const setupListener = async () => {
+      try {
+        unsubscribe = await settingsApi.onChanged((settings) => {
+          queryClient.setQueryData(["settings"], settings);
+        });
+      } catch (error) {
+        console.error("[App] Failed to subscribe settings change event", error);
+      }
+    };
+
+    setupListener();
+    return () => {
+      unsubscribe?.();
+    };
+  }, [queryClient]);
+
   // 应用启动时检测所有应用的环境变量冲突
   useEffect(() => {
     const checkEnvOnStartup = async () => {
@@ -297,6 +319,18 @@ function App() {
 
   // 复制供应商
   const handleDuplicateProvider = async (provider: Provider) => {
+    // 列表中的 API Key 已脱敏，复制前读取完整配置
+    let settingsConfig: Provider["settingsConfig"];
+    try {
+      settingsConfig = await providersApi.revealCredentials(
+        provider.id,
+        activeApp,
+      );
+    } catch (error) {
+      toast.error(String(error));
+      return;
+    }
+
     // 1️⃣ 计算新的 sortIndex：如果原供应商有 sortIndex，则复制它
     const newSortIndex =
       provider.sortIndex !== undefined ? provider.sortIndex + 1 : undefined;
@@ -304,7 +338,7 @@ function App() {
     const duplicatedProvider: Provider = {
       id: `${provider.id}-copy-${Date.now()}`, // 确保ID唯一
       name: provider.name, // 保持原名称，不加copy后缀
-      settingsConfig: JSON.parse(JSON.stringify(provider.settingsConfig)), // 深拷贝
+      settingsConfig,
       websiteUrl: provider.websiteUrl,
       category: provider.category,
       sortIndex: newSortIndex, // 复制原 sortIndex + 1
@@ -1,6 +1,10 @@
 import { useState, useEffect, useMemo } from "react";
 import { listen } from "@tauri-apps/api/event";
-import { DeepLinkImportRequest, deeplinkApi } from "@/lib/api/deeplink";
+import {
+  DeepLinkImportRequest,
+  ImportResult,
+  deeplinkApi,
+} from "@/lib/api/deeplink";
 import {
   Dialog,
   DialogContent,
@@ -16,12 +20,24 @@ import { useQueryClient } from "@tanstack/react-query";
 import { PromptConfirmation } from "./deeplink/PromptConfirmation";
 import { McpConfirmation } from "./deeplink/McpConfirmation";
 import { ProviderIcon } from "./ProviderIcon";
+import { extractErrorMessage } from "@/utils/errorUtils";
 
 interface DeeplinkError {
   url: string;
   error: string;
 }
 
+interface DeeplinkBlocked {
+  resource: string;
+  name?: string;
+  reason: string;
+}
+
+interface DeeplinkImported {
+  request: DeepLinkImportRequest;
+  result: ImportResult;
+}
+
 export function DeepLinkImportDialog() {
   const { t } = useTranslation();
   const queryClient = useQueryClient();
@@ -65,8 +81,7 @@ export function DeepLinkImportDialog() {
           } catch (error) {
             console.error("Failed to merge config:", error);
             toast.error(t("deeplink.configMergeError"), {
-              description:
-                error instanceof Error ? error.message : String(error),
+              description: extractErrorMessage(error),
             });
             // Fall back to original request
             setRequest(event.payload);
@@ -87,81 +102,71 @@ export function DeepLinkImportDialog() {
       });
     });
 
+    // Links rejected by the import policy
+    const unlistenBlocked = listen<DeeplinkBlocked>(
+      "deeplink-blocked",
+      (event) => {
+        toast.error(t("deeplink.blocked"), {
+          description: event.payload.reason,
+        });
+      },
+    );
+
+    // Signed links imported without confirmation
+    const unlistenImported = listen<DeeplinkImported>(
+      "deeplink-imported",
+      async (event) => {
+        await applyImportResult(event.payload.request, event.payload.result);
+      },
+    );
+
     return () => {
       unlistenImport.then((fn) => fn());
       unlistenError.then((fn) => fn());
+      unlistenBlocked.then((fn) => fn());
+      unlistenImported.then((fn) => fn());
     };
   }, [t]);
 
-  const handleImport = async () => {
-    if (!request) return;
-
-    setIsImporting(true);
+  // 导入完成后刷新对应数据并提示（确认导入与签名链接自动导入共用）
+  const applyImportResult = async (
+    request: DeepLinkImportRequest,
+    result: ImportResult,
+  ) => {
+    const refreshMcp = async (summary: {
+      importedCount: number;
+      importedIds: string[];
+      failed: Array<{ id: string; error: string }>;
+    }) => {
+      // 强制刷新 MCP 相关缓存，确保管理页重新从数据库加载
+      await queryClient.invalidateQueries({
+        queryKey: ["mcp", "all"],
+        refetchType: "all",
+      });
+      await queryClient.refetchQueries({
+        queryKey: ["mcp", "all"],
+        type: "all",
+      });
 
-    try {
-      const result = await deeplinkApi.importFromDeeplink(request);
-      const refreshMcp = async (summary: {
-        importedCount: number;
-        importedIds: string[];
-        failed: Array<{ id: string; error: string }>;
-      }) => {
-        // 强制刷新 MCP 相关缓存，确保管理页重新从数据库加载
-        await queryClient.invalidateQueries({
-          queryKey: ["mcp", "all"],
-          refetchType: "all",
+      if (summary.failed.length > 0) {
+        toast.warning(t("deeplink.mcpPartialSuccess"), {
+          description: t("deeplink.mcpPartialSuccessDescription", {
+            success: summary.importedCount,
+            failed: summary.failed.length,
+          }),
         });
-        await queryClient.refetchQueries({
-          queryKey: ["mcp", "all"],
-          type: "all",
+      } else {
+        toast.success(t("deeplink.mcpImportSuccess"), {
+          description: t("deeplink.mcpImportSuccessDescription", {
+            count: summary.importedCount,
+          }),
         });
+      }
+    };
 
-        if (summary.failed.length > 0) {
-          toast.warning(t("deeplink.mcpPartialSuccess"), {
-            description: t("deeplink.mcpPartialSuccessDescription", {
-              success: summary.importedCount,
-              failed: summary.failed.length,
-            }),
-          });
-        } else {
-          toast.success(t("deeplink.mcpImportSuccess"), {
-            description: t("deeplink.mcpImportSuccessDescription", {
-              count: summary.importedCount,
-            }),
-          });
-        }
-      };
-
-      // Handle different result types
-      if ("type" in result) {
-        if (result.type === "provider") {
-          await queryClient.invalidateQueries({
-            queryKey: ["providers", request.app],
-          });
-          toast.success(t("deeplink.importSuccess"), {
-            description: t("deeplink.importSuccessDescription", {
-              name: request.name,
-            }),
-          });
-        } else if (result.type === "prompt") {
-          // Prompts don't use React Query, trigger a custom event for refresh
-          window.dispatchEvent(
-            new CustomEvent("prompt-imported", {
-              detail: { app: request.app },
-            }),
-          );
-          toast.success(t("deeplink.promptImportSuccess"), {
-            description: t("deeplink.promptImportSuccessDescription", {
-              name: request.name,
-            }),
-          });
-        } else if (result.type === "mcp") {
-          await refreshMcp(result);
-        }
-      } else if (isMcpImportResult(result)) {
-        // 兜底处理：旧版本后端可能未返回 type 字段
-        await refreshMcp(result);
-      } else {
-        // Legacy return type (string ID) - assume provider
+    // Handle different result types
+    if ("type" in result) {
+      if (result.type === "provider") {
         await queryClient.invalidateQueries({
           queryKey: ["providers", request.app],
         });
@@ -170,14 +175,52 @@ export function DeepLinkImportDialog() {
             name: request.name,
           }),
         });
+      } else if (result.type === "prompt") {
+        // Prompts don't use React Query, trigger a custom event for refresh
+        window.dispatchEvent(
+          new CustomEvent("prompt-imported", {
+            detail: { app: request.app },
+          }),
+        );
+        toast.success(t("deeplink.promptImportSuccess"), {
+          description: t("deeplink.promptImportSuccessDescription", {
+            name: request.name,
+          }),
+        });
+      } else if (result.type === "mcp") {
+        await refreshMcp(result);
       }
+    } else if (isMcpImportResult(result)) {
+      // 兜底处理：旧版本后端可能未返回 type 字段
+      await refreshMcp(result);
+    } else {
+      // Legacy return type (string ID) - assume provider
+      await queryClient.invalidateQueries({
+        queryKey: ["providers", request.app],
+      });
+      toast.success(t("deeplink.importSuccess"), {
+        description: t("deeplink.importSuccessDescription", {
+          name: request.name,
+        }),
+      });
+    }
+  };
+
+  const handleImport = async () => {
+    if (!request) return;
+
+    setIsImporting(true);
+
+    try {
+      const result = await deeplinkApi.importFromDeeplink(request);
+      await applyImportResult(request, result);
 
       // Close dialog after all refreshes complete
       setIsOpen(false);
     } catch (error) {
       console.error("Failed to import from deep link:", error);
       toast.error(t("deeplink.importError"), {
-        description: error instanceof Error ? error.message : String(error),
+        description: extractErrorMessage(error),
       });
     } finally {
       setIsImporting(false);
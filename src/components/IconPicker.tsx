@@ -127,7 +127,7 @@ export const IconPicker: React.FC<IconPickerProps> = ({
         const { invoke } = await import('@tauri-apps/api/core');
         
         // 调用 Rust 命令保存图标文件
-        const result = await invoke('save_custom_icon', {
+        const customIconPath = await invoke<string>('save_custom_icon', {
           fileName: fileName,
           fileData: binaryData,
           metadata: {
@@ -139,8 +139,7 @@ export const IconPicker: React.FC<IconPickerProps> = ({
           }
         });
 
-        // 使用自定义图标路径
-        const customIconPath = `custom://${fileName}`;
+        // 使用后端返回的路径（内容相同的图标会复用已有文件）
         onValueChange(customIconPath);
         
         toast.success(t("iconPicker.uploadSuccess", {
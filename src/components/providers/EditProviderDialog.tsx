@@ -1,6 +1,7 @@
-import { useCallback, useMemo } from "react";
+import { useCallback, useEffect, useState } from "react";
 import { useTranslation } from "react-i18next";
 import { Save } from "lucide-react";
+import { toast } from "sonner";
 import { Button } from "@/components/ui/button";
 import { FullScreenPanel } from "@/components/common/FullScreenPanel";
 import type { Provider } from "@/types";
@@ -8,7 +9,7 @@ import {
   ProviderForm,
   type ProviderFormValues,
 } from "@/components/providers/forms/ProviderForm";
-import { type AppId } from "@/lib/api";
+import { providersApi, type AppId } from "@/lib/api";
 
 interface EditProviderDialogProps {
   open: boolean;
@@ -27,11 +28,41 @@ export function EditProviderDialog({
 }: EditProviderDialogProps) {
   const { t } = useTranslation();
 
-  const initialSettingsConfig = useMemo(() => {
-    // Always prefer the saved provider configuration over live settings
-    // This ensures manual edits (like removing mcpServers) are preserved
-    return (provider?.settingsConfig ?? {}) as Record<string, unknown>;
-  }, [provider]);
+  // 列表中的 API Key 已脱敏，打开时读取完整配置；读取失败（已锁定或取消验证）时
+  // 沿用脱敏配置，未修改的脱敏值保存时由后端还原
+  const [initialSettingsConfig, setInitialSettingsConfig] = useState<Record<
+    string,
+    unknown
+  > | null>(null);
+
+  useEffect(() => {
+    if (!open || !provider) {
+      setInitialSettingsConfig(null);
+      return;
+    }
+    let cancelled = false;
+    providersApi
+      .revealCredentials(provider.id, appId)
+      .then((config) => {
+        if (!cancelled) setInitialSettingsConfig(config);
+      })
+      .catch((error) => {
+        if (cancelled) return;
+        toast.warning(
+          t("provider.revealFailed", {
+            defaultValue: "无法读取完整的 API Key，将显示脱敏值",
+          }),
+          { description: String(error) },
+        );
+        // Always prefer the saved provider configuration over live settings
+        setInitialSettingsConfig(
+          (provider.settingsConfig ?? {}) as Record<string, unknown>,
+        );
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [open, provider, appId, t]);
 
   const handleSubmit = useCallback(
     async (values: ProviderFormValues) => {
@@ -63,7 +94,7 @@ export function EditProviderDialog({
     [onSubmit, onOpenChange, provider],
   );
 
-  if (!provider) {
+  if (!provider || !initialSettingsConfig) {
     return null;
   }
 
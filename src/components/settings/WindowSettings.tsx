@@ -26,6 +26,13 @@ export function WindowSettings({ settings, onChange }: WindowSettingsProps) {
         onCheckedChange={(value) => onChange({ launchOnStartup: value })}
       />
 
+      <ToggleRow
+        title={t("settings.startMinimized")}
+        description={t("settings.startMinimizedDescription")}
+        checked={!!settings.startMinimized}
+        onCheckedChange={(value) => onChange({ startMinimized: value })}
+      />
+
       <ToggleRow
         title={t("settings.minimizeToTray")}
         description={t("settings.minimizeToTrayDescription")}
@@ -0,0 +1,22 @@
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { AppId } from "./types";
+
+// 剪贴板识别结果（需在设置中开启剪贴板监听）
+export interface ClipboardDetection {
+  kind: "deepLink" | "apiKey";
+  // ccswitch:// 链接或 API Key 原文
+  value: string;
+  // 脱敏后的展示文本
+  preview: string;
+  app?: AppId;
+}
+
+export const clipboardApi = {
+  async onDetected(
+    handler: (detection: ClipboardDetection) => void,
+  ): Promise<UnlistenFn> {
+    return await listen("clipboard-detected", (event) => {
+      handler(event.payload as ClipboardDetection);
+    });
+  },
+};
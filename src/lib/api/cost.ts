@@ -0,0 +1,43 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { ModelPrice } from "@/types";
+import type { AppId } from "./types";
+
+export interface ProviderCost {
+  appType: AppId;
+  providerId: string;
+  // 供应商已删除时为空
+  providerName?: string;
+  model?: string;
+  tokens: number;
+  // 用量脚本直接报告的费用
+  reportedCost: number;
+  // 按价格表估算的费用
+  estimatedCost: number;
+  totalCost: number;
+  // 找不到模型价格、未计入费用的 token 数
+  unpricedTokens: number;
+}
+
+export interface MonthlyCost {
+  // YYYY-MM
+  month: string;
+  totalCost: number;
+  // 按费用降序
+  providers: ProviderCost[];
+}
+
+export interface CostSummary {
+  currency: string;
+  // 最近的月份在前
+  months: MonthlyCost[];
+}
+
+export const costApi = {
+  async getCostSummary(months?: number): Promise<CostSummary> {
+    return await invoke("get_cost_summary", { months });
+  },
+
+  async getModelPricing(): Promise<ModelPrice[]> {
+    return await invoke("get_model_pricing");
+  },
+};
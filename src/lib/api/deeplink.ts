@@ -1,6 +1,8 @@
 import { invoke } from "@tauri-apps/api/core";
 
-export type ResourceType = "provider" | "prompt" | "mcp";
+export type ResourceType = "provider" | "prompt" | "mcp" | "project" | "action";
+
+export type DeepLinkAction = "switch" | "next" | "show";
 
 export interface DeepLinkImportRequest {
   version: string;
@@ -29,6 +31,13 @@ export interface DeepLinkImportRequest {
   // MCP fields
   apps?: string; // "claude,codex,gemini"
 
+  // Project fields
+  path?: string;
+
+  // Action fields
+  do?: DeepLinkAction;
+  provider?: string; // provider ID or name
+
   // Config file fields
   config?: string;
   configFormat?: string;
@@ -52,8 +61,31 @@ export type ImportResult =
       importedCount: number;
       importedIds: string[];
       failed: Array<{ id: string; error: string }>;
+    }
+  | {
+      type: "project";
+      path: string;
+      switched: string[];
+      failed: Array<{ app: string; providerId: string; error: string }>;
+    }
+  | {
+      type: "action";
+      action: DeepLinkAction;
+      app?: string;
+      providerId?: string;
+      providerName?: string;
     };
 
+export interface DeepLinkDecisionRecord {
+  timestamp: number;
+  source: string;
+  resource: string;
+  name?: string;
+  signed: boolean;
+  decision: "ask" | "autoAccept" | "block";
+  reason?: string;
+}
+
 export const deeplinkApi = {
   /**
    * Parse a deep link URL
@@ -86,4 +118,11 @@ export const deeplinkApi = {
   ): Promise<ImportResult> => {
     return invoke("import_from_deeplink_unified", { request });
   },
+
+  /**
+   * Recent deep link policy decisions (most recent first)
+   */
+  getDecisionLog: async (): Promise<DeepLinkDecisionRecord[]> => {
+    return invoke("get_deeplink_decision_log");
+  },
 };
@@ -0,0 +1,30 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { AppId } from "./types";
+
+// 共享同一数据库（云盘同步）的设备，各自保存目录覆盖与当前供应商
+export interface DeviceInfo {
+  id: string;
+  name: string;
+  hostname: string;
+  configDirs: Partial<Record<AppId, string>>;
+  currentProviders: Partial<Record<AppId, string>>;
+  lastSeenAt: number;
+  updatedAt: number;
+  // 是否为当前设备
+  current: boolean;
+}
+
+export const devicesApi = {
+  async list(): Promise<DeviceInfo[]> {
+    return await invoke("list_devices");
+  },
+
+  async rename(id: string, name: string): Promise<boolean> {
+    return await invoke("rename_device", { id, name });
+  },
+
+  // 不能删除当前设备
+  async remove(id: string): Promise<boolean> {
+    return await invoke("remove_device", { id });
+  },
+};
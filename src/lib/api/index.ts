@@ -1,6 +1,77 @@
-export type { AppId } from "./types";
+export type { AppId, SyncReport } from "./types";
 export { providersApi } from "./providers";
+export { clipboardApi } from "./clipboard";
+export { costApi } from "./cost";
+export { devicesApi } from "./devices";
+export { lintApi } from "./lint";
+export { mcpApi } from "./mcp";
+export { modelsApi } from "./models";
+export { onboardingApi } from "./onboarding";
+export { pluginsApi } from "./plugins";
+export { projectsApi } from "./projects";
 export { settingsApi } from "./settings";
+export { statsApi } from "./stats";
+export { updateApi } from "./update";
 export { vscodeApi } from "./vscode";
 export * as configApi from "./config";
-export type { ProviderSwitchEvent } from "./providers";
+export type { ClipboardDetection } from "./clipboard";
+export type { CostSummary, MonthlyCost, ProviderCost } from "./cost";
+export type { DeviceInfo } from "./devices";
+export type { LintConfig, LintReport, LintRule, LintWarning } from "./lint";
+export type {
+  McpBridgeConfig,
+  McpBridgeTool,
+  McpProcessAction,
+  McpProcessState,
+  McpProcessStatus,
+  McpTransport,
+  SupervisedCommand,
+} from "./mcp";
+export type {
+  InstalledPlugin,
+  PluginInventory,
+  PluginMarketplace,
+  PluginToggleResult,
+} from "./plugins";
+export type {
+  CliOnboardingStatus,
+  GuidedImportResult,
+  ImportSelection,
+  OnboardingState,
+} from "./onboarding";
+export type { Project, ProjectApplyResult } from "./projects";
+export type {
+  CliLaunch,
+  CopiedCredential,
+  CredentialField,
+  FormatMigrationReport,
+  ProviderAttachment,
+  ProviderDeletionImpact,
+  ProviderGroup,
+  ProviderSwitchEvent,
+  ShellEnvImportResult,
+  ShellEnvProvider,
+  SmokeTestResult,
+} from "./providers";
+export type { ProviderUsageStats, StatsRange, SwitchStats } from "./stats";
+export type { UpdateInfo } from "./update";
+export type {
+  AppLockStatus,
+  BiometricStatus,
+  CliVersionInfo,
+  ConfigSnapshotResult,
+  CrashReportEntry,
+  DbLeaseStatus,
+  DiagnosticCheck,
+  DiagnosticReport,
+  EffectiveSettings,
+  ExportOptions,
+  HookRun,
+  MergeChange,
+  MergePreview,
+  MergeResult,
+  NetworkStatus,
+  Policy,
+  ProvisionResult,
+  WebhookDelivery,
+} from "./settings";
@@ -0,0 +1,54 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { AppId } from "./types";
+
+export type LintRule =
+  | "missingModel"
+  | "insecureBaseUrl"
+  | "truncatedApiKey"
+  | "promptTooLong";
+
+export interface LintConfig {
+  disabledRules: LintRule[];
+  // 提示词的 token 上限（按字符数估算）
+  promptMaxTokens: number;
+}
+
+export interface LintWarning {
+  rule: LintRule;
+  severity: "info" | "warning";
+  kind: "provider" | "prompt";
+  app: AppId;
+  targetId: string;
+  targetName: string;
+  // 按当前界面语言生成的说明
+  message: string;
+}
+
+// 保存供应商或提示词后的检查结果；warnings 为空时清除该条目的标记
+export interface LintReport {
+  kind: LintWarning["kind"];
+  app: AppId;
+  targetId: string;
+  warnings: LintWarning[];
+}
+
+export const lintApi = {
+  async lintAll(): Promise<LintWarning[]> {
+    return await invoke("lint_all");
+  },
+
+  async getConfig(): Promise<LintConfig> {
+    return await invoke("get_lint_config");
+  },
+
+  async saveConfig(config: LintConfig): Promise<void> {
+    await invoke("save_lint_config", { config });
+  },
+
+  async onReport(handler: (report: LintReport) => void): Promise<UnlistenFn> {
+    return await listen("lint-warnings", (event) => {
+      handler(event.payload as LintReport);
+    });
+  },
+};
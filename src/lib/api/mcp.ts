@@ -0,0 +1,122 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { McpServer } from "@/types";
+import type { AppId } from "./types";
+
+export interface SupervisedCommand {
+  // 关联的 MCP 服务器 ID
+  serverId: string;
+  command: string;
+  args?: string[];
+  env?: Record<string, string>;
+  cwd?: string;
+  // 应用启动时自动启动
+  autoStart?: boolean;
+}
+
+export type McpProcessState = "stopped" | "running" | "restarting" | "failed";
+
+export interface McpProcessStatus {
+  serverId: string;
+  name: string;
+  state: McpProcessState;
+  pid?: number;
+  startedAt?: number;
+  // 自动重启次数
+  restarts: number;
+  lastError?: string;
+  logPath: string;
+}
+
+export type McpProcessAction = "start" | "stop" | "restart";
+
+export type McpTransport = "stdio" | "http" | "sse";
+
+// 远程（http / sse）服务器的 stdio 桥接工具
+export type McpBridgeTool = "mcpRemote" | "supergateway";
+
+export interface McpBridgeConfig {
+  serverId: string;
+  tool?: McpBridgeTool;
+  // 需要桥接的应用；为空时桥接到不支持该服务器传输类型的应用
+  apps?: AppId[];
+}
+
+// 去抖写入 live 配置失败，涉及服务器的启用状态已回滚
+export interface McpSyncFailure {
+  app: AppId;
+  serverIds: string[];
+  error: string;
+}
+
+export const mcpApi = {
+  async getProcessStatus(): Promise<McpProcessStatus[]> {
+    return await invoke("get_mcp_process_status");
+  },
+
+  async saveSupervisedCommand(command: SupervisedCommand): Promise<void> {
+    await invoke("save_mcp_supervised_command", { command });
+  },
+
+  async removeSupervisedCommand(id: string): Promise<boolean> {
+    return await invoke("remove_mcp_supervised_command", { id });
+  },
+
+  async controlProcess(id: string, action: McpProcessAction): Promise<void> {
+    await invoke("control_mcp_process", { id, action });
+  },
+
+  // 导出单个服务器为可分享的 JSON 文档（默认清空密钥），传入 filePath 时同时写入文件
+  async exportServer(
+    id: string,
+    options?: { filePath?: string; includeSecrets?: boolean },
+  ): Promise<string> {
+    return await invoke("export_mcp_server", {
+      id,
+      filePath: options?.filePath,
+      includeSecrets: options?.includeSecrets,
+    });
+  },
+
+  // 从分享文件导入服务器（默认不启用任何应用）
+  async importServerFile(path: string): Promise<McpServer> {
+    return await invoke("import_mcp_server_file", { path });
+  },
+
+  // 各应用支持的传输类型（键为应用名，含 claudeDesktop）
+  async getTransportMatrix(): Promise<Record<string, McpTransport[]>> {
+    return await invoke("get_mcp_transport_matrix");
+  },
+
+  async getBridges(): Promise<McpBridgeConfig[]> {
+    return await invoke("get_mcp_bridges");
+  },
+
+  async saveBridge(bridge: McpBridgeConfig): Promise<void> {
+    await invoke("save_mcp_bridge", { bridge });
+  },
+
+  async removeBridge(id: string): Promise<boolean> {
+    return await invoke("remove_mcp_bridge", { id });
+  },
+
+  // 预览桥接后的 stdio 条目（不保存）
+  async previewBridge(
+    id: string,
+    tool: McpBridgeTool,
+  ): Promise<Record<string, unknown>> {
+    return await invoke("preview_mcp_bridge", { id, tool });
+  },
+
+  async onProcessChanged(handler: () => void): Promise<UnlistenFn> {
+    return await listen("mcp-process-changed", () => handler());
+  },
+
+  async onSyncFailed(
+    handler: (failure: McpSyncFailure) => void,
+  ): Promise<UnlistenFn> {
+    return await listen("mcp-sync-failed", (event) => {
+      handler(event.payload as McpSyncFailure);
+    });
+  },
+};
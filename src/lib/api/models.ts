@@ -0,0 +1,47 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { AppId } from "./types";
+
+export interface ModelWarning {
+  field: string;
+  model: string;
+  suggestion?: string;
+}
+
+export interface GrokModelsRefresh {
+  providerId: string;
+  models: string[];
+  // 列表获取时间（Unix 秒）
+  fetchedAt: number;
+  // 来自未过期的缓存（未请求 API）
+  fromCache: boolean;
+  // 供应商配置中的模型列表有变化
+  updated: boolean;
+}
+
+export const modelsApi = {
+  async getKnown(appId: AppId): Promise<string[]> {
+    return await invoke("get_known_models", { app: appId });
+  },
+
+  async validate(
+    settingsConfig: Record<string, any>,
+    appId: AppId,
+  ): Promise<ModelWarning[]> {
+    return await invoke("validate_provider_models", {
+      settingsConfig,
+      app: appId,
+    });
+  },
+
+  async listRemote(providerId: string, appId: AppId): Promise<string[]> {
+    return await invoke("list_remote_models", { providerId, app: appId });
+  },
+
+  // 默认刷新当前 Grok 供应商；force 跳过 24 小时缓存
+  async refreshGrok(
+    providerId?: string,
+    force = false,
+  ): Promise<GrokModelsRefresh> {
+    return await invoke("refresh_grok_models", { providerId, force });
+  },
+};
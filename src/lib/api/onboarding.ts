@@ -0,0 +1,59 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { AppId, SyncReport } from "./types";
+
+export type ImportKind = "provider" | "mcp" | "prompt";
+
+export interface ImportRecord {
+  app: AppId;
+  kind: ImportKind;
+  count: number;
+}
+
+// 单个 CLI 的安装与可导入配置
+export interface CliOnboardingStatus {
+  app: AppId;
+  binaryPath?: string | null;
+  configDir: string;
+  configDirExists: boolean;
+  // 找到可执行文件或配置目录
+  installed: boolean;
+  importable: {
+    provider: boolean;
+    mcpServers: number;
+    prompt: boolean;
+  };
+}
+
+export interface OnboardingState {
+  completed: boolean;
+  clis: CliOnboardingStatus[];
+  // 本次启动时自动导入的内容
+  autoImported: ImportRecord[];
+  // 本次启动导入的逐应用结果（含失败项）
+  startupImports: (SyncReport & { kind: ImportKind })[];
+}
+
+export interface ImportSelection {
+  app: AppId;
+  provider?: boolean;
+  mcp?: boolean;
+  prompt?: boolean;
+}
+
+export interface GuidedImportResult {
+  imported: ImportRecord[];
+  failed: { app: AppId; kind: ImportKind; error: string }[];
+}
+
+export const onboardingApi = {
+  async getState(): Promise<OnboardingState> {
+    return await invoke("get_onboarding_state");
+  },
+
+  // 导入向导中勾选的配置，并标记引导已完成
+  async runGuidedImport(
+    selection: ImportSelection[],
+  ): Promise<GuidedImportResult> {
+    return await invoke("run_guided_import", { selection });
+  },
+};
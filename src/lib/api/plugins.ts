@@ -0,0 +1,62 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface InstalledPlugin {
+  // 插件@市场
+  id: string;
+  name: string;
+  marketplace: string;
+  version?: string;
+  installPath?: string;
+  installedAt?: string;
+  enabled: boolean;
+}
+
+export interface PluginMarketplace {
+  name: string;
+  // GitHub 仓库、Git 地址或本地目录
+  source: string;
+  installLocation?: string;
+  lastUpdated?: string;
+  installedCount: number;
+  enabledCount: number;
+}
+
+export interface PluginInventory {
+  pluginsDir: string;
+  marketplaces: PluginMarketplace[];
+  plugins: InstalledPlugin[];
+}
+
+export interface PluginToggleResult {
+  // 状态实际发生变化的插件 ID
+  changed: string[];
+  // 修改前创建的备份目录
+  backup?: string;
+}
+
+export const pluginsApi = {
+  async list(): Promise<PluginInventory> {
+    return await invoke("list_claude_plugins");
+  },
+
+  async setPluginEnabled(
+    pluginId: string,
+    enabled: boolean,
+  ): Promise<PluginToggleResult> {
+    return await invoke("set_claude_plugin_enabled", { pluginId, enabled });
+  },
+
+  async setMarketplaceEnabled(
+    marketplace: string,
+    enabled: boolean,
+  ): Promise<PluginToggleResult> {
+    return await invoke("set_claude_marketplace_enabled", {
+      marketplace,
+      enabled,
+    });
+  },
+
+  async backup(): Promise<string> {
+    return await invoke("backup_claude_plugin_config");
+  },
+};
@@ -0,0 +1,49 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { AppId } from "./types";
+
+export interface Project {
+  path: string;
+  name: string;
+  // 应用 -> 供应商 ID
+  providers: Partial<Record<AppId, string>>;
+  createdAt?: number;
+  updatedAt?: number;
+}
+
+export interface ProjectApplyResult {
+  project: Project;
+  switched: AppId[];
+  unchanged: AppId[];
+  failed: Array<{ app: AppId; providerId: string; error: string }>;
+}
+
+export const projectsApi = {
+  async getAll(): Promise<Record<string, Project>> {
+    return await invoke("get_projects");
+  },
+
+  async upsert(project: Project): Promise<Project> {
+    return await invoke("upsert_project", { project });
+  },
+
+  async delete(path: string): Promise<void> {
+    await invoke("delete_project", { path });
+  },
+
+  async getActive(): Promise<Project | null> {
+    return await invoke("get_active_project");
+  },
+
+  async apply(path: string): Promise<ProjectApplyResult> {
+    return await invoke("apply_project", { path });
+  },
+
+  async onApplied(
+    handler: (result: ProjectApplyResult) => void,
+  ): Promise<UnlistenFn> {
+    return await listen("project-applied", (event) => {
+      handler(event.payload as ProjectApplyResult);
+    });
+  },
+};
@@ -2,6 +2,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type { Provider } from "@/types";
 import type { AppId } from "./types";
+import type { BackupInfo, EnvConflict } from "@/types/env";
 
 export interface ProviderSortUpdate {
   id: string;
@@ -9,11 +10,193 @@ export interface ProviderSortUpdate {
   isPinned?: boolean;
 }
 
+// 按分类分组的供应商（category 为 null 表示未分类）
+export interface ProviderGroup {
+  category: string | null;
+  providers: Provider[];
+}
+
 export interface ProviderSwitchEvent {
   appType: AppId;
   providerId: string;
 }
 
+export type BackfillResolution = "keepMine" | "takeLive" | "merge";
+
+export interface BackfillConflict {
+  appType: AppId;
+  providerId: string;
+  providerName: string;
+  stored: unknown;
+  live: unknown;
+  merged: unknown;
+}
+
+export interface RunningCli {
+  pid: number;
+  name: string;
+  command: string;
+}
+
+export type SwitchOutcome =
+  | { status: "switched"; running?: RunningCli[] }
+  | ({ status: "conflict" } & BackfillConflict)
+  | { status: "cliRunning"; processes: RunningCli[] };
+
+export type KeyCheckStatus =
+  | "valid"
+  | "unauthorized"
+  | "rateLimited"
+  | "unverified"
+  | "httpError"
+  | "networkError"
+  | "offline";
+
+export interface KeyCheckResult {
+  status: KeyCheckStatus;
+  httpStatus?: number;
+  latencyMs?: number;
+  message?: string;
+}
+
+export type CredentialField = "baseUrl" | "maskedApiKey" | "apiKey";
+
+export interface CopiedCredential {
+  field: CredentialField;
+  providerId: string;
+  providerName: string;
+  // 请求地址原文或脱敏后的 Key
+  preview: string;
+}
+
+export interface SmokeTestResult {
+  // 收到 2xx 响应
+  success: boolean;
+  model: string;
+  endpoint: string;
+  httpStatus?: number;
+  latencyMs?: number;
+  // 回复的开头
+  reply?: string;
+  message?: string;
+}
+
+export interface CliLaunch {
+  app: AppId;
+  // 使用的终端
+  terminal: string;
+  // 在终端中执行的命令
+  command: string;
+}
+
+export interface ProviderAttachment {
+  id: number;
+  providerId: string;
+  appType: AppId;
+  fileName: string;
+  mimeType: string;
+  size: number;
+  createdAt: number;
+}
+
+export interface ProviderDraft {
+  provider: Provider;
+  format: "json" | "env" | "toml";
+  apiKey?: string;
+  baseUrl?: string;
+  warnings: string[];
+}
+
+export type OtherTool = "claude-code-router";
+
+// 本机检测到的其他切换工具
+export interface DetectedTool {
+  tool: OtherTool;
+  name: string;
+  configPath: string;
+  providerCount: number;
+}
+
+export interface CodexProfile {
+  name: string;
+  model?: string;
+  modelProvider?: string;
+  approvalPolicy?: string;
+  sandboxMode?: string;
+  modelReasoningEffort?: string;
+}
+
+export interface CodexProfiles {
+  // config.toml 顶层 profile 键
+  active?: string;
+  profiles: CodexProfile[];
+}
+
+export interface CodexProfileImportResult {
+  imported: Array<{ app: AppId; id: string; name: string }>;
+  // 已有绑定供应商的 profile
+  skipped: string[];
+}
+
+export interface OtherToolImportResult {
+  tool: OtherTool;
+  imported: Array<{ app: AppId; id: string; name: string }>;
+  skipped: Array<{
+    name: string;
+    reason: "missingEndpoint" | "missingApiKey" | "duplicate";
+  }>;
+}
+
+// Shell 配置文件中某个应用的供应商环境变量（按文件分组）
+export interface ShellEnvProvider {
+  app: AppId;
+  sourceFile: string;
+  name: string;
+  baseUrl?: string;
+  hasApiKey: boolean;
+  skipReason?: "missingEndpoint" | "missingApiKey" | "duplicate";
+  vars: EnvConflict[];
+}
+
+export interface ShellEnvImportResult {
+  imported: Array<{ app: AppId; id: string; name: string }>;
+  skipped: OtherToolImportResult["skipped"];
+  // 注释掉原有行时的备份，可用 restoreEnvBackup 恢复
+  backup?: BackupInfo;
+}
+
+// 删除供应商前的影响分析（blocked 为 true 时删除会被拒绝）
+export interface ProviderDeletionImpact {
+  app: AppId;
+  providerId: string;
+  providerName: string;
+  currentLocal: boolean;
+  currentInDb: boolean;
+  blocked: boolean;
+  projects: Array<{ path: string; name: string }>;
+  otherDevices: Array<{ id: string; name: string; lastSeenAt: number }>;
+  history: {
+    usageRecords: number;
+    tokens: number;
+    cost: number;
+    lastUsageAt?: number;
+    switchCount: number;
+    lastSwitchedAt?: number;
+  };
+}
+
+// 供应商配置格式迁移结果（CLI 升级后配置键变化）
+export interface FormatMigrationReport {
+  dryRun: boolean;
+  changes: Array<{
+    app: AppId;
+    providerId: string;
+    providerName: string;
+    migrations: string[];
+  }>;
+  migrations: Array<{ id: string; description: string }>;
+}
+
 export const providersApi = {
   async getAll(appId: AppId): Promise<Record<string, Provider>> {
     return await invoke("get_providers", { app: appId });
@@ -35,10 +218,39 @@ export const providersApi = {
     return await invoke("delete_provider", { id, app: appId });
   },
 
+  // 列表接口返回的 API Key 已脱敏，编辑表单通过此接口获取完整配置
+  async revealCredentials(
+    id: string,
+    appId: AppId,
+  ): Promise<Record<string, any>> {
+    return await invoke("reveal_provider_credentials", { id, app: appId });
+  },
+
+  async analyzeDeletion(
+    id: string,
+    appId: AppId,
+  ): Promise<ProviderDeletionImpact> {
+    return await invoke("analyze_provider_deletion", { id, app: appId });
+  },
+
   async switch(id: string, appId: AppId): Promise<boolean> {
     return await invoke("switch_provider", { id, app: appId });
   },
 
+  async switchWithResolution(
+    id: string,
+    appId: AppId,
+    resolution?: BackfillResolution,
+    force?: boolean,
+  ): Promise<SwitchOutcome> {
+    return await invoke("switch_provider_with_resolution", {
+      id,
+      app: appId,
+      resolution,
+      force,
+    });
+  },
+
   async importDefault(appId: AppId): Promise<boolean> {
     return await invoke("import_default_config", { app: appId });
   },
@@ -54,6 +266,32 @@ export const providersApi = {
     return await invoke("update_providers_sort_order", { updates, app: appId });
   },
 
+  async getGrouped(appId: AppId): Promise<ProviderGroup[]> {
+    return await invoke("get_providers_grouped", { app: appId });
+  },
+
+  async getCategoryOrder(appId: AppId): Promise<string[]> {
+    return await invoke("get_category_order", { app: appId });
+  },
+
+  async setCategoryOrder(order: string[], appId: AppId): Promise<string[]> {
+    return await invoke("set_category_order", { order, app: appId });
+  },
+
+  async setCategory(
+    providerId: string,
+    category: string | null,
+    appId: AppId,
+    sortIndex?: number,
+  ): Promise<boolean> {
+    return await invoke("set_provider_category", {
+      providerId,
+      category,
+      sortIndex,
+      app: appId,
+    });
+  },
+
   async updatePinStatus(
     providerId: string,
     isPinned: boolean,
@@ -66,6 +304,170 @@ export const providersApi = {
     });
   },
 
+  async setTags(
+    providerId: string,
+    tags: string[],
+    appId: AppId,
+  ): Promise<string[]> {
+    return await invoke("set_provider_tags", {
+      providerId,
+      tags,
+      app: appId,
+    });
+  },
+
+  async getByTags(
+    tags: string[],
+    appId: AppId,
+    matchAll = false,
+  ): Promise<Record<string, Provider>> {
+    return await invoke("get_providers_by_tags", {
+      tags,
+      matchAll,
+      app: appId,
+    });
+  },
+
+  async getTags(appId: AppId): Promise<string[]> {
+    return await invoke("get_provider_tags", { app: appId });
+  },
+
+  async verifyKey(providerId: string, appId: AppId): Promise<KeyCheckResult> {
+    return await invoke("verify_provider_key", { providerId, app: appId });
+  },
+
+  // 不传 providerId 时复制当前供应商；confirm 为 true 时复制 Key 原文前弹出确认框，取消返回 null
+  async copyCredential(
+    appId: AppId,
+    field: CredentialField,
+    options: { providerId?: string; confirm?: boolean } = {},
+  ): Promise<CopiedCredential | null> {
+    return await invoke("copy_provider_credential", {
+      app: appId,
+      field,
+      providerId: options.providerId,
+      confirm: options.confirm,
+    });
+  },
+
+  async smokeTest(
+    providerId: string,
+    appId: AppId,
+    model?: string,
+  ): Promise<SmokeTestResult> {
+    return await invoke("smoke_test_provider", {
+      providerId,
+      app: appId,
+      model,
+    });
+  },
+
+  // 在终端中启动 CLI；传入 providerId 时先切换到该供应商
+  async launchCli(
+    appId: AppId,
+    providerId?: string,
+    cwd?: string,
+  ): Promise<CliLaunch> {
+    return await invoke("launch_cli", { appType: appId, providerId, cwd });
+  },
+
+  async renderNotes(providerId: string, appId: AppId): Promise<string> {
+    return await invoke("render_provider_notes", { providerId, app: appId });
+  },
+
+  async exportNotesHtml(
+    providerId: string,
+    appId: AppId,
+    filePath: string,
+  ): Promise<boolean> {
+    return await invoke("export_provider_notes_html", {
+      providerId,
+      app: appId,
+      filePath,
+    });
+  },
+
+  async addAttachment(
+    providerId: string,
+    appId: AppId,
+    filePath: string,
+  ): Promise<ProviderAttachment> {
+    return await invoke("add_provider_attachment", {
+      providerId,
+      app: appId,
+      filePath,
+    });
+  },
+
+  async getAttachments(
+    providerId: string,
+    appId: AppId,
+  ): Promise<ProviderAttachment[]> {
+    return await invoke("get_provider_attachments", {
+      providerId,
+      app: appId,
+    });
+  },
+
+  async saveAttachment(id: number, filePath: string): Promise<boolean> {
+    return await invoke("save_provider_attachment", { id, filePath });
+  },
+
+  async deleteAttachment(id: number): Promise<boolean> {
+    return await invoke("delete_provider_attachment", { id });
+  },
+
+  async parsePastedConfig(appId: AppId, text: string): Promise<ProviderDraft> {
+    return await invoke("parse_pasted_config", { app: appId, text });
+  },
+
+  async detectOtherTools(): Promise<DetectedTool[]> {
+    return await invoke("detect_other_tools");
+  },
+
+  async importFromOtherTool(
+    tool: OtherTool,
+    path?: string,
+  ): Promise<OtherToolImportResult> {
+    return await invoke("import_from_other_tool", { tool, path });
+  },
+
+  async scanShellEnv(): Promise<ShellEnvProvider[]> {
+    return await invoke("scan_shell_env_providers");
+  },
+
+  // commentOut 为 true 时注释掉已导入的行（自动备份）
+  async importShellEnv(
+    selections: Array<{ app: AppId; sourceFile: string }>,
+    commentOut = false,
+  ): Promise<ShellEnvImportResult> {
+    return await invoke("import_shell_env_providers", {
+      selections,
+      commentOut,
+    });
+  },
+
+  // 未指定 appId 时迁移所有应用；dryRun 只预览不保存
+  async runFormatMigrations(
+    appId?: AppId,
+    dryRun = false,
+  ): Promise<FormatMigrationReport> {
+    return await invoke("run_format_migrations", { app: appId, dryRun });
+  },
+
+  async getCodexProfiles(): Promise<CodexProfiles> {
+    return await invoke("get_codex_profiles");
+  },
+
+  // 只改写 config.toml 的 profile 键；不传 profile 时清除选择
+  async switchCodexProfile(profile?: string): Promise<CodexProfiles> {
+    return await invoke("switch_codex_profile", { profile });
+  },
+
+  async importCodexProfiles(): Promise<CodexProfileImportResult> {
+    return await invoke("import_codex_profiles");
+  },
+
   async onSwitched(
     handler: (event: ProviderSwitchEvent) => void,
   ): Promise<UnlistenFn> {
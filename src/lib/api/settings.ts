@@ -1,12 +1,184 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { Settings } from "@/types";
-import type { AppId } from "./types";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { Settings, SwitchHook } from "@/types";
+import type { AppId, SyncReport } from "./types";
 
 export interface ConfigTransferResult {
   success: boolean;
   message: string;
   filePath?: string;
   backupId?: string;
+  // 导入后同步 live 配置的逐应用结果
+  syncReports?: SyncReport[];
+}
+
+// 应用锁状态（未设置口令时 unlocked 恒为 true）
+export interface AppLockStatus {
+  enabled: boolean;
+  unlocked: boolean;
+  expiresInSecs?: number;
+}
+
+// 系统身份验证（Touch ID / Windows Hello）支持情况
+export interface BiometricStatus {
+  supported: boolean;
+  available: boolean;
+  enabled: boolean;
+}
+
+// 选择性导出选项（缺省字段按完整导出处理）
+export interface ExportOptions {
+  apps?: AppId[];
+  providers?: boolean;
+  mcp?: boolean;
+  prompts?: boolean;
+  skills?: boolean;
+  settings?: boolean;
+  redactKeys?: boolean;
+}
+
+export interface ConfigSnapshotResult {
+  dir: string;
+  files: string[];
+  // 是否有文件内容发生变化
+  changed: boolean;
+}
+
+export interface MergeChange {
+  kind: "provider" | "mcp" | "prompt";
+  action: "added" | "updated";
+  id: string;
+  appType?: string | null;
+  name?: string | null;
+  changedFields: string[];
+}
+
+export interface MergePreview {
+  fingerprint: string;
+  changes: MergeChange[];
+  added: number;
+  updated: number;
+  unchanged: number;
+}
+
+export interface MergeResult extends ConfigTransferResult {
+  added: number;
+  updated: number;
+}
+
+// 团队模板导入结果（条目 id 形如 "claude:provider-id"、"owner/repo"）
+export interface ProvisionResult {
+  url: string;
+  name?: string;
+  addedProviders: string[];
+  addedMcpServers: string[];
+  addedPrompts: string[];
+  addedSkillRepos: string[];
+  skipped: number;
+  failed: { kind: string; id: string; error: string }[];
+}
+
+// 数据库写入租约状态（readOnly 为 true 表示其他进程正在写入，本实例只读）
+export interface DbLeaseStatus {
+  readOnly: boolean;
+  holderPid?: number | null;
+  heartbeatAt?: number | null;
+}
+
+// 机器策略（MDM）强制的设置
+export interface Policy {
+  disableDeeplinkApiKeys: boolean;
+  lockConfigDirs: boolean;
+  updateChannel?: "stable" | "beta";
+  autoCheckUpdates?: boolean;
+  provisionUrl?: string;
+}
+
+export interface EffectiveSettings {
+  settings: Settings;
+  // 被策略锁定的字段（Settings 的键名）
+  locked: (keyof Settings)[];
+  policySource?: string | null;
+  policy: Policy;
+}
+
+// 启动自检（Doctor）
+export type DiagnosticStatus = "ok" | "skipped" | "warning" | "error";
+
+export interface DiagnosticCheck {
+  id: string;
+  category:
+    | "configDir"
+    | "liveConfig"
+    | "database"
+    | "deepLink"
+    | "tray"
+    | "disk";
+  status: DiagnosticStatus;
+  message: string;
+  path?: string;
+}
+
+export interface DiagnosticReport {
+  generatedAt: number;
+  appVersion: string;
+  os: string;
+  arch: string;
+  overall: DiagnosticStatus;
+  checks: DiagnosticCheck[];
+}
+
+// 已安装 CLI 的版本与兼容性
+export interface CliVersionInfo {
+  app: AppId;
+  binaryPath?: string | null;
+  version?: string | null;
+  // 上次检测到的版本（首次检测为空）
+  previousVersion?: string | null;
+  status: "compatible" | "tooOld" | "untested" | "unknown" | "notInstalled";
+  warnings: string[];
+}
+
+// 本地崩溃报告（panic 报告或命令错误日志）
+export interface CrashReportEntry {
+  fileName: string;
+  kind: "panic" | "commandErrors";
+  // 修改时间（Unix 秒）
+  modifiedAt: number;
+  size: number;
+  summary?: string;
+}
+
+export interface HookRun {
+  name: string;
+  phase: "before" | "after";
+  app: AppId;
+  providerId: string;
+  // 开始时间（Unix 秒）
+  startedAt: number;
+  durationMs: number;
+  success: boolean;
+  exitCode?: number;
+  timedOut: boolean;
+  stdout: string;
+  stderr: string;
+  // 无法启动命令时的错误
+  error?: string;
+}
+
+export interface WebhookDelivery {
+  event: string;
+  success: boolean;
+  httpStatus?: number;
+  message?: string;
+}
+
+export interface NetworkStatus {
+  mode: "auto" | "online" | "offline";
+  online: boolean;
+  lastCheckedAt?: number;
+  // 等待恢复联网后执行的操作
+  pending: string[];
 }
 
 export const settingsApi = {
@@ -14,10 +186,43 @@ export const settingsApi = {
     return await invoke("get_settings");
   },
 
+  async getEffective(): Promise<EffectiveSettings> {
+    return await invoke("get_effective_settings");
+  },
+
+  // 设置被其他窗口、CLI 或进程修改后触发，载荷为最新设置
+  async onChanged(handler: (settings: Settings) => void): Promise<UnlistenFn> {
+    return await listen("settings-changed", (event) => {
+      handler(event.payload as Settings);
+    });
+  },
+
   async save(settings: Settings): Promise<boolean> {
     return await invoke("save_settings", { settings });
   },
 
+  async getRecentHookRuns(): Promise<HookRun[]> {
+    return await invoke("get_recent_hook_runs");
+  },
+
+  // 以当前供应商为上下文试运行钩子（不触发切换）
+  async testSwitchHook(hook: SwitchHook, appId: AppId): Promise<HookRun> {
+    return await invoke("test_switch_hook", { hook, app: appId });
+  },
+
+  async testWebhook(): Promise<WebhookDelivery> {
+    return await invoke("test_webhook");
+  },
+
+  async getNetworkStatus(): Promise<NetworkStatus> {
+    return await invoke("get_network_status");
+  },
+
+  // 立即探测连通性（恢复联网时执行暂存的操作）
+  async checkNetwork(): Promise<NetworkStatus> {
+    return await invoke("check_network");
+  },
+
   async restart(): Promise<boolean> {
     return await invoke("restart_app");
   },
@@ -73,22 +278,109 @@ export const settingsApi = {
     return await invoke("open_file_dialog");
   },
 
-  async exportConfigToFile(filePath: string): Promise<ConfigTransferResult> {
-    return await invoke("export_config_to_file", { filePath });
+  async exportConfigToFile(
+    filePath: string,
+    options?: ExportOptions,
+  ): Promise<ConfigTransferResult> {
+    return await invoke("export_config_to_file", { filePath, options });
   },
 
   async importConfigFromFile(filePath: string): Promise<ConfigTransferResult> {
     return await invoke("import_config_from_file", { filePath });
   },
 
-  async syncCurrentProvidersLive(): Promise<void> {
+  async previewConfigMerge(filePath: string): Promise<MergePreview> {
+    return await invoke("preview_config_merge", { filePath });
+  },
+
+  async applyConfigMerge(
+    filePath: string,
+    fingerprint: string,
+  ): Promise<MergeResult> {
+    return await invoke("apply_config_merge", { filePath, fingerprint });
+  },
+
+  async exportConfigSnapshot(): Promise<ConfigSnapshotResult> {
+    return await invoke("export_config_snapshot");
+  },
+
+  async provisionFromUrl(url?: string): Promise<ProvisionResult> {
+    return await invoke("provision_from_url", { url });
+  },
+
+  async getDbLeaseStatus(): Promise<DbLeaseStatus> {
+    return await invoke("get_db_lease_status");
+  },
+
+  async runDiagnostics(): Promise<DiagnosticReport> {
+    return await invoke("run_diagnostics");
+  },
+
+  async detectCliVersions(): Promise<CliVersionInfo[]> {
+    return await invoke("detect_cli_versions");
+  },
+
+  async getRecentLogs(lines: number): Promise<string[]> {
+    return await invoke("get_recent_logs", { lines });
+  },
+
+  async exportLogsZip(filePath: string): Promise<string> {
+    return await invoke("export_logs_zip", { filePath });
+  },
+
+  async listCrashReports(): Promise<CrashReportEntry[]> {
+    return await invoke("list_crash_reports");
+  },
+
+  async exportCrashBundle(filePath: string): Promise<string> {
+    return await invoke("export_crash_bundle", { filePath });
+  },
+
+  async clearCrashReports(): Promise<number> {
+    return await invoke("clear_crash_reports");
+  },
+
+  async getBiometricStatus(): Promise<BiometricStatus> {
+    return await invoke("get_biometric_status");
+  },
+
+  async getAppLockStatus(): Promise<AppLockStatus> {
+    return await invoke("get_app_lock_status");
+  },
+
+  // passcode 为 null 时关闭应用锁；已设置口令时需提供当前口令
+  async setAppLockPasscode(
+    current: string | null,
+    passcode: string | null,
+  ): Promise<boolean> {
+    return await invoke("set_app_lock_passcode", { current, passcode });
+  },
+
+  async unlockSession(passcode: string): Promise<AppLockStatus> {
+    return await invoke("unlock_session", { passcode });
+  },
+
+  async lockSession(): Promise<boolean> {
+    return await invoke("lock_session");
+  },
+
+  async syncCurrentProvidersLive(): Promise<SyncReport[]> {
     const result = (await invoke("sync_current_providers_live")) as {
       success?: boolean;
       message?: string;
+      reports?: SyncReport[];
     };
     if (!result?.success) {
-      throw new Error(result?.message || "Sync current providers failed");
+      const failures = (result?.reports ?? [])
+        .filter((report) => report.status === "failed")
+        .map((report) => `${report.app}: ${report.error ?? ""}`);
+      throw new Error(
+        failures.join("; ") ||
+          result?.message ||
+          "Sync current providers failed",
+      );
     }
+    return result.reports ?? [];
   },
 
   async openExternal(url: string): Promise<void> {
@@ -0,0 +1,30 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { AppId } from "./types";
+
+export type StatsRange = "day" | "week" | "month" | "all";
+
+export interface ProviderUsageStats {
+  appType: AppId;
+  providerId: string;
+  // 供应商已删除时为空
+  providerName?: string;
+  switchCount: number;
+  // 累计使用时长（秒）
+  activeSeconds: number;
+  lastSwitchedAt?: number;
+}
+
+export interface SwitchStats {
+  range: StatsRange;
+  since: number;
+  until: number;
+  totalSwitches: number;
+  // 按切换次数降序
+  providers: ProviderUsageStats[];
+}
+
+export const statsApi = {
+  async getSwitchStats(range: StatsRange = "month"): Promise<SwitchStats> {
+    return await invoke("get_switch_stats", { range });
+  },
+};
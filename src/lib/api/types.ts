@@ -1,2 +1,10 @@
 // 前端统一使用 AppId 作为应用标识（与后端命令参数 `app` 一致）
 export type AppId = "claude" | "codex" | "gemini" | "grok" | "qwen"; // 新增 grok
+
+// 多应用操作的逐应用结果（单个应用失败不影响其他应用）
+export interface SyncReport {
+  app: AppId;
+  status: "success" | "skipped" | "failed";
+  // 失败原因；跳过时为跳过原因（如配置目录无写入权限）
+  error?: string;
+}
@@ -0,0 +1,44 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface UpdateAsset {
+  name: string;
+  url: string;
+  size: number;
+}
+
+export interface ReleaseNoteSection {
+  title?: string;
+  items: string[];
+}
+
+// 后台检查发现新版本时通过 `update-available` 事件推送同样的结构
+export interface UpdateInfo {
+  currentVersion: string;
+  latestVersion: string;
+  available: boolean;
+  skipped: boolean;
+  channel: "stable" | "beta";
+  prerelease: boolean;
+  releaseName?: string;
+  releaseNotes?: string;
+  notes: ReleaseNoteSection[];
+  releaseUrl: string;
+  publishedAt?: string;
+  asset?: UpdateAsset;
+}
+
+export const updateApi = {
+  async check(): Promise<UpdateInfo> {
+    return await invoke("check_update");
+  },
+
+  // 下载并校验安装包后交给系统安装程序，返回安装包路径
+  async perform(): Promise<string> {
+    return await invoke("perform_update");
+  },
+
+  // 传入 null 取消跳过
+  async skipVersion(version: string | null): Promise<boolean> {
+    return await invoke("skip_update_version", { version });
+  },
+};
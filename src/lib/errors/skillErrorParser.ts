@@ -36,6 +36,7 @@ function getErrorI18nKey(code: string): string {
     DOWNLOAD_FAILED: "skills.error.downloadFailed",
     SKILL_DIR_NOT_FOUND: "skills.error.skillDirNotFound",
     EMPTY_ARCHIVE: "skills.error.emptyArchive",
+    UNSAFE_ARCHIVE_PATH: "skills.error.unsafeArchivePath",
     GET_HOME_DIR_FAILED: "skills.error.getHomeDirFailed",
   };
 
@@ -52,6 +53,7 @@ function getSuggestionI18nKey(suggestion: string): string {
     retryLater: "skills.error.suggestion.retryLater",
     checkRepoUrl: "skills.error.suggestion.checkRepoUrl",
     checkPermission: "skills.error.suggestion.checkPermission",
+    http401: "skills.error.http401",
     http403: "skills.error.http403",
     http404: "skills.error.http404",
     http429: "skills.error.http429",
@@ -29,6 +29,8 @@ export interface Provider {
   isDuplicated?: boolean;
   // 是否已编辑（用于复制后编辑状态的标记）
   isEditedAfterDuplication?: boolean;
+  // 标签（团队、费用档位等），用于分组筛选
+  tags?: string[];
   current?: string;
 }
 
@@ -56,6 +58,91 @@ export interface ProviderMeta {
   partnerPromotionKey?: string;
   // 待选模型列表
   candidateModels?: string[];
+  // 供应商级配置目录（覆盖全局目录设置，用于多实例 CLI）
+  configDir?: string;
+  // 切换到该 Codex 供应商时选中的 profile（只改写 config.toml 的 profile 键）
+  codexProfile?: string;
+}
+
+// 系统通知分类开关
+export interface NotificationSettings {
+  switchSuccess: boolean;
+  switchFailure: boolean;
+  mcpSyncError: boolean;
+  usageAlert: boolean;
+  updateAvailable: boolean;
+  credentialCopied: boolean;
+}
+
+// 深链接导入策略
+export interface DeepLinkPolicySettings {
+  // 确认方式：始终确认 / 受信任签名的链接直接导入
+  confirmMode: "alwaysAsk" | "autoAcceptSigned";
+  // 拒绝携带 API Key 的链接
+  blockApiKeyLinks: boolean;
+  // 允许导入的资源类型，为空表示全部允许
+  allowedResources: Array<"provider" | "prompt" | "mcp" | "skill">;
+  // 受信任的 Ed25519 公钥（Base64）
+  trustedKeys: string[];
+}
+
+// 模型价格（美元 / 百万 token），model 按最长前缀匹配
+export interface ModelPrice {
+  model: string;
+  input: number;
+  output: number;
+}
+
+// 切换供应商前后执行的用户命令
+export interface SwitchHook {
+  name: string;
+  // 限定应用（claude/codex/...），为空表示所有应用
+  app?: string;
+  // before：写入配置之前；after：切换完成之后（默认）
+  phase?: "before" | "after";
+  // 通过系统 shell 执行
+  command: string;
+  // 超时秒数（0 或不填使用默认 30 秒）
+  timeoutSecs?: number;
+  // abort：before 钩子失败时取消切换，after 钩子失败时跳过其余钩子
+  onFailure?: "continue" | "abort";
+  enabled?: boolean;
+}
+
+// 事件 Webhook（切换供应商、MCP 同步失败、用量告警）
+export interface WebhookSettings {
+  // 为空表示关闭
+  url?: string;
+  // HMAC-SHA256 签名密钥，签名放在 X-CC-Switch-Signature 头中
+  secret?: string;
+  // 订阅的事件：provider.switched / mcp.sync_failed / usage.threshold_crossed，为空表示全部
+  events?: string[];
+}
+
+// 网络请求的超时与重试（为空时沿用上一级或功能默认值）
+export interface NetworkTimeouts {
+  connectTimeoutSecs?: number;
+  // 整个请求（含读取响应）超时
+  timeoutSecs?: number;
+  // 连接失败、超时或 429/502/503/504 时的重试次数
+  retries?: number;
+  // 首次重试前等待的毫秒数，之后每次翻倍
+  backoffMs?: number;
+}
+
+export type NetworkFeature =
+  | "speedtest"
+  | "models"
+  | "smokeTest"
+  | "skills"
+  | "updates"
+  | "downloads"
+  | "provision"
+  | "webhook";
+
+export interface NetworkPolicy extends NetworkTimeouts {
+  // 按功能覆盖全局参数
+  overrides?: Partial<Record<NetworkFeature, NetworkTimeouts>>;
 }
 
 // 应用设置类型（用于设置对话框与 Tauri API）
@@ -66,15 +153,96 @@ export interface Settings {
   showInTray: boolean;
   // 点击关闭按钮时是否最小化到托盘而不是关闭应用
   minimizeToTrayOnClose: boolean;
+  // 托盘图标叠加当前 Claude 供应商徽标（颜色 + 首字母）
+  dynamicTrayIcon?: boolean;
+  // 托盘图标样式：auto 为随系统主题变化的单色图标，color 为彩色应用图标
+  trayIconStyle?: "auto" | "color";
   // 启用 Claude 插件联动（写入 ~/.claude/config.json 的 primaryApiKey）
   enableClaudePluginIntegration?: boolean;
+  // Claude 供应商的 env 写入 settings.json（默认）还是 settings.local.json
+  claudeEnvTarget?: "settings" | "local";
+  // 配置文件是符号链接时：follow 写入链接指向的文件（默认），warn 替换为普通文件并记录警告
+  symlinkWriteMode?: "follow" | "warn";
   // 是否开机自启
   launchOnStartup?: boolean;
+  // 启动时最小化到托盘（不显示主窗口，需启用托盘图标）
+  startMinimized?: boolean;
+  // 是否已完成首次运行引导
+  onboardingCompleted?: boolean;
   // 首选语言（可选，默认中文）
   language?: "en" | "zh";
+  // 系统通知分类开关
+  notifications?: NotificationSettings;
+  // 深链接导入策略
+  deeplinkPolicy?: DeepLinkPolicySettings;
+  // 启动时与 live 配置对账 MCP 服务器
+  mcpReconcileOnLaunch?: boolean;
+  // 提示词文件被外部修改时：回填到已启用项 / 另存为修订 / 不处理
+  promptWatchMode?: "off" | "backfill" | "revision";
+  // 监听剪贴板中的 ccswitch:// 链接与 API Key 并提示导入（默认关闭）
+  clipboardMonitor?: boolean;
+  // 从托盘复制 API Key 原文前确认（默认开启）
+  confirmCredentialCopy?: boolean;
+  // 返回 API Key 原文前要求 Touch ID / Windows Hello 验证（仅 macOS、Windows）
+  biometricKeyReveal?: boolean;
+  // 将 panic 与命令错误写入本地崩溃报告（默认关闭，不上传）
+  crashReporting?: boolean;
+  // 应用锁解锁后的有效时长（分钟，默认 5）
+  appLockTimeoutMinutes?: number;
+  // 启动 CLI 的终端：内置名称（Terminal、iTerm、gnome-terminal、wt 等）或含 {cmd} 的命令模板
+  terminal?: string;
+  // 目标 CLI 正在运行时阻止切换（默认只提示）
+  blockSwitchWhenCliRunning?: boolean;
+  // 切换供应商时同步更新 Windows 用户级环境变量（仅已存在的变量，自动备份原值）
+  syncWindowsEnvOnSwitch?: boolean;
+
+  // ===== 数据库自动备份 =====
+  // 自动备份频率（默认每天）
+  dbBackupSchedule?: "off" | "daily" | "weekly";
+  // 最多保留的数据库备份数量
+  dbBackupRetainCount?: number;
+  // 数据库备份最长保留天数（0 表示不按时间清理）
+  dbBackupRetainDays?: number;
+
+  // ===== 配置快照导出 =====
+  // 脱敏配置快照的导出目录（如 dotfiles 仓库）
+  configSnapshotDir?: string;
+  // 配置快照导出时机
+  configSnapshotSchedule?: "off" | "onChange" | "nightly";
+
+  // ===== 费用估算 =====
+  // 覆盖或补充内置模型价格表（美元 / 百万 token）
+  modelPricing?: ModelPrice[];
+
+  // ===== 切换钩子 =====
+  // 切换供应商前后按顺序执行的命令
+  switchHooks?: SwitchHook[];
+
+  // ===== 事件 Webhook =====
+  webhook?: WebhookSettings;
+
+  // ===== 网络 =====
+  // 离线模式（默认 auto 自动探测；offline 时网络功能直接返回离线）
+  networkMode?: "auto" | "online" | "offline";
+  // 网络请求的超时与重试策略
+  networkPolicy?: NetworkPolicy;
+
+  // ===== 应用更新 =====
+  // 更新通道（默认 stable，beta 包含预发布版本）
+  updateChannel?: "stable" | "beta";
+  // 是否在后台定期检查更新（默认开启）
+  autoCheckUpdates?: boolean;
+  // 跳过的版本（不再提示）
+  skippedUpdateVersion?: string;
+
+  // ===== 团队模板 =====
+  // 团队模板清单地址（HTTPS），首次启动时自动导入；环境变量 CC_SWITCH_PROVISION_URL 优先
+  provisionUrl?: string;
 
   // ===== 设备级目录覆盖 =====
   // 覆盖 Claude Code 配置目录（可选）
+  // 以下目录覆盖均支持 ${HOME}、${DOCUMENTS}、${CONFIG} 等变量，以及按平台的备选：
+  // "macos=${HOME}/.claude|windows=${DOCUMENTS}\claude|${HOME}/.claude"
   claudeConfigDir?: string;
   // 覆盖 Codex 配置目录（可选）
   codexConfigDir?: string;
@@ -154,3 +322,10 @@ export interface McpConfigResponse {
   configPath: string;
   servers: Record<string, McpServer>;
 }
+
+// 后端命令返回的结构化错误（code 为稳定错误码，如 provider.codex.auth.missing）
+export interface ErrorPayload {
+  code: string;
+  message: string;
+  details?: Record<string, unknown>;
+}
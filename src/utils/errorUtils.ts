@@ -1,3 +1,5 @@
+import type { ErrorPayload } from "@/types";
+
 /**
  * 从各种错误对象中提取错误信息
  * @param error 错误对象
@@ -37,6 +39,21 @@ export const extractErrorMessage = (error: unknown): string => {
   return "";
 };
 
+/**
+ * 判断是否为后端命令返回的结构化错误
+ */
+export const isErrorPayload = (error: unknown): error is ErrorPayload =>
+  !!error &&
+  typeof error === "object" &&
+  typeof (error as Record<string, unknown>).code === "string" &&
+  typeof (error as Record<string, unknown>).message === "string";
+
+/**
+ * 提取后端错误码，非结构化错误返回 undefined
+ */
+export const getErrorCode = (error: unknown): string | undefined =>
+  isErrorPayload(error) ? error.code : undefined;
+
 /**
  * 将已知的 MCP 相关后端错误（通常为中文硬编码）映射为 i18n 文案
  * 采用包含式匹配，尽量稳健地覆盖不同上下文的相似消息。